@@ -0,0 +1,81 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use block2::RcBlock;
+use objc2::rc::Retained;
+use objc2::runtime::{AnyObject, Bool};
+use objc2::{class, msg_send};
+use objc2_foundation::NSString;
+use tokio::sync::oneshot;
+
+#[link(name = "LocalAuthentication", kind = "framework")]
+extern "C" {}
+
+/// `LAPolicyDeviceOwnerAuthentication`: Touch ID, falling back to the
+/// account password when biometrics are unavailable.
+const DEVICE_OWNER_AUTHENTICATION: isize = 2;
+
+fn new_context() -> Retained<AnyObject> {
+    unsafe { msg_send![class!(LAContext), new] }
+}
+
+fn error_message(error: *mut AnyObject) -> String {
+    if error.is_null() {
+        return "Authentication failed.".to_string();
+    }
+    let description: Option<Retained<NSString>> = unsafe { msg_send![error, localizedDescription] };
+    description
+        .map(|description| description.to_string())
+        .unwrap_or_else(|| "Authentication failed.".to_string())
+}
+
+pub(super) fn is_available() -> bool {
+    let context = new_context();
+    let available: Bool = unsafe {
+        msg_send![
+            &context,
+            canEvaluatePolicy: DEVICE_OWNER_AUTHENTICATION,
+            error: std::ptr::null_mut::<*mut AnyObject>()
+        ]
+    };
+    available.as_bool()
+}
+
+fn trigger_authentication(reason: &str, tx: oneshot::Sender<Result<(), String>>) {
+    let context = new_context();
+    let reason = NSString::from_str(reason);
+    let tx = Arc::new(Mutex::new(Some(tx)));
+    // The block owns a reference to the context so it outlives the prompt.
+    let keep_alive = context.clone();
+    let block = RcBlock::new(move |success: Bool, error: *mut AnyObject| {
+        let _ = &keep_alive;
+        let result = if success.as_bool() {
+            Ok(())
+        } else {
+            Err(error_message(error))
+        };
+        if let Ok(mut guard) = tx.lock() {
+            if let Some(sender) = guard.take() {
+                let _ = sender.send(result);
+            }
+        }
+    });
+    unsafe {
+        let () = msg_send![
+            &context,
+            evaluatePolicy: DEVICE_OWNER_AUTHENTICATION,
+            localizedReason: &*reason,
+            reply: &*block
+        ];
+    }
+}
+
+pub(super) async fn authenticate(reason: &str) -> Result<(), String> {
+    let (tx, rx) = oneshot::channel();
+    trigger_authentication(reason, tx);
+    match tokio::time::timeout(Duration::from_secs(300), rx).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(_)) => Err("Authentication was interrupted.".to_string()),
+        Err(_) => Err("Authentication timed out.".to_string()),
+    }
+}
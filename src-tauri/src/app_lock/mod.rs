@@ -0,0 +1,200 @@
+use std::sync::{Mutex as StdMutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use tauri::ipc::Invoke;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+use crate::shared::inventory_core;
+use crate::state::AppState;
+use crate::types::{AppLockStatus, AppSettings};
+
+#[cfg(target_os = "macos")]
+#[path = "macos.rs"]
+mod platform;
+#[cfg(target_os = "windows")]
+#[path = "windows.rs"]
+mod platform;
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+#[path = "unsupported.rs"]
+mod platform;
+
+pub(crate) const LOCKED_ERROR: &str = "App is locked.";
+const UNLOCK_REASON: &str = "unlock CodexMonitor";
+/// Commands the lock screen itself needs.
+const ALLOWED_WHILE_LOCKED: &[&str] = &[
+    "get_app_lock_status",
+    "lock_app",
+    "unlock_app",
+    "is_mobile_runtime",
+];
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+const MIN_AUTO_LOCK_IDLE_SECS: u64 = 60;
+
+#[derive(Default)]
+struct LockState {
+    locked: bool,
+    /// When the main window lost focus; `None` while it is focused.
+    unfocused_since: Option<Instant>,
+}
+
+static LOCK_STATE: OnceLock<StdMutex<LockState>> = OnceLock::new();
+
+fn lock_state() -> &'static StdMutex<LockState> {
+    LOCK_STATE.get_or_init(|| StdMutex::new(LockState::default()))
+}
+
+pub(crate) fn is_locked() -> bool {
+    lock_state()
+        .lock()
+        .map(|state| state.locked)
+        .unwrap_or(true)
+}
+
+fn set_locked(app: &AppHandle, locked: bool) {
+    let changed = lock_state()
+        .lock()
+        .map(|mut state| std::mem::replace(&mut state.locked, locked) != locked)
+        .unwrap_or(false);
+    if changed {
+        let _ = app.emit(
+            "app-lock-changed",
+            AppLockStatus {
+                locked,
+                available: true,
+            },
+        );
+    }
+}
+
+pub(crate) fn note_window_focus(focused: bool) {
+    if let Ok(mut state) = lock_state().lock() {
+        state.unfocused_since = if focused {
+            None
+        } else {
+            state.unfocused_since.or_else(|| Some(Instant::now()))
+        };
+    }
+}
+
+fn auto_lock_after(settings: &AppSettings) -> Option<Duration> {
+    match settings.auto_lock_idle_secs {
+        None | Some(0) => None,
+        Some(secs) => Some(Duration::from_secs(secs.max(MIN_AUTO_LOCK_IDLE_SECS))),
+    }
+}
+
+pub(crate) async fn is_available() -> bool {
+    tokio::task::spawn_blocking(platform::is_available)
+        .await
+        .unwrap_or(false)
+}
+
+/// Wraps the generated command handler so every command except the lock
+/// screen's own is rejected while locked. Plugin commands (opener, dialog,
+/// process, window) don't go through this handler and stay callable; the
+/// frontend's lock screen covers the UI that would reach them.
+pub(crate) fn guard_invoke_handler<R, F>(
+    handler: F,
+) -> impl Fn(Invoke<R>) -> bool + Send + Sync + 'static
+where
+    R: Runtime,
+    F: Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+{
+    move |invoke: Invoke<R>| {
+        if is_locked() && !ALLOWED_WHILE_LOCKED.contains(&invoke.message.command()) {
+            invoke.resolver.reject(LOCKED_ERROR);
+            return true;
+        }
+        handler(invoke)
+    }
+}
+
+/// Engages the lock at launch when `requireUnlockOnLaunch` is set. Skipped
+/// with a warning when the OS cannot authenticate, so nobody is locked out.
+pub(crate) async fn lock_on_launch(app: &AppHandle, settings: &AppSettings) {
+    if !settings.require_unlock_on_launch {
+        return;
+    }
+    if is_available().await {
+        set_locked(app, true);
+    } else {
+        eprintln!("requireUnlockOnLaunch is set but OS authentication is unavailable.");
+    }
+}
+
+/// Locks once the main window has been out of focus for the configured
+/// idle timeout.
+pub(crate) async fn run_idle_monitor(app: AppHandle) {
+    inventory_core::scheduler_started("appLockIdleMonitor");
+    loop {
+        tokio::time::sleep(IDLE_CHECK_INTERVAL).await;
+        inventory_core::scheduler_ticked("appLockIdleMonitor");
+        let settings = app.state::<AppState>().app_settings.lock().await.clone();
+        let Some(timeout) = auto_lock_after(&settings) else {
+            continue;
+        };
+        let idle = lock_state()
+            .lock()
+            .ok()
+            .and_then(|state| state.unfocused_since)
+            .is_some_and(|since| since.elapsed() >= timeout);
+        if idle && !is_locked() && is_available().await {
+            set_locked(&app, true);
+        }
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn get_app_lock_status() -> Result<AppLockStatus, String> {
+    Ok(AppLockStatus {
+        locked: is_locked(),
+        available: is_available().await,
+    })
+}
+
+#[tauri::command]
+pub(crate) async fn lock_app(app: AppHandle) -> Result<AppLockStatus, String> {
+    if !is_available().await {
+        return Err("OS authentication is not available on this device.".to_string());
+    }
+    set_locked(&app, true);
+    Ok(AppLockStatus {
+        locked: true,
+        available: true,
+    })
+}
+
+#[tauri::command]
+pub(crate) async fn unlock_app(app: AppHandle) -> Result<AppLockStatus, String> {
+    if is_locked() {
+        // Authentication that went away (e.g. Touch ID disabled) must not
+        // leave the app locked for good.
+        if is_available().await {
+            platform::authenticate(UNLOCK_REASON).await?;
+        } else {
+            eprintln!("Unlocking without OS authentication: it is no longer available.");
+        }
+        set_locked(&app, false);
+    }
+    Ok(AppLockStatus {
+        locked: false,
+        available: true,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_lock_after_enforces_minimum() {
+        let mut settings = AppSettings::default();
+        assert_eq!(auto_lock_after(&settings), None);
+        settings.auto_lock_idle_secs = Some(0);
+        assert_eq!(auto_lock_after(&settings), None);
+        settings.auto_lock_idle_secs = Some(5);
+        assert_eq!(auto_lock_after(&settings), Some(Duration::from_secs(60)));
+        settings.auto_lock_idle_secs = Some(900);
+        assert_eq!(auto_lock_after(&settings), Some(Duration::from_secs(900)));
+    }
+}
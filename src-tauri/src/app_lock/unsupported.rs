@@ -0,0 +1,7 @@
+pub(super) fn is_available() -> bool {
+    false
+}
+
+pub(super) async fn authenticate(_reason: &str) -> Result<(), String> {
+    Err("OS authentication is not available on this platform.".to_string())
+}
@@ -0,0 +1,56 @@
+use crate::shared::process_core::{std_command, tokio_command};
+
+// Windows Hello is reached through the WinRT `UserConsentVerifier` from
+// Windows PowerShell, which ships with every supported Windows release.
+const AS_TASK_PRELUDE: &str = "Add-Type -AssemblyName System.Runtime.WindowsRuntime; \
+$asTask = ([System.WindowsRuntimeSystemExtensions].GetMethods() | Where-Object { \
+$_.Name -eq 'AsTask' -and $_.GetParameters().Count -eq 1 -and \
+$_.GetParameters()[0].ParameterType.Name -eq 'IAsyncOperation`1' })[0]; \
+[Windows.Security.Credentials.UI.UserConsentVerifier,Windows.Security.Credentials.UI,ContentType=WindowsRuntime] | Out-Null;";
+
+fn await_script(operation: &str, result_type: &str) -> String {
+    format!(
+        "{AS_TASK_PRELUDE} $op = [Windows.Security.Credentials.UI.UserConsentVerifier]::{operation}; \
+$task = $asTask.MakeGenericMethod([Windows.Security.Credentials.UI.{result_type}]).Invoke($null, @($op)); \
+$task.Wait(-1) | Out-Null; $task.Result"
+    )
+}
+
+fn quote_powershell(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+pub(super) fn is_available() -> bool {
+    let script = await_script(
+        "CheckAvailabilityAsync()",
+        "UserConsentVerifierAvailability",
+    );
+    std_command("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .output()
+        .map(|output| {
+            output.status.success() && String::from_utf8_lossy(&output.stdout).trim() == "Available"
+        })
+        .unwrap_or(false)
+}
+
+pub(super) async fn authenticate(reason: &str) -> Result<(), String> {
+    let operation = format!("RequestVerificationAsync({})", quote_powershell(reason));
+    let script = await_script(&operation, "UserConsentVerificationResult");
+    let output = tokio_command("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .output()
+        .await
+        .map_err(|err| format!("Failed to start Windows Hello: {err}"))?;
+    let result = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if result == "Verified" {
+        return Ok(());
+    }
+    if result.is_empty() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(format!("Windows Hello failed: {stderr}"));
+    }
+    Err(format!(
+        "Windows Hello verification was not completed ({result})."
+    ))
+}
@@ -1,11 +1,11 @@
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::env;
 use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::process::{Child, ChildStdin, Command};
@@ -14,8 +14,19 @@ use tokio::time::timeout;
 
 use crate::backend::events::{AppServerEvent, EventSink};
 use crate::codex::args::parse_codex_args;
+use crate::codex::endpoint::CODEX_BASE_URL_ENV;
+use crate::codex::env::expand_env_value;
+use crate::shared::codex_core::dispatch_queued_messages;
+use crate::shared::message_queue_core::MessageQueue;
 use crate::shared::process_core::{kill_child_process_tree, tokio_command};
+use crate::shared::redaction_core;
+use crate::shared::session_stats_core::record_session_startup;
+use crate::shared::token_estimate_core::{
+    context_usage_from_token_usage, token_totals_from_token_usage,
+};
+use crate::shared::turn_trace_core::TurnTraceBuffer;
 use crate::types::WorkspaceEntry;
+use crate::utils::now_unix_ms;
 
 #[cfg(target_os = "windows")]
 use crate::shared::process_core::{build_cmd_c_command, resolve_windows_executable};
@@ -53,6 +64,9 @@ fn build_initialize_params(client_version: &str) -> Value {
 }
 
 const REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+/// How long to wait for the child to exit after its stdout closes.
+const EXIT_WAIT_TIMEOUT: Duration = Duration::from_secs(5);
+const STDERR_TAIL_LINES: usize = 20;
 
 pub(crate) struct WorkspaceSession {
     pub(crate) entry: WorkspaceEntry,
@@ -62,9 +76,68 @@ pub(crate) struct WorkspaceSession {
     pub(crate) next_id: AtomicU64,
     /// Callbacks for background threads - events for these threadIds are sent through the channel
     pub(crate) background_thread_callbacks: Mutex<HashMap<String, mpsc::UnboundedSender<Value>>>,
+    /// Latest context size reported per thread via `thread/tokenUsage/updated`.
+    pub(crate) context_usage: Mutex<HashMap<String, ThreadContextUsage>>,
+    /// Session token totals per thread from the same event. Unlike
+    /// `context_usage` these are kept across compaction.
+    pub(crate) token_totals: Mutex<HashMap<String, ThreadTokenTotals>>,
+    /// Notified on the next `turn/completed` for a thread.
+    pub(crate) turn_completion_waiters: Mutex<HashMap<String, Vec<oneshot::Sender<()>>>>,
+    /// Recent turns with their events, for `capture_turn_trace`.
+    pub(crate) turn_traces: Mutex<TurnTraceBuffer>,
+    /// Running turns and the messages queued behind them.
+    pub(crate) message_queue: Mutex<MessageQueue>,
+    /// Turn id per thread between `turn/started` and `turn/completed`.
+    pub(crate) running_turns: Mutex<HashMap<String, String>>,
+    /// Last stderr lines, redacted, to explain a crash.
+    pub(crate) stderr_tail: Mutex<VecDeque<String>>,
+    /// Set once the child has exited.
+    pub(crate) exit: Mutex<Option<SessionExit>>,
+    pub(crate) started_at_ms: i64,
+}
+
+/// How the app-server process ended. `signal` is only ever set on Unix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SessionExit {
+    pub(crate) code: Option<i32>,
+    pub(crate) signal: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ThreadContextUsage {
+    pub(crate) tokens: u64,
+    pub(crate) context_window: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) struct ThreadTokenTotals {
+    pub(crate) input_tokens: u64,
+    pub(crate) cached_input_tokens: u64,
+    pub(crate) output_tokens: u64,
+    pub(crate) total_tokens: u64,
 }
 
 impl WorkspaceSession {
+    pub(crate) fn new(entry: WorkspaceEntry, child: Child, stdin: ChildStdin) -> Self {
+        Self {
+            entry,
+            child: Mutex::new(child),
+            stdin: Mutex::new(stdin),
+            pending: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+            background_thread_callbacks: Mutex::new(HashMap::new()),
+            context_usage: Mutex::new(HashMap::new()),
+            token_totals: Mutex::new(HashMap::new()),
+            turn_completion_waiters: Mutex::new(HashMap::new()),
+            turn_traces: Mutex::new(TurnTraceBuffer::default()),
+            message_queue: Mutex::new(MessageQueue::default()),
+            running_turns: Mutex::new(HashMap::new()),
+            stderr_tail: Mutex::new(VecDeque::new()),
+            exit: Mutex::new(None),
+            started_at_ms: now_unix_ms(),
+        }
+    }
+
     async fn write_message(&self, value: Value) -> Result<(), String> {
         let mut stdin = self.stdin.lock().await;
         let mut line = serde_json::to_string(&value).map_err(|e| e.to_string())?;
@@ -76,6 +149,14 @@ impl WorkspaceSession {
     }
 
     pub(crate) async fn send_request(&self, method: &str, params: Value) -> Result<Value, String> {
+        if method == "turn/start" {
+            if let Some(thread_id) = params.get("threadId").and_then(Value::as_str) {
+                self.turn_traces
+                    .lock()
+                    .await
+                    .record_turn_request(thread_id, &params);
+            }
+        }
         let id = self.next_id.fetch_add(1, Ordering::SeqCst);
         let (tx, rx) = oneshot::channel();
         self.pending.lock().await.insert(id, tx);
@@ -116,6 +197,136 @@ impl WorkspaceSession {
         self.write_message(json!({ "id": id, "result": result }))
             .await
     }
+
+    pub(crate) async fn thread_context_usage(&self, thread_id: &str) -> Option<ThreadContextUsage> {
+        self.context_usage.lock().await.get(thread_id).copied()
+    }
+
+    pub(crate) async fn thread_token_totals(&self, thread_id: &str) -> Option<ThreadTokenTotals> {
+        self.token_totals.lock().await.get(thread_id).copied()
+    }
+
+    /// `(thread_id, turn_id)` for every turn still running.
+    pub(crate) async fn running_turns(&self) -> Vec<(String, String)> {
+        self.running_turns
+            .lock()
+            .await
+            .iter()
+            .map(|(thread_id, turn_id)| (thread_id.clone(), turn_id.clone()))
+            .collect()
+    }
+
+    pub(crate) async fn exit_status(&self) -> Option<SessionExit> {
+        *self.exit.lock().await
+    }
+
+    pub(crate) async fn stderr_tail(&self) -> Vec<String> {
+        self.stderr_tail.lock().await.iter().cloned().collect()
+    }
+
+    /// Polls rather than waits on the child so a kill elsewhere can still
+    /// take the lock. A child that keeps running with its stdout closed is
+    /// left unrecorded.
+    async fn record_exit(&self) {
+        let deadline = Instant::now() + EXIT_WAIT_TIMEOUT;
+        let status = loop {
+            match self.child.lock().await.try_wait() {
+                Ok(Some(status)) => break status,
+                Ok(None) if Instant::now() < deadline => {}
+                _ => return,
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        };
+        #[cfg(unix)]
+        let signal = std::os::unix::process::ExitStatusExt::signal(&status);
+        #[cfg(not(unix))]
+        let signal = None;
+        *self.exit.lock().await = Some(SessionExit {
+            code: status.code(),
+            signal,
+        });
+    }
+
+    /// Register before sending the request that starts the turn, so a fast
+    /// completion is not missed.
+    pub(crate) async fn wait_for_turn_completion(&self, thread_id: &str) -> oneshot::Receiver<()> {
+        let (tx, rx) = oneshot::channel();
+        self.turn_completion_waiters
+            .lock()
+            .await
+            .entry(thread_id.to_string())
+            .or_default()
+            .push(tx);
+        rx
+    }
+
+    /// Returns true when a turn just completed with messages queued behind it.
+    async fn track_thread_event(&self, thread_id: &str, value: &Value) -> bool {
+        self.turn_traces.lock().await.record_event(thread_id, value);
+        let method = value.get("method").and_then(Value::as_str).unwrap_or("");
+        let dispatch_due = self
+            .message_queue
+            .lock()
+            .await
+            .record_event(thread_id, method);
+        let params = value.get("params");
+        match method {
+            "thread/tokenUsage/updated" => {
+                let token_usage = params.and_then(|params| {
+                    params
+                        .get("tokenUsage")
+                        .or_else(|| params.get("token_usage"))
+                });
+                if let Some(totals) = token_usage.and_then(token_totals_from_token_usage) {
+                    self.token_totals
+                        .lock()
+                        .await
+                        .insert(thread_id.to_string(), totals);
+                }
+                let usage = token_usage.and_then(context_usage_from_token_usage);
+                if let Some((tokens, context_window)) = usage {
+                    self.context_usage.lock().await.insert(
+                        thread_id.to_string(),
+                        ThreadContextUsage {
+                            tokens,
+                            context_window,
+                        },
+                    );
+                }
+            }
+            "item/completed" => {
+                let compacted = params
+                    .and_then(|params| params.get("item"))
+                    .and_then(|item| item.get("type"))
+                    .and_then(Value::as_str)
+                    == Some("contextCompaction");
+                if compacted {
+                    self.context_usage.lock().await.remove(thread_id);
+                }
+            }
+            "turn/started" => {
+                let turn_id = params
+                    .and_then(|params| params.get("turn"))
+                    .and_then(|turn| turn.get("id"))
+                    .and_then(Value::as_str);
+                if let Some(turn_id) = turn_id {
+                    self.running_turns
+                        .lock()
+                        .await
+                        .insert(thread_id.to_string(), turn_id.to_string());
+                }
+            }
+            "turn/completed" => {
+                self.running_turns.lock().await.remove(thread_id);
+                let waiters = self.turn_completion_waiters.lock().await.remove(thread_id);
+                for waiter in waiters.unwrap_or_default() {
+                    let _ = waiter.send(());
+                }
+            }
+            _ => {}
+        }
+        dispatch_due
+    }
 }
 
 pub(crate) fn build_codex_path_env(codex_bin: Option<&str>) -> Option<String> {
@@ -325,26 +536,30 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
         vec!["app-server".to_string()],
     )?;
     command.current_dir(&entry.path);
+    // Before CODEX_HOME and the endpoint so the dedicated settings win.
+    for (name, value) in &entry.settings.env {
+        command.env(
+            name,
+            expand_env_value(value, |name| std::env::var(name).ok()),
+        );
+    }
     if let Some(codex_home) = codex_home {
         command.env("CODEX_HOME", codex_home);
     }
+    if let Some(base_url) = entry.settings.codex_base_url.as_deref() {
+        command.env(CODEX_BASE_URL_ENV, base_url);
+    }
     command.stdin(std::process::Stdio::piped());
     command.stdout(std::process::Stdio::piped());
     command.stderr(std::process::Stdio::piped());
 
+    let spawn_started = Instant::now();
     let mut child = command.spawn().map_err(|e| e.to_string())?;
     let stdin = child.stdin.take().ok_or("missing stdin")?;
     let stdout = child.stdout.take().ok_or("missing stdout")?;
     let stderr = child.stderr.take().ok_or("missing stderr")?;
 
-    let session = Arc::new(WorkspaceSession {
-        entry: entry.clone(),
-        child: Mutex::new(child),
-        stdin: Mutex::new(stdin),
-        pending: Mutex::new(HashMap::new()),
-        next_id: AtomicU64::new(1),
-        background_thread_callbacks: Mutex::new(HashMap::new()),
-    });
+    let session = Arc::new(WorkspaceSession::new(entry.clone(), child, stdin));
 
     let session_clone = Arc::clone(&session);
     let workspace_id = entry.id.clone();
@@ -376,6 +591,17 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
 
             // Check if this event is for a background thread
             let thread_id = extract_thread_id(&value);
+            if has_method {
+                if let Some(ref tid) = thread_id {
+                    if session_clone.track_thread_event(tid, &value).await {
+                        tokio::spawn(dispatch_queued_messages(
+                            Arc::clone(&session_clone),
+                            tid.clone(),
+                            event_sink_clone.clone(),
+                        ));
+                    }
+                }
+            }
 
             if let Some(id) = maybe_id {
                 if has_result_or_error {
@@ -426,8 +652,10 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
 
         // Ensure pending foreground requests cannot accumulate after process output ends.
         session_clone.pending.lock().await.clear();
+        session_clone.record_exit().await;
     });
 
+    let session_clone = Arc::clone(&session);
     let workspace_id = entry.id.clone();
     let event_sink_clone = event_sink.clone();
     tokio::spawn(async move {
@@ -436,11 +664,19 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
             if line.trim().is_empty() {
                 continue;
             }
+            let message = redaction_core::redact_for_log(&line);
+            {
+                let mut tail = session_clone.stderr_tail.lock().await;
+                if tail.len() == STDERR_TAIL_LINES {
+                    tail.pop_front();
+                }
+                tail.push_back(message.clone());
+            }
             let payload = AppServerEvent {
                 workspace_id: workspace_id.clone(),
                 message: json!({
                     "method": "codex/stderr",
-                    "params": { "message": line },
+                    "params": { "message": message },
                 }),
             };
             event_sink_clone.emit_app_server_event(payload);
@@ -466,6 +702,7 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
     };
     init_response?;
     session.send_notification("initialized", None).await?;
+    record_session_startup(&entry.id, &entry.name, spawn_started.elapsed());
 
     let payload = AppServerEvent {
         workspace_id: entry.id.clone(),
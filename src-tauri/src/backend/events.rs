@@ -1,6 +1,8 @@
 use serde::Serialize;
 use serde_json::Value;
 
+use crate::types::{GitRemoteUpdates, NetworkStatus};
+
 #[derive(Serialize, Clone)]
 pub(crate) struct AppServerEvent {
     pub(crate) workspace_id: String,
@@ -24,8 +26,103 @@ pub(crate) struct TerminalExit {
     pub(crate) terminal_id: String,
 }
 
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OperationEvent {
+    pub(crate) operation_id: String,
+    pub(crate) kind: String,
+    pub(crate) workspace_id: Option<String>,
+    pub(crate) status: String,
+    pub(crate) progress: Option<f64>,
+    pub(crate) message: Option<String>,
+    pub(crate) error: Option<String>,
+}
+
+/// `status` is `progress` while git runs, then `done` or `failed`.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CloneProgressEvent {
+    pub(crate) source_workspace_id: String,
+    pub(crate) status: String,
+    pub(crate) percent: u8,
+    pub(crate) stage: Option<String>,
+    pub(crate) error: Option<String>,
+}
+
+/// Progress of `git_push` / `git_pull`. `operation` is `push` or `pull`;
+/// `status` is `progress` while git runs, then `done` or `failed`.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitSyncProgressEvent {
+    pub(crate) workspace_id: String,
+    pub(crate) operation: String,
+    pub(crate) status: String,
+    pub(crate) percent: u8,
+    pub(crate) stage: Option<String>,
+    pub(crate) error: Option<String>,
+}
+
+/// `status` is `prompt` when `codex login` prints a sign-in URL or device
+/// code, then `done` or `failed`.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProfileLoginEvent {
+    pub(crate) profile_id: String,
+    pub(crate) status: String,
+    pub(crate) url: Option<String>,
+    pub(crate) code: Option<String>,
+    pub(crate) error: Option<String>,
+}
+
+/// `status` is `queued` when `send_user_message` holds a message behind a
+/// running turn, then `sent` or `failed` once it's dispatched, or `cleared`
+/// by `clear_message_queue`. `queueLength` is what's still waiting.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MessageQueuedEvent {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) status: String,
+    pub(crate) queue_length: usize,
+    pub(crate) error: Option<String>,
+}
+
+/// A workspace's Codex process exited while its session was in use.
+/// `status` is `crashed` when it won't be respawned, `respawning` with the
+/// attempt and its delay, `respawned`, or `gaveUp` once retries run out.
+/// `kind` tells an out-of-memory kill from a missing binary and the like.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionCrashedEvent {
+    pub(crate) workspace_id: String,
+    pub(crate) status: String,
+    pub(crate) kind: String,
+    pub(crate) reason: String,
+    pub(crate) exit_code: Option<i32>,
+    pub(crate) signal: Option<i32>,
+    pub(crate) stderr_tail: Vec<String>,
+    pub(crate) attempt: u32,
+    pub(crate) retry_in_ms: Option<u64>,
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RemoteUpdatesEvent {
+    pub(crate) workspace_id: String,
+    pub(crate) updates: GitRemoteUpdates,
+}
+
 pub(crate) trait EventSink: Clone + Send + Sync + 'static {
     fn emit_app_server_event(&self, event: AppServerEvent);
     fn emit_terminal_output(&self, event: TerminalOutput);
     fn emit_terminal_exit(&self, event: TerminalExit);
+    fn emit_operation_event(&self, event: OperationEvent);
+    fn emit_remote_updates_event(&self, event: RemoteUpdatesEvent);
+    fn emit_network_status_event(&self, event: NetworkStatus);
+    fn emit_clone_progress_event(&self, event: CloneProgressEvent);
+    fn emit_git_sync_progress_event(&self, event: GitSyncProgressEvent);
+    fn emit_profile_login_event(&self, event: ProfileLoginEvent);
+    fn emit_message_queued_event(&self, event: MessageQueuedEvent);
+    fn emit_session_crashed_event(&self, event: SessionCrashedEvent);
 }
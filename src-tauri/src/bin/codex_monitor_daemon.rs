@@ -5,6 +5,10 @@ mod backend;
 mod codex_args;
 #[path = "../codex/config.rs"]
 mod codex_config;
+#[path = "../codex/endpoint.rs"]
+mod codex_endpoint;
+#[path = "../codex/env.rs"]
+mod codex_env;
 #[path = "../codex/home.rs"]
 mod codex_home;
 #[path = "../files/io.rs"]
@@ -34,6 +38,12 @@ mod utils;
 mod workspace_macos;
 #[path = "../workspaces/settings.rs"]
 mod workspace_settings;
+#[cfg(target_os = "windows")]
+#[path = "../workspaces/windows.rs"]
+mod workspace_windows;
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+#[path = "../workspaces/xdg.rs"]
+mod workspace_xdg;
 
 // Provide feature-style module paths for shared cores when compiled in the daemon.
 mod codex {
@@ -43,6 +53,12 @@ mod codex {
     pub(crate) mod config {
         pub(crate) use crate::codex_config::*;
     }
+    pub(crate) mod endpoint {
+        pub(crate) use crate::codex_endpoint::*;
+    }
+    pub(crate) mod env {
+        pub(crate) use crate::codex_env::*;
+    }
     pub(crate) mod home {
         pub(crate) use crate::codex_home::*;
     }
@@ -81,19 +97,40 @@ use tokio_tungstenite::connect_async;
 use tokio_tungstenite::tungstenite::Message;
 
 use backend::app_server::{spawn_workspace_session, WorkspaceSession};
-use backend::events::{AppServerEvent, EventSink, TerminalExit, TerminalOutput};
+use backend::events::{
+    AppServerEvent, CloneProgressEvent, EventSink, GitSyncProgressEvent, MessageQueuedEvent,
+    OperationEvent, ProfileLoginEvent, RemoteUpdatesEvent, SessionCrashedEvent, TerminalExit,
+    TerminalOutput,
+};
 use shared::codex_core::CodexLoginCancelState;
+use shared::concurrency_core::{parse_operation_priority, OperationPriority};
+use shared::operations_core::OperationRegistry;
 use shared::prompts_core::{self, CustomPromptEntry};
 use shared::{
-    codex_aux_core, codex_core, files_core, git_core, git_ui_core, local_usage_core, settings_core,
-    workspaces_core, worktree_core,
+    auto_fetch_core, codex_aux_core, codex_core, credentials_core, event_batch_core, files_core,
+    git_core, git_ui_core, http_client_core, inventory_core, local_usage_core, network_core,
+    operation_timing_core, orphan_process_core, pinned_messages_core, profiles_core,
+    redaction_core, session_recovery_core, session_stats_core, settings_core, thread_analysis_core,
+    thread_export_core, thread_search_core, thread_titles_core, token_estimate_core,
+    turn_trace_core, workspace_bundle_core, workspace_groups_core, workspaces_core, worktree_core,
 };
 use storage::{read_settings, read_workspaces};
 use types::{
-    AppSettings, GitCommitDiff, GitFileDiff, GitHubIssuesResponse, GitHubPullRequestComment,
-    GitHubPullRequestDiff, GitHubPullRequestsResponse, GitLogResponse, LocalUsageSnapshot,
-    OrbitConnectTestResult, OrbitDeviceCodeStart, OrbitSignInPollResult, OrbitSignInStatus,
-    OrbitSignOutResult, WorkspaceEntry, WorkspaceInfo, WorkspaceSettings, WorktreeSetupStatus,
+    AppSettings, ApprovalRule, ApprovalRuleTest, ApprovalRulesExport, ApprovalRulesImport,
+    CodexProfile, GitBisectState, GitBlameLine, GitCommitDiff, GitFetchedRef, GitFileDiff,
+    GitFileHistoryEntry, GitGraphResponse, GitHubChecksResponse, GitHubCreatedPullRequest,
+    GitHubIssuesResponse, GitHubMergeResult, GitHubPullRequestComment, GitHubPullRequestDiff,
+    GitHubPullRequestInput, GitHubPullRequestsResponse, GitHubReviewComment, GitLogFilter,
+    GitLogResponse, GitRemote, GitRemoteUpdates, GitRepoStats, GitResetResult, GitStashApplyResult,
+    GitStashEntry, GitSyncResult, LocalUsageSnapshot, MessageTokenEstimate, NetworkStatus,
+    OperationInfo, OperationTimeEstimate, OrbitConnectTestResult, OrbitDeviceCodeStart,
+    OrbitSignInPollResult, OrbitSignInStatus, OrbitSignOutResult, OrphanedCodexProcess,
+    PinnedMessage, SessionAccount, SessionStartupStats, StaleCodexBin, SystemInventory, ThreadDiff,
+    ThreadExport, ThreadReplayResult, ThreadSearchResult, ThreadUsage, ThreadWindow,
+    TurnTraceCapture, TurnTraceReplay, WorkspaceAudit, WorkspaceBundleExport,
+    WorkspaceBundleImport, WorkspaceEntry, WorkspaceFileEntry, WorkspaceGroupInfo, WorkspaceInfo,
+    WorkspaceInterruptResult, WorkspaceRelocation, WorkspaceRemovalResult, WorkspaceSettings,
+    WorktreeChangesPreview, WorktreeSetupStatus,
 };
 use workspace_settings::apply_workspace_settings_update;
 
@@ -131,6 +168,14 @@ enum DaemonEvent {
     TerminalOutput(TerminalOutput),
     #[allow(dead_code)]
     TerminalExit(TerminalExit),
+    Operation(OperationEvent),
+    RemoteUpdates(RemoteUpdatesEvent),
+    NetworkStatus(NetworkStatus),
+    CloneProgress(CloneProgressEvent),
+    GitSyncProgress(GitSyncProgressEvent),
+    ProfileLogin(ProfileLoginEvent),
+    MessageQueued(MessageQueuedEvent),
+    SessionCrashed(SessionCrashedEvent),
 }
 
 impl EventSink for DaemonEventSink {
@@ -145,6 +190,38 @@ impl EventSink for DaemonEventSink {
     fn emit_terminal_exit(&self, event: TerminalExit) {
         let _ = self.tx.send(DaemonEvent::TerminalExit(event));
     }
+
+    fn emit_operation_event(&self, event: OperationEvent) {
+        let _ = self.tx.send(DaemonEvent::Operation(event));
+    }
+
+    fn emit_remote_updates_event(&self, event: RemoteUpdatesEvent) {
+        let _ = self.tx.send(DaemonEvent::RemoteUpdates(event));
+    }
+
+    fn emit_network_status_event(&self, event: NetworkStatus) {
+        let _ = self.tx.send(DaemonEvent::NetworkStatus(event));
+    }
+
+    fn emit_clone_progress_event(&self, event: CloneProgressEvent) {
+        let _ = self.tx.send(DaemonEvent::CloneProgress(event));
+    }
+
+    fn emit_git_sync_progress_event(&self, event: GitSyncProgressEvent) {
+        let _ = self.tx.send(DaemonEvent::GitSyncProgress(event));
+    }
+
+    fn emit_profile_login_event(&self, event: ProfileLoginEvent) {
+        let _ = self.tx.send(DaemonEvent::ProfileLogin(event));
+    }
+
+    fn emit_message_queued_event(&self, event: MessageQueuedEvent) {
+        let _ = self.tx.send(DaemonEvent::MessageQueued(event));
+    }
+
+    fn emit_session_crashed_event(&self, event: SessionCrashedEvent) {
+        let _ = self.tx.send(DaemonEvent::SessionCrashed(event));
+    }
 }
 
 struct DaemonConfig {
@@ -168,6 +245,7 @@ struct DaemonState {
     codex_login_cancels: Mutex<HashMap<String, CodexLoginCancelState>>,
     daemon_mode: String,
     daemon_binary_path: Option<String>,
+    operations: OperationRegistry<DaemonEventSink>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -181,7 +259,11 @@ impl DaemonState {
         let storage_path = config.data_dir.join("workspaces.json");
         let settings_path = config.data_dir.join("settings.json");
         let workspaces = read_workspaces(&storage_path).unwrap_or_default();
-        let app_settings = read_settings(&settings_path).unwrap_or_default();
+        let mut app_settings = read_settings(&settings_path).unwrap_or_default();
+        credentials_core::load_settings_secrets(&settings_path, &mut app_settings);
+        http_client_core::apply_http_settings(&app_settings);
+        redaction_core::apply_redaction_settings(&app_settings);
+        event_batch_core::apply_event_batch_settings(&app_settings);
         let daemon_mode = if config.orbit_url.is_some() {
             "orbit".to_string()
         } else {
@@ -197,6 +279,7 @@ impl DaemonState {
             storage_path,
             settings_path,
             app_settings: Mutex::new(app_settings),
+            operations: OperationRegistry::new(event_sink.clone()),
             event_sink,
             codex_login_cancels: Mutex::new(HashMap::new()),
             daemon_mode,
@@ -256,6 +339,7 @@ impl DaemonState {
         branch: String,
         name: Option<String>,
         copy_agents_md: bool,
+        base: Option<String>,
         client_version: String,
     ) -> Result<WorkspaceInfo, String> {
         let client_version = client_version.clone();
@@ -264,6 +348,7 @@ impl DaemonState {
             branch,
             name,
             copy_agents_md,
+            base,
             &self.data_dir,
             &self.workspaces,
             &self.sessions,
@@ -353,6 +438,27 @@ impl DaemonState {
         .await
     }
 
+    async fn remove_workspaces(
+        &self,
+        ids: Vec<String>,
+    ) -> Result<Vec<WorkspaceRemovalResult>, String> {
+        workspaces_core::remove_workspaces_core(
+            ids,
+            &self.workspaces,
+            &self.sessions,
+            &self.storage_path,
+            |root, args| {
+                workspaces_core::run_git_command_unit(root, args, git_core::run_git_command_owned)
+            },
+            |error| git_core::is_missing_worktree_error(error),
+            |path| {
+                std::fs::remove_dir_all(path)
+                    .map_err(|err| format!("Failed to remove worktree folder: {err}"))
+            },
+        )
+        .await
+    }
+
     async fn rename_worktree(
         &self,
         id: String,
@@ -487,6 +593,179 @@ impl DaemonState {
         .await
     }
 
+    async fn set_workspace_color(
+        &self,
+        id: String,
+        color: Option<String>,
+    ) -> Result<WorkspaceInfo, String> {
+        workspaces_core::set_workspace_color_core(
+            id,
+            color,
+            &self.workspaces,
+            &self.sessions,
+            &self.storage_path,
+        )
+        .await
+    }
+
+    async fn set_workspace_profile(
+        &self,
+        workspace_id: String,
+        profile_id: Option<String>,
+        client_version: String,
+    ) -> Result<WorkspaceInfo, String> {
+        let client_version = client_version.clone();
+        workspaces_core::set_workspace_profile_core(
+            workspace_id,
+            profile_id,
+            &self.workspaces,
+            &self.sessions,
+            &self.app_settings,
+            &self.storage_path,
+            |workspaces, workspace_id, next_settings| {
+                apply_workspace_settings_update(workspaces, workspace_id, next_settings)
+            },
+            move |entry, default_bin, codex_args, codex_home| {
+                spawn_with_client(
+                    self.event_sink.clone(),
+                    client_version.clone(),
+                    entry,
+                    default_bin,
+                    codex_args,
+                    codex_home,
+                )
+            },
+        )
+        .await
+    }
+
+    async fn check_workspace_codex_bins(&self) -> Vec<StaleCodexBin> {
+        workspaces_core::find_stale_codex_bins_core(&self.workspaces, &self.app_settings).await
+    }
+
+    async fn repoint_codex_bin(&self, id: String) -> Result<WorkspaceInfo, String> {
+        workspaces_core::repoint_codex_bin_core(
+            id,
+            &self.workspaces,
+            &self.sessions,
+            &self.storage_path,
+        )
+        .await
+    }
+
+    async fn rename_workspace(&self, id: String, name: String) -> Result<WorkspaceInfo, String> {
+        workspaces_core::rename_workspace_core(
+            id,
+            name,
+            &self.workspaces,
+            &self.sessions,
+            &self.storage_path,
+        )
+        .await
+    }
+
+    async fn relocate_workspace(
+        &self,
+        id: String,
+        new_path: String,
+        client_version: String,
+    ) -> Result<WorkspaceRelocation, String> {
+        workspaces_core::relocate_workspace_core(
+            id,
+            new_path,
+            &self.workspaces,
+            &self.sessions,
+            &self.app_settings,
+            &self.storage_path,
+            move |entry, default_bin, codex_args, codex_home| {
+                spawn_with_client(
+                    self.event_sink.clone(),
+                    client_version.clone(),
+                    entry,
+                    default_bin,
+                    codex_args,
+                    codex_home,
+                )
+            },
+        )
+        .await
+    }
+
+    async fn export_workspace_bundle(
+        &self,
+        workspace_id: String,
+        destination: Option<String>,
+    ) -> Result<WorkspaceBundleExport, String> {
+        workspace_bundle_core::export_workspace_bundle_core(
+            &self.workspaces,
+            &self.app_settings,
+            &self.settings_path,
+            workspace_id,
+            destination,
+        )
+        .await
+    }
+
+    async fn import_workspace_bundle(
+        &self,
+        path: String,
+        target_path: String,
+        trusted: bool,
+    ) -> Result<WorkspaceBundleImport, String> {
+        workspace_bundle_core::import_workspace_bundle_core(
+            &self.workspaces,
+            &self.sessions,
+            &self.app_settings,
+            &self.storage_path,
+            &self.settings_path,
+            path,
+            target_path,
+            trusted,
+        )
+        .await
+    }
+
+    async fn list_workspace_groups(&self) -> Vec<WorkspaceGroupInfo> {
+        workspace_groups_core::list_workspace_groups_core(&self.workspaces, &self.app_settings)
+            .await
+    }
+
+    async fn create_workspace_group(&self, name: String) -> Result<WorkspaceGroupInfo, String> {
+        workspace_groups_core::create_workspace_group_core(
+            name,
+            &self.workspaces,
+            &self.app_settings,
+            &self.settings_path,
+        )
+        .await
+    }
+
+    async fn rename_workspace_group(
+        &self,
+        id: String,
+        name: String,
+    ) -> Result<WorkspaceGroupInfo, String> {
+        workspace_groups_core::rename_workspace_group_core(
+            id,
+            name,
+            &self.workspaces,
+            &self.app_settings,
+            &self.settings_path,
+        )
+        .await
+    }
+
+    async fn delete_workspace_group(&self, id: String) -> Result<(), String> {
+        workspace_groups_core::delete_workspace_group_core(
+            id,
+            &self.workspaces,
+            &self.app_settings,
+            &self.storage_path,
+            &self.settings_path,
+        )
+        .await
+    }
+
     async fn connect_workspace(&self, id: String, client_version: String) -> Result<(), String> {
         {
             let sessions = self.sessions.lock().await;
@@ -495,6 +774,7 @@ impl DaemonState {
             }
         }
 
+        workspaces_core::interrupt_prewarm();
         let client_version = client_version.clone();
         workspaces_core::connect_workspace_core(
             id,
@@ -515,13 +795,75 @@ impl DaemonState {
         .await
     }
 
+    async fn prewarm_sessions(
+        &self,
+        workspace_ids: Vec<String>,
+        client_version: String,
+    ) -> Vec<String> {
+        workspaces_core::prewarm_sessions_core(
+            workspace_ids,
+            &self.workspaces,
+            &self.sessions,
+            &self.app_settings,
+            move |entry, default_bin, codex_args, codex_home| {
+                spawn_with_client(
+                    self.event_sink.clone(),
+                    client_version.clone(),
+                    entry,
+                    default_bin,
+                    codex_args,
+                    codex_home,
+                )
+            },
+        )
+        .await
+    }
+
     async fn get_app_settings(&self) -> AppSettings {
         settings_core::get_app_settings_core(&self.app_settings).await
     }
 
+    async fn list_profiles(&self) -> Vec<CodexProfile> {
+        profiles_core::list_profiles_core(&self.app_settings).await
+    }
+
+    async fn create_profile(
+        &self,
+        name: String,
+        codex_home: String,
+    ) -> Result<CodexProfile, String> {
+        profiles_core::create_profile_core(
+            name,
+            codex_home,
+            &self.app_settings,
+            &self.settings_path,
+        )
+        .await
+    }
+
+    async fn codex_profile_login(&self, profile_id: String) -> Result<(), String> {
+        profiles_core::codex_profile_login_core(profile_id, &self.app_settings, &self.event_sink)
+            .await
+    }
+
+    async fn codex_profile_logout(&self, profile_id: String) -> Result<(), String> {
+        profiles_core::codex_profile_logout_core(profile_id, &self.app_settings).await
+    }
+
+    async fn delete_profile(&self, profile_id: String) -> Result<(), String> {
+        profiles_core::delete_profile_core(
+            profile_id,
+            &self.workspaces,
+            &self.app_settings,
+            &self.settings_path,
+        )
+        .await
+    }
+
     async fn update_app_settings(&self, settings: AppSettings) -> Result<AppSettings, String> {
         settings_core::update_app_settings_core(settings, &self.app_settings, &self.settings_path)
             .await
+            .map(settings_core::settings_for_response)
     }
 
     async fn set_codex_feature_flag(&self, feature_key: String, enabled: bool) -> Result<(), String> {
@@ -597,9 +939,24 @@ impl DaemonState {
         })
     }
 
-    async fn list_workspace_files(&self, workspace_id: String) -> Result<Vec<String>, String> {
+    async fn list_workspace_files(
+        &self,
+        workspace_id: String,
+        include_ignored: bool,
+    ) -> Result<Vec<String>, String> {
         workspaces_core::list_workspace_files_core(&self.workspaces, &workspace_id, |root| {
-            list_workspace_files_inner(root, 20000)
+            list_workspace_files_inner(root, 20000, include_ignored)
+        })
+        .await
+    }
+
+    async fn list_workspace_files_detailed(
+        &self,
+        workspace_id: String,
+        include_ignored: bool,
+    ) -> Result<Vec<WorkspaceFileEntry>, String> {
+        workspaces_core::list_workspace_files_core(&self.workspaces, &workspace_id, |root| {
+            list_workspace_files_detailed_inner(root, 20000, include_ignored)
         })
         .await
     }
@@ -653,6 +1010,193 @@ impl DaemonState {
         codex_core::fork_thread_core(&self.sessions, workspace_id, thread_id).await
     }
 
+    async fn replay_thread(
+        &self,
+        workspace_id: String,
+        source_thread_id: String,
+        model: String,
+        operation_id: Option<String>,
+    ) -> Result<ThreadReplayResult, String> {
+        let sessions = &self.sessions;
+        self.operations
+            .run(
+                "replay",
+                Some(workspace_id.clone()),
+                operation_id,
+                |token| async move {
+                    thread_analysis_core::replay_thread_core(
+                        sessions,
+                        workspace_id,
+                        source_thread_id,
+                        model,
+                        |done, total| {
+                            token.report_progress(
+                                Some(done as f64 / total as f64),
+                                Some(format!("Replayed {done} of {total} turns")),
+                            )
+                        },
+                    )
+                    .await
+                },
+            )
+            .await
+    }
+
+    async fn diff_threads(
+        &self,
+        workspace_id: String,
+        thread_a: String,
+        thread_b: String,
+    ) -> Result<ThreadDiff, String> {
+        thread_analysis_core::diff_threads_core(&self.sessions, workspace_id, thread_a, thread_b)
+            .await
+    }
+
+    async fn get_thread_window(
+        &self,
+        workspace_id: String,
+        thread_id: String,
+        around_message_id: String,
+        radius: Option<u32>,
+    ) -> Result<ThreadWindow, String> {
+        thread_analysis_core::get_thread_window_core(
+            &self.sessions,
+            workspace_id,
+            thread_id,
+            around_message_id,
+            radius,
+        )
+        .await
+    }
+
+    async fn export_thread(
+        &self,
+        workspace_id: String,
+        thread_id: String,
+        format: Option<String>,
+        destination: Option<String>,
+    ) -> Result<ThreadExport, String> {
+        thread_export_core::export_thread_core(
+            &self.sessions,
+            workspace_id,
+            thread_id,
+            format,
+            destination,
+        )
+        .await
+    }
+
+    async fn search_threads(
+        &self,
+        workspace_id: String,
+        query: String,
+        include_archived: bool,
+        max_message_bytes: Option<usize>,
+        operation_id: Option<String>,
+    ) -> Result<Vec<ThreadSearchResult>, String> {
+        self.operations
+            .run("search", Some(workspace_id.clone()), operation_id, |_| {
+                thread_search_core::search_threads_core(
+                    &self.workspaces,
+                    workspace_id,
+                    query,
+                    include_archived,
+                    max_message_bytes,
+                )
+            })
+            .await
+    }
+
+    async fn estimate_message_tokens(
+        &self,
+        workspace_id: String,
+        thread_id: String,
+        message: String,
+        attachments: Vec<String>,
+        model: Option<String>,
+    ) -> Result<MessageTokenEstimate, String> {
+        token_estimate_core::estimate_message_tokens_core(
+            &self.sessions,
+            &self.workspaces,
+            workspace_id,
+            thread_id,
+            message,
+            attachments,
+            model,
+        )
+        .await
+    }
+
+    async fn thread_usage(
+        &self,
+        workspace_id: String,
+        thread_id: String,
+    ) -> Result<ThreadUsage, String> {
+        token_estimate_core::thread_usage_core(
+            &self.sessions,
+            &self.workspaces,
+            workspace_id,
+            thread_id,
+        )
+        .await
+    }
+
+    async fn capture_turn_trace(
+        &self,
+        workspace_id: String,
+        turn_id: String,
+    ) -> Result<TurnTraceCapture, String> {
+        turn_trace_core::capture_turn_trace_core(
+            &self.workspaces,
+            &self.sessions,
+            &self.app_settings,
+            &self.settings_path,
+            workspace_id,
+            turn_id,
+        )
+        .await
+    }
+
+    async fn load_turn_trace(
+        &self,
+        path: String,
+        workspace_id: Option<String>,
+    ) -> Result<TurnTraceReplay, String> {
+        turn_trace_core::load_turn_trace_core(
+            &self.workspaces,
+            &self.event_sink,
+            path,
+            workspace_id,
+        )
+        .await
+    }
+
+    fn pin_thread_message(
+        &self,
+        workspace_id: String,
+        thread_id: String,
+        message_id: String,
+    ) -> Result<Vec<PinnedMessage>, String> {
+        pinned_messages_core::pin_thread_message_core(
+            &self.storage_path,
+            workspace_id,
+            thread_id,
+            message_id,
+        )
+    }
+
+    fn unpin_thread_message(
+        &self,
+        thread_id: String,
+        message_id: String,
+    ) -> Result<Vec<PinnedMessage>, String> {
+        pinned_messages_core::unpin_thread_message_core(&self.storage_path, thread_id, message_id)
+    }
+
+    fn list_pinned_messages(&self, thread_id: String) -> Result<Vec<PinnedMessage>, String> {
+        pinned_messages_core::list_pinned_messages_core(&self.storage_path, &thread_id)
+    }
+
     async fn list_threads(
         &self,
         workspace_id: String,
@@ -660,7 +1204,16 @@ impl DaemonState {
         limit: Option<u32>,
         sort_key: Option<String>,
     ) -> Result<Value, String> {
-        codex_core::list_threads_core(&self.sessions, workspace_id, cursor, limit, sort_key).await
+        let mut response = codex_core::list_threads_core(
+            &self.sessions,
+            workspace_id.clone(),
+            cursor,
+            limit,
+            sort_key,
+        )
+        .await?;
+        thread_titles_core::apply_thread_titles(&self.storage_path, &workspace_id, &mut response);
+        Ok(response)
     }
 
     async fn list_mcp_server_status(
@@ -697,6 +1250,15 @@ impl DaemonState {
         codex_core::set_thread_name_core(&self.sessions, workspace_id, thread_id, name).await
     }
 
+    fn rename_thread(
+        &self,
+        workspace_id: String,
+        thread_id: String,
+        title: String,
+    ) -> Result<Option<String>, String> {
+        thread_titles_core::rename_thread_core(&self.storage_path, workspace_id, thread_id, title)
+    }
+
     async fn send_user_message(
         &self,
         workspace_id: String,
@@ -708,9 +1270,12 @@ impl DaemonState {
         images: Option<Vec<String>>,
         app_mentions: Option<Vec<Value>>,
         collaboration_mode: Option<Value>,
+        queue: bool,
     ) -> Result<Value, String> {
         codex_core::send_user_message_core(
             &self.sessions,
+            &self.workspaces,
+            &self.event_sink,
             workspace_id,
             thread_id,
             text,
@@ -720,10 +1285,15 @@ impl DaemonState {
             images,
             app_mentions,
             collaboration_mode,
+            queue,
         )
         .await
     }
 
+    async fn clear_message_queue(&self, workspace_id: String) -> Result<usize, String> {
+        codex_core::clear_message_queue_core(&self.sessions, &self.event_sink, workspace_id).await
+    }
+
     async fn turn_steer(
         &self,
         workspace_id: String,
@@ -754,6 +1324,10 @@ impl DaemonState {
         codex_core::turn_interrupt_core(&self.sessions, workspace_id, thread_id, turn_id).await
     }
 
+    async fn interrupt_all_turns(&self) -> Vec<WorkspaceInterruptResult> {
+        codex_core::interrupt_all_turns_core(&self.sessions).await
+    }
+
     async fn start_review(
         &self,
         workspace_id: String,
@@ -765,8 +1339,8 @@ impl DaemonState {
             .await
     }
 
-    async fn model_list(&self, workspace_id: String) -> Result<Value, String> {
-        codex_core::model_list_core(&self.sessions, workspace_id).await
+    async fn model_list(&self, workspace_id: String, refresh: bool) -> Result<Value, String> {
+        codex_core::model_list_core(&self.sessions, &self.storage_path, workspace_id, refresh).await
     }
 
     async fn experimental_feature_list(
@@ -790,6 +1364,16 @@ impl DaemonState {
         codex_core::account_read_core(&self.sessions, &self.workspaces, workspace_id).await
     }
 
+    async fn get_session_account(&self, workspace_id: String) -> Result<SessionAccount, String> {
+        codex_core::get_session_account_core(
+            &self.sessions,
+            &self.workspaces,
+            &self.app_settings,
+            workspace_id,
+        )
+        .await
+    }
+
     async fn codex_login(&self, workspace_id: String) -> Result<Value, String> {
         codex_core::codex_login_core(&self.sessions, &self.codex_login_cancels, workspace_id).await
     }
@@ -799,8 +1383,9 @@ impl DaemonState {
             .await
     }
 
-    async fn skills_list(&self, workspace_id: String) -> Result<Value, String> {
-        codex_core::skills_list_core(&self.sessions, workspace_id).await
+    async fn skills_list(&self, workspace_id: String, refresh: bool) -> Result<Value, String> {
+        codex_core::skills_list_core(&self.sessions, &self.storage_path, workspace_id, refresh)
+            .await
     }
 
     async fn apps_list(
@@ -818,23 +1403,67 @@ impl DaemonState {
         workspace_id: String,
         request_id: Value,
         result: Value,
+        rule_command: Option<Vec<String>>,
     ) -> Result<Value, String> {
-        codex_core::respond_to_server_request_core(
+        let sent = codex_core::respond_to_server_request_core(
             &self.sessions,
+            &self.workspaces,
             workspace_id,
             request_id,
             result,
+            rule_command,
         )
         .await?;
-        Ok(json!({ "ok": true }))
+        Ok(json!({ "ok": true, "sent": sent }))
     }
 
     async fn remember_approval_rule(
         &self,
         workspace_id: String,
         command: Vec<String>,
+        kind: Option<String>,
+        pattern: Option<String>,
     ) -> Result<Value, String> {
-        codex_core::remember_approval_rule_core(&self.workspaces, workspace_id, command).await
+        codex_core::remember_approval_rule_core(
+            &self.workspaces,
+            workspace_id,
+            command,
+            kind,
+            pattern,
+        )
+        .await
+    }
+
+    async fn list_approval_rules(&self, workspace_id: String) -> Result<Vec<ApprovalRule>, String> {
+        codex_core::list_approval_rules_core(&self.workspaces, workspace_id).await
+    }
+
+    fn test_approval_rule(
+        &self,
+        pattern: String,
+        command: Vec<String>,
+        kind: Option<String>,
+    ) -> Result<ApprovalRuleTest, String> {
+        codex_core::test_approval_rule_core(pattern, command, kind)
+    }
+
+    async fn delete_approval_rule(&self, workspace_id: String, id: String) -> Result<(), String> {
+        codex_core::delete_approval_rule_core(&self.workspaces, workspace_id, id).await
+    }
+
+    async fn export_approval_rules(
+        &self,
+        workspace_id: String,
+    ) -> Result<ApprovalRulesExport, String> {
+        codex_core::export_approval_rules_core(&self.workspaces, workspace_id).await
+    }
+
+    async fn import_approval_rules(
+        &self,
+        workspace_id: String,
+        data: String,
+    ) -> Result<ApprovalRulesImport, String> {
+        codex_core::import_approval_rules_core(&self.workspaces, workspace_id, data).await
     }
 
     async fn get_config_model(&self, workspace_id: String) -> Result<Value, String> {
@@ -846,34 +1475,59 @@ impl DaemonState {
         source_workspace_id: String,
         copies_folder: String,
         copy_name: String,
+        operation_id: Option<String>,
         client_version: String,
     ) -> Result<WorkspaceInfo, String> {
-        workspaces_core::add_clone_core(
-            source_workspace_id,
-            copy_name,
-            copies_folder,
-            &self.workspaces,
-            &self.sessions,
-            &self.app_settings,
-            &self.storage_path,
-            |entry, default_bin, codex_args, codex_home| {
-                spawn_with_client(
-                    self.event_sink.clone(),
-                    client_version.clone(),
-                    entry,
-                    default_bin,
-                    codex_args,
-                    codex_home,
-                )
-            },
-        )
-        .await
+        self.operations
+            .run(
+                "clone",
+                Some(source_workspace_id.clone()),
+                operation_id,
+                |_| {
+                    workspaces_core::add_clone_core(
+                        source_workspace_id,
+                        copy_name,
+                        copies_folder,
+                        &self.workspaces,
+                        &self.sessions,
+                        &self.app_settings,
+                        &self.storage_path,
+                        &self.event_sink,
+                        |entry, default_bin, codex_args, codex_home| {
+                            spawn_with_client(
+                                self.event_sink.clone(),
+                                client_version.clone(),
+                                entry,
+                                default_bin,
+                                codex_args,
+                                codex_home,
+                            )
+                        },
+                    )
+                },
+            )
+            .await
     }
 
     async fn apply_worktree_changes(&self, workspace_id: String) -> Result<(), String> {
         workspaces_core::apply_worktree_changes_core(&self.workspaces, workspace_id).await
     }
 
+    async fn apply_worktree_commits(&self, workspace_id: String) -> Result<(), String> {
+        workspaces_core::apply_worktree_commits_core(&self.workspaces, workspace_id).await
+    }
+
+    async fn move_worktree_changes_back(&self, workspace_id: String) -> Result<(), String> {
+        workspaces_core::move_worktree_changes_back_core(&self.workspaces, workspace_id).await
+    }
+
+    async fn preview_worktree_changes(
+        &self,
+        workspace_id: String,
+    ) -> Result<WorktreeChangesPreview, String> {
+        workspaces_core::preview_worktree_changes_core(&self.workspaces, workspace_id).await
+    }
+
     async fn open_workspace_in(
         &self,
         path: String,
@@ -893,14 +1547,29 @@ impl DaemonState {
             .await;
         }
 
-        #[cfg(not(target_os = "macos"))]
+        #[cfg(target_os = "windows")]
         {
-            workspaces_core::get_open_app_icon_core(app_name, |_name| None).await
+            return workspaces_core::get_open_app_icon_core(app_name, |name| {
+                workspace_windows::get_open_app_icon_inner(name)
+            })
+            .await;
+        }
+
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        {
+            workspaces_core::get_open_app_icon_core(app_name, |name| {
+                workspace_xdg::get_open_app_icon_inner(name)
+            })
+            .await
         }
     }
 
-    async fn get_git_status(&self, workspace_id: String) -> Result<Value, String> {
-        git_ui_core::get_git_status_core(&self.workspaces, workspace_id).await
+    async fn get_git_status(
+        &self,
+        workspace_id: String,
+        priority: Option<OperationPriority>,
+    ) -> Result<Value, String> {
+        git_ui_core::get_git_status_core(&self.workspaces, workspace_id, priority).await
     }
 
     async fn init_git_repo(
@@ -912,35 +1581,139 @@ impl DaemonState {
         git_ui_core::init_git_repo_core(&self.workspaces, workspace_id, branch, force).await
     }
 
-    async fn create_github_repo(
+    async fn create_github_repo(
+        &self,
+        workspace_id: String,
+        repo: String,
+        visibility: String,
+        branch: Option<String>,
+    ) -> Result<Value, String> {
+        git_ui_core::create_github_repo_core(&self.workspaces, workspace_id, repo, visibility, branch)
+            .await
+    }
+
+    async fn list_git_roots(
+        &self,
+        workspace_id: String,
+        depth: Option<usize>,
+    ) -> Result<Vec<String>, String> {
+        git_ui_core::list_git_roots_core(&self.workspaces, workspace_id, depth).await
+    }
+
+    async fn get_git_diffs(
+        &self,
+        workspace_id: String,
+        priority: Option<OperationPriority>,
+        context_lines: Option<usize>,
+    ) -> Result<Vec<GitFileDiff>, String> {
+        git_ui_core::get_git_diffs_core(
+            &self.workspaces,
+            &self.app_settings,
+            workspace_id,
+            priority,
+            context_lines,
+        )
+        .await
+    }
+
+    async fn get_git_diff_range(
+        &self,
+        workspace_id: String,
+        base: String,
+        head: String,
+        paths: Vec<String>,
+        two_dot: bool,
+    ) -> Result<Vec<GitFileDiff>, String> {
+        git_ui_core::get_git_diff_range_core(
+            &self.workspaces,
+            &self.app_settings,
+            workspace_id,
+            base,
+            head,
+            paths,
+            two_dot,
+        )
+        .await
+    }
+
+    async fn get_git_log(
+        &self,
+        workspace_id: String,
+        skip: Option<usize>,
+        limit: Option<usize>,
+        path: Option<String>,
+        filter: Option<GitLogFilter>,
+    ) -> Result<GitLogResponse, String> {
+        git_ui_core::get_git_log_core(&self.workspaces, workspace_id, skip, limit, path, filter)
+            .await
+    }
+
+    async fn get_git_graph(
+        &self,
+        workspace_id: String,
+        max_count: Option<usize>,
+        refs: Option<Vec<String>>,
+    ) -> Result<GitGraphResponse, String> {
+        git_ui_core::get_git_graph_core(&self.workspaces, workspace_id, max_count, refs).await
+    }
+
+    async fn get_file_history(
+        &self,
+        workspace_id: String,
+        path: String,
+        follow_renames: bool,
+        max_count: Option<usize>,
+    ) -> Result<Vec<GitFileHistoryEntry>, String> {
+        git_ui_core::get_file_history_core(
+            &self.workspaces,
+            workspace_id,
+            path,
+            follow_renames,
+            max_count,
+        )
+        .await
+    }
+
+    async fn git_blame(
+        &self,
+        workspace_id: String,
+        path: String,
+        rev: Option<String>,
+    ) -> Result<Vec<GitBlameLine>, String> {
+        git_ui_core::git_blame_core(&self.workspaces, workspace_id, path, rev).await
+    }
+
+    async fn get_repo_stats(&self, workspace_id: String) -> Result<GitRepoStats, String> {
+        git_ui_core::get_repo_stats_core(&self.workspaces, workspace_id).await
+    }
+
+    async fn audit_workspace(&self, workspace_id: String) -> Result<WorkspaceAudit, String> {
+        git_ui_core::audit_workspace_core(&self.workspaces, &self.app_settings, workspace_id).await
+    }
+
+    async fn git_bisect_start(
         &self,
         workspace_id: String,
-        repo: String,
-        visibility: String,
-        branch: Option<String>,
-    ) -> Result<Value, String> {
-        git_ui_core::create_github_repo_core(&self.workspaces, workspace_id, repo, visibility, branch)
-            .await
+        bad: String,
+        good: String,
+    ) -> Result<GitBisectState, String> {
+        git_ui_core::git_bisect_start_core(&self.workspaces, workspace_id, bad, good).await
     }
 
-    async fn list_git_roots(
+    async fn git_bisect_mark(
         &self,
         workspace_id: String,
-        depth: Option<usize>,
-    ) -> Result<Vec<String>, String> {
-        git_ui_core::list_git_roots_core(&self.workspaces, workspace_id, depth).await
+        verdict: String,
+    ) -> Result<GitBisectState, String> {
+        git_ui_core::git_bisect_mark_core(&self.workspaces, workspace_id, verdict).await
     }
 
-    async fn get_git_diffs(&self, workspace_id: String) -> Result<Vec<GitFileDiff>, String> {
-        git_ui_core::get_git_diffs_core(&self.workspaces, &self.app_settings, workspace_id).await
+    async fn git_bisect_reset(&self, workspace_id: String) -> Result<GitBisectState, String> {
+        git_ui_core::git_bisect_reset_core(&self.workspaces, workspace_id).await
     }
 
-    async fn get_git_log(
-        &self,
-        workspace_id: String,
-        limit: Option<usize>,
-    ) -> Result<GitLogResponse, String> {
-        git_ui_core::get_git_log_core(&self.workspaces, workspace_id, limit).await
+    async fn get_git_bisect_state(&self, workspace_id: String) -> Result<GitBisectState, String> {
+        git_ui_core::get_git_bisect_state_core(&self.workspaces, workspace_id).await
     }
 
     async fn get_git_commit_diff(
@@ -961,10 +1734,25 @@ impl DaemonState {
         git_ui_core::get_git_remote_core(&self.workspaces, workspace_id).await
     }
 
+    async fn list_git_remotes(&self, workspace_id: String) -> Result<Vec<GitRemote>, String> {
+        git_ui_core::list_git_remotes_core(&self.workspaces, workspace_id).await
+    }
+
     async fn stage_git_file(&self, workspace_id: String, path: String) -> Result<(), String> {
         git_ui_core::stage_git_file_core(&self.workspaces, workspace_id, path).await
     }
 
+    async fn stage_git_hunk(
+        &self,
+        workspace_id: String,
+        path: String,
+        hunk_header: String,
+        patch: String,
+    ) -> Result<(), String> {
+        git_ui_core::stage_git_hunk_core(&self.workspaces, workspace_id, path, hunk_header, patch)
+            .await
+    }
+
     async fn stage_git_all(&self, workspace_id: String) -> Result<(), String> {
         git_ui_core::stage_git_all_core(&self.workspaces, workspace_id).await
     }
@@ -973,6 +1761,21 @@ impl DaemonState {
         git_ui_core::unstage_git_file_core(&self.workspaces, workspace_id, path).await
     }
 
+    async fn unstage_git_hunk(
+        &self,
+        workspace_id: String,
+        path: String,
+        hunk_header: String,
+        patch: String,
+    ) -> Result<(), String> {
+        git_ui_core::unstage_git_hunk_core(&self.workspaces, workspace_id, path, hunk_header, patch)
+            .await
+    }
+
+    async fn unstage_git_all(&self, workspace_id: String) -> Result<usize, String> {
+        git_ui_core::unstage_git_all_core(&self.workspaces, workspace_id).await
+    }
+
     async fn revert_git_file(&self, workspace_id: String, path: String) -> Result<(), String> {
         git_ui_core::revert_git_file_core(&self.workspaces, workspace_id, path).await
     }
@@ -981,26 +1784,190 @@ impl DaemonState {
         git_ui_core::revert_git_all_core(&self.workspaces, workspace_id).await
     }
 
+    async fn git_reset(
+        &self,
+        workspace_id: String,
+        target: Option<String>,
+        mode: String,
+        confirm: bool,
+    ) -> Result<GitResetResult, String> {
+        git_ui_core::git_reset_core(&self.workspaces, workspace_id, target, mode, confirm).await
+    }
+
     async fn commit_git(&self, workspace_id: String, message: String) -> Result<(), String> {
         git_ui_core::commit_git_core(&self.workspaces, workspace_id, message).await
     }
 
+    async fn git_commit(
+        &self,
+        workspace_id: String,
+        message: String,
+        amend: bool,
+        signoff: bool,
+    ) -> Result<String, String> {
+        git_ui_core::git_commit_core(&self.workspaces, workspace_id, message, amend, signoff).await
+    }
+
     async fn push_git(&self, workspace_id: String) -> Result<(), String> {
         git_ui_core::push_git_core(&self.workspaces, workspace_id).await
     }
 
-    async fn pull_git(&self, workspace_id: String) -> Result<(), String> {
-        git_ui_core::pull_git_core(&self.workspaces, workspace_id).await
+    async fn pull_git(
+        &self,
+        workspace_id: String,
+        operation_id: Option<String>,
+        priority: Option<OperationPriority>,
+    ) -> Result<(), String> {
+        self.operations
+            .run("pull", Some(workspace_id.clone()), operation_id, |_| {
+                git_ui_core::pull_git_core(&self.workspaces, workspace_id, priority)
+            })
+            .await
+    }
+
+    async fn git_push(
+        &self,
+        workspace_id: String,
+        set_upstream: bool,
+        priority: Option<OperationPriority>,
+    ) -> Result<GitSyncResult, String> {
+        git_ui_core::git_push_core(
+            &self.workspaces,
+            workspace_id,
+            set_upstream,
+            priority,
+            &self.event_sink,
+        )
+        .await
+    }
+
+    async fn git_pull(
+        &self,
+        workspace_id: String,
+        rebase: bool,
+        priority: Option<OperationPriority>,
+    ) -> Result<GitSyncResult, String> {
+        git_ui_core::git_pull_core(
+            &self.workspaces,
+            workspace_id,
+            rebase,
+            priority,
+            &self.event_sink,
+        )
+        .await
+    }
+
+    async fn git_fetch(
+        &self,
+        workspace_id: String,
+        remote: Option<String>,
+        prune: bool,
+        priority: Option<OperationPriority>,
+    ) -> Result<Vec<GitFetchedRef>, String> {
+        git_ui_core::git_fetch_core(
+            &self.workspaces,
+            workspace_id,
+            remote,
+            prune,
+            priority,
+            &self.event_sink,
+        )
+        .await
+    }
+
+    async fn git_stash_list(&self, workspace_id: String) -> Result<Vec<GitStashEntry>, String> {
+        git_ui_core::git_stash_list_core(&self.workspaces, workspace_id).await
+    }
+
+    async fn git_stash_push(
+        &self,
+        workspace_id: String,
+        message: Option<String>,
+        include_untracked: bool,
+    ) -> Result<(), String> {
+        git_ui_core::git_stash_push_core(&self.workspaces, workspace_id, message, include_untracked)
+            .await
+    }
+
+    async fn git_stash_apply(
+        &self,
+        workspace_id: String,
+        index: usize,
+    ) -> Result<GitStashApplyResult, String> {
+        git_ui_core::git_stash_apply_core(&self.workspaces, workspace_id, index).await
+    }
+
+    async fn git_stash_pop(
+        &self,
+        workspace_id: String,
+        index: usize,
+    ) -> Result<GitStashApplyResult, String> {
+        git_ui_core::git_stash_pop_core(&self.workspaces, workspace_id, index).await
+    }
+
+    async fn git_stash_drop(&self, workspace_id: String, index: usize) -> Result<(), String> {
+        git_ui_core::git_stash_drop_core(&self.workspaces, workspace_id, index).await
+    }
+
+    async fn fetch_git(
+        &self,
+        workspace_id: String,
+        operation_id: Option<String>,
+        priority: Option<OperationPriority>,
+    ) -> Result<(), String> {
+        self.operations
+            .run("fetch", Some(workspace_id.clone()), operation_id, |_| {
+                git_ui_core::fetch_git_core(
+                    &self.workspaces,
+                    &self.storage_path,
+                    workspace_id,
+                    priority,
+                )
+            })
+            .await
+    }
+
+    fn cancel_operation(&self, operation_id: String) -> bool {
+        self.operations.cancel(&operation_id)
+    }
+
+    async fn get_system_inventory(&self) -> SystemInventory {
+        inventory_core::get_system_inventory_core(
+            &self.workspaces,
+            &self.sessions,
+            self.operations.list(),
+        )
+        .await
     }
 
-    async fn fetch_git(&self, workspace_id: String) -> Result<(), String> {
-        git_ui_core::fetch_git_core(&self.workspaces, workspace_id).await
+    fn list_operations(&self) -> Vec<OperationInfo> {
+        self.operations.list()
+    }
+
+    fn get_network_status(&self) -> NetworkStatus {
+        network_core::get_network_status_core()
     }
 
     async fn sync_git(&self, workspace_id: String) -> Result<(), String> {
         git_ui_core::sync_git_core(&self.workspaces, workspace_id).await
     }
 
+    async fn check_remote_updates(&self, workspace_id: String) -> Result<GitRemoteUpdates, String> {
+        git_ui_core::check_remote_updates_core(&self.workspaces, workspace_id).await
+    }
+
+    async fn estimate_operation_time(
+        &self,
+        kind: String,
+        repo_size_hint: Option<u64>,
+    ) -> Result<OperationTimeEstimate, String> {
+        operation_timing_core::estimate_operation_time_core(
+            &self.storage_path,
+            kind,
+            repo_size_hint,
+        )
+    }
+
     async fn get_github_issues(
         &self,
         workspace_id: String,
@@ -1037,6 +2004,15 @@ impl DaemonState {
         .await
     }
 
+    async fn get_github_pull_request_checks(
+        &self,
+        workspace_id: String,
+        pr_number: u64,
+    ) -> Result<GitHubChecksResponse, String> {
+        git_ui_core::get_github_pull_request_checks_core(&self.workspaces, workspace_id, pr_number)
+            .await
+    }
+
     async fn checkout_github_pull_request(
         &self,
         workspace_id: String,
@@ -1046,6 +2022,53 @@ impl DaemonState {
             .await
     }
 
+    async fn create_github_pull_request(
+        &self,
+        workspace_id: String,
+        pull_request: GitHubPullRequestInput,
+    ) -> Result<GitHubCreatedPullRequest, String> {
+        git_ui_core::create_github_pull_request_core(&self.workspaces, workspace_id, pull_request)
+            .await
+    }
+
+    async fn submit_github_pull_request_review(
+        &self,
+        workspace_id: String,
+        pr_number: u64,
+        event: String,
+        body: Option<String>,
+        comments: Vec<GitHubReviewComment>,
+    ) -> Result<u64, String> {
+        git_ui_core::submit_github_pull_request_review_core(
+            &self.workspaces,
+            workspace_id,
+            pr_number,
+            event,
+            body,
+            comments,
+        )
+        .await
+    }
+
+    async fn merge_github_pull_request(
+        &self,
+        workspace_id: String,
+        pr_number: u64,
+        method: String,
+        commit_title: Option<String>,
+        commit_message: Option<String>,
+    ) -> Result<GitHubMergeResult, String> {
+        git_ui_core::merge_github_pull_request_core(
+            &self.workspaces,
+            workspace_id,
+            pr_number,
+            method,
+            commit_title,
+            commit_message,
+        )
+        .await
+    }
+
     async fn list_git_branches(&self, workspace_id: String) -> Result<Value, String> {
         git_ui_core::list_git_branches_core(&self.workspaces, workspace_id).await
     }
@@ -1079,6 +2102,7 @@ impl DaemonState {
         &self,
         workspace_id: String,
         scope: String,
+        folder: Option<String>,
         name: String,
         description: Option<String>,
         argument_hint: Option<String>,
@@ -1089,6 +2113,7 @@ impl DaemonState {
             &self.settings_path,
             workspace_id,
             scope,
+            folder,
             name,
             description,
             argument_hint,
@@ -1129,6 +2154,7 @@ impl DaemonState {
         workspace_id: String,
         path: String,
         scope: String,
+        folder: Option<String>,
     ) -> Result<CustomPromptEntry, String> {
         prompts_core::prompts_move_core(
             &self.workspaces,
@@ -1136,6 +2162,39 @@ impl DaemonState {
             workspace_id,
             path,
             scope,
+            folder,
+        )
+        .await
+    }
+
+    async fn prompts_duplicate(
+        &self,
+        workspace_id: String,
+        path: String,
+    ) -> Result<CustomPromptEntry, String> {
+        prompts_core::prompts_duplicate_core(
+            &self.workspaces,
+            &self.settings_path,
+            workspace_id,
+            path,
+        )
+        .await
+    }
+
+    async fn prompts_render(
+        &self,
+        workspace_id: String,
+        path: String,
+        vars: HashMap<String, String>,
+        leave_unfilled: bool,
+    ) -> Result<String, String> {
+        prompts_core::prompts_render_core(
+            &self.workspaces,
+            &self.settings_path,
+            workspace_id,
+            path,
+            vars,
+            leave_unfilled,
         )
         .await
     }
@@ -1148,6 +2207,24 @@ impl DaemonState {
         codex_aux_core::codex_doctor_core(&self.app_settings, codex_bin, codex_args).await
     }
 
+    fn get_session_startup_stats(&self) -> SessionStartupStats {
+        session_stats_core::get_session_startup_stats_core()
+    }
+
+    async fn find_orphaned_codex_processes(&self) -> Result<Vec<OrphanedCodexProcess>, String> {
+        orphan_process_core::find_orphaned_codex_processes_core(&self.workspaces, &self.sessions)
+            .await
+    }
+
+    async fn kill_orphaned_codex_processes(&self, pids: Vec<u32>) -> Result<Vec<u32>, String> {
+        orphan_process_core::kill_orphaned_codex_processes_core(
+            &self.workspaces,
+            &self.sessions,
+            pids,
+        )
+        .await
+    }
+
     async fn generate_commit_message(&self, workspace_id: String) -> Result<String, String> {
         let repo_root = git_ui_core::resolve_repo_root_for_workspace_core(
             &self.workspaces,
@@ -1269,25 +2346,40 @@ fn send_notification_fallback_inner(title: String, body: String) -> Result<(), S
     }
 }
 
-fn list_workspace_files_inner(root: &PathBuf, max_files: usize) -> Vec<String> {
-    let mut results = Vec::new();
-    let walker = WalkBuilder::new(root)
+fn workspace_walker(root: &PathBuf, include_ignored: bool) -> ignore::Walk {
+    let respect_ignore_rules = !include_ignored;
+    WalkBuilder::new(root)
         .hidden(false)
         .follow_links(false)
+        .git_ignore(respect_ignore_rules)
+        .git_exclude(respect_ignore_rules)
+        .git_global(respect_ignore_rules)
+        .ignore(respect_ignore_rules)
+        .parents(respect_ignore_rules)
         .require_git(false)
-        .filter_entry(|entry| {
+        .filter_entry(move |entry| {
             if entry.depth() == 0 {
                 return true;
             }
             if entry.file_type().is_some_and(|ft| ft.is_dir()) {
                 let name = entry.file_name().to_string_lossy();
+                if include_ignored {
+                    return name != ".git";
+                }
                 return !should_skip_dir(&name);
             }
             true
         })
-        .build();
+        .build()
+}
 
-    for entry in walker {
+fn list_workspace_files_inner(
+    root: &PathBuf,
+    max_files: usize,
+    include_ignored: bool,
+) -> Vec<String> {
+    let mut results = Vec::new();
+    for entry in workspace_walker(root, include_ignored) {
         let entry = match entry {
             Ok(entry) => entry,
             Err(_) => continue,
@@ -1310,6 +2402,54 @@ fn list_workspace_files_inner(root: &PathBuf, max_files: usize) -> Vec<String> {
     results
 }
 
+fn list_workspace_files_detailed_inner(
+    root: &PathBuf,
+    max_files: usize,
+    include_ignored: bool,
+) -> Vec<WorkspaceFileEntry> {
+    let mut results = Vec::new();
+    for entry in workspace_walker(root, include_ignored) {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(_) => continue,
+        };
+        let Some(file_type) = entry.file_type() else {
+            continue;
+        };
+        if !file_type.is_file() && !file_type.is_symlink() {
+            continue;
+        }
+        let Ok(rel_path) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        let normalized = normalize_git_path(&rel_path.to_string_lossy());
+        if normalized.is_empty() {
+            continue;
+        }
+        // Link metadata, so circular links are never followed.
+        let Ok(metadata) = std::fs::symlink_metadata(entry.path()) else {
+            continue;
+        };
+        let modified_at_ms = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|duration| duration.as_millis() as i64);
+        results.push(WorkspaceFileEntry {
+            path: normalized,
+            size: metadata.len(),
+            modified_at_ms,
+            is_symlink: file_type.is_symlink(),
+        });
+        if results.len() >= max_files {
+            break;
+        }
+    }
+
+    results.sort_by(|a, b| a.path.cmp(&b.path));
+    results
+}
+
 const MAX_WORKSPACE_FILE_BYTES: u64 = 400_000;
 
 fn read_workspace_file_inner(
@@ -1521,6 +2661,7 @@ mod tests {
             storage_path: data_dir.join("workspaces.json"),
             settings_path: data_dir.join("settings.json"),
             app_settings: Mutex::new(AppSettings::default()),
+            operations: OperationRegistry::new(DaemonEventSink { tx: tx.clone() }),
             event_sink: DaemonEventSink { tx },
             codex_login_cancels: Mutex::new(HashMap::new()),
             daemon_mode: "tcp".to_string(),
@@ -1611,6 +2752,66 @@ mod tests {
         });
     }
 
+    #[test]
+    fn rpc_prompts_duplicate_copies_next_to_the_original() {
+        run_async_test(async {
+            let tmp = make_temp_dir("rpc-prompts-duplicate");
+            let workspace_id = "ws-prompts";
+            let workspace_dir = tmp.join("workspace");
+            std::fs::create_dir_all(&workspace_dir).expect("create workspace dir");
+
+            let state = test_state(&tmp);
+            insert_workspace(&state, workspace_id, &workspace_dir.to_string_lossy()).await;
+
+            let folder = tmp
+                .join("workspaces")
+                .join(workspace_id)
+                .join("prompts")
+                .join("review");
+            std::fs::create_dir_all(&folder).expect("create prompts folder");
+            let original = folder.join("pr.md");
+            std::fs::write(&original, "Review {{branch}}").expect("write prompt");
+            std::fs::write(folder.join("copy-of-pr.md"), "Older copy").expect("write copy");
+
+            let result = rpc::handle_rpc_request(
+                &state,
+                "prompts_duplicate",
+                json!({ "workspaceId": workspace_id, "path": original.to_string_lossy() }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect("prompts_duplicate should succeed");
+
+            assert_eq!(result["name"], "copy-of-pr-2");
+            assert_eq!(result["scope"], "workspace");
+            assert_eq!(result["folder"], "review");
+            assert_eq!(result["content"], "Review {{branch}}");
+            assert_eq!(result["variables"], json!(["branch"]));
+            assert!(folder.join("copy-of-pr-2.md").exists());
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
+
+    #[test]
+    fn rpc_list_operations_is_empty_when_idle() {
+        run_async_test(async {
+            let tmp = make_temp_dir("rpc-list-operations");
+            let state = test_state(&tmp);
+
+            let result = rpc::handle_rpc_request(
+                &state,
+                "list_operations",
+                json!({}),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect("list_operations should succeed");
+
+            assert_eq!(result, json!([]));
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
+
     #[test]
     fn rpc_local_usage_snapshot_returns_snapshot_shape() {
         run_async_test(async {
@@ -1682,6 +2883,45 @@ fn main() {
         };
         let state = Arc::new(DaemonState::load(&config, event_sink));
         let config = Arc::new(config);
+        {
+            let event_sink = state.event_sink.clone();
+            tokio::spawn(network_core::run_network_monitor(event_sink));
+        }
+        {
+            let state = Arc::clone(&state);
+            tokio::spawn(async move {
+                auto_fetch_core::run_auto_fetch_scheduler(
+                    &state.workspaces,
+                    &state.sessions,
+                    &state.storage_path,
+                    state.event_sink.clone(),
+                )
+                .await;
+            });
+        }
+        {
+            let state = Arc::clone(&state);
+            tokio::spawn(async move {
+                let client_version = format!("daemon-{}", env!("CARGO_PKG_VERSION"));
+                session_recovery_core::run_session_supervisor(
+                    &state.workspaces,
+                    &state.sessions,
+                    &state.app_settings,
+                    state.event_sink.clone(),
+                    |entry, default_bin, codex_args, codex_home| {
+                        spawn_with_client(
+                            state.event_sink.clone(),
+                            client_version.clone(),
+                            entry,
+                            default_bin,
+                            codex_args,
+                            codex_home,
+                        )
+                    },
+                )
+                .await;
+            });
+        }
 
         if config.orbit_url.is_some() {
             eprintln!(
@@ -49,6 +49,38 @@ fn build_event_notification(event: DaemonEvent) -> Option<String> {
             "method": "terminal-exit",
             "params": payload,
         }),
+        DaemonEvent::Operation(payload) => json!({
+            "method": "operation-event",
+            "params": payload,
+        }),
+        DaemonEvent::RemoteUpdates(payload) => json!({
+            "method": "remote-updates-available",
+            "params": payload,
+        }),
+        DaemonEvent::NetworkStatus(payload) => json!({
+            "method": "network-status-changed",
+            "params": payload,
+        }),
+        DaemonEvent::CloneProgress(payload) => json!({
+            "method": "clone-progress",
+            "params": payload,
+        }),
+        DaemonEvent::GitSyncProgress(payload) => json!({
+            "method": "git-sync-progress",
+            "params": payload,
+        }),
+        DaemonEvent::ProfileLogin(payload) => json!({
+            "method": "profile-login",
+            "params": payload,
+        }),
+        DaemonEvent::MessageQueued(payload) => json!({
+            "method": "message-queued",
+            "params": payload,
+        }),
+        DaemonEvent::SessionCrashed(payload) => json!({
+            "method": "session-crashed",
+            "params": payload,
+        }),
     };
     serde_json::to_string(&payload).ok()
 }
@@ -49,6 +49,231 @@ pub(super) async fn try_handle(
             };
             Some(state.fork_thread(workspace_id, thread_id).await)
         }
+        "replay_thread" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let source_thread_id = match parse_string(params, "sourceThreadId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let model = match parse_string(params, "model") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let operation_id = parse_optional_string(params, "operationId");
+            let result = match state
+                .replay_thread(workspace_id, source_thread_id, model, operation_id)
+                .await
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(result).map_err(|err| err.to_string()))
+        }
+        "diff_threads" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let thread_a = match parse_string(params, "threadA") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let thread_b = match parse_string(params, "threadB") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let diff = match state.diff_threads(workspace_id, thread_a, thread_b).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(diff).map_err(|err| err.to_string()))
+        }
+        "get_thread_window" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let thread_id = match parse_string(params, "threadId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let around_message_id = match parse_string(params, "aroundMessageId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let radius = parse_optional_u32(params, "radius");
+            let window = match state
+                .get_thread_window(workspace_id, thread_id, around_message_id, radius)
+                .await
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(window).map_err(|err| err.to_string()))
+        }
+        "export_thread" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let thread_id = match parse_string(params, "threadId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let format = parse_optional_string(params, "format");
+            let destination = parse_optional_string(params, "destination");
+            let export = match state
+                .export_thread(workspace_id, thread_id, format, destination)
+                .await
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(export).map_err(|err| err.to_string()))
+        }
+        "search_threads" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let query = match parse_string(params, "query") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let include_archived = parse_optional_bool(params, "includeArchived").unwrap_or(false);
+            let max_message_bytes =
+                parse_optional_u32(params, "maxMessageBytes").map(|value| value as usize);
+            let operation_id = parse_optional_string(params, "operationId");
+            let results = match state
+                .search_threads(
+                    workspace_id,
+                    query,
+                    include_archived,
+                    max_message_bytes,
+                    operation_id,
+                )
+                .await
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(results).map_err(|err| err.to_string()))
+        }
+        "estimate_message_tokens" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let thread_id = match parse_string(params, "threadId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let message = match parse_string(params, "message") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let attachments =
+                parse_optional_string_array(params, "attachments").unwrap_or_default();
+            let model = parse_optional_string(params, "model");
+            let estimate = match state
+                .estimate_message_tokens(workspace_id, thread_id, message, attachments, model)
+                .await
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(estimate).map_err(|err| err.to_string()))
+        }
+        "thread_usage" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let thread_id = match parse_string(params, "threadId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let usage = match state.thread_usage(workspace_id, thread_id).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(usage).map_err(|err| err.to_string()))
+        }
+        "capture_turn_trace" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let turn_id = match parse_string(params, "turnId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let capture = match state.capture_turn_trace(workspace_id, turn_id).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(capture).map_err(|err| err.to_string()))
+        }
+        "load_turn_trace" => {
+            let path = match parse_string(params, "path") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let workspace_id = parse_optional_string(params, "workspaceId");
+            let replay = match state.load_turn_trace(path, workspace_id).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(replay).map_err(|err| err.to_string()))
+        }
+        "pin_thread_message" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let thread_id = match parse_string(params, "threadId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let message_id = match parse_string(params, "messageId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let pins = match state.pin_thread_message(workspace_id, thread_id, message_id) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(pins).map_err(|err| err.to_string()))
+        }
+        "unpin_thread_message" => {
+            let thread_id = match parse_string(params, "threadId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let message_id = match parse_string(params, "messageId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let pins = match state.unpin_thread_message(thread_id, message_id) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(pins).map_err(|err| err.to_string()))
+        }
+        "list_pinned_messages" => {
+            let thread_id = match parse_string(params, "threadId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let pins = match state.list_pinned_messages(thread_id) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(pins).map_err(|err| err.to_string()))
+        }
         "list_threads" => {
             let workspace_id = match parse_string(params, "workspaceId") {
                 Ok(value) => value,
@@ -113,6 +338,25 @@ pub(super) async fn try_handle(
             };
             Some(state.set_thread_name(workspace_id, thread_id, name).await)
         }
+        "rename_thread" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let thread_id = match parse_string(params, "threadId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let title = match parse_string(params, "title") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let title = match state.rename_thread(workspace_id, thread_id, title) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(title).map_err(|err| err.to_string()))
+        }
         "send_user_message" => {
             let workspace_id = match parse_string(params, "workspaceId") {
                 Ok(value) => value,
@@ -133,6 +377,7 @@ pub(super) async fn try_handle(
             let app_mentions = parse_optional_value(params, "appMentions")
                 .and_then(|value| value.as_array().cloned());
             let collaboration_mode = parse_optional_value(params, "collaborationMode");
+            let queue = parse_optional_bool(params, "queue").unwrap_or(false);
             Some(
                 state
                     .send_user_message(
@@ -145,10 +390,22 @@ pub(super) async fn try_handle(
                         images,
                         app_mentions,
                         collaboration_mode,
+                        queue,
                     )
                     .await,
             )
         }
+        "clear_message_queue" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let cleared = match state.clear_message_queue(workspace_id).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(cleared).map_err(|err| err.to_string()))
+        }
         "turn_interrupt" => {
             let workspace_id = match parse_string(params, "workspaceId") {
                 Ok(value) => value,
@@ -164,6 +421,10 @@ pub(super) async fn try_handle(
             };
             Some(state.turn_interrupt(workspace_id, thread_id, turn_id).await)
         }
+        "interrupt_all_turns" => {
+            let results = state.interrupt_all_turns().await;
+            Some(serde_json::to_value(results).map_err(|err| err.to_string()))
+        }
         "turn_steer" => {
             let workspace_id = match parse_string(params, "workspaceId") {
                 Ok(value) => value,
@@ -220,7 +481,8 @@ pub(super) async fn try_handle(
                 Ok(value) => value,
                 Err(err) => return Some(Err(err)),
             };
-            Some(state.model_list(workspace_id).await)
+            let refresh = parse_optional_bool(params, "refresh").unwrap_or(false);
+            Some(state.model_list(workspace_id, refresh).await)
         }
         "experimental_feature_list" => {
             let workspace_id = match parse_string(params, "workspaceId") {
@@ -268,6 +530,17 @@ pub(super) async fn try_handle(
             };
             Some(state.account_read(workspace_id).await)
         }
+        "get_session_account" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let account = match state.get_session_account(workspace_id).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(account).map_err(|err| err.to_string()))
+        }
         "codex_login" => {
             let workspace_id = match parse_string(params, "workspaceId") {
                 Ok(value) => value,
@@ -287,7 +560,8 @@ pub(super) async fn try_handle(
                 Ok(value) => value,
                 Err(err) => return Some(Err(err)),
             };
-            Some(state.skills_list(workspace_id).await)
+            let refresh = parse_optional_bool(params, "refresh").unwrap_or(false);
+            Some(state.skills_list(workspace_id, refresh).await)
         }
         "apps_list" => {
             let workspace_id = match parse_string(params, "workspaceId") {
@@ -325,9 +599,10 @@ pub(super) async fn try_handle(
                 Ok(value) => value,
                 Err(err) => return Some(Err(err.to_string())),
             };
+            let rule_command = parse_optional_string_array(params, "ruleCommand");
             Some(
                 state
-                    .respond_to_server_request(workspace_id, request_id, result)
+                    .respond_to_server_request(workspace_id, request_id, result, rule_command)
                     .await,
             )
         }
@@ -340,13 +615,113 @@ pub(super) async fn try_handle(
                 Ok(value) => value,
                 Err(err) => return Some(Err(err)),
             };
-            Some(state.remember_approval_rule(workspace_id, command).await)
+            let kind = parse_optional_string(params, "kind");
+            let pattern = parse_optional_string(params, "pattern");
+            Some(
+                state
+                    .remember_approval_rule(workspace_id, command, kind, pattern)
+                    .await,
+            )
+        }
+        "test_approval_rule" => {
+            let pattern = match parse_string(params, "pattern") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let command = match parse_string_array(params, "command") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let kind = parse_optional_string(params, "kind");
+            let test = match state.test_approval_rule(pattern, command, kind) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(test).map_err(|err| err.to_string()))
+        }
+        "list_approval_rules" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let rules = match state.list_approval_rules(workspace_id).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(rules).map_err(|err| err.to_string()))
+        }
+        "delete_approval_rule" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let id = match parse_string(params, "id") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .delete_approval_rule(workspace_id, id)
+                    .await
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
+        "export_approval_rules" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let export = match state.export_approval_rules(workspace_id).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(export).map_err(|err| err.to_string()))
+        }
+        "import_approval_rules" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let data = match parse_string(params, "data") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let imported = match state.import_approval_rules(workspace_id, data).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(imported).map_err(|err| err.to_string()))
         }
         "codex_doctor" => {
             let codex_bin = parse_optional_string(params, "codexBin");
             let codex_args = parse_optional_string(params, "codexArgs");
             Some(state.codex_doctor(codex_bin, codex_args).await)
         }
+        "get_session_startup_stats" => {
+            let stats = state.get_session_startup_stats();
+            Some(serde_json::to_value(stats).map_err(|err| err.to_string()))
+        }
+        "find_orphaned_codex_processes" => {
+            let orphans = match state.find_orphaned_codex_processes().await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(orphans).map_err(|err| err.to_string()))
+        }
+        "kill_orphaned_codex_processes" => {
+            let pids: Vec<u32> = match parse_optional_value(params, "pids")
+                .map(serde_json::from_value)
+                .transpose()
+            {
+                Ok(value) => value.unwrap_or_default(),
+                Err(err) => return Some(Err(err.to_string())),
+            };
+            let killed = match state.kill_orphaned_codex_processes(pids).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(killed).map_err(|err| err.to_string()))
+        }
         "generate_run_metadata" => {
             let workspace_id = match parse_string(params, "workspaceId") {
                 Ok(value) => value,
@@ -35,6 +35,22 @@ pub(super) async fn try_handle(
                     .map(|_| json!({ "ok": true })),
             )
         }
+        "cancel_operation" => {
+            let operation_id = match parse_string(params, "operationId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(Ok(Value::Bool(state.cancel_operation(operation_id))))
+        }
+        "get_system_inventory" => Some(
+            serde_json::to_value(state.get_system_inventory().await).map_err(|err| err.to_string()),
+        ),
+        "list_operations" => {
+            Some(serde_json::to_value(state.list_operations()).map_err(|err| err.to_string()))
+        }
+        "get_network_status" => {
+            Some(serde_json::to_value(state.get_network_status()).map_err(|err| err.to_string()))
+        }
         "is_macos_debug_build" => {
             let is_debug = state.is_macos_debug_build().await;
             Some(Ok(Value::Bool(is_debug)))
@@ -11,7 +11,12 @@ pub(super) async fn try_handle(
                 Ok(value) => value,
                 Err(err) => return Some(Err(err)),
             };
-            Some(state.get_git_status(workspace_id).await)
+            let priority = parse_optional_string(params, "priority");
+            let priority = match parse_operation_priority(priority.as_deref()) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(state.get_git_status(workspace_id, priority).await)
         }
         "init_git_repo" => {
             let workspace_id = match parse_string(params, "workspaceId") {
@@ -62,7 +67,41 @@ pub(super) async fn try_handle(
                 Ok(value) => value,
                 Err(err) => return Some(Err(err)),
             };
-            let diffs = match state.get_git_diffs(workspace_id).await {
+            let priority = parse_optional_string(params, "priority");
+            let priority = match parse_operation_priority(priority.as_deref()) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let context_lines =
+                parse_optional_u32(params, "contextLines").map(|value| value as usize);
+            let diffs = match state
+                .get_git_diffs(workspace_id, priority, context_lines)
+                .await
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(diffs).map_err(|err| err.to_string()))
+        }
+        "get_git_diff_range" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let base = match parse_string(params, "base") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let head = match parse_string(params, "head") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let paths = parse_optional_string_array(params, "paths").unwrap_or_default();
+            let two_dot = parse_optional_bool(params, "twoDot").unwrap_or(false);
+            let diffs = match state
+                .get_git_diff_range(workspace_id, base, head, paths, two_dot)
+                .await
+            {
                 Ok(value) => value,
                 Err(err) => return Some(Err(err)),
             };
@@ -73,13 +112,153 @@ pub(super) async fn try_handle(
                 Ok(value) => value,
                 Err(err) => return Some(Err(err)),
             };
+            let skip = parse_optional_u32(params, "skip").map(|value| value as usize);
             let limit = parse_optional_u32(params, "limit").map(|value| value as usize);
-            let log = match state.get_git_log(workspace_id, limit).await {
+            let path = parse_optional_string(params, "path");
+            let filter = match parse_optional_value(params, "filter")
+                .filter(|value| !value.is_null())
+                .map(serde_json::from_value)
+                .transpose()
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err.to_string())),
+            };
+            let log = match state
+                .get_git_log(workspace_id, skip, limit, path, filter)
+                .await
+            {
                 Ok(value) => value,
                 Err(err) => return Some(Err(err)),
             };
             Some(serde_json::to_value(log).map_err(|err| err.to_string()))
         }
+        "get_git_graph" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let max_count = parse_optional_u32(params, "maxCount").map(|value| value as usize);
+            let refs = parse_optional_string_array(params, "refs");
+            let graph = match state.get_git_graph(workspace_id, max_count, refs).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(graph).map_err(|err| err.to_string()))
+        }
+        "get_file_history" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let path = match parse_string(params, "path") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let follow_renames = parse_optional_bool(params, "followRenames").unwrap_or(true);
+            let max_count = parse_optional_u32(params, "maxCount").map(|value| value as usize);
+            let history = match state
+                .get_file_history(workspace_id, path, follow_renames, max_count)
+                .await
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(history).map_err(|err| err.to_string()))
+        }
+        "git_blame" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let path = match parse_string(params, "path") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let rev = parse_optional_string(params, "rev");
+            let blame = match state.git_blame(workspace_id, path, rev).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(blame).map_err(|err| err.to_string()))
+        }
+        "get_repo_stats" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let stats = match state.get_repo_stats(workspace_id).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(stats).map_err(|err| err.to_string()))
+        }
+        "audit_workspace" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let audit = match state.audit_workspace(workspace_id).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(audit).map_err(|err| err.to_string()))
+        }
+        "git_bisect_start" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let bad = match parse_string(params, "bad") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let good = match parse_string(params, "good") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let bisect = match state.git_bisect_start(workspace_id, bad, good).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(bisect).map_err(|err| err.to_string()))
+        }
+        "git_bisect_mark" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let verdict = match parse_string(params, "verdict") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let bisect = match state.git_bisect_mark(workspace_id, verdict).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(bisect).map_err(|err| err.to_string()))
+        }
+        "git_bisect_reset" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let bisect = match state.git_bisect_reset(workspace_id).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(bisect).map_err(|err| err.to_string()))
+        }
+        "get_git_bisect_state" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let bisect = match state.get_git_bisect_state(workspace_id).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(bisect).map_err(|err| err.to_string()))
+        }
         "get_git_commit_diff" => {
             let workspace_id = match parse_string(params, "workspaceId") {
                 Ok(value) => value,
@@ -106,6 +285,17 @@ pub(super) async fn try_handle(
             };
             Some(serde_json::to_value(remote).map_err(|err| err.to_string()))
         }
+        "list_git_remotes" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let remotes = match state.list_git_remotes(workspace_id).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(remotes).map_err(|err| err.to_string()))
+        }
         "stage_git_file" => {
             let workspace_id = match parse_string(params, "workspaceId") {
                 Ok(value) => value,
@@ -122,6 +312,30 @@ pub(super) async fn try_handle(
                     .map(|_| json!({ "ok": true })),
             )
         }
+        "stage_git_hunk" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let path = match parse_string(params, "path") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let hunk_header = match parse_string(params, "hunkHeader") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let patch = match parse_string(params, "patch") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .stage_git_hunk(workspace_id, path, hunk_header, patch)
+                    .await
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
         "stage_git_all" => {
             let workspace_id = match parse_string(params, "workspaceId") {
                 Ok(value) => value,
@@ -150,6 +364,41 @@ pub(super) async fn try_handle(
                     .map(|_| json!({ "ok": true })),
             )
         }
+        "unstage_git_hunk" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let path = match parse_string(params, "path") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let hunk_header = match parse_string(params, "hunkHeader") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let patch = match parse_string(params, "patch") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .unstage_git_hunk(workspace_id, path, hunk_header, patch)
+                    .await
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
+        "unstage_git_all" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let count = match state.unstage_git_all(workspace_id).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(count).map_err(|err| err.to_string()))
+        }
         "revert_git_file" => {
             let workspace_id = match parse_string(params, "workspaceId") {
                 Ok(value) => value,
@@ -178,6 +427,23 @@ pub(super) async fn try_handle(
                     .map(|_| json!({ "ok": true })),
             )
         }
+        "git_reset" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let mode = match parse_string(params, "mode") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let target = parse_optional_string(params, "target");
+            let confirm = parse_optional_bool(params, "confirm").unwrap_or(false);
+            let result = match state.git_reset(workspace_id, target, mode, confirm).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(result).map_err(|err| err.to_string()))
+        }
         "commit_git" => {
             let workspace_id = match parse_string(params, "workspaceId") {
                 Ok(value) => value,
@@ -194,6 +460,21 @@ pub(super) async fn try_handle(
                     .map(|_| json!({ "ok": true })),
             )
         }
+        "git_commit" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let message = parse_optional_string(params, "message").unwrap_or_default();
+            let amend = parse_optional_bool(params, "amend").unwrap_or(false);
+            let signoff = parse_optional_bool(params, "signoff").unwrap_or(false);
+            Some(
+                state
+                    .git_commit(workspace_id, message, amend, signoff)
+                    .await
+                    .map(Value::String),
+            )
+        }
         "push_git" => {
             let workspace_id = match parse_string(params, "workspaceId") {
                 Ok(value) => value,
@@ -211,9 +492,136 @@ pub(super) async fn try_handle(
                 Ok(value) => value,
                 Err(err) => return Some(Err(err)),
             };
+            let operation_id = parse_optional_string(params, "operationId");
+            let priority = parse_optional_string(params, "priority");
+            let priority = match parse_operation_priority(priority.as_deref()) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .pull_git(workspace_id, operation_id, priority)
+                    .await
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
+        "git_push" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let set_upstream = parse_optional_bool(params, "setUpstream").unwrap_or(false);
+            let priority = parse_optional_string(params, "priority");
+            let priority = match parse_operation_priority(priority.as_deref()) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let result = match state.git_push(workspace_id, set_upstream, priority).await {
+                Ok(result) => result,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(result).map_err(|err| err.to_string()))
+        }
+        "git_pull" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let rebase = parse_optional_bool(params, "rebase").unwrap_or(false);
+            let priority = parse_optional_string(params, "priority");
+            let priority = match parse_operation_priority(priority.as_deref()) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let result = match state.git_pull(workspace_id, rebase, priority).await {
+                Ok(result) => result,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(result).map_err(|err| err.to_string()))
+        }
+        "git_fetch" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let remote = parse_optional_string(params, "remote");
+            let prune = parse_optional_bool(params, "prune").unwrap_or(false);
+            let priority = parse_optional_string(params, "priority");
+            let priority = match parse_operation_priority(priority.as_deref()) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let refs = match state.git_fetch(workspace_id, remote, prune, priority).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(refs).map_err(|err| err.to_string()))
+        }
+        "git_stash_list" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let stashes = match state.git_stash_list(workspace_id).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(stashes).map_err(|err| err.to_string()))
+        }
+        "git_stash_push" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let message = parse_optional_string(params, "message");
+            let include_untracked =
+                parse_optional_bool(params, "includeUntracked").unwrap_or(false);
+            Some(
+                state
+                    .git_stash_push(workspace_id, message, include_untracked)
+                    .await
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
+        "git_stash_apply" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let Some(index) = parse_optional_u32(params, "index") else {
+                return Some(Err("missing or invalid `index`".to_string()));
+            };
+            let result = match state.git_stash_apply(workspace_id, index as usize).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(result).map_err(|err| err.to_string()))
+        }
+        "git_stash_pop" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let Some(index) = parse_optional_u32(params, "index") else {
+                return Some(Err("missing or invalid `index`".to_string()));
+            };
+            let result = match state.git_stash_pop(workspace_id, index as usize).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(result).map_err(|err| err.to_string()))
+        }
+        "git_stash_drop" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let Some(index) = parse_optional_u32(params, "index") else {
+                return Some(Err("missing or invalid `index`".to_string()));
+            };
             Some(
                 state
-                    .pull_git(workspace_id)
+                    .git_stash_drop(workspace_id, index as usize)
                     .await
                     .map(|_| json!({ "ok": true })),
             )
@@ -223,9 +631,15 @@ pub(super) async fn try_handle(
                 Ok(value) => value,
                 Err(err) => return Some(Err(err)),
             };
+            let operation_id = parse_optional_string(params, "operationId");
+            let priority = parse_optional_string(params, "priority");
+            let priority = match parse_operation_priority(priority.as_deref()) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
             Some(
                 state
-                    .fetch_git(workspace_id)
+                    .fetch_git(workspace_id, operation_id, priority)
                     .await
                     .map(|_| json!({ "ok": true })),
             )
@@ -242,6 +656,29 @@ pub(super) async fn try_handle(
                     .map(|_| json!({ "ok": true })),
             )
         }
+        "check_remote_updates" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let updates = match state.check_remote_updates(workspace_id).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(updates).map_err(|err| err.to_string()))
+        }
+        "estimate_operation_time" => {
+            let kind = match parse_string(params, "kind") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let repo_size_hint = super::super::parse_optional_u64(params, "repoSizeHint");
+            let estimate = match state.estimate_operation_time(kind, repo_size_hint).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(estimate).map_err(|err| err.to_string()))
+        }
         "get_github_issues" => {
             let workspace_id = match parse_string(params, "workspaceId") {
                 Ok(value) => value,
@@ -304,6 +741,26 @@ pub(super) async fn try_handle(
             };
             Some(serde_json::to_value(comments).map_err(|err| err.to_string()))
         }
+        "get_github_pull_request_checks" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let pr_number = match super::super::parse_optional_u64(params, "prNumber")
+                .ok_or("missing or invalid `prNumber`")
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err.to_string())),
+            };
+            let checks = match state
+                .get_github_pull_request_checks(workspace_id, pr_number)
+                .await
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(checks).map_err(|err| err.to_string()))
+        }
         "checkout_github_pull_request" => {
             let workspace_id = match parse_string(params, "workspaceId") {
                 Ok(value) => value,
@@ -322,6 +779,91 @@ pub(super) async fn try_handle(
                     .map(|_| json!({ "ok": true })),
             )
         }
+        "submit_github_pull_request_review" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let pr_number = match super::super::parse_optional_u64(params, "prNumber")
+                .ok_or("missing or invalid `prNumber`")
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err.to_string())),
+            };
+            let event = match parse_string(params, "event") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let body = parse_optional_string(params, "body");
+            let comments = match parse_optional_value(params, "comments")
+                .filter(|value| !value.is_null())
+                .map(serde_json::from_value)
+                .transpose()
+            {
+                Ok(value) => value.unwrap_or_default(),
+                Err(err) => return Some(Err(err.to_string())),
+            };
+            let review_id = match state
+                .submit_github_pull_request_review(workspace_id, pr_number, event, body, comments)
+                .await
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(review_id).map_err(|err| err.to_string()))
+        }
+        "create_github_pull_request" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let pull_request =
+                match parse_optional_value(params, "pullRequest").map(serde_json::from_value) {
+                    Some(Ok(value)) => value,
+                    Some(Err(err)) => return Some(Err(err.to_string())),
+                    None => return Some(Err("missing `pullRequest`".to_string())),
+                };
+            let created = match state
+                .create_github_pull_request(workspace_id, pull_request)
+                .await
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(created).map_err(|err| err.to_string()))
+        }
+        "merge_github_pull_request" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let pr_number = match super::super::parse_optional_u64(params, "prNumber")
+                .ok_or("missing or invalid `prNumber`")
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err.to_string())),
+            };
+            let method = match parse_string(params, "method") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let commit_title = parse_optional_string(params, "commitTitle");
+            let commit_message = parse_optional_string(params, "commitMessage");
+            let result = match state
+                .merge_github_pull_request(
+                    workspace_id,
+                    pr_number,
+                    method,
+                    commit_title,
+                    commit_message,
+                )
+                .await
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(result).map_err(|err| err.to_string()))
+        }
         "list_git_branches" => {
             let workspace_id = match parse_string(params, "workspaceId") {
                 Ok(value) => value,
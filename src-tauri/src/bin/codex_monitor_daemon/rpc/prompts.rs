@@ -48,6 +48,7 @@ pub(super) async fn try_handle(
                 Ok(value) => value,
                 Err(err) => return Some(Err(err)),
             };
+            let folder = parse_optional_string(params, "folder");
             let name = match parse_string(params, "name") {
                 Ok(value) => value,
                 Err(err) => return Some(Err(err)),
@@ -62,6 +63,7 @@ pub(super) async fn try_handle(
                 .prompts_create(
                     workspace_id,
                     scope,
+                    folder,
                     name,
                     description,
                     argument_hint,
@@ -138,12 +140,52 @@ pub(super) async fn try_handle(
                 Ok(value) => value,
                 Err(err) => return Some(Err(err)),
             };
-            let prompt = match state.prompts_move(workspace_id, path, scope).await {
+            let folder = parse_optional_string(params, "folder");
+            let prompt = match state.prompts_move(workspace_id, path, scope, folder).await {
                 Ok(value) => value,
                 Err(err) => return Some(Err(err)),
             };
             Some(serde_json::to_value(prompt).map_err(|err| err.to_string()))
         }
+        "prompts_duplicate" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let path = match parse_string(params, "path") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let prompt = match state.prompts_duplicate(workspace_id, path).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(prompt).map_err(|err| err.to_string()))
+        }
+        "prompts_render" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let path = match parse_string(params, "path") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let vars: HashMap<String, String> = match parse_optional_value(params, "vars")
+                .map(serde_json::from_value)
+                .transpose()
+            {
+                Ok(value) => value.unwrap_or_default(),
+                Err(err) => return Some(Err(err.to_string())),
+            };
+            let leave_unfilled = parse_optional_bool(params, "leaveUnfilled").unwrap_or(false);
+            Some(
+                state
+                    .prompts_render(workspace_id, path, vars, leave_unfilled)
+                    .await
+                    .map(Value::String),
+            )
+        }
         _ => None,
     }
 }
@@ -70,12 +70,14 @@ pub(super) async fn try_handle(
             };
             let name = parse_optional_string(params, "name");
             let copy_agents_md = parse_optional_bool(params, "copyAgentsMd").unwrap_or(true);
+            let base = parse_optional_string(params, "base");
             let workspace = match state
                 .add_worktree(
                     parent_id,
                     branch,
                     name,
                     copy_agents_md,
+                    base,
                     client_version.to_string(),
                 )
                 .await
@@ -120,6 +122,16 @@ pub(super) async fn try_handle(
                     .map(|_| json!({ "ok": true })),
             )
         }
+        "prewarm_sessions" => {
+            let workspace_ids = match parse_string_array(params, "workspaceIds") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let warmed = state
+                .prewarm_sessions(workspace_ids, client_version.to_string())
+                .await;
+            Some(Ok(json!({ "warmed": warmed })))
+        }
         "remove_workspace" => {
             let id = match parse_string(params, "id") {
                 Ok(value) => value,
@@ -144,6 +156,17 @@ pub(super) async fn try_handle(
                     .map(|_| json!({ "ok": true })),
             )
         }
+        "remove_workspaces" => {
+            let ids = match parse_string_array(params, "ids") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let results = match state.remove_workspaces(ids).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(results).map_err(|err| err.to_string()))
+        }
         "rename_worktree" => {
             let id = match parse_string(params, "id") {
                 Ok(value) => value,
@@ -216,12 +239,182 @@ pub(super) async fn try_handle(
             };
             Some(serde_json::to_value(workspace).map_err(|err| err.to_string()))
         }
+        "check_workspace_codex_bins" => {
+            let stale = state.check_workspace_codex_bins().await;
+            Some(serde_json::to_value(stale).map_err(|err| err.to_string()))
+        }
+        "repoint_codex_bin" => {
+            let id = match parse_string(params, "id") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let workspace = match state.repoint_codex_bin(id).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(workspace).map_err(|err| err.to_string()))
+        }
+        "set_workspace_color" => {
+            let id = match parse_string(params, "id") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let color = parse_optional_string(params, "color");
+            let workspace = match state.set_workspace_color(id, color).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(workspace).map_err(|err| err.to_string()))
+        }
+        "set_workspace_profile" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let profile_id = parse_optional_string(params, "profileId");
+            let workspace = match state
+                .set_workspace_profile(workspace_id, profile_id, client_version.to_string())
+                .await
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(workspace).map_err(|err| err.to_string()))
+        }
+        "rename_workspace" => {
+            let id = match parse_string(params, "id") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let name = match parse_string(params, "name") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let workspace = match state.rename_workspace(id, name).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(workspace).map_err(|err| err.to_string()))
+        }
+        "relocate_workspace" => {
+            let id = match parse_string(params, "id") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let new_path = match parse_string(params, "newPath") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let relocation = match state
+                .relocate_workspace(id, new_path, client_version.to_string())
+                .await
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(relocation).map_err(|err| err.to_string()))
+        }
+        "export_workspace_bundle" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let destination = parse_optional_string(params, "destination");
+            let export = match state
+                .export_workspace_bundle(workspace_id, destination)
+                .await
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(export).map_err(|err| err.to_string()))
+        }
+        "import_workspace_bundle" => {
+            let path = match parse_string(params, "path") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let target_path = match parse_string(params, "targetPath") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let trusted = parse_optional_bool(params, "trusted").unwrap_or(false);
+            let import = match state
+                .import_workspace_bundle(path, target_path, trusted)
+                .await
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(import).map_err(|err| err.to_string()))
+        }
+        "list_workspace_groups" => {
+            let groups = state.list_workspace_groups().await;
+            Some(serde_json::to_value(groups).map_err(|err| err.to_string()))
+        }
+        "create_workspace_group" => {
+            let name = match parse_string(params, "name") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let group = match state.create_workspace_group(name).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(group).map_err(|err| err.to_string()))
+        }
+        "rename_workspace_group" => {
+            let id = match parse_string(params, "id") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let name = match parse_string(params, "name") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let group = match state.rename_workspace_group(id, name).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(group).map_err(|err| err.to_string()))
+        }
+        "delete_workspace_group" => {
+            let id = match parse_string(params, "id") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .delete_workspace_group(id)
+                    .await
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
         "list_workspace_files" => {
             let workspace_id = match parse_string(params, "workspaceId") {
                 Ok(value) => value,
                 Err(err) => return Some(Err(err)),
             };
-            let files = match state.list_workspace_files(workspace_id).await {
+            let include_ignored = parse_optional_bool(params, "includeIgnored").unwrap_or(false);
+            let files = match state
+                .list_workspace_files(workspace_id, include_ignored)
+                .await
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(files).map_err(|err| err.to_string()))
+        }
+        "list_workspace_files_detailed" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let include_ignored = parse_optional_bool(params, "includeIgnored").unwrap_or(false);
+            let files = match state
+                .list_workspace_files_detailed(workspace_id, include_ignored)
+                .await
+            {
                 Ok(value) => value,
                 Err(err) => return Some(Err(err)),
             };
@@ -278,6 +471,61 @@ pub(super) async fn try_handle(
             let settings = state.get_app_settings().await;
             Some(serde_json::to_value(settings).map_err(|err| err.to_string()))
         }
+        "list_profiles" => {
+            let profiles = state.list_profiles().await;
+            Some(serde_json::to_value(profiles).map_err(|err| err.to_string()))
+        }
+        "create_profile" => {
+            let name = match parse_string(params, "name") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let codex_home = match parse_string(params, "codexHome") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let profile = match state.create_profile(name, codex_home).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(profile).map_err(|err| err.to_string()))
+        }
+        "delete_profile" => {
+            let profile_id = match parse_string(params, "profileId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .delete_profile(profile_id)
+                    .await
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
+        "codex_profile_login" => {
+            let profile_id = match parse_string(params, "profileId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .codex_profile_login(profile_id)
+                    .await
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
+        "codex_profile_logout" => {
+            let profile_id = match parse_string(params, "profileId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .codex_profile_logout(profile_id)
+                    .await
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
         "update_app_settings" => {
             let settings_value = match params {
                 Value::Object(map) => map.get("settings").cloned().unwrap_or(Value::Null),
@@ -338,11 +586,13 @@ pub(super) async fn try_handle(
                 Ok(value) => value,
                 Err(err) => return Some(Err(err)),
             };
+            let operation_id = parse_optional_string(params, "operationId");
             let workspace = match state
                 .add_clone(
                     source_workspace_id,
                     copies_folder,
                     copy_name,
+                    operation_id,
                     client_version.to_string(),
                 )
                 .await
@@ -364,6 +614,41 @@ pub(super) async fn try_handle(
                     .map(|_| json!({ "ok": true })),
             )
         }
+        "apply_worktree_commits" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .apply_worktree_commits(workspace_id)
+                    .await
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
+        "move_worktree_changes_back" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .move_worktree_changes_back(workspace_id)
+                    .await
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
+        "preview_worktree_changes" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let preview = match state.preview_worktree_changes(workspace_id).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(preview).map_err(|err| err.to_string()))
+        }
         "open_workspace_in" => {
             let path = match parse_string(params, "path") {
                 Ok(value) => value,
@@ -0,0 +1,121 @@
+use crate::codex::env::resolve_workspace_env;
+use crate::types::WorkspaceEntry;
+
+/// Environment variable the Codex CLI reads for the OpenAI-compatible API
+/// endpoint of its built-in provider.
+pub(crate) const CODEX_BASE_URL_ENV: &str = "OPENAI_BASE_URL";
+
+/// Trims the value and drops trailing slashes; empty means "use the default".
+pub(crate) fn normalize_codex_base_url(value: Option<String>) -> Result<Option<String>, String> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    let trimmed = value.trim().trim_end_matches('/');
+    if trimmed.is_empty() {
+        return Ok(None);
+    }
+    let url = reqwest::Url::parse(trimmed)
+        .map_err(|err| format!("Invalid Codex base URL `{trimmed}`: {err}"))?;
+    if !matches!(url.scheme(), "https" | "http") {
+        return Err("codexBaseUrl must start with https:// or http://".to_string());
+    }
+    if url.host_str().map_or(true, str::is_empty) {
+        return Err(format!("Invalid Codex base URL `{trimmed}`: missing host"));
+    }
+    if url.query().is_some() || url.fragment().is_some() {
+        return Err("codexBaseUrl must not include a query or fragment.".to_string());
+    }
+    Ok(Some(trimmed.to_string()))
+}
+
+/// Worktrees fall back to their parent's endpoint, like `codexHome` and
+/// `codexArgs`. `None` leaves the Codex CLI on its own configuration.
+pub(crate) fn resolve_workspace_codex_base_url(
+    entry: &WorkspaceEntry,
+    parent_entry: Option<&WorkspaceEntry>,
+) -> Option<String> {
+    let own = entry.settings.codex_base_url.clone();
+    let inherited = entry
+        .kind
+        .is_worktree()
+        .then(|| parent_entry.and_then(|parent| parent.settings.codex_base_url.clone()))
+        .flatten();
+    [own, inherited]
+        .into_iter()
+        .find_map(|value| normalize_codex_base_url(value).ok().flatten())
+}
+
+/// The entry handed to the session spawner, with the inherited endpoint and
+/// environment filled in so the spawner does not need the parent.
+pub(crate) fn with_inherited_session_settings(
+    entry: &WorkspaceEntry,
+    parent_entry: Option<&WorkspaceEntry>,
+) -> WorkspaceEntry {
+    let mut entry = entry.clone();
+    entry.settings.codex_base_url = resolve_workspace_codex_base_url(&entry, parent_entry);
+    entry.settings.env = resolve_workspace_env(&entry, parent_entry);
+    entry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize_codex_base_url, resolve_workspace_codex_base_url};
+    use crate::types::{WorkspaceEntry, WorkspaceKind, WorkspaceSettings};
+
+    fn workspace(id: &str, kind: WorkspaceKind, base_url: Option<&str>) -> WorkspaceEntry {
+        WorkspaceEntry {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: format!("/tmp/{id}"),
+            codex_bin: None,
+            kind,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings {
+                codex_base_url: base_url.map(str::to_string),
+                ..WorkspaceSettings::default()
+            },
+        }
+    }
+
+    #[test]
+    fn normalizes_and_validates_base_urls() {
+        assert_eq!(
+            normalize_codex_base_url(Some(" https://codex.internal/v1/ ".to_string())),
+            Ok(Some("https://codex.internal/v1".to_string()))
+        );
+        assert_eq!(normalize_codex_base_url(Some("  ".to_string())), Ok(None));
+        assert!(normalize_codex_base_url(Some("ftp://codex.internal".to_string())).is_err());
+        assert!(normalize_codex_base_url(Some("codex.internal".to_string())).is_err());
+        assert!(
+            normalize_codex_base_url(Some("https://codex.internal/v1?x=1".to_string())).is_err()
+        );
+    }
+
+    #[test]
+    fn worktrees_inherit_the_parent_base_url() {
+        let parent = workspace(
+            "parent",
+            WorkspaceKind::Main,
+            Some("http://localhost:8080/v1"),
+        );
+        let child = workspace("child", WorkspaceKind::Worktree, None);
+        assert_eq!(
+            resolve_workspace_codex_base_url(&child, Some(&parent)).as_deref(),
+            Some("http://localhost:8080/v1")
+        );
+
+        let own = workspace(
+            "own",
+            WorkspaceKind::Worktree,
+            Some("https://api.example.com/v1"),
+        );
+        assert_eq!(
+            resolve_workspace_codex_base_url(&own, Some(&parent)).as_deref(),
+            Some("https://api.example.com/v1")
+        );
+
+        let main = workspace("main", WorkspaceKind::Main, None);
+        assert_eq!(resolve_workspace_codex_base_url(&main, Some(&parent)), None);
+    }
+}
@@ -0,0 +1,166 @@
+use crate::types::WorkspaceEntry;
+
+/// Set from the workspace's own settings; an `env` entry can't override them.
+const RESERVED_ENV_NAMES: &[&str] = &["CODEX_HOME"];
+
+fn is_valid_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first == '_' || first.is_ascii_alphabetic())
+        && chars.all(|ch| ch == '_' || ch.is_ascii_alphanumeric())
+}
+
+/// Trims names, drops rows left completely blank by the editor and rejects
+/// invalid, reserved or repeated names.
+pub(crate) fn normalize_workspace_env(
+    env: Vec<(String, String)>,
+) -> Result<Vec<(String, String)>, String> {
+    let mut normalized: Vec<(String, String)> = Vec::with_capacity(env.len());
+    for (name, value) in env {
+        let name = name.trim().to_string();
+        if name.is_empty() && value.trim().is_empty() {
+            continue;
+        }
+        if !is_valid_env_name(&name) {
+            return Err(format!("Invalid environment variable name `{name}`."));
+        }
+        if RESERVED_ENV_NAMES.contains(&name.as_str()) {
+            return Err(format!(
+                "Set {name} with the workspace's codexHome instead."
+            ));
+        }
+        if value.contains('\0') {
+            return Err(format!("{name} must not contain a NUL character."));
+        }
+        if normalized.iter().any(|(existing, _)| *existing == name) {
+            return Err(format!("{name} is set more than once."));
+        }
+        normalized.push((name, value));
+    }
+    Ok(normalized)
+}
+
+/// Worktrees use their parent's variables unless they set their own, like
+/// `codexHome` and `codexArgs`.
+pub(crate) fn resolve_workspace_env(
+    entry: &WorkspaceEntry,
+    parent_entry: Option<&WorkspaceEntry>,
+) -> Vec<(String, String)> {
+    if !entry.settings.env.is_empty() || !entry.kind.is_worktree() {
+        return entry.settings.env.clone();
+    }
+    parent_entry
+        .map(|parent| parent.settings.env.clone())
+        .unwrap_or_default()
+}
+
+/// Expands `$NAME` and `${NAME}` against the app's environment so a value
+/// like `/opt/tools/bin:$PATH` extends rather than replaces. Unset names
+/// expand to nothing; `$$` is a literal `$`.
+pub(crate) fn expand_env_value(value: &str, lookup: impl Fn(&str) -> Option<String>) -> String {
+    let mut expanded = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(index) = rest.find('$') {
+        expanded.push_str(&rest[..index]);
+        let after = &rest[index + 1..];
+        if let Some(after) = after.strip_prefix('$') {
+            expanded.push('$');
+            rest = after;
+            continue;
+        }
+        let (name, remaining) = if let Some(braced) = after.strip_prefix('{') {
+            match braced.find('}') {
+                Some(end) => (&braced[..end], &braced[end + 1..]),
+                None => ("", after),
+            }
+        } else {
+            let end = after
+                .find(|ch: char| ch != '_' && !ch.is_ascii_alphanumeric())
+                .unwrap_or(after.len());
+            (&after[..end], &after[end..])
+        };
+        if !is_valid_env_name(name) {
+            expanded.push('$');
+            rest = after;
+            continue;
+        }
+        expanded.push_str(&lookup(name).unwrap_or_default());
+        rest = remaining;
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{WorkspaceKind, WorkspaceSettings};
+
+    fn workspace(kind: WorkspaceKind, env: &[(&str, &str)]) -> WorkspaceEntry {
+        WorkspaceEntry {
+            id: "id".to_string(),
+            name: "name".to_string(),
+            path: "/tmp/workspace".to_string(),
+            codex_bin: None,
+            kind,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings {
+                env: env
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.to_string()))
+                    .collect(),
+                ..WorkspaceSettings::default()
+            },
+        }
+    }
+
+    #[test]
+    fn normalizes_and_validates_env() {
+        let env = normalize_workspace_env(vec![
+            (" HTTPS_PROXY ".to_string(), "http://proxy:8080".to_string()),
+            ("  ".to_string(), "".to_string()),
+        ])
+        .expect("valid env");
+        assert_eq!(
+            env,
+            vec![("HTTPS_PROXY".to_string(), "http://proxy:8080".to_string())]
+        );
+        assert!(normalize_workspace_env(vec![("1BAD".to_string(), "x".to_string())]).is_err());
+        assert!(
+            normalize_workspace_env(vec![("CODEX_HOME".to_string(), "x".to_string())]).is_err()
+        );
+        assert!(normalize_workspace_env(vec![
+            ("A".to_string(), "1".to_string()),
+            ("A".to_string(), "2".to_string()),
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn worktrees_inherit_parent_env_unless_they_set_their_own() {
+        let parent = workspace(WorkspaceKind::Main, &[("OPENAI_BASE_URL", "http://local")]);
+        let child = workspace(WorkspaceKind::Worktree, &[]);
+        assert_eq!(
+            resolve_workspace_env(&child, Some(&parent)),
+            parent.settings.env
+        );
+        let own = workspace(WorkspaceKind::Worktree, &[("NO_PROXY", "*")]);
+        assert_eq!(resolve_workspace_env(&own, Some(&parent)), own.settings.env);
+        let main = workspace(WorkspaceKind::Main, &[]);
+        assert!(resolve_workspace_env(&main, Some(&parent)).is_empty());
+    }
+
+    #[test]
+    fn expands_references_to_the_app_environment() {
+        let lookup = |name: &str| (name == "PATH").then(|| "/usr/bin".to_string());
+        assert_eq!(
+            expand_env_value("/opt/bin:$PATH", lookup),
+            "/opt/bin:/usr/bin"
+        );
+        assert_eq!(expand_env_value("${PATH}:/x", lookup), "/usr/bin:/x");
+        assert_eq!(expand_env_value("a$UNSET-b", lookup), "a-b");
+        assert_eq!(expand_env_value("cost $$5 $", lookup), "cost $5 $");
+    }
+}
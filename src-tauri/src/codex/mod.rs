@@ -6,6 +6,8 @@ use tauri::{AppHandle, Emitter, State};
 
 pub(crate) mod args;
 pub(crate) mod config;
+pub(crate) mod endpoint;
+pub(crate) mod env;
 pub(crate) mod home;
 
 use crate::backend::app_server::spawn_workspace_session as spawn_workspace_session_inner;
@@ -13,9 +15,18 @@ pub(crate) use crate::backend::app_server::WorkspaceSession;
 use crate::backend::events::AppServerEvent;
 use crate::event_sink::TauriEventSink;
 use crate::remote_backend;
-use crate::shared::codex_core;
+use crate::shared::{
+    catalog_cache_core, codex_core, orphan_process_core, pinned_messages_core, session_stats_core,
+    thread_analysis_core, thread_export_core, thread_search_core, thread_titles_core,
+    token_estimate_core, turn_trace_core,
+};
 use crate::state::AppState;
-use crate::types::WorkspaceEntry;
+use crate::types::{
+    ApprovalRule, ApprovalRuleTest, ApprovalRulesExport, ApprovalRulesImport, MessageTokenEstimate,
+    OrphanedCodexProcess, PinnedMessage, SessionAccount, SessionStartupStats, ThreadDiff,
+    ThreadExport, ThreadReplayResult, ThreadSearchResult, ThreadUsage, ThreadWindow,
+    TurnTraceCapture, TurnTraceReplay, WorkspaceEntry, WorkspaceInterruptResult,
+};
 
 pub(crate) async fn spawn_workspace_session(
     entry: WorkspaceEntry,
@@ -57,6 +68,59 @@ pub(crate) async fn codex_update(
         .await
 }
 
+#[tauri::command]
+pub(crate) async fn get_session_startup_stats(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<SessionStartupStats, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "get_session_startup_stats", json!({}))
+                .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+    Ok(session_stats_core::get_session_startup_stats_core())
+}
+
+#[tauri::command]
+pub(crate) async fn find_orphaned_codex_processes(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<OrphanedCodexProcess>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "find_orphaned_codex_processes", json!({}))
+                .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+    orphan_process_core::find_orphaned_codex_processes_core(&state.workspaces, &state.sessions)
+        .await
+}
+
+#[tauri::command]
+pub(crate) async fn kill_orphaned_codex_processes(
+    pids: Vec<u32>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<u32>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "kill_orphaned_codex_processes",
+            json!({ "pids": pids }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+    orphan_process_core::kill_orphaned_codex_processes_core(
+        &state.workspaces,
+        &state.sessions,
+        pids,
+    )
+    .await
+}
+
 #[tauri::command]
 pub(crate) async fn start_thread(
     workspace_id: String,
@@ -116,6 +180,386 @@ pub(crate) async fn fork_thread(
     codex_core::fork_thread_core(&state.sessions, workspace_id, thread_id).await
 }
 
+#[tauri::command]
+pub(crate) async fn replay_thread(
+    workspace_id: String,
+    source_thread_id: String,
+    model: String,
+    operation_id: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ThreadReplayResult, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "replay_thread",
+            json!({
+                "workspaceId": workspace_id,
+                "sourceThreadId": source_thread_id,
+                "model": model,
+                "operationId": operation_id,
+            }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let sessions = &state.sessions;
+    state
+        .operations
+        .run(
+            "replay",
+            Some(workspace_id.clone()),
+            operation_id,
+            |token| async move {
+                thread_analysis_core::replay_thread_core(
+                    sessions,
+                    workspace_id,
+                    source_thread_id,
+                    model,
+                    |done, total| {
+                        token.report_progress(
+                            Some(done as f64 / total as f64),
+                            Some(format!("Replayed {done} of {total} turns")),
+                        )
+                    },
+                )
+                .await
+            },
+        )
+        .await
+}
+
+#[tauri::command]
+pub(crate) async fn diff_threads(
+    workspace_id: String,
+    thread_a: String,
+    thread_b: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ThreadDiff, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "diff_threads",
+            json!({ "workspaceId": workspace_id, "threadA": thread_a, "threadB": thread_b }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    thread_analysis_core::diff_threads_core(&state.sessions, workspace_id, thread_a, thread_b).await
+}
+
+#[tauri::command]
+pub(crate) async fn get_thread_window(
+    workspace_id: String,
+    thread_id: String,
+    around_message_id: String,
+    radius: Option<u32>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ThreadWindow, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "get_thread_window",
+            json!({
+                "workspaceId": workspace_id,
+                "threadId": thread_id,
+                "aroundMessageId": around_message_id,
+                "radius": radius,
+            }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    thread_analysis_core::get_thread_window_core(
+        &state.sessions,
+        workspace_id,
+        thread_id,
+        around_message_id,
+        radius,
+    )
+    .await
+}
+
+/// Renders a thread as `format`, Markdown by default. In remote mode the
+/// daemon renders it and the file is still written on this machine.
+#[tauri::command]
+pub(crate) async fn export_thread(
+    workspace_id: String,
+    thread_id: String,
+    format: Option<String>,
+    destination: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ThreadExport, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "export_thread",
+            json!({ "workspaceId": workspace_id, "threadId": thread_id, "format": format }),
+        )
+        .await?;
+        let mut export: ThreadExport =
+            serde_json::from_value(response).map_err(|err| err.to_string())?;
+        if let Some(destination) = destination {
+            thread_export_core::write_thread_export(&mut export, &destination).await?;
+        }
+        return Ok(export);
+    }
+
+    thread_export_core::export_thread_core(
+        &state.sessions,
+        workspace_id,
+        thread_id,
+        format,
+        destination,
+    )
+    .await
+}
+
+/// Searches the workspace's stored transcripts, including archived threads
+/// when asked. Text over `max_message_bytes` is skipped.
+#[tauri::command]
+pub(crate) async fn search_threads(
+    workspace_id: String,
+    query: String,
+    include_archived: bool,
+    max_message_bytes: Option<usize>,
+    operation_id: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<ThreadSearchResult>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "search_threads",
+            json!({
+                "workspaceId": workspace_id,
+                "query": query,
+                "includeArchived": include_archived,
+                "maxMessageBytes": max_message_bytes,
+                "operationId": operation_id,
+            }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    state
+        .operations
+        .run("search", Some(workspace_id.clone()), operation_id, |_| {
+            thread_search_core::search_threads_core(
+                &state.workspaces,
+                workspace_id,
+                query,
+                include_archived,
+                max_message_bytes,
+            )
+        })
+        .await
+}
+
+#[tauri::command]
+pub(crate) async fn estimate_message_tokens(
+    workspace_id: String,
+    thread_id: String,
+    message: String,
+    attachments: Option<Vec<String>>,
+    model: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<MessageTokenEstimate, String> {
+    let attachments = attachments.unwrap_or_default();
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "estimate_message_tokens",
+            json!({
+                "workspaceId": workspace_id,
+                "threadId": thread_id,
+                "message": message,
+                "attachments": attachments,
+                "model": model,
+            }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    token_estimate_core::estimate_message_tokens_core(
+        &state.sessions,
+        &state.workspaces,
+        workspace_id,
+        thread_id,
+        message,
+        attachments,
+        model,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn thread_usage(
+    workspace_id: String,
+    thread_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ThreadUsage, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "thread_usage",
+            json!({ "workspaceId": workspace_id, "threadId": thread_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    token_estimate_core::thread_usage_core(
+        &state.sessions,
+        &state.workspaces,
+        workspace_id,
+        thread_id,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn capture_turn_trace(
+    workspace_id: String,
+    turn_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<TurnTraceCapture, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "capture_turn_trace",
+            json!({ "workspaceId": workspace_id, "turnId": turn_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    turn_trace_core::capture_turn_trace_core(
+        &state.workspaces,
+        &state.sessions,
+        &state.app_settings,
+        &state.settings_path,
+        workspace_id,
+        turn_id,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn load_turn_trace(
+    path: String,
+    workspace_id: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<TurnTraceReplay, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let path = remote_backend::normalize_path_for_remote(path);
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "load_turn_trace",
+            json!({ "path": path, "workspaceId": workspace_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    turn_trace_core::load_turn_trace_core(
+        &state.workspaces,
+        &TauriEventSink::new(app),
+        path,
+        workspace_id,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn pin_thread_message(
+    workspace_id: String,
+    thread_id: String,
+    message_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<PinnedMessage>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "pin_thread_message",
+            json!({ "workspaceId": workspace_id, "threadId": thread_id, "messageId": message_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    pinned_messages_core::pin_thread_message_core(
+        &state.storage_path,
+        workspace_id,
+        thread_id,
+        message_id,
+    )
+}
+
+#[tauri::command]
+pub(crate) async fn unpin_thread_message(
+    thread_id: String,
+    message_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<PinnedMessage>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "unpin_thread_message",
+            json!({ "threadId": thread_id, "messageId": message_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    pinned_messages_core::unpin_thread_message_core(&state.storage_path, thread_id, message_id)
+}
+
+#[tauri::command]
+pub(crate) async fn list_pinned_messages(
+    thread_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<PinnedMessage>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "list_pinned_messages",
+            json!({ "threadId": thread_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    pinned_messages_core::list_pinned_messages_core(&state.storage_path, &thread_id)
+}
+
 #[tauri::command]
 pub(crate) async fn list_threads(
     workspace_id: String,
@@ -140,7 +584,16 @@ pub(crate) async fn list_threads(
         .await;
     }
 
-    codex_core::list_threads_core(&state.sessions, workspace_id, cursor, limit, sort_key).await
+    let mut response = codex_core::list_threads_core(
+        &state.sessions,
+        workspace_id.clone(),
+        cursor,
+        limit,
+        sort_key,
+    )
+    .await?;
+    thread_titles_core::apply_thread_titles(&state.storage_path, &workspace_id, &mut response);
+    Ok(response)
 }
 
 #[tauri::command]
@@ -225,6 +678,30 @@ pub(crate) async fn set_thread_name(
     codex_core::set_thread_name_core(&state.sessions, workspace_id, thread_id, name).await
 }
 
+/// Gives the thread a title of the user's choosing, which `list_threads`
+/// returns as its `name`. An empty title goes back to the generated one.
+#[tauri::command]
+pub(crate) async fn rename_thread(
+    workspace_id: String,
+    thread_id: String,
+    title: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Option<String>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "rename_thread",
+            json!({ "workspaceId": workspace_id, "threadId": thread_id, "title": title }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    thread_titles_core::rename_thread_core(&state.storage_path, workspace_id, thread_id, title)
+}
+
 #[tauri::command]
 pub(crate) async fn send_user_message(
     workspace_id: String,
@@ -236,6 +713,7 @@ pub(crate) async fn send_user_message(
     images: Option<Vec<String>>,
     app_mentions: Option<Vec<Value>>,
     collaboration_mode: Option<Value>,
+    queue: Option<bool>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<Value, String> {
@@ -260,6 +738,9 @@ pub(crate) async fn send_user_message(
                 payload.insert("collaborationMode".to_string(), mode);
             }
         }
+        if let Some(queue) = queue {
+            payload.insert("queue".to_string(), json!(queue));
+        }
         return remote_backend::call_remote(
             &*state,
             app,
@@ -271,6 +752,8 @@ pub(crate) async fn send_user_message(
 
     codex_core::send_user_message_core(
         &state.sessions,
+        &state.workspaces,
+        &TauriEventSink::new(app),
         workspace_id,
         thread_id,
         text,
@@ -280,10 +763,32 @@ pub(crate) async fn send_user_message(
         images,
         app_mentions,
         collaboration_mode,
+        queue.unwrap_or(false),
     )
     .await
 }
 
+#[tauri::command]
+pub(crate) async fn clear_message_queue(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<usize, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "clear_message_queue",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    codex_core::clear_message_queue_core(&state.sessions, &TauriEventSink::new(app), workspace_id)
+        .await
+}
+
 #[tauri::command]
 pub(crate) async fn turn_steer(
     workspace_id: String,
@@ -370,6 +875,20 @@ pub(crate) async fn turn_interrupt(
     codex_core::turn_interrupt_core(&state.sessions, workspace_id, thread_id, turn_id).await
 }
 
+#[tauri::command]
+pub(crate) async fn interrupt_all_turns(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<WorkspaceInterruptResult>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "interrupt_all_turns", json!({})).await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    Ok(codex_core::interrupt_all_turns_core(&state.sessions).await)
+}
+
 #[tauri::command]
 pub(crate) async fn start_review(
     workspace_id: String,
@@ -400,20 +919,31 @@ pub(crate) async fn start_review(
 #[tauri::command]
 pub(crate) async fn model_list(
     workspace_id: String,
+    refresh: Option<bool>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<Value, String> {
+    let refresh = refresh.unwrap_or(false);
     if remote_backend::is_remote_mode(&*state).await {
-        return remote_backend::call_remote(
-            &*state,
-            app,
-            "model_list",
-            json!({ "workspaceId": workspace_id }),
+        // Cached here too, so the list survives losing the daemon.
+        return catalog_cache_core::cached_list(
+            &state.storage_path,
+            "model/list",
+            &workspace_id,
+            refresh,
+            || {
+                remote_backend::call_remote(
+                    &*state,
+                    app,
+                    "model_list",
+                    json!({ "workspaceId": workspace_id, "refresh": refresh }),
+                )
+            },
         )
         .await;
     }
 
-    codex_core::model_list_core(&state.sessions, workspace_id).await
+    codex_core::model_list_core(&state.sessions, &state.storage_path, workspace_id, refresh).await
 }
 
 #[tauri::command]
@@ -503,6 +1033,32 @@ pub(crate) async fn account_read(
     codex_core::account_read_core(&state.sessions, &state.workspaces, workspace_id).await
 }
 
+#[tauri::command]
+pub(crate) async fn get_session_account(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<SessionAccount, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "get_session_account",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    codex_core::get_session_account_core(
+        &state.sessions,
+        &state.workspaces,
+        &state.app_settings,
+        workspace_id,
+    )
+    .await
+}
+
 #[tauri::command]
 pub(crate) async fn codex_login(
     workspace_id: String,
@@ -545,20 +1101,31 @@ pub(crate) async fn codex_login_cancel(
 #[tauri::command]
 pub(crate) async fn skills_list(
     workspace_id: String,
+    refresh: Option<bool>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<Value, String> {
+    let refresh = refresh.unwrap_or(false);
     if remote_backend::is_remote_mode(&*state).await {
-        return remote_backend::call_remote(
-            &*state,
-            app,
-            "skills_list",
-            json!({ "workspaceId": workspace_id }),
+        // Cached here too, so the list survives losing the daemon.
+        return catalog_cache_core::cached_list(
+            &state.storage_path,
+            "skills/list",
+            &workspace_id,
+            refresh,
+            || {
+                remote_backend::call_remote(
+                    &*state,
+                    app,
+                    "skills_list",
+                    json!({ "workspaceId": workspace_id, "refresh": refresh }),
+                )
+            },
         )
         .await;
     }
 
-    codex_core::skills_list_core(&state.sessions, workspace_id).await
+    codex_core::skills_list_core(&state.sessions, &state.storage_path, workspace_id, refresh).await
 }
 
 #[tauri::command]
@@ -593,31 +1160,93 @@ pub(crate) async fn respond_to_server_request(
     workspace_id: String,
     request_id: Value,
     result: Value,
+    rule_command: Option<Vec<String>>,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<(), String> {
+) -> Result<bool, String> {
     if remote_backend::is_remote_mode(&*state).await {
-        remote_backend::call_remote(
+        let response = remote_backend::call_remote(
             &*state,
             app,
             "respond_to_server_request",
-            json!({ "workspaceId": workspace_id, "requestId": request_id, "result": result }),
+            json!({
+                "workspaceId": workspace_id,
+                "requestId": request_id,
+                "result": result,
+                "ruleCommand": rule_command,
+            }),
         )
         .await?;
-        return Ok(());
+        return Ok(response
+            .get("sent")
+            .and_then(Value::as_bool)
+            .unwrap_or(true));
     }
 
-    codex_core::respond_to_server_request_core(&state.sessions, workspace_id, request_id, result)
-        .await
+    codex_core::respond_to_server_request_core(
+        &state.sessions,
+        &state.workspaces,
+        workspace_id,
+        request_id,
+        result,
+        rule_command,
+    )
+    .await
 }
 
 #[tauri::command]
 pub(crate) async fn remember_approval_rule(
     workspace_id: String,
     command: Vec<String>,
+    kind: Option<String>,
+    pattern: Option<String>,
     state: State<'_, AppState>,
 ) -> Result<Value, String> {
-    codex_core::remember_approval_rule_core(&state.workspaces, workspace_id, command).await
+    codex_core::remember_approval_rule_core(&state.workspaces, workspace_id, command, kind, pattern)
+        .await
+}
+
+#[tauri::command]
+pub(crate) fn test_approval_rule(
+    pattern: String,
+    command: Vec<String>,
+    kind: Option<String>,
+) -> Result<ApprovalRuleTest, String> {
+    codex_core::test_approval_rule_core(pattern, command, kind)
+}
+
+#[tauri::command]
+pub(crate) async fn list_approval_rules(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<ApprovalRule>, String> {
+    codex_core::list_approval_rules_core(&state.workspaces, workspace_id).await
+}
+
+#[tauri::command]
+pub(crate) async fn delete_approval_rule(
+    workspace_id: String,
+    id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    codex_core::delete_approval_rule_core(&state.workspaces, workspace_id, id).await
+}
+
+#[tauri::command]
+pub(crate) async fn export_approval_rules(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<ApprovalRulesExport, String> {
+    codex_core::export_approval_rules_core(&state.workspaces, workspace_id).await
+}
+
+#[tauri::command]
+pub(crate) async fn import_approval_rules(
+    workspace_id: String,
+    data: String,
+    state: State<'_, AppState>,
+) -> Result<ApprovalRulesImport, String> {
+    codex_core::import_approval_rules_core(&state.workspaces, workspace_id, data).await
 }
 
 #[tauri::command]
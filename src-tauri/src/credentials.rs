@@ -0,0 +1,75 @@
+use tauri::State;
+
+use crate::shared::credentials_core::{self, REMOTE_BACKEND_TOKEN_KEY};
+use crate::shared::settings_core;
+use crate::state::AppState;
+use crate::types::CredentialStatus;
+
+// Credentials live in this machine's keychain, so these commands are
+// never forwarded to a remote daemon.
+
+#[tauri::command]
+pub(crate) async fn set_credential(
+    key: String,
+    secret: String,
+    state: State<'_, AppState>,
+) -> Result<CredentialStatus, String> {
+    let key = credentials_core::normalize_credential_key(&key)?;
+    if key == REMOTE_BACKEND_TOKEN_KEY {
+        // Keep the in-memory settings that the remote backend reads in sync.
+        settings_core::update_remote_backend_token_core(
+            &state.app_settings,
+            &state.settings_path,
+            Some(&secret),
+        )
+        .await?;
+        return remote_backend_token_status(&state).await;
+    }
+    credentials_core::set_credential_core(&key, &secret)
+}
+
+#[tauri::command]
+pub(crate) async fn get_credential_status(
+    key: String,
+    state: State<'_, AppState>,
+) -> Result<CredentialStatus, String> {
+    let key = credentials_core::normalize_credential_key(&key)?;
+    if key == REMOTE_BACKEND_TOKEN_KEY {
+        return remote_backend_token_status(&state).await;
+    }
+    credentials_core::get_credential_status_core(&key)
+}
+
+/// Read from the in-memory settings, since the token stays in the settings
+/// file where there is no OS credential store.
+async fn remote_backend_token_status(
+    state: &State<'_, AppState>,
+) -> Result<CredentialStatus, String> {
+    let exists = state
+        .app_settings
+        .lock()
+        .await
+        .remote_backend_token
+        .is_some();
+    Ok(CredentialStatus {
+        key: REMOTE_BACKEND_TOKEN_KEY.to_string(),
+        exists,
+    })
+}
+
+#[tauri::command]
+pub(crate) async fn delete_credential(
+    key: String,
+    state: State<'_, AppState>,
+) -> Result<CredentialStatus, String> {
+    let key = credentials_core::normalize_credential_key(&key)?;
+    if key == REMOTE_BACKEND_TOKEN_KEY {
+        settings_core::update_remote_backend_token_core(
+            &state.app_settings,
+            &state.settings_path,
+            None,
+        )
+        .await?;
+    }
+    credentials_core::delete_credential_core(&key)
+}
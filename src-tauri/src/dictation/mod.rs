@@ -1,5 +1,7 @@
 #[cfg_attr(any(target_os = "ios", target_os = "android"), path = "stub.rs")]
 #[cfg_attr(not(any(target_os = "ios", target_os = "android")), path = "real.rs")]
 mod imp;
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+mod wav;
 
 pub(crate) use imp::*;
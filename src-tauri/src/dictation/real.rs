@@ -1,19 +1,21 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::{mpsc, Arc, Mutex};
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use serde::Serialize;
 use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::io::AsyncWriteExt;
-use tokio::sync::oneshot;
+use tokio::sync::{oneshot, Notify};
 
+use super::wav::{read_wav, write_wav};
+use crate::shared::{http_client_core, settings_core};
 use crate::state::AppState;
 
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{FromSample, Sample, SampleFormat, SizedSample};
 use sha2::{Digest, Sha256};
-use whisper_rs::get_lang_id;
+use whisper_rs::{get_lang_id, get_lang_max_id, get_lang_str, get_lang_str_full};
 use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
 
 #[cfg(target_os = "macos")]
@@ -21,6 +23,18 @@ use objc2_av_foundation::{AVAuthorizationStatus, AVCaptureDevice, AVMediaTypeAud
 
 const DEFAULT_MODEL_ID: &str = "base";
 const MAX_CAPTURE_SECONDS: u32 = 120;
+/// How often the audio captured so far is decoded for a partial transcript.
+const PARTIAL_INTERVAL: Duration = Duration::from_millis(1500);
+/// Once the undecided audio is this long its text is committed, and later
+/// partials and the final transcript only decode what follows.
+const STREAM_WINDOW_SECONDS: u32 = 15;
+/// Input level, on the scale of `level` events, from which the microphone
+/// counts as hearing speech.
+const VAD_SPEECH_LEVEL: f32 = 0.08;
+/// Quiet shorter than this is a pause within speech rather than silence.
+const VAD_SILENCE_HOLD: Duration = Duration::from_millis(300);
+/// Shortest auto-stop delay, so a breath doesn't end the dictation.
+const MIN_AUTO_STOP_SILENCE_MS: u32 = 500;
 
 #[cfg(target_os = "macos")]
 static MIC_PERMISSION_REQUESTED: AtomicBool = AtomicBool::new(false);
@@ -104,6 +118,21 @@ async fn request_microphone_permission_with_completion(app: &AppHandle) -> Resul
     }
 }
 
+/// WASAPI reports a microphone blocked in the Windows privacy settings as an
+/// ordinary stream error, and there is no prompt to ask again.
+#[cfg(target_os = "windows")]
+fn with_microphone_hint(error: String) -> String {
+    format!(
+        "{error} If Windows is blocking the microphone, allow desktop apps to use it \
+         under Settings > Privacy & security > Microphone."
+    )
+}
+
+#[cfg(not(target_os = "windows"))]
+fn with_microphone_hint(error: String) -> String {
+    error
+}
+
 #[cfg(not(target_os = "macos"))]
 async fn request_microphone_permission(_app: &AppHandle) -> Result<bool, String> {
     // On non-macOS platforms, assume permission is granted
@@ -116,6 +145,7 @@ struct DictationModelInfo {
     filename: &'static str,
     url: &'static str,
     sha256: &'static str,
+    size_bytes: u64,
 }
 
 const MODEL_CATALOG: &[DictationModelInfo] = &[
@@ -124,30 +154,35 @@ const MODEL_CATALOG: &[DictationModelInfo] = &[
         filename: "ggml-tiny.bin",
         url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-tiny.bin",
         sha256: "be07e048e1e599ad46341c8d2a135645097a538221678b7acdd1b1919c6e1b21",
+        size_bytes: 77_691_713,
     },
     DictationModelInfo {
         id: "base",
         filename: "ggml-base.bin",
         url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base.bin",
         sha256: "60ed5bc3dd14eea856493d334349b405782ddcaf0028d4b5df4088345fba2efe",
+        size_bytes: 147_951_465,
     },
     DictationModelInfo {
         id: "small",
         filename: "ggml-small.bin",
         url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-small.bin",
         sha256: "1be3a9b2063867b937e64e2ec7483364a79917e157fa98c5d94b5c1fffea987b",
+        size_bytes: 487_601_967,
     },
     DictationModelInfo {
         id: "medium",
         filename: "ggml-medium.bin",
         url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-medium.bin",
         sha256: "6c14d5adee5f86394037b4e4e8b59f1673b6cee10e3cf0b11bbdbee79c156208",
+        size_bytes: 1_533_763_059,
     },
     DictationModelInfo {
         id: "large-v3",
         filename: "ggml-large-v3.bin",
         url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-large-v3.bin",
         sha256: "64d182b440b98d5203c4f9bd541544d84c605196c4f7b845dfa11fb23594d1e2",
+        size_bytes: 3_095_033_483,
     },
 ];
 
@@ -157,6 +192,8 @@ pub(crate) enum DictationModelState {
     Missing,
     Downloading,
     Ready,
+    /// On disk but with the wrong size or SHA-256.
+    Corrupt,
     Error,
 }
 
@@ -178,6 +215,31 @@ pub(crate) struct DictationModelStatus {
     pub(crate) path: Option<String>,
 }
 
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DictationModelVariant {
+    pub(crate) id: String,
+    pub(crate) size_bytes: u64,
+    pub(crate) downloaded: bool,
+    pub(crate) active: bool,
+}
+
+/// cpal has no stable device ids, so a device is identified by its name.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DictationInputDevice {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) is_default: bool,
+}
+
+/// A language Whisper can be told to transcribe in.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub(crate) struct DictationLanguage {
+    pub(crate) code: String,
+    pub(crate) name: String,
+}
+
 #[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub(crate) enum DictationSessionState {
@@ -189,11 +251,46 @@ pub(crate) enum DictationSessionState {
 #[derive(Debug, Serialize, Clone)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub(crate) enum DictationEvent {
-    State { state: DictationSessionState },
-    Level { value: f32 },
-    Transcript { text: String },
-    Error { message: String },
-    Canceled { message: String },
+    State {
+        state: DictationSessionState,
+    },
+    Level {
+        value: f32,
+    },
+    /// The transcript so far, while listening. Each one replaces the last.
+    Partial {
+        text: String,
+        #[serde(rename = "targetId")]
+        target_id: Option<String>,
+    },
+    Final {
+        text: String,
+        #[serde(rename = "targetId")]
+        target_id: Option<String>,
+    },
+    AudioSaved {
+        path: String,
+    },
+    Error {
+        message: String,
+    },
+    /// Something went wrong that dictation recovered from.
+    Warning {
+        message: String,
+    },
+    /// Sent when the microphone starts or stops hearing speech.
+    VoiceActivity {
+        speaking: bool,
+    },
+    /// The session stopped itself after `silenceMs` of silence; the usual
+    /// processing and final transcript follow.
+    AutoStopped {
+        #[serde(rename = "silenceMs")]
+        silence_ms: u32,
+    },
+    Canceled {
+        message: String,
+    },
 }
 
 pub(crate) struct DictationSessionHandle {
@@ -203,6 +300,128 @@ pub(crate) struct DictationSessionHandle {
     pub(crate) sample_rate: u32,
     pub(crate) model_id: String,
     pub(crate) preferred_language: Option<String>,
+    /// Forced on Whisper instead of detecting, already validated.
+    pub(crate) language: Option<String>,
+    /// Decodes to English text whatever language is spoken.
+    pub(crate) translate: bool,
+    /// Whatever the UI passed to `dictation_start`, echoed on the transcript
+    /// so text lands in the input that asked for it.
+    pub(crate) target_id: Option<String>,
+    pub(crate) stream: DictationStream,
+}
+
+/// Transcript text for the audio before `committed_samples`, which is not
+/// decoded again.
+#[derive(Debug, Default)]
+struct StreamProgress {
+    committed_text: String,
+    committed_samples: usize,
+}
+
+/// Decodes while listening and emits partial transcripts. Dropping it stops
+/// the decoding without a final transcript.
+pub(crate) struct DictationStream {
+    stop: Arc<Notify>,
+    stopping: Arc<AtomicBool>,
+    task: Option<tokio::task::JoinHandle<StreamProgress>>,
+}
+
+impl DictationStream {
+    fn start(
+        app: AppHandle,
+        audio: Arc<Mutex<Vec<f32>>>,
+        sample_rate: u32,
+        model_id: String,
+        preferred_language: Option<String>,
+        language: Option<String>,
+        translate: bool,
+        target_id: Option<String>,
+    ) -> Self {
+        let stop = Arc::new(Notify::new());
+        let stopping = Arc::new(AtomicBool::new(false));
+        let task = tokio::spawn({
+            let stop = Arc::clone(&stop);
+            let stopping = Arc::clone(&stopping);
+            async move {
+                let mut progress = StreamProgress::default();
+                // A model that fails to load is reported by the final decode.
+                let Ok(context) = load_whisper_context(&app, &model_id).await else {
+                    return progress;
+                };
+                let window = (sample_rate as usize).saturating_mul(STREAM_WINDOW_SECONDS as usize);
+                let mut last_decoded = 0;
+                loop {
+                    let _ = tokio::time::timeout(PARTIAL_INTERVAL, stop.notified()).await;
+                    if stopping.load(Ordering::Relaxed) {
+                        return progress;
+                    }
+                    let tail = {
+                        let guard = audio.lock().unwrap();
+                        guard
+                            .get(progress.committed_samples..)
+                            .map(<[f32]>::to_vec)
+                            .unwrap_or_default()
+                    };
+                    let len = tail.len();
+                    if len == last_decoded {
+                        continue;
+                    }
+                    let context = Arc::clone(&context);
+                    let preferred = preferred_language.clone();
+                    let forced = language.clone();
+                    let decoded = tokio::task::spawn_blocking(move || {
+                        transcribe_audio(tail, sample_rate, &context, preferred, forced, translate)
+                    })
+                    .await;
+                    let Ok(Ok(text)) = decoded else {
+                        last_decoded = len;
+                        continue;
+                    };
+                    let partial = if len >= window {
+                        progress.committed_text = join_transcript(&progress.committed_text, &text);
+                        progress.committed_samples += len;
+                        last_decoded = 0;
+                        progress.committed_text.clone()
+                    } else {
+                        last_decoded = len;
+                        join_transcript(&progress.committed_text, &text)
+                    };
+                    if !partial.is_empty() && !stopping.load(Ordering::Relaxed) {
+                        emit_event(
+                            &app,
+                            DictationEvent::Partial {
+                                text: partial,
+                                target_id: target_id.clone(),
+                            },
+                        );
+                    }
+                }
+            }
+        });
+        Self {
+            stop,
+            stopping,
+            task: Some(task),
+        }
+    }
+
+    /// Waits for a decode in flight and returns what has been committed.
+    async fn finish(mut self) -> StreamProgress {
+        self.stopping.store(true, Ordering::Relaxed);
+        self.stop.notify_one();
+        match self.task.take() {
+            Some(task) => task.await.unwrap_or_default(),
+            None => StreamProgress::default(),
+        }
+    }
+}
+
+impl Drop for DictationStream {
+    fn drop(&mut self) {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+    }
 }
 
 pub(crate) struct DictationState {
@@ -242,6 +461,24 @@ fn model_dir(app: &AppHandle) -> PathBuf {
         .join("whisper")
 }
 
+/// Only written to with `dictationSaveAudio` on.
+fn recordings_dir(app: &AppHandle) -> PathBuf {
+    app.path()
+        .app_data_dir()
+        .unwrap_or_else(|_| std::env::current_dir().unwrap_or_else(|_| ".".into()))
+        .join("dictation-recordings")
+}
+
+fn save_recording(app: &AppHandle, samples: &[f32], sample_rate: u32) -> Result<PathBuf, String> {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or(0);
+    let path = recordings_dir(app).join(format!("dictation-{millis}.wav"));
+    write_wav(&path, samples, sample_rate)?;
+    Ok(path)
+}
+
 fn model_info(model_id: &str) -> Option<&'static DictationModelInfo> {
     MODEL_CATALOG.iter().find(|info| info.id == model_id)
 }
@@ -278,6 +515,145 @@ fn ready_status(model_id: &str, path: &PathBuf) -> DictationModelStatus {
     }
 }
 
+fn corrupt_status(model_id: &str, path: &Path, error: String) -> DictationModelStatus {
+    DictationModelStatus {
+        state: DictationModelState::Corrupt,
+        model_id: model_id.to_string(),
+        progress: None,
+        error: Some(error),
+        path: Some(path.to_string_lossy().to_string()),
+    }
+}
+
+fn not_ready_message(status: &DictationModelStatus) -> String {
+    if status.state == DictationModelState::Corrupt {
+        "Dictation model is damaged; download it again.".to_string()
+    } else {
+        "Dictation model is not downloaded yet.".to_string()
+    }
+}
+
+/// Sits next to a model whose SHA-256 checked out and holds the size and
+/// modification time it had then, so later checks skip hashing.
+fn verified_marker_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".verified");
+    path.with_file_name(name)
+}
+
+fn file_fingerprint(metadata: &std::fs::Metadata) -> String {
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_nanos())
+        .unwrap_or(0);
+    format!("{}:{modified}", metadata.len())
+}
+
+fn hash_file(path: &Path) -> Result<Sha256, String> {
+    let mut file = std::fs::File::open(path).map_err(|error| error.to_string())?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher).map_err(|error| error.to_string())?;
+    Ok(hasher)
+}
+
+fn hex_digest(hasher: Sha256) -> String {
+    let mut hex = String::with_capacity(64);
+    for byte in hasher.finalize() {
+        use std::fmt::Write;
+        let _ = write!(&mut hex, "{:02x}", byte);
+    }
+    hex
+}
+
+fn mark_verified(path: &Path) {
+    if let Ok(metadata) = std::fs::metadata(path) {
+        let _ = std::fs::write(verified_marker_path(path), file_fingerprint(&metadata));
+    }
+}
+
+/// Checks the size on every call and the SHA-256 once per file. Blocks
+/// while hashing, which takes seconds for the larger models.
+fn verify_model_file(path: &Path, info: &DictationModelInfo) -> Result<(), String> {
+    let metadata = std::fs::metadata(path).map_err(|error| error.to_string())?;
+    if metadata.len() != info.size_bytes {
+        return Err(format!(
+            "The model file is incomplete: {} of {} bytes.",
+            metadata.len(),
+            info.size_bytes
+        ));
+    }
+    let fingerprint = file_fingerprint(&metadata);
+    let marker = verified_marker_path(path);
+    if std::fs::read_to_string(&marker).ok().as_deref() == Some(fingerprint.as_str()) {
+        return Ok(());
+    }
+    let hash = hex_digest(hash_file(path)?);
+    if hash != info.sha256 {
+        return Err("The model file is damaged (SHA-256 mismatch).".to_string());
+    }
+    let _ = std::fs::write(marker, fingerprint);
+    Ok(())
+}
+
+/// Whisper's language table, or just English for an English-only model.
+fn supported_languages(multilingual: bool) -> Vec<DictationLanguage> {
+    (0..=get_lang_max_id())
+        .filter_map(|id| {
+            let code = get_lang_str(id)?;
+            let name = get_lang_str_full(id)?;
+            Some(DictationLanguage {
+                code: code.to_string(),
+                name: name.to_string(),
+            })
+        })
+        .filter(|language| multilingual || language.code == "en")
+        .collect()
+}
+
+/// Accepts a code (`de`) or a name (`german`) and returns the code. `None`
+/// for a missing, empty or `auto` language, which leaves detection on.
+fn validate_language(language: Option<&str>, multilingual: bool) -> Result<Option<String>, String> {
+    let Some(language) = language
+        .map(|language| language.trim().to_lowercase())
+        .filter(|language| !language.is_empty() && language != "auto")
+    else {
+        return Ok(None);
+    };
+    let code = get_lang_id(&language)
+        .and_then(get_lang_str)
+        .ok_or_else(|| format!("Whisper does not support the language `{language}`."))?;
+    if !multilingual && code != "en" {
+        return Err(format!(
+            "The loaded dictation model only transcribes English, not `{code}`."
+        ));
+    }
+    Ok(Some(code.to_string()))
+}
+
+/// Catalog models are all multilingual; an English-only file dropped in
+/// under a catalog name is only known once loaded.
+async fn model_is_multilingual(state: &State<'_, AppState>, model_id: &str) -> bool {
+    state
+        .dictation
+        .lock()
+        .await
+        .cached_context
+        .as_ref()
+        .filter(|cached| cached.model_id == model_id)
+        .map(|cached| cached.context.is_multilingual())
+        .unwrap_or(true)
+}
+
+fn join_transcript(committed: &str, text: &str) -> String {
+    match (committed.trim(), text.trim()) {
+        ("", text) => text.to_string(),
+        (committed, "") => committed.to_string(),
+        (committed, text) => format!("{committed} {text}"),
+    }
+}
+
 fn emit_status(app: &AppHandle, status: &DictationModelStatus) {
     let _ = app.emit("dictation-download", status);
 }
@@ -332,40 +708,89 @@ async fn resolve_model_id(state: &State<'_, AppState>, model_id: Option<String>)
     }
 }
 
+/// Reuses the cached context when it is for the same model.
+async fn load_whisper_context(
+    app: &AppHandle,
+    model_id: &str,
+) -> Result<Arc<WhisperContext>, String> {
+    let state_handle = app.state::<AppState>();
+    {
+        let dictation = state_handle.dictation.lock().await;
+        if let Some(cached) = dictation
+            .cached_context
+            .as_ref()
+            .filter(|cached| cached.model_id == model_id)
+        {
+            return Ok(Arc::clone(&cached.context));
+        }
+    }
+    let path = model_path(app, model_id)?.to_string_lossy().into_owned();
+    let context = tokio::task::spawn_blocking(move || {
+        WhisperContext::new_with_params(&path, WhisperContextParameters::default())
+    })
+    .await
+    .map_err(|error| format!("Failed to load Whisper model: {error}"))?
+    .map_err(|error| format!("Failed to load Whisper model: {error}"))?;
+    let context = Arc::new(context);
+    let mut dictation = state_handle.dictation.lock().await;
+    dictation.cached_context = Some(CachedWhisperContext {
+        model_id: model_id.to_string(),
+        context: Arc::clone(&context),
+    });
+    Ok(context)
+}
+
 async fn refresh_status(
     app: &AppHandle,
     state: &State<'_, AppState>,
     model_id: &str,
 ) -> DictationModelStatus {
+    let info = {
+        let mut dictation = state.dictation.lock().await;
+        if dictation.model_status.state == DictationModelState::Downloading
+            && dictation.model_status.model_id == model_id
+        {
+            return dictation.model_status.clone();
+        }
+        match model_info(model_id) {
+            Some(info) => info,
+            None => {
+                dictation.model_status = DictationModelStatus {
+                    state: DictationModelState::Error,
+                    model_id: model_id.to_string(),
+                    progress: None,
+                    error: Some(format!("Unknown dictation model: {model_id}")),
+                    path: None,
+                };
+                return dictation.model_status.clone();
+            }
+        }
+    };
+    let path = model_dir(app).join(info.filename);
+
+    // Verified without holding the lock; hashing can take a while.
+    let status = if path.exists() {
+        let check_path = path.clone();
+        match tokio::task::spawn_blocking(move || verify_model_file(&check_path, info)).await {
+            Ok(Ok(())) => ready_status(model_id, &path),
+            Ok(Err(error)) => corrupt_status(model_id, &path, error),
+            Err(error) => corrupt_status(model_id, &path, error.to_string()),
+        }
+    } else {
+        missing_status(model_id)
+    };
+
     let mut dictation = state.dictation.lock().await;
     if dictation.model_status.state == DictationModelState::Downloading
         && dictation.model_status.model_id == model_id
     {
         return dictation.model_status.clone();
     }
-
-    let path = match model_path(app, model_id) {
-        Ok(path) => path,
-        Err(error) => {
-            dictation.model_status = DictationModelStatus {
-                state: DictationModelState::Error,
-                model_id: model_id.to_string(),
-                progress: None,
-                error: Some(error),
-                path: None,
-            };
-            return dictation.model_status.clone();
-        }
-    };
-
-    if path.exists() {
-        dictation.model_status = ready_status(model_id, &path);
-    } else {
-        dictation.model_status = missing_status(model_id);
-    }
+    dictation.model_status = status;
     dictation.model_status.clone()
 }
 
+/// Without a `model_id`, reports the active variant from `dictationModelId`.
 #[tauri::command]
 pub(crate) async fn dictation_model_status(
     app: AppHandle,
@@ -376,6 +801,96 @@ pub(crate) async fn dictation_model_status(
     Ok(refresh_status(&app, &state, &model_id).await)
 }
 
+/// Smallest first, which is also fastest first.
+#[tauri::command]
+pub(crate) async fn dictation_list_available_models(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<DictationModelVariant>, String> {
+    let active_id = resolve_model_id(&state, None).await;
+    Ok(MODEL_CATALOG
+        .iter()
+        .map(|info| DictationModelVariant {
+            id: info.id.to_string(),
+            size_bytes: info.size_bytes,
+            downloaded: model_dir(&app).join(info.filename).exists(),
+            active: info.id == active_id,
+        })
+        .collect())
+}
+
+/// Makes `model_id` the active variant and starts its download when it is
+/// not on disk yet; the returned status tracks that download.
+#[tauri::command]
+pub(crate) async fn dictation_select_model(
+    model_id: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<DictationModelStatus, String> {
+    if model_info(&model_id).is_none() {
+        return Err(format!("Unknown dictation model: {model_id}"));
+    }
+    if state.dictation.lock().await.session_state != DictationSessionState::Idle {
+        return Err("Finish the current dictation before switching models.".to_string());
+    }
+    let mut next_settings = state.app_settings.lock().await.clone();
+    if next_settings.dictation_model_id != model_id {
+        next_settings.dictation_model_id = model_id.clone();
+        settings_core::update_app_settings_core(
+            next_settings,
+            &state.app_settings,
+            &state.settings_path,
+        )
+        .await?;
+    }
+    {
+        // Whisper contexts are large; keep only the active one loaded.
+        let mut dictation = state.dictation.lock().await;
+        if dictation
+            .cached_context
+            .as_ref()
+            .is_some_and(|cached| cached.model_id != model_id)
+        {
+            dictation.cached_context = None;
+        }
+    }
+    dictation_download_model(app, state, Some(model_id)).await
+}
+
+/// Asks for the bytes after `offset` when a partial file is there. A server
+/// that answers with the whole file, or refuses the range, starts the
+/// download over. Returns the response with the offset it continues from.
+async fn request_model(
+    client: &reqwest::Client,
+    url: &str,
+    temp_path: &Path,
+    offset: u64,
+) -> Result<(reqwest::Response, u64), String> {
+    if offset > 0 {
+        let response = client
+            .get(url)
+            .header(reqwest::header::RANGE, format!("bytes={offset}-"))
+            .send()
+            .await
+            .map_err(|error| format!("Failed to download model: {error}"))?;
+        if response.status() == reqwest::StatusCode::PARTIAL_CONTENT {
+            return Ok((response, offset));
+        }
+        if response.status().is_success() {
+            return Ok((response, 0));
+        }
+        let _ = tokio::fs::remove_file(temp_path).await;
+    }
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|error| format!("Failed to download model: {error}"))?
+        .error_for_status()
+        .map_err(|error| format!("Model download failed: {error}"))?;
+    Ok((response, 0))
+}
+
 #[tauri::command]
 pub(crate) async fn dictation_download_model(
     app: AppHandle,
@@ -390,6 +905,21 @@ pub(crate) async fn dictation_download_model(
     if current.state == DictationModelState::Downloading && current.model_id == model_id {
         return Ok(current);
     }
+    if current.state == DictationModelState::Corrupt {
+        let model_path = model_path(&app, &model_id)?;
+        tokio::fs::remove_file(&model_path)
+            .await
+            .map_err(|error| format!("Failed to remove damaged model: {error}"))?;
+        let _ = tokio::fs::remove_file(verified_marker_path(&model_path)).await;
+        let mut dictation = state.dictation.lock().await;
+        if dictation
+            .cached_context
+            .as_ref()
+            .is_some_and(|cached| cached.model_id == model_id)
+        {
+            dictation.cached_context = None;
+        }
+    }
 
     let cancel_flag = Arc::new(AtomicBool::new(false));
     {
@@ -467,8 +997,8 @@ pub(crate) async fn dictation_download_model(
             return;
         }
 
-        let (url, expected_sha) = match model_info(&model_id_clone) {
-            Some(info) => (info.url, info.sha256),
+        let (url, expected_sha, size_bytes) = match model_info(&model_id_clone) {
+            Some(info) => (info.url, info.sha256, info.size_bytes),
             None => {
                 let status = DictationModelStatus {
                     state: DictationModelState::Error,
@@ -482,11 +1012,13 @@ pub(crate) async fn dictation_download_model(
                 return;
             }
         };
-        let client = match reqwest::Client::builder()
-            .connect_timeout(Duration::from_secs(10))
-            .timeout(Duration::from_secs(30 * 60))
-            .build()
-        {
+        let client = match http_client_core::http_client_builder().and_then(|builder| {
+            builder
+                .connect_timeout(Duration::from_secs(10))
+                .timeout(Duration::from_secs(30 * 60))
+                .build()
+                .map_err(|err| err.to_string())
+        }) {
             Ok(client) => client,
             Err(error) => {
                 let status = DictationModelStatus {
@@ -501,41 +1033,48 @@ pub(crate) async fn dictation_download_model(
                 return;
             }
         };
-        let response = match client.get(url).send().await {
-            Ok(response) => response,
-            Err(error) => {
-                let status = DictationModelStatus {
-                    state: DictationModelState::Error,
-                    model_id: model_id_clone.clone(),
-                    progress: None,
-                    error: Some(format!("Failed to download model: {error}")),
-                    path: None,
-                };
-                update_status(&app_handle, &state, status).await;
-                clear_download_state(&state).await;
-                return;
-            }
+        // Picks up where an interrupted download stopped.
+        let partial_len = match tokio::fs::metadata(&temp_path).await {
+            Ok(metadata) if metadata.len() < size_bytes => metadata.len(),
+            _ => 0,
         };
-        let response = match response.error_for_status() {
-            Ok(response) => response,
-            Err(error) => {
-                let status = DictationModelStatus {
-                    state: DictationModelState::Error,
-                    model_id: model_id_clone.clone(),
-                    progress: None,
-                    error: Some(format!("Model download failed: {error}")),
-                    path: None,
-                };
-                update_status(&app_handle, &state, status).await;
-                clear_download_state(&state).await;
-                return;
+        let (response, resume_from) =
+            match request_model(&client, url, &temp_path, partial_len).await {
+                Ok(response) => response,
+                Err(error) => {
+                    let status = DictationModelStatus {
+                        state: DictationModelState::Error,
+                        model_id: model_id_clone.clone(),
+                        progress: None,
+                        error: Some(error),
+                        path: None,
+                    };
+                    update_status(&app_handle, &state, status).await;
+                    clear_download_state(&state).await;
+                    return;
+                }
+            };
+
+        let total = response.content_length().map(|length| length + resume_from);
+        let mut downloaded = resume_from;
+        let opened = if resume_from > 0 {
+            let hash_path = temp_path.clone();
+            match tokio::task::spawn_blocking(move || hash_file(&hash_path)).await {
+                Ok(Ok(hasher)) => tokio::fs::OpenOptions::new()
+                    .append(true)
+                    .open(&temp_path)
+                    .await
+                    .map(|file| (file, hasher)),
+                Ok(Err(error)) => Err(std::io::Error::other(error)),
+                Err(error) => Err(std::io::Error::other(error)),
             }
+        } else {
+            tokio::fs::File::create(&temp_path)
+                .await
+                .map(|file| (file, Sha256::new()))
         };
-
-        let total = response.content_length();
-        let mut downloaded = 0u64;
-        let mut file = match tokio::fs::File::create(&temp_path).await {
-            Ok(file) => file,
+        let (mut file, mut hasher) = match opened {
+            Ok(opened) => opened,
             Err(error) => {
                 let status = DictationModelStatus {
                     state: DictationModelState::Error,
@@ -551,7 +1090,6 @@ pub(crate) async fn dictation_download_model(
         };
 
         let mut response = response;
-        let mut hasher = Sha256::new();
         let mut last_progress = Instant::now();
         loop {
             let cancel = {
@@ -574,12 +1112,12 @@ pub(crate) async fn dictation_download_model(
                 Ok(Some(chunk)) => chunk,
                 Ok(None) => break,
                 Err(error) => {
-                    let _ = tokio::fs::remove_file(&temp_path).await;
+                    // The partial file stays so the next download resumes.
                     let status = DictationModelStatus {
                         state: DictationModelState::Error,
                         model_id: model_id_clone.clone(),
                         progress: None,
-                        error: Some(format!("Model download failed: {error}")),
+                        error: Some(format!("Model download interrupted: {error}")),
                         path: None,
                     };
                     update_status(&app_handle, &state, status).await;
@@ -620,13 +1158,7 @@ pub(crate) async fn dictation_download_model(
             }
         }
 
-        let hash = hasher.finalize();
-        let mut hash_hex = String::with_capacity(64);
-        for byte in hash {
-            use std::fmt::Write;
-            let _ = write!(&mut hash_hex, "{:02x}", byte);
-        }
-        if hash_hex != expected_sha {
+        if hex_digest(hasher) != expected_sha {
             let _ = tokio::fs::remove_file(&temp_path).await;
             let status = DictationModelStatus {
                 state: DictationModelState::Error,
@@ -668,6 +1200,7 @@ pub(crate) async fn dictation_download_model(
             return;
         }
 
+        mark_verified(&model_path);
         let status = ready_status(&model_id_clone, &model_path);
         update_status(&app_handle, &state, status).await;
         clear_download_state(&state).await;
@@ -719,6 +1252,7 @@ pub(crate) async fn dictation_remove_model(
             .await
             .map_err(|error| format!("Failed to remove model: {error}"))?;
     }
+    let _ = tokio::fs::remove_file(verified_marker_path(&model_path)).await;
     {
         let mut dictation = state.dictation.lock().await;
         if dictation
@@ -736,16 +1270,64 @@ pub(crate) async fn dictation_remove_model(
     Ok(status)
 }
 
+/// With `translate`, Whisper writes English text whatever the spoken
+/// language, forced through `language` or detected. English is the only
+/// target Whisper translates into, and English-only models can't translate.
 #[tauri::command]
 pub(crate) async fn dictation_start(
     preferred_language: Option<String>,
+    target_id: Option<String>,
+    language: Option<String>,
+    device_id: Option<String>,
+    auto_stop_silence_ms: Option<u32>,
+    translate: Option<bool>,
     app: AppHandle,
     state: State<'_, AppState>,
 ) -> Result<DictationSessionState, String> {
     let model_id = resolve_model_id(&state, None).await;
+    let (device_id, auto_stop_silence_ms) = {
+        let settings = state.app_settings.lock().await;
+        (
+            device_id.or_else(|| settings.dictation_input_device.clone()),
+            auto_stop_silence_ms.or(settings.dictation_auto_stop_silence_ms),
+        )
+    };
+    let device_id = device_id.filter(|device_id| !device_id.trim().is_empty());
+    let capture_options = CaptureOptions {
+        device_id,
+        auto_stop_silence: auto_stop_silence_ms
+            .filter(|ms| *ms > 0)
+            .map(|ms| Duration::from_millis(u64::from(ms.max(MIN_AUTO_STOP_SILENCE_MS)))),
+    };
     let model_status = refresh_status(&app, &state, &model_id).await;
     if model_status.state != DictationModelState::Ready {
-        let message = "Dictation model is not downloaded yet.".to_string();
+        let message = not_ready_message(&model_status);
+        emit_event(
+            &app,
+            DictationEvent::Error {
+                message: message.clone(),
+            },
+        );
+        return Err(message);
+    }
+    let multilingual = model_is_multilingual(&state, &model_id).await;
+    let language = match validate_language(language.as_deref(), multilingual) {
+        Ok(language) => language,
+        Err(message) => {
+            emit_event(
+                &app,
+                DictationEvent::Error {
+                    message: message.clone(),
+                },
+            );
+            return Err(message);
+        }
+    };
+    let translate = translate.unwrap_or(false);
+    if translate && !multilingual {
+        let message =
+            "Translation needs a multilingual model; English-only models only transcribe."
+                .to_string();
         emit_event(
             &app,
             DictationEvent::Error {
@@ -811,6 +1393,7 @@ pub(crate) async fn dictation_start(
             stop_tx_thread,
             stopped_tx,
             ready_tx,
+            capture_options,
         );
     });
 
@@ -839,6 +1422,16 @@ pub(crate) async fn dictation_start(
 
     {
         let mut dictation = state.dictation.lock().await;
+        let stream = DictationStream::start(
+            app.clone(),
+            Arc::clone(&audio),
+            sample_rate,
+            model_id.clone(),
+            preferred_clone.clone(),
+            language.clone(),
+            translate,
+            target_id.clone(),
+        );
         dictation.session_state = DictationSessionState::Listening;
         dictation.session = Some(DictationSessionHandle {
             stop: stop_tx,
@@ -847,6 +1440,10 @@ pub(crate) async fn dictation_start(
             sample_rate,
             model_id: model_id.clone(),
             preferred_language: preferred_clone,
+            language,
+            translate,
+            target_id,
+            stream,
         });
     }
 
@@ -860,6 +1457,55 @@ pub(crate) async fn dictation_start(
     Ok(DictationSessionState::Listening)
 }
 
+#[tauri::command]
+pub(crate) async fn dictation_list_input_devices() -> Result<Vec<DictationInputDevice>, String> {
+    tokio::task::spawn_blocking(|| {
+        let host = cpal::default_host();
+        let default_name = host
+            .default_input_device()
+            .and_then(|device| device.name().ok());
+        let devices = host
+            .input_devices()
+            .map_err(|error| format!("Failed to list microphones: {error}"))?;
+        Ok(devices
+            .filter_map(|device| device.name().ok())
+            .map(|name| DictationInputDevice {
+                id: name.clone(),
+                is_default: default_name.as_deref() == Some(name.as_str()),
+                name,
+            })
+            .collect())
+    })
+    .await
+    .map_err(|error| format!("Failed to list microphones: {error}"))?
+}
+
+/// The device named `device_id`, or the system default with a warning when
+/// it is gone (unplugged since it was chosen, say).
+fn select_input_device(
+    app: &AppHandle,
+    host: &cpal::Host,
+    device_id: Option<&str>,
+) -> Option<cpal::Device> {
+    if let Some(device_id) = device_id {
+        let found = host.input_devices().ok().and_then(|mut devices| {
+            devices.find(|device| device.name().ok().as_deref() == Some(device_id))
+        });
+        if found.is_some() {
+            return found;
+        }
+        emit_event(
+            app,
+            DictationEvent::Warning {
+                message: format!(
+                    "Microphone \"{device_id}\" is not available; using the system default."
+                ),
+            },
+        );
+    }
+    host.default_input_device()
+}
+
 #[tauri::command]
 pub(crate) async fn dictation_request_permission(app: AppHandle) -> Result<bool, String> {
     request_microphone_permission(&app).await
@@ -871,7 +1517,7 @@ pub(crate) async fn dictation_stop(
     state: State<'_, AppState>,
 ) -> Result<DictationSessionState, String> {
     let cancel_flag = Arc::new(AtomicBool::new(false));
-    let (audio, sample_rate, model_id, preferred_language, stopped, stop_tx) = {
+    let session = {
         let mut dictation = state.dictation.lock().await;
         if dictation.session_state != DictationSessionState::Listening {
             let message = "Dictation is not currently listening.".to_string();
@@ -885,19 +1531,23 @@ pub(crate) async fn dictation_stop(
         }
         dictation.session_state = DictationSessionState::Processing;
         dictation.processing_cancel = Some(Arc::clone(&cancel_flag));
-        let session = dictation
+        dictation
             .session
             .take()
-            .ok_or_else(|| "Dictation session is unavailable.".to_string())?;
-        (
-            session.audio,
-            session.sample_rate,
-            session.model_id,
-            session.preferred_language,
-            session.stopped,
-            session.stop,
-        )
+            .ok_or_else(|| "Dictation session is unavailable.".to_string())?
     };
+    let DictationSessionHandle {
+        stop: stop_tx,
+        stopped,
+        audio,
+        sample_rate,
+        model_id,
+        preferred_language,
+        language,
+        translate,
+        target_id,
+        stream,
+    } = session;
 
     emit_event(
         &app,
@@ -910,6 +1560,7 @@ pub(crate) async fn dictation_stop(
     let _ = stop_tx.send(());
     let _ = stopped.await;
     tokio::spawn(async move {
+        let progress = stream.finish().await;
         let samples = {
             let mut guard = audio.lock().unwrap();
             let captured = guard.clone();
@@ -922,94 +1573,54 @@ pub(crate) async fn dictation_stop(
         }
 
         let state_handle = app_handle.state::<AppState>();
-        let cached_context = {
-            let dictation = state_handle.dictation.lock().await;
-            dictation
-                .cached_context
-                .as_ref()
-                .filter(|cached| cached.model_id == model_id)
-                .map(|cached| Arc::clone(&cached.context))
+        if state_handle.app_settings.lock().await.dictation_save_audio {
+            match save_recording(&app_handle, &samples, sample_rate) {
+                Ok(path) => emit_event(
+                    &app_handle,
+                    DictationEvent::AudioSaved {
+                        path: path.to_string_lossy().to_string(),
+                    },
+                ),
+                Err(error) => eprintln!("dictation: {error}"),
+            }
+        }
+
+        let context = match load_whisper_context(&app_handle, &model_id).await {
+            Ok(context) => context,
+            Err(message) => {
+                emit_event(&app_handle, DictationEvent::Error { message });
+                let mut dictation = state_handle.dictation.lock().await;
+                dictation.session_state = DictationSessionState::Idle;
+                emit_event(
+                    &app_handle,
+                    DictationEvent::State {
+                        state: DictationSessionState::Idle,
+                    },
+                );
+                return;
+            }
         };
 
-        let context = if let Some(context) = cached_context {
-            context
+        let preferred = preferred_language.clone();
+        let tail = samples
+            .get(progress.committed_samples..)
+            .map(<[f32]>::to_vec)
+            .unwrap_or_default();
+        // Too short a tail after committed text is a pause, not an error.
+        let skip_tail =
+            !progress.committed_text.is_empty() && (tail.len() as f32) < sample_rate as f32 * 0.2;
+
+        let outcome = if skip_tail {
+            Ok(progress.committed_text.clone())
         } else {
-            let model_path = match model_path(&app_handle, &model_id) {
-                Ok(path) => path,
-                Err(error) => {
-                    emit_event(&app_handle, DictationEvent::Error { message: error });
-                    let mut dictation = state_handle.dictation.lock().await;
-                    dictation.session_state = DictationSessionState::Idle;
-                    emit_event(
-                        &app_handle,
-                        DictationEvent::State {
-                            state: DictationSessionState::Idle,
-                        },
-                    );
-                    return;
-                }
-            };
-            let path = model_path.to_string_lossy().into_owned();
-            let created = tokio::task::spawn_blocking(move || {
-                WhisperContext::new_with_params(&path, WhisperContextParameters::default())
+            let result = tokio::task::spawn_blocking(move || {
+                transcribe_audio(tail, sample_rate, &context, preferred, language, translate)
             })
             .await;
-            let context = match created {
-                Ok(Ok(context)) => context,
-                Ok(Err(error)) => {
-                    emit_event(
-                        &app_handle,
-                        DictationEvent::Error {
-                            message: format!("Failed to load Whisper model: {error}"),
-                        },
-                    );
-                    let mut dictation = state_handle.dictation.lock().await;
-                    dictation.session_state = DictationSessionState::Idle;
-                    emit_event(
-                        &app_handle,
-                        DictationEvent::State {
-                            state: DictationSessionState::Idle,
-                        },
-                    );
-                    return;
-                }
-                Err(error) => {
-                    emit_event(
-                        &app_handle,
-                        DictationEvent::Error {
-                            message: format!("Failed to load Whisper model: {error}"),
-                        },
-                    );
-                    let mut dictation = state_handle.dictation.lock().await;
-                    dictation.session_state = DictationSessionState::Idle;
-                    emit_event(
-                        &app_handle,
-                        DictationEvent::State {
-                            state: DictationSessionState::Idle,
-                        },
-                    );
-                    return;
-                }
-            };
-            let context = Arc::new(context);
-            let mut dictation = state_handle.dictation.lock().await;
-            dictation.cached_context = Some(CachedWhisperContext {
-                model_id: model_id.clone(),
-                context: Arc::clone(&context),
-            });
-            context
-        };
-
-        let preferred = preferred_language.clone();
-
-        let result = tokio::task::spawn_blocking(move || {
-            transcribe_audio(samples, sample_rate, &context, preferred)
-        })
-        .await;
-
-        let outcome = match result {
-            Ok(result) => result,
-            Err(error) => Err(format!("Transcription task failed: {error}")),
+            match result {
+                Ok(result) => result.map(|text| join_transcript(&progress.committed_text, &text)),
+                Err(error) => Err(format!("Transcription task failed: {error}")),
+            }
         };
 
         if cancel_flag.load(Ordering::Relaxed) {
@@ -1020,7 +1631,7 @@ pub(crate) async fn dictation_stop(
         match outcome {
             Ok(text) => {
                 if !text.trim().is_empty() {
-                    emit_event(&app_handle, DictationEvent::Transcript { text });
+                    emit_event(&app_handle, DictationEvent::Final { text, target_id });
                 }
             }
             Err(message) => {
@@ -1043,6 +1654,62 @@ pub(crate) async fn dictation_stop(
     Ok(DictationSessionState::Processing)
 }
 
+/// Runs the model on a saved recording (or any WAV) without touching the
+/// live session, so post-processing can be tuned against fixed audio.
+#[tauri::command]
+pub(crate) async fn dictation_transcribe_file(
+    path: String,
+    preferred_language: Option<String>,
+    model_id: Option<String>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let model_id = resolve_model_id(&state, model_id).await;
+    let model_status = refresh_status(&app, &state, &model_id).await;
+    if model_status.state != DictationModelState::Ready {
+        return Err(not_ready_message(&model_status));
+    }
+    let preferred_language = match preferred_language {
+        Some(language) => Some(language),
+        None => state
+            .app_settings
+            .lock()
+            .await
+            .dictation_preferred_language
+            .clone(),
+    };
+    let path = PathBuf::from(path);
+    let (samples, sample_rate) = tokio::task::spawn_blocking(move || read_wav(&path))
+        .await
+        .map_err(|error| format!("Failed to read audio file: {error}"))??;
+    let context = load_whisper_context(&app, &model_id).await?;
+    tokio::task::spawn_blocking(move || {
+        transcribe_audio(
+            samples,
+            sample_rate,
+            &context,
+            preferred_language,
+            None,
+            false,
+        )
+    })
+    .await
+    .map_err(|error| format!("Transcription task failed: {error}"))?
+}
+
+/// What `dictation_start` accepts as `language` for the model, the selected
+/// one by default.
+#[tauri::command]
+pub(crate) async fn dictation_supported_languages(
+    model_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<DictationLanguage>, String> {
+    let model_id = resolve_model_id(&state, model_id).await;
+    Ok(supported_languages(
+        model_is_multilingual(&state, &model_id).await,
+    ))
+}
+
 #[tauri::command]
 pub(crate) async fn dictation_cancel(
     app: AppHandle,
@@ -1113,6 +1780,31 @@ pub(crate) async fn dictation_cancel(
     Ok(DictationSessionState::Idle)
 }
 
+/// What `dictation_start` asks of the capture thread.
+struct CaptureOptions {
+    device_id: Option<String>,
+    /// Stops the session after this much silence following speech.
+    auto_stop_silence: Option<Duration>,
+}
+
+/// Runs the stop the user would have, unless they got there first.
+fn auto_stop_session(app: &AppHandle, silence: Duration) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        if state.dictation.lock().await.session_state != DictationSessionState::Listening {
+            return;
+        }
+        emit_event(
+            &app,
+            DictationEvent::AutoStopped {
+                silence_ms: silence.as_millis() as u32,
+            },
+        );
+        let _ = dictation_stop(app.clone(), state).await;
+    });
+}
+
 fn start_capture_thread(
     app: AppHandle,
     audio: Arc<Mutex<Vec<f32>>>,
@@ -1120,10 +1812,10 @@ fn start_capture_thread(
     stop_tx: mpsc::Sender<()>,
     stopped_tx: oneshot::Sender<()>,
     ready_tx: oneshot::Sender<Result<u32, String>>,
+    options: CaptureOptions,
 ) {
     let host = cpal::default_host();
-    let device = host
-        .default_input_device()
+    let device = select_input_device(&app, &host, options.device_id.as_deref())
         .ok_or_else(|| "No microphone input device available.".to_string());
     let device = match device {
         Ok(device) => device,
@@ -1227,13 +1919,15 @@ fn start_capture_thread(
     let stream = match stream {
         Ok(stream) => stream,
         Err(error) => {
-            let _ = ready_tx.send(Err(error));
+            let _ = ready_tx.send(Err(with_microphone_hint(error)));
             let _ = stopped_tx.send(());
             return;
         }
     };
     if let Err(error) = stream.play() {
-        let _ = ready_tx.send(Err(format!("Failed to start microphone: {error}")));
+        let _ = ready_tx.send(Err(with_microphone_hint(format!(
+            "Failed to start microphone: {error}"
+        ))));
         let _ = stopped_tx.send(());
         return;
     }
@@ -1242,10 +1936,42 @@ fn start_capture_thread(
     let level_task_app = app.clone();
     let level_task_value = level_value.clone();
     let level_task_running = running.clone();
+    let auto_stop_silence = options.auto_stop_silence;
     std::thread::spawn(move || {
+        let mut speaking = false;
+        let mut heard_speech = false;
+        let mut quiet_since: Option<Instant> = None;
         while level_task_running.load(Ordering::Relaxed) {
             let value = f32::from_bits(level_task_value.load(Ordering::Relaxed));
             emit_event(&level_task_app, DictationEvent::Level { value });
+            let now = Instant::now();
+            if value >= VAD_SPEECH_LEVEL {
+                quiet_since = None;
+                heard_speech = true;
+                if !speaking {
+                    speaking = true;
+                    emit_event(
+                        &level_task_app,
+                        DictationEvent::VoiceActivity { speaking: true },
+                    );
+                }
+            } else {
+                let quiet = now.duration_since(*quiet_since.get_or_insert(now));
+                if speaking && quiet >= VAD_SILENCE_HOLD {
+                    speaking = false;
+                    emit_event(
+                        &level_task_app,
+                        DictationEvent::VoiceActivity { speaking: false },
+                    );
+                }
+                // Silence before the first word doesn't count.
+                if let Some(limit) = auto_stop_silence {
+                    if heard_speech && quiet >= limit {
+                        auto_stop_session(&level_task_app, limit);
+                        break;
+                    }
+                }
+            }
             std::thread::sleep(Duration::from_millis(33));
         }
     });
@@ -1333,6 +2059,8 @@ fn transcribe_audio(
     sample_rate: u32,
     context: &WhisperContext,
     preferred_language: Option<String>,
+    language: Option<String>,
+    translate: bool,
 ) -> Result<String, String> {
     if samples.is_empty() {
         return Ok(String::new());
@@ -1391,11 +2119,13 @@ fn transcribe_audio(
     params.set_print_realtime(false);
     params.set_print_timestamps(false);
     params.set_no_timestamps(true);
-    params.set_translate(false);
+    // Whisper only translates into English; the source language is still
+    // forced or detected as usual.
+    params.set_translate(translate);
     params.set_no_context(true);
     params.set_single_segment(false);
-    let mut forced_language: Option<String> = None;
-    if let Some(preferred) = preferred_language.clone() {
+    let mut forced_language = validate_language(language.as_deref(), context.is_multilingual())?;
+    if let Some(preferred) = preferred_language.filter(|_| forced_language.is_none()) {
         if let Some(pref_id) = get_lang_id(&preferred) {
             if state.pcm_to_mel(&audio, threads).is_ok() {
                 if let Ok((_detected, probs)) = state.lang_detect(0, threads) {
@@ -1414,7 +2144,8 @@ fn transcribe_audio(
     }
 
     if let Some(language) = forced_language.as_deref() {
-        // Use the preferred language only when detection is ambiguous.
+        // Either forced by the caller or, for the preferred language, used
+        // only when detection is ambiguous.
         params.set_language(Some(language));
     } else {
         // Auto-detect language while still running transcription.
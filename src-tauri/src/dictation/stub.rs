@@ -12,6 +12,7 @@ pub(crate) enum DictationModelState {
     Missing,
     Downloading,
     Ready,
+    Corrupt,
     Error,
 }
 
@@ -33,6 +34,29 @@ pub(crate) struct DictationModelStatus {
     pub(crate) path: Option<String>,
 }
 
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DictationModelVariant {
+    pub(crate) id: String,
+    pub(crate) size_bytes: u64,
+    pub(crate) downloaded: bool,
+    pub(crate) active: bool,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DictationInputDevice {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) is_default: bool,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub(crate) struct DictationLanguage {
+    pub(crate) code: String,
+    pub(crate) name: String,
+}
+
 #[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub(crate) enum DictationSessionState {
@@ -76,6 +100,23 @@ pub(crate) async fn dictation_model_status(
     })
 }
 
+#[tauri::command]
+pub(crate) async fn dictation_list_available_models(
+    _app: AppHandle,
+    _state: State<'_, AppState>,
+) -> Result<Vec<DictationModelVariant>, String> {
+    Ok(Vec::new())
+}
+
+#[tauri::command]
+pub(crate) async fn dictation_select_model(
+    _model_id: String,
+    _app: AppHandle,
+    _state: State<'_, AppState>,
+) -> Result<DictationModelStatus, String> {
+    Err(UNSUPPORTED_MESSAGE.to_string())
+}
+
 #[tauri::command]
 pub(crate) async fn dictation_download_model(
     app: AppHandle,
@@ -106,12 +147,22 @@ pub(crate) async fn dictation_remove_model(
 #[tauri::command]
 pub(crate) async fn dictation_start(
     _preferred_language: Option<String>,
+    _target_id: Option<String>,
+    _language: Option<String>,
+    _device_id: Option<String>,
+    _auto_stop_silence_ms: Option<u32>,
+    _translate: Option<bool>,
     _app: AppHandle,
     _state: State<'_, AppState>,
 ) -> Result<DictationSessionState, String> {
     Err(UNSUPPORTED_MESSAGE.to_string())
 }
 
+#[tauri::command]
+pub(crate) async fn dictation_list_input_devices() -> Result<Vec<DictationInputDevice>, String> {
+    Ok(Vec::new())
+}
+
 #[tauri::command]
 pub(crate) async fn dictation_request_permission(_app: AppHandle) -> Result<bool, String> {
     Ok(false)
@@ -125,6 +176,25 @@ pub(crate) async fn dictation_stop(
     Err(UNSUPPORTED_MESSAGE.to_string())
 }
 
+#[tauri::command]
+pub(crate) async fn dictation_transcribe_file(
+    _path: String,
+    _preferred_language: Option<String>,
+    _model_id: Option<String>,
+    _app: AppHandle,
+    _state: State<'_, AppState>,
+) -> Result<String, String> {
+    Err(UNSUPPORTED_MESSAGE.to_string())
+}
+
+#[tauri::command]
+pub(crate) async fn dictation_supported_languages(
+    _model_id: Option<String>,
+    _state: State<'_, AppState>,
+) -> Result<Vec<DictationLanguage>, String> {
+    Ok(Vec::new())
+}
+
 #[tauri::command]
 pub(crate) async fn dictation_cancel(
     _app: AppHandle,
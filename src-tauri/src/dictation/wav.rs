@@ -0,0 +1,160 @@
+//! Just enough RIFF/WAVE to save captured audio and read it back: mono
+//! 16-bit PCM on the way out; 8/16/24/32-bit PCM or 32-bit float, any
+//! channel count, on the way in.
+
+use std::path::Path;
+
+const FORMAT_PCM: u16 = 1;
+const FORMAT_IEEE_FLOAT: u16 = 3;
+const FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+pub(crate) fn write_wav(path: &Path, samples: &[f32], sample_rate: u32) -> Result<(), String> {
+    let data_len = (samples.len() * 2) as u32;
+    let mut bytes = Vec::with_capacity(44 + data_len as usize);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVE");
+    bytes.extend_from_slice(b"fmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&FORMAT_PCM.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&sample_rate.to_le_bytes());
+    bytes.extend_from_slice(&(sample_rate * 2).to_le_bytes());
+    bytes.extend_from_slice(&2u16.to_le_bytes());
+    bytes.extend_from_slice(&16u16.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        let value = (sample.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16;
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|error| format!("Failed to create recordings folder: {error}"))?;
+    }
+    std::fs::write(path, bytes).map_err(|error| format!("Failed to write audio file: {error}"))
+}
+
+/// Returns mono samples in `-1.0..=1.0` and the file's sample rate.
+pub(crate) fn read_wav(path: &Path) -> Result<(Vec<f32>, u32), String> {
+    let bytes =
+        std::fs::read(path).map_err(|error| format!("Failed to read audio file: {error}"))?;
+    parse_wav(&bytes)
+}
+
+fn parse_wav(bytes: &[u8]) -> Result<(Vec<f32>, u32), String> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err("Not a WAV file.".to_string());
+    }
+    let u16_at = |offset: usize| u16::from_le_bytes([bytes[offset], bytes[offset + 1]]);
+    let mut format: Option<(u16, u16, u32, u16)> = None;
+    let mut data: Option<&[u8]> = None;
+    let mut offset = 12;
+    while offset + 8 <= bytes.len() {
+        let id = &bytes[offset..offset + 4];
+        let len = u32::from_le_bytes([
+            bytes[offset + 4],
+            bytes[offset + 5],
+            bytes[offset + 6],
+            bytes[offset + 7],
+        ]) as usize;
+        let body = offset + 8;
+        let end = body.saturating_add(len).min(bytes.len());
+        match id {
+            b"fmt " if end - body >= 16 => {
+                let mut tag = u16_at(body);
+                if tag == FORMAT_EXTENSIBLE && end - body >= 26 {
+                    tag = u16_at(body + 24);
+                }
+                let channels = u16_at(body + 2);
+                let rate = u32::from_le_bytes([
+                    bytes[body + 4],
+                    bytes[body + 5],
+                    bytes[body + 6],
+                    bytes[body + 7],
+                ]);
+                let bits = u16_at(body + 14);
+                format = Some((tag, channels, rate, bits));
+            }
+            b"data" => data = Some(&bytes[body..end]),
+            _ => {}
+        }
+        // Chunks are padded to an even length.
+        offset = body.saturating_add(len).saturating_add(len % 2);
+    }
+    let (tag, channels, sample_rate, bits) =
+        format.ok_or_else(|| "WAV file has no format chunk.".to_string())?;
+    let data = data.ok_or_else(|| "WAV file has no audio data.".to_string())?;
+    if channels == 0 || sample_rate == 0 {
+        return Err("WAV file has an invalid format.".to_string());
+    }
+    let width = (bits as usize).div_ceil(8);
+    let decode: fn(&[u8]) -> f32 = match (tag, bits) {
+        (FORMAT_PCM, 8) => |b| (b[0] as f32 - 128.0) / 128.0,
+        (FORMAT_PCM, 16) => |b| i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0,
+        (FORMAT_PCM, 24) => {
+            |b| (i32::from_le_bytes([0, b[0], b[1], b[2]]) >> 8) as f32 / 8_388_608.0
+        }
+        (FORMAT_PCM, 32) => {
+            |b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f32 / 2_147_483_648.0
+        }
+        (FORMAT_IEEE_FLOAT, 32) => |b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+        _ => {
+            return Err(format!(
+                "Unsupported WAV format (format {tag}, {bits}-bit)."
+            ))
+        }
+    };
+    let frame_width = width * channels as usize;
+    let samples = data
+        .chunks_exact(frame_width)
+        .map(|frame| {
+            let sum: f32 = frame.chunks_exact(width).map(decode).sum();
+            sum / channels as f32
+        })
+        .collect();
+    Ok((samples, sample_rate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn written_audio_reads_back() {
+        let path = std::env::temp_dir()
+            .join(format!("codex-monitor-wav-{}", uuid::Uuid::new_v4()))
+            .join("clip.wav");
+        let samples = vec![0.0, 0.5, -0.5, 1.0, -1.0];
+        write_wav(&path, &samples, 16_000).expect("write wav");
+        let (read, rate) = read_wav(&path).expect("read wav");
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+        assert_eq!(rate, 16_000);
+        assert_eq!(read.len(), samples.len());
+        for (expected, actual) in samples.iter().zip(&read) {
+            assert!((expected - actual).abs() < 0.001, "{expected} vs {actual}");
+        }
+    }
+
+    #[test]
+    fn stereo_is_downmixed() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&FORMAT_IEEE_FLOAT.to_le_bytes());
+        bytes.extend_from_slice(&2u16.to_le_bytes());
+        bytes.extend_from_slice(&48_000u32.to_le_bytes());
+        bytes.extend_from_slice(&(48_000u32 * 8).to_le_bytes());
+        bytes.extend_from_slice(&8u16.to_le_bytes());
+        bytes.extend_from_slice(&32u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&8u32.to_le_bytes());
+        bytes.extend_from_slice(&0.5f32.to_le_bytes());
+        bytes.extend_from_slice(&(-0.25f32).to_le_bytes());
+        let (samples, rate) = parse_wav(&bytes).expect("parse wav");
+        assert_eq!(rate, 48_000);
+        assert_eq!(samples, vec![0.125]);
+    }
+}
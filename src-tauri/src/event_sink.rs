@@ -1,20 +1,80 @@
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+
 use tauri::{AppHandle, Emitter};
 
-use crate::backend::events::{AppServerEvent, EventSink, TerminalExit, TerminalOutput};
+use crate::backend::events::{
+    AppServerEvent, CloneProgressEvent, EventSink, GitSyncProgressEvent, MessageQueuedEvent,
+    OperationEvent, ProfileLoginEvent, RemoteUpdatesEvent, SessionCrashedEvent, TerminalExit,
+    TerminalOutput,
+};
+use crate::shared::event_batch_core::{self, EventBatch};
+use crate::types::NetworkStatus;
+
+/// One batch for the whole app, since sinks are created per command.
+fn shared_batch() -> Arc<Mutex<EventBatch>> {
+    static BATCH: OnceLock<Arc<Mutex<EventBatch>>> = OnceLock::new();
+    BATCH.get_or_init(Default::default).clone()
+}
+
+fn lock_batch(batch: &Mutex<EventBatch>) -> MutexGuard<'_, EventBatch> {
+    batch
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Emits while the batch is still locked so a timer flush and a discrete
+/// event never reach the webview out of order.
+fn flush_batch(app: &AppHandle, batch: &mut EventBatch) {
+    for event in batch.take() {
+        let _ = app.emit("app-server-event", event);
+    }
+}
 
 #[derive(Clone)]
 pub(crate) struct TauriEventSink {
     app: AppHandle,
+    batch: Arc<Mutex<EventBatch>>,
 }
 
 impl TauriEventSink {
     pub(crate) fn new(app: AppHandle) -> Self {
-        Self { app }
+        Self {
+            app,
+            batch: shared_batch(),
+        }
+    }
+
+    /// Deltas still held back by `eventBatchIntervalMs`.
+    pub(crate) fn pending_events(&self) -> usize {
+        lock_batch(&self.batch).len()
+    }
+
+    /// Sends held-back deltas now instead of waiting for the timer.
+    pub(crate) fn flush(&self) {
+        flush_batch(&self.app, &mut lock_batch(&self.batch));
     }
 }
 
 impl EventSink for TauriEventSink {
+    /// With `eventBatchIntervalMs` set, streaming deltas are held and joined
+    /// until the interval passes; any other event flushes them first and is
+    /// sent right away.
     fn emit_app_server_event(&self, event: AppServerEvent) {
+        let mut batch = lock_batch(&self.batch);
+        if let Some(interval) = event_batch_core::batch_interval() {
+            if event_batch_core::is_coalesced(&event) {
+                if batch.push(event) {
+                    let app = self.app.clone();
+                    let shared = self.batch.clone();
+                    tauri::async_runtime::spawn(async move {
+                        tokio::time::sleep(interval).await;
+                        flush_batch(&app, &mut lock_batch(&shared));
+                    });
+                }
+                return;
+            }
+        }
+        flush_batch(&self.app, &mut batch);
         let _ = self.app.emit("app-server-event", event);
     }
 
@@ -25,4 +85,36 @@ impl EventSink for TauriEventSink {
     fn emit_terminal_exit(&self, event: TerminalExit) {
         let _ = self.app.emit("terminal-exit", event);
     }
+
+    fn emit_operation_event(&self, event: OperationEvent) {
+        let _ = self.app.emit("operation-event", event);
+    }
+
+    fn emit_remote_updates_event(&self, event: RemoteUpdatesEvent) {
+        let _ = self.app.emit("remote-updates-available", event);
+    }
+
+    fn emit_network_status_event(&self, event: NetworkStatus) {
+        let _ = self.app.emit("network-status-changed", event);
+    }
+
+    fn emit_clone_progress_event(&self, event: CloneProgressEvent) {
+        let _ = self.app.emit("clone-progress", event);
+    }
+
+    fn emit_git_sync_progress_event(&self, event: GitSyncProgressEvent) {
+        let _ = self.app.emit("git-sync-progress", event);
+    }
+
+    fn emit_profile_login_event(&self, event: ProfileLoginEvent) {
+        let _ = self.app.emit("profile-login", event);
+    }
+
+    fn emit_message_queued_event(&self, event: MessageQueuedEvent) {
+        let _ = self.app.emit("message-queued", event);
+    }
+
+    fn emit_session_crashed_event(&self, event: SessionCrashedEvent) {
+        let _ = self.app.emit("session-crashed", event);
+    }
 }
@@ -2,12 +2,18 @@ use serde::de::DeserializeOwned;
 use serde_json::{json, Value};
 use tauri::{AppHandle, State};
 
+use crate::event_sink::TauriEventSink;
 use crate::remote_backend;
-use crate::shared::git_ui_core;
+use crate::shared::concurrency_core::parse_operation_priority;
+use crate::shared::{git_ui_core, operation_timing_core};
 use crate::state::AppState;
 use crate::types::{
-    GitCommitDiff, GitFileDiff, GitHubIssuesResponse, GitHubPullRequestComment,
-    GitHubPullRequestDiff, GitHubPullRequestsResponse, GitLogResponse,
+    GitBisectState, GitBlameLine, GitCommitDiff, GitFetchedRef, GitFileDiff, GitFileHistoryEntry,
+    GitGraphResponse, GitHubChecksResponse, GitHubCreatedPullRequest, GitHubIssuesResponse,
+    GitHubMergeResult, GitHubPullRequestComment, GitHubPullRequestDiff, GitHubPullRequestInput,
+    GitHubPullRequestsResponse, GitHubReviewComment, GitLogFilter, GitLogResponse, GitRemote,
+    GitRemoteUpdates, GitRepoStats, GitResetResult, GitStashApplyResult, GitStashEntry,
+    GitSyncResult, OperationTimeEstimate, WorkspaceAudit,
 };
 
 async fn call_remote_if_enabled(
@@ -72,6 +78,7 @@ macro_rules! try_remote_unit {
 #[tauri::command]
 pub(crate) async fn get_git_status(
     workspace_id: String,
+    priority: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<Value, String> {
@@ -79,9 +86,10 @@ pub(crate) async fn get_git_status(
         state,
         app,
         "get_git_status",
-        json!({ "workspaceId": &workspace_id })
+        json!({ "workspaceId": &workspace_id, "priority": &priority })
     );
-    git_ui_core::get_git_status_core(&state.workspaces, workspace_id).await
+    let priority = parse_operation_priority(priority.as_deref())?;
+    git_ui_core::get_git_status_core(&state.workspaces, workspace_id, priority).await
 }
 
 #[tauri::command]
@@ -137,6 +145,30 @@ pub(crate) async fn stage_git_file(
     git_ui_core::stage_git_file_core(&state.workspaces, workspace_id, path).await
 }
 
+#[tauri::command]
+pub(crate) async fn stage_git_hunk(
+    workspace_id: String,
+    path: String,
+    hunk_header: String,
+    patch: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    try_remote_unit!(
+        state,
+        app,
+        "stage_git_hunk",
+        json!({
+            "workspaceId": &workspace_id,
+            "path": &path,
+            "hunkHeader": &hunk_header,
+            "patch": &patch,
+        })
+    );
+    git_ui_core::stage_git_hunk_core(&state.workspaces, workspace_id, path, hunk_header, patch)
+        .await
+}
+
 #[tauri::command]
 pub(crate) async fn stage_git_all(
     workspace_id: String,
@@ -168,6 +200,46 @@ pub(crate) async fn unstage_git_file(
     git_ui_core::unstage_git_file_core(&state.workspaces, workspace_id, path).await
 }
 
+#[tauri::command]
+pub(crate) async fn unstage_git_hunk(
+    workspace_id: String,
+    path: String,
+    hunk_header: String,
+    patch: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    try_remote_unit!(
+        state,
+        app,
+        "unstage_git_hunk",
+        json!({
+            "workspaceId": &workspace_id,
+            "path": &path,
+            "hunkHeader": &hunk_header,
+            "patch": &patch,
+        })
+    );
+    git_ui_core::unstage_git_hunk_core(&state.workspaces, workspace_id, path, hunk_header, patch)
+        .await
+}
+
+#[tauri::command]
+pub(crate) async fn unstage_git_all(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<usize, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "unstage_git_all",
+        json!({ "workspaceId": &workspace_id }),
+        usize
+    );
+    git_ui_core::unstage_git_all_core(&state.workspaces, workspace_id).await
+}
+
 #[tauri::command]
 pub(crate) async fn revert_git_file(
     workspace_id: String,
@@ -199,6 +271,31 @@ pub(crate) async fn revert_git_all(
     git_ui_core::revert_git_all_core(&state.workspaces, workspace_id).await
 }
 
+#[tauri::command]
+pub(crate) async fn git_reset(
+    workspace_id: String,
+    target: Option<String>,
+    mode: String,
+    confirm: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitResetResult, String> {
+    let confirm = confirm.unwrap_or(false);
+    try_remote_typed!(
+        state,
+        app,
+        "git_reset",
+        json!({
+            "workspaceId": &workspace_id,
+            "target": &target,
+            "mode": &mode,
+            "confirm": confirm,
+        }),
+        GitResetResult
+    );
+    git_ui_core::git_reset_core(&state.workspaces, workspace_id, target, mode, confirm).await
+}
+
 #[tauri::command]
 pub(crate) async fn commit_git(
     workspace_id: String,
@@ -215,6 +312,30 @@ pub(crate) async fn commit_git(
     git_ui_core::commit_git_core(&state.workspaces, workspace_id, message).await
 }
 
+#[tauri::command]
+pub(crate) async fn git_commit(
+    workspace_id: String,
+    message: String,
+    amend: bool,
+    signoff: bool,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "git_commit",
+        json!({
+            "workspaceId": &workspace_id,
+            "message": &message,
+            "amend": amend,
+            "signoff": signoff,
+        }),
+        String
+    );
+    git_ui_core::git_commit_core(&state.workspaces, workspace_id, message, amend, signoff).await
+}
+
 #[tauri::command]
 pub(crate) async fn push_git(
     workspace_id: String,
@@ -233,6 +354,8 @@ pub(crate) async fn push_git(
 #[tauri::command]
 pub(crate) async fn pull_git(
     workspace_id: String,
+    operation_id: Option<String>,
+    priority: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<(), String> {
@@ -240,14 +363,210 @@ pub(crate) async fn pull_git(
         state,
         app,
         "pull_git",
-        json!({ "workspaceId": &workspace_id })
+        json!({
+            "workspaceId": &workspace_id,
+            "operationId": operation_id,
+            "priority": &priority,
+        })
+    );
+    let priority = parse_operation_priority(priority.as_deref())?;
+    state
+        .operations
+        .run("pull", Some(workspace_id.clone()), operation_id, |_| {
+            git_ui_core::pull_git_core(&state.workspaces, workspace_id, priority)
+        })
+        .await
+}
+
+#[tauri::command]
+pub(crate) async fn git_push(
+    workspace_id: String,
+    set_upstream: bool,
+    priority: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitSyncResult, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "git_push",
+        json!({
+            "workspaceId": &workspace_id,
+            "setUpstream": set_upstream,
+            "priority": &priority,
+        }),
+        GitSyncResult
+    );
+    let priority = parse_operation_priority(priority.as_deref())?;
+    let event_sink = TauriEventSink::new(app.clone());
+    git_ui_core::git_push_core(
+        &state.workspaces,
+        workspace_id,
+        set_upstream,
+        priority,
+        &event_sink,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn git_pull(
+    workspace_id: String,
+    rebase: bool,
+    priority: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitSyncResult, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "git_pull",
+        json!({
+            "workspaceId": &workspace_id,
+            "rebase": rebase,
+            "priority": &priority,
+        }),
+        GitSyncResult
     );
-    git_ui_core::pull_git_core(&state.workspaces, workspace_id).await
+    let priority = parse_operation_priority(priority.as_deref())?;
+    let event_sink = TauriEventSink::new(app.clone());
+    git_ui_core::git_pull_core(
+        &state.workspaces,
+        workspace_id,
+        rebase,
+        priority,
+        &event_sink,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn git_fetch(
+    workspace_id: String,
+    remote: Option<String>,
+    prune: bool,
+    priority: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<GitFetchedRef>, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "git_fetch",
+        json!({
+            "workspaceId": &workspace_id,
+            "remote": &remote,
+            "prune": prune,
+            "priority": &priority,
+        }),
+        Vec<GitFetchedRef>
+    );
+    let priority = parse_operation_priority(priority.as_deref())?;
+    let event_sink = TauriEventSink::new(app.clone());
+    git_ui_core::git_fetch_core(
+        &state.workspaces,
+        workspace_id,
+        remote,
+        prune,
+        priority,
+        &event_sink,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn git_stash_list(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<GitStashEntry>, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "git_stash_list",
+        json!({ "workspaceId": &workspace_id }),
+        Vec<GitStashEntry>
+    );
+    git_ui_core::git_stash_list_core(&state.workspaces, workspace_id).await
+}
+
+#[tauri::command]
+pub(crate) async fn git_stash_push(
+    workspace_id: String,
+    message: Option<String>,
+    include_untracked: bool,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    try_remote_unit!(
+        state,
+        app,
+        "git_stash_push",
+        json!({
+            "workspaceId": &workspace_id,
+            "message": &message,
+            "includeUntracked": include_untracked,
+        })
+    );
+    git_ui_core::git_stash_push_core(&state.workspaces, workspace_id, message, include_untracked)
+        .await
+}
+
+#[tauri::command]
+pub(crate) async fn git_stash_apply(
+    workspace_id: String,
+    index: usize,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitStashApplyResult, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "git_stash_apply",
+        json!({ "workspaceId": &workspace_id, "index": index }),
+        GitStashApplyResult
+    );
+    git_ui_core::git_stash_apply_core(&state.workspaces, workspace_id, index).await
+}
+
+#[tauri::command]
+pub(crate) async fn git_stash_pop(
+    workspace_id: String,
+    index: usize,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitStashApplyResult, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "git_stash_pop",
+        json!({ "workspaceId": &workspace_id, "index": index }),
+        GitStashApplyResult
+    );
+    git_ui_core::git_stash_pop_core(&state.workspaces, workspace_id, index).await
+}
+
+#[tauri::command]
+pub(crate) async fn git_stash_drop(
+    workspace_id: String,
+    index: usize,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    try_remote_unit!(
+        state,
+        app,
+        "git_stash_drop",
+        json!({ "workspaceId": &workspace_id, "index": index })
+    );
+    git_ui_core::git_stash_drop_core(&state.workspaces, workspace_id, index).await
 }
 
 #[tauri::command]
 pub(crate) async fn fetch_git(
     workspace_id: String,
+    operation_id: Option<String>,
+    priority: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<(), String> {
@@ -255,9 +574,24 @@ pub(crate) async fn fetch_git(
         state,
         app,
         "fetch_git",
-        json!({ "workspaceId": &workspace_id })
+        json!({
+            "workspaceId": &workspace_id,
+            "operationId": operation_id,
+            "priority": &priority,
+        })
     );
-    git_ui_core::fetch_git_core(&state.workspaces, workspace_id).await
+    let priority = parse_operation_priority(priority.as_deref())?;
+    state
+        .operations
+        .run("fetch", Some(workspace_id.clone()), operation_id, |_| {
+            git_ui_core::fetch_git_core(
+                &state.workspaces,
+                &state.storage_path,
+                workspace_id,
+                priority,
+            )
+        })
+        .await
 }
 
 #[tauri::command]
@@ -275,6 +609,39 @@ pub(crate) async fn sync_git(
     git_ui_core::sync_git_core(&state.workspaces, workspace_id).await
 }
 
+#[tauri::command]
+pub(crate) async fn check_remote_updates(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitRemoteUpdates, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "check_remote_updates",
+        json!({ "workspaceId": &workspace_id }),
+        GitRemoteUpdates
+    );
+    git_ui_core::check_remote_updates_core(&state.workspaces, workspace_id).await
+}
+
+#[tauri::command]
+pub(crate) async fn estimate_operation_time(
+    kind: String,
+    repo_size_hint: Option<u64>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<OperationTimeEstimate, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "estimate_operation_time",
+        json!({ "kind": &kind, "repoSizeHint": repo_size_hint }),
+        OperationTimeEstimate
+    );
+    operation_timing_core::estimate_operation_time_core(&state.storage_path, kind, repo_size_hint)
+}
+
 #[tauri::command]
 pub(crate) async fn list_git_roots(
     workspace_id: String,
@@ -308,6 +675,8 @@ pub(crate) async fn get_workspace_diff(
 #[tauri::command]
 pub(crate) async fn get_git_diffs(
     workspace_id: String,
+    priority: Option<String>,
+    context_lines: Option<usize>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<Vec<GitFileDiff>, String> {
@@ -315,16 +684,66 @@ pub(crate) async fn get_git_diffs(
         state,
         app,
         "get_git_diffs",
-        json!({ "workspaceId": &workspace_id }),
+        json!({
+            "workspaceId": &workspace_id,
+            "priority": &priority,
+            "contextLines": context_lines,
+        }),
         Vec<GitFileDiff>
     );
-    git_ui_core::get_git_diffs_core(&state.workspaces, &state.app_settings, workspace_id).await
+    let priority = parse_operation_priority(priority.as_deref())?;
+    git_ui_core::get_git_diffs_core(
+        &state.workspaces,
+        &state.app_settings,
+        workspace_id,
+        priority,
+        context_lines,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_diff_range(
+    workspace_id: String,
+    base: String,
+    head: String,
+    paths: Option<Vec<String>>,
+    two_dot: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<GitFileDiff>, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "get_git_diff_range",
+        json!({
+            "workspaceId": &workspace_id,
+            "base": &base,
+            "head": &head,
+            "paths": &paths,
+            "twoDot": &two_dot,
+        }),
+        Vec<GitFileDiff>
+    );
+    git_ui_core::get_git_diff_range_core(
+        &state.workspaces,
+        &state.app_settings,
+        workspace_id,
+        base,
+        head,
+        paths.unwrap_or_default(),
+        two_dot.unwrap_or(false),
+    )
+    .await
 }
 
 #[tauri::command]
 pub(crate) async fn get_git_log(
     workspace_id: String,
+    skip: Option<usize>,
     limit: Option<usize>,
+    path: Option<String>,
+    filter: Option<GitLogFilter>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<GitLogResponse, String> {
@@ -332,10 +751,182 @@ pub(crate) async fn get_git_log(
         state,
         app,
         "get_git_log",
-        json!({ "workspaceId": &workspace_id, "limit": limit }),
+        json!({
+            "workspaceId": &workspace_id,
+            "skip": skip,
+            "limit": limit,
+            "path": &path,
+            "filter": &filter,
+        }),
         GitLogResponse
     );
-    git_ui_core::get_git_log_core(&state.workspaces, workspace_id, limit).await
+    git_ui_core::get_git_log_core(&state.workspaces, workspace_id, skip, limit, path, filter).await
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_graph(
+    workspace_id: String,
+    max_count: Option<usize>,
+    refs: Option<Vec<String>>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitGraphResponse, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "get_git_graph",
+        json!({ "workspaceId": &workspace_id, "maxCount": max_count, "refs": &refs }),
+        GitGraphResponse
+    );
+    git_ui_core::get_git_graph_core(&state.workspaces, workspace_id, max_count, refs).await
+}
+
+#[tauri::command]
+pub(crate) async fn get_file_history(
+    workspace_id: String,
+    path: String,
+    follow_renames: Option<bool>,
+    max_count: Option<usize>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<GitFileHistoryEntry>, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "get_file_history",
+        json!({
+            "workspaceId": &workspace_id,
+            "path": &path,
+            "followRenames": follow_renames,
+            "maxCount": max_count,
+        }),
+        Vec<GitFileHistoryEntry>
+    );
+    git_ui_core::get_file_history_core(
+        &state.workspaces,
+        workspace_id,
+        path,
+        follow_renames.unwrap_or(true),
+        max_count,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn git_blame(
+    workspace_id: String,
+    path: String,
+    rev: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<GitBlameLine>, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "git_blame",
+        json!({ "workspaceId": &workspace_id, "path": &path, "rev": &rev }),
+        Vec<GitBlameLine>
+    );
+    git_ui_core::git_blame_core(&state.workspaces, workspace_id, path, rev).await
+}
+
+#[tauri::command]
+pub(crate) async fn get_repo_stats(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitRepoStats, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "get_repo_stats",
+        json!({ "workspaceId": &workspace_id }),
+        GitRepoStats
+    );
+    git_ui_core::get_repo_stats_core(&state.workspaces, workspace_id).await
+}
+
+#[tauri::command]
+pub(crate) async fn audit_workspace(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceAudit, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "audit_workspace",
+        json!({ "workspaceId": &workspace_id }),
+        WorkspaceAudit
+    );
+    git_ui_core::audit_workspace_core(&state.workspaces, &state.app_settings, workspace_id).await
+}
+
+#[tauri::command]
+pub(crate) async fn git_bisect_start(
+    workspace_id: String,
+    bad: String,
+    good: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitBisectState, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "git_bisect_start",
+        json!({ "workspaceId": &workspace_id, "bad": &bad, "good": &good }),
+        GitBisectState
+    );
+    git_ui_core::git_bisect_start_core(&state.workspaces, workspace_id, bad, good).await
+}
+
+#[tauri::command]
+pub(crate) async fn git_bisect_mark(
+    workspace_id: String,
+    verdict: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitBisectState, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "git_bisect_mark",
+        json!({ "workspaceId": &workspace_id, "verdict": &verdict }),
+        GitBisectState
+    );
+    git_ui_core::git_bisect_mark_core(&state.workspaces, workspace_id, verdict).await
+}
+
+#[tauri::command]
+pub(crate) async fn git_bisect_reset(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitBisectState, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "git_bisect_reset",
+        json!({ "workspaceId": &workspace_id }),
+        GitBisectState
+    );
+    git_ui_core::git_bisect_reset_core(&state.workspaces, workspace_id).await
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_bisect_state(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitBisectState, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "get_git_bisect_state",
+        json!({ "workspaceId": &workspace_id }),
+        GitBisectState
+    );
+    git_ui_core::get_git_bisect_state_core(&state.workspaces, workspace_id).await
 }
 
 #[tauri::command]
@@ -372,6 +963,22 @@ pub(crate) async fn get_git_remote(
     git_ui_core::get_git_remote_core(&state.workspaces, workspace_id).await
 }
 
+#[tauri::command]
+pub(crate) async fn list_git_remotes(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<GitRemote>, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "list_git_remotes",
+        json!({ "workspaceId": &workspace_id }),
+        Vec<GitRemote>
+    );
+    git_ui_core::list_git_remotes_core(&state.workspaces, workspace_id).await
+}
+
 #[tauri::command]
 pub(crate) async fn get_github_issues(
     workspace_id: String,
@@ -439,6 +1046,24 @@ pub(crate) async fn get_github_pull_request_comments(
         .await
 }
 
+#[tauri::command]
+pub(crate) async fn get_github_pull_request_checks(
+    workspace_id: String,
+    pr_number: u64,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitHubChecksResponse, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "get_github_pull_request_checks",
+        json!({ "workspaceId": &workspace_id, "prNumber": pr_number }),
+        GitHubChecksResponse
+    );
+    git_ui_core::get_github_pull_request_checks_core(&state.workspaces, workspace_id, pr_number)
+        .await
+}
+
 #[tauri::command]
 pub(crate) async fn checkout_github_pull_request(
     workspace_id: String,
@@ -455,6 +1080,92 @@ pub(crate) async fn checkout_github_pull_request(
     git_ui_core::checkout_github_pull_request_core(&state.workspaces, workspace_id, pr_number).await
 }
 
+#[tauri::command]
+pub(crate) async fn create_github_pull_request(
+    workspace_id: String,
+    pull_request: GitHubPullRequestInput,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitHubCreatedPullRequest, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "create_github_pull_request",
+        json!({ "workspaceId": &workspace_id, "pullRequest": &pull_request }),
+        GitHubCreatedPullRequest
+    );
+    git_ui_core::create_github_pull_request_core(&state.workspaces, workspace_id, pull_request)
+        .await
+}
+
+#[tauri::command]
+pub(crate) async fn submit_github_pull_request_review(
+    workspace_id: String,
+    pr_number: u64,
+    event: String,
+    body: Option<String>,
+    comments: Option<Vec<GitHubReviewComment>>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<u64, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "submit_github_pull_request_review",
+        json!({
+            "workspaceId": &workspace_id,
+            "prNumber": pr_number,
+            "event": &event,
+            "body": &body,
+            "comments": &comments,
+        }),
+        u64
+    );
+    git_ui_core::submit_github_pull_request_review_core(
+        &state.workspaces,
+        workspace_id,
+        pr_number,
+        event,
+        body,
+        comments.unwrap_or_default(),
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn merge_github_pull_request(
+    workspace_id: String,
+    pr_number: u64,
+    method: String,
+    commit_title: Option<String>,
+    commit_message: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitHubMergeResult, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "merge_github_pull_request",
+        json!({
+            "workspaceId": &workspace_id,
+            "prNumber": pr_number,
+            "method": &method,
+            "commitTitle": &commit_title,
+            "commitMessage": &commit_message,
+        }),
+        GitHubMergeResult
+    );
+    git_ui_core::merge_github_pull_request_core(
+        &state.workspaces,
+        workspace_id,
+        pr_number,
+        method,
+        commit_title,
+        commit_message,
+    )
+    .await
+}
+
 #[tauri::command]
 pub(crate) async fn list_git_branches(
     workspace_id: String,
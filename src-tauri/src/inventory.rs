@@ -0,0 +1,31 @@
+use serde_json::json;
+use tauri::{AppHandle, State};
+
+use crate::remote_backend;
+use crate::shared::inventory_core;
+use crate::state::AppState;
+use crate::terminal;
+use crate::types::SystemInventory;
+
+/// In remote mode the daemon reports its workspaces, sessions, operations
+/// and schedulers; terminals always run in the app, so they're added here.
+#[tauri::command]
+pub(crate) async fn get_system_inventory(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<SystemInventory, String> {
+    let mut inventory = if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "get_system_inventory", json!({})).await?;
+        serde_json::from_value(response).map_err(|err| err.to_string())?
+    } else {
+        inventory_core::get_system_inventory_core(
+            &state.workspaces,
+            &state.sessions,
+            state.operations.list(),
+        )
+        .await
+    };
+    inventory.terminals = terminal::terminal_inventory(&state).await;
+    Ok(inventory)
+}
@@ -3,30 +3,37 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use tauri::Manager;
 #[cfg(desktop)]
 use tauri::RunEvent;
-#[cfg(target_os = "macos")]
 use tauri::WindowEvent;
 
+mod app_lock;
 mod backend;
 mod codex;
+mod credentials;
 mod daemon_binary;
 mod dictation;
 mod event_sink;
 mod files;
 mod git;
 mod git_utils;
+mod inventory;
 mod local_usage;
 #[cfg(desktop)]
 mod menu;
 #[cfg(not(desktop))]
 #[path = "menu_mobile.rs"]
 mod menu;
+mod network;
 mod notifications;
+mod operations;
 mod orbit;
+mod profiles;
 mod prompts;
+mod redaction;
 mod remote_backend;
 mod rules;
 mod settings;
 mod shared;
+mod shutdown;
 mod state;
 mod storage;
 mod tailscale;
@@ -108,6 +115,9 @@ pub fn run() {
             if window.label() != "main" {
                 return;
             }
+            if let WindowEvent::Focused(focused) = event {
+                app_lock::note_window_focus(*focused);
+            }
             #[cfg(target_os = "macos")]
             if let WindowEvent::CloseRequested { api, .. } = event {
                 api.prevent_close();
@@ -116,7 +126,75 @@ pub fn run() {
         })
         .setup(|app| {
             let state = state::AppState::load(&app.handle());
+            let launch_settings = tauri::async_runtime::block_on(state.app_settings.lock()).clone();
             app.manage(state);
+            {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::block_on(app_lock::lock_on_launch(
+                    &app_handle,
+                    &launch_settings,
+                ));
+                tauri::async_runtime::spawn(app_lock::run_idle_monitor(app_handle));
+            }
+            {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    shared::network_core::run_network_monitor(event_sink::TauriEventSink::new(
+                        app_handle,
+                    ))
+                    .await;
+                });
+            }
+            if matches!(
+                launch_settings.backend_mode,
+                crate::types::BackendMode::Local
+            ) {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let state = app_handle.state::<state::AppState>();
+                    shared::workspaces_core::check_codex_bins_on_startup(
+                        &state.workspaces,
+                        &state.app_settings,
+                        &event_sink::TauriEventSink::new(app_handle.clone()),
+                    )
+                    .await;
+                });
+            }
+            {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let state = app_handle.state::<state::AppState>();
+                    shared::auto_fetch_core::run_auto_fetch_scheduler(
+                        &state.workspaces,
+                        &state.sessions,
+                        &state.storage_path,
+                        event_sink::TauriEventSink::new(app_handle.clone()),
+                    )
+                    .await;
+                });
+            }
+            {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let state = app_handle.state::<state::AppState>();
+                    shared::session_recovery_core::run_session_supervisor(
+                        &state.workspaces,
+                        &state.sessions,
+                        &state.app_settings,
+                        event_sink::TauriEventSink::new(app_handle.clone()),
+                        |entry, default_bin, codex_args, codex_home| {
+                            codex::spawn_workspace_session(
+                                entry,
+                                default_bin,
+                                codex_args,
+                                app_handle.clone(),
+                                codex_home,
+                            )
+                        },
+                    )
+                    .await;
+                });
+            }
             #[cfg(desktop)]
             {
                 let app_handle = app.handle().clone();
@@ -209,7 +287,7 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_notification::init())
-        .invoke_handler(tauri::generate_handler![
+        .invoke_handler(app_lock::guard_invoke_handler(tauri::generate_handler![
             settings::get_app_settings,
             settings::update_app_settings,
             settings::get_codex_config_path,
@@ -218,6 +296,9 @@ pub fn run() {
             codex::get_config_model,
             menu::menu_set_accelerators,
             codex::codex_doctor,
+            codex::get_session_startup_stats,
+            codex::find_orphaned_codex_processes,
+            codex::kill_orphaned_codex_processes,
             codex::codex_update,
             workspaces::list_workspaces,
             workspaces::is_workspace_path_dir,
@@ -228,53 +309,139 @@ pub fn run() {
             workspaces::worktree_setup_mark_ran,
             workspaces::remove_workspace,
             workspaces::remove_worktree,
+            workspaces::remove_workspaces,
             workspaces::rename_worktree,
             workspaces::rename_worktree_upstream,
             workspaces::apply_worktree_changes,
+            workspaces::apply_worktree_commits,
+            workspaces::move_worktree_changes_back,
+            workspaces::preview_worktree_changes,
             workspaces::update_workspace_settings,
             workspaces::update_workspace_codex_bin,
+            workspaces::check_workspace_codex_bins,
+            workspaces::repoint_codex_bin,
+            workspaces::set_workspace_color,
+            workspaces::rename_workspace,
+            workspaces::relocate_workspace,
+            workspaces::export_workspace_bundle,
+            workspaces::import_workspace_bundle,
+            workspaces::list_workspace_groups,
+            workspaces::create_workspace_group,
+            workspaces::rename_workspace_group,
+            workspaces::delete_workspace_group,
+            workspaces::set_workspace_profile,
+            profiles::list_profiles,
+            profiles::create_profile,
+            profiles::delete_profile,
+            profiles::codex_profile_login,
+            profiles::codex_profile_logout,
             codex::start_thread,
             codex::send_user_message,
             codex::turn_steer,
             codex::turn_interrupt,
+            codex::interrupt_all_turns,
+            codex::clear_message_queue,
             codex::start_review,
             codex::respond_to_server_request,
             codex::remember_approval_rule,
+            codex::list_approval_rules,
+            codex::delete_approval_rule,
+            codex::test_approval_rule,
+            codex::export_approval_rules,
+            codex::import_approval_rules,
             codex::generate_commit_message,
             codex::generate_run_metadata,
             codex::resume_thread,
             codex::fork_thread,
+            codex::replay_thread,
+            codex::diff_threads,
+            codex::get_thread_window,
+            codex::export_thread,
+            codex::search_threads,
+            codex::estimate_message_tokens,
+            codex::thread_usage,
+            codex::capture_turn_trace,
+            codex::load_turn_trace,
+            codex::pin_thread_message,
+            codex::unpin_thread_message,
+            codex::list_pinned_messages,
             codex::list_threads,
             codex::list_mcp_server_status,
             codex::archive_thread,
             codex::compact_thread,
             codex::set_thread_name,
+            codex::rename_thread,
             codex::collaboration_mode_list,
             workspaces::connect_workspace,
+            workspaces::prewarm_sessions,
             git::get_git_status,
             git::init_git_repo,
             git::create_github_repo,
             git::list_git_roots,
             git::get_git_diffs,
+            git::get_git_diff_range,
             git::get_git_log,
+            git::get_git_graph,
+            git::get_file_history,
+            git::git_blame,
+            git::get_repo_stats,
+            git::audit_workspace,
+            git::git_bisect_start,
+            git::git_bisect_mark,
+            git::git_bisect_reset,
+            git::get_git_bisect_state,
             git::get_git_commit_diff,
             git::get_git_remote,
+            git::list_git_remotes,
             git::stage_git_file,
+            git::stage_git_hunk,
             git::stage_git_all,
             git::unstage_git_file,
+            git::unstage_git_hunk,
+            git::unstage_git_all,
             git::revert_git_file,
             git::revert_git_all,
+            git::git_reset,
             git::commit_git,
+            git::git_commit,
             git::push_git,
             git::pull_git,
+            git::git_push,
+            git::git_pull,
+            git::git_fetch,
+            git::git_stash_list,
+            git::git_stash_push,
+            git::git_stash_apply,
+            git::git_stash_pop,
+            git::git_stash_drop,
             git::fetch_git,
             git::sync_git,
+            git::check_remote_updates,
+            git::estimate_operation_time,
+            operations::cancel_operation,
+            inventory::get_system_inventory,
+            operations::list_operations,
+            shutdown::prepare_shutdown,
+            shutdown::graceful_shutdown,
+            network::get_network_status,
+            redaction::redact_secrets,
+            credentials::set_credential,
+            credentials::get_credential_status,
+            credentials::delete_credential,
+            app_lock::get_app_lock_status,
+            app_lock::lock_app,
+            app_lock::unlock_app,
             git::get_github_issues,
             git::get_github_pull_requests,
             git::get_github_pull_request_diff,
             git::get_github_pull_request_comments,
+            git::get_github_pull_request_checks,
             git::checkout_github_pull_request,
+            git::create_github_pull_request,
+            git::submit_github_pull_request_review,
+            git::merge_github_pull_request,
             workspaces::list_workspace_files,
+            workspaces::list_workspace_files_detailed,
             workspaces::read_workspace_file,
             workspaces::open_workspace_in,
             workspaces::get_open_app_icon,
@@ -286,6 +453,7 @@ pub fn run() {
             codex::set_codex_feature_flag,
             codex::account_rate_limits,
             codex::account_read,
+            codex::get_session_account,
             codex::codex_login,
             codex::codex_login_cancel,
             codex::skills_list,
@@ -295,19 +463,27 @@ pub fn run() {
             prompts::prompts_update,
             prompts::prompts_delete,
             prompts::prompts_move,
+            prompts::prompts_duplicate,
+            prompts::prompts_render,
             prompts::prompts_workspace_dir,
             prompts::prompts_global_dir,
             terminal::terminal_open,
             terminal::terminal_write,
             terminal::terminal_resize,
+            terminal::terminal_signal,
             terminal::terminal_close,
             dictation::dictation_model_status,
+            dictation::dictation_list_available_models,
+            dictation::dictation_select_model,
             dictation::dictation_download_model,
             dictation::dictation_cancel_download,
             dictation::dictation_remove_model,
             dictation::dictation_start,
             dictation::dictation_request_permission,
             dictation::dictation_stop,
+            dictation::dictation_transcribe_file,
+            dictation::dictation_supported_languages,
+            dictation::dictation_list_input_devices,
             dictation::dictation_cancel,
             local_usage::local_usage_snapshot,
             notifications::is_macos_debug_build,
@@ -325,7 +501,7 @@ pub fn run() {
             tailscale::tailscale_daemon_stop,
             tailscale::tailscale_daemon_status,
             is_mobile_runtime
-        ])
+        ]))
         .build(tauri::generate_context!())
         .expect("error while running tauri application");
 
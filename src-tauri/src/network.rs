@@ -0,0 +1,10 @@
+use crate::shared::network_core;
+use crate::types::NetworkStatus;
+
+/// Always answered locally: in remote mode the question is still whether
+/// this machine has a connection, and the daemon could not be reached to
+/// ask otherwise.
+#[tauri::command]
+pub(crate) async fn get_network_status() -> Result<NetworkStatus, String> {
+    Ok(network_core::get_network_status_core())
+}
@@ -0,0 +1,40 @@
+use serde_json::json;
+use tauri::{AppHandle, State};
+
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::types::OperationInfo;
+
+#[tauri::command]
+pub(crate) async fn list_operations(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<OperationInfo>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "list_operations", json!({})).await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    Ok(state.operations.list())
+}
+
+#[tauri::command]
+pub(crate) async fn cancel_operation(
+    operation_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<bool, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "cancel_operation",
+            json!({ "operationId": operation_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    Ok(state.operations.cancel(&operation_id))
+}
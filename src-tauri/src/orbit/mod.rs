@@ -1,5 +1,4 @@
 use std::process::Stdio;
-use std::time::{SystemTime, UNIX_EPOCH};
 
 use serde::{Deserialize, Serialize};
 use tauri::State;
@@ -14,6 +13,7 @@ use crate::types::{
     OrbitConnectTestResult, OrbitRunnerState, OrbitRunnerStatus, OrbitSignInPollResult,
     OrbitSignInStatus, OrbitSignOutResult,
 };
+use crate::utils::now_unix_ms;
 
 const CURRENT_APP_VERSION: &str = env!("CARGO_PKG_VERSION");
 const ORBIT_RUNNER_RECORD_FILE: &str = "orbit_runner.json";
@@ -91,13 +91,6 @@ async fn is_pid_running(_pid: u32) -> bool {
     false
 }
 
-fn now_unix_ms() -> i64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|duration| duration.as_millis() as i64)
-        .unwrap_or(0)
-}
-
 async fn refresh_runner_runtime(runtime: &mut OrbitRunnerRuntime) {
     let Some(child) = runtime.child.as_mut() else {
         runtime.status.state = OrbitRunnerState::Stopped;
@@ -0,0 +1,107 @@
+use serde_json::json;
+use tauri::{AppHandle, State};
+
+use crate::event_sink::TauriEventSink;
+use crate::remote_backend;
+use crate::shared::profiles_core;
+use crate::state::AppState;
+use crate::types::CodexProfile;
+
+#[tauri::command]
+pub(crate) async fn list_profiles(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<CodexProfile>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "list_profiles", json!({})).await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+    Ok(profiles_core::list_profiles_core(&state.app_settings).await)
+}
+
+#[tauri::command]
+pub(crate) async fn create_profile(
+    name: String,
+    codex_home: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<CodexProfile, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let codex_home = remote_backend::normalize_path_for_remote(codex_home);
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "create_profile",
+            json!({ "name": name, "codexHome": codex_home }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+    profiles_core::create_profile_core(name, codex_home, &state.app_settings, &state.settings_path)
+        .await
+}
+
+#[tauri::command]
+pub(crate) async fn delete_profile(
+    profile_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "delete_profile",
+            json!({ "profileId": profile_id }),
+        )
+        .await?;
+        return Ok(());
+    }
+    profiles_core::delete_profile_core(
+        profile_id,
+        &state.workspaces,
+        &state.app_settings,
+        &state.settings_path,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn codex_profile_login(
+    profile_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "codex_profile_login",
+            json!({ "profileId": profile_id }),
+        )
+        .await?;
+        return Ok(());
+    }
+    let event_sink = TauriEventSink::new(app);
+    profiles_core::codex_profile_login_core(profile_id, &state.app_settings, &event_sink).await
+}
+
+#[tauri::command]
+pub(crate) async fn codex_profile_logout(
+    profile_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "codex_profile_logout",
+            json!({ "profileId": profile_id }),
+        )
+        .await?;
+        return Ok(());
+    }
+    profiles_core::codex_profile_logout_core(profile_id, &state.app_settings).await
+}
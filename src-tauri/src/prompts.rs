@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use tauri::State;
 
 use crate::shared::prompts_core::{self, CustomPromptEntry};
@@ -33,6 +35,7 @@ pub(crate) async fn prompts_create(
     state: State<'_, AppState>,
     workspace_id: String,
     scope: String,
+    folder: Option<String>,
     name: String,
     description: Option<String>,
     argument_hint: Option<String>,
@@ -43,6 +46,7 @@ pub(crate) async fn prompts_create(
         &state.settings_path,
         workspace_id,
         scope,
+        folder,
         name,
         description,
         argument_hint,
@@ -90,6 +94,7 @@ pub(crate) async fn prompts_move(
     workspace_id: String,
     path: String,
     scope: String,
+    folder: Option<String>,
 ) -> Result<CustomPromptEntry, String> {
     prompts_core::prompts_move_core(
         &state.workspaces,
@@ -97,6 +102,41 @@ pub(crate) async fn prompts_move(
         workspace_id,
         path,
         scope,
+        folder,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn prompts_duplicate(
+    state: State<'_, AppState>,
+    workspace_id: String,
+    path: String,
+) -> Result<CustomPromptEntry, String> {
+    prompts_core::prompts_duplicate_core(
+        &state.workspaces,
+        &state.settings_path,
+        workspace_id,
+        path,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn prompts_render(
+    state: State<'_, AppState>,
+    workspace_id: String,
+    path: String,
+    vars: HashMap<String, String>,
+    leave_unfilled: Option<bool>,
+) -> Result<String, String> {
+    prompts_core::prompts_render_core(
+        &state.workspaces,
+        &state.settings_path,
+        workspace_id,
+        path,
+        vars,
+        leave_unfilled.unwrap_or(false),
     )
     .await
 }
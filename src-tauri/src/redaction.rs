@@ -0,0 +1,8 @@
+use crate::shared::redaction_core;
+
+/// Runs the secret patterns over arbitrary text, such as the debug log before
+/// it is copied out. Answered locally with this machine's settings.
+#[tauri::command]
+pub(crate) fn redact_secrets(text: String) -> String {
+    redaction_core::redact_for_log(&text)
+}
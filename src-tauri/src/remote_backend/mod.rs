@@ -150,32 +150,44 @@ fn can_retry_after_disconnect(method: &str) -> bool {
         "account_rate_limits"
             | "account_read"
             | "apps_list"
+            | "check_workspace_codex_bins"
             | "collaboration_mode_list"
             | "connect_workspace"
+            | "estimate_message_tokens"
             | "experimental_feature_list"
             | "file_read"
             | "get_config_model"
             | "get_git_commit_diff"
+            | "get_git_diff_range"
             | "get_git_diffs"
             | "get_git_log"
             | "get_git_remote"
             | "get_git_status"
             | "get_github_issues"
+            | "get_github_pull_request_checks"
             | "get_github_pull_request_comments"
             | "get_github_pull_request_diff"
             | "get_github_pull_requests"
+            | "get_system_inventory"
+            | "git_blame"
+            | "git_stash_list"
             | "is_workspace_path_dir"
             | "list_git_branches"
+            | "list_git_remotes"
             | "list_git_roots"
             | "list_mcp_server_status"
             | "list_threads"
             | "local_usage_snapshot"
             | "list_workspace_files"
+            | "list_workspace_files_detailed"
+            | "list_workspace_groups"
             | "list_workspaces"
             | "model_list"
             | "read_workspace_file"
             | "resume_thread"
+            | "search_threads"
             | "skills_list"
+            | "thread_usage"
             | "worktree_setup_status"
     )
 }
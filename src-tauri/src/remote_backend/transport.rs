@@ -10,6 +10,8 @@ use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader
 use tokio::sync::{mpsc, oneshot, Mutex};
 
 use super::protocol::{parse_incoming_line, IncomingMessage, DISCONNECTED_MESSAGE};
+use crate::backend::events::{AppServerEvent, EventSink};
+use crate::event_sink::TauriEventSink;
 
 pub(crate) type PendingMap = HashMap<u64, oneshot::Sender<Result<Value, String>>>;
 const OUTBOUND_QUEUE_CAPACITY: usize = 512;
@@ -140,7 +142,22 @@ pub(crate) async fn dispatch_incoming_line(
         }
         IncomingMessage::Notification { method, params } => match method.as_str() {
             "app-server-event" => {
-                let _ = app.emit("app-server-event", params);
+                // Through the sink so remote deltas are batched like local ones.
+                let workspace_id = params
+                    .get("workspace_id")
+                    .and_then(Value::as_str)
+                    .map(str::to_string);
+                match (workspace_id, params.get("message")) {
+                    (Some(workspace_id), Some(message)) => {
+                        TauriEventSink::new(app.clone()).emit_app_server_event(AppServerEvent {
+                            workspace_id,
+                            message: message.clone(),
+                        });
+                    }
+                    _ => {
+                        let _ = app.emit("app-server-event", params);
+                    }
+                }
             }
             "terminal-output" => {
                 let _ = app.emit("terminal-output", params);
@@ -148,6 +165,27 @@ pub(crate) async fn dispatch_incoming_line(
             "terminal-exit" => {
                 let _ = app.emit("terminal-exit", params);
             }
+            "operation-event" => {
+                let _ = app.emit("operation-event", params);
+            }
+            "remote-updates-available" => {
+                let _ = app.emit("remote-updates-available", params);
+            }
+            "clone-progress" => {
+                let _ = app.emit("clone-progress", params);
+            }
+            "git-sync-progress" => {
+                let _ = app.emit("git-sync-progress", params);
+            }
+            "profile-login" => {
+                let _ = app.emit("profile-login", params);
+            }
+            "message-queued" => {
+                let _ = app.emit("message-queued", params);
+            }
+            "session-crashed" => {
+                let _ = app.emit("session-crashed", params);
+            }
             _ => {}
         },
     }
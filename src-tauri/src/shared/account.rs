@@ -99,6 +99,62 @@ pub(crate) fn read_auth_account(codex_home: Option<PathBuf>) -> Option<AuthAccou
     })
 }
 
+/// Access tokens closer than this to expiry, with no refresh token to renew
+/// them, are reported as `expiring`.
+const EXPIRY_WARNING_SECS: i64 = 10 * 60;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct AuthFileStatus {
+    pub(crate) status: &'static str,
+    pub(crate) expires_at: Option<i64>,
+}
+
+/// Reads `auth.json` without touching the network. Codex renews an expired
+/// access token itself when it has a refresh token, so that case is
+/// `needsRefresh` rather than `expired`.
+pub(crate) fn read_auth_status(codex_home: Option<PathBuf>, now_secs: i64) -> AuthFileStatus {
+    let auth_value = codex_home
+        .map(|home| home.join("auth.json"))
+        .and_then(|path| fs::read(path).ok())
+        .and_then(|data| serde_json::from_slice::<Value>(&data).ok());
+    match auth_value {
+        Some(value) => auth_status_from_value(&value, now_secs),
+        None => AuthFileStatus {
+            status: "missing",
+            expires_at: None,
+        },
+    }
+}
+
+pub(crate) fn auth_status_from_value(auth_value: &Value, now_secs: i64) -> AuthFileStatus {
+    let token = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| auth_value.get("tokens")?.get(*key))
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    let access_token = token(&["accessToken", "access_token"]);
+    let Some(access_token) = access_token else {
+        let has_api_key = normalize_string(auth_value.get("OPENAI_API_KEY")).is_some();
+        return AuthFileStatus {
+            status: if has_api_key { "apiKey" } else { "missing" },
+            expires_at: None,
+        };
+    };
+    let has_refresh_token = token(&["refreshToken", "refresh_token"]).is_some();
+    let expires_at = decode_jwt_payload(&access_token)
+        .and_then(|payload| payload.get("exp").and_then(Value::as_i64));
+    let status = match expires_at {
+        Some(exp) if exp <= now_secs && has_refresh_token => "needsRefresh",
+        Some(exp) if exp <= now_secs => "expired",
+        Some(exp) if exp - now_secs < EXPIRY_WARNING_SECS && !has_refresh_token => "expiring",
+        _ => "valid",
+    };
+    AuthFileStatus { status, expires_at }
+}
+
 fn extract_account_map(value: &Value) -> Option<Map<String, Value>> {
     let account = value
         .get("account")
@@ -198,6 +254,39 @@ mod tests {
         assert_eq!(account.get("type").and_then(Value::as_str), Some("chatgpt"));
     }
 
+    fn jwt_with_exp(exp: i64) -> String {
+        let payload = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .encode(json!({ "exp": exp }).to_string());
+        format!("header.{payload}.signature")
+    }
+
+    #[test]
+    fn auth_status_reports_expiry_and_refreshability() {
+        let now = 1_700_000_000;
+        let expired = json!({ "tokens": { "access_token": jwt_with_exp(now - 60) } });
+        assert_eq!(auth_status_from_value(&expired, now).status, "expired");
+
+        let refreshable = json!({
+            "tokens": {
+                "access_token": jwt_with_exp(now - 60),
+                "refresh_token": "refresh"
+            }
+        });
+        assert_eq!(
+            auth_status_from_value(&refreshable, now).status,
+            "needsRefresh"
+        );
+
+        let expiring = json!({ "tokens": { "access_token": jwt_with_exp(now + 120) } });
+        let status = auth_status_from_value(&expiring, now);
+        assert_eq!(status.status, "expiring");
+        assert_eq!(status.expires_at, Some(now + 120));
+
+        let api_key = json!({ "OPENAI_API_KEY": "sk-test" });
+        assert_eq!(auth_status_from_value(&api_key, now).status, "apiKey");
+        assert_eq!(auth_status_from_value(&json!({}), now).status, "missing");
+    }
+
     #[test]
     fn build_account_response_allows_fallback_for_chatgpt_type() {
         let response = Some(json!({
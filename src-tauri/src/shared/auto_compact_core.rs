@@ -0,0 +1,231 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tokio::sync::Mutex;
+use tokio::time::timeout;
+
+use crate::backend::app_server::WorkspaceSession;
+use crate::backend::events::{AppServerEvent, EventSink};
+use crate::shared::token_estimate_core::{context_window_for_model, estimate_incoming_tokens};
+use crate::types::WorkspaceEntry;
+
+/// How long a turn waits for the compaction it triggered before going ahead
+/// anyway.
+const AUTO_COMPACT_TIMEOUT: Duration = Duration::from_secs(120);
+
+pub(crate) fn validate_auto_compact_at_pct(value: Option<u8>) -> Result<Option<u8>, String> {
+    match value {
+        None => Ok(None),
+        Some(pct) if (1..=100).contains(&pct) => Ok(Some(pct)),
+        Some(_) => Err("autoCompactAtPct must be between 1 and 100.".to_string()),
+    }
+}
+
+/// Worktrees without their own threshold use the parent's.
+pub(crate) fn resolve_auto_compact_at_pct(
+    entry: &WorkspaceEntry,
+    parent_entry: Option<&WorkspaceEntry>,
+) -> Option<u8> {
+    entry.settings.auto_compact_at_pct.or_else(|| {
+        entry
+            .kind
+            .is_worktree()
+            .then(|| parent_entry.and_then(|parent| parent.settings.auto_compact_at_pct))
+            .flatten()
+    })
+}
+
+pub(crate) async fn resolve_compact_at_pct_for_workspace(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: &str,
+) -> Option<u8> {
+    let workspaces = workspaces.lock().await;
+    let entry = workspaces.get(workspace_id)?;
+    let parent_entry = entry
+        .parent_id
+        .as_ref()
+        .and_then(|parent_id| workspaces.get(parent_id));
+    resolve_auto_compact_at_pct(entry, parent_entry)
+}
+
+/// The projected share of `context_window` in percent, or `None` while it
+/// stays under `threshold`.
+pub(crate) fn compaction_due(
+    context_tokens: u64,
+    incoming_tokens: u64,
+    context_window: u64,
+    threshold: u8,
+) -> Option<f64> {
+    if context_window == 0 {
+        return None;
+    }
+    let pct = (context_tokens + incoming_tokens) as f64 * 100.0 / context_window as f64;
+    (pct >= f64::from(threshold)).then_some(pct)
+}
+
+fn emit_auto_compaction<E: EventSink>(
+    event_sink: &E,
+    session: &WorkspaceSession,
+    thread_id: &str,
+    status: &str,
+    details: Value,
+) {
+    let mut params = json!({ "threadId": thread_id, "status": status });
+    if let (Some(params), Value::Object(details)) = (params.as_object_mut(), details) {
+        params.extend(details);
+    }
+    event_sink.emit_app_server_event(AppServerEvent {
+        workspace_id: session.entry.id.clone(),
+        message: json!({ "method": "codex/autoCompaction", "params": params }),
+    });
+}
+
+/// Compacts `thread_id` first when the message would push the context the
+/// session last saw past the workspace's `autoCompactAtPct`. Emits
+/// `codex/autoCompaction` as `started`, then `completed` or `failed`; a failed
+/// compaction never blocks the turn.
+pub(crate) async fn auto_compact_before_turn<E: EventSink>(
+    session: &WorkspaceSession,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    thread_id: &str,
+    text: &str,
+    attachment_count: usize,
+    model: Option<&str>,
+    event_sink: &E,
+) {
+    let Some(threshold) = resolve_compact_at_pct_for_workspace(workspaces, &session.entry.id).await
+    else {
+        return;
+    };
+    auto_compact_at_threshold(
+        session,
+        threshold,
+        thread_id,
+        text,
+        attachment_count,
+        model,
+        event_sink,
+    )
+    .await;
+}
+
+/// `auto_compact_before_turn` with the threshold already resolved, for a
+/// queued message that no longer has the workspaces at hand.
+pub(crate) async fn auto_compact_at_threshold<E: EventSink>(
+    session: &WorkspaceSession,
+    threshold: u8,
+    thread_id: &str,
+    text: &str,
+    attachment_count: usize,
+    model: Option<&str>,
+    event_sink: &E,
+) {
+    let Some(usage) = session.thread_context_usage(thread_id).await else {
+        return;
+    };
+    let Some(context_window) = usage
+        .context_window
+        .or_else(|| model.and_then(context_window_for_model))
+    else {
+        return;
+    };
+    let model_name = model.map(str::to_string);
+    let message = text.to_string();
+    let Ok(incoming_tokens) = tokio::task::spawn_blocking(move || {
+        estimate_incoming_tokens(model_name.as_deref(), &message, attachment_count)
+    })
+    .await
+    else {
+        return;
+    };
+    let Some(projected_pct) =
+        compaction_due(usage.tokens, incoming_tokens, context_window, threshold)
+    else {
+        return;
+    };
+
+    emit_auto_compaction(
+        event_sink,
+        session,
+        thread_id,
+        "started",
+        json!({
+            "contextTokens": usage.tokens,
+            "contextWindow": context_window,
+            "projectedPct": projected_pct,
+            "thresholdPct": threshold,
+        }),
+    );
+    let completion = session.wait_for_turn_completion(thread_id).await;
+    let error = match session
+        .send_request("thread/compact/start", json!({ "threadId": thread_id }))
+        .await
+    {
+        Err(error) => Some(error),
+        Ok(response) => match response.get("error") {
+            Some(error) => Some(
+                error
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .unwrap_or("Compaction failed.")
+                    .to_string(),
+            ),
+            None => match timeout(AUTO_COMPACT_TIMEOUT, completion).await {
+                Ok(Ok(())) => None,
+                Ok(Err(_)) => Some("Session closed during compaction.".to_string()),
+                Err(_) => Some("Timed out waiting for compaction.".to_string()),
+            },
+        },
+    };
+    match error {
+        None => emit_auto_compaction(event_sink, session, thread_id, "completed", json!({})),
+        Some(error) => emit_auto_compaction(
+            event_sink,
+            session,
+            thread_id,
+            "failed",
+            json!({ "error": error }),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{WorkspaceKind, WorkspaceSettings};
+
+    fn entry(id: &str, kind: WorkspaceKind, pct: Option<u8>) -> WorkspaceEntry {
+        WorkspaceEntry {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: format!("/tmp/{id}"),
+            codex_bin: None,
+            kind,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings {
+                auto_compact_at_pct: pct,
+                ..WorkspaceSettings::default()
+            },
+        }
+    }
+
+    #[test]
+    fn worktrees_inherit_threshold_and_compaction_triggers_at_it() {
+        let parent = entry("main", WorkspaceKind::Main, Some(80));
+        let worktree = entry("wt", WorkspaceKind::Worktree, None);
+        let other = entry("other", WorkspaceKind::Main, None);
+        assert_eq!(
+            resolve_auto_compact_at_pct(&worktree, Some(&parent)),
+            Some(80)
+        );
+        assert_eq!(resolve_auto_compact_at_pct(&other, Some(&parent)), None);
+        assert!(validate_auto_compact_at_pct(Some(0)).is_err());
+        assert!(validate_auto_compact_at_pct(Some(101)).is_err());
+
+        assert!(compaction_due(70_000, 9_000, 100_000, 80).is_none());
+        assert_eq!(compaction_due(70_000, 10_000, 100_000, 80), Some(80.0));
+        assert!(compaction_due(1, 1, 0, 80).is_none());
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::sync::Mutex;
+
+use crate::backend::app_server::WorkspaceSession;
+use crate::backend::events::{EventSink, RemoteUpdatesEvent};
+use crate::shared::concurrency_core::OperationPriority;
+use crate::shared::{git_ui_core, inventory_core, network_core, redaction_core};
+use crate::types::{WorkspaceEntry, WorkspaceSettings};
+
+const AUTO_FETCH_TICK: Duration = Duration::from_secs(15);
+const MIN_AUTO_FETCH_INTERVAL_SECS: u64 = 60;
+
+/// `None` (or 0) disables auto-fetch; anything else is raised to the minimum.
+pub(crate) fn auto_fetch_interval(settings: &WorkspaceSettings) -> Option<Duration> {
+    match settings.auto_fetch_interval_secs {
+        None | Some(0) => None,
+        Some(secs) => Some(Duration::from_secs(secs.max(MIN_AUTO_FETCH_INTERVAL_SECS))),
+    }
+}
+
+/// Connected workspaces whose interval has elapsed since their last fetch.
+/// A workspace seen for the first time waits one full interval.
+pub(crate) fn due_workspaces(
+    entries: &[WorkspaceEntry],
+    connected: &HashSet<String>,
+    last_fetch: &mut HashMap<String, Instant>,
+    now: Instant,
+) -> Vec<String> {
+    last_fetch.retain(|id, _| connected.contains(id));
+    let mut due = Vec::new();
+    for entry in entries {
+        let Some(interval) = auto_fetch_interval(&entry.settings) else {
+            last_fetch.remove(&entry.id);
+            continue;
+        };
+        if !connected.contains(&entry.id) {
+            continue;
+        }
+        let last = *last_fetch.entry(entry.id.clone()).or_insert(now);
+        if now.duration_since(last) >= interval {
+            last_fetch.insert(entry.id.clone(), now);
+            due.push(entry.id.clone());
+        }
+    }
+    due
+}
+
+/// Runs for the lifetime of the process. Fetches at background priority and
+/// emits `remote-updates-available` when the upstream gains new commits.
+/// Ticks are skipped while offline; overdue workspaces fetch on reconnect.
+pub(crate) async fn run_auto_fetch_scheduler<E: EventSink>(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    storage_path: &Path,
+    event_sink: E,
+) {
+    let mut last_fetch: HashMap<String, Instant> = HashMap::new();
+    let mut last_behind: HashMap<String, usize> = HashMap::new();
+    inventory_core::scheduler_started("autoFetch");
+    loop {
+        tokio::time::sleep(AUTO_FETCH_TICK).await;
+        inventory_core::scheduler_ticked("autoFetch");
+        if !network_core::is_online() {
+            continue;
+        }
+        let entries: Vec<WorkspaceEntry> = workspaces.lock().await.values().cloned().collect();
+        let connected: HashSet<String> = sessions.lock().await.keys().cloned().collect();
+        for workspace_id in due_workspaces(&entries, &connected, &mut last_fetch, Instant::now()) {
+            if let Err(error) = git_ui_core::fetch_git_core(
+                workspaces,
+                storage_path,
+                workspace_id.clone(),
+                Some(OperationPriority::Background),
+            )
+            .await
+            {
+                eprintln!(
+                    "Auto-fetch failed for workspace {workspace_id}: {}",
+                    redaction_core::redact_for_log(&error)
+                );
+                continue;
+            }
+            let Ok(updates) =
+                git_ui_core::check_remote_updates_core(workspaces, workspace_id.clone()).await
+            else {
+                continue;
+            };
+            let previous = last_behind.insert(workspace_id.clone(), updates.behind);
+            if updates.has_updates && previous != Some(updates.behind) {
+                event_sink.emit_remote_updates_event(RemoteUpdatesEvent {
+                    workspace_id,
+                    updates,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::WorkspaceKind;
+
+    fn workspace(id: &str, interval: Option<u64>) -> WorkspaceEntry {
+        WorkspaceEntry {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: format!("/tmp/{id}"),
+            codex_bin: None,
+            kind: WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings {
+                auto_fetch_interval_secs: interval,
+                ..WorkspaceSettings::default()
+            },
+        }
+    }
+
+    #[test]
+    fn due_workspaces_waits_for_interval_and_skips_disconnected() {
+        let entries = vec![
+            workspace("fast", Some(10)),
+            workspace("off", None),
+            workspace("away", Some(60)),
+        ];
+        let connected: HashSet<String> = ["fast", "off"].iter().map(|id| id.to_string()).collect();
+        let mut last_fetch = HashMap::new();
+        let start = Instant::now();
+
+        assert!(due_workspaces(&entries, &connected, &mut last_fetch, start).is_empty());
+        let later = start + Duration::from_secs(59);
+        assert!(due_workspaces(&entries, &connected, &mut last_fetch, later).is_empty());
+        let due = due_workspaces(
+            &entries,
+            &connected,
+            &mut last_fetch,
+            start + Duration::from_secs(60),
+        );
+        assert_eq!(due, vec!["fast".to_string()]);
+        assert!(!last_fetch.contains_key("away"));
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::shared::json_store_core::{read_json_store, update_json_store};
+use crate::utils::now_unix_ms;
+
+const CACHE_FILE_NAME: &str = "catalog_cache.json";
+/// A cached list this recent is served without asking Codex again.
+const CACHE_MAX_AGE_MS: i64 = 10 * 60 * 1000;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct CachedList {
+    response: Value,
+    cached_at_ms: i64,
+}
+
+/// Last good responses by `"{method}:{workspace_id}"`.
+type CatalogCache = HashMap<String, CachedList>;
+
+fn cache_path(storage_path: &Path) -> PathBuf {
+    storage_path.with_file_name(CACHE_FILE_NAME)
+}
+
+fn with_cache_flags(mut response: Value, stale: bool, cached_at_ms: Option<i64>) -> Value {
+    if let Some(object) = response.as_object_mut() {
+        object.insert("stale".to_string(), Value::Bool(stale));
+        object.insert("cachedAtMs".to_string(), cached_at_ms.into());
+    }
+    response
+}
+
+/// Serves a list request such as `model/list` through the on-disk cache.
+/// A recent copy is returned as is unless `refresh` is set; otherwise
+/// `fetch` runs, and when it fails (an error or an error response) the last
+/// good copy comes back with `stale: true`. Without a cached copy the
+/// failure is returned unchanged.
+pub(crate) async fn cached_list<F, Fut>(
+    storage_path: &Path,
+    method: &str,
+    workspace_id: &str,
+    refresh: bool,
+    fetch: F,
+) -> Result<Value, String>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<Value, String>>,
+{
+    let path = cache_path(storage_path);
+    let key = format!("{method}:{workspace_id}");
+    let cached = match read_json_store::<CatalogCache>(&path) {
+        Ok(mut cache) => cache.remove(&key),
+        Err(error) => {
+            eprintln!("Failed to read the catalog cache: {error}");
+            None
+        }
+    };
+    if !refresh {
+        if let Some(cached) = cached
+            .as_ref()
+            .filter(|cached| now_unix_ms() - cached.cached_at_ms < CACHE_MAX_AGE_MS)
+        {
+            return Ok(with_cache_flags(
+                cached.response.clone(),
+                false,
+                Some(cached.cached_at_ms),
+            ));
+        }
+    }
+    let result = fetch().await;
+    let succeeded = result
+        .as_ref()
+        .ok()
+        .filter(|response| response.get("error").is_none());
+    if let Some(response) = succeeded {
+        let cached_at_ms = now_unix_ms();
+        let stored = update_json_store(&path, |cache: &mut CatalogCache| {
+            cache.insert(
+                key,
+                CachedList {
+                    response: response.clone(),
+                    cached_at_ms,
+                },
+            );
+        });
+        if let Err(error) = stored {
+            eprintln!("Failed to cache {method} for workspace {workspace_id}: {error}");
+        }
+        return Ok(with_cache_flags(
+            response.clone(),
+            false,
+            Some(cached_at_ms),
+        ));
+    }
+    match cached {
+        Some(cached) => Ok(with_cache_flags(
+            cached.response,
+            true,
+            Some(cached.cached_at_ms),
+        )),
+        None => result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    #[test]
+    fn cached_list_falls_back_to_the_last_good_response() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        runtime.block_on(cached_list_fallback());
+    }
+
+    async fn cached_list_fallback() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-catalog-{}", Uuid::new_v4()));
+        let storage_path = dir.join("workspaces.json");
+        let models = json!({ "id": 1, "result": { "data": [{ "id": "gpt-5" }] } });
+
+        let fresh = cached_list(&storage_path, "model/list", "ws", true, || async {
+            Ok(models.clone())
+        })
+        .await
+        .expect("fresh");
+        assert_eq!(fresh["stale"], false);
+        assert_eq!(fresh["result"]["data"][0]["id"], "gpt-5");
+
+        // Served from the cache without running the fetch.
+        let cached = cached_list(&storage_path, "model/list", "ws", false, || async {
+            Err::<Value, _>("should not fetch".to_string())
+        })
+        .await
+        .expect("cached");
+        assert_eq!(cached["stale"], false);
+
+        let offline = cached_list(&storage_path, "model/list", "ws", true, || async {
+            Err::<Value, _>("workspace not connected".to_string())
+        })
+        .await
+        .expect("stale");
+        assert_eq!(offline["stale"], true);
+        assert_eq!(offline["result"]["data"][0]["id"], "gpt-5");
+
+        let rejected = cached_list(&storage_path, "model/list", "ws", true, || async {
+            Ok(json!({ "id": 2, "error": { "message": "unavailable" } }))
+        })
+        .await
+        .expect("stale after error response");
+        assert_eq!(rejected["stale"], true);
+
+        let missing = cached_list(&storage_path, "skills/list", "ws", true, || async {
+            Err::<Value, _>("workspace not connected".to_string())
+        })
+        .await;
+        assert_eq!(missing, Err("workspace not connected".to_string()));
+
+        std::fs::write(cache_path(&storage_path), "{\"model/list").expect("truncate");
+        let uncached = cached_list(&storage_path, "model/list", "ws", false, || async {
+            Ok(models.clone())
+        })
+        .await
+        .expect("fetched past a damaged cache");
+        assert_eq!(uncached["result"]["data"][0]["id"], "gpt-5");
+        assert_eq!(
+            std::fs::read_to_string(cache_path(&storage_path)).expect("read"),
+            "{\"model/list"
+        );
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -1,9 +1,10 @@
+use futures_util::future::join_all;
 use serde_json::{json, Map, Value};
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::pin::Pin;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use tokio::sync::oneshot::error::TryRecvError;
 use tokio::sync::{oneshot, Mutex};
@@ -11,13 +12,25 @@ use tokio::time::timeout;
 use tokio::time::Instant;
 
 use crate::backend::app_server::WorkspaceSession;
+use crate::backend::events::{EventSink, MessageQueuedEvent};
 use crate::codex::config as codex_config;
+use crate::codex::endpoint::resolve_workspace_codex_base_url;
 use crate::codex::home::{resolve_default_codex_home, resolve_workspace_codex_home};
 use crate::rules;
-use crate::shared::account::{build_account_response, read_auth_account};
-use crate::types::WorkspaceEntry;
+use crate::shared::account::{build_account_response, read_auth_account, read_auth_status};
+use crate::shared::auto_compact_core;
+use crate::shared::catalog_cache_core;
+use crate::shared::message_queue_core::QueuedMessage;
+use crate::types::{
+    AppSettings, ApprovalRule, ApprovalRuleTest, ApprovalRulesExport, ApprovalRulesImport,
+    SessionAccount, TurnInterruptOutcome, WorkspaceEntry, WorkspaceInterruptResult,
+};
 
 const LOGIN_START_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_IMAGE_BYTES: u64 = 20 * 1024 * 1024;
+const DATA_URL_IMAGE_TYPES: &[&str] = &["image/png", "image/jpeg", "image/gif", "image/webp"];
+const LOCAL_IMAGE_EXTENSIONS: &[&str] =
+    &["png", "jpg", "jpeg", "gif", "webp", "bmp", "tiff", "tif"];
 
 pub(crate) enum CodexLoginCancelState {
     PendingStart(oneshot::Sender<()>),
@@ -52,7 +65,7 @@ async fn resolve_workspace_and_parent(
     Ok((entry, parent_entry))
 }
 
-async fn resolve_codex_home_for_workspace_core(
+pub(crate) async fn resolve_codex_home_for_workspace_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: &str,
 ) -> Result<PathBuf, String> {
@@ -155,10 +168,87 @@ pub(crate) async fn set_thread_name_core(
     session.send_request("thread/name/set", params).await
 }
 
+/// Relative paths have to stay inside the workspace once symlinks are
+/// followed. Absolute paths come from the file picker or a drop, where the
+/// user chose the file, so they may live anywhere.
+fn resolve_local_image(image: &str, workspace_root: &Path) -> Result<PathBuf, String> {
+    let requested = Path::new(image);
+    if requested.is_absolute() {
+        return requested
+            .canonicalize()
+            .map_err(|_| format!("image not found: {image}"));
+    }
+    let root = workspace_root
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve workspace path: {e}"))?;
+    let resolved = root
+        .join(requested)
+        .canonicalize()
+        .map_err(|_| format!("image not found: {image}"))?;
+    if !resolved.starts_with(&root) {
+        return Err(format!("image is outside the workspace: {image}"));
+    }
+    Ok(resolved)
+}
+
+/// Images are forwarded to the model as they are, so a data URL has to be
+/// one of the types it reads. Local files are re-encoded by Codex, which
+/// also takes the other formats the image picker offers.
+fn image_input_item(image: &str, workspace_root: &Path) -> Result<Value, String> {
+    if let Some(data) = image.strip_prefix("data:") {
+        let (meta, payload) = data
+            .split_once(',')
+            .ok_or_else(|| "invalid image data URL".to_string())?;
+        let mime = meta.split(';').next().unwrap_or("").to_ascii_lowercase();
+        if !DATA_URL_IMAGE_TYPES.contains(&mime.as_str()) {
+            return Err(format!(
+                "unsupported image type `{mime}`; use PNG, JPEG, GIF or WebP"
+            ));
+        }
+        if !meta.ends_with(";base64") {
+            return Err("image data URLs must be base64-encoded".to_string());
+        }
+        if payload.len() as u64 / 4 * 3 > MAX_IMAGE_BYTES {
+            return Err(format!(
+                "image is larger than {} MB",
+                MAX_IMAGE_BYTES / 1024 / 1024
+            ));
+        }
+        return Ok(json!({ "type": "image", "url": image }));
+    }
+    if image.starts_with("http://") || image.starts_with("https://") {
+        return Ok(json!({ "type": "image", "url": image }));
+    }
+    let path = resolve_local_image(image, workspace_root)?;
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+    if !LOCAL_IMAGE_EXTENSIONS.contains(&extension.as_str()) {
+        return Err(format!("unsupported image file: {}", path.display()));
+    }
+    let metadata =
+        std::fs::metadata(&path).map_err(|_| format!("image not found: {}", path.display()))?;
+    if !metadata.is_file() {
+        return Err(format!("image is not a file: {}", path.display()));
+    }
+    if metadata.len() > MAX_IMAGE_BYTES {
+        return Err(format!(
+            "image is larger than {} MB: {}",
+            MAX_IMAGE_BYTES / 1024 / 1024,
+            path.display()
+        ));
+    }
+    Ok(json!({ "type": "localImage", "path": path.to_string_lossy() }))
+}
+
+/// Relative image paths are resolved against `workspace_root`.
 fn build_turn_input_items(
     text: String,
     images: Option<Vec<String>>,
     app_mentions: Option<Vec<Value>>,
+    workspace_root: &Path,
 ) -> Result<Vec<Value>, String> {
     let trimmed_text = text.trim();
     let mut input: Vec<Value> = Vec::new();
@@ -171,14 +261,7 @@ fn build_turn_input_items(
             if trimmed.is_empty() {
                 continue;
             }
-            if trimmed.starts_with("data:")
-                || trimmed.starts_with("http://")
-                || trimmed.starts_with("https://")
-            {
-                input.push(json!({ "type": "image", "url": trimmed }));
-            } else {
-                input.push(json!({ "type": "localImage", "path": trimmed }));
-            }
+            input.push(image_input_item(trimmed, workspace_root)?);
         }
     }
     if let Some(mentions) = app_mentions {
@@ -214,8 +297,10 @@ fn build_turn_input_items(
     Ok(input)
 }
 
-pub(crate) async fn send_user_message_core(
+pub(crate) async fn send_user_message_core<E: EventSink>(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    event_sink: &E,
     workspace_id: String,
     thread_id: String,
     text: String,
@@ -225,6 +310,7 @@ pub(crate) async fn send_user_message_core(
     images: Option<Vec<String>>,
     app_mentions: Option<Vec<Value>>,
     collaboration_mode: Option<Value>,
+    queue: bool,
 ) -> Result<Value, String> {
     let session = get_session_clone(sessions, &workspace_id).await?;
     let access_mode = access_mode.unwrap_or_else(|| "current".to_string());
@@ -244,7 +330,13 @@ pub(crate) async fn send_user_message_core(
         "on-request"
     };
 
-    let input = build_turn_input_items(text, images, app_mentions)?;
+    let attachment_count = images.as_ref().map(Vec::len).unwrap_or(0);
+    let input = build_turn_input_items(
+        text.clone(),
+        images,
+        app_mentions,
+        Path::new(&session.entry.path),
+    )?;
 
     let mut params = Map::new();
     params.insert("threadId".to_string(), json!(thread_id));
@@ -259,9 +351,182 @@ pub(crate) async fn send_user_message_core(
             params.insert("collaborationMode".to_string(), mode);
         }
     }
+    let params = Value::Object(params);
+
+    if !queue {
+        auto_compact_core::auto_compact_before_turn(
+            &session,
+            workspaces,
+            &thread_id,
+            &text,
+            attachment_count,
+            model.as_deref(),
+            event_sink,
+        )
+        .await;
+        return start_turn(&session, &thread_id, params).await;
+    }
+
+    let compact_at_pct =
+        auto_compact_core::resolve_compact_at_pct_for_workspace(workspaces, &workspace_id).await;
+    let queue_length = {
+        let mut message_queue = session.message_queue.lock().await;
+        if message_queue.is_busy(&thread_id) {
+            Some(message_queue.enqueue(
+                &thread_id,
+                QueuedMessage {
+                    params: params.clone(),
+                    text: text.clone(),
+                    attachment_count,
+                    model: model.clone(),
+                    compact_at_pct,
+                },
+            ))
+        } else {
+            message_queue.begin_send(&thread_id);
+            None
+        }
+    };
+    if let Some(queue_length) = queue_length {
+        event_sink.emit_message_queued_event(MessageQueuedEvent {
+            workspace_id,
+            thread_id: thread_id.clone(),
+            status: "queued".to_string(),
+            queue_length,
+            error: None,
+        });
+        return Ok(json!({
+            "queued": true,
+            "threadId": thread_id,
+            "queueLength": queue_length,
+        }));
+    }
+
+    if let Some(threshold) = compact_at_pct {
+        auto_compact_core::auto_compact_at_threshold(
+            &session,
+            threshold,
+            &thread_id,
+            &text,
+            attachment_count,
+            model.as_deref(),
+            event_sink,
+        )
+        .await;
+    }
+    let response = start_turn(&session, &thread_id, params).await;
+    if session.message_queue.lock().await.end_send(&thread_id) {
+        tokio::spawn(dispatch_queued_messages(
+            Arc::clone(&session),
+            thread_id,
+            event_sink.clone(),
+        ));
+    }
+    response
+}
+
+/// Sends `turn/start`, keeping the thread busy until its `turn/completed`
+/// unless the request itself fails.
+async fn start_turn(
+    session: &WorkspaceSession,
+    thread_id: &str,
+    params: Value,
+) -> Result<Value, String> {
     session
-        .send_request("turn/start", Value::Object(params))
+        .message_queue
+        .lock()
         .await
+        .mark_turn_requested(thread_id);
+    let response = session.send_request("turn/start", params).await;
+    if response_error(&response).is_some() {
+        session
+            .message_queue
+            .lock()
+            .await
+            .mark_turn_failed(thread_id);
+    }
+    response
+}
+
+/// The error of a failed request or of an app-server error response.
+fn response_error(response: &Result<Value, String>) -> Option<String> {
+    match response {
+        Ok(value) => value.get("error").map(|error| {
+            error
+                .get("message")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .unwrap_or_else(|| error.to_string())
+        }),
+        Err(error) => Some(error.clone()),
+    }
+}
+
+/// Sends the thread's queued messages one turn at a time. Runs after a
+/// `turn/completed` and stops as soon as the thread is busy again; the next
+/// completion picks up from there.
+pub(crate) async fn dispatch_queued_messages<E: EventSink>(
+    session: Arc<WorkspaceSession>,
+    thread_id: String,
+    event_sink: E,
+) {
+    loop {
+        let Some((message, remaining)) = session
+            .message_queue
+            .lock()
+            .await
+            .begin_dispatch(&thread_id)
+        else {
+            return;
+        };
+        if let Some(threshold) = message.compact_at_pct {
+            auto_compact_core::auto_compact_at_threshold(
+                &session,
+                threshold,
+                &thread_id,
+                &message.text,
+                message.attachment_count,
+                message.model.as_deref(),
+                &event_sink,
+            )
+            .await;
+        }
+        let response = start_turn(&session, &thread_id, message.params).await;
+        let error = response_error(&response);
+        event_sink.emit_message_queued_event(MessageQueuedEvent {
+            workspace_id: session.entry.id.clone(),
+            thread_id: thread_id.clone(),
+            status: if error.is_some() { "failed" } else { "sent" }.to_string(),
+            queue_length: remaining,
+            error,
+        });
+        if !session.message_queue.lock().await.end_send(&thread_id) {
+            return;
+        }
+    }
+}
+
+/// Drops every message waiting in the workspace's queues. Returns how many
+/// were dropped.
+pub(crate) async fn clear_message_queue_core<E: EventSink>(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    event_sink: &E,
+    workspace_id: String,
+) -> Result<usize, String> {
+    let session = get_session_clone(sessions, &workspace_id).await?;
+    let cleared = session.message_queue.lock().await.clear();
+    let mut total = 0;
+    for (thread_id, count) in cleared {
+        total += count;
+        event_sink.emit_message_queued_event(MessageQueuedEvent {
+            workspace_id: workspace_id.clone(),
+            thread_id,
+            status: "cleared".to_string(),
+            queue_length: 0,
+            error: None,
+        });
+    }
+    Ok(total)
 }
 
 pub(crate) async fn turn_steer_core(
@@ -277,7 +542,7 @@ pub(crate) async fn turn_steer_core(
         return Err("missing active turn id".to_string());
     }
     let session = get_session_clone(sessions, &workspace_id).await?;
-    let input = build_turn_input_items(text, images, app_mentions)?;
+    let input = build_turn_input_items(text, images, app_mentions, Path::new(&session.entry.path))?;
     let params = json!({
         "threadId": thread_id,
         "expectedTurnId": turn_id,
@@ -307,6 +572,49 @@ pub(crate) async fn turn_interrupt_core(
     session.send_request("turn/interrupt", params).await
 }
 
+async fn interrupt_running_turns(
+    workspace_id: String,
+    session: Arc<WorkspaceSession>,
+) -> WorkspaceInterruptResult {
+    let running = session.running_turns().await;
+    let session = &session;
+    let turns = running.into_iter().map(|(thread_id, turn_id)| async move {
+        let params = json!({ "threadId": thread_id, "turnId": turn_id });
+        let error = response_error(&session.send_request("turn/interrupt", params).await);
+        TurnInterruptOutcome {
+            thread_id,
+            turn_id,
+            delivered: error.is_none(),
+            error,
+        }
+    });
+    WorkspaceInterruptResult {
+        workspace_id,
+        turns: join_all(turns).await,
+    }
+}
+
+/// Sends `turn/interrupt` for every running turn in every connected
+/// workspace at once, reporting each delivery.
+pub(crate) async fn interrupt_all_turns_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+) -> Vec<WorkspaceInterruptResult> {
+    let sessions: Vec<(String, Arc<WorkspaceSession>)> = sessions
+        .lock()
+        .await
+        .iter()
+        .map(|(id, session)| (id.clone(), Arc::clone(session)))
+        .collect();
+    let mut results = join_all(
+        sessions
+            .into_iter()
+            .map(|(workspace_id, session)| interrupt_running_turns(workspace_id, session)),
+    )
+    .await;
+    results.sort_by(|a, b| a.workspace_id.cmp(&b.workspace_id));
+    results
+}
+
 pub(crate) async fn start_review_core(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
     workspace_id: String,
@@ -328,10 +636,21 @@ pub(crate) async fn start_review_core(
 
 pub(crate) async fn model_list_core(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    storage_path: &Path,
     workspace_id: String,
+    refresh: bool,
 ) -> Result<Value, String> {
-    let session = get_session_clone(sessions, &workspace_id).await?;
-    session.send_request("model/list", json!({})).await
+    catalog_cache_core::cached_list(
+        storage_path,
+        "model/list",
+        &workspace_id,
+        refresh,
+        || async {
+            let session = get_session_clone(sessions, &workspace_id).await?;
+            session.send_request("model/list", json!({})).await
+        },
+    )
+    .await
 }
 
 pub(crate) async fn experimental_feature_list_core(
@@ -378,6 +697,72 @@ pub(crate) async fn account_read_core(
     Ok(build_account_response(response, fallback))
 }
 
+/// Combines the running session's `account/read` with what `auth.json` in the
+/// resolved home says about token expiry, so the UI can ask for a new login
+/// before a turn fails.
+pub(crate) async fn get_session_account_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    workspace_id: String,
+) -> Result<SessionAccount, String> {
+    let connected = sessions.lock().await.contains_key(&workspace_id);
+    let response = account_read_core(sessions, workspaces, workspace_id.clone()).await?;
+    let (entry, parent_entry) = resolve_workspace_and_parent(workspaces, &workspace_id).await?;
+    let codex_home = resolve_workspace_codex_home(&entry, parent_entry.as_ref())
+        .or_else(resolve_default_codex_home);
+
+    // A worktree without its own home runs under its parent's profile.
+    let profile_id = match (&entry.settings.codex_home, parent_entry.as_ref()) {
+        (None, Some(parent)) if entry.kind.is_worktree() => parent.settings.profile_id.clone(),
+        _ => entry.settings.profile_id.clone(),
+    };
+    let profile = match profile_id {
+        Some(profile_id) => app_settings
+            .lock()
+            .await
+            .codex_profiles
+            .iter()
+            .find(|profile| profile.id == profile_id)
+            .cloned(),
+        None => None,
+    };
+
+    let now_secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or(0);
+    let auth = read_auth_status(codex_home.clone(), now_secs);
+    let account = response.get("account").filter(|value| !value.is_null());
+    let field = |key: &str| {
+        account
+            .and_then(|account| account.get(key))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    };
+    let requires_openai_auth = response.get("requiresOpenaiAuth").and_then(Value::as_bool);
+    // The session knows best: if it reports no account while auth is
+    // required, a stale auth.json should not read as signed in.
+    let auth_status = if connected && account.is_none() && requires_openai_auth == Some(true) {
+        "missing"
+    } else {
+        auth.status
+    };
+
+    Ok(SessionAccount {
+        workspace_id,
+        profile,
+        codex_home: codex_home.map(|home| home.to_string_lossy().to_string()),
+        connected,
+        account_type: field("type"),
+        email: field("email"),
+        plan_type: field("planType"),
+        requires_openai_auth,
+        auth_status: auth_status.to_string(),
+        expires_at: auth.expires_at,
+    })
+}
+
 pub(crate) async fn codex_login_core(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
     codex_login_cancels: &Mutex<HashMap<String, CodexLoginCancelState>>,
@@ -518,11 +903,22 @@ pub(crate) async fn codex_login_cancel_core(
 
 pub(crate) async fn skills_list_core(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    storage_path: &Path,
     workspace_id: String,
+    refresh: bool,
 ) -> Result<Value, String> {
-    let session = get_session_clone(sessions, &workspace_id).await?;
-    let params = json!({ "cwd": session.entry.path });
-    session.send_request("skills/list", params).await
+    catalog_cache_core::cached_list(
+        storage_path,
+        "skills/list",
+        &workspace_id,
+        refresh,
+        || async {
+            let session = get_session_clone(sessions, &workspace_id).await?;
+            let params = json!({ "cwd": session.entry.path });
+            session.send_request("skills/list", params).await
+        },
+    )
+    .await
 }
 
 pub(crate) async fn apps_list_core(
@@ -537,21 +933,53 @@ pub(crate) async fn apps_list_core(
     session.send_request("app/list", params).await
 }
 
+/// Sends `result` for a server request. With `rule_command`, it is only
+/// sent when one of the workspace's allow rules covers that command, so an
+/// approval can be auto-accepted without the caller deciding. Returns
+/// whether the response went out.
 pub(crate) async fn respond_to_server_request_core(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
     request_id: Value,
     result: Value,
-) -> Result<(), String> {
+    rule_command: Option<Vec<String>>,
+) -> Result<bool, String> {
+    if let Some(command) = rule_command {
+        let rules_path = rules_path_for_workspace(workspaces, &workspace_id).await?;
+        if rules::matching_rule(&rules_path, &command).is_none() {
+            return Ok(false);
+        }
+    }
     let session = get_session_clone(sessions, &workspace_id).await?;
-    session.send_response(request_id, result).await
+    session.send_response(request_id, result).await?;
+    Ok(true)
 }
 
+/// `kind` defaults to `exact`, which appends a `prefix_rule` for `command`.
+/// A `glob` or `regex` rule stores `pattern` instead and fails when it
+/// doesn't compile.
 pub(crate) async fn remember_approval_rule_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
     command: Vec<String>,
+    kind: Option<String>,
+    pattern: Option<String>,
 ) -> Result<Value, String> {
+    let kind = rules::parse_rule_kind(kind.as_deref())?;
+    if kind != rules::RULE_KIND_EXACT {
+        let pattern = pattern
+            .map(|pattern| pattern.trim().to_string())
+            .filter(|pattern| !pattern.is_empty())
+            .ok_or_else(|| format!("A {kind} rule needs a pattern."))?;
+        let rules_path = rules_path_for_workspace(workspaces, &workspace_id).await?;
+        rules::append_pattern_rule(&rules_path, kind, &pattern)?;
+        return Ok(json!({
+            "ok": true,
+            "rulesPath": rules_path,
+        }));
+    }
+
     let command = command
         .into_iter()
         .map(|item| item.trim().to_string())
@@ -571,11 +999,130 @@ pub(crate) async fn remember_approval_rule_core(
     }))
 }
 
+async fn rules_path_for_workspace(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: &str,
+) -> Result<PathBuf, String> {
+    let codex_home = resolve_codex_home_for_workspace_core(workspaces, workspace_id).await?;
+    Ok(rules::default_rules_path(&codex_home))
+}
+
+pub(crate) async fn list_approval_rules_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<Vec<ApprovalRule>, String> {
+    let rules_path = rules_path_for_workspace(workspaces, &workspace_id).await?;
+    rules::list_rules(&rules_path)
+}
+
+pub(crate) async fn delete_approval_rule_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    id: String,
+) -> Result<(), String> {
+    let rules_path = rules_path_for_workspace(workspaces, &workspace_id).await?;
+    if rules::delete_rule(&rules_path, id.trim())? {
+        Ok(())
+    } else {
+        Err("Approval rule not found.".to_string())
+    }
+}
+
+pub(crate) fn test_approval_rule_core(
+    pattern: String,
+    command: Vec<String>,
+    kind: Option<String>,
+) -> Result<ApprovalRuleTest, String> {
+    let kind = rules::parse_rule_kind(kind.as_deref())?;
+    rules::test_rule(kind, &pattern, &command)
+}
+
+pub(crate) async fn export_approval_rules_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<ApprovalRulesExport, String> {
+    let rules_path = rules_path_for_workspace(workspaces, &workspace_id).await?;
+    rules::export_rules(&rules_path)
+}
+
+pub(crate) async fn import_approval_rules_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    data: String,
+) -> Result<ApprovalRulesImport, String> {
+    let rules_path = rules_path_for_workspace(workspaces, &workspace_id).await?;
+    rules::import_rules(&rules_path, &data)
+}
+
+/// `codexBaseUrl` is the workspace override passed to the session, or null
+/// when Codex uses the endpoint from its own configuration.
 pub(crate) async fn get_config_model_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
 ) -> Result<Value, String> {
+    let (entry, parent_entry) = resolve_workspace_and_parent(workspaces, &workspace_id).await?;
+    let codex_base_url = resolve_workspace_codex_base_url(&entry, parent_entry.as_ref());
     let codex_home = resolve_codex_home_for_workspace_core(workspaces, &workspace_id).await?;
     let model = codex_config::read_config_model(Some(codex_home))?;
-    Ok(json!({ "model": model }))
+    Ok(json!({ "model": model, "codexBaseUrl": codex_base_url }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn build_turn_input_items_validates_images() {
+        let root = std::env::temp_dir().join(format!("codex-monitor-images-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("shots")).expect("create temp workspace");
+        std::fs::write(root.join("shots/bug.png"), b"png").expect("write image");
+        std::fs::write(root.join("notes.txt"), b"text").expect("write text");
+        let outside = root.with_extension("outside.png");
+        std::fs::write(&outside, b"png").expect("write outside image");
+        let outside_path = outside.to_string_lossy().to_string();
+        let escape = format!(
+            "../{}",
+            outside.file_name().unwrap_or_default().to_string_lossy()
+        );
+        let canonical_root = root.canonicalize().expect("canonical root");
+
+        let input = build_turn_input_items(
+            "look".to_string(),
+            Some(vec![
+                "shots/bug.png".to_string(),
+                "data:image/png;base64,AAAA".to_string(),
+            ]),
+            None,
+            &root,
+        )
+        .expect("valid images");
+        assert_eq!(input[1]["type"], "localImage");
+        assert_eq!(
+            input[1]["path"],
+            json!(canonical_root.join("shots/bug.png").to_string_lossy())
+        );
+        assert_eq!(input[2]["type"], "image");
+
+        let images = |image: &str| Some(vec![image.to_string()]);
+        for image in [
+            "notes.txt",
+            "missing.png",
+            "shots",
+            escape.as_str(),
+            "data:image/svg+xml;base64,AAAA",
+            "data:image/png,raw",
+        ] {
+            let result = build_turn_input_items("look".to_string(), images(image), None, &root);
+            assert!(result.is_err(), "{image} should be rejected");
+        }
+
+        let absolute =
+            build_turn_input_items("look".to_string(), images(&outside_path), None, &root)
+                .expect("picked image outside the workspace");
+        assert_eq!(absolute[1]["type"], "localImage");
+
+        std::fs::remove_dir_all(root).expect("remove temp workspace");
+        std::fs::remove_file(outside).expect("remove outside image");
+    }
 }
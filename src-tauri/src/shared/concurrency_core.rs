@@ -0,0 +1,202 @@
+use std::pin::pin;
+use std::sync::{Mutex as StdMutex, OnceLock};
+
+use tokio::sync::Notify;
+
+const SHARED_PERMITS: usize = 4;
+/// Permits background work can never take, so a status or diff still runs
+/// while every other permit is held by slow network operations.
+const INTERACTIVE_RESERVE: usize = 1;
+
+static SHARED_LIMITER: OnceLock<PriorityLimiter> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OperationPriority {
+    /// User is waiting on the result (status, current ref, diffs).
+    Interactive,
+    /// Bulk or network work that can yield to interactive requests.
+    Background,
+}
+
+pub(crate) fn parse_operation_priority(
+    value: Option<&str>,
+) -> Result<Option<OperationPriority>, String> {
+    match value.map(str::trim) {
+        None | Some("") => Ok(None),
+        Some("interactive") => Ok(Some(OperationPriority::Interactive)),
+        Some("background") => Ok(Some(OperationPriority::Background)),
+        Some(other) => Err(format!("Invalid operation priority: {other}")),
+    }
+}
+
+struct LimiterState {
+    available: usize,
+    waiting_interactive: usize,
+}
+
+/// Semaphore that hands freed permits to interactive waiters before
+/// background ones, so a quick status never queues behind a bulk fetch.
+pub(crate) struct PriorityLimiter {
+    state: StdMutex<LimiterState>,
+    notify: Notify,
+    reserved: usize,
+}
+
+pub(crate) struct PriorityPermit<'a> {
+    limiter: &'a PriorityLimiter,
+}
+
+struct InteractiveWaiter<'a> {
+    limiter: &'a PriorityLimiter,
+}
+
+impl Drop for InteractiveWaiter<'_> {
+    fn drop(&mut self) {
+        let mut state = self.limiter.lock_state();
+        state.waiting_interactive = state.waiting_interactive.saturating_sub(1);
+        drop(state);
+        // Background waiters may have been held back by this waiter.
+        self.limiter.notify.notify_waiters();
+    }
+}
+
+impl PriorityLimiter {
+    pub(crate) fn new(permits: usize) -> Self {
+        Self {
+            state: StdMutex::new(LimiterState {
+                available: permits,
+                waiting_interactive: 0,
+            }),
+            notify: Notify::new(),
+            // A single-permit limiter has nothing to spare.
+            reserved: INTERACTIVE_RESERVE.min(permits.saturating_sub(1)),
+        }
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, LimiterState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub(crate) async fn acquire(&self, priority: OperationPriority) -> PriorityPermit<'_> {
+        let _waiter = match priority {
+            OperationPriority::Interactive => {
+                self.lock_state().waiting_interactive += 1;
+                Some(InteractiveWaiter { limiter: self })
+            }
+            OperationPriority::Background => None,
+        };
+        loop {
+            let mut notified = pin!(self.notify.notified());
+            notified.as_mut().enable();
+            {
+                let mut state = self.lock_state();
+                let may_take = match priority {
+                    OperationPriority::Interactive => state.available > 0,
+                    OperationPriority::Background => {
+                        state.waiting_interactive == 0 && state.available > self.reserved
+                    }
+                };
+                if may_take {
+                    state.available -= 1;
+                    return PriorityPermit { limiter: self };
+                }
+            }
+            notified.await;
+        }
+    }
+}
+
+impl Drop for PriorityPermit<'_> {
+    fn drop(&mut self) {
+        self.limiter.lock_state().available += 1;
+        self.limiter.notify.notify_waiters();
+    }
+}
+
+/// Process-wide limiter shared by git status, diff, fetch and pull.
+pub(crate) fn shared_limiter() -> &'static PriorityLimiter {
+    SHARED_LIMITER.get_or_init(|| PriorityLimiter::new(SHARED_PERMITS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[test]
+    fn parse_operation_priority_accepts_known_values() {
+        assert_eq!(parse_operation_priority(None), Ok(None));
+        assert_eq!(
+            parse_operation_priority(Some("background")),
+            Ok(Some(OperationPriority::Background))
+        );
+        assert!(parse_operation_priority(Some("urgent")).is_err());
+    }
+
+    #[test]
+    fn interactive_waiter_is_served_before_background_waiter() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        runtime.block_on(async {
+            let limiter = Arc::new(PriorityLimiter::new(1));
+            let order = Arc::new(StdMutex::new(Vec::new()));
+            let held = limiter.acquire(OperationPriority::Background).await;
+
+            let background = {
+                let limiter = Arc::clone(&limiter);
+                let order = Arc::clone(&order);
+                tokio::spawn(async move {
+                    let _permit = limiter.acquire(OperationPriority::Background).await;
+                    order.lock().unwrap().push("background");
+                })
+            };
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            let interactive = {
+                let limiter = Arc::clone(&limiter);
+                let order = Arc::clone(&order);
+                tokio::spawn(async move {
+                    let _permit = limiter.acquire(OperationPriority::Interactive).await;
+                    order.lock().unwrap().push("interactive");
+                })
+            };
+            tokio::time::sleep(Duration::from_millis(20)).await;
+
+            drop(held);
+            interactive.await.expect("interactive task");
+            background.await.expect("background task");
+            assert_eq!(*order.lock().unwrap(), vec!["interactive", "background"]);
+        });
+    }
+
+    #[test]
+    fn background_work_cannot_take_the_reserved_permit() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        runtime.block_on(async {
+            let limiter = PriorityLimiter::new(SHARED_PERMITS);
+            let mut held = Vec::new();
+            for _ in 0..SHARED_PERMITS - INTERACTIVE_RESERVE {
+                held.push(limiter.acquire(OperationPriority::Background).await);
+            }
+            let blocked = tokio::time::timeout(
+                Duration::from_millis(20),
+                limiter.acquire(OperationPriority::Background),
+            )
+            .await;
+            assert!(blocked.is_err(), "background took the reserved permit");
+            let interactive = tokio::time::timeout(
+                Duration::from_millis(20),
+                limiter.acquire(OperationPriority::Interactive),
+            )
+            .await;
+            assert!(interactive.is_ok(), "interactive waited behind background");
+        });
+    }
+}
@@ -0,0 +1,162 @@
+use std::path::PathBuf;
+
+use crate::storage::write_settings;
+use crate::types::{AppSettings, CredentialStatus};
+
+const MAX_KEY_LEN: usize = 128;
+
+pub(crate) const REMOTE_BACKEND_TOKEN_KEY: &str = "remoteBackendToken";
+
+pub(crate) fn normalize_credential_key(key: &str) -> Result<String, String> {
+    let key = key.trim();
+    if key.is_empty() {
+        return Err("Credential key is required.".to_string());
+    }
+    if key.len() > MAX_KEY_LEN
+        || !key
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '.' | '_' | '-' | ':'))
+    {
+        return Err(format!("Invalid credential key: {key}"));
+    }
+    Ok(key.to_string())
+}
+
+/// The OS keychain. `keyring` is a desktop-only dependency, so mobile builds
+/// get a store that never holds anything and secrets stay in settings.
+#[cfg(desktop)]
+mod store {
+    const SERVICE_NAME: &str = "com.dimillian.codexmonitor";
+
+    fn entry(key: &str) -> Result<keyring::Entry, String> {
+        keyring::Entry::new(SERVICE_NAME, key)
+            .map_err(|err| format!("Credential store error: {err}"))
+    }
+
+    pub(crate) fn read_credential(key: &str) -> Result<Option<String>, String> {
+        match entry(key)?.get_password() {
+            Ok(secret) => Ok(Some(secret)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(err) => Err(format!("Failed to read credential {key}: {err}")),
+        }
+    }
+
+    pub(super) fn write_credential(key: &str, secret: &str) -> Result<(), String> {
+        entry(key)?
+            .set_password(secret)
+            .map_err(|err| format!("Failed to store credential {key}: {err}"))
+    }
+
+    pub(super) fn remove_credential(key: &str) -> Result<(), String> {
+        match entry(key)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(err) => Err(format!("Failed to delete credential {key}: {err}")),
+        }
+    }
+}
+
+#[cfg(mobile)]
+mod store {
+    pub(crate) fn read_credential(_key: &str) -> Result<Option<String>, String> {
+        Ok(None)
+    }
+
+    pub(super) fn write_credential(_key: &str, _secret: &str) -> Result<(), String> {
+        Err("Secure credential storage is not available on this platform.".to_string())
+    }
+
+    pub(super) fn remove_credential(_key: &str) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+pub(crate) use store::read_credential;
+use store::{remove_credential, write_credential};
+
+pub(crate) fn set_credential_core(key: &str, secret: &str) -> Result<CredentialStatus, String> {
+    let key = normalize_credential_key(key)?;
+    if secret.is_empty() {
+        return Err("Credential secret is required.".to_string());
+    }
+    write_credential(&key, secret)?;
+    Ok(CredentialStatus { key, exists: true })
+}
+
+/// Reports presence only; the secret itself never leaves the backend.
+pub(crate) fn get_credential_status_core(key: &str) -> Result<CredentialStatus, String> {
+    let key = normalize_credential_key(key)?;
+    let exists = read_credential(&key)?.is_some();
+    Ok(CredentialStatus { key, exists })
+}
+
+pub(crate) fn delete_credential_core(key: &str) -> Result<CredentialStatus, String> {
+    let key = normalize_credential_key(key)?;
+    remove_credential(&key)?;
+    Ok(CredentialStatus { key, exists: false })
+}
+
+/// The copy of `settings` that goes to disk. Secrets move into the OS
+/// credential store; if that store is unavailable (headless Linux, mobile)
+/// they stay in the file so nothing is lost. The store is only written when
+/// the token differs from `stored_token`, the one already saved.
+pub(crate) fn settings_for_disk(settings: &AppSettings, stored_token: Option<&str>) -> AppSettings {
+    let mut persisted = settings.clone();
+    let token = settings.remote_backend_token.as_deref();
+    if token == stored_token {
+        // An unchanged token is already in the credential store, unless the
+        // store was unavailable when it was saved and it stayed in the file.
+        let in_store = token.and_then(|_| read_credential(REMOTE_BACKEND_TOKEN_KEY).ok().flatten());
+        if in_store.as_deref() == token {
+            persisted.remote_backend_token = None;
+        }
+        return persisted;
+    }
+    match token {
+        Some(token) => match write_credential(REMOTE_BACKEND_TOKEN_KEY, token) {
+            Ok(()) => persisted.remote_backend_token = None,
+            Err(err) => eprintln!("Keeping remote backend token in settings: {err}"),
+        },
+        None => {
+            if let Err(err) = remove_credential(REMOTE_BACKEND_TOKEN_KEY) {
+                eprintln!("{err}");
+            }
+        }
+    }
+    persisted
+}
+
+/// Run once after settings are read. Plaintext secrets left by older
+/// versions are moved into the credential store; otherwise the in-memory
+/// settings are filled from it so existing readers keep working.
+pub(crate) fn load_settings_secrets(settings_path: &PathBuf, settings: &mut AppSettings) {
+    if settings.remote_backend_token.is_some() {
+        let persisted = settings_for_disk(settings, None);
+        if persisted.remote_backend_token.is_none() {
+            if let Err(err) = write_settings(settings_path, &persisted) {
+                eprintln!("Failed to rewrite settings after credential migration: {err}");
+            }
+        }
+        return;
+    }
+    match read_credential(REMOTE_BACKEND_TOKEN_KEY) {
+        Ok(token) => settings.remote_backend_token = token,
+        Err(err) => eprintln!("{err}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_credential_key;
+
+    #[test]
+    fn normalize_credential_key_rejects_unsafe_keys() {
+        assert_eq!(
+            normalize_credential_key(" github:ghe.corp ").as_deref(),
+            Ok("github:ghe.corp")
+        );
+        assert!(normalize_credential_key("").is_err());
+        assert!(normalize_credential_key("has space").is_err());
+        assert!(normalize_credential_key("../escape").is_err());
+        assert!(normalize_credential_key(&"k".repeat(129)).is_err());
+    }
+}
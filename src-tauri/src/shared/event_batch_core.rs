@@ -0,0 +1,163 @@
+// Batching happens in the app's event sink; the daemon only stores the setting.
+#![allow(dead_code)]
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use serde_json::Value;
+
+use crate::backend::events::AppServerEvent;
+use crate::types::AppSettings;
+
+pub(crate) const MAX_EVENT_BATCH_INTERVAL_MS: u64 = 1000;
+
+/// Streaming fragments of a larger item. Consecutive ones for the same item
+/// can be joined into one event without the frontend noticing.
+const COALESCED_METHODS: &[&str] = &[
+    "item/agentMessage/delta",
+    "item/commandExecution/outputDelta",
+    "item/fileChange/outputDelta",
+    "item/plan/delta",
+    "item/reasoning/summaryTextDelta",
+    "item/reasoning/textDelta",
+];
+
+static BATCH_INTERVAL_MS: AtomicU64 = AtomicU64::new(0);
+
+pub(crate) fn validate_event_batch_settings(settings: &AppSettings) -> Result<(), String> {
+    if settings.event_batch_interval_ms > MAX_EVENT_BATCH_INTERVAL_MS {
+        return Err(format!(
+            "Event batch interval must be at most {MAX_EVENT_BATCH_INTERVAL_MS} ms."
+        ));
+    }
+    Ok(())
+}
+
+/// Called whenever settings are loaded or saved.
+pub(crate) fn apply_event_batch_settings(settings: &AppSettings) {
+    let interval = settings
+        .event_batch_interval_ms
+        .min(MAX_EVENT_BATCH_INTERVAL_MS);
+    BATCH_INTERVAL_MS.store(interval, Ordering::Relaxed);
+}
+
+/// `None` while batching is off, which is the default.
+pub(crate) fn batch_interval() -> Option<Duration> {
+    match BATCH_INTERVAL_MS.load(Ordering::Relaxed) {
+        0 => None,
+        interval => Some(Duration::from_millis(interval)),
+    }
+}
+
+/// Everything else (turn start/end, item start/complete, errors, requests)
+/// is delivered as soon as it arrives.
+pub(crate) fn is_coalesced(event: &AppServerEvent) -> bool {
+    event
+        .message
+        .get("method")
+        .and_then(Value::as_str)
+        .is_some_and(|method| COALESCED_METHODS.contains(&method))
+        && event.message.get("id").is_none()
+        && event
+            .message
+            .get("params")
+            .and_then(|params| params.get("delta"))
+            .is_some_and(Value::is_string)
+}
+
+/// Two deltas merge when everything but the `delta` text matches: same
+/// workspace, method, thread, item and part index.
+fn same_stream(a: &AppServerEvent, b: &AppServerEvent) -> bool {
+    if a.workspace_id != b.workspace_id || a.message.get("method") != b.message.get("method") {
+        return false;
+    }
+    let (Some(Value::Object(a)), Some(Value::Object(b))) =
+        (a.message.get("params"), b.message.get("params"))
+    else {
+        return false;
+    };
+    a.len() == b.len()
+        && a.iter()
+            .all(|(key, value)| key == "delta" || b.get(key) == Some(value))
+}
+
+/// Deltas held back until the next flush, in arrival order.
+#[derive(Default)]
+pub(crate) struct EventBatch {
+    pending: Vec<AppServerEvent>,
+}
+
+impl EventBatch {
+    /// Returns `true` when the batch was empty, so the caller schedules a
+    /// flush.
+    pub(crate) fn push(&mut self, event: AppServerEvent) -> bool {
+        let was_empty = self.pending.is_empty();
+        if let Some(last) = self.pending.last_mut() {
+            if same_stream(last, &event) {
+                let delta = event.message["params"]["delta"]
+                    .as_str()
+                    .unwrap_or_default();
+                if let Some(Value::String(text)) = last
+                    .message
+                    .get_mut("params")
+                    .and_then(|params| params.get_mut("delta"))
+                {
+                    text.push_str(delta);
+                    return false;
+                }
+            }
+        }
+        self.pending.push(event);
+        was_empty
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub(crate) fn take(&mut self) -> Vec<AppServerEvent> {
+        std::mem::take(&mut self.pending)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn delta(item_id: &str, text: &str) -> AppServerEvent {
+        AppServerEvent {
+            workspace_id: "ws".to_string(),
+            message: json!({
+                "method": "item/agentMessage/delta",
+                "params": { "threadId": "t", "itemId": item_id, "delta": text },
+            }),
+        }
+    }
+
+    #[test]
+    fn consecutive_deltas_for_an_item_are_joined() {
+        let mut batch = EventBatch::default();
+        assert!(batch.push(delta("a", "Hel")));
+        assert!(!batch.push(delta("a", "lo")));
+        assert!(!batch.push(delta("b", "x")));
+        assert!(!batch.push(delta("a", "!")));
+        let events = batch.take();
+        let deltas: Vec<&str> = events
+            .iter()
+            .map(|event| event.message["params"]["delta"].as_str().unwrap())
+            .collect();
+        assert_eq!(deltas, vec!["Hello", "x", "!"]);
+        assert!(batch.take().is_empty());
+    }
+
+    #[test]
+    fn discrete_events_are_not_coalesced() {
+        assert!(is_coalesced(&delta("a", "x")));
+        let completed = AppServerEvent {
+            workspace_id: "ws".to_string(),
+            message: json!({ "method": "turn/completed", "params": { "threadId": "t" } }),
+        };
+        assert!(!is_coalesced(&completed));
+    }
+}
@@ -1,4 +1,7 @@
 use std::path::PathBuf;
+use std::process::Stdio;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 use crate::shared::process_core::tokio_command;
 use crate::utils::{git_env_path, resolve_git_binary};
@@ -24,6 +27,7 @@ pub(crate) async fn run_git_command(repo_path: &PathBuf, args: &[&str]) -> Resul
         .args(args)
         .current_dir(repo_path)
         .env("PATH", git_env_path())
+        .kill_on_drop(true)
         .output()
         .await
         .map_err(|err| format!("Failed to run git: {err}"))?;
@@ -33,6 +37,110 @@ pub(crate) async fn run_git_command(repo_path: &PathBuf, args: &[&str]) -> Resul
     Err(format_git_error(&output.stdout, &output.stderr))
 }
 
+/// Like `run_git_command`, but hands every stderr line to `on_line` as it
+/// arrives. Git redraws progress with `\r`, so both `\r` and `\n` end a line;
+/// only the `\n`-terminated ones are kept for the error message.
+pub(crate) async fn run_git_command_streaming<F>(
+    repo_path: &PathBuf,
+    args: &[&str],
+    mut on_line: F,
+) -> Result<String, String>
+where
+    F: FnMut(&str),
+{
+    let git_bin = resolve_git_binary().map_err(|err| format!("Failed to run git: {err}"))?;
+    let mut child = tokio_command(git_bin)
+        .args(args)
+        .current_dir(repo_path)
+        .env("PATH", git_env_path())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|err| format!("Failed to run git: {err}"))?;
+    let mut stdout = child.stdout.take().ok_or("missing stdout")?;
+    let mut stderr = child.stderr.take().ok_or("missing stderr")?;
+    let stdout_task = tokio::spawn(async move {
+        let mut buffer = Vec::new();
+        let _ = stdout.read_to_end(&mut buffer).await;
+        buffer
+    });
+
+    let mut stderr_output = Vec::new();
+    let mut pending = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        let read = stderr
+            .read(&mut chunk)
+            .await
+            .map_err(|err| format!("Failed to read git output: {err}"))?;
+        if read == 0 {
+            break;
+        }
+        for byte in &chunk[..read] {
+            if *byte == b'\r' || *byte == b'\n' {
+                if !pending.is_empty() {
+                    on_line(&String::from_utf8_lossy(&pending));
+                    if *byte == b'\n' {
+                        stderr_output.extend_from_slice(&pending);
+                        stderr_output.push(b'\n');
+                    }
+                    pending.clear();
+                }
+            } else {
+                pending.push(*byte);
+            }
+        }
+    }
+    if !pending.is_empty() {
+        on_line(&String::from_utf8_lossy(&pending));
+        stderr_output.extend_from_slice(&pending);
+    }
+
+    let status = child
+        .wait()
+        .await
+        .map_err(|err| format!("Failed to run git: {err}"))?;
+    let stdout = stdout_task.await.unwrap_or_default();
+    if status.success() {
+        return Ok(String::from_utf8_lossy(&stdout).trim().to_string());
+    }
+    Err(format_git_error(&stdout, &stderr_output))
+}
+
+/// Like `run_git_command`, with `input` written to git's stdin.
+pub(crate) async fn run_git_command_with_input(
+    repo_path: &PathBuf,
+    args: &[&str],
+    input: &str,
+) -> Result<String, String> {
+    let git_bin = resolve_git_binary().map_err(|err| format!("Failed to run git: {err}"))?;
+    let mut child = tokio_command(git_bin)
+        .args(args)
+        .current_dir(repo_path)
+        .env("PATH", git_env_path())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|err| format!("Failed to run git: {err}"))?;
+    let mut stdin = child.stdin.take().ok_or("missing stdin")?;
+    stdin
+        .write_all(input.as_bytes())
+        .await
+        .map_err(|err| format!("Failed to write to git: {err}"))?;
+    drop(stdin);
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|err| format!("Failed to run git: {err}"))?;
+    if output.status.success() {
+        return Ok(String::from_utf8_lossy(&output.stdout).trim().to_string());
+    }
+    Err(format_git_error(&output.stdout, &output.stderr))
+}
+
 pub(crate) async fn run_git_command_owned(
     repo_path: PathBuf,
     args_owned: Vec<String>,
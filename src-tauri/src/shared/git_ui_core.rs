@@ -4,11 +4,23 @@ use std::path::{Path, PathBuf};
 use serde_json::Value;
 use tokio::sync::Mutex;
 
+use crate::backend::events::EventSink;
+use crate::shared::concurrency_core::{shared_limiter, OperationPriority};
 use crate::types::{
-    AppSettings, GitCommitDiff, GitFileDiff, GitHubIssuesResponse, GitHubPullRequestComment,
-    GitHubPullRequestDiff, GitHubPullRequestsResponse, GitLogResponse, WorkspaceEntry,
+    AppSettings, GitBisectState, GitBlameLine, GitCommitDiff, GitFetchedRef, GitFileDiff,
+    GitFileHistoryEntry, GitGraphResponse, GitHubChecksResponse, GitHubCreatedPullRequest,
+    GitHubIssuesResponse, GitHubMergeResult, GitHubPullRequestComment, GitHubPullRequestDiff,
+    GitHubPullRequestInput, GitHubPullRequestsResponse, GitHubReviewComment, GitLogFilter,
+    GitLogResponse, GitRemote, GitRemoteUpdates, GitRepoStats, GitResetResult, GitStashApplyResult,
+    GitStashEntry, GitSyncResult, WorkspaceAudit, WorkspaceEntry,
 };
 
+#[path = "git_ui_core/audit.rs"]
+mod audit;
+#[path = "git_ui_core/bisect.rs"]
+mod bisect;
+#[path = "git_ui_core/blame.rs"]
+mod blame;
 #[path = "git_ui_core/commands.rs"]
 mod commands;
 #[path = "git_ui_core/context.rs"]
@@ -17,8 +29,16 @@ mod context;
 mod diff;
 #[path = "git_ui_core/github.rs"]
 mod github;
+#[path = "git_ui_core/hunks.rs"]
+mod hunks;
 #[path = "git_ui_core/log.rs"]
 mod log;
+#[path = "git_ui_core/stash.rs"]
+mod stash;
+#[path = "git_ui_core/stats.rs"]
+mod stats;
+#[path = "git_ui_core/sync.rs"]
+mod sync;
 
 #[cfg(test)]
 #[path = "git_ui_core/tests.rs"]
@@ -38,7 +58,11 @@ pub(crate) fn collect_workspace_diff_core(repo_root: &Path) -> Result<String, St
 pub(crate) async fn get_git_status_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
+    priority: Option<OperationPriority>,
 ) -> Result<Value, String> {
+    let _permit = shared_limiter()
+        .acquire(priority.unwrap_or(OperationPriority::Interactive))
+        .await;
     diff::get_git_status_inner(workspaces, workspace_id).await
 }
 
@@ -73,16 +97,99 @@ pub(crate) async fn get_git_diffs_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     app_settings: &Mutex<AppSettings>,
     workspace_id: String,
+    priority: Option<OperationPriority>,
+    context_lines: Option<usize>,
 ) -> Result<Vec<GitFileDiff>, String> {
-    diff::get_git_diffs_inner(workspaces, app_settings, workspace_id).await
+    let _permit = shared_limiter()
+        .acquire(priority.unwrap_or(OperationPriority::Interactive))
+        .await;
+    diff::get_git_diffs_inner(workspaces, app_settings, workspace_id, context_lines).await
+}
+
+pub(crate) async fn get_git_diff_range_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    workspace_id: String,
+    base: String,
+    head: String,
+    paths: Vec<String>,
+    two_dot: bool,
+) -> Result<Vec<GitFileDiff>, String> {
+    let _permit = shared_limiter()
+        .acquire(OperationPriority::Interactive)
+        .await;
+    diff::get_git_diff_range_inner(
+        workspaces,
+        app_settings,
+        workspace_id,
+        base,
+        head,
+        paths,
+        two_dot,
+    )
+    .await
 }
 
 pub(crate) async fn get_git_log_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
+    skip: Option<usize>,
     limit: Option<usize>,
+    path: Option<String>,
+    filter: Option<GitLogFilter>,
 ) -> Result<GitLogResponse, String> {
-    log::get_git_log_inner(workspaces, workspace_id, limit).await
+    log::get_git_log_inner(
+        workspaces,
+        workspace_id,
+        skip,
+        limit,
+        path,
+        filter.unwrap_or_default(),
+    )
+    .await
+}
+
+pub(crate) async fn get_git_graph_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    max_count: Option<usize>,
+    refs: Option<Vec<String>>,
+) -> Result<GitGraphResponse, String> {
+    log::get_git_graph_inner(workspaces, workspace_id, max_count, refs).await
+}
+
+pub(crate) async fn get_file_history_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+    follow_renames: bool,
+    max_count: Option<usize>,
+) -> Result<Vec<GitFileHistoryEntry>, String> {
+    log::get_file_history_inner(workspaces, workspace_id, path, follow_renames, max_count).await
+}
+
+pub(crate) async fn git_blame_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+    rev: Option<String>,
+) -> Result<Vec<GitBlameLine>, String> {
+    blame::git_blame_inner(workspaces, workspace_id, path, rev).await
+}
+
+pub(crate) async fn get_repo_stats_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<GitRepoStats, String> {
+    stats::get_repo_stats_inner(workspaces, workspace_id).await
+}
+
+pub(crate) async fn audit_workspace_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    workspace_id: String,
+) -> Result<WorkspaceAudit, String> {
+    audit::audit_workspace_inner(workspaces, app_settings, workspace_id).await
 }
 
 pub(crate) async fn get_git_commit_diff_core(
@@ -101,6 +208,13 @@ pub(crate) async fn get_git_remote_core(
     log::get_git_remote_inner(workspaces, workspace_id).await
 }
 
+pub(crate) async fn list_git_remotes_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<Vec<GitRemote>, String> {
+    log::list_git_remotes_inner(workspaces, workspace_id).await
+}
+
 pub(crate) async fn stage_git_file_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
@@ -109,6 +223,16 @@ pub(crate) async fn stage_git_file_core(
     commands::stage_git_file_inner(workspaces, workspace_id, path).await
 }
 
+pub(crate) async fn stage_git_hunk_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+    hunk_header: String,
+    patch: String,
+) -> Result<(), String> {
+    hunks::stage_git_hunk_inner(workspaces, workspace_id, path, hunk_header, patch).await
+}
+
 pub(crate) async fn stage_git_all_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
@@ -124,6 +248,23 @@ pub(crate) async fn unstage_git_file_core(
     commands::unstage_git_file_inner(workspaces, workspace_id, path).await
 }
 
+pub(crate) async fn unstage_git_hunk_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+    hunk_header: String,
+    patch: String,
+) -> Result<(), String> {
+    hunks::unstage_git_hunk_inner(workspaces, workspace_id, path, hunk_header, patch).await
+}
+
+pub(crate) async fn unstage_git_all_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<usize, String> {
+    commands::unstage_git_all_inner(workspaces, workspace_id).await
+}
+
 pub(crate) async fn revert_git_file_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
@@ -139,6 +280,16 @@ pub(crate) async fn revert_git_all_core(
     commands::revert_git_all_inner(workspaces, workspace_id).await
 }
 
+pub(crate) async fn git_reset_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    target: Option<String>,
+    mode: String,
+    confirm: bool,
+) -> Result<GitResetResult, String> {
+    commands::git_reset_inner(workspaces, workspace_id, target, mode, confirm).await
+}
+
 pub(crate) async fn commit_git_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
@@ -147,6 +298,16 @@ pub(crate) async fn commit_git_core(
     commands::commit_git_inner(workspaces, workspace_id, message).await
 }
 
+pub(crate) async fn git_commit_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    message: String,
+    amend: bool,
+    signoff: bool,
+) -> Result<String, String> {
+    commands::git_commit_inner(workspaces, workspace_id, message, amend, signoff).await
+}
+
 pub(crate) async fn push_git_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
@@ -157,15 +318,104 @@ pub(crate) async fn push_git_core(
 pub(crate) async fn pull_git_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
+    priority: Option<OperationPriority>,
 ) -> Result<(), String> {
+    let _permit = shared_limiter()
+        .acquire(priority.unwrap_or(OperationPriority::Background))
+        .await;
     commands::pull_git_inner(workspaces, workspace_id).await
 }
 
+pub(crate) async fn git_push_core<E: EventSink>(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    set_upstream: bool,
+    priority: Option<OperationPriority>,
+    event_sink: &E,
+) -> Result<GitSyncResult, String> {
+    let _permit = shared_limiter()
+        .acquire(priority.unwrap_or(OperationPriority::Background))
+        .await;
+    sync::git_push_inner(workspaces, workspace_id, set_upstream, event_sink).await
+}
+
+pub(crate) async fn git_pull_core<E: EventSink>(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    rebase: bool,
+    priority: Option<OperationPriority>,
+    event_sink: &E,
+) -> Result<GitSyncResult, String> {
+    let _permit = shared_limiter()
+        .acquire(priority.unwrap_or(OperationPriority::Background))
+        .await;
+    sync::git_pull_inner(workspaces, workspace_id, rebase, event_sink).await
+}
+
+pub(crate) async fn git_fetch_core<E: EventSink>(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    remote: Option<String>,
+    prune: bool,
+    priority: Option<OperationPriority>,
+    event_sink: &E,
+) -> Result<Vec<GitFetchedRef>, String> {
+    let _permit = shared_limiter()
+        .acquire(priority.unwrap_or(OperationPriority::Background))
+        .await;
+    sync::git_fetch_inner(workspaces, workspace_id, remote, prune, event_sink).await
+}
+
+pub(crate) async fn git_stash_list_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<Vec<GitStashEntry>, String> {
+    stash::git_stash_list_inner(workspaces, workspace_id).await
+}
+
+pub(crate) async fn git_stash_push_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    message: Option<String>,
+    include_untracked: bool,
+) -> Result<(), String> {
+    stash::git_stash_push_inner(workspaces, workspace_id, message, include_untracked).await
+}
+
+pub(crate) async fn git_stash_apply_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    index: usize,
+) -> Result<GitStashApplyResult, String> {
+    stash::git_stash_apply_inner(workspaces, workspace_id, index).await
+}
+
+pub(crate) async fn git_stash_pop_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    index: usize,
+) -> Result<GitStashApplyResult, String> {
+    stash::git_stash_pop_inner(workspaces, workspace_id, index).await
+}
+
+pub(crate) async fn git_stash_drop_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    index: usize,
+) -> Result<(), String> {
+    stash::git_stash_drop_inner(workspaces, workspace_id, index).await
+}
+
 pub(crate) async fn fetch_git_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    storage_path: &Path,
     workspace_id: String,
+    priority: Option<OperationPriority>,
 ) -> Result<(), String> {
-    commands::fetch_git_inner(workspaces, workspace_id).await
+    let _permit = shared_limiter()
+        .acquire(priority.unwrap_or(OperationPriority::Background))
+        .await;
+    commands::fetch_git_inner(workspaces, storage_path, workspace_id).await
 }
 
 pub(crate) async fn sync_git_core(
@@ -175,6 +425,13 @@ pub(crate) async fn sync_git_core(
     commands::sync_git_inner(workspaces, workspace_id).await
 }
 
+pub(crate) async fn check_remote_updates_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<GitRemoteUpdates, String> {
+    commands::check_remote_updates_inner(workspaces, workspace_id).await
+}
+
 pub(crate) async fn get_github_issues_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
@@ -205,6 +462,14 @@ pub(crate) async fn get_github_pull_request_comments_core(
     github::get_github_pull_request_comments_inner(workspaces, workspace_id, pr_number).await
 }
 
+pub(crate) async fn get_github_pull_request_checks_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    pr_number: u64,
+) -> Result<GitHubChecksResponse, String> {
+    github::get_github_pull_request_checks_inner(workspaces, workspace_id, pr_number).await
+}
+
 pub(crate) async fn checkout_github_pull_request_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
@@ -213,6 +478,52 @@ pub(crate) async fn checkout_github_pull_request_core(
     github::checkout_github_pull_request_inner(workspaces, workspace_id, pr_number).await
 }
 
+pub(crate) async fn create_github_pull_request_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    input: GitHubPullRequestInput,
+) -> Result<GitHubCreatedPullRequest, String> {
+    github::create_github_pull_request_inner(workspaces, workspace_id, input).await
+}
+
+pub(crate) async fn submit_github_pull_request_review_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    pr_number: u64,
+    event: String,
+    body: Option<String>,
+    comments: Vec<GitHubReviewComment>,
+) -> Result<u64, String> {
+    github::submit_github_pull_request_review_inner(
+        workspaces,
+        workspace_id,
+        pr_number,
+        event,
+        body,
+        comments,
+    )
+    .await
+}
+
+pub(crate) async fn merge_github_pull_request_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    pr_number: u64,
+    method: String,
+    commit_title: Option<String>,
+    commit_message: Option<String>,
+) -> Result<GitHubMergeResult, String> {
+    github::merge_github_pull_request_inner(
+        workspaces,
+        workspace_id,
+        pr_number,
+        method,
+        commit_title,
+        commit_message,
+    )
+    .await
+}
+
 pub(crate) async fn list_git_branches_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
@@ -235,3 +546,34 @@ pub(crate) async fn create_git_branch_core(
 ) -> Result<(), String> {
     commands::create_git_branch_inner(workspaces, workspace_id, name).await
 }
+
+pub(crate) async fn git_bisect_start_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    bad: String,
+    good: String,
+) -> Result<GitBisectState, String> {
+    bisect::git_bisect_start_inner(workspaces, workspace_id, bad, good).await
+}
+
+pub(crate) async fn git_bisect_mark_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    verdict: String,
+) -> Result<GitBisectState, String> {
+    bisect::git_bisect_mark_inner(workspaces, workspace_id, verdict).await
+}
+
+pub(crate) async fn git_bisect_reset_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<GitBisectState, String> {
+    bisect::git_bisect_reset_inner(workspaces, workspace_id).await
+}
+
+pub(crate) async fn get_git_bisect_state_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<GitBisectState, String> {
+    bisect::get_git_bisect_state_inner(workspaces, workspace_id).await
+}
@@ -0,0 +1,212 @@
+use std::collections::{HashMap, HashSet};
+
+use git2::Repository;
+use tokio::sync::Mutex;
+
+use crate::shared::redaction_core::SecretRedactor;
+use crate::types::{AppSettings, WorkspaceAudit, WorkspaceAuditFinding, WorkspaceEntry};
+use crate::utils::normalize_git_path;
+
+use super::context::resolve_repo_root_for_workspace;
+
+const MAX_AUDITED_FILES: usize = 20_000;
+const MAX_SCANNED_BLOB_BYTES: u64 = 1024 * 1024;
+const LARGE_FILE_BYTES: u64 = 5 * 1024 * 1024;
+const HUGE_FILE_BYTES: u64 = 50 * 1024 * 1024;
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+const MAX_SECRET_FINDINGS_PER_FILE: usize = 20;
+
+const SENSITIVE_FILE_NAMES: &[&str] = &[
+    "id_rsa",
+    "id_dsa",
+    "id_ecdsa",
+    "id_ed25519",
+    ".netrc",
+    ".pgpass",
+    "credentials.json",
+];
+const SENSITIVE_EXTENSIONS: &[&str] = &["pem", "key", "p12", "pfx", "jks", "keystore"];
+const ENV_TEMPLATE_SUFFIXES: &[&str] = &["example", "sample", "template", "dist"];
+
+/// Names files that usually hold credentials: `.env` variants other than
+/// templates, private keys and keystores.
+pub(super) fn sensitive_file_reason(path: &str) -> Option<&'static str> {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    let lower = name.to_ascii_lowercase();
+    if lower == ".env" {
+        return Some("Environment files usually hold credentials.");
+    }
+    if let Some(suffix) = lower.strip_prefix(".env.") {
+        if ENV_TEMPLATE_SUFFIXES.contains(&suffix) {
+            return None;
+        }
+        return Some("Environment files usually hold credentials.");
+    }
+    if SENSITIVE_FILE_NAMES.contains(&lower.as_str()) {
+        return Some("This file usually holds private credentials.");
+    }
+    let extension = lower.rsplit_once('.').map(|(_, extension)| extension)?;
+    if SENSITIVE_EXTENSIONS.contains(&extension) {
+        return Some("Key and certificate files should not be committed.");
+    }
+    None
+}
+
+fn finding(
+    path: &str,
+    kind: &str,
+    severity: &str,
+    line: Option<u32>,
+    message: String,
+    staged: bool,
+) -> WorkspaceAuditFinding {
+    WorkspaceAuditFinding {
+        path: path.to_string(),
+        kind: kind.to_string(),
+        severity: severity.to_string(),
+        line,
+        message,
+        staged,
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+/// Reports secrets by pattern name and line only; the matched text never
+/// leaves this function.
+pub(super) fn scan_for_secrets(
+    redactor: &SecretRedactor,
+    path: &str,
+    content: &str,
+    staged: bool,
+) -> Vec<WorkspaceAuditFinding> {
+    let mut findings = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let Some((_, hits)) = redactor.redact_line(line) else {
+            continue;
+        };
+        for pattern in hits {
+            findings.push(finding(
+                path,
+                "secret",
+                "high",
+                Some(index as u32 + 1),
+                format!("Possible secret ({pattern})."),
+                staged,
+            ));
+        }
+        if findings.len() >= MAX_SECRET_FINDINGS_PER_FILE {
+            break;
+        }
+    }
+    findings
+}
+
+pub(super) fn audit_repository(
+    repo: &Repository,
+    redactor: &SecretRedactor,
+) -> Result<WorkspaceAudit, String> {
+    let index = repo.index().map_err(|e| e.to_string())?;
+    let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+    let staged: HashSet<String> = repo
+        .diff_tree_to_index(head_tree.as_ref(), Some(&index), None)
+        .map_err(|e| e.to_string())?
+        .deltas()
+        .filter(|delta| delta.status() != git2::Delta::Deleted)
+        .filter_map(|delta| delta.new_file().path())
+        .map(|path| normalize_git_path(&path.to_string_lossy()))
+        .collect();
+
+    let mut findings = Vec::new();
+    let mut scanned_files = 0usize;
+    let truncated = index.len() > MAX_AUDITED_FILES;
+    for entry in index.iter().take(MAX_AUDITED_FILES) {
+        let path = normalize_git_path(&String::from_utf8_lossy(&entry.path));
+        let is_staged = staged.contains(&path);
+        scanned_files += 1;
+
+        if let Some(reason) = sensitive_file_reason(&path) {
+            findings.push(finding(
+                &path,
+                "sensitiveFile",
+                "high",
+                None,
+                reason.to_string(),
+                is_staged,
+            ));
+        }
+
+        let size = u64::from(entry.file_size);
+        if size >= LARGE_FILE_BYTES {
+            let severity = if size >= HUGE_FILE_BYTES {
+                "high"
+            } else {
+                "medium"
+            };
+            findings.push(finding(
+                &path,
+                "largeFile",
+                severity,
+                None,
+                format!("{} blob; consider Git LFS.", format_size(size)),
+                is_staged,
+            ));
+        }
+        if size > MAX_SCANNED_BLOB_BYTES {
+            continue;
+        }
+        let Ok(blob) = repo.find_blob(entry.id) else {
+            continue;
+        };
+        let content = blob.content();
+        if content[..content.len().min(BINARY_SNIFF_BYTES)].contains(&0) {
+            continue;
+        }
+        let text = String::from_utf8_lossy(content);
+        findings.extend(scan_for_secrets(redactor, &path, &text, is_staged));
+    }
+
+    findings.sort_by(|a, b| {
+        severity_rank(&a.severity)
+            .cmp(&severity_rank(&b.severity))
+            .then(b.staged.cmp(&a.staged))
+            .then(a.path.cmp(&b.path))
+    });
+    let blocking = findings
+        .iter()
+        .any(|finding| finding.staged && finding.severity == "high");
+    Ok(WorkspaceAudit {
+        findings,
+        scanned_files,
+        truncated,
+        blocking,
+    })
+}
+
+fn severity_rank(severity: &str) -> u8 {
+    match severity {
+        "high" => 0,
+        "medium" => 1,
+        _ => 2,
+    }
+}
+
+/// Scans the index, so staged content is what gets checked. Custom secret
+/// patterns are included even while diff redaction is turned off.
+pub(super) async fn audit_workspace_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    workspace_id: String,
+) -> Result<WorkspaceAudit, String> {
+    let repo_root = resolve_repo_root_for_workspace(workspaces, workspace_id).await?;
+    let custom_patterns = app_settings.lock().await.secret_patterns.clone();
+    let redactor = SecretRedactor::with_custom_patterns(&custom_patterns)?;
+    tokio::task::spawn_blocking(move || {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        audit_repository(&repo, &redactor)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
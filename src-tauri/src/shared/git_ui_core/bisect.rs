@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use git2::{Oid, Repository};
+use tokio::sync::Mutex;
+
+use crate::git_utils::commit_to_entry;
+use crate::types::{GitBisectState, GitLogEntry, WorkspaceEntry};
+
+use super::commands::run_git_command_output;
+use super::context::resolve_repo_root_for_workspace;
+
+#[derive(Debug, Default, PartialEq)]
+pub(super) struct BisectLog {
+    pub(super) bad: Option<String>,
+    pub(super) good: Vec<String>,
+    pub(super) first_bad: Option<String>,
+}
+
+fn bracketed_sha(rest: &str) -> Option<String> {
+    let start = rest.find('[')? + 1;
+    let end = start + rest[start..].find(']')?;
+    Some(rest[start..end].to_string())
+}
+
+/// Reads the `# bad:`, `# good:` and `# first bad commit:` comments that
+/// `git bisect log` writes after every step.
+pub(super) fn parse_bisect_log(output: &str) -> BisectLog {
+    let mut log = BisectLog::default();
+    for line in output.lines() {
+        let Some(comment) = line.strip_prefix("# ") else {
+            continue;
+        };
+        if let Some(rest) = comment.strip_prefix("bad:") {
+            log.bad = bracketed_sha(rest);
+        } else if let Some(rest) = comment.strip_prefix("good:") {
+            if let Some(sha) = bracketed_sha(rest) {
+                log.good.push(sha);
+            }
+        } else if let Some(rest) = comment.strip_prefix("first bad commit:") {
+            log.first_bad = bracketed_sha(rest);
+        }
+    }
+    log
+}
+
+/// Returns `(bisect_nr, bisect_steps)` from `git rev-list --bisect-vars`.
+pub(super) fn parse_bisect_vars(output: &str) -> (Option<u32>, Option<u32>) {
+    let mut revisions = None;
+    let mut steps = None;
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim().trim_matches('\'');
+        match key.trim() {
+            "bisect_nr" => revisions = value.parse().ok(),
+            "bisect_steps" => steps = value.parse().ok(),
+            _ => {}
+        }
+    }
+    (revisions, steps)
+}
+
+fn find_entry(repo: &Repository, sha: &str) -> Option<GitLogEntry> {
+    let oid = Oid::from_str(sha).ok()?;
+    repo.find_commit(oid).ok().map(commit_to_entry)
+}
+
+/// Rebuilt from the repository on every call, so a bisect started in a
+/// terminal or before a restart is picked up as well.
+async fn read_bisect_state(repo_root: &Path) -> Result<GitBisectState, String> {
+    let Ok(log_output) = run_git_command_output(repo_root, &["bisect", "log"]).await else {
+        return Ok(GitBisectState::default());
+    };
+    let log = parse_bisect_log(&log_output);
+    let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
+
+    let mut state = GitBisectState {
+        active: true,
+        bad: log.bad.clone(),
+        good: log.good.clone(),
+        ..GitBisectState::default()
+    };
+    if let Some(first_bad) = log.first_bad.as_deref() {
+        state.first_bad_commit = find_entry(&repo, first_bad);
+        return Ok(state);
+    }
+    let Some(bad) = log.bad.as_deref() else {
+        return Ok(state);
+    };
+    if log.good.is_empty() {
+        return Ok(state);
+    }
+    let mut args = vec!["rev-list", "--bisect-vars", bad, "--not"];
+    args.extend(log.good.iter().map(String::as_str));
+    let vars = run_git_command_output(repo_root, &args).await?;
+    let (revisions_left, steps_left) = parse_bisect_vars(&vars);
+    state.revisions_left = revisions_left;
+    state.steps_left = steps_left;
+    state.candidate = repo
+        .head()
+        .ok()
+        .and_then(|head| head.peel_to_commit().ok())
+        .map(commit_to_entry);
+    Ok(state)
+}
+
+pub(super) async fn git_bisect_start_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    bad: String,
+    good: String,
+) -> Result<GitBisectState, String> {
+    let bad = bad.trim();
+    let good = good.trim();
+    if bad.is_empty() || good.is_empty() {
+        return Err("Both a bad and a good commit are required.".to_string());
+    }
+    let repo_root = resolve_repo_root_for_workspace(workspaces, workspace_id).await?;
+    let bad = resolve_bisect_commit(&repo_root, bad).await?;
+    let good = resolve_bisect_commit(&repo_root, good).await?;
+    run_git_command_output(&repo_root, &["bisect", "start", &bad, &good, "--"]).await?;
+    read_bisect_state(&repo_root).await
+}
+
+/// Resolves `rev` to a commit sha before it reaches `git bisect start`,
+/// which would otherwise read a leading `-` as an option.
+async fn resolve_bisect_commit(repo_root: &Path, rev: &str) -> Result<String, String> {
+    if rev.starts_with('-') {
+        return Err("Revision must not start with `-`.".to_string());
+    }
+    let spec = format!("{rev}^{{commit}}");
+    run_git_command_output(repo_root, &["rev-parse", "--verify", "--quiet", &spec])
+        .await
+        .ok()
+        .map(|sha| sha.trim().to_string())
+        .filter(|sha| !sha.is_empty())
+        .ok_or_else(|| format!("`{rev}` doesn't name a commit in this repository."))
+}
+
+pub(super) async fn git_bisect_mark_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    verdict: String,
+) -> Result<GitBisectState, String> {
+    let verdict = match verdict.trim() {
+        "good" => "good",
+        "bad" => "bad",
+        other => return Err(format!("Invalid bisect verdict: {other}")),
+    };
+    let repo_root = resolve_repo_root_for_workspace(workspaces, workspace_id).await?;
+    if run_git_command_output(&repo_root, &["bisect", "log"])
+        .await
+        .is_err()
+    {
+        return Err("No bisect in progress.".to_string());
+    }
+    run_git_command_output(&repo_root, &["bisect", verdict]).await?;
+    read_bisect_state(&repo_root).await
+}
+
+pub(super) async fn git_bisect_reset_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<GitBisectState, String> {
+    let repo_root = resolve_repo_root_for_workspace(workspaces, workspace_id).await?;
+    run_git_command_output(&repo_root, &["bisect", "reset"]).await?;
+    read_bisect_state(&repo_root).await
+}
+
+pub(super) async fn get_git_bisect_state_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<GitBisectState, String> {
+    let repo_root = resolve_repo_root_for_workspace(workspaces, workspace_id).await?;
+    read_bisect_state(&repo_root).await
+}
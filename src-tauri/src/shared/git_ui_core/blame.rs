@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use tokio::sync::Mutex;
+
+use crate::git_utils::resolve_git_root;
+use crate::types::{GitBlameLine, WorkspaceEntry};
+use crate::utils::normalize_git_path;
+
+use super::commands::run_git_command_output;
+use super::context::workspace_entry_for_id;
+
+const UNCOMMITTED_AUTHOR: &str = "Not Committed Yet";
+
+#[derive(Default, Clone)]
+struct BlameCommit {
+    author: String,
+    author_email: String,
+    author_time: i64,
+    summary: String,
+}
+
+/// Reads `git blame --porcelain`. A commit's details are only written the
+/// first time it appears, so they're kept by hash for its later lines.
+pub(super) fn parse_blame_porcelain(output: &str) -> Vec<GitBlameLine> {
+    let mut commits: HashMap<String, BlameCommit> = HashMap::new();
+    let mut lines = Vec::new();
+    let mut current: Option<(String, u32)> = None;
+    for line in output.lines() {
+        if let Some(content) = line.strip_prefix('\t') {
+            let Some((sha, line_number)) = current.take() else {
+                continue;
+            };
+            let commit = commits.get(&sha).cloned().unwrap_or_default();
+            let committed = sha.chars().any(|ch| ch != '0');
+            lines.push(GitBlameLine {
+                line_number,
+                author: if committed {
+                    commit.author
+                } else {
+                    UNCOMMITTED_AUTHOR.to_string()
+                },
+                author_email: commit.author_email,
+                author_time: commit.author_time,
+                summary: commit.summary,
+                content: content.to_string(),
+                committed,
+                sha,
+            });
+            continue;
+        }
+        if let Some((sha, _)) = current.as_ref() {
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            let commit = commits.entry(sha.clone()).or_default();
+            match key {
+                "author" => commit.author = value.to_string(),
+                "author-mail" => {
+                    commit.author_email = value
+                        .trim_start_matches('<')
+                        .trim_end_matches('>')
+                        .to_string()
+                }
+                "author-time" => commit.author_time = value.parse().unwrap_or(0),
+                "summary" => commit.summary = value.to_string(),
+                _ => {}
+            }
+            continue;
+        }
+        // `<sha> <original line> <final line> [<lines in group>]`
+        let mut fields = line.split(' ');
+        let (Some(sha), Some(_), Some(final_line)) = (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        if sha.len() < 40 || !sha.chars().all(|ch| ch.is_ascii_hexdigit()) {
+            continue;
+        }
+        if let Ok(final_line) = final_line.parse() {
+            current = Some((sha.to_string(), final_line));
+        }
+    }
+    lines
+}
+
+/// Blames `path` as of `rev`, or the working tree when `rev` is unset, in
+/// which case edited lines come back as "Not Committed Yet". Git runs as a
+/// child process and the output is parsed off the async runtime.
+pub(super) async fn git_blame_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+    rev: Option<String>,
+) -> Result<Vec<GitBlameLine>, String> {
+    let path = normalize_git_path(path.trim());
+    if path.is_empty() {
+        return Err("File path is required.".to_string());
+    }
+    let rev = rev
+        .map(|rev| rev.trim().to_string())
+        .filter(|rev| !rev.is_empty());
+    if rev.as_deref().is_some_and(|rev| rev.starts_with('-')) {
+        return Err("Revision must not start with `-`.".to_string());
+    }
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let mut args = vec!["blame", "--porcelain"];
+    if let Some(rev) = rev.as_deref() {
+        args.push(rev);
+    }
+    args.extend(["--", path.as_str()]);
+    let output = run_git_command_output(&repo_root, &args).await?;
+    tokio::task::spawn_blocking(move || parse_blame_porcelain(&output))
+        .await
+        .map_err(|err| err.to_string())
+}
@@ -1,36 +1,53 @@
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::{Mutex as StdMutex, OnceLock};
+use std::time::{Duration, Instant};
 
-use git2::{BranchType, Repository, Status, StatusOptions};
+use git2::{BranchType, Oid, Repository, Status, StatusOptions};
 use serde_json::{json, Value};
 use tokio::sync::Mutex;
 
 use crate::git_utils::{
     checkout_branch, list_git_roots as scan_git_roots, parse_github_repo, resolve_git_root,
 };
+use crate::shared::operation_timing_core;
 use crate::shared::process_core::tokio_command;
-use crate::types::{BranchInfo, WorkspaceEntry};
-use crate::utils::{git_env_path, normalize_git_path, resolve_git_binary};
+use crate::types::{BranchInfo, GitRemoteUpdates, GitResetResult, WorkspaceEntry};
+use crate::utils::{git_env_path, normalize_git_path, now_unix_ms, resolve_git_binary};
 
 use super::context::workspace_entry_for_id;
 
+const REMOTE_UPDATES_TIMEOUT: Duration = Duration::from_secs(20);
+const REMOTE_UPDATES_MIN_INTERVAL: Duration = Duration::from_secs(30);
+
+static REMOTE_UPDATE_CHECKS: OnceLock<StdMutex<HashMap<PathBuf, (Instant, GitRemoteUpdates)>>> =
+    OnceLock::new();
+
 async fn run_git_command(repo_root: &Path, args: &[&str]) -> Result<(), String> {
+    run_git_command_output(repo_root, args).await.map(|_| ())
+}
+
+pub(super) async fn run_git_command_output(
+    repo_root: &Path,
+    args: &[&str],
+) -> Result<String, String> {
     let git_bin = resolve_git_binary().map_err(|e| format!("Failed to run git: {e}"))?;
     let output = tokio_command(git_bin)
         .args(args)
         .current_dir(repo_root)
         .env("PATH", git_env_path())
+        .kill_on_drop(true)
         .output()
         .await
         .map_err(|e| format!("Failed to run git: {e}"))?;
 
+    let stdout = String::from_utf8_lossy(&output.stdout);
     if output.status.success() {
-        return Ok(());
+        return Ok(stdout.to_string());
     }
 
     let stderr = String::from_utf8_lossy(&output.stderr);
-    let stdout = String::from_utf8_lossy(&output.stdout);
     let detail = if stderr.trim().is_empty() {
         stdout.trim()
     } else {
@@ -308,7 +325,9 @@ fn parse_upstream_ref(name: &str) -> Option<(String, String)> {
     Some((remote.to_string(), branch.to_string()))
 }
 
-fn upstream_remote_and_branch(repo_root: &Path) -> Result<Option<(String, String)>, String> {
+pub(super) fn upstream_remote_and_branch(
+    repo_root: &Path,
+) -> Result<Option<(String, String)>, String> {
     let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
     let head = match repo.head() {
         Ok(head) => head,
@@ -394,6 +413,86 @@ async fn pull_with_default_strategy(repo_root: &Path) -> Result<(), String> {
     }
 }
 
+async fn run_git_network_command(repo_root: &Path, args: &[&str]) -> Result<String, String> {
+    let git_bin = resolve_git_binary().map_err(|e| format!("Failed to run git: {e}"))?;
+    let mut command = tokio_command(git_bin);
+    command
+        .args(args)
+        .current_dir(repo_root)
+        .env("PATH", git_env_path())
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .kill_on_drop(true);
+    // Dropping the future (timeout or caller cancellation) kills the git child.
+    let output = tokio::time::timeout(REMOTE_UPDATES_TIMEOUT, command.output())
+        .await
+        .map_err(|_| "Timed out contacting the remote.".to_string())?
+        .map_err(|e| format!("Failed to run git: {e}"))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    if output.status.success() {
+        return Ok(stdout);
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let detail = if stderr.trim().is_empty() {
+        stdout.trim()
+    } else {
+        stderr.trim()
+    };
+    if detail.is_empty() {
+        return Err("Git command failed.".to_string());
+    }
+    Err(detail.to_string())
+}
+
+pub(super) fn parse_ls_remote_sha(output: &str, branch: &str) -> Option<String> {
+    let target = format!("refs/heads/{branch}");
+    output.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        let sha = parts.next()?;
+        let name = parts.next()?;
+        (name == target).then(|| sha.to_string())
+    })
+}
+
+fn cached_remote_updates(repo_root: &Path) -> Option<GitRemoteUpdates> {
+    let checks = REMOTE_UPDATE_CHECKS.get_or_init(|| StdMutex::new(HashMap::new()));
+    let checks = checks.lock().ok()?;
+    let (checked, result) = checks.get(repo_root)?;
+    if checked.elapsed() >= REMOTE_UPDATES_MIN_INTERVAL {
+        return None;
+    }
+    let mut result = result.clone();
+    result.cached = true;
+    Some(result)
+}
+
+fn store_remote_updates(repo_root: &Path, result: &GitRemoteUpdates) {
+    let checks = REMOTE_UPDATE_CHECKS.get_or_init(|| StdMutex::new(HashMap::new()));
+    if let Ok(mut checks) = checks.lock() {
+        checks.retain(|_, (checked, _)| checked.elapsed() < REMOTE_UPDATES_MIN_INTERVAL);
+        checks.insert(repo_root.to_path_buf(), (Instant::now(), result.clone()));
+    }
+}
+
+fn ahead_behind_against(
+    repo_root: &Path,
+    remote_sha: &str,
+) -> Result<Option<(usize, usize)>, String> {
+    let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
+    let remote_oid = Oid::from_str(remote_sha).map_err(|e| e.to_string())?;
+    if repo.find_commit(remote_oid).is_err() {
+        return Ok(None);
+    }
+    let head_oid = repo
+        .head()
+        .ok()
+        .and_then(|head| head.target())
+        .ok_or_else(|| "HEAD has no commits.".to_string())?;
+    repo.graph_ahead_behind(head_oid, remote_oid)
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
+
 pub(super) async fn stage_git_file_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
@@ -429,6 +528,24 @@ pub(super) async fn unstage_git_file_inner(
     Ok(())
 }
 
+/// Takes every staged change back out of the index, leaving the working
+/// tree as it is, and returns how many files were unstaged.
+pub(super) async fn unstage_git_all_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<usize, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let staged = diff_name_only(&repo_root, &["--cached", "--"]).await?;
+    if staged.is_empty() {
+        return Ok(0);
+    }
+    // Unlike `git restore --staged`, `git reset` also works before the
+    // first commit.
+    run_git_command(&repo_root, &["reset", "-q", "--", "."]).await?;
+    Ok(staged.len())
+}
+
 pub(super) async fn revert_git_file_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
@@ -465,6 +582,82 @@ pub(super) async fn revert_git_all_inner(
     run_git_command(&repo_root, &["clean", "-f", "-d"]).await
 }
 
+const RESET_MODES: &[&str] = &["soft", "mixed", "hard"];
+
+/// Paths `git diff -z --name-only` lists for `args`.
+async fn diff_name_only(repo_root: &Path, args: &[&str]) -> Result<Vec<String>, String> {
+    let mut command = vec!["diff", "-z", "--name-only"];
+    command.extend_from_slice(args);
+    let output = run_git_command_output(repo_root, &command).await?;
+    Ok(output
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(normalize_git_path)
+        .collect())
+}
+
+/// Moves HEAD to `target`, HEAD unless given, like `git reset --<mode>`.
+/// A hard reset throws away uncommitted changes, so it needs `confirm`.
+/// The files it put back, or for a mixed reset the files whose staged
+/// changes left the index, are listed before git runs; a soft reset
+/// touches neither.
+pub(super) async fn git_reset_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    target: Option<String>,
+    mode: String,
+    confirm: bool,
+) -> Result<GitResetResult, String> {
+    let mode = mode.trim().to_ascii_lowercase();
+    if !RESET_MODES.contains(&mode.as_str()) {
+        return Err(format!(
+            "Unknown reset mode `{mode}`; use soft, mixed or hard."
+        ));
+    }
+    if mode == "hard" && !confirm {
+        return Err(
+            "A hard reset discards uncommitted changes; confirm it to go ahead.".to_string(),
+        );
+    }
+    let target = target
+        .map(|target| target.trim().to_string())
+        .filter(|target| !target.is_empty())
+        .unwrap_or_else(|| "HEAD".to_string());
+    if target.starts_with('-') {
+        return Err("Revision must not start with `-`.".to_string());
+    }
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let spec = format!("{target}^{{commit}}");
+    let sha = run_git_command_output(&repo_root, &["rev-parse", "--verify", "--quiet", &spec])
+        .await
+        .ok()
+        .map(|sha| sha.trim().to_string())
+        .filter(|sha| !sha.is_empty())
+        .ok_or_else(|| format!("`{target}` doesn't name a commit in this repository."))?;
+
+    let staged = diff_name_only(&repo_root, &["--cached", &sha, "--"]).await?;
+    let (restored, unstaged) = match mode.as_str() {
+        "hard" => {
+            let mut restored = diff_name_only(&repo_root, &[&sha, "--"]).await?;
+            restored.extend(staged);
+            restored.sort();
+            restored.dedup();
+            (restored, Vec::new())
+        }
+        "mixed" => (Vec::new(), staged),
+        _ => (Vec::new(), Vec::new()),
+    };
+    let flag = format!("--{mode}");
+    run_git_command(&repo_root, &["reset", &flag, &sha, "--"]).await?;
+    let head = run_git_command_output(&repo_root, &["rev-parse", "--short", "HEAD"]).await?;
+    Ok(GitResetResult {
+        head: head.trim().to_string(),
+        restored,
+        unstaged,
+    })
+}
+
 pub(super) async fn commit_git_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
@@ -475,6 +668,39 @@ pub(super) async fn commit_git_inner(
     run_git_command(&repo_root, &["commit", "-m", &message]).await
 }
 
+/// Commits what is staged and returns the new commit's short hash. An empty
+/// message is only allowed when amending, which keeps the old one. A
+/// rejecting hook's output comes back as the error.
+pub(super) async fn git_commit_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    message: String,
+    amend: bool,
+    signoff: bool,
+) -> Result<String, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let message = message.trim();
+    let mut args = vec!["commit"];
+    if amend {
+        args.push("--amend");
+    }
+    if signoff {
+        args.push("--signoff");
+    }
+    if message.is_empty() {
+        if !amend {
+            return Err("Commit message is required.".to_string());
+        }
+        args.push("--no-edit");
+    } else {
+        args.extend(["-m", message]);
+    }
+    run_git_command(&repo_root, &args).await?;
+    let hash = run_git_command_output(&repo_root, &["rev-parse", "--short", "HEAD"]).await?;
+    Ok(hash.trim().to_string())
+}
+
 pub(super) async fn push_git_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
@@ -495,11 +721,20 @@ pub(super) async fn pull_git_inner(
 
 pub(super) async fn fetch_git_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    storage_path: &Path,
     workspace_id: String,
 ) -> Result<(), String> {
     let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
     let repo_root = resolve_git_root(&entry)?;
-    fetch_with_default_remote(&repo_root).await
+    let started = Instant::now();
+    fetch_with_default_remote(&repo_root).await?;
+    operation_timing_core::record_operation_timing(
+        storage_path,
+        "fetch",
+        operation_timing_core::repo_size_hint_bytes(&repo_root),
+        started.elapsed(),
+    );
+    Ok(())
 }
 
 pub(super) async fn sync_git_inner(
@@ -512,6 +747,64 @@ pub(super) async fn sync_git_inner(
     push_with_upstream(&repo_root).await
 }
 
+pub(super) async fn check_remote_updates_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<GitRemoteUpdates, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let Some((remote, branch)) = upstream_remote_and_branch(&repo_root)? else {
+        return Ok(GitRemoteUpdates {
+            upstream: None,
+            remote_sha: None,
+            ahead: 0,
+            behind: 0,
+            has_updates: false,
+            checked_at: now_unix_ms(),
+            cached: false,
+        });
+    };
+    if let Some(cached) = cached_remote_updates(&repo_root) {
+        return Ok(cached);
+    }
+
+    let head_ref = format!("refs/heads/{branch}");
+    let output = run_git_network_command(
+        &repo_root,
+        &["ls-remote", remote.as_str(), head_ref.as_str()],
+    )
+    .await?;
+    let remote_sha = parse_ls_remote_sha(&output, &branch);
+
+    let (ahead, behind) = match remote_sha.as_deref() {
+        Some(sha) => match ahead_behind_against(&repo_root, sha)? {
+            Some(counts) => counts,
+            None => {
+                // The remote tip is not local yet; fetch just the tracking branch.
+                run_git_network_command(
+                    &repo_root,
+                    &["fetch", "--quiet", remote.as_str(), branch.as_str()],
+                )
+                .await?;
+                ahead_behind_against(&repo_root, sha)?.unwrap_or((0, 0))
+            }
+        },
+        None => (0, 0),
+    };
+
+    let result = GitRemoteUpdates {
+        upstream: Some(format!("{remote}/{branch}")),
+        remote_sha,
+        ahead,
+        behind,
+        has_updates: behind > 0,
+        checked_at: now_unix_ms(),
+        cached: false,
+    };
+    store_remote_updates(&repo_root, &result);
+    Ok(result)
+}
+
 pub(super) async fn list_git_roots_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
@@ -776,7 +1069,7 @@ pub(super) async fn create_git_branch_inner(
 
 #[cfg(test)]
 mod tests {
-    use super::{gh_repo_create_args, validate_branch_name};
+    use super::{gh_repo_create_args, parse_ls_remote_sha, validate_branch_name};
 
     #[test]
     fn validate_branch_name_rejects_repeated_slashes() {
@@ -808,4 +1101,14 @@ mod tests {
             vec!["repo", "create", "owner/repo", "--public"]
         );
     }
+
+    #[test]
+    fn parse_ls_remote_sha_matches_exact_branch_ref() {
+        let output = "aaa111\trefs/heads/main-old\nbbb222\trefs/heads/main\n";
+        assert_eq!(
+            parse_ls_remote_sha(output, "main"),
+            Some("bbb222".to_string())
+        );
+        assert_eq!(parse_ls_remote_sha(output, "missing"), None);
+    }
 }
@@ -5,22 +5,26 @@ use std::path::{Path, PathBuf};
 use std::process::Stdio;
 
 use base64::{engine::general_purpose::STANDARD, Engine as _};
-use git2::{DiffOptions, Repository, Status, StatusOptions};
+use git2::{BranchType, DiffOptions, Oid, Repository, Status, StatusOptions};
 use serde_json::{json, Value};
 use tokio::sync::Mutex;
 
 use crate::git_utils::{
     diff_patch_to_string, diff_stats_for_path, image_mime_type, resolve_git_root,
 };
+use crate::shared::git_core::run_git_command;
 use crate::shared::process_core::std_command;
+use crate::shared::redaction_core;
 use crate::types::{AppSettings, GitCommitDiff, GitFileDiff, GitFileStatus, WorkspaceEntry};
 use crate::utils::{git_env_path, normalize_git_path, resolve_git_binary};
 
 use super::context::workspace_entry_for_id;
+use super::hunks;
 
 const INDEX_SKIP_WORKTREE_FLAG: u16 = 0x4000;
 const MAX_IMAGE_BYTES: usize = 10 * 1024 * 1024;
 const MAX_TEXT_DIFF_BYTES: usize = 2 * 1024 * 1024;
+const DEFAULT_CONTEXT_LINES: usize = 3;
 
 fn encode_image_base64(data: &[u8]) -> Option<String> {
     if data.len() > MAX_IMAGE_BYTES {
@@ -80,6 +84,31 @@ fn read_text_lines(path: &Path) -> Option<Vec<String>> {
     Some(split_lines_preserving_newlines(content.as_ref()))
 }
 
+fn blob_size(blob: Option<&git2::Blob<'_>>) -> Option<u64> {
+    blob.map(|blob| blob.size() as u64)
+}
+
+/// A binary file that isn't an image. Its text diff would only say that the
+/// files differ, so the size of each side comes back instead.
+fn binary_file_diff(path: String, old_size: Option<u64>, new_size: Option<u64>) -> GitFileDiff {
+    GitFileDiff {
+        path,
+        diff: String::new(),
+        old_lines: None,
+        new_lines: None,
+        is_binary: true,
+        is_image: false,
+        old_image_data: None,
+        new_image_data: None,
+        old_image_mime: None,
+        new_image_mime: None,
+        old_size,
+        new_size,
+        redactions: Vec::new(),
+        hunks: Vec::new(),
+    }
+}
+
 fn status_for_index(status: Status) -> Option<&'static str> {
     if status.contains(Status::INDEX_NEW) {
         Some("A")
@@ -331,6 +360,39 @@ pub(super) fn collect_workspace_diff(repo_root: &Path) -> Result<String, String>
     Ok(build_combined_diff(&repo, &diff))
 }
 
+/// The upstream's short name plus the counts `git rev-list --left-right
+/// --count @{upstream}...HEAD` would print, as `(upstream, ahead, behind)`.
+/// All `None` on a detached HEAD or a branch without an upstream.
+pub(super) fn upstream_tracking(
+    repo: &Repository,
+) -> (Option<String>, Option<usize>, Option<usize>) {
+    let Ok(head) = repo.head() else {
+        return (None, None, None);
+    };
+    let Some(upstream) = head
+        .shorthand()
+        .filter(|_| head.is_branch())
+        .and_then(|name| repo.find_branch(name, BranchType::Local).ok())
+        .and_then(|branch| branch.upstream().ok())
+    else {
+        return (None, None, None);
+    };
+    let upstream_ref = upstream.get();
+    let upstream_name = upstream_ref
+        .shorthand()
+        .or_else(|| upstream_ref.name())
+        .map(str::to_string);
+    match (head.target(), upstream_ref.target()) {
+        (Some(head_oid), Some(upstream_oid)) => {
+            match repo.graph_ahead_behind(head_oid, upstream_oid) {
+                Ok((ahead, behind)) => (upstream_name, Some(ahead), Some(behind)),
+                Err(_) => (upstream_name, None, None),
+            }
+        }
+        _ => (upstream_name, None, None),
+    }
+}
+
 pub(super) async fn get_git_status_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
@@ -344,6 +406,8 @@ pub(super) async fn get_git_status_inner(
         .ok()
         .and_then(|head| head.shorthand().map(|s| s.to_string()))
         .unwrap_or_else(|| "unknown".to_string());
+    let detached = repo.head_detached().unwrap_or(false);
+    let (upstream, ahead, behind) = upstream_tracking(&repo);
 
     let mut status_options = StatusOptions::new();
     status_options
@@ -454,6 +518,10 @@ pub(super) async fn get_git_status_inner(
 
     Ok(json!({
         "branchName": branch_name,
+        "detached": detached,
+        "upstream": upstream,
+        "ahead": ahead,
+        "behind": behind,
         "files": files,
         "stagedFiles": staged_files,
         "unstagedFiles": unstaged_files,
@@ -462,10 +530,14 @@ pub(super) async fn get_git_status_inner(
     }))
 }
 
+/// Diffs the working tree and index against `HEAD` with `context_lines`
+/// lines around each change, three by default. Zero gives the smallest
+/// hunks; a count longer than the file shows all of it in one hunk.
 pub(super) async fn get_git_diffs_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     app_settings: &Mutex<AppSettings>,
     workspace_id: String,
+    context_lines: Option<usize>,
 ) -> Result<Vec<GitFileDiff>, String> {
     let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
     let repo_root = resolve_git_root(&entry)?;
@@ -474,6 +546,10 @@ pub(super) async fn get_git_diffs_inner(
         settings.git_diff_ignore_whitespace_changes
     };
 
+    let context_lines = context_lines.unwrap_or(DEFAULT_CONTEXT_LINES);
+
+    let redactor = redaction_core::current_redactor();
+
     tokio::task::spawn_blocking(move || {
         let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
         let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
@@ -482,7 +558,8 @@ pub(super) async fn get_git_diffs_inner(
         options
             .include_untracked(true)
             .recurse_untracked_dirs(true)
-            .show_untracked_content(true);
+            .show_untracked_content(true)
+            .context_lines(u32::try_from(context_lines).unwrap_or(u32::MAX));
         options.ignore_whitespace_change(ignore_whitespace_changes);
 
         let diff = match head_tree.as_ref() {
@@ -521,50 +598,28 @@ pub(super) async fn get_git_diffs_inner(
             let is_deleted = delta.status() == git2::Delta::Deleted;
             let is_added = delta.status() == git2::Delta::Added;
 
-            let old_lines = if !is_added {
-                head_tree
-                    .as_ref()
-                    .and_then(|tree| old_path.and_then(|path| tree.get_path(path).ok()))
-                    .and_then(|entry| repo.find_blob(entry.id()).ok())
-                    .and_then(blob_to_lines)
-            } else {
+            let old_blob = if is_added {
                 None
-            };
-
-            let new_lines = if !is_deleted {
-                match new_path {
-                    Some(path) => {
-                        let full_path = repo_root.join(path);
-                        read_text_lines(&full_path)
-                    }
-                    None => None,
-                }
             } else {
-                None
+                head_tree
+                    .as_ref()
+                    .and_then(|tree| tree_blob(&repo, tree, old_path))
             };
+            let new_full_path = new_path
+                .filter(|_| !is_deleted)
+                .map(|path| repo_root.join(path));
+            let old_size = blob_size(old_blob.as_ref());
+            let new_size = new_full_path
+                .as_ref()
+                .and_then(|path| fs::metadata(path).ok())
+                .map(|metadata| metadata.len());
 
             if is_image {
-                let old_image_data = if !is_added && old_image_mime.is_some() {
-                    head_tree
-                        .as_ref()
-                        .and_then(|tree| old_path.and_then(|path| tree.get_path(path).ok()))
-                        .and_then(|entry| repo.find_blob(entry.id()).ok())
-                        .and_then(blob_to_base64)
-                } else {
-                    None
-                };
-
-                let new_image_data = if !is_deleted && new_image_mime.is_some() {
-                    match new_path {
-                        Some(path) => {
-                            let full_path = repo_root.join(path);
-                            read_image_base64(&full_path)
-                        }
-                        None => None,
-                    }
-                } else {
-                    None
-                };
+                let old_image_data = old_image_mime.and(old_blob).and_then(blob_to_base64);
+
+                let new_image_data = new_image_mime
+                    .and(new_full_path.as_deref())
+                    .and_then(read_image_base64);
 
                 results.push(GitFileDiff {
                     path: normalized_path,
@@ -577,6 +632,10 @@ pub(super) async fn get_git_diffs_inner(
                     new_image_data,
                     old_image_mime: old_image_mime.map(str::to_string),
                     new_image_mime: new_image_mime.map(str::to_string),
+                    old_size,
+                    new_size,
+                    redactions: Vec::new(),
+                    hunks: Vec::new(),
                 });
                 continue;
             }
@@ -588,6 +647,12 @@ pub(super) async fn get_git_diffs_inner(
             let Some(mut patch) = patch else {
                 continue;
             };
+            if patch.delta().flags().is_binary() {
+                results.push(binary_file_diff(normalized_path, old_size, new_size));
+                continue;
+            }
+            let old_lines = old_blob.and_then(blob_to_lines);
+            let new_lines = new_full_path.as_deref().and_then(read_text_lines);
             let content = match diff_patch_to_string(&mut patch) {
                 Ok(content) => content,
                 Err(_) => continue,
@@ -595,6 +660,199 @@ pub(super) async fn get_git_diffs_inner(
             if content.trim().is_empty() {
                 continue;
             }
+            let (content, old_lines, new_lines, redactions) = match redactor.as_deref() {
+                Some(redactor) => {
+                    let (content, redactions) = redactor.redact_diff(&content);
+                    (
+                        content,
+                        old_lines.map(|lines| redactor.redact_lines(lines)),
+                        new_lines.map(|lines| redactor.redact_lines(lines)),
+                        redactions,
+                    )
+                }
+                None => (content, old_lines, new_lines, Vec::new()),
+            };
+            let hunks = hunks::diff_hunks(&normalized_path, &content);
+            results.push(GitFileDiff {
+                path: normalized_path,
+                diff: content,
+                old_lines,
+                new_lines,
+                is_binary: false,
+                is_image: false,
+                old_image_data: None,
+                new_image_data: None,
+                old_image_mime: None,
+                new_image_mime: None,
+                old_size: None,
+                new_size: None,
+                redactions,
+                hunks,
+            });
+        }
+
+        Ok(results)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+fn tree_blob<'repo>(
+    repo: &'repo Repository,
+    tree: &git2::Tree<'_>,
+    path: Option<&Path>,
+) -> Option<git2::Blob<'repo>> {
+    let entry = tree.get_path(path?).ok()?;
+    repo.find_blob(entry.id()).ok()
+}
+
+/// Resolves `rev` with `git rev-parse`, so branch names, tags, `HEAD~3` and
+/// abbreviated hashes all work.
+async fn resolve_commit(repo_root: &PathBuf, rev: &str) -> Result<String, String> {
+    if rev.is_empty() {
+        return Err("Both a base and a head revision are required.".to_string());
+    }
+    if rev.starts_with('-') {
+        return Err("Revision must not start with `-`.".to_string());
+    }
+    let spec = format!("{rev}^{{commit}}");
+    run_git_command(repo_root, &["rev-parse", "--verify", "--quiet", &spec])
+        .await
+        .ok()
+        .filter(|sha| !sha.is_empty())
+        .ok_or_else(|| format!("`{rev}` doesn't name a commit in this repository."))
+}
+
+/// Diffs `head` against where it branched from `base`, as `git diff
+/// base...head` does, or against `base` itself with `two_dot`. Non-empty
+/// `paths` limit the diff to those files and directories.
+pub(super) async fn get_git_diff_range_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    workspace_id: String,
+    base: String,
+    head: String,
+    paths: Vec<String>,
+    two_dot: bool,
+) -> Result<Vec<GitFileDiff>, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let base = base.trim().to_string();
+    let head = head.trim().to_string();
+    let base_sha = resolve_commit(&repo_root, &base).await?;
+    let head_sha = resolve_commit(&repo_root, &head).await?;
+    let paths: Vec<String> = paths
+        .iter()
+        .map(|path| normalize_git_path(path.trim()))
+        .filter(|path| !path.is_empty())
+        .collect();
+    let ignore_whitespace_changes = {
+        let settings = app_settings.lock().await;
+        settings.git_diff_ignore_whitespace_changes
+    };
+
+    let redactor = redaction_core::current_redactor();
+
+    tokio::task::spawn_blocking(move || {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        let head_oid = Oid::from_str(&head_sha).map_err(|e| e.to_string())?;
+        let mut base_oid = Oid::from_str(&base_sha).map_err(|e| e.to_string())?;
+        if !two_dot {
+            base_oid = repo
+                .merge_base(base_oid, head_oid)
+                .map_err(|_| format!("`{base}` and `{head}` have no common ancestor."))?;
+        }
+        let old_tree = repo
+            .find_commit(base_oid)
+            .and_then(|commit| commit.tree())
+            .map_err(|e| e.to_string())?;
+        let new_tree = repo
+            .find_commit(head_oid)
+            .and_then(|commit| commit.tree())
+            .map_err(|e| e.to_string())?;
+
+        let mut options = DiffOptions::new();
+        options.ignore_whitespace_change(ignore_whitespace_changes);
+        for path in &paths {
+            options.pathspec(path);
+        }
+        let diff = repo
+            .diff_tree_to_tree(Some(&old_tree), Some(&new_tree), Some(&mut options))
+            .map_err(|e| e.to_string())?;
+
+        let mut results = Vec::new();
+        for (index, delta) in diff.deltas().enumerate() {
+            let old_path = delta.old_file().path();
+            let new_path = delta.new_file().path();
+            let Some(display_path) = new_path.or(old_path) else {
+                continue;
+            };
+            let normalized_path = normalize_git_path(&display_path.to_string_lossy());
+            let old_image_mime = old_path.and_then(|path| image_mime_type(&path.to_string_lossy()));
+            let new_image_mime = new_path.and_then(|path| image_mime_type(&path.to_string_lossy()));
+            let is_deleted = delta.status() == git2::Delta::Deleted;
+            let is_added = delta.status() == git2::Delta::Added;
+            let old_blob = if is_added {
+                None
+            } else {
+                tree_blob(&repo, &old_tree, old_path)
+            };
+            let new_blob = if is_deleted {
+                None
+            } else {
+                tree_blob(&repo, &new_tree, new_path)
+            };
+
+            let old_size = blob_size(old_blob.as_ref());
+            let new_size = blob_size(new_blob.as_ref());
+            if old_image_mime.is_some() || new_image_mime.is_some() {
+                results.push(GitFileDiff {
+                    path: normalized_path,
+                    diff: String::new(),
+                    old_lines: None,
+                    new_lines: None,
+                    is_binary: true,
+                    is_image: true,
+                    old_image_data: old_image_mime.and(old_blob).and_then(blob_to_base64),
+                    new_image_data: new_image_mime.and(new_blob).and_then(blob_to_base64),
+                    old_image_mime: old_image_mime.map(str::to_string),
+                    new_image_mime: new_image_mime.map(str::to_string),
+                    old_size,
+                    new_size,
+                    redactions: Vec::new(),
+                    hunks: Vec::new(),
+                });
+                continue;
+            }
+
+            let Ok(Some(mut patch)) = git2::Patch::from_diff(&diff, index) else {
+                continue;
+            };
+            if patch.delta().flags().is_binary() {
+                results.push(binary_file_diff(normalized_path, old_size, new_size));
+                continue;
+            }
+            let Ok(content) = diff_patch_to_string(&mut patch) else {
+                continue;
+            };
+            if content.trim().is_empty() {
+                continue;
+            }
+            let old_lines = old_blob.and_then(blob_to_lines);
+            let new_lines = new_blob.and_then(blob_to_lines);
+            let (content, old_lines, new_lines, redactions) = match redactor.as_deref() {
+                Some(redactor) => {
+                    let (content, redactions) = redactor.redact_diff(&content);
+                    (
+                        content,
+                        old_lines.map(|lines| redactor.redact_lines(lines)),
+                        new_lines.map(|lines| redactor.redact_lines(lines)),
+                        redactions,
+                    )
+                }
+                None => (content, old_lines, new_lines, Vec::new()),
+            };
+            let hunks = hunks::diff_hunks(&normalized_path, &content);
             results.push(GitFileDiff {
                 path: normalized_path,
                 diff: content,
@@ -606,6 +864,10 @@ pub(super) async fn get_git_diffs_inner(
                 new_image_data: None,
                 old_image_mime: None,
                 new_image_mime: None,
+                old_size: None,
+                new_size: None,
+                redactions,
+                hunks,
             });
         }
 
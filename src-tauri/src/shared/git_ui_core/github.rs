@@ -1,21 +1,122 @@
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
 
 use git2::Repository;
+use serde_json::{json, Value};
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
 use tokio::sync::Mutex;
 
-use crate::git_utils::{parse_github_repo, resolve_git_root};
+use crate::git_utils::{parse_git_remote_host, resolve_git_root};
+use crate::shared::credentials_core::read_credential;
 use crate::shared::process_core::tokio_command;
 use crate::types::{
-    GitHubIssue, GitHubIssuesResponse, GitHubPullRequest, GitHubPullRequestComment,
-    GitHubPullRequestDiff, GitHubPullRequestsResponse, WorkspaceEntry,
+    GitHubCheck, GitHubChecksResponse, GitHubChecksState, GitHubCreatedPullRequest, GitHubIssue,
+    GitHubIssuesResponse, GitHubMergeResult, GitHubPullRequest, GitHubPullRequestComment,
+    GitHubPullRequestDiff, GitHubPullRequestInput, GitHubPullRequestsResponse, GitHubReviewComment,
+    WorkspaceEntry,
 };
 use crate::utils::normalize_git_path;
 
-use super::context::workspace_entry_for_id;
+const GITHUB_COM: &str = "github.com";
 
-fn github_repo_from_path(path: &Path) -> Result<String, String> {
-    let repo = Repository::open(path).map_err(|e| e.to_string())?;
+/// Where a workspace's pull requests and issues live.
+struct GitHubTarget {
+    host: String,
+    /// `owner/repo`.
+    repo: String,
+    /// Stored under `github:<host>`; `gh auth`'s login is used without one.
+    token: Option<String>,
+}
+
+impl GitHubTarget {
+    /// The `--repo` value. gh reads a host prefix for anything but github.com.
+    fn repo_arg(&self) -> String {
+        if self.host == GITHUB_COM {
+            self.repo.clone()
+        } else {
+            format!("{}/{}", self.host, self.repo)
+        }
+    }
+
+    /// `gh` aimed at this host. `GH_HOST` makes `gh api` call
+    /// `https://<host>/api/v3` for an Enterprise host, and the stored token
+    /// goes in the variable gh reads for that kind of host.
+    fn gh(&self, repo_root: &Path) -> Command {
+        let mut command = tokio_command("gh");
+        command.current_dir(repo_root).env("GH_HOST", &self.host);
+        if let Some(token) = self.token.as_deref() {
+            let variable = if self.host == GITHUB_COM {
+                "GH_TOKEN"
+            } else {
+                "GH_ENTERPRISE_TOKEN"
+            };
+            command.env(variable, token);
+        }
+        command
+    }
+}
+
+/// `ghe.company.com` from a host or URL as typed in settings.
+pub(super) fn normalize_github_host(value: Option<&str>) -> Option<String> {
+    let value = value?.trim();
+    let value = value
+        .strip_prefix("https://")
+        .or_else(|| value.strip_prefix("http://"))
+        .unwrap_or(value);
+    let host = value.split('/').next().unwrap_or("").to_ascii_lowercase();
+    (!host.is_empty()).then_some(host)
+}
+
+/// The workspace's `githubHost` wins, which also covers ssh host aliases.
+/// Without one the remote's host is used when it's github.com or a token is
+/// stored for it; anything else is most likely not GitHub at all.
+pub(super) fn resolve_github_host(
+    configured: Option<String>,
+    remote_host: &str,
+    remote_has_token: bool,
+) -> Result<String, String> {
+    if let Some(host) = configured {
+        return Ok(host);
+    }
+    if remote_host == GITHUB_COM || remote_has_token {
+        return Ok(remote_host.to_string());
+    }
+    Err(format!(
+        "The remote is on {remote_host}, not github.com. For GitHub Enterprise, set this workspace's GitHub host or store a token under `github:{remote_host}`."
+    ))
+}
+
+fn github_token(host: &str) -> Option<String> {
+    read_credential(&format!("github:{host}")).ok().flatten()
+}
+
+/// The workspace's repo root and GitHub target. Worktrees use their
+/// parent's `githubHost` when they set none.
+async fn github_target(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: &str,
+) -> Result<(PathBuf, GitHubTarget), String> {
+    let (entry, configured) = {
+        let workspaces = workspaces.lock().await;
+        let entry = workspaces
+            .get(workspace_id)
+            .cloned()
+            .ok_or_else(|| "workspace not found".to_string())?;
+        let inherited = entry
+            .kind
+            .is_worktree()
+            .then(|| entry.parent_id.as_ref().and_then(|id| workspaces.get(id)))
+            .flatten()
+            .and_then(|parent| parent.settings.github_host.clone());
+        let configured = [entry.settings.github_host.clone(), inherited]
+            .iter()
+            .find_map(|value| normalize_github_host(value.as_deref()));
+        (entry, configured)
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
     let remotes = repo.remotes().map_err(|e| e.to_string())?;
     let name = if remotes.iter().any(|remote| remote == Some("origin")) {
         "origin".to_string()
@@ -27,7 +128,23 @@ fn github_repo_from_path(path: &Path) -> Result<String, String> {
     }
     let remote = repo.find_remote(&name).map_err(|e| e.to_string())?;
     let remote_url = remote.url().ok_or("Remote has no URL configured.")?;
-    parse_github_repo(remote_url).ok_or("Remote is not a GitHub repository.".to_string())
+    let (remote_host, repo_name) = parse_git_remote_host(remote_url)
+        .ok_or_else(|| "Remote is not a GitHub repository.".to_string())?;
+    let remote_token = github_token(&remote_host);
+    let host = resolve_github_host(configured, &remote_host, remote_token.is_some())?;
+    let token = if host == remote_host {
+        remote_token
+    } else {
+        github_token(&host)
+    };
+    Ok((
+        repo_root,
+        GitHubTarget {
+            host,
+            repo: repo_name,
+            token,
+        },
+    ))
 }
 
 fn parse_pr_diff(diff: &str) -> Vec<GitHubPullRequestDiff> {
@@ -140,18 +257,408 @@ fn command_failure_detail(stdout: &[u8], stderr: &[u8], fallback: &str) -> Strin
     }
 }
 
+/// GitHub's explanation from an API error body, with the detail of each
+/// validation error, like `Validation Failed: No commits between main and
+/// feature`. Falls back to what `gh` printed.
+pub(super) fn github_api_error(stdout: &[u8], stderr: &[u8]) -> String {
+    let response: Option<Value> = serde_json::from_slice(stdout).ok();
+    let Some(message) = response
+        .as_ref()
+        .and_then(|response| response.get("message"))
+        .and_then(Value::as_str)
+    else {
+        return command_failure_detail(stdout, stderr, "GitHub CLI command failed.");
+    };
+    let details: Vec<&str> = response
+        .as_ref()
+        .and_then(|response| response.get("errors"))
+        .and_then(Value::as_array)
+        .map(|errors| {
+            errors
+                .iter()
+                .filter_map(|error| {
+                    error
+                        .as_str()
+                        .or_else(|| error.get("message").and_then(Value::as_str))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    if details.is_empty() {
+        message.to_string()
+    } else {
+        format!("{message}: {}", details.join("; "))
+    }
+}
+
+/// Calls the target's REST API through `gh api`, which signs the request
+/// with the stored token or the one from `gh auth`. `body` is sent as JSON
+/// on stdin. A write GitHub refuses with 403 says so, since the usual cause
+/// is a read-only token.
+async fn gh_api(
+    target: &GitHubTarget,
+    repo_root: &Path,
+    method: &str,
+    endpoint: &str,
+    body: Option<&Value>,
+) -> Result<Value, String> {
+    let mut command = target.gh(repo_root);
+    command
+        .args(["api", "--method", method, endpoint])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    if body.is_some() {
+        command.args(["--input", "-"]).stdin(Stdio::piped());
+    }
+    let mut child = command
+        .spawn()
+        .map_err(|e| format!("Failed to run gh: {e}"))?;
+    if let Some(body) = body {
+        let mut stdin = child.stdin.take().ok_or("missing stdin")?;
+        stdin
+            .write_all(body.to_string().as_bytes())
+            .await
+            .map_err(|e| format!("Failed to write to gh: {e}"))?;
+        drop(stdin);
+    }
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|e| format!("Failed to run gh: {e}"))?;
+    if !output.status.success() {
+        let error = github_api_error(&output.stdout, &output.stderr);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if method != "GET"
+            && (stderr.contains("(HTTP 403)") || error.contains("Resource not accessible"))
+        {
+            let hostname = if target.host == GITHUB_COM {
+                String::new()
+            } else {
+                format!(" -h {}", target.host)
+            };
+            return Err(format!(
+                "{error}. Your GitHub token can't write to this repository; run `gh auth refresh{hostname} -s repo` or ask for write access."
+            ));
+        }
+        return Err(error);
+    }
+    if String::from_utf8_lossy(&output.stdout).trim().is_empty() {
+        return Ok(Value::Null);
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())
+}
+
+fn current_branch_name(repo_root: &Path) -> Result<String, String> {
+    let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
+    let head = repo.head().map_err(|e| e.to_string())?;
+    if !head.is_branch() {
+        return Err("HEAD is detached; choose the branch to open a pull request from.".to_string());
+    }
+    head.shorthand()
+        .map(str::to_string)
+        .ok_or_else(|| "Current branch name is not valid UTF-8.".to_string())
+}
+
+fn trimmed(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Opens a pull request from `head`, the current branch unless given, into
+/// `base`, the repository's default branch unless given. The branch has to
+/// be pushed already; GitHub's own error comes back when it isn't.
+pub(super) async fn create_github_pull_request_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    input: GitHubPullRequestInput,
+) -> Result<GitHubCreatedPullRequest, String> {
+    let title = input.title.trim().to_string();
+    if title.is_empty() {
+        return Err("A pull request needs a title.".to_string());
+    }
+    let (repo_root, target) = github_target(workspaces, &workspace_id).await?;
+    let repo_name = &target.repo;
+
+    let head = match trimmed(input.head) {
+        Some(head) => head,
+        None => current_branch_name(&repo_root)?,
+    };
+    let base = match trimmed(input.base) {
+        Some(base) => base,
+        None => gh_api(
+            &target,
+            &repo_root,
+            "GET",
+            &format!("/repos/{repo_name}"),
+            None,
+        )
+        .await?
+        .get("default_branch")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| "Couldn't find the repository's default branch.".to_string())?,
+    };
+    let payload = json!({
+        "title": title,
+        "head": head,
+        "base": base,
+        "body": input.body.unwrap_or_default(),
+        "draft": input.draft,
+    });
+    let created = gh_api(
+        &target,
+        &repo_root,
+        "POST",
+        &format!("/repos/{repo_name}/pulls"),
+        Some(&payload),
+    )
+    .await?;
+    let number = created
+        .get("number")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| "GitHub didn't return the new pull request's number.".to_string())?;
+    let url = created
+        .get("html_url")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    Ok(GitHubCreatedPullRequest { number, url })
+}
+
+const REVIEW_EVENTS: &[&str] = &["APPROVE", "REQUEST_CHANGES", "COMMENT"];
+
+/// Submits a review of `pr_number` with its inline `comments`, each on a
+/// line of the PR's head version of the file. Returns the review's id.
+pub(super) async fn submit_github_pull_request_review_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    pr_number: u64,
+    event: String,
+    body: Option<String>,
+    comments: Vec<GitHubReviewComment>,
+) -> Result<u64, String> {
+    let event = event.trim().to_ascii_uppercase();
+    if !REVIEW_EVENTS.contains(&event.as_str()) {
+        return Err(format!(
+            "Unknown review event `{event}`; use APPROVE, REQUEST_CHANGES or COMMENT."
+        ));
+    }
+    let (repo_root, target) = github_target(workspaces, &workspace_id).await?;
+    let repo_name = &target.repo;
+
+    let comments: Vec<Value> = comments
+        .into_iter()
+        .map(|comment| {
+            json!({
+                "path": normalize_git_path(&comment.path),
+                "line": comment.line,
+                "body": comment.body,
+            })
+        })
+        .collect();
+    let payload = json!({
+        "event": event,
+        "body": body.unwrap_or_default(),
+        "comments": comments,
+    });
+    let review = gh_api(
+        &target,
+        &repo_root,
+        "POST",
+        &format!("/repos/{repo_name}/pulls/{pr_number}/reviews"),
+        Some(&payload),
+    )
+    .await?;
+    review
+        .get("id")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| "GitHub didn't return the review's id.".to_string())
+}
+
+/// Check runs from `/commits/{sha}/check-runs`.
+pub(super) fn parse_check_runs(response: &Value) -> Vec<GitHubCheck> {
+    let Some(runs) = response.get("check_runs").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    runs.iter()
+        .map(|run| {
+            let text = |key: &str| run.get(key).and_then(Value::as_str).map(str::to_string);
+            GitHubCheck {
+                name: text("name").unwrap_or_default(),
+                status: text("status").unwrap_or_else(|| "queued".to_string()),
+                conclusion: text("conclusion"),
+                details_url: text("details_url").or_else(|| text("html_url")),
+            }
+        })
+        .collect()
+}
+
+/// Commit statuses from `/commits/{sha}/status`, whose single `state`
+/// becomes a check's status and conclusion.
+pub(super) fn parse_commit_statuses(response: &Value) -> Vec<GitHubCheck> {
+    let Some(statuses) = response.get("statuses").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    statuses
+        .iter()
+        .map(|status| {
+            let text = |key: &str| status.get(key).and_then(Value::as_str).map(str::to_string);
+            let state = text("state").unwrap_or_else(|| "pending".to_string());
+            let pending = state == "pending";
+            GitHubCheck {
+                name: text("context").unwrap_or_default(),
+                status: if pending { "in_progress" } else { "completed" }.to_string(),
+                conclusion: (!pending).then_some(state),
+                details_url: text("target_url"),
+            }
+        })
+        .collect()
+}
+
+const FAILED_CONCLUSIONS: &[&str] = &[
+    "failure",
+    "error",
+    "timed_out",
+    "cancelled",
+    "action_required",
+    "startup_failure",
+];
+
+/// Failing if any check failed, pending while any is still running, and
+/// successful otherwise, including when there are no checks.
+pub(super) fn checks_state(checks: &[GitHubCheck]) -> GitHubChecksState {
+    let failed = checks.iter().any(|check| {
+        check
+            .conclusion
+            .as_deref()
+            .is_some_and(|conclusion| FAILED_CONCLUSIONS.contains(&conclusion))
+    });
+    if failed {
+        GitHubChecksState::Failure
+    } else if checks.iter().any(|check| check.status != "completed") {
+        GitHubChecksState::Pending
+    } else {
+        GitHubChecksState::Success
+    }
+}
+
+/// The check runs and commit statuses on the PR's head commit.
+pub(super) async fn get_github_pull_request_checks_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    pr_number: u64,
+) -> Result<GitHubChecksResponse, String> {
+    let (repo_root, target) = github_target(workspaces, &workspace_id).await?;
+    let repo_name = &target.repo;
+
+    let pull_request = gh_api(
+        &target,
+        &repo_root,
+        "GET",
+        &format!("/repos/{repo_name}/pulls/{pr_number}"),
+        None,
+    )
+    .await?;
+    let head_sha = pull_request
+        .get("head")
+        .and_then(|head| head.get("sha"))
+        .and_then(Value::as_str)
+        .ok_or_else(|| "GitHub didn't return the pull request's head commit.".to_string())?
+        .to_string();
+    let check_runs = gh_api(
+        &target,
+        &repo_root,
+        "GET",
+        &format!("/repos/{repo_name}/commits/{head_sha}/check-runs?per_page=100"),
+        None,
+    )
+    .await?;
+    let statuses = gh_api(
+        &target,
+        &repo_root,
+        "GET",
+        &format!("/repos/{repo_name}/commits/{head_sha}/status"),
+        None,
+    )
+    .await?;
+
+    let mut checks = parse_check_runs(&check_runs);
+    checks.extend(parse_commit_statuses(&statuses));
+    checks.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    Ok(GitHubChecksResponse {
+        state: checks_state(&checks),
+        head_sha,
+        checks,
+    })
+}
+
+const MERGE_METHODS: &[&str] = &["merge", "squash", "rebase"];
+
+/// Merges `pr_number` with `method`. The title and message set the merge
+/// or squash commit; GitHub ignores them for a rebase. When GitHub refuses,
+/// its reason, such as a failing required check, comes back as the error.
+pub(super) async fn merge_github_pull_request_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    pr_number: u64,
+    method: String,
+    commit_title: Option<String>,
+    commit_message: Option<String>,
+) -> Result<GitHubMergeResult, String> {
+    let method = method.trim().to_ascii_lowercase();
+    if !MERGE_METHODS.contains(&method.as_str()) {
+        return Err(format!(
+            "Unknown merge method `{method}`; use merge, squash or rebase."
+        ));
+    }
+    let (repo_root, target) = github_target(workspaces, &workspace_id).await?;
+    let repo_name = &target.repo;
+
+    let mut payload = json!({ "merge_method": method });
+    if let Some(title) = trimmed(commit_title) {
+        payload["commit_title"] = json!(title);
+    }
+    if let Some(message) = commit_message.filter(|message| !message.trim().is_empty()) {
+        payload["commit_message"] = json!(message);
+    }
+    let response = gh_api(
+        &target,
+        &repo_root,
+        "PUT",
+        &format!("/repos/{repo_name}/pulls/{pr_number}/merge"),
+        Some(&payload),
+    )
+    .await
+    .map_err(|error| format!("GitHub didn't merge #{pr_number}: {error}"))?;
+    let text = |key: &str| {
+        response
+            .get(key)
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    };
+    Ok(GitHubMergeResult {
+        merged: response
+            .get("merged")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+        sha: text("sha"),
+        message: text("message").unwrap_or_default(),
+    })
+}
+
 pub(super) async fn checkout_github_pull_request_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
     pr_number: u64,
 ) -> Result<(), String> {
-    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
-    let repo_root = resolve_git_root(&entry)?;
+    let (repo_root, target) = github_target(workspaces, &workspace_id).await?;
     let pr_number_text = pr_number.to_string();
 
-    let output = tokio_command("gh")
+    let output = target
+        .gh(&repo_root)
         .args(["pr", "checkout", &pr_number_text])
-        .current_dir(&repo_root)
         .output()
         .await
         .map_err(|e| format!("Failed to run gh: {e}"))?;
@@ -171,22 +678,21 @@ pub(super) async fn get_github_issues_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
 ) -> Result<GitHubIssuesResponse, String> {
-    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
-    let repo_root = resolve_git_root(&entry)?;
-    let repo_name = github_repo_from_path(&repo_root)?;
+    let (repo_root, target) = github_target(workspaces, &workspace_id).await?;
+    let repo_name = &target.repo;
 
-    let output = tokio_command("gh")
+    let output = target
+        .gh(&repo_root)
         .args([
             "issue",
             "list",
             "--repo",
-            &repo_name,
+            &target.repo_arg(),
             "--limit",
             "50",
             "--json",
             "number,title,url,updatedAt",
         ])
-        .current_dir(&repo_root)
         .output()
         .await
         .map_err(|e| format!("Failed to run gh: {e}"))?;
@@ -203,14 +709,14 @@ pub(super) async fn get_github_issues_inner(
         serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())?;
 
     let search_query = format!("repo:{repo_name} is:issue is:open").replace(' ', "+");
-    let total = match tokio_command("gh")
+    let total = match target
+        .gh(&repo_root)
         .args([
             "api",
             &format!("/search/issues?q={search_query}"),
             "--jq",
             ".total_count",
         ])
-        .current_dir(&repo_root)
         .output()
         .await
     {
@@ -228,16 +734,16 @@ pub(super) async fn get_github_pull_requests_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
 ) -> Result<GitHubPullRequestsResponse, String> {
-    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
-    let repo_root = resolve_git_root(&entry)?;
-    let repo_name = github_repo_from_path(&repo_root)?;
+    let (repo_root, target) = github_target(workspaces, &workspace_id).await?;
+    let repo_name = &target.repo;
 
-    let output = tokio_command("gh")
+    let output = target
+        .gh(&repo_root)
         .args([
             "pr",
             "list",
             "--repo",
-            &repo_name,
+            &target.repo_arg(),
             "--state",
             "open",
             "--limit",
@@ -245,7 +751,6 @@ pub(super) async fn get_github_pull_requests_inner(
             "--json",
             "number,title,url,updatedAt,createdAt,body,headRefName,baseRefName,isDraft,author",
         ])
-        .current_dir(&repo_root)
         .output()
         .await
         .map_err(|e| format!("Failed to run gh: {e}"))?;
@@ -262,14 +767,14 @@ pub(super) async fn get_github_pull_requests_inner(
         serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())?;
 
     let search_query = format!("repo:{repo_name} is:pr is:open").replace(' ', "+");
-    let total = match tokio_command("gh")
+    let total = match target
+        .gh(&repo_root)
         .args([
             "api",
             &format!("/search/issues?q={search_query}"),
             "--jq",
             ".total_count",
         ])
-        .current_dir(&repo_root)
         .output()
         .await
     {
@@ -291,21 +796,20 @@ pub(super) async fn get_github_pull_request_diff_inner(
     workspace_id: String,
     pr_number: u64,
 ) -> Result<Vec<GitHubPullRequestDiff>, String> {
-    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
-    let repo_root = resolve_git_root(&entry)?;
-    let repo_name = github_repo_from_path(&repo_root)?;
+    let (repo_root, target) = github_target(workspaces, &workspace_id).await?;
+    let repo_name = &target.repo;
 
-    let output = tokio_command("gh")
+    let output = target
+        .gh(&repo_root)
         .args([
             "pr",
             "diff",
             &pr_number.to_string(),
             "--repo",
-            &repo_name,
+            &target.repo_arg(),
             "--color",
             "never",
         ])
-        .current_dir(&repo_root)
         .output()
         .await
         .map_err(|e| format!("Failed to run gh: {e}"))?;
@@ -327,16 +831,15 @@ pub(super) async fn get_github_pull_request_comments_inner(
     workspace_id: String,
     pr_number: u64,
 ) -> Result<Vec<GitHubPullRequestComment>, String> {
-    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
-    let repo_root = resolve_git_root(&entry)?;
-    let repo_name = github_repo_from_path(&repo_root)?;
+    let (repo_root, target) = github_target(workspaces, &workspace_id).await?;
+    let repo_name = &target.repo;
 
     let comments_endpoint = format!("/repos/{repo_name}/issues/{pr_number}/comments?per_page=30");
     let jq_filter = r#"[.[] | {id, body, createdAt: .created_at, url: .html_url, author: (if .user then {login: .user.login} else null end)}]"#;
 
-    let output = tokio_command("gh")
+    let output = target
+        .gh(&repo_root)
         .args(["api", &comments_endpoint, "--jq", jq_filter])
-        .current_dir(&repo_root)
         .output()
         .await
         .map_err(|e| format!("Failed to run gh: {e}"))?;
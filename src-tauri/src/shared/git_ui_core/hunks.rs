@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use git2::{ObjectType, Oid};
+use tokio::sync::Mutex;
+
+use crate::git_utils::resolve_git_root;
+use crate::shared::git_core::{run_git_command, run_git_command_with_input};
+use crate::shared::redaction_core::parse_hunk_header;
+use crate::types::{GitDiffHunk, WorkspaceEntry};
+use crate::utils::normalize_git_path;
+
+use super::context::workspace_entry_for_id;
+
+const REDACTION_PLACEHOLDER: &str = "[REDACTED:";
+
+/// Hashes the path and the hunk's lines but not its header, so a hunk keeps
+/// its id when an edit above it shifts the line numbers.
+fn hunk_id(path: &str, body: &[&str]) -> String {
+    let mut content = String::from(path);
+    for line in body {
+        content.push('\n');
+        content.push_str(line);
+    }
+    let id = Oid::hash_object(ObjectType::Blob, content.as_bytes())
+        .map(|oid| oid.to_string())
+        .unwrap_or_default();
+    id.chars().take(12).collect()
+}
+
+/// The hunks of one file's unified diff, in order.
+pub(super) fn diff_hunks(path: &str, diff: &str) -> Vec<GitDiffHunk> {
+    let mut hunks = Vec::new();
+    let mut current: Option<(&str, Vec<&str>)> = None;
+    let mut finish = |current: Option<(&str, Vec<&str>)>| {
+        let Some((header, body)) = current else {
+            return;
+        };
+        let Some(((old_start, old_lines), (new_start, new_lines))) = parse_hunk_header(header)
+        else {
+            return;
+        };
+        hunks.push(GitDiffHunk {
+            id: hunk_id(path, &body),
+            header: header.to_string(),
+            old_start,
+            old_lines,
+            new_start,
+            new_lines,
+        });
+    };
+    for line in diff.lines() {
+        if line.starts_with("@@ ") {
+            finish(current.take());
+            current = Some((line, Vec::new()));
+        } else if let Some((_, body)) = current.as_mut() {
+            body.push(line);
+        }
+    }
+    finish(current);
+    hunks
+}
+
+/// Wraps one hunk in the file headers `git apply` needs. `patch` is the
+/// hunk's lines without its header.
+pub(super) fn build_hunk_patch(
+    path: &str,
+    hunk_header: &str,
+    patch: &str,
+) -> Result<String, String> {
+    let header = hunk_header.trim_end();
+    if parse_hunk_header(header).is_none() {
+        return Err(format!("`{header}` is not a hunk header."));
+    }
+    let body = patch.strip_suffix('\n').unwrap_or(patch);
+    if body.is_empty() {
+        return Err("The hunk is empty.".to_string());
+    }
+    if let Some(line) = body
+        .lines()
+        .find(|line| !matches!(line.chars().next(), Some(' ' | '+' | '-' | '\\')))
+    {
+        return Err(format!("`{line}` is not a line of a hunk."));
+    }
+    if body.contains(REDACTION_PLACEHOLDER) {
+        return Err(
+            "This hunk contains a redacted secret; stage the whole file instead.".to_string(),
+        );
+    }
+    Ok(format!(
+        "diff --git a/{path} b/{path}\n--- a/{path}\n+++ b/{path}\n{header}\n{body}\n"
+    ))
+}
+
+/// `git apply` with `flags`, reading the patch from stdin. A hunk from a
+/// diff without context lines also needs `--unidiff-zero`, or git refuses it.
+fn apply_args<'a>(flags: &[&'a str], patch: &str) -> Vec<&'a str> {
+    let mut args = vec!["apply"];
+    args.extend_from_slice(flags);
+    if !patch.lines().any(|line| line.starts_with(' ')) {
+        args.push("--unidiff-zero");
+    }
+    args.push("-");
+    args
+}
+
+/// Applies the hunk to the index after checking it's still in the working
+/// tree, so a file edited since the diff was loaded isn't staged blind.
+pub(super) async fn stage_git_hunk_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+    hunk_header: String,
+    patch: String,
+) -> Result<(), String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let path = normalize_git_path(&path);
+    let patch = build_hunk_patch(&path, &hunk_header, &patch)?;
+    if run_git_command(&repo_root, &["ls-files", "--error-unmatch", "--", &path])
+        .await
+        .is_err()
+    {
+        return Err(format!(
+            "{path} is not tracked yet; stage the whole file first."
+        ));
+    }
+    let check = apply_args(&["--check", "--reverse", "--recount"], &patch);
+    run_git_command_with_input(&repo_root, &check, &patch)
+        .await
+        .map_err(|_| format!("{path} changed since the diff was loaded. Refresh and try again."))?;
+    let stage = apply_args(&["--cached", "--recount"], &patch);
+    run_git_command_with_input(&repo_root, &stage, &patch)
+        .await
+        .map_err(|error| {
+            format!("The hunk doesn't apply to the staged version of {path}: {error}")
+        })?;
+    Ok(())
+}
+
+pub(super) async fn unstage_git_hunk_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+    hunk_header: String,
+    patch: String,
+) -> Result<(), String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let path = normalize_git_path(&path);
+    let patch = build_hunk_patch(&path, &hunk_header, &patch)?;
+    let unstage = apply_args(&["--cached", "--reverse", "--recount"], &patch);
+    run_git_command_with_input(&repo_root, &unstage, &patch)
+        .await
+        .map_err(|_| format!("The hunk is no longer staged in {path}. Refresh and try again."))?;
+    Ok(())
+}
@@ -1,42 +1,129 @@
 use std::collections::HashMap;
 
-use git2::{BranchType, Repository, Sort};
+use git2::{BranchType, Oid, Repository, Sort};
 use tokio::sync::Mutex;
 
 use crate::git_utils::{commit_to_entry, resolve_git_root};
-use crate::types::{GitLogResponse, WorkspaceEntry};
+use crate::types::{
+    GitFileHistoryEntry, GitGraphNode, GitGraphResponse, GitLogEntry, GitLogFilter, GitLogResponse,
+    GitRefLabel, GitRemote, WorkspaceEntry,
+};
+use crate::utils::normalize_git_path;
+
+use super::commands::run_git_command_output;
+
+const DEFAULT_LOG_COUNT: usize = 100;
+const MAX_LOG_COUNT: usize = 1000;
+const DEFAULT_GRAPH_COUNT: usize = 200;
+const MAX_GRAPH_COUNT: usize = 2000;
+const DEFAULT_FILE_HISTORY_COUNT: usize = 100;
+const MAX_FILE_HISTORY_COUNT: usize = 1000;
+const LOG_FORMAT: &str = "--format=%x1e%H%x1f%an%x1f%at%x1f%s";
+const RECORD_SEPARATOR: char = '\u{1e}';
+const FIELD_SEPARATOR: char = '\u{1f}';
 
 use super::context::workspace_entry_for_id;
 
+/// The `sha`, author, timestamp and summary written by `LOG_FORMAT`.
+fn parse_log_header(header: &str) -> Option<GitLogEntry> {
+    let mut fields = header.splitn(4, FIELD_SEPARATOR);
+    let sha = fields.next()?.trim().to_string();
+    if sha.is_empty() {
+        return None;
+    }
+    let author = fields.next().unwrap_or("").to_string();
+    let timestamp = fields
+        .next()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(0);
+    let summary = fields.next().unwrap_or("").to_string();
+    Some(GitLogEntry {
+        sha,
+        summary,
+        author,
+        timestamp,
+    })
+}
+
+pub(super) fn parse_log_entries(output: &str) -> Vec<GitLogEntry> {
+    output
+        .split(RECORD_SEPARATOR)
+        .filter_map(|record| parse_log_header(record.lines().next()?))
+        .collect()
+}
+
+/// `--author`, `--since` and `--until` for the filters that are set. The
+/// author is matched literally, so an email's `.` or `+` needs no escaping.
+pub(super) fn log_filter_args(filter: &GitLogFilter) -> Vec<String> {
+    let value = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    let mut args = Vec::new();
+    if let Some(author) = value(&filter.author) {
+        args.push("--fixed-strings".to_string());
+        args.push(format!("--author={author}"));
+    }
+    if let Some(since) = value(&filter.since) {
+        args.push(format!("--since={since}"));
+    }
+    if let Some(until) = value(&filter.until) {
+        args.push(format!("--until={until}"));
+    }
+    args
+}
+
+/// One page of `git log`: `limit` commits (100 by default) after the first
+/// `skip`, optionally only those touching `path` and matching `filter`.
+/// `total` counts every matching commit, and `hasMore` is set when another
+/// page follows. `aheadEntries` and `behindEntries` are never paged or
+/// filtered.
 pub(super) async fn get_git_log_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
+    skip: Option<usize>,
     limit: Option<usize>,
+    path: Option<String>,
+    filter: GitLogFilter,
 ) -> Result<GitLogResponse, String> {
     let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
     let repo_root = resolve_git_root(&entry)?;
-    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
-    let max_items = limit.unwrap_or(40);
-    let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
-    revwalk.push_head().map_err(|e| e.to_string())?;
-    revwalk.set_sorting(Sort::TIME).map_err(|e| e.to_string())?;
+    let max_items = limit.unwrap_or(DEFAULT_LOG_COUNT).clamp(1, MAX_LOG_COUNT);
+    let path = path
+        .map(|path| normalize_git_path(path.trim()))
+        .filter(|path| !path.is_empty());
 
-    let mut total = 0usize;
-    for oid_result in revwalk {
-        oid_result.map_err(|e| e.to_string())?;
-        total += 1;
+    let skip_arg = format!("--skip={}", skip.unwrap_or(0));
+    // One extra commit tells whether there is another page.
+    let count_arg = format!("--max-count={}", max_items + 1);
+    let mut log_args = vec![
+        "log",
+        LOG_FORMAT,
+        skip_arg.as_str(),
+        count_arg.as_str(),
+        "HEAD",
+    ];
+    let mut count_args = vec!["rev-list", "--count", "HEAD"];
+    let filter_args = log_filter_args(&filter);
+    log_args.extend(filter_args.iter().map(String::as_str));
+    count_args.extend(filter_args.iter().map(String::as_str));
+    if let Some(path) = path.as_deref() {
+        log_args.extend(["--", path]);
+        count_args.extend(["--", path]);
     }
+    let total = run_git_command_output(&repo_root, &count_args)
+        .await?
+        .trim()
+        .parse()
+        .unwrap_or(0);
+    let mut entries = parse_log_entries(&run_git_command_output(&repo_root, &log_args).await?);
+    let has_more = entries.len() > max_items;
+    entries.truncate(max_items);
 
-    let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
-    revwalk.push_head().map_err(|e| e.to_string())?;
-    revwalk.set_sorting(Sort::TIME).map_err(|e| e.to_string())?;
-
-    let mut entries = Vec::new();
-    for oid_result in revwalk.take(max_items) {
-        let oid = oid_result.map_err(|e| e.to_string())?;
-        let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
-        entries.push(commit_to_entry(commit));
-    }
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
 
     let mut ahead = 0usize;
     let mut behind = 0usize;
@@ -92,6 +179,7 @@ pub(super) async fn get_git_log_inner(
     Ok(GitLogResponse {
         total,
         entries,
+        has_more,
         ahead,
         behind,
         ahead_entries,
@@ -100,6 +188,204 @@ pub(super) async fn get_git_log_inner(
     })
 }
 
+/// Branch, remote-branch and tag labels keyed by the commit they point at.
+pub(super) fn collect_ref_labels(
+    repo: &Repository,
+) -> Result<HashMap<Oid, Vec<GitRefLabel>>, String> {
+    let mut labels: HashMap<Oid, Vec<GitRefLabel>> = HashMap::new();
+    if let Ok(head) = repo.head() {
+        if let Some(oid) = head.target() {
+            labels.entry(oid).or_default().push(GitRefLabel {
+                name: "HEAD".to_string(),
+                kind: "head".to_string(),
+            });
+        }
+    }
+    for reference in repo.references().map_err(|e| e.to_string())? {
+        let Ok(reference) = reference else {
+            continue;
+        };
+        let kind = if reference.is_branch() {
+            "branch"
+        } else if reference.is_remote() {
+            "remote"
+        } else if reference.is_tag() {
+            "tag"
+        } else {
+            continue;
+        };
+        let Some(name) = reference.shorthand() else {
+            continue;
+        };
+        // `origin/HEAD` only duplicates the remote default branch label.
+        if kind == "remote" && name.ends_with("/HEAD") {
+            continue;
+        }
+        // Annotated tags point at a tag object, not the commit.
+        let Ok(commit) = reference.peel_to_commit() else {
+            continue;
+        };
+        labels.entry(commit.id()).or_default().push(GitRefLabel {
+            name: name.to_string(),
+            kind: kind.to_string(),
+        });
+    }
+    Ok(labels)
+}
+
+pub(super) async fn get_git_graph_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    max_count: Option<usize>,
+    refs: Option<Vec<String>>,
+) -> Result<GitGraphResponse, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    let max_count = max_count
+        .unwrap_or(DEFAULT_GRAPH_COUNT)
+        .clamp(1, MAX_GRAPH_COUNT);
+
+    let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
+    revwalk
+        .set_sorting(Sort::TOPOLOGICAL | Sort::TIME)
+        .map_err(|e| e.to_string())?;
+    let refs: Vec<String> = refs
+        .unwrap_or_default()
+        .into_iter()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect();
+    if refs.is_empty() {
+        revwalk.push_head().map_err(|e| e.to_string())?;
+    } else {
+        for name in &refs {
+            let commit = repo
+                .revparse_single(name)
+                .and_then(|object| object.peel_to_commit())
+                .map_err(|_| format!("Unknown ref: {name}"))?;
+            revwalk.push(commit.id()).map_err(|e| e.to_string())?;
+        }
+    }
+
+    let mut labels = collect_ref_labels(&repo)?;
+    let mut nodes = Vec::new();
+    let mut truncated = false;
+    for oid_result in revwalk {
+        let oid = oid_result.map_err(|e| e.to_string())?;
+        if nodes.len() == max_count {
+            truncated = true;
+            break;
+        }
+        let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
+        let parents = commit.parent_ids().map(|id| id.to_string()).collect();
+        let refs = labels.remove(&oid).unwrap_or_default();
+        let entry = commit_to_entry(commit);
+        nodes.push(GitGraphNode {
+            sha: entry.sha,
+            summary: entry.summary,
+            author: entry.author,
+            timestamp: entry.timestamp,
+            parents,
+            refs,
+        });
+    }
+
+    Ok(GitGraphResponse { nodes, truncated })
+}
+
+/// Expands a numstat path such as `src/{old.rs => new.rs}` or
+/// `old.rs => new.rs` into `(previous_path, path)`.
+pub(super) fn parse_numstat_path(raw: &str) -> (Option<String>, String) {
+    if let (Some(open), Some(close)) = (raw.find('{'), raw.rfind('}')) {
+        if let Some((old, new)) = raw[open + 1..close].split_once(" => ") {
+            let prefix = &raw[..open];
+            let suffix = &raw[close + 1..];
+            let join = |middle: &str| format!("{prefix}{middle}{suffix}").replace("//", "/");
+            return (Some(join(old)), join(new));
+        }
+    }
+    match raw.split_once(" => ") {
+        Some((old, new)) => (Some(old.to_string()), new.to_string()),
+        None => (None, raw.to_string()),
+    }
+}
+
+pub(super) fn parse_file_history(output: &str) -> Vec<GitFileHistoryEntry> {
+    output
+        .split(RECORD_SEPARATOR)
+        .filter_map(|record| {
+            let mut lines = record.lines();
+            let GitLogEntry {
+                sha,
+                summary,
+                author,
+                timestamp,
+            } = parse_log_header(lines.next()?)?;
+            let stat = lines.find(|line| !line.trim().is_empty());
+            let (additions, deletions, previous_path, path) = match stat {
+                Some(line) => {
+                    let mut parts = line.splitn(3, '\t');
+                    // Binary files report `-` for both counts.
+                    let additions = parts.next().and_then(|value| value.parse().ok());
+                    let deletions = parts.next().and_then(|value| value.parse().ok());
+                    let (previous_path, path) = parse_numstat_path(parts.next().unwrap_or(""));
+                    (additions, deletions, previous_path, path)
+                }
+                None => (None, None, None, String::new()),
+            };
+            Some(GitFileHistoryEntry {
+                sha,
+                summary,
+                author,
+                timestamp,
+                path,
+                previous_path,
+                additions,
+                deletions,
+            })
+        })
+        .collect()
+}
+
+pub(super) async fn get_file_history_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+    follow_renames: bool,
+    max_count: Option<usize>,
+) -> Result<Vec<GitFileHistoryEntry>, String> {
+    let path = normalize_git_path(path.trim());
+    if path.is_empty() {
+        return Err("File path is required.".to_string());
+    }
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let max_count = max_count
+        .unwrap_or(DEFAULT_FILE_HISTORY_COUNT)
+        .clamp(1, MAX_FILE_HISTORY_COUNT)
+        .to_string();
+    let mut args = vec![
+        "log",
+        "--numstat",
+        "-M",
+        LOG_FORMAT,
+        "-n",
+        max_count.as_str(),
+    ];
+    if follow_renames {
+        args.push("--follow");
+    }
+    args.extend(["--", path.as_str()]);
+    let output = run_git_command_output(&repo_root, &args).await?;
+    let mut entries = parse_file_history(&output);
+    // Commits that only touched the file's mode have no numstat line.
+    for entry in entries.iter_mut().filter(|entry| entry.path.is_empty()) {
+        entry.path = path.clone();
+    }
+    Ok(entries)
+}
+
 pub(super) async fn get_git_remote_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
@@ -119,3 +405,45 @@ pub(super) async fn get_git_remote_inner(
     let remote = repo.find_remote(&name).map_err(|e| e.to_string())?;
     Ok(remote.url().map(|url| url.to_string()))
 }
+
+/// Reads `git remote -v`, which lists each remote once with `(fetch)` and
+/// once per push URL with `(push)`. Only the first push URL is kept.
+pub(super) fn parse_git_remotes(output: &str) -> Vec<GitRemote> {
+    let mut remotes: Vec<GitRemote> = Vec::new();
+    for line in output.lines() {
+        let Some((name, rest)) = line.split_once('\t') else {
+            continue;
+        };
+        let Some((url, kind)) = rest.rsplit_once(' ') else {
+            continue;
+        };
+        let index = match remotes.iter().position(|remote| remote.name == name) {
+            Some(index) => index,
+            None => {
+                remotes.push(GitRemote {
+                    name: name.to_string(),
+                    fetch_url: None,
+                    push_url: None,
+                });
+                remotes.len() - 1
+            }
+        };
+        let remote = &mut remotes[index];
+        match kind {
+            "(fetch)" => remote.fetch_url = Some(url.to_string()),
+            "(push)" if remote.push_url.is_none() => remote.push_url = Some(url.to_string()),
+            _ => {}
+        }
+    }
+    remotes
+}
+
+pub(super) async fn list_git_remotes_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<Vec<GitRemote>, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let output = run_git_command_output(&repo_root, &["remote", "-v"]).await?;
+    Ok(parse_git_remotes(&output))
+}
@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+
+use tokio::sync::Mutex;
+
+use crate::git_utils::resolve_git_root;
+use crate::shared::git_core::run_git_command;
+use crate::types::{GitStashApplyResult, GitStashEntry, GitStashOutcome, WorkspaceEntry};
+
+use super::context::workspace_entry_for_id;
+use super::sync::conflicted_paths;
+
+const STASH_LIST_FORMAT: &str = "--format=%gd%x1f%ct%x1f%gs";
+const FIELD_SEPARATOR: char = '\u{1f}';
+
+/// Splits a stash subject into its branch and message: `On main: note` for
+/// a named stash, `WIP on main: abc1234 subject` for an unnamed one, whose
+/// whole subject is kept as the message.
+fn parse_stash_subject(subject: &str) -> (Option<String>, String) {
+    let (rest, named) = match subject.strip_prefix("On ") {
+        Some(rest) => (rest, true),
+        None => match subject.strip_prefix("WIP on ") {
+            Some(rest) => (rest, false),
+            None => return (None, subject.to_string()),
+        },
+    };
+    let Some((branch, message)) = rest.split_once(": ") else {
+        return (None, subject.to_string());
+    };
+    let branch = (branch != "(no branch)").then(|| branch.to_string());
+    let message = if named { message } else { subject };
+    (branch, message.to_string())
+}
+
+pub(super) fn parse_stash_list(output: &str) -> Vec<GitStashEntry> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, FIELD_SEPARATOR);
+            let index = fields
+                .next()?
+                .strip_prefix("stash@{")?
+                .strip_suffix('}')?
+                .parse()
+                .ok()?;
+            let created_at = fields.next()?.parse().unwrap_or(0);
+            let (branch, message) = parse_stash_subject(fields.next().unwrap_or(""));
+            Some(GitStashEntry {
+                index,
+                message,
+                branch,
+                created_at,
+            })
+        })
+        .collect()
+}
+
+pub(super) async fn git_stash_list_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<Vec<GitStashEntry>, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let output = run_git_command(&repo_root, &["stash", "list", STASH_LIST_FORMAT]).await?;
+    Ok(parse_stash_list(&output))
+}
+
+pub(super) async fn git_stash_push_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    message: Option<String>,
+    include_untracked: bool,
+) -> Result<(), String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let message = message
+        .map(|message| message.trim().to_string())
+        .filter(|message| !message.is_empty());
+    let mut args = vec!["stash", "push"];
+    if include_untracked {
+        args.push("--include-untracked");
+    }
+    if let Some(message) = message.as_deref() {
+        args.extend(["-m", message]);
+    }
+    let stash_ref = ["rev-parse", "--quiet", "--verify", "refs/stash"];
+    let before = run_git_command(&repo_root, &stash_ref).await.ok();
+    run_git_command(&repo_root, &args).await?;
+    // Git exits cleanly when there is nothing to stash, so look at the ref.
+    let after = run_git_command(&repo_root, &stash_ref).await.ok();
+    if after.is_none() || after == before {
+        return Err("There are no local changes to stash.".to_string());
+    }
+    Ok(())
+}
+
+/// Applies `stash@{index}` and, for `pop`, drops it once it applied
+/// cleanly. When the stash conflicts with the working tree git leaves the
+/// conflicts in place and keeps the stash, which comes back as `conflicts`.
+async fn apply_stash(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    index: usize,
+    pop: bool,
+) -> Result<GitStashApplyResult, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let stash = format!("stash@{{{index}}}");
+    let command = if pop { "pop" } else { "apply" };
+    match run_git_command(&repo_root, &["stash", command, &stash]).await {
+        Ok(_) => Ok(GitStashApplyResult {
+            outcome: GitStashOutcome::Applied,
+            conflicts: Vec::new(),
+            dropped: pop,
+            message: None,
+        }),
+        Err(error) => {
+            let conflicts = conflicted_paths(&repo_root);
+            if conflicts.is_empty() {
+                return Err(error);
+            }
+            Ok(GitStashApplyResult {
+                outcome: GitStashOutcome::Conflicts,
+                conflicts,
+                dropped: false,
+                message: Some(error),
+            })
+        }
+    }
+}
+
+pub(super) async fn git_stash_apply_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    index: usize,
+) -> Result<GitStashApplyResult, String> {
+    apply_stash(workspaces, workspace_id, index, false).await
+}
+
+pub(super) async fn git_stash_pop_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    index: usize,
+) -> Result<GitStashApplyResult, String> {
+    apply_stash(workspaces, workspace_id, index, true).await
+}
+
+pub(super) async fn git_stash_drop_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    index: usize,
+) -> Result<(), String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let stash = format!("stash@{{{index}}}");
+    run_git_command(&repo_root, &["stash", "drop", &stash]).await?;
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex as StdMutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use tokio::sync::Mutex;
+use tokio::time::timeout;
+
+use crate::types::{GitRepoContributor, GitRepoStats, WorkspaceEntry};
+use crate::utils::now_unix_ms;
+
+use super::commands::run_git_command_output;
+use super::context::resolve_repo_root_for_workspace;
+
+const REPO_STATS_CACHE_TTL: Duration = Duration::from_secs(600);
+const REPO_STATS_TIMEOUT: Duration = Duration::from_secs(30);
+const TOP_CONTRIBUTORS: usize = 10;
+const MAX_COUNTED_FILES: usize = 20_000;
+const MAX_COUNTED_FILE_BYTES: u64 = 2 * 1024 * 1024;
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+
+static REPO_STATS_CACHE: OnceLock<StdMutex<HashMap<PathBuf, (Instant, String, GitRepoStats)>>> =
+    OnceLock::new();
+
+/// Parses `git shortlog -sn` lines such as `   42\tAda Lovelace`.
+pub(super) fn parse_shortlog(output: &str) -> Vec<GitRepoContributor> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (count, name) = line.trim_start().split_once('\t')?;
+            Some(GitRepoContributor {
+                name: name.trim().to_string(),
+                commits: count.trim().parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// Counts newlines in tracked text files, skipping binaries and anything
+/// over the size cap. Returns `(files, lines, truncated)`.
+fn count_lines(repo_root: &Path, files: &[String]) -> (usize, u64, bool) {
+    let truncated = files.len() > MAX_COUNTED_FILES;
+    let mut counted = 0usize;
+    let mut lines = 0u64;
+    let mut buffer = Vec::new();
+    for file in files.iter().take(MAX_COUNTED_FILES) {
+        let path = repo_root.join(file);
+        let Ok(metadata) = std::fs::metadata(&path) else {
+            continue;
+        };
+        if !metadata.is_file() || metadata.len() > MAX_COUNTED_FILE_BYTES {
+            continue;
+        }
+        buffer.clear();
+        let Ok(mut handle) = File::open(&path) else {
+            continue;
+        };
+        if handle.read_to_end(&mut buffer).is_err() {
+            continue;
+        }
+        let sniff = &buffer[..buffer.len().min(BINARY_SNIFF_BYTES)];
+        if sniff.contains(&0) {
+            continue;
+        }
+        counted += 1;
+        lines += buffer.iter().filter(|byte| **byte == b'\n').count() as u64;
+        if !buffer.is_empty() && buffer.last() != Some(&b'\n') {
+            lines += 1;
+        }
+    }
+    (counted, lines, truncated)
+}
+
+fn cached_stats(repo_root: &Path, head: &str) -> Option<GitRepoStats> {
+    let cache = REPO_STATS_CACHE.get_or_init(|| StdMutex::new(HashMap::new()));
+    let cache = cache.lock().ok()?;
+    let (computed, cached_head, stats) = cache.get(repo_root)?;
+    if computed.elapsed() >= REPO_STATS_CACHE_TTL || cached_head != head {
+        return None;
+    }
+    let mut stats = stats.clone();
+    stats.cached = true;
+    Some(stats)
+}
+
+fn store_stats(repo_root: &Path, head: &str, stats: &GitRepoStats) {
+    let cache = REPO_STATS_CACHE.get_or_init(|| StdMutex::new(HashMap::new()));
+    if let Ok(mut cache) = cache.lock() {
+        cache.retain(|_, (computed, _, _)| computed.elapsed() < REPO_STATS_CACHE_TTL);
+        cache.insert(
+            repo_root.to_path_buf(),
+            (Instant::now(), head.to_string(), stats.clone()),
+        );
+    }
+}
+
+async fn compute_repo_stats(repo_root: &Path) -> Result<GitRepoStats, String> {
+    let total_commits = run_git_command_output(repo_root, &["rev-list", "--count", "HEAD"])
+        .await?
+        .trim()
+        .parse()
+        .unwrap_or(0);
+    let last_commit_at = run_git_command_output(repo_root, &["log", "-1", "--format=%ct"])
+        .await?
+        .trim()
+        .parse()
+        .ok();
+    // `shortlog` reads stdin unless a revision is given.
+    let shortlog = run_git_command_output(repo_root, &["shortlog", "-sn", "HEAD"]).await?;
+    let mut contributors = parse_shortlog(&shortlog);
+    let contributor_count = contributors.len();
+    contributors.truncate(TOP_CONTRIBUTORS);
+
+    let files: Vec<String> = run_git_command_output(repo_root, &["ls-files", "-z"])
+        .await?
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(str::to_string)
+        .collect();
+    let root = repo_root.to_path_buf();
+    let (file_count, lines_of_code, lines_truncated) =
+        tokio::task::spawn_blocking(move || count_lines(&root, &files))
+            .await
+            .map_err(|err| err.to_string())?;
+
+    Ok(GitRepoStats {
+        total_commits,
+        contributor_count,
+        top_contributors: contributors,
+        last_commit_at,
+        file_count,
+        lines_of_code,
+        lines_truncated,
+        computed_at_ms: now_unix_ms(),
+        cached: false,
+    })
+}
+
+pub(super) async fn get_repo_stats_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<GitRepoStats, String> {
+    let repo_root = resolve_repo_root_for_workspace(workspaces, workspace_id).await?;
+    let head = run_git_command_output(&repo_root, &["rev-parse", "HEAD"])
+        .await
+        .map_err(|_| "Repository has no commits yet.".to_string())?;
+    let head = head.trim();
+    if let Some(stats) = cached_stats(&repo_root, head) {
+        return Ok(stats);
+    }
+    let stats = timeout(REPO_STATS_TIMEOUT, compute_repo_stats(&repo_root))
+        .await
+        .map_err(|_| "Timed out computing repository statistics.".to_string())??;
+    store_stats(&repo_root, head, &stats);
+    Ok(stats)
+}
@@ -0,0 +1,448 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use git2::{Oid, Repository};
+use tokio::sync::Mutex;
+
+use crate::backend::events::{EventSink, GitSyncProgressEvent};
+use crate::git_utils::resolve_git_root;
+use crate::shared::{git_core, worktree_core};
+use crate::types::{GitFetchedRef, GitSyncOutcome, GitSyncResult, WorkspaceEntry};
+
+use super::commands::upstream_remote_and_branch;
+use super::context::workspace_entry_for_id;
+
+/// The share of a push each `git push --progress` stage covers, as
+/// `(stage, start, end)` percentages.
+const PUSH_PROGRESS_STAGES: &[(&str, u32, u32)] = &[
+    ("Counting objects", 0, 10),
+    ("Compressing objects", 10, 30),
+    ("Writing objects", 30, 100),
+];
+
+fn sync_progress_event(
+    workspace_id: &str,
+    operation: &str,
+    status: &str,
+    percent: u8,
+    stage: Option<&str>,
+    error: Option<String>,
+) -> GitSyncProgressEvent {
+    GitSyncProgressEvent {
+        workspace_id: workspace_id.to_string(),
+        operation: operation.to_string(),
+        status: status.to_string(),
+        percent,
+        stage: stage.map(str::to_string),
+        error,
+    }
+}
+
+fn finished_event<T>(
+    workspace_id: &str,
+    operation: &str,
+    result: &Result<T, String>,
+) -> GitSyncProgressEvent {
+    match result {
+        Ok(_) => sync_progress_event(workspace_id, operation, "done", 100, None, None),
+        Err(error) => sync_progress_event(
+            workspace_id,
+            operation,
+            "failed",
+            0,
+            None,
+            Some(error.clone()),
+        ),
+    }
+}
+
+async fn run_with_progress<E: EventSink>(
+    repo_root: &PathBuf,
+    args: &[&str],
+    workspace_id: &str,
+    operation: &str,
+    stages: &[(&'static str, u32, u32)],
+    event_sink: &E,
+) -> Result<String, String> {
+    let mut last_percent = None;
+    git_core::run_git_command_streaming(repo_root, args, |line| {
+        let Some((stage, percent)) = worktree_core::parse_git_progress(line, stages) else {
+            return;
+        };
+        if last_percent.is_some_and(|last| percent <= last) {
+            return;
+        }
+        last_percent = Some(percent);
+        event_sink.emit_git_sync_progress_event(sync_progress_event(
+            workspace_id,
+            operation,
+            "progress",
+            percent,
+            Some(stage),
+            None,
+        ));
+    })
+    .await
+}
+
+fn current_branch(repo_root: &Path) -> Result<String, String> {
+    let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
+    let head = repo.head().map_err(|e| e.to_string())?;
+    if !head.is_branch() {
+        return Err("Cannot push a detached HEAD. Check out a branch first.".to_string());
+    }
+    head.shorthand()
+        .map(str::to_string)
+        .ok_or_else(|| "Current branch name is not valid UTF-8.".to_string())
+}
+
+fn head_oid(repo_root: &Path) -> Option<Oid> {
+    let repo = Repository::open(repo_root).ok()?;
+    let head = repo.head().ok()?;
+    head.target()
+}
+
+fn remote_branch_oid(repo_root: &Path, remote: &str, branch: &str) -> Option<Oid> {
+    let repo = Repository::open(repo_root).ok()?;
+    let oid = repo
+        .refname_to_id(&format!("refs/remotes/{remote}/{branch}"))
+        .ok()?;
+    Some(oid)
+}
+
+pub(super) fn conflicted_paths(repo_root: &Path) -> Vec<String> {
+    let Ok(repo) = Repository::open(repo_root) else {
+        return Vec::new();
+    };
+    let Ok(index) = repo.index() else {
+        return Vec::new();
+    };
+    let Ok(conflicts) = index.conflicts() else {
+        return Vec::new();
+    };
+    let mut paths: Vec<String> = conflicts
+        .flatten()
+        .filter_map(|conflict| conflict.our.or(conflict.their).or(conflict.ancestor))
+        .map(|entry| String::from_utf8_lossy(&entry.path).to_string())
+        .collect();
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+/// Reads the flag column of `git push --porcelain`: `=` is up to date and a
+/// space is a fast-forward; a new branch or forced update counts as updated.
+pub(super) fn push_outcome(stdout: &str) -> GitSyncOutcome {
+    let flags: Vec<char> = stdout
+        .lines()
+        .filter(|line| line.contains('\t'))
+        .filter_map(|line| line.chars().next())
+        .collect();
+    if flags.iter().all(|flag| *flag == '=') {
+        GitSyncOutcome::UpToDate
+    } else if flags.iter().all(|flag| matches!(flag, '=' | ' ')) {
+        GitSyncOutcome::FastForwarded
+    } else {
+        GitSyncOutcome::Updated
+    }
+}
+
+/// Non-fast-forward refs and refs a server hook declined both end with
+/// git's "failed to push some refs".
+fn is_push_rejection(error: &str) -> bool {
+    let lower = error.to_lowercase();
+    lower.contains("failed to push some refs")
+        || lower.contains("[rejected]")
+        || lower.contains("[remote rejected]")
+}
+
+async fn push<E: EventSink>(
+    repo_root: &PathBuf,
+    workspace_id: &str,
+    set_upstream: bool,
+    event_sink: &E,
+) -> Result<GitSyncResult, String> {
+    let (target, upstream) = match upstream_remote_and_branch(repo_root)? {
+        Some((remote, branch)) => (
+            vec![remote.clone(), format!("HEAD:{branch}")],
+            Some(format!("{remote}/{branch}")),
+        ),
+        None if set_upstream => {
+            let branch = current_branch(repo_root)?;
+            (
+                vec!["-u".to_string(), "origin".to_string(), branch.clone()],
+                Some(format!("origin/{branch}")),
+            )
+        }
+        None => (Vec::new(), None),
+    };
+    let mut args = vec!["push", "--porcelain", "--progress"];
+    args.extend(target.iter().map(String::as_str));
+    match run_with_progress(
+        repo_root,
+        &args,
+        workspace_id,
+        "push",
+        PUSH_PROGRESS_STAGES,
+        event_sink,
+    )
+    .await
+    {
+        Ok(stdout) => Ok(GitSyncResult {
+            outcome: push_outcome(&stdout),
+            upstream,
+            conflicts: Vec::new(),
+            message: None,
+        }),
+        Err(error) if is_push_rejection(&error) => Ok(GitSyncResult {
+            outcome: GitSyncOutcome::Rejected,
+            upstream,
+            conflicts: Vec::new(),
+            message: Some(error),
+        }),
+        Err(error) => Err(error),
+    }
+}
+
+async fn pull<E: EventSink>(
+    repo_root: &PathBuf,
+    workspace_id: &str,
+    rebase: bool,
+    event_sink: &E,
+) -> Result<GitSyncResult, String> {
+    let (remote, branch) = upstream_remote_and_branch(repo_root)?
+        .ok_or_else(|| "The current branch has no upstream to pull from.".to_string())?;
+    let upstream = Some(format!("{remote}/{branch}"));
+    let before = head_oid(repo_root);
+    let mode = if rebase { "--rebase" } else { "--no-rebase" };
+    let mut args = vec!["pull", "--progress", mode, "--autostash"];
+    let mut result = run_with_progress(
+        repo_root,
+        &args,
+        workspace_id,
+        "pull",
+        worktree_core::CLONE_PROGRESS_STAGES,
+        event_sink,
+    )
+    .await;
+    if let Err(error) = &result {
+        let lower = error.to_lowercase();
+        if lower.contains("unknown option") && lower.contains("autostash") {
+            args.pop();
+            result = run_with_progress(
+                repo_root,
+                &args,
+                workspace_id,
+                "pull",
+                worktree_core::CLONE_PROGRESS_STAGES,
+                event_sink,
+            )
+            .await;
+        }
+    }
+
+    // A merge that stops on conflicts fails; an autostash that doesn't
+    // apply cleanly afterwards doesn't, so look at the index either way.
+    let conflicts = conflicted_paths(repo_root);
+    if !conflicts.is_empty() {
+        return Ok(GitSyncResult {
+            outcome: GitSyncOutcome::Conflicts,
+            upstream,
+            conflicts,
+            message: result.err(),
+        });
+    }
+    result?;
+    let after = head_oid(repo_root);
+    let outcome = if before == after {
+        GitSyncOutcome::UpToDate
+    } else if after.is_some() && after == remote_branch_oid(repo_root, &remote, &branch) {
+        GitSyncOutcome::FastForwarded
+    } else {
+        GitSyncOutcome::Updated
+    };
+    Ok(GitSyncResult {
+        outcome,
+        upstream,
+        conflicts: Vec::new(),
+        message: None,
+    })
+}
+
+/// Pushes the current branch and reports whether the remote moved. With
+/// `set_upstream`, a branch without an upstream is published to `origin`
+/// and starts tracking it. Emits `git-sync-progress` while git runs.
+pub(super) async fn git_push_inner<E: EventSink>(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    set_upstream: bool,
+    event_sink: &E,
+) -> Result<GitSyncResult, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let result = push(&repo_root, &workspace_id, set_upstream, event_sink).await;
+    event_sink.emit_git_sync_progress_event(finished_event(&workspace_id, "push", &result));
+    result
+}
+
+/// Pulls the upstream with a merge, or a rebase when `rebase` is set,
+/// stashing local changes around it. Conflicts come back as an outcome with
+/// the conflicted paths rather than as an error.
+pub(super) async fn git_pull_inner<E: EventSink>(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    rebase: bool,
+    event_sink: &E,
+) -> Result<GitSyncResult, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let result = pull(&repo_root, &workspace_id, rebase, event_sink).await;
+    event_sink.emit_git_sync_progress_event(finished_event(&workspace_id, "pull", &result));
+    result
+}
+
+/// Remote-tracking branches and tags by name. Symbolic refs such as
+/// `origin/HEAD` are left out.
+fn remote_ref_snapshot(repo_root: &Path) -> Result<BTreeMap<String, Oid>, String> {
+    let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
+    let mut refs = BTreeMap::new();
+    for glob in ["refs/remotes/*", "refs/tags/*"] {
+        let references = repo.references_glob(glob).map_err(|e| e.to_string())?;
+        for reference in references.flatten() {
+            if let (Some(name), Some(oid)) = (reference.shorthand(), reference.target()) {
+                refs.insert(name.to_string(), oid);
+            }
+        }
+    }
+    Ok(refs)
+}
+
+/// The refs that differ between two snapshots, by name. A new ref has no
+/// `old_sha` and a pruned one no `new_sha`.
+pub(super) fn updated_refs(
+    before: &BTreeMap<String, Oid>,
+    after: &BTreeMap<String, Oid>,
+) -> Vec<GitFetchedRef> {
+    let mut names: Vec<&String> = before.keys().chain(after.keys()).collect();
+    names.sort();
+    names.dedup();
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let old = before.get(name);
+            let new = after.get(name);
+            (old != new).then(|| GitFetchedRef {
+                name: name.clone(),
+                old_sha: old.map(Oid::to_string),
+                new_sha: new.map(Oid::to_string),
+            })
+        })
+        .collect()
+}
+
+/// Rewords the transport failures people actually hit: no network, an
+/// unreachable host, rejected credentials or a wrong URL. Anything else is
+/// passed through as git wrote it.
+pub(super) fn describe_fetch_error(error: &str) -> String {
+    let lower = error.to_lowercase();
+    let has = |needles: &[&str]| needles.iter().any(|needle| lower.contains(needle));
+    let message = if has(&[
+        "could not resolve host",
+        "could not resolve hostname",
+        "name or service not known",
+        "temporary failure in name resolution",
+        "nodename nor servname provided",
+    ]) {
+        "Couldn't find the remote server. Check your internet connection and the remote's URL."
+    } else if has(&[
+        "connection timed out",
+        "operation timed out",
+        "connection refused",
+        "network is unreachable",
+        "no route to host",
+        "failed to connect",
+    ]) {
+        "Couldn't connect to the remote server. Check your internet connection and try again."
+    } else if has(&[
+        "authentication failed",
+        "permission denied",
+        "could not read username",
+        "terminal prompts disabled",
+        "invalid username or password",
+    ]) {
+        "The remote didn't accept your credentials. Sign in again or check that you have access to the repository."
+    } else if has(&[
+        "repository not found",
+        "does not appear to be a git repository",
+    ]) {
+        "The remote repository couldn't be found. Check the remote's URL."
+    } else if has(&["could not read from remote repository", "unable to access"]) {
+        "Couldn't reach the remote repository. Check your internet connection and that you have access to it."
+    } else {
+        return error.to_string();
+    };
+    message.to_string()
+}
+
+async fn fetch<E: EventSink>(
+    repo_root: &PathBuf,
+    workspace_id: &str,
+    remote: Option<&str>,
+    prune: bool,
+    event_sink: &E,
+) -> Result<Vec<GitFetchedRef>, String> {
+    let mut args = vec!["fetch", "--progress"];
+    match remote {
+        Some(remote) => {
+            if remote.starts_with('-') || !git_core::git_remote_exists(repo_root, remote).await? {
+                return Err(format!("There is no remote named `{remote}`."));
+            }
+            args.push(remote);
+        }
+        None => args.push("--all"),
+    }
+    if prune {
+        args.push("--prune");
+    }
+    let before = remote_ref_snapshot(repo_root)?;
+    run_with_progress(
+        repo_root,
+        &args,
+        workspace_id,
+        "fetch",
+        worktree_core::CLONE_PROGRESS_STAGES,
+        event_sink,
+    )
+    .await
+    .map_err(|error| describe_fetch_error(&error))?;
+    let after = remote_ref_snapshot(repo_root)?;
+    Ok(updated_refs(&before, &after))
+}
+
+/// Fetches `remote`, or every remote when it's unset, optionally pruning
+/// remote-tracking branches that are gone upstream. Returns the
+/// remote-tracking branches and tags the fetch created, moved or deleted.
+/// Emits `git-sync-progress` while git runs.
+pub(super) async fn git_fetch_inner<E: EventSink>(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    remote: Option<String>,
+    prune: bool,
+    event_sink: &E,
+) -> Result<Vec<GitFetchedRef>, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let remote = remote
+        .map(|remote| remote.trim().to_string())
+        .filter(|remote| !remote.is_empty());
+    let result = fetch(
+        &repo_root,
+        &workspace_id,
+        remote.as_deref(),
+        prune,
+        event_sink,
+    )
+    .await;
+    event_sink.emit_git_sync_progress_event(finished_event(&workspace_id, "fetch", &result));
+    result
+}
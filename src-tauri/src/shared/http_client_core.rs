@@ -0,0 +1,284 @@
+use std::sync::{Mutex as StdMutex, OnceLock};
+
+use reqwest::{Certificate, NoProxy, Proxy, Url};
+
+use crate::types::AppSettings;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct ProxyConfig {
+    pub(crate) http: Option<String>,
+    pub(crate) https: Option<String>,
+    pub(crate) no_proxy: Option<String>,
+}
+
+/// A private CA trusted only for the listed hosts. A host entry starting
+/// with `.` or `*.` also matches its subdomains.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CustomCa {
+    pub(crate) path: String,
+    pub(crate) pem: Vec<u8>,
+    pub(crate) hosts: Vec<String>,
+}
+
+static PROXY_CONFIG: OnceLock<StdMutex<ProxyConfig>> = OnceLock::new();
+static CUSTOM_CA: OnceLock<StdMutex<Option<CustomCa>>> = OnceLock::new();
+
+fn proxy_cell() -> &'static StdMutex<ProxyConfig> {
+    PROXY_CONFIG.get_or_init(|| StdMutex::new(resolve_proxy_config(&AppSettings::default())))
+}
+
+fn custom_ca_cell() -> &'static StdMutex<Option<CustomCa>> {
+    CUSTOM_CA.get_or_init(|| StdMutex::new(None))
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+fn env_value(names: &[&str]) -> Option<String> {
+    names
+        .iter()
+        .find_map(|name| non_empty(std::env::var(name).ok().as_deref()))
+}
+
+pub(crate) fn validate_proxy_url(label: &str, value: &str) -> Result<(), String> {
+    let url =
+        Url::parse(value.trim()).map_err(|err| format!("{label} is not a valid URL: {err}"))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("{label} must start with http:// or https://"));
+    }
+    if url.host_str().map_or(true, str::is_empty) {
+        return Err(format!("{label} must include a host."));
+    }
+    Ok(())
+}
+
+fn normalized_ca_hosts(hosts: &[String]) -> Vec<String> {
+    hosts
+        .iter()
+        .filter_map(|host| non_empty(Some(host)))
+        .map(|host| host.to_ascii_lowercase())
+        .collect()
+}
+
+pub(crate) fn host_matches(patterns: &[String], host: &str) -> bool {
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    patterns.iter().any(|pattern| {
+        let suffix = pattern
+            .strip_prefix("*.")
+            .or_else(|| pattern.strip_prefix('.'));
+        match suffix {
+            Some(suffix) => host.len() > suffix.len() && host.ends_with(&format!(".{suffix}")),
+            None => host == *pattern,
+        }
+    })
+}
+
+/// Reads and parses the configured CA. `None` when no path is set.
+fn load_custom_ca(settings: &AppSettings) -> Result<Option<CustomCa>, String> {
+    let Some(path) = non_empty(settings.custom_ca_cert_path.as_deref()) else {
+        return Ok(None);
+    };
+    let hosts = normalized_ca_hosts(&settings.custom_ca_hosts);
+    if hosts.is_empty() {
+        return Err("customCaHosts must list at least one host for the custom CA.".to_string());
+    }
+    let pem = std::fs::read(&path)
+        .map_err(|err| format!("Failed to read CA certificate {path}: {err}"))?;
+    Certificate::from_pem(&pem)
+        .map_err(|err| format!("CA certificate {path} is not valid PEM: {err}"))?;
+    Ok(Some(CustomCa { path, pem, hosts }))
+}
+
+pub(crate) fn validate_http_settings(settings: &AppSettings) -> Result<(), String> {
+    if let Some(value) = non_empty(settings.http_proxy.as_deref()) {
+        validate_proxy_url("httpProxy", &value)?;
+    }
+    if let Some(value) = non_empty(settings.https_proxy.as_deref()) {
+        validate_proxy_url("httpsProxy", &value)?;
+    }
+    load_custom_ca(settings).map(|_| ())
+}
+
+/// Settings win; the conventional environment variables fill in anything
+/// left blank.
+pub(crate) fn resolve_proxy_config(settings: &AppSettings) -> ProxyConfig {
+    ProxyConfig {
+        http: non_empty(settings.http_proxy.as_deref())
+            .or_else(|| env_value(&["HTTP_PROXY", "http_proxy"])),
+        https: non_empty(settings.https_proxy.as_deref())
+            .or_else(|| env_value(&["HTTPS_PROXY", "https_proxy"])),
+        no_proxy: non_empty(settings.no_proxy.as_deref())
+            .or_else(|| env_value(&["NO_PROXY", "no_proxy"])),
+    }
+}
+
+/// Called whenever settings are loaded or saved so every client built
+/// afterwards picks up the change.
+pub(crate) fn apply_http_settings(settings: &AppSettings) {
+    let config = resolve_proxy_config(settings);
+    if let Ok(mut current) = proxy_cell().lock() {
+        *current = config;
+    }
+    let custom_ca = load_custom_ca(settings).unwrap_or_else(|err| {
+        eprintln!("Ignoring custom CA certificate: {err}");
+        None
+    });
+    if let Ok(mut current) = custom_ca_cell().lock() {
+        *current = custom_ca;
+    }
+}
+
+fn current_custom_ca() -> Option<CustomCa> {
+    custom_ca_cell().lock().ok().and_then(|ca| ca.clone())
+}
+
+pub(crate) fn current_proxy_config() -> ProxyConfig {
+    proxy_cell()
+        .lock()
+        .map(|config| config.clone())
+        .unwrap_or_default()
+}
+
+/// Starting point for outbound HTTP clients whose host is not known up
+/// front. Prefer [`http_client_builder_for`] when it is.
+pub(crate) fn http_client_builder() -> Result<reqwest::ClientBuilder, String> {
+    let config = current_proxy_config();
+    // The environment was already folded into `config`; stop reqwest from
+    // reading it a second time with different precedence.
+    let mut builder = reqwest::Client::builder().no_proxy();
+    let no_proxy = config.no_proxy.as_deref().and_then(NoProxy::from_string);
+    if let Some(url) = config.http.as_deref() {
+        let proxy = Proxy::http(url).map_err(|err| format!("Invalid HTTP proxy: {err}"))?;
+        builder = builder.proxy(proxy.no_proxy(no_proxy.clone()));
+    }
+    if let Some(url) = config.https.as_deref() {
+        let proxy = Proxy::https(url).map_err(|err| format!("Invalid HTTPS proxy: {err}"))?;
+        builder = builder.proxy(proxy.no_proxy(no_proxy));
+    }
+    Ok(builder)
+}
+
+/// Like [`http_client_builder`], but also trusts the custom CA when `url`
+/// points at one of its hosts. Verification stays on for everything else.
+pub(crate) fn http_client_builder_for(url: &str) -> Result<reqwest::ClientBuilder, String> {
+    let builder = http_client_builder()?;
+    let Some(custom_ca) = current_custom_ca() else {
+        return Ok(builder);
+    };
+    let host = Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string));
+    if !host.is_some_and(|host| host_matches(&custom_ca.hosts, &host)) {
+        return Ok(builder);
+    }
+    let certificate = Certificate::from_pem(&custom_ca.pem)
+        .map_err(|err| format!("Invalid CA certificate {}: {err}", custom_ca.path))?;
+    Ok(builder.add_root_certificate(certificate))
+}
+
+/// `http.<url>.sslCAInfo` scoped to each configured host. Wildcard entries
+/// rely on git's `*` host matching.
+fn git_ca_config(custom_ca: &CustomCa) -> Vec<(String, String)> {
+    custom_ca
+        .hosts
+        .iter()
+        .map(|host| {
+            let host = host
+                .strip_prefix('.')
+                .map(|suffix| format!("*.{suffix}"))
+                .unwrap_or_else(|| host.clone());
+            (
+                format!("http.https://{host}/.sslCAInfo"),
+                custom_ca.path.clone(),
+            )
+        })
+        .collect()
+}
+
+/// Exports the active proxy to child processes (git, gh, codex) which read
+/// the same conventional variables, and hands git the custom CA through
+/// `GIT_CONFIG_*` so it stays scoped to the configured hosts.
+pub(crate) fn apply_network_env(command: &mut std::process::Command) {
+    let config = current_proxy_config();
+    if let Some(value) = config.http.as_deref() {
+        command.env("HTTP_PROXY", value).env("http_proxy", value);
+    }
+    if let Some(value) = config.https.as_deref() {
+        command.env("HTTPS_PROXY", value).env("https_proxy", value);
+    }
+    if let Some(value) = config.no_proxy.as_deref() {
+        command.env("NO_PROXY", value).env("no_proxy", value);
+    }
+    let Some(custom_ca) = current_custom_ca() else {
+        return;
+    };
+    let mut index: usize = std::env::var("GIT_CONFIG_COUNT")
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(0);
+    for (key, value) in git_ca_config(&custom_ca) {
+        command
+            .env(format!("GIT_CONFIG_KEY_{index}"), key)
+            .env(format!("GIT_CONFIG_VALUE_{index}"), value);
+        index += 1;
+    }
+    command.env("GIT_CONFIG_COUNT", index.to_string());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_proxy_url_requires_http_scheme_and_host() {
+        assert!(validate_proxy_url("httpProxy", "http://proxy.corp:8080").is_ok());
+        assert!(validate_proxy_url("httpsProxy", "https://user:pw@proxy.corp").is_ok());
+        assert!(validate_proxy_url("httpProxy", "socks5://proxy.corp:1080").is_err());
+        assert!(validate_proxy_url("httpProxy", "proxy.corp:8080").is_err());
+        assert!(validate_proxy_url("httpProxy", "not a url").is_err());
+    }
+
+    #[test]
+    fn resolve_proxy_config_prefers_settings() {
+        let settings = AppSettings {
+            http_proxy: Some(" http://proxy.corp:8080 ".to_string()),
+            https_proxy: Some("http://secure.corp:8443".to_string()),
+            no_proxy: Some("localhost,.corp".to_string()),
+            ..AppSettings::default()
+        };
+        let config = resolve_proxy_config(&settings);
+        assert_eq!(config.http.as_deref(), Some("http://proxy.corp:8080"));
+        assert_eq!(config.https.as_deref(), Some("http://secure.corp:8443"));
+        assert_eq!(config.no_proxy.as_deref(), Some("localhost,.corp"));
+    }
+
+    #[test]
+    fn host_matches_exact_and_subdomain_entries() {
+        let patterns = normalized_ca_hosts(&[
+            "GHE.corp.example".to_string(),
+            ".internal".to_string(),
+            "*.dev.example".to_string(),
+            " ".to_string(),
+        ]);
+        assert_eq!(patterns.len(), 3);
+        assert!(host_matches(&patterns, "ghe.corp.example"));
+        assert!(!host_matches(&patterns, "api.ghe.corp.example"));
+        assert!(host_matches(&patterns, "git.internal"));
+        assert!(!host_matches(&patterns, "internal"));
+        assert!(host_matches(&patterns, "a.dev.example"));
+        assert!(!host_matches(&patterns, "github.com"));
+    }
+
+    #[test]
+    fn custom_ca_requires_hosts() {
+        let settings = AppSettings {
+            custom_ca_cert_path: Some("/tmp/ca.pem".to_string()),
+            ..AppSettings::default()
+        };
+        assert!(validate_http_settings(&settings).is_err());
+    }
+}
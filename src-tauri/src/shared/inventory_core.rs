@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex as StdMutex, OnceLock};
+
+use tokio::sync::Mutex;
+
+use crate::backend::app_server::WorkspaceSession;
+use crate::shared::workspaces_core::list_workspaces_core;
+use crate::types::{
+    InventoryScheduler, InventorySession, InventoryWorkspace, OperationInfo, SystemInventory,
+    WorkspaceEntry,
+};
+use crate::utils::now_unix_ms;
+
+static SCHEDULERS: OnceLock<StdMutex<HashMap<&'static str, InventoryScheduler>>> = OnceLock::new();
+
+fn schedulers() -> &'static StdMutex<HashMap<&'static str, InventoryScheduler>> {
+    SCHEDULERS.get_or_init(|| StdMutex::new(HashMap::new()))
+}
+
+pub(crate) fn uptime_ms(now_ms: i64, started_at_ms: i64) -> u64 {
+    now_ms.saturating_sub(started_at_ms).max(0) as u64
+}
+
+/// Called once as a background loop starts.
+pub(crate) fn scheduler_started(name: &'static str) {
+    if let Ok(mut schedulers) = schedulers().lock() {
+        schedulers.insert(
+            name,
+            InventoryScheduler {
+                name: name.to_string(),
+                started_at_ms: now_unix_ms(),
+                last_tick_ms: None,
+            },
+        );
+    }
+}
+
+/// Called at the top of each pass of a loop registered with
+/// [`scheduler_started`].
+pub(crate) fn scheduler_ticked(name: &'static str) {
+    if let Ok(mut schedulers) = schedulers().lock() {
+        if let Some(scheduler) = schedulers.get_mut(name) {
+            scheduler.last_tick_ms = Some(now_unix_ms());
+        }
+    }
+}
+
+pub(crate) fn list_schedulers() -> Vec<InventoryScheduler> {
+    let mut list: Vec<InventoryScheduler> = schedulers()
+        .lock()
+        .map(|schedulers| schedulers.values().cloned().collect())
+        .unwrap_or_default();
+    list.sort_by(|a, b| a.name.cmp(&b.name));
+    list
+}
+
+/// Everything this process is managing. Terminals live in the app, so the
+/// app adds them to whatever this returns.
+pub(crate) async fn get_system_inventory_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    operations: Vec<OperationInfo>,
+) -> SystemInventory {
+    let now = now_unix_ms();
+    let inventory_workspaces = list_workspaces_core(workspaces, sessions)
+        .await
+        .into_iter()
+        .map(|info| InventoryWorkspace {
+            id: info.id,
+            name: info.name,
+            kind: info.kind,
+            parent_id: info.parent_id,
+            path: info.path,
+            connected: info.connected,
+            path_exists: info.path_exists,
+        })
+        .collect();
+
+    let live: Vec<Arc<WorkspaceSession>> = sessions.lock().await.values().cloned().collect();
+    let mut inventory_sessions = Vec::with_capacity(live.len());
+    for session in live {
+        let pid = session.child.lock().await.id();
+        inventory_sessions.push(InventorySession {
+            workspace_id: session.entry.id.clone(),
+            pid,
+            started_at_ms: session.started_at_ms,
+            uptime_ms: uptime_ms(now, session.started_at_ms),
+        });
+    }
+    inventory_sessions.sort_by(|a, b| a.workspace_id.cmp(&b.workspace_id));
+
+    SystemInventory {
+        generated_at_ms: now,
+        workspaces: inventory_workspaces,
+        sessions: inventory_sessions,
+        terminals: Vec::new(),
+        operations,
+        schedulers: list_schedulers(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schedulers_record_start_and_ticks() {
+        scheduler_started("inventoryTest");
+        let started = list_schedulers()
+            .into_iter()
+            .find(|scheduler| scheduler.name == "inventoryTest")
+            .expect("registered scheduler");
+        assert!(started.last_tick_ms.is_none());
+
+        scheduler_ticked("inventoryTest");
+        scheduler_ticked("neverStarted");
+        let schedulers = list_schedulers();
+        let ticked = schedulers
+            .iter()
+            .find(|scheduler| scheduler.name == "inventoryTest")
+            .expect("registered scheduler");
+        assert!(ticked.last_tick_ms.is_some());
+        assert!(!schedulers
+            .iter()
+            .any(|scheduler| scheduler.name == "neverStarted"));
+    }
+
+    #[test]
+    fn uptime_never_goes_negative() {
+        assert_eq!(uptime_ms(5_000, 2_000), 3_000);
+        assert_eq!(uptime_ms(1_000, 2_000), 0);
+    }
+}
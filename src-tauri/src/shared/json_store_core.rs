@@ -0,0 +1,86 @@
+use std::path::Path;
+use std::sync::Mutex as StdMutex;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::utils::write_file_atomic;
+
+/// Held across each read-modify-write, so two commands updating a store at
+/// once can't drop each other's change.
+static STORE_LOCK: StdMutex<()> = StdMutex::new(());
+
+/// The document at `path`, or `T::default()` when there is no file yet. A
+/// file that doesn't parse is an error rather than an empty store, so a
+/// damaged file is never silently overwritten.
+pub(crate) fn read_json_store<T>(path: &Path) -> Result<T, String>
+where
+    T: DeserializeOwned + Default,
+{
+    if !path.exists() {
+        return Ok(T::default());
+    }
+    let data = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+/// Reads the document at `path`, lets `update` change it and writes it back
+/// through a temp file. Returns what `update` returned.
+pub(crate) fn update_json_store<T, R>(
+    path: &Path,
+    update: impl FnOnce(&mut T) -> R,
+) -> Result<R, String>
+where
+    T: DeserializeOwned + Serialize + Default,
+{
+    let _guard = STORE_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let mut document = read_json_store(path)?;
+    let result = update(&mut document);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let data = serde_json::to_string_pretty(&document).map_err(|e| e.to_string())?;
+    write_file_atomic(path, data.as_bytes())?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    type Counts = HashMap<String, u32>;
+
+    #[test]
+    fn updates_from_many_threads_all_land_and_bad_files_are_kept() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-store-{}", Uuid::new_v4()));
+        let path = dir.join("counts.json");
+
+        let threads: Vec<_> = (0..8)
+            .map(|index| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    update_json_store(&path, |counts: &mut Counts| {
+                        counts.insert(format!("t{index}"), index);
+                    })
+                    .expect("update");
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().expect("join");
+        }
+        let counts: Counts = read_json_store(&path).expect("read");
+        assert_eq!(counts.len(), 8);
+
+        std::fs::write(&path, "{\"t0\": ").expect("truncate");
+        assert!(read_json_store::<Counts>(&path).is_err());
+        assert!(update_json_store(&path, |counts: &mut Counts| counts.clear()).is_err());
+        assert_eq!(std::fs::read_to_string(&path).expect("read"), "{\"t0\": ");
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
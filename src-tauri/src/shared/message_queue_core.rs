@@ -0,0 +1,181 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use serde_json::Value;
+
+/// A message `send_user_message` held back behind a running turn, with its
+/// `turn/start` params already built.
+#[derive(Debug, Clone)]
+pub(crate) struct QueuedMessage {
+    pub(crate) params: Value,
+    pub(crate) text: String,
+    pub(crate) attachment_count: usize,
+    pub(crate) model: Option<String>,
+    /// The workspace's `autoCompactAtPct` when the message was queued.
+    pub(crate) compact_at_pct: Option<u8>,
+}
+
+/// Per-session turn state for queued messages. A thread is busy from the
+/// moment a turn is requested until its `turn/completed`, and while a
+/// queued send is in progress for it, auto-compaction included.
+#[derive(Debug, Default)]
+pub(crate) struct MessageQueue {
+    active_turns: HashSet<String>,
+    sending: HashSet<String>,
+    queued: HashMap<String, VecDeque<QueuedMessage>>,
+}
+
+impl MessageQueue {
+    /// Follows the thread's turn events. Returns true when a turn just
+    /// completed and the thread has messages waiting.
+    pub(crate) fn record_event(&mut self, thread_id: &str, method: &str) -> bool {
+        match method {
+            "turn/started" => {
+                self.active_turns.insert(thread_id.to_string());
+                false
+            }
+            "turn/completed" => {
+                self.active_turns.remove(thread_id);
+                self.queued_len(thread_id) > 0
+            }
+            _ => false,
+        }
+    }
+
+    pub(crate) fn is_busy(&self, thread_id: &str) -> bool {
+        self.active_turns.contains(thread_id)
+            || self.sending.contains(thread_id)
+            || self.queued_len(thread_id) > 0
+    }
+
+    pub(crate) fn queued_len(&self, thread_id: &str) -> usize {
+        self.queued.get(thread_id).map(VecDeque::len).unwrap_or(0)
+    }
+
+    /// Returns the thread's queue length including the new message.
+    pub(crate) fn enqueue(&mut self, thread_id: &str, message: QueuedMessage) -> usize {
+        let queue = self.queued.entry(thread_id.to_string()).or_default();
+        queue.push_back(message);
+        queue.len()
+    }
+
+    /// Marks a turn as requested, before `turn/started` arrives, so a
+    /// message sent right after it is queued rather than interleaved.
+    pub(crate) fn mark_turn_requested(&mut self, thread_id: &str) {
+        self.active_turns.insert(thread_id.to_string());
+    }
+
+    pub(crate) fn mark_turn_failed(&mut self, thread_id: &str) {
+        self.active_turns.remove(thread_id);
+    }
+
+    /// Claims an idle thread for a send, so the turn events of an
+    /// auto-compaction don't let a queued message slip in first.
+    pub(crate) fn begin_send(&mut self, thread_id: &str) {
+        self.sending.insert(thread_id.to_string());
+    }
+
+    /// Takes the oldest message when the thread is idle and nothing else is
+    /// sending for it, along with how many are left behind it.
+    pub(crate) fn begin_dispatch(&mut self, thread_id: &str) -> Option<(QueuedMessage, usize)> {
+        if self.active_turns.contains(thread_id) || self.sending.contains(thread_id) {
+            return None;
+        }
+        let queue = self.queued.get_mut(thread_id)?;
+        let message = queue.pop_front()?;
+        let remaining = queue.len();
+        if remaining == 0 {
+            self.queued.remove(thread_id);
+        }
+        self.sending.insert(thread_id.to_string());
+        Some((message, remaining))
+    }
+
+    /// Releases the thread. Returns true when its turn already ended (or
+    /// never started) and messages are waiting, since no `turn/completed`
+    /// is coming to dispatch them.
+    pub(crate) fn end_send(&mut self, thread_id: &str) -> bool {
+        self.sending.remove(thread_id);
+        !self.active_turns.contains(thread_id) && self.queued_len(thread_id) > 0
+    }
+
+    /// Drops every waiting message. Returns the affected threads with how
+    /// many messages each lost.
+    pub(crate) fn clear(&mut self) -> Vec<(String, usize)> {
+        self.queued
+            .drain()
+            .map(|(thread_id, queue)| (thread_id, queue.len()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn message(text: &str) -> QueuedMessage {
+        QueuedMessage {
+            params: json!({ "threadId": "t1" }),
+            text: text.to_string(),
+            attachment_count: 0,
+            model: None,
+            compact_at_pct: None,
+        }
+    }
+
+    #[test]
+    fn queue_holds_messages_until_the_turn_completes() {
+        let mut queue = MessageQueue::default();
+        assert!(!queue.is_busy("t1"));
+        queue.mark_turn_requested("t1");
+        assert!(queue.is_busy("t1"));
+        assert_eq!(queue.enqueue("t1", message("first")), 1);
+        assert_eq!(queue.enqueue("t1", message("second")), 2);
+        assert!(queue.begin_dispatch("t1").is_none());
+
+        assert!(!queue.record_event("t1", "turn/started"));
+        assert!(queue.record_event("t1", "turn/completed"));
+        let (next, remaining) = queue.begin_dispatch("t1").expect("first message");
+        assert_eq!((next.text.as_str(), remaining), ("first", 1));
+        // A completion while sending (e.g. an auto-compaction) must not
+        // send the next message early.
+        assert!(queue.record_event("t1", "turn/completed"));
+        assert!(queue.begin_dispatch("t1").is_none());
+        queue.mark_turn_requested("t1");
+        assert!(!queue.end_send("t1"));
+        assert!(queue.begin_dispatch("t1").is_none());
+
+        assert!(queue.record_event("t1", "turn/completed"));
+        let (next, remaining) = queue.begin_dispatch("t1").expect("second message");
+        assert_eq!((next.text.as_str(), remaining), ("second", 0));
+        queue.mark_turn_failed("t1");
+        assert!(!queue.end_send("t1"));
+        assert!(!queue.is_busy("t1"));
+    }
+
+    #[test]
+    fn end_send_reports_messages_left_behind_a_finished_turn() {
+        let mut queue = MessageQueue::default();
+        queue.begin_send("t1");
+        queue.mark_turn_requested("t1");
+        assert!(queue.is_busy("t1"));
+        queue.enqueue("t1", message("follow-up"));
+        // The turn finished before the send was released.
+        assert!(queue.record_event("t1", "turn/completed"));
+        assert!(queue.begin_dispatch("t1").is_none());
+        assert!(queue.end_send("t1"));
+        assert!(queue.begin_dispatch("t1").is_some());
+    }
+
+    #[test]
+    fn clear_reports_dropped_messages_per_thread() {
+        let mut queue = MessageQueue::default();
+        queue.enqueue("t1", message("a"));
+        queue.enqueue("t1", message("b"));
+        queue.enqueue("t2", message("c"));
+        let mut cleared = queue.clear();
+        cleared.sort();
+        assert_eq!(cleared, vec![("t1".to_string(), 2), ("t2".to_string(), 1)]);
+        assert_eq!(queue.queued_len("t1"), 0);
+    }
+}
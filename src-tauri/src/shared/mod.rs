@@ -1,14 +1,41 @@
 pub(crate) mod account;
+pub(crate) mod auto_compact_core;
+pub(crate) mod auto_fetch_core;
+pub(crate) mod catalog_cache_core;
 pub(crate) mod codex_aux_core;
 pub(crate) mod codex_update_core;
 pub(crate) mod codex_core;
+pub(crate) mod concurrency_core;
+pub(crate) mod credentials_core;
+pub(crate) mod event_batch_core;
 pub(crate) mod files_core;
 pub(crate) mod git_core;
 pub(crate) mod git_ui_core;
+pub(crate) mod http_client_core;
+pub(crate) mod inventory_core;
+pub(crate) mod json_store_core;
 pub(crate) mod local_usage_core;
+pub(crate) mod message_queue_core;
+pub(crate) mod network_core;
+pub(crate) mod operation_timing_core;
+pub(crate) mod operations_core;
 pub(crate) mod orbit_core;
+pub(crate) mod orphan_process_core;
+pub(crate) mod pinned_messages_core;
 pub(crate) mod process_core;
+pub(crate) mod profiles_core;
 pub(crate) mod prompts_core;
+pub(crate) mod redaction_core;
+pub(crate) mod session_recovery_core;
+pub(crate) mod session_stats_core;
 pub(crate) mod settings_core;
+pub(crate) mod thread_analysis_core;
+pub(crate) mod thread_export_core;
+pub(crate) mod thread_search_core;
+pub(crate) mod thread_titles_core;
+pub(crate) mod token_estimate_core;
+pub(crate) mod turn_trace_core;
+pub(crate) mod workspace_bundle_core;
+pub(crate) mod workspace_groups_core;
 pub(crate) mod workspaces_core;
 pub(crate) mod worktree_core;
@@ -0,0 +1,164 @@
+use std::net::UdpSocket;
+use std::sync::{Mutex as StdMutex, OnceLock};
+use std::time::Duration;
+
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+use crate::backend::events::EventSink;
+use crate::shared::{http_client_core, inventory_core};
+use crate::types::NetworkStatus;
+use crate::utils::now_unix_ms;
+
+const PROBE_TARGETS: &[&str] = &["1.1.1.1:443", "8.8.8.8:443", "github.com:443"];
+const ROUTE_HINT_TARGETS: &[(&str, &str)] = &[
+    ("0.0.0.0:0", "1.1.1.1:53"),
+    ("[::]:0", "[2606:4700:4700::1111]:53"),
+];
+const PROXY_PROBE_URL: &str = "https://github.com";
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+const ONLINE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+const OFFLINE_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+static NETWORK_STATUS: OnceLock<StdMutex<NetworkStatus>> = OnceLock::new();
+
+fn status_cell() -> &'static StdMutex<NetworkStatus> {
+    // Assume online until the first probe says otherwise so startup work
+    // is not held back.
+    NETWORK_STATUS.get_or_init(|| {
+        StdMutex::new(NetworkStatus {
+            online: true,
+            checked_at_ms: None,
+            changed_at_ms: None,
+        })
+    })
+}
+
+/// Records a probe result. Returns true when the online state flipped.
+pub(crate) fn apply_probe_result(status: &mut NetworkStatus, online: bool, now_ms: i64) -> bool {
+    status.checked_at_ms = Some(now_ms);
+    if status.online == online {
+        return false;
+    }
+    status.online = online;
+    status.changed_at_ms = Some(now_ms);
+    true
+}
+
+pub(crate) fn is_online() -> bool {
+    status_cell()
+        .lock()
+        .map(|status| status.online)
+        .unwrap_or(true)
+}
+
+pub(crate) fn get_network_status_core() -> NetworkStatus {
+    status_cell()
+        .lock()
+        .map(|status| status.clone())
+        .unwrap_or(NetworkStatus {
+            online: true,
+            checked_at_ms: None,
+            changed_at_ms: None,
+        })
+}
+
+/// Asks the OS for a route to the public internet. Connecting a UDP socket
+/// sends nothing; it fails immediately when no interface has a route, which
+/// lets a dropped link be reported without waiting for probe timeouts.
+fn os_has_route() -> bool {
+    ROUTE_HINT_TARGETS.iter().any(|(bind, target)| {
+        UdpSocket::bind(bind)
+            .and_then(|socket| socket.connect(target))
+            .is_ok()
+    })
+}
+
+/// With a proxy configured, direct connections may be blocked on purpose,
+/// so reachability is judged by whether the proxy relays a request. Any
+/// HTTP response counts; only transport failures mean offline.
+async fn probe_through_proxy() -> bool {
+    let client = http_client_core::http_client_builder_for(PROXY_PROBE_URL).and_then(|builder| {
+        builder
+            .timeout(PROBE_TIMEOUT)
+            .build()
+            .map_err(|err| format!("Failed to create HTTP client: {err}"))
+    });
+    // A proxy we cannot build a client for says nothing about the network;
+    // leave the status as online rather than pausing background work.
+    let Ok(client) = client else {
+        return true;
+    };
+    client.head(PROXY_PROBE_URL).send().await.is_ok()
+}
+
+/// A plain TCP connect is enough to tell whether packets leave the machine,
+/// and avoids depending on any single service answering HTTP.
+async fn probe_direct() -> bool {
+    if !os_has_route() {
+        return false;
+    }
+    for target in PROBE_TARGETS {
+        if let Ok(Ok(_)) = timeout(PROBE_TIMEOUT, TcpStream::connect(*target)).await {
+            return true;
+        }
+    }
+    false
+}
+
+async fn probe_connectivity() -> bool {
+    let proxy = http_client_core::current_proxy_config();
+    if proxy.http.is_some() || proxy.https.is_some() {
+        probe_through_proxy().await
+    } else {
+        probe_direct().await
+    }
+}
+
+/// Runs for the lifetime of the process and emits `network-status-changed`
+/// whenever reachability flips. Checks more often while offline so recovery
+/// is noticed quickly.
+pub(crate) async fn run_network_monitor<E: EventSink>(event_sink: E) {
+    inventory_core::scheduler_started("networkMonitor");
+    loop {
+        inventory_core::scheduler_ticked("networkMonitor");
+        let online = probe_connectivity().await;
+        let changed = status_cell().lock().ok().and_then(|mut status| {
+            apply_probe_result(&mut status, online, now_unix_ms()).then(|| status.clone())
+        });
+        if let Some(status) = changed {
+            event_sink.emit_network_status_event(status);
+        }
+        let interval = if online {
+            ONLINE_CHECK_INTERVAL
+        } else {
+            OFFLINE_CHECK_INTERVAL
+        };
+        tokio::time::sleep(interval).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_probe_result_only_reports_transitions() {
+        let mut status = NetworkStatus {
+            online: true,
+            checked_at_ms: None,
+            changed_at_ms: None,
+        };
+        assert!(!apply_probe_result(&mut status, true, 10));
+        assert_eq!(status.checked_at_ms, Some(10));
+        assert_eq!(status.changed_at_ms, None);
+
+        assert!(apply_probe_result(&mut status, false, 20));
+        assert!(!status.online);
+        assert_eq!(status.changed_at_ms, Some(20));
+
+        assert!(!apply_probe_result(&mut status, false, 30));
+        assert!(apply_probe_result(&mut status, true, 40));
+        assert_eq!(status.changed_at_ms, Some(40));
+    }
+}
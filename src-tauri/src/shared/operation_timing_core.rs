@@ -0,0 +1,197 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::shared::json_store_core::{read_json_store, update_json_store};
+use crate::types::OperationTimeEstimate;
+
+const TIMINGS_FILE_NAME: &str = "operation_timings.json";
+const MAX_SAMPLES_PER_KIND: usize = 50;
+const BYTES_PER_MB: f64 = 1024.0 * 1024.0;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OperationTimingSample {
+    pub(crate) kind: String,
+    #[serde(default)]
+    pub(crate) size_bytes: u64,
+    pub(crate) duration_ms: u64,
+}
+
+fn timings_path(storage_path: &Path) -> PathBuf {
+    storage_path.with_file_name(TIMINGS_FILE_NAME)
+}
+
+/// Sums pack files under `.git/objects/pack`; cheap enough to run per operation.
+pub(crate) fn repo_size_hint_bytes(repo_root: &Path) -> u64 {
+    let git_dir = repo_root.join(".git");
+    let pack_dir = if git_dir.is_dir() {
+        git_dir.join("objects").join("pack")
+    } else {
+        repo_root.join("objects").join("pack")
+    };
+    let Ok(entries) = std::fs::read_dir(pack_dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+pub(crate) fn record_operation_timing(
+    storage_path: &Path,
+    kind: &str,
+    size_bytes: u64,
+    duration: Duration,
+) {
+    let recorded = update_json_store(
+        &timings_path(storage_path),
+        |samples: &mut Vec<OperationTimingSample>| {
+            samples.push(OperationTimingSample {
+                kind: kind.to_string(),
+                size_bytes,
+                duration_ms: duration.as_millis() as u64,
+            });
+            let kind_count = samples.iter().filter(|sample| sample.kind == kind).count();
+            let mut excess = kind_count.saturating_sub(MAX_SAMPLES_PER_KIND);
+            samples.retain(|sample| {
+                if excess > 0 && sample.kind == kind {
+                    excess -= 1;
+                    return false;
+                }
+                true
+            });
+        },
+    );
+    if let Err(error) = recorded {
+        eprintln!("Failed to record {kind} timing: {error}");
+    }
+}
+
+fn heuristic_estimate_ms(kind: &str, size_bytes: Option<u64>) -> u64 {
+    let (base_ms, ms_per_mb) = match kind {
+        "clone" => (1500.0, 120.0),
+        "fetch" | "pull" => (800.0, 40.0),
+        _ => (1000.0, 60.0),
+    };
+    let size_mb = size_bytes.unwrap_or(0) as f64 / BYTES_PER_MB;
+    (base_ms + size_mb * ms_per_mb).round() as u64
+}
+
+pub(crate) fn estimate_from_samples(
+    kind: &str,
+    size_bytes: Option<u64>,
+    samples: &[OperationTimingSample],
+) -> OperationTimeEstimate {
+    let matching: Vec<&OperationTimingSample> = samples
+        .iter()
+        .filter(|sample| sample.kind == kind)
+        .collect();
+    if matching.is_empty() {
+        return OperationTimeEstimate {
+            kind: kind.to_string(),
+            estimated_ms: heuristic_estimate_ms(kind, size_bytes),
+            sample_count: 0,
+            source: "heuristic".to_string(),
+        };
+    }
+
+    let sized: Vec<&&OperationTimingSample> = matching
+        .iter()
+        .filter(|sample| sample.size_bytes > 0)
+        .collect();
+    let estimated_ms = match size_bytes.filter(|size| *size > 0) {
+        Some(size) if !sized.is_empty() => {
+            let total_ms: u64 = sized.iter().map(|sample| sample.duration_ms).sum();
+            let total_bytes: u64 = sized.iter().map(|sample| sample.size_bytes).sum();
+            (total_ms as f64 / total_bytes as f64 * size as f64).round() as u64
+        }
+        _ => {
+            let mut durations: Vec<u64> =
+                matching.iter().map(|sample| sample.duration_ms).collect();
+            durations.sort_unstable();
+            durations[durations.len() / 2]
+        }
+    };
+
+    OperationTimeEstimate {
+        kind: kind.to_string(),
+        estimated_ms,
+        sample_count: matching.len(),
+        source: "history".to_string(),
+    }
+}
+
+pub(crate) fn estimate_operation_time_core(
+    storage_path: &Path,
+    kind: String,
+    repo_size_hint: Option<u64>,
+) -> Result<OperationTimeEstimate, String> {
+    let kind = kind.trim().to_lowercase();
+    if kind.is_empty() {
+        return Err("Operation kind is required.".to_string());
+    }
+    let samples: Vec<OperationTimingSample> = read_json_store(&timings_path(storage_path))?;
+    Ok(estimate_from_samples(&kind, repo_size_hint, &samples))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(kind: &str, size_bytes: u64, duration_ms: u64) -> OperationTimingSample {
+        OperationTimingSample {
+            kind: kind.to_string(),
+            size_bytes,
+            duration_ms,
+        }
+    }
+
+    #[test]
+    fn estimate_falls_back_to_heuristic_without_history() {
+        let estimate = estimate_from_samples("clone", Some(10 * 1024 * 1024), &[]);
+        assert_eq!(estimate.source, "heuristic");
+        assert_eq!(estimate.sample_count, 0);
+        assert_eq!(estimate.estimated_ms, 2700);
+    }
+
+    #[test]
+    fn estimate_scales_history_by_size_hint() {
+        let samples = vec![
+            sample("clone", 1000, 2000),
+            sample("clone", 3000, 6000),
+            sample("fetch", 1000, 50),
+        ];
+        let estimate = estimate_from_samples("clone", Some(2000), &samples);
+        assert_eq!(estimate.source, "history");
+        assert_eq!(estimate.sample_count, 2);
+        assert_eq!(estimate.estimated_ms, 4000);
+    }
+
+    #[test]
+    fn record_operation_timing_caps_samples_per_kind() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", uuid::Uuid::new_v4()));
+        let storage_path = dir.join("workspaces.json");
+        for index in 0..(MAX_SAMPLES_PER_KIND + 5) {
+            record_operation_timing(
+                &storage_path,
+                "fetch",
+                0,
+                Duration::from_millis(index as u64),
+            );
+        }
+        let samples: Vec<OperationTimingSample> =
+            read_json_store(&timings_path(&storage_path)).expect("samples");
+        assert_eq!(samples.len(), MAX_SAMPLES_PER_KIND);
+        assert_eq!(samples[0].duration_ms, 5);
+
+        std::fs::write(timings_path(&storage_path), "[{\"kind\"").expect("truncate");
+        record_operation_timing(&storage_path, "fetch", 0, Duration::from_millis(1));
+        assert!(estimate_operation_time_core(&storage_path, "fetch".into(), None).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
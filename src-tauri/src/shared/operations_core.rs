@@ -0,0 +1,318 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::pin;
+use std::sync::{Arc, Mutex as StdMutex};
+
+use futures_util::future::{select, Either};
+use tokio::sync::watch;
+use uuid::Uuid;
+
+use crate::backend::events::{EventSink, OperationEvent};
+use crate::types::OperationInfo;
+use crate::utils::now_unix_ms;
+
+const CANCELLED_MESSAGE: &str = "Operation cancelled.";
+
+struct OperationEntry {
+    info: OperationInfo,
+    cancel: watch::Sender<bool>,
+}
+
+/// Tracks long-running operations (clone, fetch, pull, search) so any client
+/// can list them and cancel them by id.
+#[derive(Clone)]
+pub(crate) struct OperationRegistry<E: EventSink> {
+    entries: Arc<StdMutex<HashMap<String, OperationEntry>>>,
+    event_sink: E,
+}
+
+pub(crate) struct OperationToken<E: EventSink> {
+    id: String,
+    registry: OperationRegistry<E>,
+    cancel: watch::Receiver<bool>,
+}
+
+/// Marks the operation cancelled if `run` is dropped before it finishes, so
+/// an abandoned request or an outer timeout does not leave a phantom entry.
+struct FinishGuard<'a, E: EventSink> {
+    registry: &'a OperationRegistry<E>,
+    id: Option<String>,
+}
+
+impl<E: EventSink> FinishGuard<'_, E> {
+    fn finish(mut self, status: &str, error: Option<String>) {
+        if let Some(id) = self.id.take() {
+            self.registry.finish(&id, status, error);
+        }
+    }
+}
+
+impl<E: EventSink> Drop for FinishGuard<'_, E> {
+    fn drop(&mut self) {
+        if let Some(id) = self.id.take() {
+            self.registry.finish(&id, "cancelled", None);
+        }
+    }
+}
+
+impl<E: EventSink> OperationRegistry<E> {
+    pub(crate) fn new(event_sink: E) -> Self {
+        Self {
+            entries: Arc::new(StdMutex::new(HashMap::new())),
+            event_sink,
+        }
+    }
+
+    fn emit(&self, info: &OperationInfo, status: &str, error: Option<String>) {
+        self.event_sink.emit_operation_event(OperationEvent {
+            operation_id: info.id.clone(),
+            kind: info.kind.clone(),
+            workspace_id: info.workspace_id.clone(),
+            status: status.to_string(),
+            progress: info.progress,
+            message: info.message.clone(),
+            error,
+        });
+    }
+
+    pub(crate) fn start(
+        &self,
+        kind: &str,
+        workspace_id: Option<String>,
+        operation_id: Option<String>,
+    ) -> Result<OperationToken<E>, String> {
+        let id = operation_id
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        let (cancel_tx, cancel_rx) = watch::channel(false);
+        let info = OperationInfo {
+            id: id.clone(),
+            kind: kind.to_string(),
+            workspace_id,
+            started_at_ms: now_unix_ms(),
+            progress: None,
+            message: None,
+        };
+        {
+            let mut entries = self
+                .entries
+                .lock()
+                .map_err(|_| "operations lock poisoned")?;
+            if entries.contains_key(&id) {
+                return Err(format!("Operation `{id}` is already running."));
+            }
+            entries.insert(
+                id.clone(),
+                OperationEntry {
+                    info: info.clone(),
+                    cancel: cancel_tx,
+                },
+            );
+        }
+        self.emit(&info, "started", None);
+        Ok(OperationToken {
+            id,
+            registry: self.clone(),
+            cancel: cancel_rx,
+        })
+    }
+
+    /// Signals cancellation; returns false when no operation has that id.
+    pub(crate) fn cancel(&self, operation_id: &str) -> bool {
+        let Ok(entries) = self.entries.lock() else {
+            return false;
+        };
+        match entries.get(operation_id) {
+            Some(entry) => {
+                let _ = entry.cancel.send(true);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn list(&self) -> Vec<OperationInfo> {
+        let Ok(entries) = self.entries.lock() else {
+            return Vec::new();
+        };
+        let mut list: Vec<OperationInfo> =
+            entries.values().map(|entry| entry.info.clone()).collect();
+        list.sort_by_key(|info| info.started_at_ms);
+        list
+    }
+
+    fn finish(&self, operation_id: &str, status: &str, error: Option<String>) {
+        let removed = self
+            .entries
+            .lock()
+            .ok()
+            .and_then(|mut entries| entries.remove(operation_id));
+        if let Some(entry) = removed {
+            self.emit(&entry.info, status, error);
+        }
+    }
+
+    /// Runs `operation` under a registered id. Cancelling drops the future,
+    /// which kills any child spawned with `kill_on_drop`.
+    pub(crate) async fn run<T, F, Fut>(
+        &self,
+        kind: &str,
+        workspace_id: Option<String>,
+        operation_id: Option<String>,
+        operation: F,
+    ) -> Result<T, String>
+    where
+        F: FnOnce(OperationToken<E>) -> Fut,
+        Fut: Future<Output = Result<T, String>>,
+    {
+        let token = self.start(kind, workspace_id, operation_id)?;
+        let guard = FinishGuard {
+            registry: self,
+            id: Some(token.id.clone()),
+        };
+        let mut cancel = token.cancel.clone();
+        let operation = pin!(operation(token));
+        let cancelled = pin!(async move {
+            let _ = cancel.wait_for(|cancelled| *cancelled).await;
+        });
+        let result = match select(operation, cancelled).await {
+            Either::Left((result, _)) => Some(result),
+            Either::Right(_) => None,
+        };
+        match result {
+            Some(Ok(value)) => {
+                guard.finish("done", None);
+                Ok(value)
+            }
+            Some(Err(error)) => {
+                guard.finish("failed", Some(error.clone()));
+                Err(error)
+            }
+            None => {
+                guard.finish("cancelled", None);
+                Err(CANCELLED_MESSAGE.to_string())
+            }
+        }
+    }
+}
+
+impl<E: EventSink> OperationToken<E> {
+    pub(crate) fn report_progress(&self, progress: Option<f64>, message: Option<String>) {
+        let info = {
+            let Ok(mut entries) = self.registry.entries.lock() else {
+                return;
+            };
+            let Some(entry) = entries.get_mut(&self.id) else {
+                return;
+            };
+            entry.info.progress = progress;
+            entry.info.message = message;
+            entry.info.clone()
+        };
+        self.registry.emit(&info, "progress", None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::events::{
+        AppServerEvent, CloneProgressEvent, GitSyncProgressEvent, MessageQueuedEvent,
+        ProfileLoginEvent, RemoteUpdatesEvent, SessionCrashedEvent, TerminalExit, TerminalOutput,
+    };
+    use crate::types::NetworkStatus;
+
+    #[derive(Clone, Default)]
+    struct RecordingSink {
+        statuses: Arc<StdMutex<Vec<String>>>,
+    }
+
+    impl EventSink for RecordingSink {
+        fn emit_app_server_event(&self, _event: AppServerEvent) {}
+        fn emit_terminal_output(&self, _event: TerminalOutput) {}
+        fn emit_terminal_exit(&self, _event: TerminalExit) {}
+        fn emit_operation_event(&self, event: OperationEvent) {
+            self.statuses.lock().unwrap().push(event.status);
+        }
+        fn emit_remote_updates_event(&self, _event: RemoteUpdatesEvent) {}
+        fn emit_network_status_event(&self, _event: NetworkStatus) {}
+        fn emit_clone_progress_event(&self, _event: CloneProgressEvent) {}
+        fn emit_git_sync_progress_event(&self, _event: GitSyncProgressEvent) {}
+        fn emit_profile_login_event(&self, _event: ProfileLoginEvent) {}
+        fn emit_message_queued_event(&self, _event: MessageQueuedEvent) {}
+        fn emit_session_crashed_event(&self, _event: SessionCrashedEvent) {}
+    }
+
+    fn run_async_test<F: Future<Output = ()>>(future: F) {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime")
+            .block_on(future);
+    }
+
+    #[test]
+    fn run_reports_started_and_done() {
+        run_async_test(async {
+            let sink = RecordingSink::default();
+            let registry = OperationRegistry::new(sink.clone());
+            let value = registry
+                .run(
+                    "fetch",
+                    None,
+                    Some("op-1".to_string()),
+                    |token| async move {
+                        token.report_progress(Some(0.5), None);
+                        Ok::<_, String>(7)
+                    },
+                )
+                .await
+                .expect("operation result");
+            assert_eq!(value, 7);
+            assert!(registry.list().is_empty());
+            assert_eq!(
+                *sink.statuses.lock().unwrap(),
+                vec!["started", "progress", "done"]
+            );
+        });
+    }
+
+    #[test]
+    fn cancel_stops_a_running_operation() {
+        run_async_test(async {
+            let sink = RecordingSink::default();
+            let registry = OperationRegistry::new(sink.clone());
+            let canceller = registry.clone();
+            let run = registry.run("clone", None, Some("op-2".to_string()), |_token| async {
+                std::future::pending::<Result<(), String>>().await
+            });
+            let cancel = async {
+                tokio::task::yield_now().await;
+                let running = canceller.list();
+                assert_eq!(running.len(), 1);
+                assert_eq!(running[0].kind, "clone");
+                assert!(canceller.cancel("op-2"));
+            };
+            let (result, _) = futures_util::future::join(run, cancel).await;
+            assert_eq!(result, Err(CANCELLED_MESSAGE.to_string()));
+            assert!(!registry.cancel("op-2"));
+            assert_eq!(*sink.statuses.lock().unwrap(), vec!["started", "cancelled"]);
+        });
+    }
+
+    #[test]
+    fn dropping_the_caller_finishes_the_operation() {
+        run_async_test(async {
+            let sink = RecordingSink::default();
+            let registry = OperationRegistry::new(sink.clone());
+            let run = registry.run("fetch", None, Some("op-3".to_string()), |_token| async {
+                std::future::pending::<Result<(), String>>().await
+            });
+            let timed_out = tokio::time::timeout(std::time::Duration::from_millis(10), run).await;
+            assert!(timed_out.is_err());
+            assert!(registry.list().is_empty());
+            assert_eq!(*sink.statuses.lock().unwrap(), vec!["started", "cancelled"]);
+        });
+    }
+}
@@ -4,6 +4,7 @@ use std::time::{Duration, Instant};
 use serde_json::{json, Value};
 use tokio_tungstenite::connect_async;
 
+use crate::shared::http_client_core;
 use crate::types::{
     AppSettings, OrbitConnectTestResult, OrbitDeviceCodeStart, OrbitSignInPollResult,
     OrbitSignInStatus,
@@ -13,8 +14,8 @@ const DEFAULT_DEVICE_POLL_INTERVAL_SECONDS: u32 = 5;
 const DEFAULT_DEVICE_EXPIRES_SECONDS: u32 = 600;
 const MAX_ERROR_BODY_BYTES: usize = 400;
 
-fn reqwest_client() -> Result<reqwest::Client, String> {
-    reqwest::Client::builder()
+fn reqwest_client(endpoint: &str) -> Result<reqwest::Client, String> {
+    http_client_core::http_client_builder_for(endpoint)?
         .timeout(Duration::from_secs(20))
         .build()
         .map_err(|err| format!("Failed to create HTTP client: {err}"))
@@ -202,7 +203,7 @@ pub(crate) async fn orbit_sign_in_start_core(
     let auth_base = normalize_auth_base_url(auth_url)?;
     let endpoint = auth_endpoint(&auth_base, "/auth/device/code");
 
-    let client = reqwest_client()?;
+    let client = reqwest_client(&endpoint)?;
     let requested_name = runner_name
         .map(str::trim)
         .filter(|value| !value.is_empty())
@@ -279,7 +280,7 @@ pub(crate) async fn orbit_sign_in_poll_core(
         return Err("Device code is required.".to_string());
     }
 
-    let client = reqwest_client()?;
+    let client = reqwest_client(&endpoint)?;
     let response = client
         .post(&endpoint)
         .json(&json!({
@@ -372,7 +373,7 @@ pub(crate) async fn orbit_sign_out_core(auth_url: &str, token: &str) -> Result<(
         return Ok(());
     }
 
-    let client = reqwest_client()?;
+    let client = reqwest_client(&endpoint)?;
     let response = client
         .post(&endpoint)
         .bearer_auth(token)
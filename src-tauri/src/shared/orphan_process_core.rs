@@ -0,0 +1,281 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+
+use crate::backend::app_server::WorkspaceSession;
+use crate::shared::process_core::tokio_command;
+use crate::types::{OrphanedCodexProcess, WorkspaceEntry};
+
+#[derive(Debug, Clone, PartialEq)]
+struct ProcessRow {
+    pid: u32,
+    ppid: u32,
+    command: String,
+}
+
+fn parse_ps_output(output: &str) -> Vec<ProcessRow> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let pid = parts.next()?.parse().ok()?;
+            let ppid = parts.next()?.parse().ok()?;
+            let command = parts.collect::<Vec<_>>().join(" ");
+            if command.is_empty() {
+                return None;
+            }
+            Some(ProcessRow { pid, ppid, command })
+        })
+        .collect()
+}
+
+/// We only ever spawn `codex app-server`, so an interactive `codex` the user
+/// started in a terminal is never treated as an orphan.
+fn is_codex_app_server_command(command: &str) -> bool {
+    let is_codex = command.split_whitespace().take(3).any(|token| {
+        let name = Path::new(token)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or(token);
+        matches!(name, "codex" | "codex.exe" | "codex.js")
+    });
+    is_codex
+        && command
+            .split_whitespace()
+            .any(|token| token == "app-server")
+}
+
+fn command_name(command: &str) -> &str {
+    let program = command.split_whitespace().next().unwrap_or_default();
+    Path::new(program)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(program)
+}
+
+/// A process whose parent has exited is reparented to init, or on Linux to
+/// a subreaper such as `systemd --user`. One whose parent is still alive
+/// belongs to someone: another CodexMonitor, a CLI session or the daemon.
+fn is_orphaned(row: &ProcessRow, processes: &HashMap<u32, &ProcessRow>) -> bool {
+    if row.ppid <= 1 {
+        return true;
+    }
+    match processes.get(&row.ppid) {
+        None => true,
+        Some(parent) => matches!(
+            command_name(&parent.command),
+            "init" | "launchd" | "systemd"
+        ),
+    }
+}
+
+fn match_workspace<'a>(cwd: &Path, workspaces: &'a [WorkspaceEntry]) -> Option<&'a WorkspaceEntry> {
+    workspaces
+        .iter()
+        .filter(|entry| cwd.starts_with(&entry.path))
+        .max_by_key(|entry| entry.path.len())
+}
+
+#[cfg(not(windows))]
+async fn list_processes() -> Result<Vec<ProcessRow>, String> {
+    let output = tokio_command("ps")
+        .args(["-axo", "pid=,ppid=,command="])
+        .output()
+        .await
+        .map_err(|err| format!("Failed to list processes: {err}"))?;
+    if !output.status.success() {
+        return Err("Failed to list processes.".to_string());
+    }
+    Ok(parse_ps_output(&String::from_utf8_lossy(&output.stdout)))
+}
+
+#[cfg(windows)]
+async fn list_processes() -> Result<Vec<ProcessRow>, String> {
+    // Windows does not expose another process's working directory, so there
+    // is nothing to match against workspaces.
+    Err("Finding orphaned Codex processes is not supported on Windows.".to_string())
+}
+
+#[cfg(target_os = "linux")]
+async fn process_cwd(pid: u32) -> Option<String> {
+    std::fs::read_link(format!("/proc/{pid}/cwd"))
+        .ok()
+        .map(|path| path.to_string_lossy().to_string())
+}
+
+#[cfg(not(target_os = "linux"))]
+async fn process_cwd(pid: u32) -> Option<String> {
+    let output = tokio_command("lsof")
+        .args(["-a", "-p", &pid.to_string(), "-d", "cwd", "-Fn"])
+        .output()
+        .await
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.strip_prefix('n').map(|path| path.to_string()))
+}
+
+async fn owned_pids(sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>) -> HashSet<u32> {
+    let sessions: Vec<Arc<WorkspaceSession>> = sessions.lock().await.values().cloned().collect();
+    let mut pids = HashSet::new();
+    for session in sessions {
+        if let Some(pid) = session.child.lock().await.id() {
+            pids.insert(pid);
+        }
+    }
+    pids
+}
+
+pub(crate) async fn find_orphaned_codex_processes_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+) -> Result<Vec<OrphanedCodexProcess>, String> {
+    let known: Vec<WorkspaceEntry> = workspaces.lock().await.values().cloned().collect();
+    let owned = owned_pids(sessions).await;
+    let current_pid = std::process::id();
+
+    let rows = list_processes().await?;
+    let processes: HashMap<u32, &ProcessRow> = rows.iter().map(|row| (row.pid, row)).collect();
+    let mut orphans = Vec::new();
+    for row in &rows {
+        if row.pid == current_pid
+            || row.ppid == current_pid
+            || owned.contains(&row.pid)
+            || owned.contains(&row.ppid)
+            || !is_codex_app_server_command(&row.command)
+            || !is_orphaned(row, &processes)
+        {
+            continue;
+        }
+        let Some(cwd) = process_cwd(row.pid).await else {
+            continue;
+        };
+        let Some(entry) = match_workspace(Path::new(&cwd), &known) else {
+            continue;
+        };
+        orphans.push(OrphanedCodexProcess {
+            pid: row.pid,
+            command: row.command.clone(),
+            cwd,
+            workspace_id: entry.id.clone(),
+            workspace_name: entry.name.clone(),
+        });
+    }
+    Ok(orphans)
+}
+
+/// Kills the given pids, but only those that still look orphaned right now;
+/// the caller passes back the pids the user confirmed from the find result.
+pub(crate) async fn kill_orphaned_codex_processes_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    pids: Vec<u32>,
+) -> Result<Vec<u32>, String> {
+    if pids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let confirmed: HashSet<u32> = pids.into_iter().collect();
+    let mut killed = Vec::new();
+    for orphan in find_orphaned_codex_processes_core(workspaces, sessions).await? {
+        if !confirmed.contains(&orphan.pid) {
+            continue;
+        }
+        let status = tokio_command("kill")
+            .args(["-TERM", &orphan.pid.to_string()])
+            .status()
+            .await
+            .map_err(|err| format!("Failed to kill process {}: {err}", orphan.pid))?;
+        if status.success() {
+            killed.push(orphan.pid);
+        }
+    }
+    Ok(killed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{WorkspaceKind, WorkspaceSettings};
+
+    fn workspace(id: &str, path: &str) -> WorkspaceEntry {
+        WorkspaceEntry {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: path.to_string(),
+            codex_bin: None,
+            kind: WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings::default(),
+        }
+    }
+
+    #[test]
+    fn parse_ps_output_reads_pid_ppid_and_command() {
+        let rows = parse_ps_output("  12     1 /usr/local/bin/codex app-server\nbad line\n");
+        assert_eq!(
+            rows,
+            vec![ProcessRow {
+                pid: 12,
+                ppid: 1,
+                command: "/usr/local/bin/codex app-server".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn only_app_server_codex_processes_match() {
+        assert!(is_codex_app_server_command("/opt/bin/codex app-server"));
+        assert!(is_codex_app_server_command(
+            "node /usr/lib/node_modules/@openai/codex/bin/codex.js app-server"
+        ));
+        assert!(!is_codex_app_server_command("/opt/bin/codex"));
+        assert!(!is_codex_app_server_command(
+            "vim codex-notes.md app-server"
+        ));
+    }
+
+    #[test]
+    fn only_processes_whose_parent_is_gone_are_orphans() {
+        let row = |pid: u32, ppid: u32, command: &str| ProcessRow {
+            pid,
+            ppid,
+            command: command.to_string(),
+        };
+        let rows = vec![
+            row(1, 0, "/sbin/launchd"),
+            row(40, 1, "/lib/systemd/systemd --user"),
+            row(
+                50,
+                1,
+                "/usr/local/bin/codex-monitor-daemon --listen 127.0.0.1:4732",
+            ),
+            row(60, 1, "/bin/zsh -i"),
+            row(100, 1, "codex app-server"),
+            row(101, 40, "codex app-server"),
+            row(102, 999, "codex app-server"),
+            row(103, 50, "codex app-server"),
+            row(104, 60, "codex app-server"),
+        ];
+        let processes: HashMap<u32, &ProcessRow> = rows.iter().map(|row| (row.pid, row)).collect();
+        let orphaned: Vec<u32> = rows
+            .iter()
+            .filter(|row| row.command == "codex app-server" && is_orphaned(row, &processes))
+            .map(|row| row.pid)
+            .collect();
+        assert_eq!(orphaned, vec![100, 101, 102]);
+    }
+
+    #[test]
+    fn match_workspace_prefers_the_deepest_path() {
+        let known = vec![
+            workspace("repo", "/work/repo"),
+            workspace("nested", "/work/repo/packages/app"),
+        ];
+        let matched = match_workspace(Path::new("/work/repo/packages/app/src"), &known);
+        assert_eq!(matched.map(|entry| entry.id.as_str()), Some("nested"));
+        assert!(match_workspace(Path::new("/elsewhere"), &known).is_none());
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::shared::json_store_core::{read_json_store, update_json_store};
+use crate::types::PinnedMessage;
+use crate::utils::now_unix_ms;
+
+const PINS_FILE_NAME: &str = "pinned_messages.json";
+
+type PinStore = HashMap<String, Vec<PinnedMessage>>;
+
+fn pins_path(storage_path: &Path) -> PathBuf {
+    storage_path.with_file_name(PINS_FILE_NAME)
+}
+
+fn required(value: String, label: &str) -> Result<String, String> {
+    let value = value.trim().to_string();
+    if value.is_empty() {
+        return Err(format!("{label} is required."));
+    }
+    Ok(value)
+}
+
+/// Pinning an already pinned message is a no-op. Returns the thread's pins
+/// in the order they were added.
+pub(crate) fn pin_thread_message_core(
+    storage_path: &Path,
+    workspace_id: String,
+    thread_id: String,
+    message_id: String,
+) -> Result<Vec<PinnedMessage>, String> {
+    let thread_id = required(thread_id, "Thread id")?;
+    let message_id = required(message_id, "Message id")?;
+    update_json_store(&pins_path(storage_path), |pins: &mut PinStore| {
+        let thread_pins = pins.entry(thread_id.clone()).or_default();
+        if !thread_pins.iter().any(|pin| pin.message_id == message_id) {
+            thread_pins.push(PinnedMessage {
+                workspace_id,
+                thread_id,
+                message_id,
+                pinned_at_ms: now_unix_ms(),
+            });
+        }
+        thread_pins.clone()
+    })
+}
+
+pub(crate) fn unpin_thread_message_core(
+    storage_path: &Path,
+    thread_id: String,
+    message_id: String,
+) -> Result<Vec<PinnedMessage>, String> {
+    let thread_id = required(thread_id, "Thread id")?;
+    update_json_store(&pins_path(storage_path), |pins: &mut PinStore| {
+        let Some(thread_pins) = pins.get_mut(&thread_id) else {
+            return Vec::new();
+        };
+        thread_pins.retain(|pin| pin.message_id != message_id.trim());
+        let result = thread_pins.clone();
+        if result.is_empty() {
+            pins.remove(&thread_id);
+        }
+        result
+    })
+}
+
+pub(crate) fn list_pinned_messages_core(
+    storage_path: &Path,
+    thread_id: &str,
+) -> Result<Vec<PinnedMessage>, String> {
+    let mut pins: PinStore = read_json_store(&pins_path(storage_path))?;
+    Ok(pins.remove(thread_id.trim()).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn pins_persist_per_thread_and_dedupe() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-pins-{}", Uuid::new_v4()));
+        let storage_path = dir.join("workspaces.json");
+
+        pin_thread_message_core(&storage_path, "ws".into(), "t1".into(), "m1".into())
+            .expect("pin m1");
+        pin_thread_message_core(&storage_path, "ws".into(), "t1".into(), "m2".into())
+            .expect("pin m2");
+        let pins = pin_thread_message_core(&storage_path, "ws".into(), "t1".into(), "m1".into())
+            .expect("pin m1 again");
+        assert_eq!(
+            pins.iter()
+                .map(|pin| pin.message_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["m1", "m2"]
+        );
+        assert!(list_pinned_messages_core(&storage_path, "t2")
+            .expect("list t2")
+            .is_empty());
+
+        let remaining =
+            unpin_thread_message_core(&storage_path, "t1".into(), "m1".into()).expect("unpin m1");
+        assert_eq!(remaining.len(), 1);
+        let stored = list_pinned_messages_core(&storage_path, "t1").expect("list t1");
+        assert_eq!(stored[0].message_id, "m2");
+        assert_eq!(stored[0].workspace_id, "ws");
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -8,6 +8,8 @@ use std::process::Stdio;
 
 use tokio::process::{Child, Command};
 
+use crate::shared::http_client_core::apply_network_env;
+
 /// On Windows, spawning a console app from a GUI subsystem app will open a new
 /// console window unless we explicitly disable it.
 fn hide_console_on_windows(_command: &mut std::process::Command) {
@@ -22,12 +24,14 @@ fn hide_console_on_windows(_command: &mut std::process::Command) {
 pub(crate) fn tokio_command(program: impl AsRef<OsStr>) -> Command {
     let mut command = Command::new(program);
     hide_console_on_windows(command.as_std_mut());
+    apply_network_env(command.as_std_mut());
     command
 }
 
 pub(crate) fn std_command(program: impl AsRef<OsStr>) -> std::process::Command {
     let mut command = std::process::Command::new(program);
     hide_console_on_windows(&mut command);
+    apply_network_env(&mut command);
     command
 }
 
@@ -161,3 +165,19 @@ pub(crate) fn build_cmd_c_command(program: &Path, args: &[String]) -> Result<Str
     let inner = parts.join(" ");
     Ok(format!("\"{inner}\""))
 }
+
+/// Same quoting as [`build_cmd_c_command`], but runs the target through
+/// `start` so app names registered under App Paths resolve too. The empty
+/// `""` is the window title `start` would otherwise take from the target.
+#[cfg(target_os = "windows")]
+pub(crate) fn build_cmd_start_command(target: &str, args: &[String]) -> Result<String, String> {
+    let mut parts: Vec<String> = Vec::with_capacity(args.len() + 3);
+    parts.push("start".to_string());
+    parts.push("\"\"".to_string());
+    parts.push(quote_cmd_token(target)?);
+    for arg in args {
+        parts.push(quote_cmd_token(arg)?);
+    }
+    let inner = parts.join(" ");
+    Ok(format!("\"{inner}\""))
+}
@@ -0,0 +1,320 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::sync::{mpsc, Mutex};
+use tokio::time::timeout_at;
+use uuid::Uuid;
+
+use crate::backend::app_server::build_codex_command_with_bin;
+use crate::backend::events::{EventSink, ProfileLoginEvent};
+use crate::codex::home::normalize_codex_home;
+use crate::shared::process_core::kill_child_process_tree;
+use crate::shared::redaction_core;
+use crate::shared::settings_core::update_app_settings_core;
+use crate::types::{AppSettings, CodexProfile, WorkspaceEntry};
+
+/// Kept under the remote backend's request timeout so a forwarded login
+/// reports its own failure instead of a transport timeout.
+const PROFILE_LOGIN_TIMEOUT: Duration = Duration::from_secs(4 * 60);
+const PROFILE_LOGOUT_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub(crate) async fn list_profiles_core(app_settings: &Mutex<AppSettings>) -> Vec<CodexProfile> {
+    app_settings.lock().await.codex_profiles.clone()
+}
+
+/// Names are unique ignoring case; the home is stored as typed and expanded
+/// the same way as a workspace `codexHome` when a session starts.
+pub(crate) async fn create_profile_core(
+    name: String,
+    codex_home: String,
+    app_settings: &Mutex<AppSettings>,
+    settings_path: &PathBuf,
+) -> Result<CodexProfile, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Profile name is required.".to_string());
+    }
+    let codex_home = codex_home.trim().to_string();
+    if codex_home.is_empty() {
+        return Err("Profile codexHome is required.".to_string());
+    }
+    let mut next_settings = app_settings.lock().await.clone();
+    if next_settings
+        .codex_profiles
+        .iter()
+        .any(|profile| profile.name.eq_ignore_ascii_case(&name))
+    {
+        return Err(format!("A profile named `{name}` already exists."));
+    }
+    let profile = CodexProfile {
+        id: Uuid::new_v4().to_string(),
+        name,
+        codex_home,
+    };
+    next_settings.codex_profiles.push(profile.clone());
+    update_app_settings_core(next_settings, app_settings, settings_path).await?;
+    Ok(profile)
+}
+
+/// Refuses while a workspace still uses the profile, since those sessions
+/// would silently keep the deleted home.
+pub(crate) async fn delete_profile_core(
+    profile_id: String,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    settings_path: &PathBuf,
+) -> Result<(), String> {
+    let in_use = workspaces
+        .lock()
+        .await
+        .values()
+        .filter(|entry| entry.settings.profile_id.as_deref() == Some(profile_id.as_str()))
+        .count();
+    if in_use > 0 {
+        return Err(format!(
+            "Profile is used by {in_use} workspace(s). Switch them to another profile first."
+        ));
+    }
+    let mut next_settings = app_settings.lock().await.clone();
+    let before = next_settings.codex_profiles.len();
+    next_settings
+        .codex_profiles
+        .retain(|profile| profile.id != profile_id);
+    if next_settings.codex_profiles.len() == before {
+        return Err("profile not found".to_string());
+    }
+    update_app_settings_core(next_settings, app_settings, settings_path).await?;
+    Ok(())
+}
+
+pub(crate) async fn find_profile_core(
+    app_settings: &Mutex<AppSettings>,
+    profile_id: &str,
+) -> Result<CodexProfile, String> {
+    app_settings
+        .lock()
+        .await
+        .codex_profiles
+        .iter()
+        .find(|profile| profile.id == profile_id)
+        .cloned()
+        .ok_or_else(|| "profile not found".to_string())
+}
+
+fn strip_ansi(line: &str) -> String {
+    let mut output = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\u{1b}' {
+            // Skip a CSI sequence such as `\x1b[1;34m`.
+            for next in chars.by_ref() {
+                if next.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+            continue;
+        }
+        output.push(ch);
+    }
+    output
+}
+
+fn is_device_code(token: &str) -> bool {
+    let Some((left, right)) = token.split_once('-') else {
+        return false;
+    };
+    let part = |value: &str| {
+        (4..=6).contains(&value.len())
+            && value
+                .chars()
+                .all(|ch| ch.is_ascii_uppercase() || ch.is_ascii_digit())
+    };
+    part(left) && part(right)
+}
+
+/// Picks the sign-in URL and one-time device code out of a line printed by
+/// `codex login`.
+pub(crate) fn parse_login_output(line: &str) -> (Option<String>, Option<String>) {
+    let line = strip_ansi(line);
+    let mut url = None;
+    let mut code = None;
+    for token in line.split_whitespace() {
+        let token = token.trim_end_matches(&['.', ',', ')'][..]);
+        if url.is_none() && (token.starts_with("https://") || token.starts_with("http://")) {
+            url = Some(token.to_string());
+        } else if code.is_none() && is_device_code(token) {
+            code = Some(token.to_string());
+        }
+    }
+    (url, code)
+}
+
+async fn resolve_profile_home(
+    app_settings: &Mutex<AppSettings>,
+    profile_id: &str,
+) -> Result<(PathBuf, Option<String>, Option<String>), String> {
+    let profile = find_profile_core(app_settings, profile_id).await?;
+    let codex_home = normalize_codex_home(&profile.codex_home)
+        .ok_or_else(|| format!("Invalid codexHome for profile `{}`.", profile.name))?;
+    let settings = app_settings.lock().await;
+    Ok((
+        codex_home,
+        settings.codex_bin.clone(),
+        settings.codex_args.clone(),
+    ))
+}
+
+fn forward_lines<R>(reader: R, tx: mpsc::UnboundedSender<String>)
+where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(reader).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+/// Runs `codex login` against the profile's home, emitting `profile-login`
+/// events for the URL or device code it prints, and returns once the CLI
+/// exits.
+pub(crate) async fn codex_profile_login_core<E: EventSink>(
+    profile_id: String,
+    app_settings: &Mutex<AppSettings>,
+    event_sink: &E,
+) -> Result<(), String> {
+    let (codex_home, codex_bin, codex_args) =
+        resolve_profile_home(app_settings, &profile_id).await?;
+    std::fs::create_dir_all(&codex_home)
+        .map_err(|err| format!("Failed to create {}: {err}", codex_home.display()))?;
+    let emit = |status: &str, url: Option<String>, code: Option<String>, error: Option<String>| {
+        event_sink.emit_profile_login_event(ProfileLoginEvent {
+            profile_id: profile_id.clone(),
+            status: status.to_string(),
+            url,
+            code,
+            error,
+        });
+    };
+
+    let mut command =
+        build_codex_command_with_bin(codex_bin, codex_args.as_deref(), vec!["login".to_string()])?;
+    command.env("CODEX_HOME", &codex_home);
+    command.stdin(Stdio::null());
+    command.stdout(Stdio::piped());
+    command.stderr(Stdio::piped());
+    command.kill_on_drop(true);
+    let mut child = command
+        .spawn()
+        .map_err(|err| format!("Failed to run codex login: {err}"))?;
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    if let Some(stdout) = child.stdout.take() {
+        forward_lines(stdout, tx.clone());
+    }
+    if let Some(stderr) = child.stderr.take() {
+        forward_lines(stderr, tx);
+    }
+
+    let deadline = tokio::time::Instant::now() + PROFILE_LOGIN_TIMEOUT;
+    let mut last_line = None;
+    loop {
+        match timeout_at(deadline, rx.recv()).await {
+            Ok(Some(line)) => {
+                let (url, code) = parse_login_output(&line);
+                if url.is_some() || code.is_some() {
+                    emit("prompt", url, code, None);
+                }
+                let trimmed = strip_ansi(&line).trim().to_string();
+                if !trimmed.is_empty() {
+                    last_line = Some(trimmed);
+                }
+            }
+            Ok(None) => break,
+            Err(_) => {
+                kill_child_process_tree(&mut child).await;
+                let error = "Timed out waiting for codex login to finish.".to_string();
+                emit("failed", None, None, Some(error.clone()));
+                return Err(error);
+            }
+        }
+    }
+
+    let status = child
+        .wait()
+        .await
+        .map_err(|err| format!("Failed to wait for codex login: {err}"))?;
+    if status.success() {
+        emit("done", None, None, None);
+        return Ok(());
+    }
+    let error = last_line
+        .map(|line| redaction_core::redact_for_log(&line))
+        .unwrap_or_else(|| format!("codex login exited with {status}"));
+    emit("failed", None, None, Some(error.clone()));
+    Err(error)
+}
+
+pub(crate) async fn codex_profile_logout_core(
+    profile_id: String,
+    app_settings: &Mutex<AppSettings>,
+) -> Result<(), String> {
+    let (codex_home, codex_bin, codex_args) =
+        resolve_profile_home(app_settings, &profile_id).await?;
+    let mut command =
+        build_codex_command_with_bin(codex_bin, codex_args.as_deref(), vec!["logout".to_string()])?;
+    command.env("CODEX_HOME", &codex_home);
+    command.stdin(Stdio::null());
+    command.kill_on_drop(true);
+    let output = tokio::time::timeout(PROFILE_LOGOUT_TIMEOUT, command.output())
+        .await
+        .map_err(|_| "Timed out waiting for codex logout.".to_string())?
+        .map_err(|err| format!("Failed to run codex logout: {err}"))?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let detail = if stderr.trim().is_empty() {
+        stdout.trim()
+    } else {
+        stderr.trim()
+    };
+    Err(if detail.is_empty() {
+        format!("codex logout exited with {}", output.status)
+    } else {
+        redaction_core::redact_for_log(detail)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_login_output;
+
+    #[test]
+    fn parse_login_output_finds_url_and_device_code() {
+        let (url, code) = parse_login_output(
+            "If your browser did not open, navigate to this URL to authenticate:\n",
+        );
+        assert!(url.is_none() && code.is_none());
+
+        let (url, _) = parse_login_output(
+            "\u{1b}[94mhttps://auth.openai.com/oauth/authorize?client_id=app\u{1b}[0m",
+        );
+        assert_eq!(
+            url.as_deref(),
+            Some("https://auth.openai.com/oauth/authorize?client_id=app")
+        );
+
+        let (url, code) = parse_login_output("Enter this one-time code: ABCD-12345");
+        assert!(url.is_none());
+        assert_eq!(code.as_deref(), Some("ABCD-12345"));
+        assert!(parse_login_output("Signed in via 2024-01 plan").1.is_none());
+    }
+}
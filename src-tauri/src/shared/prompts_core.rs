@@ -18,6 +18,12 @@ pub(crate) struct CustomPromptEntry {
     pub(crate) content: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) scope: Option<String>,
+    /// Subfolder of the scope's prompts dir, `/`-separated; `None` at the
+    /// top level.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) folder: Option<String>,
+    /// `{{name}}` placeholders in `content`, for `prompts_render`.
+    pub(crate) variables: Vec<String>,
 }
 
 fn resolve_codex_home_for_workspace(
@@ -55,7 +61,10 @@ fn app_data_dir(settings_path: &Path) -> Result<PathBuf, String> {
         .ok_or_else(|| "Unable to resolve app data dir.".to_string())
 }
 
-fn workspace_prompts_dir(settings_path: &Path, entry: &WorkspaceEntry) -> Result<PathBuf, String> {
+pub(crate) fn workspace_prompts_dir(
+    settings_path: &Path,
+    entry: &WorkspaceEntry,
+) -> Result<PathBuf, String> {
     let data_dir = app_data_dir(settings_path)?;
     Ok(data_dir.join("workspaces").join(&entry.id).join("prompts"))
 }
@@ -222,17 +231,145 @@ fn sanitize_prompt_name(name: &str) -> Result<String, String> {
     Ok(trimmed.to_string())
 }
 
+/// How deep `prompts_list` looks into subfolders.
+const MAX_FOLDER_DEPTH: usize = 8;
+
+/// A relative folder such as `review/security`. Separators may be either
+/// slash and empty components are dropped; `.`, `..` and hidden names are
+/// rejected. An empty folder means the top level.
+fn sanitize_prompt_folder(folder: Option<&str>) -> Result<Option<PathBuf>, String> {
+    let Some(folder) = folder else {
+        return Ok(None);
+    };
+    let mut path = PathBuf::new();
+    for component in folder.split(['/', '\\']).map(str::trim) {
+        if component.is_empty() {
+            continue;
+        }
+        if component.starts_with('.') {
+            return Err("Prompt folder names cannot start with a dot.".to_string());
+        }
+        path.push(component);
+    }
+    Ok((!path.as_os_str().is_empty()).then_some(path))
+}
+
+/// `path`'s folder relative to `root`, the way `CustomPromptEntry::folder`
+/// reports it.
+fn prompt_folder(root: &Path, path: &Path) -> Option<String> {
+    let relative = path.parent()?.strip_prefix(root).ok()?;
+    let parts: Vec<String> = relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().to_string())
+        .collect();
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-' | '.'))
+}
+
+/// Byte ranges and names of the `{{name}}` placeholders in `content`, with
+/// whitespace allowed inside the braces. Braces around anything that isn't
+/// a variable name are left as text.
+fn placeholders(content: &str) -> Vec<(usize, usize, &str)> {
+    let mut out = Vec::new();
+    let mut offset = 0;
+    while let Some(open) = content[offset..].find("{{") {
+        let start = offset + open;
+        let Some(close) = content[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 2 + close + 2;
+        let name = content[start + 2..end - 2].trim();
+        if is_variable_name(name) {
+            out.push((start, end, name));
+            offset = end;
+        } else {
+            offset = start + 1;
+        }
+    }
+    out
+}
+
+/// Variable names in order of first use, each once.
+pub(crate) fn prompt_variables(content: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for (_, _, name) in placeholders(content) {
+        if !names.iter().any(|existing| existing == name) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+/// Fills each placeholder from `vars`. Values are inserted as is, never
+/// expanded again. A variable without a value is an error listing every
+/// missing name, unless `leave_unfilled` keeps its placeholder literally.
+pub(crate) fn render_prompt(
+    content: &str,
+    vars: &HashMap<String, String>,
+    leave_unfilled: bool,
+) -> Result<String, String> {
+    let mut output = String::with_capacity(content.len());
+    let mut missing: Vec<&str> = Vec::new();
+    let mut last = 0;
+    for (start, end, name) in placeholders(content) {
+        output.push_str(&content[last..start]);
+        match vars.get(name) {
+            Some(value) => output.push_str(value),
+            None => {
+                if !missing.contains(&name) {
+                    missing.push(name);
+                }
+                output.push_str(&content[start..end]);
+            }
+        }
+        last = end;
+    }
+    output.push_str(&content[last..]);
+    if !missing.is_empty() && !leave_unfilled {
+        return Err(format!("Missing values for: {}.", missing.join(", ")));
+    }
+    Ok(output)
+}
+
 fn discover_prompts_in(dir: &Path, scope: Option<&str>) -> Vec<CustomPromptEntry> {
     let mut out: Vec<CustomPromptEntry> = Vec::new();
+    collect_prompts(dir, dir, scope, 0, &mut out);
+    out.sort_by(|a, b| a.folder.cmp(&b.folder).then_with(|| a.name.cmp(&b.name)));
+    out
+}
+
+fn collect_prompts(
+    root: &Path,
+    dir: &Path,
+    scope: Option<&str>,
+    depth: usize,
+    out: &mut Vec<CustomPromptEntry>,
+) {
     let entries = match fs::read_dir(dir) {
         Ok(entries) => entries,
-        Err(_) => return out,
+        Err(_) => return,
     };
 
     for entry in entries.flatten() {
         let path = entry.path();
-        let is_file = fs::metadata(&path).map(|m| m.is_file()).unwrap_or(false);
-        if !is_file {
+        let Ok(metadata) = fs::metadata(&path) else {
+            continue;
+        };
+        if metadata.is_dir() {
+            let hidden = entry.file_name().to_string_lossy().starts_with('.');
+            if !hidden && depth < MAX_FOLDER_DEPTH {
+                collect_prompts(root, &path, scope, depth + 1, out);
+            }
+            continue;
+        }
+        if !metadata.is_file() {
             continue;
         }
         let is_md = path
@@ -260,13 +397,12 @@ fn discover_prompts_in(dir: &Path, scope: Option<&str>) -> Vec<CustomPromptEntry
             path: path.to_string_lossy().to_string(),
             description,
             argument_hint,
+            variables: prompt_variables(&body),
             content: body,
             scope: scope.map(|value| value.to_string()),
+            folder: prompt_folder(root, &path),
         });
     }
-
-    out.sort_by(|a, b| a.name.cmp(&b.name));
-    out
 }
 
 pub(crate) async fn prompts_list_core(
@@ -333,13 +469,15 @@ pub(crate) async fn prompts_create_core(
     settings_path: &Path,
     workspace_id: String,
     scope: String,
+    folder: Option<String>,
     name: String,
     description: Option<String>,
     argument_hint: Option<String>,
     content: String,
 ) -> Result<CustomPromptEntry, String> {
     let name = sanitize_prompt_name(&name)?;
-    let (target_dir, resolved_scope) = {
+    let folder = sanitize_prompt_folder(folder.as_deref())?;
+    let (root, resolved_scope) = {
         let workspaces = workspaces.lock().await;
         let entry = require_workspace_entry(&workspaces, &workspace_id)?;
         match scope.as_str() {
@@ -355,6 +493,10 @@ pub(crate) async fn prompts_create_core(
             _ => return Err("Invalid scope.".to_string()),
         }
     };
+    let target_dir = match &folder {
+        Some(folder) => root.join(folder),
+        None => root.clone(),
+    };
     let path = target_dir.join(format!("{name}.md"));
     if path.exists() {
         return Err("Prompt already exists.".to_string());
@@ -369,8 +511,10 @@ pub(crate) async fn prompts_create_core(
         path: path.to_string_lossy().to_string(),
         description,
         argument_hint,
+        variables: prompt_variables(&content),
         content,
         scope: Some(resolved_scope.to_string()),
+        folder: prompt_folder(&root, &path),
     })
 }
 
@@ -407,26 +551,42 @@ pub(crate) async fn prompts_update_core(
     if next_path != target_path {
         fs::remove_file(&target_path).map_err(|err| err.to_string())?;
     }
-    let scope = {
-        let workspaces = workspaces.lock().await;
-        let entry = require_workspace_entry(&workspaces, &workspace_id)?;
-        let workspace_dir = workspace_prompts_dir(settings_path, &entry)?;
-        if next_path.starts_with(&workspace_dir) {
-            Some("workspace".to_string())
-        } else {
-            Some("global".to_string())
-        }
-    };
+    let (scope, folder) =
+        scope_and_folder(workspaces, settings_path, &workspace_id, &next_path).await?;
     Ok(CustomPromptEntry {
         name,
         path: next_path.to_string_lossy().to_string(),
         description,
         argument_hint,
+        variables: prompt_variables(&content),
         content,
         scope,
+        folder,
     })
 }
 
+/// The scope and folder of a prompt file already checked to be within the
+/// workspace's prompt roots.
+async fn scope_and_folder(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    settings_path: &Path,
+    workspace_id: &str,
+    path: &Path,
+) -> Result<(Option<String>, Option<String>), String> {
+    let workspaces = workspaces.lock().await;
+    let entry = require_workspace_entry(&workspaces, workspace_id)?;
+    let workspace_dir = workspace_prompts_dir(settings_path, &entry)?;
+    if path.starts_with(&workspace_dir) {
+        return Ok((
+            Some("workspace".to_string()),
+            prompt_folder(&workspace_dir, path),
+        ));
+    }
+    let folder = default_prompts_dir_for_workspace(&workspaces, &entry)
+        .and_then(|global_dir| prompt_folder(&global_dir, path));
+    Ok((Some("global".to_string()), folder))
+}
+
 pub(crate) async fn prompts_delete_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     settings_path: &Path,
@@ -452,7 +612,9 @@ pub(crate) async fn prompts_move_core(
     workspace_id: String,
     path: String,
     scope: String,
+    folder: Option<String>,
 ) -> Result<CustomPromptEntry, String> {
+    let folder = sanitize_prompt_folder(folder.as_deref())?;
     let target_path = PathBuf::from(&path);
     if !target_path.exists() {
         return Err("Prompt not found.".to_string());
@@ -467,7 +629,7 @@ pub(crate) async fn prompts_move_core(
         .file_name()
         .and_then(|value| value.to_str())
         .ok_or("Invalid prompt path.".to_string())?;
-    let target_dir = {
+    let root = {
         let workspaces = workspaces.lock().await;
         let entry = require_workspace_entry(&workspaces, &workspace_id)?;
         match scope.as_str() {
@@ -477,9 +639,13 @@ pub(crate) async fn prompts_move_core(
             _ => return Err("Invalid scope.".to_string()),
         }
     };
+    let target_dir = match &folder {
+        Some(folder) => root.join(folder),
+        None => root.clone(),
+    };
     let next_path = target_dir.join(file_name);
     if next_path == target_path {
-        return Err("Prompt is already in that scope.".to_string());
+        return Err("Prompt is already in that folder.".to_string());
     }
     if next_path.exists() {
         return Err("Prompt with that name already exists.".to_string());
@@ -500,7 +666,154 @@ pub(crate) async fn prompts_move_core(
         path: next_path.to_string_lossy().to_string(),
         description,
         argument_hint,
+        variables: prompt_variables(&body),
         content: body,
         scope: Some(scope),
+        folder: prompt_folder(&root, &next_path),
+    })
+}
+
+/// Copies the prompt at `path` into the same folder as `copy-of-{name}`,
+/// then `copy-of-{name}-2` and so on when that is taken. Prompt names can't
+/// hold whitespace, so this stands in for "Copy of …".
+pub(crate) async fn prompts_duplicate_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    settings_path: &Path,
+    workspace_id: String,
+    path: String,
+) -> Result<CustomPromptEntry, String> {
+    let source = PathBuf::from(&path);
+    if !source.exists() {
+        return Err("Prompt not found.".to_string());
+    }
+    {
+        let workspaces = workspaces.lock().await;
+        let entry = require_workspace_entry(&workspaces, &workspace_id)?;
+        let roots = prompt_roots_for_workspace(settings_path, &workspaces, &entry)?;
+        ensure_path_within_roots(&source, &roots)?;
+    }
+    let name = source
+        .file_stem()
+        .and_then(|value| value.to_str())
+        .ok_or("Invalid prompt path.".to_string())?;
+    let dir = source
+        .parent()
+        .ok_or("Unable to resolve prompt directory.".to_string())?;
+    let base = format!("copy-of-{name}");
+    let mut next_name = base.clone();
+    let mut suffix = 2;
+    while dir.join(format!("{next_name}.md")).exists() {
+        next_name = format!("{base}-{suffix}");
+        suffix += 1;
+    }
+    let next_path = dir.join(format!("{next_name}.md"));
+    fs::copy(&source, &next_path).map_err(|err| err.to_string())?;
+    let content = fs::read_to_string(&next_path).map_err(|err| err.to_string())?;
+    let (description, argument_hint, body) = parse_frontmatter(&content);
+    let (scope, folder) =
+        scope_and_folder(workspaces, settings_path, &workspace_id, &next_path).await?;
+    Ok(CustomPromptEntry {
+        name: next_name,
+        path: next_path.to_string_lossy().to_string(),
+        description,
+        argument_hint,
+        variables: prompt_variables(&body),
+        content: body,
+        scope,
+        folder,
     })
 }
+
+/// The body of the prompt at `path`, frontmatter dropped, with its
+/// variables filled in by `render_prompt`.
+pub(crate) async fn prompts_render_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    settings_path: &Path,
+    workspace_id: String,
+    path: String,
+    vars: HashMap<String, String>,
+    leave_unfilled: bool,
+) -> Result<String, String> {
+    let target_path = PathBuf::from(&path);
+    if !target_path.exists() {
+        return Err("Prompt not found.".to_string());
+    }
+    {
+        let workspaces = workspaces.lock().await;
+        let entry = require_workspace_entry(&workspaces, &workspace_id)?;
+        let roots = prompt_roots_for_workspace(settings_path, &workspaces, &entry)?;
+        ensure_path_within_roots(&target_path, &roots)?;
+    }
+    let content = fs::read_to_string(&target_path).map_err(|err| err.to_string())?;
+    let (_, _, body) = parse_frontmatter(&content);
+    render_prompt(&body, &vars, leave_unfilled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_prompt_fills_variables_and_reports_missing_ones() {
+        let content = "Refactor {{file}} to use {{ pattern }}; keep {{file}} tidy. {{not a var}}";
+        assert_eq!(prompt_variables(content), vec!["file", "pattern"]);
+
+        let vars = HashMap::from([
+            ("file".to_string(), "lib.rs".to_string()),
+            ("pattern".to_string(), "{{file}}".to_string()),
+        ]);
+        assert_eq!(
+            render_prompt(content, &vars, false).as_deref(),
+            Ok("Refactor lib.rs to use {{file}}; keep lib.rs tidy. {{not a var}}")
+        );
+
+        let partial = HashMap::from([("file".to_string(), "lib.rs".to_string())]);
+        assert_eq!(
+            render_prompt(content, &partial, false),
+            Err("Missing values for: pattern.".to_string())
+        );
+        assert_eq!(
+            render_prompt(content, &partial, true).as_deref(),
+            Ok("Refactor lib.rs to use {{ pattern }}; keep lib.rs tidy. {{not a var}}")
+        );
+        assert_eq!(prompt_variables("{{{x}}} {{"), vec!["x"]);
+    }
+
+    #[test]
+    fn discover_prompts_in_reports_subfolders() {
+        let root =
+            std::env::temp_dir().join(format!("codex-monitor-prompts-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(root.join("review").join("security")).expect("create folders");
+        fs::create_dir_all(root.join(".hidden")).expect("create hidden folder");
+        fs::write(root.join("top.md"), "Top").expect("write top");
+        fs::write(root.join("review").join("pr.md"), "PR").expect("write pr");
+        fs::write(
+            root.join("review").join("security").join("audit.md"),
+            "Audit",
+        )
+        .expect("write audit");
+        fs::write(root.join(".hidden").join("skip.md"), "Skip").expect("write skip");
+
+        let found: Vec<(Option<String>, String)> = discover_prompts_in(&root, Some("workspace"))
+            .into_iter()
+            .map(|entry| (entry.folder, entry.name))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (None, "top".to_string()),
+                (Some("review".to_string()), "pr".to_string()),
+                (Some("review/security".to_string()), "audit".to_string()),
+            ]
+        );
+
+        assert_eq!(
+            sanitize_prompt_folder(Some("/review//docs/")),
+            Ok(Some(PathBuf::from("review").join("docs")))
+        );
+        assert_eq!(sanitize_prompt_folder(Some(" / ")), Ok(None));
+        assert!(sanitize_prompt_folder(Some("review/../etc")).is_err());
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
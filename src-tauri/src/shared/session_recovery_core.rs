@@ -0,0 +1,274 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::sync::Mutex;
+
+use crate::backend::app_server::{SessionExit, WorkspaceSession};
+use crate::backend::events::{EventSink, SessionCrashedEvent};
+use crate::shared::{inventory_core, workspaces_core};
+use crate::types::{AppSettings, WorkspaceEntry};
+
+const SUPERVISOR_TICK: Duration = Duration::from_secs(1);
+const MAX_RESPAWN_ATTEMPTS: u32 = 5;
+const RESPAWN_BASE_DELAY: Duration = Duration::from_secs(1);
+const RESPAWN_MAX_DELAY: Duration = Duration::from_secs(30);
+/// A workspace that stays up this long after a crash gets its full retry
+/// budget back.
+const RESPAWN_RESET_AFTER: Duration = Duration::from_secs(600);
+
+const SIGABRT: i32 = 6;
+const SIGKILL: i32 = 9;
+const SIGSEGV: i32 = 11;
+const SIGTERM: i32 = 15;
+
+/// A kind the UI can key on and a sentence for the user. Exit codes 126,
+/// 127 and 137 are what a shell wrapper reports for an unrunnable binary, a
+/// missing one and a SIGKILL.
+pub(crate) fn describe_exit(exit: &SessionExit) -> (&'static str, String) {
+    match (exit.signal, exit.code) {
+        (Some(SIGKILL), _) | (None, Some(137)) => (
+            "outOfMemory",
+            "Codex was killed with SIGKILL, most often because the system ran out of memory."
+                .to_string(),
+        ),
+        (None, Some(127)) => (
+            "notFound",
+            "Codex exited with status 127: its binary or something it runs wasn't found. \
+             Check the Codex path in settings."
+                .to_string(),
+        ),
+        (None, Some(126)) => (
+            "notExecutable",
+            "Codex exited with status 126: its binary isn't executable.".to_string(),
+        ),
+        (Some(signal), _) => {
+            let name = match signal {
+                SIGABRT => "SIGABRT".to_string(),
+                SIGSEGV => "SIGSEGV".to_string(),
+                SIGTERM => "SIGTERM".to_string(),
+                other => format!("signal {other}"),
+            };
+            ("signal", format!("Codex was killed by {name}."))
+        }
+        (None, Some(code)) => ("exit", format!("Codex exited with status {code}.")),
+        (None, None) => ("exit", "Codex exited.".to_string()),
+    }
+}
+
+/// Doubles from one second per attempt, up to thirty.
+pub(crate) fn respawn_delay(attempt: u32) -> Duration {
+    let factor = 1u32 << attempt.saturating_sub(1).min(16);
+    RESPAWN_BASE_DELAY
+        .saturating_mul(factor)
+        .min(RESPAWN_MAX_DELAY)
+}
+
+fn crash_event(
+    workspace_id: &str,
+    exit: &SessionExit,
+    stderr_tail: Vec<String>,
+) -> SessionCrashedEvent {
+    let (kind, reason) = describe_exit(exit);
+    SessionCrashedEvent {
+        workspace_id: workspace_id.to_string(),
+        status: "crashed".to_string(),
+        kind: kind.to_string(),
+        reason,
+        exit_code: exit.code,
+        signal: exit.signal,
+        stderr_tail,
+        attempt: 0,
+        retry_in_ms: None,
+        error: None,
+    }
+}
+
+/// Sessions whose child has exited while still registered. They are taken
+/// out of `sessions`, so the workspace reads as disconnected. Intentional
+/// kills always unregister the session first and never show up here.
+async fn take_crashed_sessions(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+) -> Vec<(String, SessionExit, Vec<String>)> {
+    let registered: Vec<(String, Arc<WorkspaceSession>)> = sessions
+        .lock()
+        .await
+        .iter()
+        .map(|(id, session)| (id.clone(), Arc::clone(session)))
+        .collect();
+    let mut crashed = Vec::new();
+    for (workspace_id, session) in registered {
+        let Some(exit) = session.exit_status().await else {
+            continue;
+        };
+        {
+            let mut sessions = sessions.lock().await;
+            match sessions.get(&workspace_id) {
+                Some(current) if Arc::ptr_eq(current, &session) => {
+                    sessions.remove(&workspace_id);
+                }
+                _ => continue,
+            }
+        }
+        crashed.push((workspace_id, exit, session.stderr_tail().await));
+    }
+    crashed
+}
+
+struct PendingRespawn {
+    crash: SessionCrashedEvent,
+    attempt: u32,
+    due: Instant,
+}
+
+/// Runs for the lifetime of the process. Emits `session-crashed` for each
+/// Codex process that dies under a registered session and, with
+/// `autoRespawnSessions` on, reconnects the workspace the way
+/// `connect_workspace` does, with backoff and a capped number of attempts.
+pub(crate) async fn run_session_supervisor<E, F, Fut>(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    app_settings: &Mutex<AppSettings>,
+    event_sink: E,
+    spawn_session: F,
+) where
+    E: EventSink,
+    F: Fn(WorkspaceEntry, Option<String>, Option<String>, Option<PathBuf>) -> Fut,
+    Fut: Future<Output = Result<Arc<WorkspaceSession>, String>>,
+{
+    let mut pending: HashMap<String, PendingRespawn> = HashMap::new();
+    // Attempts used per workspace and when it last crashed.
+    let mut history: HashMap<String, (u32, Instant)> = HashMap::new();
+    inventory_core::scheduler_started("sessionSupervisor");
+    loop {
+        tokio::time::sleep(SUPERVISOR_TICK).await;
+        inventory_core::scheduler_ticked("sessionSupervisor");
+        let now = Instant::now();
+
+        for (workspace_id, exit, stderr_tail) in take_crashed_sessions(sessions).await {
+            let mut crash = crash_event(&workspace_id, &exit, stderr_tail);
+            eprintln!(
+                "Codex session for workspace {workspace_id} exited: {}",
+                crash.reason
+            );
+            if !app_settings.lock().await.auto_respawn_sessions {
+                event_sink.emit_session_crashed_event(crash);
+                continue;
+            }
+            let used = match history.get(&workspace_id) {
+                Some((used, last)) if now.duration_since(*last) < RESPAWN_RESET_AFTER => *used,
+                _ => 0,
+            };
+            let attempt = used + 1;
+            history.insert(workspace_id.clone(), (attempt, now));
+            crash.attempt = attempt;
+            if attempt > MAX_RESPAWN_ATTEMPTS {
+                crash.status = "gaveUp".to_string();
+                event_sink.emit_session_crashed_event(crash);
+                continue;
+            }
+            let delay = respawn_delay(attempt);
+            crash.status = "respawning".to_string();
+            crash.retry_in_ms = Some(delay.as_millis() as u64);
+            event_sink.emit_session_crashed_event(crash.clone());
+            pending.insert(
+                workspace_id,
+                PendingRespawn {
+                    crash,
+                    attempt,
+                    due: now + delay,
+                },
+            );
+        }
+
+        let due: Vec<String> = pending
+            .iter()
+            .filter(|(_, respawn)| respawn.due <= now)
+            .map(|(workspace_id, _)| workspace_id.clone())
+            .collect();
+        for workspace_id in due {
+            let Some(respawn) = pending.remove(&workspace_id) else {
+                continue;
+            };
+            // Reconnected by hand in the meantime.
+            if sessions.lock().await.contains_key(&workspace_id) {
+                continue;
+            }
+            let mut crash = respawn.crash;
+            crash.retry_in_ms = None;
+            match workspaces_core::connect_workspace_core(
+                workspace_id.clone(),
+                workspaces,
+                sessions,
+                app_settings,
+                &spawn_session,
+            )
+            .await
+            {
+                Ok(()) => {
+                    crash.status = "respawned".to_string();
+                    crash.error = None;
+                    event_sink.emit_session_crashed_event(crash);
+                }
+                Err(error) => {
+                    let attempt = respawn.attempt + 1;
+                    history.insert(workspace_id.clone(), (attempt, Instant::now()));
+                    crash.attempt = attempt;
+                    crash.error = Some(error);
+                    if attempt > MAX_RESPAWN_ATTEMPTS
+                        || !app_settings.lock().await.auto_respawn_sessions
+                    {
+                        crash.status = "gaveUp".to_string();
+                        event_sink.emit_session_crashed_event(crash);
+                        continue;
+                    }
+                    let delay = respawn_delay(attempt);
+                    crash.retry_in_ms = Some(delay.as_millis() as u64);
+                    event_sink.emit_session_crashed_event(crash.clone());
+                    pending.insert(
+                        workspace_id,
+                        PendingRespawn {
+                            crash,
+                            attempt,
+                            due: Instant::now() + delay,
+                        },
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exit(code: Option<i32>, signal: Option<i32>) -> SessionExit {
+        SessionExit { code, signal }
+    }
+
+    #[test]
+    fn describe_exit_tells_oom_from_a_bad_binary() {
+        assert_eq!(describe_exit(&exit(None, Some(SIGKILL))).0, "outOfMemory");
+        assert_eq!(describe_exit(&exit(Some(137), None)).0, "outOfMemory");
+        assert_eq!(describe_exit(&exit(Some(127), None)).0, "notFound");
+        assert_eq!(describe_exit(&exit(Some(126), None)).0, "notExecutable");
+        let (kind, reason) = describe_exit(&exit(None, Some(SIGSEGV)));
+        assert_eq!(kind, "signal");
+        assert!(reason.contains("SIGSEGV"));
+        let (kind, reason) = describe_exit(&exit(Some(1), None));
+        assert_eq!(kind, "exit");
+        assert!(reason.contains("status 1"));
+    }
+
+    #[test]
+    fn respawn_delay_doubles_up_to_the_cap() {
+        assert_eq!(respawn_delay(1), Duration::from_secs(1));
+        assert_eq!(respawn_delay(2), Duration::from_secs(2));
+        assert_eq!(respawn_delay(4), Duration::from_secs(8));
+        assert_eq!(respawn_delay(6), RESPAWN_MAX_DELAY);
+        assert_eq!(respawn_delay(40), RESPAWN_MAX_DELAY);
+    }
+}
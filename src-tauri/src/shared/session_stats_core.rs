@@ -0,0 +1,133 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex as StdMutex, OnceLock};
+use std::time::Duration;
+
+use crate::types::{SessionStartupSample, SessionStartupStats, WorkspaceStartupSummary};
+use crate::utils::now_unix_ms;
+
+const MAX_STARTUP_SAMPLES: usize = 100;
+const RECENT_STARTUP_SAMPLES: usize = 20;
+
+static STARTUP_SAMPLES: OnceLock<StdMutex<VecDeque<SessionStartupSample>>> = OnceLock::new();
+
+fn startup_samples() -> &'static StdMutex<VecDeque<SessionStartupSample>> {
+    STARTUP_SAMPLES.get_or_init(|| StdMutex::new(VecDeque::new()))
+}
+
+/// Records the time from spawning `codex app-server` to its initialize response.
+pub(crate) fn record_session_startup(workspace_id: &str, workspace_name: &str, elapsed: Duration) {
+    let Ok(mut samples) = startup_samples().lock() else {
+        return;
+    };
+    samples.push_back(SessionStartupSample {
+        workspace_id: workspace_id.to_string(),
+        workspace_name: workspace_name.to_string(),
+        duration_ms: elapsed.as_millis() as u64,
+        recorded_at_ms: now_unix_ms(),
+    });
+    while samples.len() > MAX_STARTUP_SAMPLES {
+        samples.pop_front();
+    }
+}
+
+pub(crate) fn summarize_startup_samples(samples: &[SessionStartupSample]) -> SessionStartupStats {
+    let recent: Vec<SessionStartupSample> = samples
+        .iter()
+        .rev()
+        .take(RECENT_STARTUP_SAMPLES)
+        .cloned()
+        .collect();
+
+    let mut total_ms: u64 = 0;
+    let mut count: u64 = 0;
+    let mut order: Vec<String> = Vec::new();
+    let mut by_workspace: HashMap<String, (WorkspaceStartupSummary, u64)> = HashMap::new();
+    for sample in samples {
+        total_ms += sample.duration_ms;
+        count += 1;
+        let (summary, workspace_total) = by_workspace
+            .entry(sample.workspace_id.clone())
+            .or_insert_with(|| {
+                order.push(sample.workspace_id.clone());
+                (
+                    WorkspaceStartupSummary {
+                        workspace_id: sample.workspace_id.clone(),
+                        workspace_name: sample.workspace_name.clone(),
+                        sample_count: 0,
+                        average_ms: 0,
+                        max_ms: 0,
+                        last_ms: 0,
+                    },
+                    0,
+                )
+            });
+        *workspace_total += sample.duration_ms;
+        summary.sample_count += 1;
+        summary.workspace_name = sample.workspace_name.clone();
+        summary.max_ms = summary.max_ms.max(sample.duration_ms);
+        summary.last_ms = sample.duration_ms;
+        summary.average_ms = *workspace_total / summary.sample_count as u64;
+    }
+
+    let mut workspaces: Vec<WorkspaceStartupSummary> = order
+        .into_iter()
+        .filter_map(|id| by_workspace.remove(&id).map(|(summary, _)| summary))
+        .collect();
+    // Slowest first, since that is what this is for.
+    workspaces.sort_by(|a, b| b.average_ms.cmp(&a.average_ms));
+
+    SessionStartupStats {
+        recent,
+        average_ms: if count > 0 {
+            Some(total_ms / count)
+        } else {
+            None
+        },
+        workspaces,
+    }
+}
+
+pub(crate) fn get_session_startup_stats_core() -> SessionStartupStats {
+    let samples: Vec<SessionStartupSample> = startup_samples()
+        .lock()
+        .map(|samples| samples.iter().cloned().collect())
+        .unwrap_or_default();
+    summarize_startup_samples(&samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(workspace_id: &str, duration_ms: u64) -> SessionStartupSample {
+        SessionStartupSample {
+            workspace_id: workspace_id.to_string(),
+            workspace_name: workspace_id.to_uppercase(),
+            duration_ms,
+            recorded_at_ms: 0,
+        }
+    }
+
+    #[test]
+    fn summarize_startup_samples_averages_per_workspace() {
+        let samples = vec![sample("a", 100), sample("b", 900), sample("a", 300)];
+        let stats = summarize_startup_samples(&samples);
+        assert_eq!(stats.average_ms, Some(433));
+        assert_eq!(stats.recent[0].duration_ms, 300);
+        assert_eq!(stats.workspaces.len(), 2);
+        assert_eq!(stats.workspaces[0].workspace_id, "b");
+        let a = &stats.workspaces[1];
+        assert_eq!(a.sample_count, 2);
+        assert_eq!(a.average_ms, 200);
+        assert_eq!(a.max_ms, 300);
+        assert_eq!(a.last_ms, 300);
+    }
+
+    #[test]
+    fn summarize_startup_samples_handles_empty_history() {
+        let stats = summarize_startup_samples(&[]);
+        assert_eq!(stats.average_ms, None);
+        assert!(stats.recent.is_empty());
+        assert!(stats.workspaces.is_empty());
+    }
+}
@@ -3,6 +3,7 @@ use std::path::PathBuf;
 use tokio::sync::Mutex;
 
 use crate::codex::config as codex_config;
+use crate::shared::{credentials_core, event_batch_core, http_client_core, redaction_core};
 use crate::storage::write_settings;
 use crate::types::AppSettings;
 
@@ -14,6 +15,13 @@ fn normalize_personality(value: &str) -> Option<&'static str> {
     }
 }
 
+/// Settings as they are sent to the UI. The remote backend token stays in
+/// the backend; `get_credential_status` reports whether one is stored.
+pub(crate) fn settings_for_response(mut settings: AppSettings) -> AppSettings {
+    settings.remote_backend_token = None;
+    settings
+}
+
 pub(crate) async fn get_app_settings_core(app_settings: &Mutex<AppSettings>) -> AppSettings {
     let mut settings = app_settings.lock().await.clone();
     if let Ok(Some(collab_enabled)) = codex_config::read_collab_enabled() {
@@ -39,21 +47,44 @@ pub(crate) async fn get_app_settings_core(app_settings: &Mutex<AppSettings>) ->
             .unwrap_or("friendly")
             .to_string();
     }
-    settings
+    settings_for_response(settings)
 }
 
+/// Settings sent without a remote backend token, as the UI always sends
+/// them, keep the stored one. `update_remote_backend_token_core` changes it.
 pub(crate) async fn update_app_settings_core(
+    mut settings: AppSettings,
+    app_settings: &Mutex<AppSettings>,
+    settings_path: &PathBuf,
+) -> Result<AppSettings, String> {
+    if settings.remote_backend_token.is_none() {
+        settings.remote_backend_token = app_settings.lock().await.remote_backend_token.clone();
+    }
+    store_app_settings(settings, app_settings, settings_path).await
+}
+
+async fn store_app_settings(
     settings: AppSettings,
     app_settings: &Mutex<AppSettings>,
     settings_path: &PathBuf,
 ) -> Result<AppSettings, String> {
+    http_client_core::validate_http_settings(&settings)?;
+    redaction_core::validate_redaction_settings(&settings)?;
+    event_batch_core::validate_event_batch_settings(&settings)?;
     let _ = codex_config::write_collab_enabled(settings.experimental_collab_enabled);
     let _ = codex_config::write_collaboration_modes_enabled(settings.collaboration_modes_enabled);
     let _ = codex_config::write_steer_enabled(settings.steer_enabled);
     let _ = codex_config::write_unified_exec_enabled(settings.unified_exec_enabled);
     let _ = codex_config::write_apps_enabled(settings.experimental_apps_enabled);
     let _ = codex_config::write_personality(settings.personality.as_str());
-    write_settings(settings_path, &settings)?;
+    let stored_token = app_settings.lock().await.remote_backend_token.clone();
+    write_settings(
+        settings_path,
+        &credentials_core::settings_for_disk(&settings, stored_token.as_deref()),
+    )?;
+    http_client_core::apply_http_settings(&settings);
+    redaction_core::apply_redaction_settings(&settings);
+    event_batch_core::apply_event_batch_settings(&settings);
     let mut current = app_settings.lock().await;
     *current = settings.clone();
     Ok(settings)
@@ -73,7 +104,7 @@ pub(crate) async fn update_remote_backend_token_core(
         return Ok(next_settings);
     }
     next_settings.remote_backend_token = normalized_token;
-    update_app_settings_core(next_settings, app_settings, settings_path).await
+    store_app_settings(next_settings, app_settings, settings_path).await
 }
 
 pub(crate) fn get_codex_config_path_core() -> Result<String, String> {
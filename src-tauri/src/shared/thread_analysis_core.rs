@@ -0,0 +1,626 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tokio::sync::{mpsc, Mutex};
+use tokio::time::timeout;
+
+use crate::backend::app_server::WorkspaceSession;
+use crate::types::{
+    ThreadDiff, ThreadDiffTurn, ThreadReplayResult, ThreadTurnSummary, ThreadWindow,
+};
+
+const REPLAY_TURN_TIMEOUT: Duration = Duration::from_secs(600);
+const DEFAULT_WINDOW_RADIUS: usize = 20;
+const MAX_WINDOW_RADIUS: usize = 200;
+
+fn response_error(response: &Value) -> Option<String> {
+    response.get("error").map(|error| {
+        error
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("Unknown app-server error")
+            .to_string()
+    })
+}
+
+fn response_thread(response: &Value) -> Option<&Value> {
+    response
+        .get("result")
+        .and_then(|result| result.get("thread"))
+        .or_else(|| response.get("thread"))
+}
+
+fn response_thread_id(response: &Value) -> Option<String> {
+    response
+        .get("result")
+        .and_then(|result| result.get("threadId"))
+        .or_else(|| response_thread(response).and_then(|thread| thread.get("id")))
+        .or_else(|| response.get("threadId"))
+        .and_then(Value::as_str)
+        .map(|value| value.to_string())
+}
+
+fn response_turn_id(response: &Value) -> Option<String> {
+    let result = response.get("result").unwrap_or(response);
+    result
+        .get("turn")
+        .and_then(|turn| turn.get("id"))
+        .or_else(|| result.get("turnId"))
+        .and_then(Value::as_str)
+        .map(|value| value.to_string())
+}
+
+fn thread_turn_items(thread: &Value) -> Vec<&[Value]> {
+    thread
+        .get("turns")
+        .and_then(Value::as_array)
+        .map(|turns| {
+            turns
+                .iter()
+                .map(|turn| {
+                    turn.get("items")
+                        .and_then(Value::as_array)
+                        .map(Vec::as_slice)
+                        .unwrap_or(&[])
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn user_message_text(item: &Value) -> Option<String> {
+    if item.get("type").and_then(Value::as_str) != Some("userMessage") {
+        return None;
+    }
+    let parts: Vec<String> = item
+        .get("content")
+        .and_then(Value::as_array)?
+        .iter()
+        .filter_map(|input| match input.get("type").and_then(Value::as_str) {
+            Some("text") => input
+                .get("text")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            Some("skill") => input
+                .get("name")
+                .and_then(Value::as_str)
+                .map(|name| format!("${name}")),
+            _ => None,
+        })
+        .collect();
+    let text = parts.join(" ").trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+/// User prompts in thread order. Tool calls and outputs are dropped so a
+/// replay regenerates them.
+pub(crate) fn extract_user_turns(thread: &Value) -> Vec<String> {
+    thread_turn_items(thread)
+        .into_iter()
+        .flat_map(|items| items.iter().filter_map(user_message_text))
+        .collect()
+}
+
+fn turn_duration_ms(turn: &Value) -> Option<i64> {
+    let started = turn.get("startedAt").and_then(Value::as_i64)?;
+    let completed = turn.get("completedAt").and_then(Value::as_i64)?;
+    (completed >= started).then_some(completed - started)
+}
+
+/// One summary per turn that starts with a user message; other turns (for
+/// example compaction) have nothing to align against.
+pub(crate) fn summarize_thread_turns(thread: &Value) -> Vec<ThreadTurnSummary> {
+    let Some(turns) = thread.get("turns").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    turns
+        .iter()
+        .filter_map(|turn| {
+            let items = turn.get("items").and_then(Value::as_array)?;
+            let user_message = items.iter().find_map(user_message_text)?;
+            let mut responses: Vec<&str> = Vec::new();
+            let mut tool_call_count = 0;
+            for item in items {
+                match item.get("type").and_then(Value::as_str).unwrap_or("") {
+                    "agentMessage" => {
+                        if let Some(text) = item.get("text").and_then(Value::as_str) {
+                            responses.push(text);
+                        }
+                    }
+                    "userMessage" | "reasoning" | "" => {}
+                    _ => tool_call_count += 1,
+                }
+            }
+            Some(ThreadTurnSummary {
+                user_message,
+                response: responses.join("\n\n"),
+                tool_call_count,
+                status: turn
+                    .get("status")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                duration_ms: turn_duration_ms(turn),
+                token_usage: turn
+                    .get("tokenUsage")
+                    .or_else(|| turn.get("usage"))
+                    .filter(|usage| !usage.is_null())
+                    .cloned(),
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn align_thread_turns(
+    a: Vec<ThreadTurnSummary>,
+    b: Vec<ThreadTurnSummary>,
+) -> Vec<ThreadDiffTurn> {
+    let count = a.len().max(b.len());
+    let mut a = a.into_iter();
+    let mut b = b.into_iter();
+    (0..count)
+        .map(|index| {
+            let a = a.next();
+            let b = b.next();
+            let prompts_match = match (&a, &b) {
+                (Some(a), Some(b)) => a.user_message == b.user_message,
+                _ => false,
+            };
+            ThreadDiffTurn {
+                index,
+                prompts_match,
+                a,
+                b,
+            }
+        })
+        .collect()
+}
+
+pub(crate) async fn read_thread(
+    session: &WorkspaceSession,
+    thread_id: &str,
+) -> Result<Value, String> {
+    let response = session
+        .send_request("thread/resume", json!({ "threadId": thread_id }))
+        .await?;
+    if let Some(error) = response_error(&response) {
+        return Err(error);
+    }
+    response_thread(&response)
+        .cloned()
+        .ok_or_else(|| format!("Thread {thread_id} returned no history"))
+}
+
+/// Unregisters a replay thread's event callback and interrupts the turn it
+/// was waiting on. `finish` does this when the replay returns; dropping the
+/// guard, as cancelling the replay's operation does, spawns it instead.
+struct ReplayCleanup {
+    session: Arc<WorkspaceSession>,
+    thread_id: String,
+    active_turn: Option<String>,
+    armed: bool,
+}
+
+impl ReplayCleanup {
+    async fn finish(mut self) {
+        self.armed = false;
+        let turn_id = self.active_turn.take();
+        clean_up_replay(&self.session, &self.thread_id, turn_id).await;
+    }
+}
+
+impl Drop for ReplayCleanup {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let session = Arc::clone(&self.session);
+        let thread_id = std::mem::take(&mut self.thread_id);
+        let turn_id = self.active_turn.take();
+        runtime.spawn(async move {
+            clean_up_replay(&session, &thread_id, turn_id).await;
+        });
+    }
+}
+
+async fn clean_up_replay(session: &WorkspaceSession, thread_id: &str, turn_id: Option<String>) {
+    session
+        .background_thread_callbacks
+        .lock()
+        .await
+        .remove(thread_id);
+    if let Some(turn_id) = turn_id {
+        let params = json!({ "threadId": thread_id, "turnId": turn_id });
+        let _ = session.send_request("turn/interrupt", params).await;
+    }
+}
+
+async fn run_replay_turns<F>(
+    session: &WorkspaceSession,
+    thread_id: &str,
+    model: &str,
+    turns: &[String],
+    rx: &mut mpsc::UnboundedReceiver<Value>,
+    cleanup: &mut ReplayCleanup,
+    on_progress: F,
+) -> Result<(), String>
+where
+    F: Fn(usize, usize),
+{
+    for (index, text) in turns.iter().enumerate() {
+        on_progress(index, turns.len());
+        // Replays run unattended, so they cannot answer approval prompts.
+        let turn_params = json!({
+            "threadId": thread_id,
+            "input": [{ "type": "text", "text": text }],
+            "cwd": session.entry.path,
+            "approvalPolicy": "never",
+            "sandboxPolicy": { "type": "readOnly" },
+            "model": model,
+        });
+        let response = session.send_request("turn/start", turn_params).await?;
+        if let Some(error) = response_error(&response) {
+            return Err(error);
+        }
+        cleanup.active_turn = response_turn_id(&response);
+        let completed = timeout(REPLAY_TURN_TIMEOUT, async {
+            loop {
+                let Some(event) = rx.recv().await else {
+                    return Err("Replay stream closed before the turn completed".to_string());
+                };
+                match event.get("method").and_then(Value::as_str).unwrap_or("") {
+                    "turn/completed" => return Ok(()),
+                    "turn/error" => {
+                        let error = event
+                            .get("params")
+                            .and_then(|params| params.get("error"))
+                            .and_then(Value::as_str)
+                            .unwrap_or("Replay turn failed");
+                        return Err(error.to_string());
+                    }
+                    _ => {}
+                }
+            }
+        })
+        .await;
+        match completed {
+            Ok(Ok(())) => cleanup.active_turn = None,
+            Ok(Err(error)) => {
+                cleanup.active_turn = None;
+                return Err(error);
+            }
+            // The turn may still be running; `cleanup` interrupts it.
+            Err(_) => return Err(format!("Replay turn {} timed out", index + 1)),
+        }
+    }
+    on_progress(turns.len(), turns.len());
+    Ok(())
+}
+
+/// Starts a fresh thread and sends it the source thread's user messages one
+/// turn at a time on `model`.
+pub(crate) async fn replay_thread_core<F>(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: String,
+    source_thread_id: String,
+    model: String,
+    on_progress: F,
+) -> Result<ThreadReplayResult, String>
+where
+    F: Fn(usize, usize),
+{
+    let model = model.trim().to_string();
+    if model.is_empty() {
+        return Err("Model is required.".to_string());
+    }
+    let session = sessions
+        .lock()
+        .await
+        .get(&workspace_id)
+        .cloned()
+        .ok_or_else(|| "workspace not connected".to_string())?;
+
+    let source = read_thread(&session, &source_thread_id).await?;
+    let turns = extract_user_turns(&source);
+    if turns.is_empty() {
+        return Err("Source thread has no user messages to replay.".to_string());
+    }
+
+    let start_params = json!({
+        "cwd": session.entry.path,
+        "approvalPolicy": "never",
+        "model": model,
+    });
+    let start_response = session.send_request("thread/start", start_params).await?;
+    if let Some(error) = response_error(&start_response) {
+        return Err(error);
+    }
+    let thread_id = response_thread_id(&start_response)
+        .ok_or_else(|| "Failed to read threadId from thread/start response".to_string())?;
+
+    let (tx, mut rx) = mpsc::unbounded_channel::<Value>();
+    session
+        .background_thread_callbacks
+        .lock()
+        .await
+        .insert(thread_id.clone(), tx);
+    let mut cleanup = ReplayCleanup {
+        session: Arc::clone(&session),
+        thread_id: thread_id.clone(),
+        active_turn: None,
+        armed: true,
+    };
+    let result = run_replay_turns(
+        &session,
+        &thread_id,
+        &model,
+        &turns,
+        &mut rx,
+        &mut cleanup,
+        on_progress,
+    )
+    .await;
+    cleanup.finish().await;
+    result?;
+
+    Ok(ThreadReplayResult {
+        thread_id,
+        source_thread_id,
+        model,
+        turn_count: turns.len(),
+    })
+}
+
+pub(crate) async fn diff_threads_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: String,
+    thread_a: String,
+    thread_b: String,
+) -> Result<ThreadDiff, String> {
+    let session = sessions
+        .lock()
+        .await
+        .get(&workspace_id)
+        .cloned()
+        .ok_or_else(|| "workspace not connected".to_string())?;
+    let a = read_thread(&session, &thread_a).await?;
+    let b = read_thread(&session, &thread_b).await?;
+    Ok(ThreadDiff {
+        turns: align_thread_turns(summarize_thread_turns(&a), summarize_thread_turns(&b)),
+        thread_a,
+        thread_b,
+    })
+}
+
+/// Every item across all turns, in transcript order. Offsets returned by
+/// `get_thread_window` index into this list.
+fn flatten_thread_items(thread: &Value) -> Vec<&Value> {
+    thread_turn_items(thread)
+        .into_iter()
+        .flat_map(|items| items.iter())
+        .collect()
+}
+
+pub(crate) fn thread_window(
+    thread: &Value,
+    thread_id: String,
+    around_message_id: &str,
+    radius: Option<u32>,
+) -> Result<ThreadWindow, String> {
+    let items = flatten_thread_items(thread);
+    let index = items
+        .iter()
+        .position(|item| item.get("id").and_then(Value::as_str) == Some(around_message_id))
+        .ok_or_else(|| format!("Message {around_message_id} not found in thread {thread_id}"))?;
+    let radius = radius
+        .map(|value| value as usize)
+        .unwrap_or(DEFAULT_WINDOW_RADIUS)
+        .min(MAX_WINDOW_RADIUS);
+    let offset = index.saturating_sub(radius);
+    let end = (index + radius + 1).min(items.len());
+    Ok(ThreadWindow {
+        thread_id,
+        total: items.len(),
+        offset,
+        anchor_index: index,
+        items: items[offset..end].iter().copied().cloned().collect(),
+    })
+}
+
+/// Returns the items within `radius` of one message so the UI can expand
+/// collapsed history without loading the whole transcript.
+pub(crate) async fn get_thread_window_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: String,
+    thread_id: String,
+    around_message_id: String,
+    radius: Option<u32>,
+) -> Result<ThreadWindow, String> {
+    let session = sessions
+        .lock()
+        .await
+        .get(&workspace_id)
+        .cloned()
+        .ok_or_else(|| "workspace not connected".to_string())?;
+    let thread = read_thread(&session, &thread_id).await?;
+    thread_window(&thread, thread_id, around_message_id.trim(), radius)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_user_turns_skips_tool_items() {
+        let thread = json!({
+            "turns": [
+                {
+                    "items": [
+                        {
+                            "type": "userMessage",
+                            "content": [
+                                { "type": "text", "text": "Fix the build" },
+                                { "type": "localImage", "path": "/tmp/shot.png" }
+                            ]
+                        },
+                        { "type": "commandExecution", "command": "cargo build" },
+                        { "type": "agentMessage", "text": "Done" }
+                    ]
+                },
+                {
+                    "items": [
+                        {
+                            "type": "userMessage",
+                            "content": [
+                                { "type": "skill", "name": "review" },
+                                { "type": "text", "text": "the diff" }
+                            ]
+                        }
+                    ]
+                },
+                { "items": [{ "type": "userMessage", "content": [] }] }
+            ]
+        });
+        assert_eq!(
+            extract_user_turns(&thread),
+            vec!["Fix the build".to_string(), "$review the diff".to_string()]
+        );
+    }
+
+    #[test]
+    fn response_thread_id_reads_nested_thread() {
+        let response = json!({ "result": { "thread": { "id": "thread-9" } } });
+        assert_eq!(response_thread_id(&response), Some("thread-9".to_string()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn dropping_a_replay_unregisters_it_and_interrupts_the_turn() {
+        use crate::types::{WorkspaceEntry, WorkspaceKind, WorkspaceSettings};
+        use std::process::Stdio;
+        use tokio::io::{AsyncBufReadExt, BufReader};
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        runtime.block_on(async {
+            let entry = WorkspaceEntry {
+                id: "w1".to_string(),
+                name: "w1".to_string(),
+                path: std::env::temp_dir().to_string_lossy().to_string(),
+                codex_bin: None,
+                kind: WorkspaceKind::Main,
+                parent_id: None,
+                worktree: None,
+                settings: WorkspaceSettings::default(),
+            };
+            // `cat` hands back whatever the session writes to app-server.
+            let mut child = tokio::process::Command::new("cat")
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .kill_on_drop(true)
+                .spawn()
+                .expect("spawn cat");
+            let stdin = child.stdin.take().expect("stdin");
+            let mut written = BufReader::new(child.stdout.take().expect("stdout")).lines();
+            let session = Arc::new(WorkspaceSession::new(entry, child, stdin));
+            let (tx, _rx) = mpsc::unbounded_channel::<Value>();
+            session
+                .background_thread_callbacks
+                .lock()
+                .await
+                .insert("replay-1".to_string(), tx);
+
+            drop(ReplayCleanup {
+                session: Arc::clone(&session),
+                thread_id: "replay-1".to_string(),
+                active_turn: Some("turn-3".to_string()),
+                armed: true,
+            });
+
+            let line = written.next_line().await.expect("read").expect("request");
+            let request: Value = serde_json::from_str(&line).expect("json");
+            assert_eq!(request["method"], "turn/interrupt");
+            assert_eq!(request["params"]["threadId"], "replay-1");
+            assert_eq!(request["params"]["turnId"], "turn-3");
+            assert!(session.background_thread_callbacks.lock().await.is_empty());
+        });
+    }
+
+    #[test]
+    fn align_thread_turns_pairs_by_user_message_order() {
+        let a = json!({
+            "turns": [
+                {
+                    "status": "completed",
+                    "startedAt": 100,
+                    "completedAt": 400,
+                    "items": [
+                        { "type": "userMessage", "content": [{ "type": "text", "text": "one" }] },
+                        { "type": "commandExecution", "command": "ls" },
+                        { "type": "agentMessage", "text": "first" },
+                        { "type": "agentMessage", "text": "second" }
+                    ]
+                },
+                { "items": [{ "type": "contextCompaction" }] },
+                {
+                    "items": [
+                        { "type": "userMessage", "content": [{ "type": "text", "text": "two" }] }
+                    ]
+                }
+            ]
+        });
+        let b = json!({
+            "turns": [
+                {
+                    "items": [
+                        { "type": "userMessage", "content": [{ "type": "text", "text": "one" }] },
+                        { "type": "agentMessage", "text": "other" }
+                    ]
+                }
+            ]
+        });
+        let turns = align_thread_turns(summarize_thread_turns(&a), summarize_thread_turns(&b));
+        assert_eq!(turns.len(), 2);
+        assert!(turns[0].prompts_match);
+        let first = turns[0].a.as_ref().expect("turn a");
+        assert_eq!(first.response, "first\n\nsecond");
+        assert_eq!(first.tool_call_count, 1);
+        assert_eq!(first.duration_ms, Some(300));
+        assert_eq!(
+            turns[0].b.as_ref().map(|turn| turn.response.as_str()),
+            Some("other")
+        );
+        assert!(!turns[1].prompts_match);
+        assert!(turns[1].b.is_none());
+    }
+
+    #[test]
+    fn thread_window_clamps_around_the_anchor() {
+        let thread = json!({
+            "turns": [
+                { "items": [{ "id": "a" }, { "id": "b" }, { "id": "c" }] },
+                { "items": [{ "id": "d" }, { "id": "e" }] }
+            ]
+        });
+        let window = thread_window(&thread, "t".to_string(), "b", Some(2)).expect("window");
+        assert_eq!(window.total, 5);
+        assert_eq!(window.offset, 0);
+        assert_eq!(window.anchor_index, 1);
+        assert_eq!(window.items.len(), 4);
+
+        let window = thread_window(&thread, "t".to_string(), "e", Some(1)).expect("window");
+        assert_eq!(window.offset, 3);
+        assert_eq!(
+            window.items,
+            vec![json!({ "id": "d" }), json!({ "id": "e" })]
+        );
+
+        assert!(thread_window(&thread, "t".to_string(), "missing", None).is_err());
+    }
+}
@@ -0,0 +1,411 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use tokio::sync::Mutex;
+
+use crate::backend::app_server::WorkspaceSession;
+use crate::shared::redaction_core;
+use crate::shared::thread_analysis_core::read_thread;
+use crate::types::ThreadExport;
+
+const EXPORT_FORMATS: &[&str] = &["markdown"];
+
+/// `createdAt` is in seconds and turn times are in milliseconds, so anything
+/// too small to be a millisecond timestamp is read as seconds.
+fn format_timestamp(value: &Value) -> Option<String> {
+    let value = value.as_i64().filter(|value| *value > 0)?;
+    let millis = if value < 1_000_000_000_000 {
+        value * 1000
+    } else {
+        value
+    };
+    DateTime::<Utc>::from_timestamp_millis(millis)
+        .map(|time| time.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+}
+
+/// A fenced block whose fence is longer than any run of backticks in
+/// `body`, so output that contains fences of its own can't close it early.
+fn fenced(language: &str, body: &str) -> String {
+    let longest_run = body.split(|ch| ch != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    format!(
+        "{fence}{language}\n{}\n{fence}",
+        body.trim_end_matches('\n')
+    )
+}
+
+fn text_field<'a>(item: &'a Value, key: &str) -> Option<&'a str> {
+    item.get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+/// Reasoning summaries and content come as a list of strings or one string.
+fn joined_text(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(text)) => text.trim().to_string(),
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+        _ => String::new(),
+    }
+}
+
+fn blockquote(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            if line.is_empty() {
+                ">".to_string()
+            } else {
+                format!("> {line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_user_message(item: &Value) -> Option<String> {
+    let mut text = Vec::new();
+    let mut images = Vec::new();
+    for input in item.get("content").and_then(Value::as_array)? {
+        match input.get("type").and_then(Value::as_str) {
+            Some("text") => text.extend(text_field(input, "text").map(str::to_string)),
+            Some("skill") => text.extend(text_field(input, "name").map(|name| format!("${name}"))),
+            Some("image") => images.extend(text_field(input, "url")),
+            Some("localImage") => images.extend(text_field(input, "path")),
+            _ => {}
+        }
+    }
+    let mut body = text.join(" ");
+    for image in images {
+        if !body.is_empty() {
+            body.push_str("\n\n");
+        }
+        body.push_str(&format!("![image]({image})"));
+    }
+    (!body.is_empty()).then(|| format!("### User\n\n{body}"))
+}
+
+fn render_command(item: &Value) -> String {
+    let command = match item.get("command") {
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(" "),
+        Some(Value::String(command)) => command.clone(),
+        _ => String::new(),
+    };
+    let mut section = format!("### Command\n\n{}", fenced("sh", &command));
+    let mut details = Vec::new();
+    if let Some(cwd) = text_field(item, "cwd") {
+        details.push(format!("in `{cwd}`"));
+    }
+    if let Some(code) = item.get("exitCode").and_then(Value::as_i64) {
+        details.push(format!("exit code {code}"));
+    }
+    if !details.is_empty() {
+        section.push_str(&format!("\n\n_{}_", details.join(", ")));
+    }
+    if let Some(output) = text_field(item, "aggregatedOutput") {
+        section.push_str(&format!("\n\n{}", fenced("text", output)));
+    }
+    section
+}
+
+fn change_kind(change: &Value) -> Option<String> {
+    match change.get("kind") {
+        Some(Value::String(kind)) => Some(kind.to_lowercase()),
+        Some(kind) => text_field(kind, "type").map(str::to_lowercase),
+        None => None,
+    }
+}
+
+fn render_file_changes(item: &Value) -> String {
+    let changes = item
+        .get("changes")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or(&[]);
+    let mut section = "### File changes".to_string();
+    for change in changes {
+        let Some(path) = text_field(change, "path") else {
+            continue;
+        };
+        section.push_str(&format!("\n\n`{path}`"));
+        if let Some(kind) = change_kind(change) {
+            section.push_str(&format!(" ({kind})"));
+        }
+        if let Some(diff) = text_field(change, "diff") {
+            section.push_str(&format!("\n\n{}", fenced("diff", diff)));
+        }
+    }
+    section
+}
+
+fn json_block(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(text) if text.trim().is_empty() => None,
+        Value::String(text) => Some(fenced("text", text)),
+        value => serde_json::to_string_pretty(value)
+            .ok()
+            .map(|json| fenced("json", &json)),
+    }
+}
+
+fn render_tool_call(item: &Value) -> String {
+    let server = text_field(item, "server").unwrap_or("");
+    let tool = text_field(item, "tool").unwrap_or("");
+    let name = match (server.is_empty(), tool.is_empty()) {
+        (false, false) => format!("{server} / {tool}"),
+        (true, false) => tool.to_string(),
+        _ => server.to_string(),
+    };
+    let mut section = format!("### Tool call: {name}");
+    if let Some(arguments) = item.get("arguments").and_then(json_block) {
+        section.push_str(&format!("\n\n{arguments}"));
+    }
+    let result = item
+        .get("result")
+        .filter(|result| !result.is_null())
+        .or_else(|| item.get("error"));
+    if let Some(result) = result.and_then(json_block) {
+        section.push_str(&format!("\n\n{result}"));
+    }
+    section
+}
+
+fn render_item(item: &Value) -> Option<String> {
+    match item.get("type").and_then(Value::as_str)? {
+        "userMessage" => render_user_message(item),
+        "agentMessage" => text_field(item, "text").map(|text| format!("### Assistant\n\n{text}")),
+        "reasoning" => {
+            let summary = joined_text(item.get("summary"));
+            let content = joined_text(item.get("content"));
+            let text = [summary, content]
+                .into_iter()
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            (!text.is_empty()).then(|| format!("### Reasoning\n\n{}", blockquote(&text)))
+        }
+        "plan" => text_field(item, "text").map(|text| format!("### Plan\n\n{text}")),
+        "commandExecution" => Some(render_command(item)),
+        "fileChange" => Some(render_file_changes(item)),
+        "mcpToolCall" => Some(render_tool_call(item)),
+        "webSearch" => text_field(item, "query").map(|query| format!("### Web search\n\n{query}")),
+        "imageView" => text_field(item, "path").map(|path| format!("### Image view\n\n`{path}`")),
+        "contextCompaction" => Some("_Context compacted._".to_string()),
+        _ => None,
+    }
+}
+
+/// The whole thread as Markdown: a title and the thread's dates, then each
+/// turn with its start time and items in transcript order. Commands, diffs
+/// and tool results go in fenced blocks and reasoning is quoted.
+pub(crate) fn render_thread_markdown(thread: &Value, thread_id: &str) -> String {
+    let title = text_field(thread, "name")
+        .or_else(|| text_field(thread, "preview"))
+        .and_then(|title| title.lines().next())
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("Thread {thread_id}"));
+    let mut sections = vec![format!("# {title}")];
+    let mut details = vec![format!("- Thread: `{thread_id}`")];
+    if let Some(created) = thread.get("createdAt").and_then(format_timestamp) {
+        details.push(format!("- Created: {created}"));
+    }
+    if let Some(updated) = thread.get("updatedAt").and_then(format_timestamp) {
+        details.push(format!("- Updated: {updated}"));
+    }
+    sections.push(details.join("\n"));
+
+    let turns = thread
+        .get("turns")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or(&[]);
+    for (index, turn) in turns.iter().enumerate() {
+        let items: Vec<String> = turn
+            .get("items")
+            .and_then(Value::as_array)
+            .map(|items| items.iter().filter_map(render_item).collect())
+            .unwrap_or_default();
+        if items.is_empty() {
+            continue;
+        }
+        let mut heading = format!("## Turn {}", index + 1);
+        if let Some(started) = turn.get("startedAt").and_then(format_timestamp) {
+            heading.push_str(&format!(" · {started}"));
+        }
+        sections.push(heading);
+        sections.extend(items);
+    }
+    let mut markdown = sections.join("\n\n");
+    markdown.push('\n');
+    markdown
+}
+
+/// Renders a thread for saving or sharing, with secrets redacted when
+/// redaction is on. With `destination` the result is also written there.
+pub(crate) async fn export_thread_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: String,
+    thread_id: String,
+    format: Option<String>,
+    destination: Option<String>,
+) -> Result<ThreadExport, String> {
+    let format = format
+        .map(|format| format.trim().to_ascii_lowercase())
+        .filter(|format| !format.is_empty())
+        .unwrap_or_else(|| "markdown".to_string());
+    let format = if format == "md" {
+        "markdown".to_string()
+    } else {
+        format
+    };
+    if !EXPORT_FORMATS.contains(&format.as_str()) {
+        return Err(format!("Unknown export format `{format}`; use markdown."));
+    }
+    let session = sessions
+        .lock()
+        .await
+        .get(&workspace_id)
+        .cloned()
+        .ok_or_else(|| "workspace not connected".to_string())?;
+    let thread = read_thread(&session, &thread_id).await?;
+    let mut content = render_thread_markdown(&thread, &thread_id);
+    if let Some(redactor) = redaction_core::current_redactor() {
+        content = redactor.redact_text(&content);
+    }
+    let mut export = ThreadExport {
+        thread_id,
+        format,
+        content,
+        path: None,
+    };
+    if let Some(destination) = destination {
+        write_thread_export(&mut export, &destination).await?;
+    }
+    Ok(export)
+}
+
+/// Writes a rendered export to `destination`, a file path the user picked.
+pub(crate) async fn write_thread_export(
+    export: &mut ThreadExport,
+    destination: &str,
+) -> Result<(), String> {
+    let destination = destination.trim();
+    if destination.is_empty() {
+        return Ok(());
+    }
+    let path = PathBuf::from(destination);
+    tokio::fs::write(&path, &export.content)
+        .await
+        .map_err(|err| format!("Failed to write {}: {err}", path.display()))?;
+    export.path = Some(path.to_string_lossy().to_string());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn render_thread_markdown_keeps_order_and_fences_tool_output() {
+        let thread = json!({
+            "name": "Fix the build",
+            "createdAt": 1_760_000_000,
+            "turns": [
+                {
+                    "startedAt": 1_760_000_005_000_i64,
+                    "items": [
+                        {
+                            "type": "userMessage",
+                            "content": [
+                                { "type": "text", "text": "Why does it fail?" },
+                                { "type": "localImage", "path": "/tmp/shot.png" }
+                            ]
+                        },
+                        { "type": "reasoning", "summary": ["Check the logs"], "content": [] },
+                        {
+                            "type": "commandExecution",
+                            "command": "cargo build",
+                            "cwd": "/repo",
+                            "exitCode": 101,
+                            "aggregatedOutput": "error: ```oops```\n"
+                        },
+                        {
+                            "type": "fileChange",
+                            "changes": [
+                                { "path": "src/lib.rs", "kind": { "type": "update" }, "diff": "-a\n+b" }
+                            ]
+                        },
+                        { "type": "agentMessage", "text": "Fixed." }
+                    ]
+                },
+                { "items": [] }
+            ]
+        });
+        let markdown = render_thread_markdown(&thread, "thread-1");
+        let expected = "\
+# Fix the build
+
+- Thread: `thread-1`
+- Created: 2025-10-09 08:53:20 UTC
+
+## Turn 1 · 2025-10-09 08:53:25 UTC
+
+### User
+
+Why does it fail?
+
+![image](/tmp/shot.png)
+
+### Reasoning
+
+> Check the logs
+
+### Command
+
+```sh
+cargo build
+```
+
+_in `/repo`, exit code 101_
+
+````text
+error: ```oops```
+````
+
+### File changes
+
+`src/lib.rs` (update)
+
+```diff
+-a
++b
+```
+
+### Assistant
+
+Fixed.
+";
+        assert_eq!(markdown, expected);
+    }
+
+    #[test]
+    fn render_thread_markdown_falls_back_to_the_thread_id() {
+        let markdown = render_thread_markdown(&json!({ "turns": [] }), "thread-2");
+        assert_eq!(markdown, "# Thread thread-2\n\n- Thread: `thread-2`\n");
+    }
+}
@@ -0,0 +1,393 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use serde_json::Value;
+use tokio::sync::Mutex;
+
+use crate::codex::home::resolve_workspace_codex_home;
+use crate::shared::redaction_core;
+use crate::types::{ThreadSearchResult, WorkspaceEntry};
+
+/// Text longer than this is skipped unless the caller sets its own cap, so
+/// a giant command dump doesn't dominate the scan or the results.
+const DEFAULT_MAX_MESSAGE_BYTES: usize = 64 * 1024;
+const MAX_RESULTS: usize = 50;
+const SNIPPET_CONTEXT_CHARS: usize = 60;
+const PREVIEW_CHARS: usize = 120;
+
+struct RolloutFile {
+    path: PathBuf,
+    archived: bool,
+}
+
+/// The searchable text of one rollout line: message content and tool
+/// output. Event lines repeat what the response items already hold, so
+/// they're left out to keep match counts honest.
+fn line_texts(value: &Value) -> Vec<(&str, Option<&str>)> {
+    if value.get("type").and_then(Value::as_str) != Some("response_item") {
+        return Vec::new();
+    }
+    let Some(payload) = value.get("payload") else {
+        return Vec::new();
+    };
+    match payload.get("type").and_then(Value::as_str) {
+        Some("message") => {
+            let role = payload.get("role").and_then(Value::as_str);
+            payload
+                .get("content")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|part| part.get("text").and_then(Value::as_str))
+                .map(|text| (text, role))
+                .collect()
+        }
+        Some("function_call_output" | "custom_tool_call_output") => {
+            let output = payload.get("output");
+            let text = output
+                .and_then(Value::as_str)
+                .or_else(|| output.and_then(|output| output.get("content")?.as_str()));
+            text.map(|text| vec![(text, None)]).unwrap_or_default()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Lowercases `text` and keeps, for each byte of the result, the offset of
+/// the character it came from, since lowercasing can change byte lengths.
+fn fold_case(text: &str) -> (String, Vec<usize>) {
+    let mut folded = String::with_capacity(text.len());
+    let mut offsets = Vec::with_capacity(text.len() + 1);
+    for (offset, ch) in text.char_indices() {
+        for lower in ch.to_lowercase() {
+            folded.push(lower);
+            offsets.resize(folded.len(), offset);
+        }
+    }
+    offsets.push(text.len());
+    (folded, offsets)
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The match at `start..end` of `text` with some context on either side.
+fn snippet_around(text: &str, start: usize, end: usize) -> String {
+    let before: Vec<(usize, char)> = text[..start].char_indices().collect();
+    let from = before
+        .len()
+        .checked_sub(SNIPPET_CONTEXT_CHARS)
+        .map(|index| before[index].0)
+        .unwrap_or(0);
+    let to = text[end..]
+        .char_indices()
+        .nth(SNIPPET_CONTEXT_CHARS)
+        .map(|(index, _)| end + index)
+        .unwrap_or(text.len());
+    let mut snippet = collapse_whitespace(&text[from..to]);
+    if from > 0 {
+        snippet.insert(0, '…');
+    }
+    if to < text.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
+/// Injected context (environment, AGENTS.md) arrives as user messages that
+/// open with a tag; those don't make a useful preview.
+fn preview_text(text: &str) -> Option<String> {
+    let text = text.trim();
+    if text.is_empty() || text.starts_with('<') {
+        return None;
+    }
+    let preview = collapse_whitespace(text);
+    if preview.chars().count() <= PREVIEW_CHARS {
+        return Some(preview);
+    }
+    let mut preview: String = preview.chars().take(PREVIEW_CHARS).collect();
+    preview.push('…');
+    Some(preview)
+}
+
+fn path_matches_workspace(cwd: &str, workspace_path: &Path) -> bool {
+    let cwd_path = Path::new(cwd);
+    cwd_path == workspace_path || cwd_path.starts_with(workspace_path)
+}
+
+fn modified_ms(path: &Path) -> i64 {
+    path.metadata()
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+fn collect_rollouts(dir: &Path, archived: bool, files: &mut Vec<RolloutFile>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_rollouts(&path, archived, files);
+        } else if path.extension().and_then(|ext| ext.to_str()) == Some("jsonl") {
+            files.push(RolloutFile { path, archived });
+        }
+    }
+}
+
+/// Scans one rollout for `query`, which is already lowercased. Returns
+/// `None` when the rollout belongs to another workspace or doesn't match.
+fn search_rollout(
+    file: &RolloutFile,
+    workspace_path: &Path,
+    query: &str,
+    max_message_bytes: usize,
+) -> Option<ThreadSearchResult> {
+    let reader = BufReader::new(File::open(&file.path).ok()?);
+    let mut thread_id: Option<String> = None;
+    let mut preview: Option<String> = None;
+    let mut snippet: Option<String> = None;
+    let mut match_count = 0;
+    for line in reader.lines() {
+        let Ok(line) = line else {
+            continue;
+        };
+        let Ok(value) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        if value.get("type").and_then(Value::as_str) == Some("session_meta") {
+            let payload = value.get("payload")?;
+            let cwd = payload.get("cwd").and_then(Value::as_str)?;
+            if !path_matches_workspace(cwd, workspace_path) {
+                return None;
+            }
+            thread_id = payload
+                .get("id")
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            continue;
+        }
+        thread_id.as_ref()?;
+        for (text, role) in line_texts(&value) {
+            if text.len() > max_message_bytes {
+                continue;
+            }
+            if preview.is_none() && role == Some("user") {
+                preview = preview_text(text);
+            }
+            let (folded, offsets) = fold_case(text);
+            let mut matches = folded.match_indices(query);
+            let Some((start, _)) = matches.next() else {
+                continue;
+            };
+            match_count += 1 + matches.count();
+            if snippet.is_none() {
+                let end = offsets[start + query.len()];
+                snippet = Some(snippet_around(text, offsets[start], end));
+            }
+        }
+    }
+    if match_count == 0 {
+        return None;
+    }
+    let mut snippet = snippet.unwrap_or_default();
+    if let Some(redactor) = redaction_core::current_redactor() {
+        snippet = redactor.redact_text(&snippet);
+        preview = preview.map(|preview| redactor.redact_text(&preview));
+    }
+    Some(ThreadSearchResult {
+        thread_id: thread_id?,
+        preview,
+        snippet,
+        match_count,
+        updated_at: modified_ms(&file.path),
+        archived: file.archived,
+    })
+}
+
+/// Most matches first, then the most recently updated thread.
+fn rank_results(results: &mut Vec<ThreadSearchResult>) {
+    results.sort_by(|a, b| {
+        b.match_count
+            .cmp(&a.match_count)
+            .then(b.updated_at.cmp(&a.updated_at))
+    });
+    results.truncate(MAX_RESULTS);
+}
+
+fn search_rollouts(
+    codex_home: &Path,
+    workspace_path: &Path,
+    query: &str,
+    include_archived: bool,
+    max_message_bytes: usize,
+) -> Vec<ThreadSearchResult> {
+    let mut files = Vec::new();
+    collect_rollouts(&codex_home.join("sessions"), false, &mut files);
+    if include_archived {
+        collect_rollouts(&codex_home.join("archived_sessions"), true, &mut files);
+    }
+    let mut results: Vec<ThreadSearchResult> = files
+        .iter()
+        .filter_map(|file| search_rollout(file, workspace_path, query, max_message_bytes))
+        .collect();
+    rank_results(&mut results);
+    results
+}
+
+/// Case-insensitive substring search over the workspace's stored thread
+/// transcripts. The rollout files are read straight from CODEX_HOME, so
+/// threads are found without a running session.
+pub(crate) async fn search_threads_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    query: String,
+    include_archived: bool,
+    max_message_bytes: Option<usize>,
+) -> Result<Vec<ThreadSearchResult>, String> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Err("Search query is required.".to_string());
+    }
+    let (codex_home, workspace_path) = {
+        let workspaces = workspaces.lock().await;
+        let entry = workspaces
+            .get(&workspace_id)
+            .ok_or_else(|| "workspace not found".to_string())?;
+        let parent_entry = entry
+            .parent_id
+            .as_ref()
+            .and_then(|parent_id| workspaces.get(parent_id));
+        let codex_home = resolve_workspace_codex_home(entry, parent_entry)
+            .ok_or_else(|| "Unable to resolve CODEX_HOME".to_string())?;
+        (codex_home, PathBuf::from(&entry.path))
+    };
+    let max_message_bytes = max_message_bytes.unwrap_or(DEFAULT_MAX_MESSAGE_BYTES);
+    tokio::task::spawn_blocking(move || {
+        search_rollouts(
+            &codex_home,
+            &workspace_path,
+            &query,
+            include_archived,
+            max_message_bytes,
+        )
+    })
+    .await
+    .map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io::Write;
+    use uuid::Uuid;
+
+    fn write_rollout(dir: &Path, id: &str, cwd: &str, lines: &[Value]) {
+        fs::create_dir_all(dir).expect("create rollout dir");
+        let path = dir.join(format!("rollout-{id}.jsonl"));
+        let mut file = File::create(path).expect("create rollout");
+        let meta = serde_json::json!({
+            "type": "session_meta",
+            "payload": { "id": id, "cwd": cwd },
+        });
+        writeln!(file, "{meta}").expect("write session meta");
+        for line in lines {
+            writeln!(file, "{line}").expect("write rollout line");
+        }
+    }
+
+    fn message(role: &str, text: &str) -> Value {
+        serde_json::json!({
+            "type": "response_item",
+            "payload": {
+                "type": "message",
+                "role": role,
+                "content": [{ "type": "input_text", "text": text }],
+            },
+        })
+    }
+
+    fn tool_output(output: &str) -> Value {
+        serde_json::json!({
+            "type": "response_item",
+            "payload": { "type": "function_call_output", "call_id": "1", "output": output },
+        })
+    }
+
+    #[test]
+    fn search_rollouts_ranks_by_matches_and_respects_workspace_and_archive() {
+        let home = std::env::temp_dir().join(format!("codexmonitor-search-{}", Uuid::new_v4()));
+        let day = home.join("sessions").join("2026").join("01").join("19");
+        write_rollout(
+            &day,
+            "one",
+            "/work/app",
+            &[
+                message("user", "Fix the AUTH bug"),
+                tool_output("auth: token expired\nauth: retrying"),
+            ],
+        );
+        write_rollout(
+            &day,
+            "two",
+            "/work/app/sub",
+            &[message("assistant", "auth is fine")],
+        );
+        write_rollout(
+            &day,
+            "other",
+            "/work/other",
+            &[message("user", "auth elsewhere")],
+        );
+        write_rollout(
+            &day,
+            "dump",
+            "/work/app",
+            &[tool_output(&format!("auth {}", "x".repeat(200)))],
+        );
+        write_rollout(
+            &home.join("archived_sessions"),
+            "old",
+            "/work/app",
+            &[message("user", "old auth notes")],
+        );
+
+        let results = search_rollouts(&home, Path::new("/work/app"), "auth", false, 100);
+        let ids: Vec<&str> = results
+            .iter()
+            .map(|result| result.thread_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["one", "two"]);
+        assert_eq!(results[0].match_count, 3);
+        assert_eq!(results[0].snippet, "Fix the AUTH bug");
+        assert_eq!(results[0].preview.as_deref(), Some("Fix the AUTH bug"));
+        assert!(!results[0].archived);
+
+        let results = search_rollouts(&home, Path::new("/work/app"), "old auth", true, 100);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].thread_id, "old");
+        assert!(results[0].archived);
+
+        fs::remove_dir_all(home).expect("remove temp codex home");
+    }
+
+    #[test]
+    fn snippet_around_keeps_context_and_marks_truncation() {
+        let text = format!("{} needle {}", "a".repeat(100), "b".repeat(100));
+        let (folded, offsets) = fold_case(&text);
+        let start = folded.find("needle").expect("match");
+        let snippet = snippet_around(&text, offsets[start], offsets[start + 6]);
+        assert!(snippet.starts_with('…'));
+        assert!(snippet.ends_with('…'));
+        assert!(snippet.contains(" needle "));
+        assert_eq!(snippet.chars().count(), 2 * SNIPPET_CONTEXT_CHARS + 6 + 2);
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::shared::json_store_core::{read_json_store, update_json_store};
+
+const TITLES_FILE_NAME: &str = "thread_titles.json";
+
+/// Titles by workspace id, then thread id.
+type TitleStore = HashMap<String, HashMap<String, String>>;
+
+fn titles_path(storage_path: &Path) -> PathBuf {
+    storage_path.with_file_name(TITLES_FILE_NAME)
+}
+
+/// Stores a user-chosen title for the thread. An empty title drops it, so
+/// the thread falls back to its generated name. Returns the stored title.
+pub(crate) fn rename_thread_core(
+    storage_path: &Path,
+    workspace_id: String,
+    thread_id: String,
+    title: String,
+) -> Result<Option<String>, String> {
+    let thread_id = thread_id.trim().to_string();
+    if thread_id.is_empty() {
+        return Err("Thread id is required.".to_string());
+    }
+    let title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+    update_json_store(&titles_path(storage_path), |titles: &mut TitleStore| {
+        if title.is_empty() {
+            if let Some(workspace_titles) = titles.get_mut(&workspace_id) {
+                workspace_titles.remove(&thread_id);
+                if workspace_titles.is_empty() {
+                    titles.remove(&workspace_id);
+                }
+            }
+            return None;
+        }
+        titles
+            .entry(workspace_id)
+            .or_default()
+            .insert(thread_id, title.clone());
+        Some(title)
+    })
+}
+
+/// Sets `name` on each thread of a `thread/list` response that has a
+/// stored title, keeping the generated one as `generatedName`. A titles
+/// file that can't be read leaves the response as Codex sent it.
+pub(crate) fn apply_thread_titles(storage_path: &Path, workspace_id: &str, response: &mut Value) {
+    let mut titles: TitleStore = match read_json_store(&titles_path(storage_path)) {
+        Ok(titles) => titles,
+        Err(error) => {
+            eprintln!("Failed to read thread titles: {error}");
+            return;
+        }
+    };
+    let Some(titles) = titles.remove(workspace_id) else {
+        return;
+    };
+    let result = if response.get("result").is_some() {
+        &mut response["result"]
+    } else {
+        response
+    };
+    let Some(threads) = result.get_mut("data").and_then(Value::as_array_mut) else {
+        return;
+    };
+    for thread in threads {
+        let Some(title) = thread
+            .get("id")
+            .and_then(Value::as_str)
+            .and_then(|id| titles.get(id))
+        else {
+            continue;
+        };
+        let Some(thread) = thread.as_object_mut() else {
+            continue;
+        };
+        let generated = thread.get("name").cloned().unwrap_or(Value::Null);
+        thread.insert("generatedName".to_string(), generated);
+        thread.insert("name".to_string(), Value::String(title.clone()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    #[test]
+    fn titles_apply_to_thread_list_and_reset_when_empty() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-titles-{}", Uuid::new_v4()));
+        let storage_path = dir.join("workspaces.json");
+
+        let stored = rename_thread_core(
+            &storage_path,
+            "ws".into(),
+            "t1".into(),
+            "  Auth   bug fix ".into(),
+        )
+        .expect("rename t1");
+        assert_eq!(stored.as_deref(), Some("Auth bug fix"));
+
+        let mut response = json!({
+            "result": {
+                "data": [
+                    { "id": "t1", "name": "Generated", "preview": "fix auth" },
+                    { "id": "t2", "preview": "other" },
+                ],
+            },
+        });
+        apply_thread_titles(&storage_path, "ws", &mut response);
+        assert_eq!(response["result"]["data"][0]["name"], "Auth bug fix");
+        assert_eq!(response["result"]["data"][0]["generatedName"], "Generated");
+        assert!(response["result"]["data"][1].get("name").is_none());
+        let mut other = json!({ "data": [{ "id": "t1", "name": "Generated" }] });
+        apply_thread_titles(&storage_path, "other", &mut other);
+        assert_eq!(other["data"][0]["name"], "Generated");
+
+        let stored = rename_thread_core(&storage_path, "ws".into(), "t1".into(), "  ".into())
+            .expect("reset");
+        assert_eq!(stored, None);
+        let mut response = json!({ "data": [{ "id": "t1", "name": "Generated" }] });
+        apply_thread_titles(&storage_path, "ws", &mut response);
+        assert_eq!(response["data"][0]["name"], "Generated");
+        assert!(response["data"][0].get("generatedName").is_none());
+
+        std::fs::write(titles_path(&storage_path), "{\"ws\": {").expect("truncate");
+        assert!(rename_thread_core(&storage_path, "ws".into(), "t1".into(), "New".into()).is_err());
+        apply_thread_titles(&storage_path, "ws", &mut response);
+        assert_eq!(response["data"][0]["name"], "Generated");
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
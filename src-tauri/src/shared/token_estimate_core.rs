@@ -0,0 +1,468 @@
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+
+use serde_json::Value;
+use tiktoken_rs::CoreBPE;
+use tokio::sync::Mutex;
+
+use crate::backend::app_server::{ThreadTokenTotals, WorkspaceSession};
+use crate::codex::config as codex_config;
+use crate::shared::auto_compact_core::resolve_compact_at_pct_for_workspace;
+use crate::shared::codex_core::resolve_codex_home_for_workspace_core;
+use crate::shared::thread_analysis_core::read_thread;
+use crate::types::{MessageTokenEstimate, ThreadUsage, WorkspaceEntry};
+
+/// Where Codex compacts on its own when the workspace sets no
+/// `autoCompactAtPct`.
+pub(crate) const DEFAULT_COMPACT_AT_PCT: u8 = 90;
+/// What a 1024x1024 image costs at high detail; attachments are not decoded
+/// just to size them.
+const IMAGE_TOKEN_ESTIMATE: u64 = 765;
+/// Rough per-message framing (role, separators) added by the chat format.
+const MESSAGE_OVERHEAD_TOKENS: u64 = 4;
+/// Item fields that are identifiers or enums rather than transcript text.
+const SKIPPED_ITEM_KEYS: &[&str] = &["id", "type", "status", "callId", "threadId", "turnId"];
+
+const KNOWN_CONTEXT_WINDOWS: &[(&str, u64)] = &[
+    ("gpt-5", 272_000),
+    ("codex-", 272_000),
+    ("gpt-4.1", 1_047_576),
+    ("gpt-4o", 128_000),
+    ("o1", 200_000),
+    ("o3", 200_000),
+    ("o4", 200_000),
+];
+
+static O200K: OnceLock<Option<CoreBPE>> = OnceLock::new();
+static CL100K: OnceLock<Option<CoreBPE>> = OnceLock::new();
+
+/// `o200k_base` for the GPT-4o generation onward, `cl100k_base` before it.
+pub(crate) fn tokenizer_for_model(model: Option<&str>) -> &'static str {
+    let Some(model) = model.map(str::to_ascii_lowercase) else {
+        return "o200k_base";
+    };
+    let legacy = ["gpt-4-", "gpt-3.5"]
+        .iter()
+        .any(|prefix| model.starts_with(prefix))
+        || model == "gpt-4";
+    if legacy {
+        "cl100k_base"
+    } else {
+        "o200k_base"
+    }
+}
+
+pub(crate) fn context_window_for_model(model: &str) -> Option<u64> {
+    let model = model.to_ascii_lowercase();
+    KNOWN_CONTEXT_WINDOWS
+        .iter()
+        .find(|(prefix, _)| model.starts_with(prefix))
+        .map(|(_, window)| *window)
+}
+
+fn tokenizer(name: &str) -> Option<&'static CoreBPE> {
+    let cell = if name == "cl100k_base" {
+        CL100K.get_or_init(|| tiktoken_rs::cl100k_base().ok())
+    } else {
+        O200K.get_or_init(|| tiktoken_rs::o200k_base().ok())
+    };
+    cell.as_ref()
+}
+
+/// Falls back to four characters per token if the vocabulary fails to load.
+pub(crate) fn count_tokens(tokenizer_name: &str, text: &str) -> u64 {
+    if text.is_empty() {
+        return 0;
+    }
+    match tokenizer(tokenizer_name) {
+        Some(bpe) => bpe.encode_with_special_tokens(text).len() as u64,
+        None => (text.chars().count() as u64).div_ceil(4),
+    }
+}
+
+fn read_u64(map: &Value, keys: &[&str]) -> Option<u64> {
+    keys.iter()
+        .find_map(|key| map.get(*key).and_then(Value::as_u64))
+}
+
+/// Reads `(context tokens, model window)` from a token usage payload; the
+/// `last` breakdown is what currently occupies the context.
+pub(crate) fn context_usage_from_token_usage(usage: &Value) -> Option<(u64, Option<u64>)> {
+    if usage.is_null() {
+        return None;
+    }
+    let last = usage.get("last").unwrap_or(usage);
+    let tokens = read_u64(last, &["totalTokens", "total_tokens"]).or_else(|| {
+        let input = read_u64(last, &["inputTokens", "input_tokens"])?;
+        let output = read_u64(last, &["outputTokens", "output_tokens"]).unwrap_or(0);
+        Some(input + output)
+    })?;
+    let window = read_u64(usage, &["modelContextWindow", "model_context_window"]);
+    Some((tokens, window))
+}
+
+/// Context size reported by the most recent turn that carries usage, plus
+/// the model window when the server included one.
+pub(crate) fn reported_context_usage(thread: &Value) -> Option<(u64, Option<u64>)> {
+    let turns = thread.get("turns").and_then(Value::as_array)?;
+    turns.iter().rev().find_map(|turn| {
+        turn.get("tokenUsage")
+            .or_else(|| turn.get("usage"))
+            .and_then(context_usage_from_token_usage)
+    })
+}
+
+/// Reads the session totals from the `total` breakdown of a token usage
+/// payload. Codex keeps adding to them turn after turn.
+pub(crate) fn token_totals_from_token_usage(usage: &Value) -> Option<ThreadTokenTotals> {
+    let total = usage.get("total")?;
+    let input_tokens = read_u64(total, &["inputTokens", "input_tokens"])?;
+    let output_tokens = read_u64(total, &["outputTokens", "output_tokens"]).unwrap_or(0);
+    Some(ThreadTokenTotals {
+        input_tokens,
+        cached_input_tokens: read_u64(total, &["cachedInputTokens", "cached_input_tokens"])
+            .unwrap_or(0),
+        output_tokens,
+        total_tokens: read_u64(total, &["totalTokens", "total_tokens"])
+            .unwrap_or(input_tokens + output_tokens),
+    })
+}
+
+/// The most recent turn's usage payload, for threads with no live session
+/// state yet.
+fn last_turn_token_usage(thread: &Value) -> Option<&Value> {
+    let turns = thread.get("turns").and_then(Value::as_array)?;
+    turns.iter().rev().find_map(|turn| {
+        turn.get("tokenUsage")
+            .or_else(|| turn.get("usage"))
+            .filter(|usage| !usage.is_null())
+    })
+}
+
+fn collect_item_text<'a>(value: &'a Value, output: &mut Vec<&'a str>) {
+    match value {
+        Value::String(text) => output.push(text),
+        Value::Array(values) => values
+            .iter()
+            .for_each(|value| collect_item_text(value, output)),
+        Value::Object(map) => map
+            .iter()
+            .filter(|(key, _)| !SKIPPED_ITEM_KEYS.contains(&key.as_str()))
+            .for_each(|(_, value)| collect_item_text(value, output)),
+        _ => {}
+    }
+}
+
+/// Tokenizes the transcript since the last compaction. Used when no turn has
+/// reported usage yet, so it undercounts the system prompt and tool schemas.
+pub(crate) fn estimate_transcript_tokens(thread: &Value, tokenizer_name: &str) -> u64 {
+    let items: Vec<&Value> = thread
+        .get("turns")
+        .and_then(Value::as_array)
+        .map(|turns| {
+            turns
+                .iter()
+                .filter_map(|turn| turn.get("items").and_then(Value::as_array))
+                .flatten()
+                .collect()
+        })
+        .unwrap_or_default();
+    let start = items
+        .iter()
+        .rposition(|item| item.get("type").and_then(Value::as_str) == Some("contextCompaction"))
+        .map(|index| index + 1)
+        .unwrap_or(0);
+    items[start..]
+        .iter()
+        .map(|item| {
+            let mut parts = Vec::new();
+            collect_item_text(item, &mut parts);
+            count_tokens(tokenizer_name, &parts.join("\n")) + MESSAGE_OVERHEAD_TOKENS
+        })
+        .sum()
+}
+
+/// Tokens a new message adds: its text, framing and attachments.
+pub(crate) fn estimate_incoming_tokens(
+    model: Option<&str>,
+    message: &str,
+    attachment_count: usize,
+) -> u64 {
+    let tokenizer_name = tokenizer_for_model(model);
+    count_tokens(tokenizer_name, message)
+        + MESSAGE_OVERHEAD_TOKENS
+        + attachment_count as u64 * IMAGE_TOKEN_ESTIMATE
+}
+
+/// `reported` is the live `(tokens, window)` from the session; without it the
+/// thread's own usage is used, and failing that its transcript is tokenized.
+pub(crate) fn build_estimate(
+    reported: Option<(u64, Option<u64>)>,
+    thread: Option<&Value>,
+    model: Option<String>,
+    message: &str,
+    attachment_count: usize,
+    compact_at_pct: u8,
+) -> MessageTokenEstimate {
+    let tokenizer_name = tokenizer_for_model(model.as_deref());
+    let message_tokens = count_tokens(tokenizer_name, message) + MESSAGE_OVERHEAD_TOKENS;
+    let attachment_tokens = attachment_count as u64 * IMAGE_TOKEN_ESTIMATE;
+    let reported = reported.or_else(|| thread.and_then(reported_context_usage));
+    let (context_tokens, context_source) = match reported {
+        Some((tokens, _)) => (tokens, "usage"),
+        None => (
+            thread
+                .map(|thread| estimate_transcript_tokens(thread, tokenizer_name))
+                .unwrap_or(0),
+            "estimate",
+        ),
+    };
+    let context_window = reported
+        .and_then(|(_, window)| window)
+        .or_else(|| model.as_deref().and_then(context_window_for_model));
+    let projected_tokens = context_tokens + message_tokens + attachment_tokens;
+    let projected_pct = context_window
+        .filter(|window| *window > 0)
+        .map(|window| projected_tokens as f64 * 100.0 / window as f64);
+    let will_overflow = projected_pct.is_some_and(|pct| pct > 100.0);
+    let will_compact =
+        !will_overflow && projected_pct.is_some_and(|pct| pct >= f64::from(compact_at_pct));
+    let warning = if will_overflow {
+        Some(format!(
+            "This message needs about {projected_tokens} tokens, more than the {} token context window.",
+            context_window.unwrap_or_default()
+        ))
+    } else if will_compact {
+        Some(format!(
+            "This message brings the thread to {:.0}% of the context window and will likely trigger compaction.",
+            projected_pct.unwrap_or_default()
+        ))
+    } else {
+        None
+    };
+    MessageTokenEstimate {
+        model,
+        tokenizer: tokenizer_name.to_string(),
+        message_tokens,
+        attachment_tokens,
+        context_tokens,
+        context_source: context_source.to_string(),
+        projected_tokens,
+        context_window,
+        projected_pct,
+        will_compact,
+        will_overflow,
+        warning,
+    }
+}
+
+pub(crate) fn build_thread_usage(
+    thread_id: String,
+    totals: Option<ThreadTokenTotals>,
+    context: Option<(u64, Option<u64>)>,
+    model: Option<&str>,
+) -> ThreadUsage {
+    let totals = totals.unwrap_or_default();
+    let context_window = context
+        .and_then(|(_, window)| window)
+        .or_else(|| model.and_then(context_window_for_model));
+    let context_tokens = context.map(|(tokens, _)| tokens);
+    let context_pct = context_tokens
+        .zip(context_window.filter(|window| *window > 0))
+        .map(|(tokens, window)| tokens as f64 * 100.0 / window as f64);
+    ThreadUsage {
+        thread_id,
+        input_tokens: totals.input_tokens,
+        cached_input_tokens: totals.cached_input_tokens,
+        output_tokens: totals.output_tokens,
+        total_tokens: totals.total_tokens,
+        context_tokens,
+        context_window,
+        context_pct,
+    }
+}
+
+/// Token totals and context fill for a thread, from what Codex reported in
+/// this session. A thread that hasn't run a turn since the session started
+/// falls back to the usage stored on its last turn. After a compaction the
+/// context size is unknown until the next turn reports it.
+pub(crate) async fn thread_usage_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    thread_id: String,
+) -> Result<ThreadUsage, String> {
+    let session = sessions
+        .lock()
+        .await
+        .get(&workspace_id)
+        .cloned()
+        .ok_or_else(|| "workspace not connected".to_string())?;
+    let mut totals = session.thread_token_totals(&thread_id).await;
+    let mut context = session
+        .thread_context_usage(&thread_id)
+        .await
+        .map(|usage| (usage.tokens, usage.context_window));
+    if totals.is_none() {
+        // A thread that hasn't been saved yet can't be read; it has no usage.
+        let thread = read_thread(&session, &thread_id).await.ok();
+        if let Some(usage) = thread.as_ref().and_then(last_turn_token_usage) {
+            totals = token_totals_from_token_usage(usage);
+            context = context.or_else(|| context_usage_from_token_usage(usage));
+        }
+    }
+    let model = if context.is_some_and(|(_, window)| window.is_some()) {
+        None
+    } else {
+        let codex_home = resolve_codex_home_for_workspace_core(workspaces, &workspace_id).await?;
+        codex_config::read_config_model(Some(codex_home))?
+    };
+    Ok(build_thread_usage(
+        thread_id,
+        totals,
+        context,
+        model.as_deref(),
+    ))
+}
+
+/// Counts `message` and its attachments on top of the thread's current
+/// context. `model` defaults to the workspace's configured model.
+pub(crate) async fn estimate_message_tokens_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    thread_id: String,
+    message: String,
+    attachments: Vec<String>,
+    model: Option<String>,
+) -> Result<MessageTokenEstimate, String> {
+    let session = sessions
+        .lock()
+        .await
+        .get(&workspace_id)
+        .cloned()
+        .ok_or_else(|| "workspace not connected".to_string())?;
+    let model = match model.filter(|value| !value.trim().is_empty()) {
+        Some(model) => Some(model),
+        None => {
+            let codex_home =
+                resolve_codex_home_for_workspace_core(workspaces, &workspace_id).await?;
+            codex_config::read_config_model(Some(codex_home))?
+        }
+    };
+    let compact_at_pct = resolve_compact_at_pct_for_workspace(workspaces, &workspace_id)
+        .await
+        .unwrap_or(DEFAULT_COMPACT_AT_PCT);
+    let live = session
+        .thread_context_usage(&thread_id)
+        .await
+        .map(|usage| (usage.tokens, usage.context_window));
+    let thread = match live {
+        Some(_) => None,
+        None => Some(read_thread(&session, &thread_id).await?),
+    };
+    let attachment_count = attachments
+        .iter()
+        .filter(|attachment| !attachment.trim().is_empty())
+        .count();
+    tokio::task::spawn_blocking(move || {
+        build_estimate(
+            live,
+            thread.as_ref(),
+            model,
+            &message,
+            attachment_count,
+            compact_at_pct,
+        )
+    })
+    .await
+    .map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn build_estimate_uses_reported_usage_and_flags_compaction() {
+        let thread = json!({
+            "turns": [
+                { "items": [], "tokenUsage": {
+                    "last": { "totalTokens": 240_000 },
+                    "modelContextWindow": 272_000
+                } },
+                { "items": [{ "type": "userMessage", "content": [] }] }
+            ]
+        });
+        let estimate = build_estimate(
+            None,
+            Some(&thread),
+            Some("gpt-5".to_string()),
+            "hello world",
+            1,
+            DEFAULT_COMPACT_AT_PCT,
+        );
+        assert_eq!(estimate.context_source, "usage");
+        assert_eq!(estimate.context_tokens, 240_000);
+        assert_eq!(estimate.context_window, Some(272_000));
+        assert_eq!(estimate.attachment_tokens, IMAGE_TOKEN_ESTIMATE);
+        assert!(estimate.will_compact);
+        assert!(!estimate.will_overflow);
+        assert!(estimate.warning.is_some());
+    }
+
+    #[test]
+    fn thread_usage_reads_totals_and_context_from_last_turn() {
+        let thread = json!({
+            "turns": [
+                { "items": [], "tokenUsage": {
+                    "total": {
+                        "inputTokens": 150_000,
+                        "cachedInputTokens": 90_000,
+                        "outputTokens": 12_000
+                    },
+                    "last": { "totalTokens": 68_000 },
+                    "modelContextWindow": 272_000
+                } },
+                { "items": [] }
+            ]
+        });
+        let usage = last_turn_token_usage(&thread).expect("turn usage");
+        let result = build_thread_usage(
+            "t1".to_string(),
+            token_totals_from_token_usage(usage),
+            context_usage_from_token_usage(usage),
+            None,
+        );
+        assert_eq!(result.input_tokens, 150_000);
+        assert_eq!(result.cached_input_tokens, 90_000);
+        assert_eq!(result.output_tokens, 12_000);
+        assert_eq!(result.total_tokens, 162_000);
+        assert_eq!(result.context_tokens, Some(68_000));
+        assert_eq!(result.context_window, Some(272_000));
+        assert_eq!(result.context_pct, Some(25.0));
+
+        let compacted = build_thread_usage("t1".to_string(), None, None, Some("gpt-5-codex"));
+        assert_eq!(compacted.total_tokens, 0);
+        assert_eq!(compacted.context_window, Some(272_000));
+        assert_eq!(compacted.context_pct, None);
+    }
+
+    #[test]
+    fn transcript_estimate_starts_after_last_compaction() {
+        let thread = json!({
+            "turns": [
+                { "items": [
+                    { "type": "agentMessage", "id": "a", "text": "old ".repeat(500) },
+                    { "type": "contextCompaction", "id": "c" }
+                ] },
+                { "items": [{ "type": "agentMessage", "id": "b", "text": "recent" }] }
+            ]
+        });
+        let tokens = estimate_transcript_tokens(&thread, "o200k_base");
+        assert!(tokens > MESSAGE_OVERHEAD_TOKENS && tokens < 20);
+        assert_eq!(tokenizer_for_model(Some("gpt-4-turbo")), "cl100k_base");
+        assert_eq!(tokenizer_for_model(Some("gpt-5-codex")), "o200k_base");
+    }
+}
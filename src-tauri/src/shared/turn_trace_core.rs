@@ -0,0 +1,572 @@
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::sync::Mutex;
+
+use crate::backend::app_server::WorkspaceSession;
+use crate::backend::events::{AppServerEvent, EventSink};
+use crate::codex::config as codex_config;
+use crate::shared::codex_core::resolve_codex_home_for_workspace_core;
+use crate::shared::redaction_core::SecretRedactor;
+use crate::types::{AppSettings, TurnTraceCapture, TurnTraceReplay, WorkspaceEntry};
+use crate::utils::now_unix_ms;
+
+pub(crate) const TRACE_FORMAT: &str = "codex-monitor-turn-trace";
+pub(crate) const TRACE_VERSION: u32 = 1;
+const TRACES_DIR: &str = "traces";
+/// Turns kept per session so a trace can still be captured after the fact.
+const MAX_TRACED_TURNS: usize = 20;
+/// Streaming deltas make long turns chatty; later events are counted but not
+/// kept.
+const MAX_TRACE_EVENTS: usize = 5_000;
+/// Item types that are the agent's own text rather than a tool call.
+const NON_TOOL_ITEM_TYPES: &[&str] = &["userMessage", "agentMessage", "reasoning"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TracedEvent {
+    /// Milliseconds since the turn was requested (or started, if the request
+    /// was not seen).
+    pub(crate) offset_ms: i64,
+    pub(crate) message: Value,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct RecordedTurn {
+    thread_id: String,
+    turn_id: String,
+    /// The `turn/start` params, which carry the user's input and overrides.
+    request: Option<Value>,
+    requested_at_ms: Option<i64>,
+    started_at_ms: i64,
+    completed_at_ms: Option<i64>,
+    events: Vec<TracedEvent>,
+    dropped_events: u32,
+}
+
+impl RecordedTurn {
+    fn push(&mut self, message: &Value, now_ms: i64) {
+        if self.events.len() >= MAX_TRACE_EVENTS {
+            self.dropped_events += 1;
+            return;
+        }
+        let origin = self.requested_at_ms.unwrap_or(self.started_at_ms);
+        self.events.push(TracedEvent {
+            offset_ms: now_ms - origin,
+            message: message.clone(),
+        });
+    }
+}
+
+/// The last few turns of one session with every app-server message that
+/// belonged to them.
+#[derive(Debug, Default)]
+pub(crate) struct TurnTraceBuffer {
+    /// `turn/start` params per thread, waiting for the `turn/started` that
+    /// names the turn.
+    pending_requests: HashMap<String, (Value, i64)>,
+    turns: VecDeque<RecordedTurn>,
+}
+
+impl TurnTraceBuffer {
+    pub(crate) fn record_turn_request(&mut self, thread_id: &str, params: &Value) {
+        self.pending_requests
+            .insert(thread_id.to_string(), (params.clone(), now_unix_ms()));
+    }
+
+    pub(crate) fn record_event(&mut self, thread_id: &str, message: &Value) {
+        self.record_event_at(thread_id, message, now_unix_ms());
+    }
+
+    fn record_event_at(&mut self, thread_id: &str, message: &Value, now_ms: i64) {
+        let method = message.get("method").and_then(Value::as_str).unwrap_or("");
+        let params = message.get("params");
+        let turn_id = params.and_then(|params| {
+            params
+                .get("turnId")
+                .or_else(|| params.get("turn").and_then(|turn| turn.get("id")))
+                .and_then(Value::as_str)
+        });
+
+        if method == "turn/started" {
+            let Some(turn_id) = turn_id else {
+                return;
+            };
+            let (request, requested_at_ms) = match self.pending_requests.remove(thread_id) {
+                Some((request, at)) => (Some(request), Some(at)),
+                None => (None, None),
+            };
+            if self.turns.len() >= MAX_TRACED_TURNS {
+                self.turns.pop_front();
+            }
+            self.turns.push_back(RecordedTurn {
+                thread_id: thread_id.to_string(),
+                turn_id: turn_id.to_string(),
+                request,
+                requested_at_ms,
+                started_at_ms: now_ms,
+                completed_at_ms: None,
+                events: Vec::new(),
+                dropped_events: 0,
+            });
+        }
+
+        // Events without a turn id belong to the thread's turn in flight.
+        let turn = self.turns.iter_mut().rev().find(|turn| {
+            turn.thread_id == thread_id
+                && match turn_id {
+                    Some(turn_id) => turn.turn_id == turn_id,
+                    None => turn.completed_at_ms.is_none(),
+                }
+        });
+        let Some(turn) = turn else {
+            return;
+        };
+        turn.push(message, now_ms);
+        if method == "turn/completed" {
+            turn.completed_at_ms = Some(now_ms);
+        }
+    }
+
+    pub(crate) fn find(&self, turn_id: &str) -> Option<&RecordedTurn> {
+        self.turns.iter().rev().find(|turn| turn.turn_id == turn_id)
+    }
+
+    /// `(thread id, turn id, started at)` for turns without a
+    /// `turn/completed` yet.
+    // Only the app asks before quitting; the daemon keeps its turns running.
+    #[allow(dead_code)]
+    pub(crate) fn running_turns(&self) -> Vec<(String, String, i64)> {
+        self.turns
+            .iter()
+            .filter(|turn| turn.completed_at_ms.is_none())
+            .map(|turn| {
+                (
+                    turn.thread_id.clone(),
+                    turn.turn_id.clone(),
+                    turn.started_at_ms,
+                )
+            })
+            .collect()
+    }
+}
+
+/// What `capture_turn_trace` writes and `load_turn_trace` reads.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TurnTrace {
+    pub(crate) format: String,
+    pub(crate) version: u32,
+    pub(crate) captured_at_ms: i64,
+    pub(crate) workspace_id: String,
+    pub(crate) workspace_name: String,
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: String,
+    #[serde(default)]
+    pub(crate) model: Option<String>,
+    /// The `input` of the `turn/start` request.
+    #[serde(default)]
+    pub(crate) user_message: Option<Value>,
+    #[serde(default)]
+    pub(crate) request: Option<Value>,
+    #[serde(default)]
+    pub(crate) requested_at_ms: Option<i64>,
+    pub(crate) started_at_ms: i64,
+    #[serde(default)]
+    pub(crate) completed_at_ms: Option<i64>,
+    #[serde(default)]
+    pub(crate) duration_ms: Option<i64>,
+    /// Completed tool items (commands, file changes, MCP calls) with their
+    /// output, pulled out of `events` for quick reading.
+    #[serde(default)]
+    pub(crate) tool_calls: Vec<Value>,
+    #[serde(default)]
+    pub(crate) events: Vec<TracedEvent>,
+    #[serde(default)]
+    pub(crate) dropped_events: u32,
+    /// The workspace's `config.toml`.
+    #[serde(default)]
+    pub(crate) config: Option<String>,
+}
+
+/// Redacts every string in `value`, returning how many were changed.
+fn redact_value(redactor: &SecretRedactor, value: &mut Value) -> u32 {
+    match value {
+        Value::String(text) => {
+            let redacted = redactor.redact_text(text);
+            if redacted == *text {
+                return 0;
+            }
+            *text = redacted;
+            1
+        }
+        Value::Array(values) => values
+            .iter_mut()
+            .map(|value| redact_value(redactor, value))
+            .sum(),
+        Value::Object(map) => map
+            .values_mut()
+            .map(|value| redact_value(redactor, value))
+            .sum(),
+        _ => 0,
+    }
+}
+
+fn completed_tool_calls(events: &[TracedEvent]) -> Vec<Value> {
+    events
+        .iter()
+        .filter(|event| {
+            event.message.get("method").and_then(Value::as_str) == Some("item/completed")
+        })
+        .filter_map(|event| event.message.get("params")?.get("item"))
+        .filter(|item| {
+            item.get("type")
+                .and_then(Value::as_str)
+                .is_some_and(|kind| !NON_TOOL_ITEM_TYPES.contains(&kind))
+        })
+        .cloned()
+        .collect()
+}
+
+fn build_trace(
+    entry: &WorkspaceEntry,
+    turn: &RecordedTurn,
+    config: Option<String>,
+    config_model: Option<String>,
+    redactor: &SecretRedactor,
+) -> (TurnTrace, u32) {
+    let mut redacted_secrets = 0u32;
+    let mut request = turn.request.clone();
+    if let Some(request) = request.as_mut() {
+        redacted_secrets += redact_value(redactor, request);
+    }
+    let mut events = turn.events.clone();
+    for event in &mut events {
+        redacted_secrets += redact_value(redactor, &mut event.message);
+    }
+    let config = config.map(|config| {
+        let redacted = redactor.redact_text(&config);
+        if redacted != config {
+            redacted_secrets += 1;
+        }
+        redacted
+    });
+    let model = request
+        .as_ref()
+        .and_then(|request| request.get("model"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .or(config_model);
+    let user_message = request
+        .as_ref()
+        .and_then(|request| request.get("input"))
+        .cloned();
+    let origin = turn.requested_at_ms.unwrap_or(turn.started_at_ms);
+    let trace = TurnTrace {
+        format: TRACE_FORMAT.to_string(),
+        version: TRACE_VERSION,
+        captured_at_ms: now_unix_ms(),
+        workspace_id: entry.id.clone(),
+        workspace_name: entry.name.clone(),
+        thread_id: turn.thread_id.clone(),
+        turn_id: turn.turn_id.clone(),
+        model,
+        user_message,
+        request,
+        requested_at_ms: turn.requested_at_ms,
+        started_at_ms: turn.started_at_ms,
+        completed_at_ms: turn.completed_at_ms,
+        duration_ms: turn.completed_at_ms.map(|completed| completed - origin),
+        tool_calls: completed_tool_calls(&events),
+        events,
+        dropped_events: turn.dropped_events,
+        config,
+    };
+    (trace, redacted_secrets)
+}
+
+fn trace_path(settings_path: &Path, turn_id: &str) -> Result<PathBuf, String> {
+    let data_dir = settings_path
+        .parent()
+        .ok_or_else(|| "Unable to resolve app data dir.".to_string())?;
+    let safe_turn_id: String = turn_id
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '-' {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    Ok(data_dir.join(TRACES_DIR).join(format!(
+        "turn-{safe_turn_id}-{}.json",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    )))
+}
+
+/// Writes one of the session's recent turns to `<data dir>/traces` as a
+/// single JSON file: the user message, every event with its timing, the tool
+/// calls, the model and the workspace's `config.toml`. Secrets are redacted
+/// even when redaction is off for the UI, since traces are made to be shared.
+pub(crate) async fn capture_turn_trace_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    app_settings: &Mutex<AppSettings>,
+    settings_path: &Path,
+    workspace_id: String,
+    turn_id: String,
+) -> Result<TurnTraceCapture, String> {
+    let session = sessions
+        .lock()
+        .await
+        .get(&workspace_id)
+        .cloned()
+        .ok_or_else(|| "workspace not connected".to_string())?;
+    let turn = session
+        .turn_traces
+        .lock()
+        .await
+        .find(&turn_id)
+        .cloned()
+        .ok_or_else(|| {
+            format!(
+                "Turn {turn_id} is not among the last {MAX_TRACED_TURNS} turns of this session."
+            )
+        })?;
+    let codex_home = resolve_codex_home_for_workspace_core(workspaces, &workspace_id)
+        .await
+        .ok();
+    let custom_patterns = app_settings.lock().await.secret_patterns.clone();
+    let redactor = SecretRedactor::with_custom_patterns(&custom_patterns)?;
+    let path = trace_path(settings_path, &turn_id)?;
+    let entry = session.entry.clone();
+
+    tokio::task::spawn_blocking(move || {
+        let config = codex_home
+            .as_ref()
+            .and_then(|home| fs::read_to_string(home.join("config.toml")).ok());
+        let config_model = codex_home
+            .and_then(|home| codex_config::read_config_model(Some(home)).ok())
+            .flatten();
+        let (trace, redacted_secrets) = build_trace(&entry, &turn, config, config_model, &redactor);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+        }
+        let data = serde_json::to_string_pretty(&trace).map_err(|err| err.to_string())?;
+        fs::write(&path, data).map_err(|err| err.to_string())?;
+        Ok(TurnTraceCapture {
+            path: path.to_string_lossy().to_string(),
+            event_count: trace.events.len() as u32,
+            tool_call_count: trace.tool_calls.len() as u32,
+            complete: trace.completed_at_ms.is_some(),
+            redacted_secrets,
+        })
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+/// Points the message at `thread_id` and marks it with `replay`, which the
+/// normal handlers ignore.
+fn retag_for_replay(message: &Value, thread_id: &str, replay: &Value) -> Value {
+    let mut message = message.clone();
+    if let Some(params) = message.get_mut("params").and_then(Value::as_object_mut) {
+        for key in ["threadId", "thread_id"] {
+            if params.contains_key(key) {
+                params.insert(key.to_string(), json!(thread_id));
+            }
+        }
+        if let Some(thread) = params.get_mut("thread").and_then(Value::as_object_mut) {
+            thread.insert("id".to_string(), json!(thread_id));
+        }
+    }
+    if let Some(message) = message.as_object_mut() {
+        message.insert("replay".to_string(), replay.clone());
+    }
+    message
+}
+
+/// The messages to emit for `trace`: a `thread/started` for the replay
+/// thread, then the recorded events. Server requests such as approvals are
+/// left out (and counted) since no session is there to answer them.
+fn replay_messages(trace: &TurnTrace, thread_id: &str, trace_path: &str) -> (Vec<Value>, u32) {
+    let replay = |offset_ms: i64| json!({ "tracePath": trace_path, "turnId": trace.turn_id, "offsetMs": offset_ms });
+    let preview = trace
+        .user_message
+        .as_ref()
+        .and_then(|input| {
+            input.as_str().map(str::to_string).or_else(|| {
+                input
+                    .as_array()?
+                    .iter()
+                    .find_map(|part| part.get("text").and_then(Value::as_str).map(str::to_string))
+            })
+        })
+        .unwrap_or_default();
+    let started = json!({
+        "method": "thread/started",
+        "params": { "thread": {
+            "id": thread_id,
+            "name": format!("Replay of turn {}", trace.turn_id),
+            "preview": preview,
+        } },
+    });
+    let mut messages = vec![retag_for_replay(&started, thread_id, &replay(0))];
+    let mut skipped_requests = 0u32;
+    for event in &trace.events {
+        if event.message.get("id").is_some() {
+            skipped_requests += 1;
+            continue;
+        }
+        messages.push(retag_for_replay(
+            &event.message,
+            thread_id,
+            &replay(event.offset_ms),
+        ));
+    }
+    (messages, skipped_requests)
+}
+
+/// Reads a trace written by `capture_turn_trace` and re-emits its events as
+/// app-server events into a `replay-<turn id>` thread, so the normal thread
+/// view renders the turn without re-running the agent. `workspace_id`
+/// defaults to the workspace the trace was captured in.
+pub(crate) async fn load_turn_trace_core<E: EventSink>(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    event_sink: &E,
+    path: String,
+    workspace_id: Option<String>,
+) -> Result<TurnTraceReplay, String> {
+    let data = fs::read_to_string(&path).map_err(|err| format!("Failed to read trace: {err}"))?;
+    let trace: TurnTrace =
+        serde_json::from_str(&data).map_err(|err| format!("Invalid turn trace: {err}"))?;
+    if trace.format != TRACE_FORMAT {
+        return Err("Not a Codex Monitor turn trace.".to_string());
+    }
+    if trace.version > TRACE_VERSION {
+        return Err(format!(
+            "Turn trace version {} is newer than this app supports.",
+            trace.version
+        ));
+    }
+    let workspace_id = workspace_id
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| trace.workspace_id.clone());
+    if !workspaces.lock().await.contains_key(&workspace_id) {
+        return Err(format!(
+            "Workspace {workspace_id} not found. Pick a workspace to replay the trace into."
+        ));
+    }
+
+    let thread_id = format!("replay-{}", trace.turn_id);
+    let (messages, skipped_requests) = replay_messages(&trace, &thread_id, &path);
+    let event_count = messages.len() as u32 - 1;
+    for message in messages {
+        event_sink.emit_app_server_event(AppServerEvent {
+            workspace_id: workspace_id.clone(),
+            message,
+        });
+    }
+    Ok(TurnTraceReplay {
+        workspace_id,
+        thread_id,
+        turn_id: trace.turn_id,
+        event_count,
+        skipped_requests,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn buffer_groups_events_by_turn_and_keeps_the_request() {
+        let mut buffer = TurnTraceBuffer::default();
+        buffer.record_turn_request("t1", &json!({ "threadId": "t1", "input": "hi" }));
+        buffer.record_event_at(
+            "t1",
+            &json!({ "method": "turn/started", "params": { "threadId": "t1", "turn": { "id": "a" } } }),
+            10,
+        );
+        buffer.record_event_at(
+            "t1",
+            &json!({ "method": "item/completed", "params": { "threadId": "t1", "turnId": "a",
+                "item": { "type": "commandExecution", "command": "ls" } } }),
+            20,
+        );
+        buffer.record_event_at(
+            "t1",
+            &json!({ "method": "thread/tokenUsage/updated", "params": { "threadId": "t1" } }),
+            25,
+        );
+        buffer.record_event_at(
+            "t1",
+            &json!({ "method": "turn/completed", "params": { "threadId": "t1", "turn": { "id": "a" } } }),
+            30,
+        );
+        buffer.record_event_at(
+            "t1",
+            &json!({ "method": "thread/tokenUsage/updated", "params": { "threadId": "t1" } }),
+            40,
+        );
+
+        let turn = buffer.find("a").expect("turn recorded");
+        assert_eq!(turn.events.len(), 4);
+        assert_eq!(turn.completed_at_ms, Some(30));
+        assert_eq!(
+            turn.request
+                .as_ref()
+                .and_then(|request| request.get("input")),
+            Some(&json!("hi"))
+        );
+        assert_eq!(completed_tool_calls(&turn.events).len(), 1);
+        assert!(buffer.running_turns().is_empty());
+
+        for index in 0..MAX_TRACED_TURNS {
+            buffer.record_event_at(
+                "t2",
+                &json!({ "method": "turn/started", "params": { "turn": { "id": format!("b{index}") } } }),
+                50,
+            );
+        }
+        assert!(buffer.find("a").is_none());
+        assert_eq!(buffer.running_turns().len(), MAX_TRACED_TURNS);
+    }
+
+    #[test]
+    fn replay_moves_events_to_the_replay_thread_and_skips_requests() {
+        let trace: TurnTrace = serde_json::from_value(json!({
+            "format": TRACE_FORMAT,
+            "version": TRACE_VERSION,
+            "capturedAtMs": 0,
+            "workspaceId": "ws",
+            "workspaceName": "ws",
+            "threadId": "t1",
+            "turnId": "a",
+            "userMessage": [{ "type": "text", "text": "fix it" }],
+            "startedAtMs": 0,
+            "events": [
+                { "offsetMs": 5, "message": { "method": "turn/started",
+                    "params": { "threadId": "t1", "turn": { "id": "a" } } } },
+                { "offsetMs": 8, "message": { "id": 3, "method": "item/commandExecution/requestApproval",
+                    "params": { "threadId": "t1" } } },
+                { "offsetMs": 9, "message": { "method": "turn/completed",
+                    "params": { "threadId": "t1", "turn": { "id": "a" } } } }
+            ]
+        }))
+        .expect("valid trace");
+        let (messages, skipped) = replay_messages(&trace, "replay-a", "/tmp/a.json");
+        assert_eq!(skipped, 1);
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0]["params"]["thread"]["preview"], "fix it");
+        assert_eq!(messages[1]["params"]["threadId"], "replay-a");
+        assert_eq!(messages[2]["replay"]["offsetMs"], 9);
+    }
+}
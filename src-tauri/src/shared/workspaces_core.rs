@@ -1,21 +1,33 @@
+mod codex_bin;
 mod connect;
 mod crud_persistence;
 mod git_orchestration;
 mod helpers;
 mod io;
+mod prewarm;
+mod relocate;
 mod worktree;
 
+pub(crate) use codex_bin::{
+    check_codex_bins_on_startup, find_stale_codex_bins_core, repoint_codex_bin_core,
+};
 pub(crate) use connect::connect_workspace_core;
 pub(crate) use crud_persistence::{
-    add_clone_core, add_workspace_core, remove_workspace_core, update_workspace_codex_bin_core,
-    update_workspace_settings_core,
+    add_clone_core, add_workspace_core, remove_workspace_core, remove_workspaces_core,
+    rename_workspace_core, set_workspace_color_core, set_workspace_profile_core,
+    update_workspace_codex_bin_core, update_workspace_settings_core,
+};
+pub(crate) use git_orchestration::{
+    apply_worktree_changes_core, apply_worktree_commits_core, move_worktree_changes_back_core,
+    preview_worktree_changes_core, run_git_command_unit,
 };
-pub(crate) use git_orchestration::{apply_worktree_changes_core, run_git_command_unit};
 pub(crate) use helpers::{is_workspace_path_dir_core, list_workspaces_core};
 pub(crate) use io::{
     get_open_app_icon_core, list_workspace_files_core, open_workspace_in_core,
     read_workspace_file_core,
 };
+pub(crate) use prewarm::{interrupt_prewarm, prewarm_sessions_core};
+pub(crate) use relocate::relocate_workspace_core;
 pub(crate) use worktree::{
     add_worktree_core, remove_worktree_core, rename_worktree_core, rename_worktree_upstream_core,
     worktree_setup_mark_ran_core, worktree_setup_status_core,
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde_json::json;
+use tokio::sync::Mutex;
+
+use crate::backend::app_server::{check_codex_installation, WorkspaceSession};
+use crate::backend::events::{AppServerEvent, EventSink};
+use crate::types::{AppSettings, StaleCodexBin, WorkspaceEntry, WorkspaceInfo};
+
+use super::crud_persistence::update_workspace_codex_bin_core;
+
+/// Only a pin that names a path can go missing on upgrade; a bare `codex`
+/// is looked up on PATH at spawn time like the default.
+pub(super) fn pinned_codex_bin_missing(codex_bin: &str) -> bool {
+    let codex_bin = codex_bin.trim();
+    if codex_bin.is_empty() {
+        return false;
+    }
+    let path = Path::new(codex_bin);
+    let names_path = path.is_absolute() || path.components().count() > 1;
+    names_path && !path.exists()
+}
+
+/// Workspaces pinned to a `codexBin` that no longer exists, listed only when
+/// the global binary runs so switching to it would actually help.
+pub(crate) async fn find_stale_codex_bins_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+) -> Vec<StaleCodexBin> {
+    let mut missing: Vec<(String, String, String)> = workspaces
+        .lock()
+        .await
+        .values()
+        .filter_map(|entry| {
+            let codex_bin = entry.codex_bin.as_deref()?;
+            pinned_codex_bin_missing(codex_bin).then(|| {
+                (
+                    entry.id.clone(),
+                    entry.name.clone(),
+                    codex_bin.trim().to_string(),
+                )
+            })
+        })
+        .collect();
+    if missing.is_empty() {
+        return Vec::new();
+    }
+    let default_bin = app_settings.lock().await.codex_bin.clone();
+    let Ok(default_version) = check_codex_installation(default_bin.clone()).await else {
+        return Vec::new();
+    };
+    missing.sort_by(|a, b| a.1.cmp(&b.1));
+    missing
+        .into_iter()
+        .map(|(workspace_id, workspace_name, codex_bin)| StaleCodexBin {
+            workspace_id,
+            workspace_name,
+            codex_bin,
+            default_codex_bin: default_bin.clone(),
+            default_version: default_version.clone(),
+        })
+        .collect()
+}
+
+/// Runs once at launch. Each stale pin becomes a `codex/codexBinMissing`
+/// event; nothing changes until the user calls `repoint_codex_bin`.
+pub(crate) async fn check_codex_bins_on_startup<E: EventSink>(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    event_sink: &E,
+) {
+    for stale in find_stale_codex_bins_core(workspaces, app_settings).await {
+        event_sink.emit_app_server_event(AppServerEvent {
+            workspace_id: stale.workspace_id.clone(),
+            message: json!({ "method": "codex/codexBinMissing", "params": stale }),
+        });
+    }
+}
+
+/// Drops the workspace's pinned binary so it falls back to the global
+/// `codexBin`.
+pub(crate) async fn repoint_codex_bin_core(
+    id: String,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    storage_path: &PathBuf,
+) -> Result<WorkspaceInfo, String> {
+    let pinned = workspaces
+        .lock()
+        .await
+        .get(&id)
+        .map(|entry| entry.codex_bin.clone())
+        .ok_or_else(|| "workspace not found".to_string())?;
+    if pinned.is_none() {
+        return Err("Workspace already uses the default Codex binary.".to_string());
+    }
+    update_workspace_codex_bin_core(id, None, workspaces, sessions, storage_path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_missing_path_pins_are_stale() {
+        let missing = std::env::temp_dir()
+            .join(format!("codex-monitor-missing-{}", uuid::Uuid::new_v4()))
+            .join("codex");
+        assert!(pinned_codex_bin_missing(&missing.to_string_lossy()));
+        assert!(!pinned_codex_bin_missing(
+            &std::env::temp_dir().to_string_lossy()
+        ));
+        assert!(!pinned_codex_bin_missing("codex"));
+        assert!(!pinned_codex_bin_missing("  "));
+    }
+}
@@ -7,11 +7,12 @@ use tokio::sync::Mutex;
 
 use crate::backend::app_server::WorkspaceSession;
 use crate::codex::args::resolve_workspace_codex_args;
+use crate::codex::endpoint::with_inherited_session_settings;
 use crate::codex::home::resolve_workspace_codex_home;
 use crate::shared::process_core::kill_child_process_tree;
 use crate::types::{AppSettings, WorkspaceEntry};
 
-use super::helpers::resolve_entry_and_parent;
+use super::helpers::{resolve_entry_and_parent, workspace_path_exists};
 
 pub(crate) async fn connect_workspace_core<F, Fut>(
     workspace_id: String,
@@ -25,6 +26,12 @@ where
     Fut: Future<Output = Result<Arc<WorkspaceSession>, String>>,
 {
     let (entry, parent_entry) = resolve_entry_and_parent(workspaces, &workspace_id).await?;
+    if !workspace_path_exists(&entry.path).await {
+        return Err(format!(
+            "Workspace folder not found: {}. Relocate or remove the workspace.",
+            entry.path
+        ));
+    }
     let (default_bin, codex_args) = {
         let settings = app_settings.lock().await;
         (
@@ -33,8 +40,24 @@ where
         )
     };
     let codex_home = resolve_workspace_codex_home(&entry, parent_entry.as_ref());
-    let session = spawn_session(entry.clone(), default_bin, codex_args, codex_home).await?;
-    sessions.lock().await.insert(entry.id, session);
+    let session = spawn_session(
+        with_inherited_session_settings(&entry, parent_entry.as_ref()),
+        default_bin,
+        codex_args,
+        codex_home,
+    )
+    .await?;
+    let mut sessions = sessions.lock().await;
+    if sessions.contains_key(&entry.id) {
+        // Another connect (a prewarm, a respawn or a second click) finished
+        // first. Clients may already be talking to that session, so keep it
+        // and stop the child this call spawned.
+        drop(sessions);
+        let mut child = session.child.lock().await;
+        kill_child_process_tree(&mut child).await;
+        return Ok(());
+    }
+    sessions.insert(entry.id, session);
     Ok(())
 }
 
@@ -47,3 +70,87 @@ pub(super) async fn kill_session_by_id(
         kill_child_process_tree(&mut child).await;
     }
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::process::Stdio;
+    use std::sync::Mutex as StdMutex;
+    use std::time::Duration;
+
+    use crate::types::{WorkspaceKind, WorkspaceSettings};
+
+    #[test]
+    fn racing_connects_keep_one_session_and_kill_the_other_child() {
+        let root =
+            std::env::temp_dir().join(format!("codex-monitor-connect-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root).expect("create workspace dir");
+        let entry = WorkspaceEntry {
+            id: "w1".to_string(),
+            name: "w1".to_string(),
+            path: root.to_string_lossy().to_string(),
+            codex_bin: None,
+            kind: WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings::default(),
+        };
+        let workspaces = Mutex::new(HashMap::from([("w1".to_string(), entry)]));
+        let sessions = Mutex::new(HashMap::new());
+        let app_settings = Mutex::new(AppSettings::default());
+        let spawned: StdMutex<Vec<Arc<WorkspaceSession>>> = StdMutex::new(Vec::new());
+        let spawn_session =
+            |entry: WorkspaceEntry, _: Option<String>, _: Option<String>, _: Option<PathBuf>| {
+                let spawned = &spawned;
+                async move {
+                    let mut child = tokio::process::Command::new("cat")
+                        .stdin(Stdio::piped())
+                        .stdout(Stdio::null())
+                        .spawn()
+                        .map_err(|err| err.to_string())?;
+                    let stdin = child.stdin.take().ok_or("missing stdin")?;
+                    // Both connects are in flight before either registers.
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    let session = Arc::new(WorkspaceSession::new(entry, child, stdin));
+                    spawned.lock().unwrap().push(Arc::clone(&session));
+                    Ok::<_, String>(session)
+                }
+            };
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        runtime.block_on(async {
+            let connect = || {
+                connect_workspace_core(
+                    "w1".to_string(),
+                    &workspaces,
+                    &sessions,
+                    &app_settings,
+                    &spawn_session,
+                )
+            };
+            let (first, second) = futures_util::future::join(connect(), connect()).await;
+            first.expect("first connect");
+            second.expect("second connect");
+
+            let spawned = spawned.lock().unwrap().clone();
+            assert_eq!(spawned.len(), 2);
+            let registered = sessions.lock().await.get("w1").cloned().expect("session");
+            for session in spawned {
+                let exited = session
+                    .child
+                    .lock()
+                    .await
+                    .try_wait()
+                    .expect("child status")
+                    .is_some();
+                assert_eq!(exited, !Arc::ptr_eq(&session, &registered));
+            }
+            kill_session_by_id(&sessions, "w1").await;
+        });
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
@@ -1,21 +1,35 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::future::Future;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Instant;
 
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
 use crate::backend::app_server::WorkspaceSession;
+use crate::backend::events::{CloneProgressEvent, EventSink};
 use crate::codex::args::resolve_workspace_codex_args;
+use crate::codex::endpoint::{
+    normalize_codex_base_url, resolve_workspace_codex_base_url, with_inherited_session_settings,
+};
+use crate::codex::env::{normalize_workspace_env, resolve_workspace_env};
 use crate::codex::home::resolve_workspace_codex_home;
 use crate::shared::process_core::kill_child_process_tree;
-use crate::shared::{git_core, worktree_core};
+use crate::shared::{
+    auto_compact_core, git_core, operation_timing_core, profiles_core, worktree_core,
+};
 use crate::storage::write_workspaces;
-use crate::types::{AppSettings, WorkspaceEntry, WorkspaceInfo, WorkspaceKind, WorkspaceSettings};
+use crate::types::{
+    AppSettings, WorkspaceEntry, WorkspaceInfo, WorkspaceKind, WorkspaceRemovalResult,
+    WorkspaceSettings,
+};
 
 use super::connect::kill_session_by_id;
-use super::helpers::normalize_setup_script;
+use super::helpers::{
+    normalize_setup_script, normalize_workspace_color, resolve_workspace_color,
+    workspace_path_exists,
+};
 
 pub(crate) async fn add_workspace_core<F, Fut>(
     path: String,
@@ -87,10 +101,50 @@ where
         parent_id: entry.parent_id,
         worktree: entry.worktree,
         settings: entry.settings,
+        color: None,
+        path_exists: true,
     })
 }
 
-pub(crate) async fn add_clone_core<F, Fut>(
+/// Removes a partially cloned copy if the clone future is dropped (cancelled)
+/// before the workspace is persisted.
+struct CloneDestinationGuard {
+    path: Option<PathBuf>,
+}
+
+impl CloneDestinationGuard {
+    fn disarm(&mut self) {
+        self.path = None;
+    }
+}
+
+impl Drop for CloneDestinationGuard {
+    fn drop(&mut self) {
+        if let Some(path) = self.path.take() {
+            let _ = std::fs::remove_dir_all(path);
+        }
+    }
+}
+
+fn clone_progress_event(
+    source_workspace_id: &str,
+    status: &str,
+    percent: u8,
+    stage: Option<&str>,
+    error: Option<String>,
+) -> CloneProgressEvent {
+    CloneProgressEvent {
+        source_workspace_id: source_workspace_id.to_string(),
+        status: status.to_string(),
+        percent,
+        stage: stage.map(str::to_string),
+        error,
+    }
+}
+
+/// Emits `clone-progress` while git runs and a final `done` or `failed`
+/// event once the copy is registered or abandoned.
+pub(crate) async fn add_clone_core<F, Fut, E>(
     source_workspace_id: String,
     copy_name: String,
     copies_folder: String,
@@ -98,11 +152,51 @@ pub(crate) async fn add_clone_core<F, Fut>(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
     app_settings: &Mutex<AppSettings>,
     storage_path: &PathBuf,
+    event_sink: &E,
     spawn_session: F,
 ) -> Result<WorkspaceInfo, String>
 where
     F: Fn(WorkspaceEntry, Option<String>, Option<String>, Option<PathBuf>) -> Fut,
     Fut: Future<Output = Result<Arc<WorkspaceSession>, String>>,
+    E: EventSink,
+{
+    let result = add_clone_inner(
+        source_workspace_id.clone(),
+        copy_name,
+        copies_folder,
+        workspaces,
+        sessions,
+        app_settings,
+        storage_path,
+        event_sink,
+        spawn_session,
+    )
+    .await;
+    let event = match &result {
+        Ok(_) => clone_progress_event(&source_workspace_id, "done", 100, None, None),
+        Err(error) => {
+            clone_progress_event(&source_workspace_id, "failed", 0, None, Some(error.clone()))
+        }
+    };
+    event_sink.emit_clone_progress_event(event);
+    result
+}
+
+async fn add_clone_inner<F, Fut, E>(
+    source_workspace_id: String,
+    copy_name: String,
+    copies_folder: String,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    app_settings: &Mutex<AppSettings>,
+    storage_path: &PathBuf,
+    event_sink: &E,
+    spawn_session: F,
+) -> Result<WorkspaceInfo, String>
+where
+    F: Fn(WorkspaceEntry, Option<String>, Option<String>, Option<PathBuf>) -> Fut,
+    Fut: Future<Output = Result<Arc<WorkspaceSession>, String>>,
+    E: EventSink,
 {
     let copy_name = copy_name.trim().to_string();
     if copy_name.is_empty() {
@@ -142,15 +236,47 @@ where
         worktree_core::build_clone_destination_path(&copies_folder_path, &copy_name);
     let destination_path_string = destination_path.to_string_lossy().to_string();
 
-    if let Err(error) = git_core::run_git_command(
+    let mut destination_guard = CloneDestinationGuard {
+        path: Some(destination_path.clone()),
+    };
+    let clone_started = Instant::now();
+    let mut last_percent = None;
+    if let Err(error) = git_core::run_git_command_streaming(
         &copies_folder_path,
-        &["clone", &source_entry.path, &destination_path_string],
+        &[
+            "clone",
+            "--progress",
+            &source_entry.path,
+            &destination_path_string,
+        ],
+        |line| {
+            let Some((stage, percent)) = worktree_core::parse_clone_progress(line) else {
+                return;
+            };
+            if last_percent.is_some_and(|last| percent <= last) {
+                return;
+            }
+            last_percent = Some(percent);
+            event_sink.emit_clone_progress_event(clone_progress_event(
+                &source_workspace_id,
+                "progress",
+                percent,
+                Some(stage),
+                None,
+            ));
+        },
     )
     .await
     {
         let _ = tokio::fs::remove_dir_all(&destination_path).await;
         return Err(error);
     }
+    operation_timing_core::record_operation_timing(
+        storage_path,
+        "clone",
+        operation_timing_core::repo_size_hint_bytes(&destination_path),
+        clone_started.elapsed(),
+    );
 
     if let Some(origin_url) = git_core::git_get_origin_url(&PathBuf::from(&source_entry.path)).await
     {
@@ -206,6 +332,7 @@ where
         let _ = tokio::fs::remove_dir_all(&destination_path).await;
         return Err(error);
     }
+    destination_guard.disarm();
 
     sessions.lock().await.insert(entry.id.clone(), session);
 
@@ -219,6 +346,8 @@ where
         parent_id: entry.parent_id,
         worktree: entry.worktree,
         settings: entry.settings,
+        color: None,
+        path_exists: true,
     })
 }
 
@@ -334,6 +463,172 @@ where
     Ok(())
 }
 
+async fn remove_worktree_folder<FRunGit, FutRunGit, FIsMissing, FRemoveDirAll>(
+    parent_path: &PathBuf,
+    parent_path_exists: bool,
+    entry: &WorkspaceEntry,
+    run_git_command: &FRunGit,
+    is_missing_worktree_error: &FIsMissing,
+    remove_dir_all: &FRemoveDirAll,
+) -> Result<(), String>
+where
+    FRunGit: Fn(&PathBuf, &[&str]) -> FutRunGit,
+    FutRunGit: Future<Output = Result<(), String>>,
+    FIsMissing: Fn(&str) -> bool,
+    FRemoveDirAll: Fn(&PathBuf) -> Result<(), String>,
+{
+    let entry_path = PathBuf::from(&entry.path);
+    if !entry_path.exists() {
+        return Ok(());
+    }
+    if !parent_path_exists {
+        return remove_dir_all(&entry_path);
+    }
+    match run_git_command(parent_path, &["worktree", "remove", "--force", &entry.path]).await {
+        Ok(()) => Ok(()),
+        Err(error) if is_missing_worktree_error(&error) => {
+            if entry_path.exists() {
+                remove_dir_all(&entry_path)?;
+            }
+            Ok(())
+        }
+        Err(error) => Err(error),
+    }
+}
+
+/// Bulk variant of `remove_workspace_core`/`remove_worktree_core`. Worktrees
+/// are grouped by parent so `worktree prune` runs once per repo, and the
+/// workspace list is written once. A failure on one id never aborts the
+/// rest; results come back in request order.
+pub(crate) async fn remove_workspaces_core<FRunGit, FutRunGit, FIsMissing, FRemoveDirAll>(
+    ids: Vec<String>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    storage_path: &PathBuf,
+    run_git_command: FRunGit,
+    is_missing_worktree_error: FIsMissing,
+    remove_dir_all: FRemoveDirAll,
+) -> Result<Vec<WorkspaceRemovalResult>, String>
+where
+    FRunGit: Fn(&PathBuf, &[&str]) -> FutRunGit,
+    FutRunGit: Future<Output = Result<(), String>>,
+    FIsMissing: Fn(&str) -> bool,
+    FRemoveDirAll: Fn(&PathBuf) -> Result<(), String>,
+{
+    let snapshot = workspaces.lock().await.clone();
+    let mut order: Vec<String> = Vec::new();
+    let mut errors: HashMap<String, String> = HashMap::new();
+    // Parent id -> worktrees to remove from that repo.
+    let mut groups: HashMap<String, Vec<WorkspaceEntry>> = HashMap::new();
+    let mut scheduled: HashSet<String> = HashSet::new();
+    let mut main_ids: Vec<String> = Vec::new();
+
+    for id in ids {
+        if order.contains(&id) {
+            continue;
+        }
+        order.push(id.clone());
+        let Some(entry) = snapshot.get(&id) else {
+            errors.insert(id, "workspace not found".to_string());
+            continue;
+        };
+        if entry.kind.is_worktree() {
+            let Some(parent_id) = entry
+                .parent_id
+                .clone()
+                .filter(|parent_id| snapshot.contains_key(parent_id))
+            else {
+                errors.insert(id, "worktree parent not found".to_string());
+                continue;
+            };
+            if scheduled.insert(id) {
+                groups.entry(parent_id).or_default().push(entry.clone());
+            }
+            continue;
+        }
+        for child in snapshot
+            .values()
+            .filter(|workspace| workspace.parent_id.as_deref() == Some(id.as_str()))
+        {
+            if scheduled.insert(child.id.clone()) {
+                groups.entry(id.clone()).or_default().push(child.clone());
+            }
+        }
+        main_ids.push(id);
+    }
+
+    let mut removed: HashSet<String> = HashSet::new();
+    let mut failures: HashMap<String, String> = HashMap::new();
+    for (parent_id, children) in &groups {
+        let parent_path = PathBuf::from(&snapshot[parent_id].path);
+        let parent_path_exists = parent_path.is_dir();
+        for child in children {
+            kill_session_by_id(sessions, &child.id).await;
+            match remove_worktree_folder(
+                &parent_path,
+                parent_path_exists,
+                child,
+                &run_git_command,
+                &is_missing_worktree_error,
+                &remove_dir_all,
+            )
+            .await
+            {
+                Ok(()) => {
+                    removed.insert(child.id.clone());
+                }
+                Err(error) => {
+                    failures.insert(child.id.clone(), error);
+                }
+            }
+        }
+        if parent_path_exists {
+            let _ = run_git_command(&parent_path, &["worktree", "prune", "--expire", "now"]).await;
+        }
+    }
+
+    for id in main_ids {
+        let child_failures: Vec<(&String, &String)> = groups
+            .get(&id)
+            .into_iter()
+            .flatten()
+            .filter_map(|child| failures.get_key_value(&child.id))
+            .collect();
+        if child_failures.is_empty() {
+            kill_session_by_id(sessions, &id).await;
+            removed.insert(id);
+            continue;
+        }
+        let mut message =
+            "Failed to remove one or more worktrees; parent workspace was not removed.".to_string();
+        for (child_id, error) in child_failures {
+            message.push_str(&format!("\n- {child_id}: {error}"));
+        }
+        errors.insert(id, message);
+    }
+
+    {
+        let mut workspaces = workspaces.lock().await;
+        for id in &removed {
+            workspaces.remove(id);
+        }
+        let list: Vec<_> = workspaces.values().cloned().collect();
+        write_workspaces(storage_path, &list)?;
+    }
+
+    Ok(order
+        .into_iter()
+        .map(|id| {
+            let error = errors.remove(&id).or_else(|| failures.remove(&id));
+            WorkspaceRemovalResult {
+                removed: removed.contains(&id),
+                id,
+                error,
+            }
+        })
+        .collect())
+}
+
 pub(crate) async fn update_workspace_settings_core<FApplySettings, FSpawn, FutSpawn>(
     id: String,
     mut settings: WorkspaceSettings,
@@ -354,6 +649,11 @@ where
     FutSpawn: Future<Output = Result<Arc<WorkspaceSession>, String>>,
 {
     settings.worktree_setup_script = normalize_setup_script(settings.worktree_setup_script);
+    settings.color = normalize_workspace_color(settings.color)?;
+    settings.codex_base_url = normalize_codex_base_url(settings.codex_base_url)?;
+    settings.env = normalize_workspace_env(settings.env)?;
+    settings.auto_compact_at_pct =
+        auto_compact_core::validate_auto_compact_at_pct(settings.auto_compact_at_pct)?;
 
     let (
         previous_entry,
@@ -361,6 +661,8 @@ where
         parent_entry,
         previous_codex_home,
         previous_codex_args,
+        previous_codex_base_url,
+        previous_env,
         previous_worktree_setup_script,
         child_entries,
     ) = {
@@ -371,6 +673,8 @@ where
             .ok_or_else(|| "workspace not found".to_string())?;
         let previous_codex_home = previous_entry.settings.codex_home.clone();
         let previous_codex_args = previous_entry.settings.codex_args.clone();
+        let previous_codex_base_url = previous_entry.settings.codex_base_url.clone();
+        let previous_env = previous_entry.settings.env.clone();
         let previous_worktree_setup_script = previous_entry.settings.worktree_setup_script.clone();
         let entry_snapshot = apply_settings_update(&mut workspaces, &id, settings)?;
         let parent_entry = entry_snapshot
@@ -389,6 +693,8 @@ where
             parent_entry,
             previous_codex_home,
             previous_codex_args,
+            previous_codex_base_url,
+            previous_env,
             previous_worktree_setup_script,
             child_entries,
         )
@@ -396,10 +702,14 @@ where
 
     let codex_home_changed = previous_codex_home != entry_snapshot.settings.codex_home;
     let codex_args_changed = previous_codex_args != entry_snapshot.settings.codex_args;
+    let codex_base_url_changed = previous_codex_base_url != entry_snapshot.settings.codex_base_url;
+    let env_changed = previous_env != entry_snapshot.settings.env;
+    let session_config_changed =
+        codex_home_changed || codex_args_changed || codex_base_url_changed || env_changed;
     let worktree_setup_script_changed =
         previous_worktree_setup_script != entry_snapshot.settings.worktree_setup_script;
     let connected = sessions.lock().await.contains_key(&id);
-    if connected && (codex_home_changed || codex_args_changed) {
+    if connected && session_config_changed {
         let rollback_entry = previous_entry.clone();
         let (default_bin, codex_args) = {
             let settings = app_settings.lock().await;
@@ -414,7 +724,7 @@ where
         };
         let codex_home = resolve_workspace_codex_home(&entry_snapshot, parent_entry.as_ref());
         let new_session = match spawn_session(
-            entry_snapshot.clone(),
+            with_inherited_session_settings(&entry_snapshot, parent_entry.as_ref()),
             default_bin,
             codex_args,
             codex_home,
@@ -437,7 +747,7 @@ where
             kill_child_process_tree(&mut child).await;
         }
     }
-    if codex_home_changed || codex_args_changed {
+    if session_config_changed {
         let app_settings_snapshot = app_settings.lock().await.clone();
         let default_bin = app_settings_snapshot.codex_bin.clone();
         for child in &child_entries {
@@ -457,11 +767,21 @@ where
                 Some(&entry_snapshot),
                 Some(&app_settings_snapshot),
             );
-            if previous_child_home == next_child_home && previous_child_args == next_child_args {
+            let previous_child_base_url =
+                resolve_workspace_codex_base_url(child, Some(&previous_entry));
+            let next_child_base_url =
+                resolve_workspace_codex_base_url(child, Some(&entry_snapshot));
+            let previous_child_env = resolve_workspace_env(child, Some(&previous_entry));
+            let next_child_env = resolve_workspace_env(child, Some(&entry_snapshot));
+            if previous_child_home == next_child_home
+                && previous_child_args == next_child_args
+                && previous_child_base_url == next_child_base_url
+                && previous_child_env == next_child_env
+            {
                 continue;
             }
             let new_session = match spawn_session(
-                child.clone(),
+                with_inherited_session_settings(child, Some(&entry_snapshot)),
                 default_bin.clone(),
                 next_child_args,
                 next_child_home,
@@ -503,6 +823,8 @@ where
         workspaces.values().cloned().collect()
     };
     write_workspaces(storage_path, &list)?;
+    let color = resolve_workspace_color(&entry_snapshot, parent_entry.as_ref());
+    let path_exists = workspace_path_exists(&entry_snapshot.path).await;
     Ok(WorkspaceInfo {
         id: entry_snapshot.id,
         name: entry_snapshot.name,
@@ -513,9 +835,69 @@ where
         parent_id: entry_snapshot.parent_id,
         worktree: entry_snapshot.worktree,
         settings: entry_snapshot.settings,
+        color,
+        path_exists,
     })
 }
 
+/// Points the workspace's `codexHome` at the profile's home, so the usual
+/// settings update respawns it (and any inheriting worktrees) under the new
+/// account. `None` drops the profile and its home.
+pub(crate) async fn set_workspace_profile_core<FApplySettings, FSpawn, FutSpawn>(
+    id: String,
+    profile_id: Option<String>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    app_settings: &Mutex<AppSettings>,
+    storage_path: &PathBuf,
+    apply_settings_update: FApplySettings,
+    spawn_session: FSpawn,
+) -> Result<WorkspaceInfo, String>
+where
+    FApplySettings: Fn(
+        &mut HashMap<String, WorkspaceEntry>,
+        &str,
+        WorkspaceSettings,
+    ) -> Result<WorkspaceEntry, String>,
+    FSpawn: Fn(WorkspaceEntry, Option<String>, Option<String>, Option<PathBuf>) -> FutSpawn,
+    FutSpawn: Future<Output = Result<Arc<WorkspaceSession>, String>>,
+{
+    let profile = match profile_id.as_deref().map(str::trim) {
+        Some(profile_id) if !profile_id.is_empty() => {
+            Some(profiles_core::find_profile_core(app_settings, profile_id).await?)
+        }
+        _ => None,
+    };
+    let mut settings = workspaces
+        .lock()
+        .await
+        .get(&id)
+        .map(|entry| entry.settings.clone())
+        .ok_or_else(|| "workspace not found".to_string())?;
+    match profile {
+        Some(profile) => {
+            settings.codex_home = Some(profile.codex_home);
+            settings.profile_id = Some(profile.id);
+        }
+        None => {
+            if settings.profile_id.take().is_some() {
+                settings.codex_home = None;
+            }
+        }
+    }
+    update_workspace_settings_core(
+        id,
+        settings,
+        workspaces,
+        sessions,
+        app_settings,
+        storage_path,
+        apply_settings_update,
+        spawn_session,
+    )
+    .await
+}
+
 pub(crate) async fn update_workspace_codex_bin_core(
     id: String,
     codex_bin: Option<String>,
@@ -523,7 +905,7 @@ pub(crate) async fn update_workspace_codex_bin_core(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
     storage_path: &PathBuf,
 ) -> Result<WorkspaceInfo, String> {
-    let (entry_snapshot, list) = {
+    let (entry_snapshot, color, list) = {
         let mut workspaces = workspaces.lock().await;
         let entry_snapshot = match workspaces.get_mut(&id) {
             Some(entry) => {
@@ -532,12 +914,110 @@ pub(crate) async fn update_workspace_codex_bin_core(
             }
             None => return Err("workspace not found".to_string()),
         };
+        let parent_entry = entry_snapshot
+            .parent_id
+            .as_ref()
+            .and_then(|parent_id| workspaces.get(parent_id));
+        let color = resolve_workspace_color(&entry_snapshot, parent_entry);
+        let list: Vec<_> = workspaces.values().cloned().collect();
+        (entry_snapshot, color, list)
+    };
+    write_workspaces(storage_path, &list)?;
+
+    let connected = sessions.lock().await.contains_key(&id);
+    let path_exists = workspace_path_exists(&entry_snapshot.path).await;
+    Ok(WorkspaceInfo {
+        id: entry_snapshot.id,
+        name: entry_snapshot.name,
+        path: entry_snapshot.path,
+        codex_bin: entry_snapshot.codex_bin,
+        connected,
+        kind: entry_snapshot.kind,
+        parent_id: entry_snapshot.parent_id,
+        worktree: entry_snapshot.worktree,
+        settings: entry_snapshot.settings,
+        color,
+        path_exists,
+    })
+}
+
+pub(crate) async fn set_workspace_color_core(
+    id: String,
+    color: Option<String>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    storage_path: &PathBuf,
+) -> Result<WorkspaceInfo, String> {
+    let color = normalize_workspace_color(color)?;
+    let (entry_snapshot, effective_color, list) = {
+        let mut workspaces = workspaces.lock().await;
+        let entry_snapshot = match workspaces.get_mut(&id) {
+            Some(entry) => {
+                entry.settings.color = color;
+                entry.clone()
+            }
+            None => return Err("workspace not found".to_string()),
+        };
+        let parent_entry = entry_snapshot
+            .parent_id
+            .as_ref()
+            .and_then(|parent_id| workspaces.get(parent_id));
+        let effective_color = resolve_workspace_color(&entry_snapshot, parent_entry);
+        let list: Vec<_> = workspaces.values().cloned().collect();
+        (entry_snapshot, effective_color, list)
+    };
+    write_workspaces(storage_path, &list)?;
+
+    let connected = sessions.lock().await.contains_key(&id);
+    let path_exists = workspace_path_exists(&entry_snapshot.path).await;
+    Ok(WorkspaceInfo {
+        id: entry_snapshot.id,
+        name: entry_snapshot.name,
+        path: entry_snapshot.path,
+        codex_bin: entry_snapshot.codex_bin,
+        connected,
+        kind: entry_snapshot.kind,
+        parent_id: entry_snapshot.parent_id,
+        worktree: entry_snapshot.worktree,
+        settings: entry_snapshot.settings,
+        color: effective_color,
+        path_exists,
+    })
+}
+
+/// Relabels a main workspace without touching its folder; worktree children
+/// keep their own names.
+pub(crate) async fn rename_workspace_core(
+    id: String,
+    name: String,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    storage_path: &PathBuf,
+) -> Result<WorkspaceInfo, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Workspace name is required.".to_string());
+    }
+    let (entry_snapshot, color, list) = {
+        let mut workspaces = workspaces.lock().await;
+        let entry_snapshot = match workspaces.get_mut(&id) {
+            Some(entry) if entry.kind.is_worktree() => {
+                return Err("Use rename_worktree to rename a worktree.".to_string());
+            }
+            Some(entry) => {
+                entry.name = name;
+                entry.clone()
+            }
+            None => return Err("workspace not found".to_string()),
+        };
+        let color = resolve_workspace_color(&entry_snapshot, None);
         let list: Vec<_> = workspaces.values().cloned().collect();
-        (entry_snapshot, list)
+        (entry_snapshot, color, list)
     };
     write_workspaces(storage_path, &list)?;
 
     let connected = sessions.lock().await.contains_key(&id);
+    let path_exists = workspace_path_exists(&entry_snapshot.path).await;
     Ok(WorkspaceInfo {
         id: entry_snapshot.id,
         name: entry_snapshot.name,
@@ -548,5 +1028,7 @@ pub(crate) async fn update_workspace_codex_bin_core(
         parent_id: entry_snapshot.parent_id,
         worktree: entry_snapshot.worktree,
         settings: entry_snapshot.settings,
+        color,
+        path_exists,
     })
 }
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::future::Future;
 use std::path::PathBuf;
-use std::process::Stdio;
+use std::process::{Output, Stdio};
 
 use tokio::io::AsyncWriteExt;
 use tokio::sync::Mutex;
@@ -9,7 +9,7 @@ use tokio::sync::Mutex;
 use crate::git_utils::resolve_git_root;
 use crate::shared::process_core::tokio_command;
 use crate::shared::{git_core, worktree_core};
-use crate::types::WorkspaceEntry;
+use crate::types::{WorkspaceEntry, WorktreeChangedFile, WorktreeChangesPreview};
 
 pub(crate) fn run_git_command_unit<F, Fut>(
     repo_path: &PathBuf,
@@ -32,62 +32,295 @@ where
     }
 }
 
+async fn resolve_worktree_and_parent(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: &str,
+) -> Result<(WorkspaceEntry, WorkspaceEntry), String> {
+    let workspaces = workspaces.lock().await;
+    let entry = workspaces
+        .get(workspace_id)
+        .cloned()
+        .ok_or_else(|| "workspace not found".to_string())?;
+    if !entry.kind.is_worktree() {
+        return Err("Not a worktree workspace.".to_string());
+    }
+    let parent_id = entry
+        .parent_id
+        .clone()
+        .ok_or_else(|| "worktree parent not found".to_string())?;
+    let parent = workspaces
+        .get(&parent_id)
+        .cloned()
+        .ok_or_else(|| "worktree parent not found".to_string())?;
+    Ok((entry, parent))
+}
+
 pub(crate) async fn apply_worktree_changes_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
 ) -> Result<(), String> {
-    let (entry, parent) = {
-        let workspaces = workspaces.lock().await;
-        let entry = workspaces
-            .get(&workspace_id)
-            .cloned()
-            .ok_or_else(|| "workspace not found".to_string())?;
-        if !entry.kind.is_worktree() {
-            return Err("Not a worktree workspace.".to_string());
+    let (entry, parent) = resolve_worktree_and_parent(workspaces, &workspace_id).await?;
+    apply_worktree_changes_inner_core(&entry, &parent).await
+}
+
+/// Commit-preserving alternative to `apply_worktree_changes`: cherry-picks the
+/// worktree's commits since it forked from the parent's branch, keeping their
+/// messages and authorship. Uncommitted work in the worktree is not carried.
+pub(crate) async fn apply_worktree_commits_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<(), String> {
+    let (entry, parent) = resolve_worktree_and_parent(workspaces, &workspace_id).await?;
+    let worktree_root = resolve_git_root(&entry)?;
+    let parent_root = resolve_git_root(&parent)?;
+
+    if has_uncommitted_changes(&parent_root).await? {
+        return Err(
+            "Your current branch has uncommitted changes. Please commit, stash, or discard them before applying worktree commits."
+                .to_string(),
+        );
+    }
+
+    let worktree_head = git_core::run_git_command(&worktree_root, &["rev-parse", "HEAD"]).await?;
+    let base =
+        git_core::run_git_command(&parent_root, &["merge-base", "HEAD", &worktree_head]).await?;
+    let range = format!("{base}..{worktree_head}");
+    let commits = git_core::run_git_command(&parent_root, &["rev-list", "--count", &range]).await?;
+    if commits.trim() == "0" {
+        return Err("No commits to apply.".to_string());
+    }
+    let merges =
+        git_core::run_git_command(&parent_root, &["rev-list", "--merges", "--count", &range])
+            .await?;
+    if merges.trim() != "0" {
+        return Err(
+            "The worktree's commits include a merge, which can't be cherry-picked. Apply its changes instead."
+                .to_string(),
+        );
+    }
+    let pre_pick_head = git_core::run_git_command(&parent_root, &["rev-parse", "HEAD"]).await?;
+
+    let Err(error) = git_core::run_git_command(&parent_root, &["cherry-pick", &range]).await else {
+        return Ok(());
+    };
+    let conflicting =
+        git_core::run_git_command(&parent_root, &["rev-parse", "--verify", "CHERRY_PICK_HEAD"])
+            .await
+            .ok();
+    let failure = match conflicting {
+        Some(conflicting) => {
+            let summary = git_core::run_git_command(
+                &parent_root,
+                &["log", "-1", "--format=%h \"%s\"", &conflicting],
+            )
+            .await
+            .unwrap_or(conflicting);
+            format!("Cherry-picking {summary} conflicted")
         }
-        let parent_id = entry
-            .parent_id
-            .clone()
-            .ok_or_else(|| "worktree parent not found".to_string())?;
-        let parent = workspaces
-            .get(&parent_id)
-            .cloned()
-            .ok_or_else(|| "worktree parent not found".to_string())?;
-        (entry, parent)
+        None => format!("Cherry-picking failed: {error}"),
     };
+    match restore_pre_pick_head(&parent_root, &pre_pick_head).await {
+        Ok(()) => Err(format!(
+            "{failure}. The cherry-pick was aborted and your branch is unchanged."
+        )),
+        Err(restore_error) => Err(format!(
+            "{failure}, and restoring your branch failed: {restore_error}. Run `git cherry-pick --abort` in the parent repo."
+        )),
+    }
+}
 
-    apply_worktree_changes_inner_core(&entry, &parent).await
+/// Undoes a failed `cherry-pick <range>`. The sequencer is aborted while it
+/// is still in progress; either way the branch is reset to `pre_pick_head`
+/// if commits picked before the failure are left on it. The parent was
+/// clean beforehand, so the hard reset loses nothing.
+async fn restore_pre_pick_head(repo_root: &PathBuf, pre_pick_head: &str) -> Result<(), String> {
+    if cherry_pick_in_progress(repo_root).await
+        && git_core::run_git_command(repo_root, &["cherry-pick", "--abort"])
+            .await
+            .is_err()
+    {
+        git_core::run_git_command(repo_root, &["cherry-pick", "--quit"]).await?;
+    }
+    let head = git_core::run_git_command(repo_root, &["rev-parse", "HEAD"]).await?;
+    if head != pre_pick_head {
+        git_core::run_git_command(repo_root, &["reset", "--hard", pre_pick_head]).await?;
+    }
+    Ok(())
 }
 
-pub(super) async fn apply_worktree_changes_inner_core(
-    entry: &WorkspaceEntry,
-    parent: &WorkspaceEntry,
+async fn cherry_pick_in_progress(repo_root: &PathBuf) -> bool {
+    let sequencer = git_core::run_git_command(repo_root, &["rev-parse", "--git-path", "sequencer"])
+        .await
+        .map(|path| repo_root.join(path).exists())
+        .unwrap_or(false);
+    sequencer
+        || git_core::run_git_command(repo_root, &["rev-parse", "--verify", "CHERRY_PICK_HEAD"])
+            .await
+            .is_ok()
+}
+
+/// Moves the worktree's uncommitted changes, untracked files included, onto
+/// the parent's branch through a stash: pushed in the worktree, re-stored in
+/// the parent and popped there. Nothing is committed on either side.
+pub(crate) async fn move_worktree_changes_back_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
 ) -> Result<(), String> {
-    let worktree_root = resolve_git_root(entry)?;
-    let parent_root = resolve_git_root(parent)?;
+    let (entry, parent) = resolve_worktree_and_parent(workspaces, &workspace_id).await?;
+    let worktree_root = resolve_git_root(&entry)?;
+    let parent_root = resolve_git_root(&parent)?;
 
-    let parent_status =
-        git_core::run_git_command_bytes(&parent_root, &["status", "--porcelain"]).await?;
-    if !String::from_utf8_lossy(&parent_status).trim().is_empty() {
+    if has_uncommitted_changes(&parent_root).await? {
         return Err(
-            "Your current branch has uncommitted changes. Please commit, stash, or discard them before applying worktree changes."
+            "Your current branch has uncommitted changes. Please commit, stash, or discard them before moving worktree changes."
                 .to_string(),
         );
     }
+    if !has_uncommitted_changes(&worktree_root).await? {
+        return Err("No changes to move.".to_string());
+    }
+
+    let message = format!("codex-monitor: moved from worktree {}", entry.name);
+    git_core::run_git_command(
+        &worktree_root,
+        &["stash", "push", "--include-untracked", "-m", &message],
+    )
+    .await?;
+    let stash =
+        git_core::run_git_command(&worktree_root, &["rev-parse", "--verify", "refs/stash"]).await?;
+    // Worktrees share `refs/stash` with the parent, so the entry is dropped
+    // and stored again to make it the parent's newest stash either way.
+    git_core::run_git_command(&worktree_root, &["stash", "drop", "--quiet"]).await?;
+    if let Err(error) =
+        git_core::run_git_command(&parent_root, &["stash", "store", "-m", &message, &stash]).await
+    {
+        let restored = git_core::run_git_command(&worktree_root, &["stash", "apply", &stash]).await;
+        return Err(match restored {
+            Ok(_) => format!("Could not move the stash to the parent repo: {error}"),
+            Err(_) => format!(
+                "Could not move the stash to the parent repo: {error}. Your changes are in commit {stash}; run `git stash apply {stash}` in the worktree to restore them."
+            ),
+        });
+    }
+
+    let Err(error) = git_core::run_git_command(&parent_root, &["stash", "pop"]).await else {
+        return Ok(());
+    };
+    let conflicted =
+        git_core::run_git_command(&parent_root, &["diff", "--name-only", "--diff-filter=U"])
+            .await
+            .unwrap_or_default();
+    if conflicted.is_empty() {
+        return Err(format!(
+            "Could not pop the moved changes in the parent repo: {error}. They are kept as stash@{{0}} there."
+        ));
+    }
+    let files = conflicted.lines().collect::<Vec<_>>().join(", ");
+    Err(format!(
+        "Moved with conflicts in {files}. Resolve them in the parent repo, then drop stash@{{0}}."
+    ))
+}
+
+/// Dry run of `apply_worktree_changes`: the same patch is checked against the
+/// parent with `git apply --3way --check`, which writes nothing.
+pub(crate) async fn preview_worktree_changes_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<WorktreeChangesPreview, String> {
+    let (entry, parent) = resolve_worktree_and_parent(workspaces, &workspace_id).await?;
+    let worktree_root = resolve_git_root(&entry)?;
+    let parent_root = resolve_git_root(&parent)?;
+
+    let parent_dirty = has_uncommitted_changes(&parent_root).await?;
+    let patch = build_worktree_patch(&worktree_root).await?;
+    if String::from_utf8_lossy(&patch).trim().is_empty() {
+        return Ok(WorktreeChangesPreview {
+            files: Vec::new(),
+            additions: 0,
+            deletions: 0,
+            would_conflict: false,
+            conflict_detail: None,
+            parent_dirty,
+        });
+    }
+
+    let numstat = run_git_apply(&parent_root, &["apply", "--numstat", "-"], &patch).await?;
+    if !numstat.status.success() {
+        return Err(git_apply_detail(&numstat).unwrap_or_else(|| "Git apply failed.".to_string()));
+    }
+    let files = parse_apply_numstat(&String::from_utf8_lossy(&numstat.stdout));
+    let additions = files.iter().map(|file| file.additions).sum();
+    let deletions = files.iter().map(|file| file.deletions).sum();
+
+    let check = run_git_apply(
+        &parent_root,
+        &["apply", "--3way", "--check", "--whitespace=nowarn", "-"],
+        &patch,
+    )
+    .await?;
+    let detail = git_apply_detail(&check);
+    // A 3-way check that falls back to merging still exits 0 and only says
+    // "with conflicts" in its report.
+    let would_conflict = !check.status.success()
+        || detail
+            .as_deref()
+            .is_some_and(|detail| detail.contains("with conflicts"));
+    let conflict_detail = if would_conflict { detail } else { None };
+
+    Ok(WorktreeChangesPreview {
+        files,
+        additions,
+        deletions,
+        would_conflict,
+        conflict_detail,
+        parent_dirty,
+    })
+}
+
+/// Parses `git apply --numstat`; binary files report `-` for both counts.
+pub(super) fn parse_apply_numstat(output: &str) -> Vec<WorktreeChangedFile> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, '\t');
+            let additions = parts.next()?;
+            let deletions = parts.next()?;
+            let path = parts.next()?.trim();
+            if path.is_empty() {
+                return None;
+            }
+            let binary = additions == "-" && deletions == "-";
+            Some(WorktreeChangedFile {
+                path: path.to_string(),
+                additions: additions.parse().unwrap_or(0),
+                deletions: deletions.parse().unwrap_or(0),
+                binary,
+            })
+        })
+        .collect()
+}
+
+async fn has_uncommitted_changes(repo_root: &PathBuf) -> Result<bool, String> {
+    let status = git_core::run_git_command_bytes(repo_root, &["status", "--porcelain"]).await?;
+    Ok(!String::from_utf8_lossy(&status).trim().is_empty())
+}
 
+/// Staged, unstaged and untracked changes of the worktree as one binary
+/// patch. Shared by apply and preview so both see the same changes.
+async fn build_worktree_patch(worktree_root: &PathBuf) -> Result<Vec<u8>, String> {
     let mut patch: Vec<u8> = Vec::new();
     let staged_patch = git_core::run_git_diff(
-        &worktree_root,
+        worktree_root,
         &["diff", "--binary", "--no-color", "--cached"],
     )
     .await?;
     patch.extend_from_slice(&staged_patch);
     let unstaged_patch =
-        git_core::run_git_diff(&worktree_root, &["diff", "--binary", "--no-color"]).await?;
+        git_core::run_git_diff(worktree_root, &["diff", "--binary", "--no-color"]).await?;
     patch.extend_from_slice(&unstaged_patch);
 
     let untracked_output = git_core::run_git_command_bytes(
-        &worktree_root,
+        worktree_root,
         &["ls-files", "--others", "--exclude-standard", "-z"],
     )
     .await?;
@@ -97,7 +330,7 @@ pub(super) async fn apply_worktree_changes_inner_core(
         }
         let path = String::from_utf8_lossy(raw_path).to_string();
         let diff = git_core::run_git_diff(
-            &worktree_root,
+            worktree_root,
             &[
                 "diff",
                 "--binary",
@@ -111,16 +344,19 @@ pub(super) async fn apply_worktree_changes_inner_core(
         .await?;
         patch.extend_from_slice(&diff);
     }
+    Ok(patch)
+}
 
-    if String::from_utf8_lossy(&patch).trim().is_empty() {
-        return Err("No changes to apply.".to_string());
-    }
-
+async fn run_git_apply(
+    parent_root: &PathBuf,
+    args: &[&str],
+    patch: &[u8],
+) -> Result<Output, String> {
     let git_bin =
         crate::utils::resolve_git_binary().map_err(|e| format!("Failed to run git: {e}"))?;
     let mut child = tokio_command(git_bin)
-        .args(["apply", "--3way", "--whitespace=nowarn", "-"])
-        .current_dir(&parent_root)
+        .args(args)
+        .current_dir(parent_root)
         .env("PATH", crate::utils::git_env_path())
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
@@ -130,20 +366,18 @@ pub(super) async fn apply_worktree_changes_inner_core(
 
     if let Some(mut stdin) = child.stdin.take() {
         stdin
-            .write_all(&patch)
+            .write_all(patch)
             .await
             .map_err(|e| format!("Failed to write git apply input: {e}"))?;
     }
 
-    let output = child
+    child
         .wait_with_output()
         .await
-        .map_err(|e| format!("Failed to run git: {e}"))?;
-
-    if output.status.success() {
-        return Ok(());
-    }
+        .map_err(|e| format!("Failed to run git: {e}"))
+}
 
+fn git_apply_detail(output: &Output) -> Option<String> {
     let stderr = String::from_utf8_lossy(&output.stderr);
     let stdout = String::from_utf8_lossy(&output.stdout);
     let detail = if stderr.trim().is_empty() {
@@ -151,10 +385,43 @@ pub(super) async fn apply_worktree_changes_inner_core(
     } else {
         stderr.trim()
     };
-    if detail.is_empty() {
-        return Err("Git apply failed.".to_string());
+    (!detail.is_empty()).then(|| detail.to_string())
+}
+
+pub(super) async fn apply_worktree_changes_inner_core(
+    entry: &WorkspaceEntry,
+    parent: &WorkspaceEntry,
+) -> Result<(), String> {
+    let worktree_root = resolve_git_root(entry)?;
+    let parent_root = resolve_git_root(parent)?;
+
+    if has_uncommitted_changes(&parent_root).await? {
+        return Err(
+            "Your current branch has uncommitted changes. Please commit, stash, or discard them before applying worktree changes."
+                .to_string(),
+        );
     }
 
+    let patch = build_worktree_patch(&worktree_root).await?;
+    if String::from_utf8_lossy(&patch).trim().is_empty() {
+        return Err("No changes to apply.".to_string());
+    }
+
+    let output = run_git_apply(
+        &parent_root,
+        &["apply", "--3way", "--whitespace=nowarn", "-"],
+        &patch,
+    )
+    .await?;
+
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let Some(detail) = git_apply_detail(&output) else {
+        return Err("Git apply failed.".to_string());
+    };
+
     if detail.contains("Applied patch to") {
         if detail.contains("with conflicts") {
             return Err(
@@ -168,5 +435,115 @@ pub(super) async fn apply_worktree_changes_inner_core(
         );
     }
 
-    Err(detail.to_string())
+    Err(detail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_apply_numstat_reads_counts_and_binary_files() {
+        let files = parse_apply_numstat("3\t1\tsrc/main.rs\n-\t-\tassets/logo.png\n\n");
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, "src/main.rs");
+        assert_eq!((files[0].additions, files[0].deletions), (3, 1));
+        assert!(!files[0].binary);
+        assert_eq!(files[1].path, "assets/logo.png");
+        assert!(files[1].binary);
+    }
+
+    fn git(dir: &std::path::Path, args: &[&str]) -> String {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .expect("run git");
+        assert!(output.status.success(), "git {args:?}: {output:?}");
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    #[test]
+    fn apply_worktree_commits_restores_the_branch_when_a_later_pick_conflicts() {
+        use crate::types::{WorkspaceKind, WorkspaceSettings, WorktreeInfo};
+
+        let root =
+            std::env::temp_dir().join(format!("codex-monitor-pick-{}", uuid::Uuid::new_v4()));
+        let parent_root = root.join("parent");
+        let worktree_root = root.join("wt");
+        std::fs::create_dir_all(&parent_root).expect("create parent");
+        git(&parent_root, &["init", "--quiet"]);
+        git(&parent_root, &["config", "user.name", "Test"]);
+        git(&parent_root, &["config", "user.email", "test@example.com"]);
+        std::fs::write(parent_root.join("a.txt"), "base\n").expect("write");
+        git(&parent_root, &["add", "."]);
+        git(&parent_root, &["commit", "--quiet", "-m", "base"]);
+        let worktree_path = worktree_root.to_string_lossy().to_string();
+        git(
+            &parent_root,
+            &["worktree", "add", "--quiet", "-b", "wt", &worktree_path],
+        );
+
+        std::fs::write(worktree_root.join("b.txt"), "new\n").expect("write");
+        git(&worktree_root, &["add", "."]);
+        git(&worktree_root, &["commit", "--quiet", "-m", "add b"]);
+        std::fs::write(worktree_root.join("a.txt"), "worktree\n").expect("write");
+        git(&worktree_root, &["commit", "--quiet", "-am", "edit a"]);
+        std::fs::write(parent_root.join("a.txt"), "parent\n").expect("write");
+        git(
+            &parent_root,
+            &["commit", "--quiet", "-am", "edit a in parent"],
+        );
+        let parent_head = git(&parent_root, &["rev-parse", "HEAD"]);
+
+        let entry = |id: &str, path: &std::path::Path, worktree: bool| WorkspaceEntry {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: path.to_string_lossy().to_string(),
+            codex_bin: None,
+            kind: if worktree {
+                WorkspaceKind::Worktree
+            } else {
+                WorkspaceKind::Main
+            },
+            parent_id: worktree.then(|| "parent".to_string()),
+            worktree: worktree.then(|| WorktreeInfo {
+                branch: "wt".to_string(),
+            }),
+            settings: WorkspaceSettings::default(),
+        };
+        let workspaces = Mutex::new(HashMap::from([
+            ("parent".to_string(), entry("parent", &parent_root, false)),
+            ("wt".to_string(), entry("wt", &worktree_root, true)),
+        ]));
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        let apply = || runtime.block_on(apply_worktree_commits_core(&workspaces, "wt".to_string()));
+
+        let conflict = apply().unwrap_err();
+        assert!(conflict.contains("\"edit a\" conflicted"), "{conflict}");
+        assert!(conflict.contains("your branch is unchanged"), "{conflict}");
+        assert_eq!(git(&parent_root, &["rev-parse", "HEAD"]), parent_head);
+        assert!(!parent_root.join("b.txt").exists());
+        assert!(!parent_root.join(".git").join("sequencer").exists());
+
+        git(&worktree_root, &["reset", "--quiet", "--hard", "HEAD~1"]);
+        git(&worktree_root, &["checkout", "--quiet", "-b", "side"]);
+        git(
+            &worktree_root,
+            &["commit", "--quiet", "--allow-empty", "-m", "side"],
+        );
+        git(&worktree_root, &["checkout", "--quiet", "wt"]);
+        git(
+            &worktree_root,
+            &["merge", "--quiet", "--no-ff", "-m", "merge side", "side"],
+        );
+        let merge = apply().unwrap_err();
+        assert!(merge.contains("include a merge"), "{merge}");
+        assert_eq!(git(&parent_root, &["rev-parse", "HEAD"]), parent_head);
+
+        let _ = std::fs::remove_dir_all(root);
+    }
 }
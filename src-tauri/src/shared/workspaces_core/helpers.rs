@@ -1,8 +1,11 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use tokio::sync::Mutex;
+use tokio::task::JoinSet;
+use tokio::time::Instant;
 
 use crate::backend::app_server::WorkspaceSession;
 use crate::types::{WorkspaceEntry, WorkspaceInfo};
@@ -10,6 +13,10 @@ use crate::types::{WorkspaceEntry, WorkspaceInfo};
 pub(crate) const WORKTREE_SETUP_MARKERS_DIR: &str = "worktree-setup";
 pub(crate) const WORKTREE_SETUP_MARKER_EXT: &str = "ran";
 pub(super) const AGENTS_MD_FILE_NAME: &str = "AGENTS.md";
+/// How long folder checks may take in total. A stat on a stalled network
+/// mount can hang, and such a folder counts as present rather than holding up
+/// the list.
+const PATH_CHECK_TIMEOUT: Duration = Duration::from_millis(500);
 
 pub(super) fn copy_agents_md_from_parent_to_worktree(
     parent_repo_root: &PathBuf,
@@ -56,6 +63,38 @@ pub(crate) fn normalize_setup_script(script: Option<String>) -> Option<String> {
     }
 }
 
+/// Accepts `#rgb` or `#rrggbb` and stores the long lowercase form; blank clears.
+pub(crate) fn normalize_workspace_color(color: Option<String>) -> Result<Option<String>, String> {
+    let Some(value) = color else {
+        return Ok(None);
+    };
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Ok(None);
+    }
+    let hex = trimmed
+        .strip_prefix('#')
+        .filter(|hex| matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+        .ok_or_else(|| format!("Invalid workspace color `{trimmed}`; expected #rrggbb."))?
+        .to_ascii_lowercase();
+    if hex.len() == 3 {
+        let expanded: String = hex.chars().flat_map(|c| [c, c]).collect();
+        return Ok(Some(format!("#{expanded}")));
+    }
+    Ok(Some(format!("#{hex}")))
+}
+
+pub(crate) fn resolve_workspace_color(
+    entry: &WorkspaceEntry,
+    parent_entry: Option<&WorkspaceEntry>,
+) -> Option<String> {
+    entry.settings.color.clone().or_else(|| {
+        parent_entry
+            .filter(|_| entry.kind.is_worktree())
+            .and_then(|parent| parent.settings.color.clone())
+    })
+}
+
 pub(crate) fn worktree_setup_marker_path(data_dir: &PathBuf, workspace_id: &str) -> PathBuf {
     data_dir
         .join(WORKTREE_SETUP_MARKERS_DIR)
@@ -66,14 +105,54 @@ pub(crate) fn is_workspace_path_dir_core(path: &str) -> bool {
     PathBuf::from(path).is_dir()
 }
 
+/// Whether each path is a directory, checked in parallel under
+/// `PATH_CHECK_TIMEOUT`. Paths whose check has not finished map to `true`.
+pub(super) async fn check_workspace_paths(paths: Vec<String>) -> HashMap<String, bool> {
+    let mut checks = JoinSet::new();
+    for path in paths.iter().cloned() {
+        checks.spawn_blocking(move || {
+            let exists = PathBuf::from(&path).is_dir();
+            (path, exists)
+        });
+    }
+    let deadline = Instant::now() + PATH_CHECK_TIMEOUT;
+    let mut result: HashMap<String, bool> = paths.into_iter().map(|path| (path, true)).collect();
+    while let Ok(Some(joined)) = tokio::time::timeout_at(deadline, checks.join_next()).await {
+        if let Ok((path, exists)) = joined {
+            result.insert(path, exists);
+        }
+    }
+    checks.detach_all();
+    result
+}
+
+pub(super) async fn workspace_path_exists(path: &str) -> bool {
+    check_workspace_paths(vec![path.to_string()])
+        .await
+        .get(path)
+        .copied()
+        .unwrap_or(true)
+}
+
 pub(crate) async fn list_workspaces_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
 ) -> Vec<WorkspaceInfo> {
+    let paths = workspaces
+        .lock()
+        .await
+        .values()
+        .map(|entry| entry.path.clone())
+        .collect();
+    let path_exists = check_workspace_paths(paths).await;
     let workspaces = workspaces.lock().await;
     let sessions = sessions.lock().await;
     let mut result = Vec::new();
     for entry in workspaces.values() {
+        let parent_entry = entry
+            .parent_id
+            .as_ref()
+            .and_then(|parent_id| workspaces.get(parent_id));
         result.push(WorkspaceInfo {
             id: entry.id.clone(),
             name: entry.name.clone(),
@@ -84,6 +163,8 @@ pub(crate) async fn list_workspaces_core(
             parent_id: entry.parent_id.clone(),
             worktree: entry.worktree.clone(),
             settings: entry.settings.clone(),
+            color: resolve_workspace_color(entry, parent_entry),
+            path_exists: path_exists.get(&entry.path).copied().unwrap_or(true),
         });
     }
     sort_workspaces(&mut result);
@@ -132,7 +213,10 @@ pub(super) fn sort_workspaces(workspaces: &mut [WorkspaceInfo]) {
 
 #[cfg(test)]
 mod tests {
-    use super::{copy_agents_md_from_parent_to_worktree, AGENTS_MD_FILE_NAME};
+    use super::{
+        check_workspace_paths, copy_agents_md_from_parent_to_worktree, normalize_workspace_color,
+        AGENTS_MD_FILE_NAME,
+    };
     use uuid::Uuid;
 
     fn make_temp_dir() -> std::path::PathBuf {
@@ -180,4 +264,37 @@ mod tests {
         let _ = std::fs::remove_dir_all(parent);
         let _ = std::fs::remove_dir_all(worktree);
     }
+
+    #[test]
+    fn normalize_workspace_color_expands_and_validates_hex() {
+        assert_eq!(
+            normalize_workspace_color(Some(" #A1B ".to_string())),
+            Ok(Some("#aa11bb".to_string()))
+        );
+        assert_eq!(
+            normalize_workspace_color(Some("#12ab34".to_string())),
+            Ok(Some("#12ab34".to_string()))
+        );
+        assert_eq!(normalize_workspace_color(Some("".to_string())), Ok(None));
+        assert!(normalize_workspace_color(Some("red".to_string())).is_err());
+        assert!(normalize_workspace_color(Some("#12345".to_string())).is_err());
+    }
+
+    #[test]
+    fn check_workspace_paths_flags_missing_folders() {
+        let existing = make_temp_dir();
+        let missing = existing.join("moved-away");
+        let existing_path = existing.to_string_lossy().to_string();
+        let missing_path = missing.to_string_lossy().to_string();
+
+        let runtime = tokio::runtime::Runtime::new().expect("create runtime");
+        let result = runtime.block_on(check_workspace_paths(vec![
+            existing_path.clone(),
+            missing_path.clone(),
+        ]));
+        assert_eq!(result.get(&existing_path), Some(&true));
+        assert_eq!(result.get(&missing_path), Some(&false));
+
+        let _ = std::fs::remove_dir_all(existing);
+    }
 }
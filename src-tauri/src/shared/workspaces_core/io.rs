@@ -7,11 +7,113 @@ use tokio::sync::Mutex;
 
 use crate::shared::process_core::tokio_command;
 #[cfg(target_os = "windows")]
-use crate::shared::process_core::{build_cmd_c_command, resolve_windows_executable};
+use crate::shared::process_core::{
+    build_cmd_c_command, build_cmd_start_command, resolve_windows_executable,
+};
 use crate::types::WorkspaceEntry;
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+use crate::utils::find_in_path;
 
 use super::helpers::resolve_workspace_root;
 
+/// Launcher names for apps whose binary is not just the app name.
+#[cfg(not(target_os = "macos"))]
+const KNOWN_APP_BINARIES: &[(&str, &str)] = &[
+    ("visual studio code", "code"),
+    ("visual studio code - insiders", "code-insiders"),
+];
+
+/// Binary names to try for an app given by its display name, e.g.
+/// `Visual Studio Code` -> `code`, `Sublime Text` -> `sublime-text`.
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn app_binary_candidates(app: &str) -> Vec<String> {
+    let lower = app.trim().to_ascii_lowercase();
+    let mut candidates = vec![app.trim().to_string()];
+    if let Some((_, binary)) = KNOWN_APP_BINARIES.iter().find(|(name, _)| *name == lower) {
+        candidates.push(binary.to_string());
+    }
+    candidates.push(lower.clone());
+    candidates.push(lower.split_whitespace().collect::<Vec<_>>().join("-"));
+    candidates.dedup();
+    candidates
+}
+
+/// `xdg-open` cannot pick a specific app, so it is only used when no binary
+/// for `app` is on `PATH`; the folder then opens in the default handler.
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn linux_app_command(app: &str, args: &[String], path: &str) -> tokio::process::Command {
+    let binary = if app.contains('/') {
+        Some(PathBuf::from(app)).filter(|candidate| candidate.is_file())
+    } else {
+        app_binary_candidates(app)
+            .iter()
+            .find_map(|candidate| find_in_path(candidate))
+    };
+    match binary {
+        Some(binary) => {
+            let mut cmd = tokio_command(binary);
+            cmd.args(args).arg(path);
+            cmd
+        }
+        None => {
+            let mut cmd = tokio_command("xdg-open");
+            cmd.arg(path);
+            cmd
+        }
+    }
+}
+
+/// Runs a resolvable executable directly; anything else goes through
+/// `cmd /c start`. Explorer is started that way too because it exits with
+/// 1 even after opening the folder.
+#[cfg(target_os = "windows")]
+fn windows_app_command(
+    app: &str,
+    args: &[String],
+    path: &str,
+) -> Result<tokio::process::Command, String> {
+    let resolved = app_binary_candidates(app)
+        .iter()
+        .find_map(|candidate| resolve_windows_executable(candidate, None));
+    let mut command_args = args.to_vec();
+    command_args.push(path.to_string());
+    let resolved = resolved.filter(|resolved| {
+        resolved
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .map(|stem| !stem.eq_ignore_ascii_case("explorer"))
+            .unwrap_or(true)
+    });
+    let Some(resolved) = resolved else {
+        let target = if app.eq_ignore_ascii_case("file explorer") {
+            "explorer"
+        } else {
+            app
+        };
+        let mut cmd = tokio_command("cmd");
+        cmd.arg("/D");
+        cmd.arg("/S");
+        cmd.arg("/C");
+        cmd.raw_arg(build_cmd_start_command(target, &command_args)?);
+        return Ok(cmd);
+    };
+    let ext = resolved
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase());
+    if matches!(ext.as_deref(), Some("cmd") | Some("bat")) {
+        let mut cmd = tokio_command("cmd");
+        cmd.arg("/D");
+        cmd.arg("/S");
+        cmd.arg("/C");
+        cmd.raw_arg(build_cmd_c_command(&resolved, &command_args)?);
+        return Ok(cmd);
+    }
+    let mut cmd = tokio_command(&resolved);
+    cmd.args(&command_args);
+    Ok(cmd)
+}
+
 pub(crate) async fn open_workspace_in_core(
     path: String,
     app: Option<String>,
@@ -97,12 +199,11 @@ pub(crate) async fn open_workspace_in_core(
             cmd
         };
 
-        #[cfg(not(target_os = "macos"))]
-        let mut cmd = {
-            let mut cmd = tokio_command(trimmed);
-            cmd.args(&args).arg(&path);
-            cmd
-        };
+        #[cfg(target_os = "windows")]
+        let mut cmd = windows_app_command(trimmed, &args, &path)?;
+
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        let mut cmd = linux_app_command(trimmed, &args, &path);
 
         cmd.output()
             .await
@@ -140,7 +241,8 @@ pub(crate) async fn open_workspace_in_core(
     }
 }
 
-#[cfg(target_os = "macos")]
+/// `icon_loader` is the platform lookup; it returns `None` when the app or
+/// its icon cannot be found.
 pub(crate) async fn get_open_app_icon_core<F>(
     app_name: String,
     icon_loader: F,
@@ -158,26 +260,13 @@ where
         .map_err(|err| err.to_string())
 }
 
-#[cfg(not(target_os = "macos"))]
-pub(crate) async fn get_open_app_icon_core<F>(
-    app_name: String,
-    icon_loader: F,
-) -> Result<Option<String>, String>
-where
-    F: Fn(&str) -> Option<String> + Send + Sync + 'static,
-{
-    let _ = app_name;
-    let _ = icon_loader;
-    Ok(None)
-}
-
-pub(crate) async fn list_workspace_files_core<F>(
+pub(crate) async fn list_workspace_files_core<F, T>(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: &str,
     list_files: F,
-) -> Result<Vec<String>, String>
+) -> Result<Vec<T>, String>
 where
-    F: Fn(&PathBuf) -> Vec<String>,
+    F: Fn(&PathBuf) -> Vec<T>,
 {
     let root = resolve_workspace_root(workspaces, workspace_id).await?;
     Ok(list_files(&root))
@@ -195,3 +284,22 @@ where
     let root = resolve_workspace_root(workspaces, workspace_id).await?;
     read_file(&root, path)
 }
+
+#[cfg(all(test, not(target_os = "macos")))]
+mod tests {
+    use super::app_binary_candidates;
+
+    #[test]
+    fn app_binary_candidates_map_display_names_to_launchers() {
+        assert_eq!(
+            app_binary_candidates("Visual Studio Code"),
+            vec![
+                "Visual Studio Code",
+                "code",
+                "visual studio code",
+                "visual-studio-code"
+            ]
+        );
+        assert_eq!(app_binary_candidates(" Zed "), vec!["Zed", "zed"]);
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+
+use crate::backend::app_server::WorkspaceSession;
+use crate::shared::concurrency_core::{shared_limiter, OperationPriority};
+use crate::types::{AppSettings, WorkspaceEntry};
+
+use super::connect::connect_workspace_core;
+
+const MAX_PREWARM_WORKSPACES: usize = 3;
+const PREWARM_IDLE_DELAY: Duration = Duration::from_secs(2);
+
+/// Bumped whenever the user connects a workspace; a running prewarm stops
+/// as soon as it notices the generation moved.
+static PREWARM_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+pub(crate) fn interrupt_prewarm() {
+    PREWARM_GENERATION.fetch_add(1, Ordering::SeqCst);
+}
+
+fn prewarm_targets(workspace_ids: Vec<String>) -> Vec<String> {
+    let mut targets: Vec<String> = Vec::new();
+    for id in workspace_ids {
+        let id = id.trim().to_string();
+        if id.is_empty() || targets.contains(&id) {
+            continue;
+        }
+        targets.push(id);
+        if targets.len() == MAX_PREWARM_WORKSPACES {
+            break;
+        }
+    }
+    targets
+}
+
+/// Connects up to a few workspaces in the background, one at a time and at
+/// background priority. Returns the ids that were actually warmed.
+pub(crate) async fn prewarm_sessions_core<F, Fut>(
+    workspace_ids: Vec<String>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    app_settings: &Mutex<AppSettings>,
+    spawn_session: F,
+) -> Vec<String>
+where
+    F: Fn(WorkspaceEntry, Option<String>, Option<String>, Option<PathBuf>) -> Fut,
+    Fut: Future<Output = Result<Arc<WorkspaceSession>, String>>,
+{
+    let generation = PREWARM_GENERATION.load(Ordering::SeqCst);
+    let interrupted = || PREWARM_GENERATION.load(Ordering::SeqCst) != generation;
+    let targets = prewarm_targets(workspace_ids);
+    let mut warmed = Vec::new();
+    if targets.is_empty() {
+        return warmed;
+    }
+
+    tokio::time::sleep(PREWARM_IDLE_DELAY).await;
+    for id in targets {
+        if interrupted() {
+            break;
+        }
+        if sessions.lock().await.contains_key(&id) {
+            continue;
+        }
+        let _permit = shared_limiter()
+            .acquire(OperationPriority::Background)
+            .await;
+        if interrupted() {
+            break;
+        }
+        match connect_workspace_core(
+            id.clone(),
+            workspaces,
+            sessions,
+            app_settings,
+            &spawn_session,
+        )
+        .await
+        {
+            Ok(()) => warmed.push(id),
+            Err(error) => eprintln!("Failed to prewarm workspace {id}: {error}"),
+        }
+    }
+    warmed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prewarm_targets_dedupes_and_caps() {
+        let ids = ["a", " a ", "", "b", "c", "d"]
+            .iter()
+            .map(|id| id.to_string())
+            .collect();
+        assert_eq!(prewarm_targets(ids), vec!["a", "b", "c"]);
+    }
+}
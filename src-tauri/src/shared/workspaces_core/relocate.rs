@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+
+use crate::backend::app_server::WorkspaceSession;
+use crate::codex::args::resolve_workspace_codex_args;
+use crate::codex::endpoint::with_inherited_session_settings;
+use crate::codex::home::resolve_workspace_codex_home;
+use crate::git_utils::resolve_git_root;
+use crate::shared::git_core;
+use crate::shared::process_core::kill_child_process_tree;
+use crate::shared::workspace_bundle_core::rebase_path;
+use crate::storage::write_workspaces;
+use crate::types::{AppSettings, WorkspaceEntry, WorkspaceInfo, WorkspaceRelocation};
+
+use super::helpers::{check_workspace_paths, resolve_workspace_color};
+
+/// A main workspace has to land on the root of a repository, not a folder
+/// inside one; a worktree has to land on a checkout.
+async fn validate_relocated_repo(entry: &WorkspaceEntry) -> Result<(), String> {
+    let git_root = resolve_git_root(entry)?;
+    if entry.kind.is_worktree() {
+        if !git_root.join(".git").is_file() {
+            return Err(format!("{} is not a git worktree.", git_root.display()));
+        }
+        return Ok(());
+    }
+    let toplevel = git_core::run_git_command(&git_root, &["rev-parse", "--show-toplevel"])
+        .await
+        .map_err(|_| format!("{} is not a git repository.", git_root.display()))?;
+    let same_root = match (Path::new(&toplevel).canonicalize(), git_root.canonicalize()) {
+        (Ok(toplevel), Ok(git_root)) => toplevel == git_root,
+        _ => Path::new(&toplevel) == git_root,
+    };
+    if !same_root {
+        return Err(format!(
+            "{} is inside the repository at {toplevel}; pick the repository root.",
+            git_root.display()
+        ));
+    }
+    Ok(())
+}
+
+/// Points the workspace at the folder it was moved to, keeping its id,
+/// settings, group and worktrees. An absolute `gitRoot` inside the old folder
+/// moves along. Git's worktree links are repaired best effort, and anything
+/// left pointing at a missing folder comes back in `warnings`.
+pub(crate) async fn relocate_workspace_core<F, Fut>(
+    id: String,
+    new_path: String,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    app_settings: &Mutex<AppSettings>,
+    storage_path: &PathBuf,
+    spawn_session: F,
+) -> Result<WorkspaceRelocation, String>
+where
+    F: Fn(WorkspaceEntry, Option<String>, Option<String>, Option<PathBuf>) -> Fut,
+    Fut: Future<Output = Result<Arc<WorkspaceSession>, String>>,
+{
+    let new_path = new_path.trim().to_string();
+    if !PathBuf::from(&new_path).is_dir() {
+        return Err("Workspace path must be a folder.".to_string());
+    }
+    let (previous, parent_entry, children) = {
+        let workspaces = workspaces.lock().await;
+        let previous = workspaces
+            .get(&id)
+            .cloned()
+            .ok_or_else(|| "workspace not found".to_string())?;
+        if Path::new(&previous.path) == Path::new(&new_path) {
+            return Err("The workspace already points at this folder.".to_string());
+        }
+        if workspaces
+            .values()
+            .any(|entry| entry.id != id && Path::new(&entry.path) == Path::new(&new_path))
+        {
+            return Err("A workspace for this folder already exists.".to_string());
+        }
+        let parent_entry = previous
+            .parent_id
+            .as_ref()
+            .and_then(|parent_id| workspaces.get(parent_id))
+            .cloned();
+        let children: Vec<WorkspaceEntry> = workspaces
+            .values()
+            .filter(|entry| entry.parent_id.as_deref() == Some(id.as_str()))
+            .cloned()
+            .collect();
+        (previous, parent_entry, children)
+    };
+
+    let mut relocated = previous.clone();
+    relocated.path = new_path.clone();
+    if let Some(git_root) = relocated.settings.git_root.clone() {
+        if Path::new(&git_root).is_absolute() {
+            if let Some(rebased) =
+                rebase_path(&git_root, Path::new(&previous.path), Path::new(&new_path))
+            {
+                relocated.settings.git_root = Some(rebased);
+            }
+        }
+    }
+    validate_relocated_repo(&relocated).await?;
+
+    let mut warnings = Vec::new();
+    let git_root = resolve_git_root(&relocated)?;
+    if relocated.kind.is_worktree() {
+        if let Err(error) = git_core::run_git_command(&git_root, &["worktree", "repair"]).await {
+            warnings.push(format!("`git worktree repair` failed: {error}"));
+        }
+    } else if !children.is_empty() {
+        let child_paths: Vec<String> = children.iter().map(|child| child.path.clone()).collect();
+        let exists = check_workspace_paths(child_paths.clone()).await;
+        let existing: Vec<&str> = child_paths
+            .iter()
+            .filter(|path| exists.get(*path).copied().unwrap_or(true))
+            .map(String::as_str)
+            .collect();
+        if !existing.is_empty() {
+            let mut args = vec!["worktree", "repair"];
+            args.extend(existing);
+            if let Err(error) = git_core::run_git_command(&git_root, &args).await {
+                warnings.push(format!("`git worktree repair` failed: {error}"));
+            }
+        }
+        for child in &children {
+            if !exists.get(&child.path).copied().unwrap_or(true) {
+                warnings.push(format!(
+                    "Worktree {} is still recorded at {}, which no longer exists. Relocate it too.",
+                    child.name, child.path
+                ));
+            }
+        }
+    }
+
+    let list: Vec<_> = {
+        let mut workspaces = workspaces.lock().await;
+        workspaces.insert(relocated.id.clone(), relocated.clone());
+        workspaces.values().cloned().collect()
+    };
+    write_workspaces(storage_path, &list)?;
+
+    let old_session = sessions.lock().await.remove(&id);
+    if let Some(old_session) = old_session {
+        {
+            let mut child = old_session.child.lock().await;
+            kill_child_process_tree(&mut child).await;
+        }
+        let (default_bin, codex_args) = {
+            let settings = app_settings.lock().await;
+            (
+                settings.codex_bin.clone(),
+                resolve_workspace_codex_args(&relocated, parent_entry.as_ref(), Some(&settings)),
+            )
+        };
+        let codex_home = resolve_workspace_codex_home(&relocated, parent_entry.as_ref());
+        match spawn_session(
+            with_inherited_session_settings(&relocated, parent_entry.as_ref()),
+            default_bin,
+            codex_args,
+            codex_home,
+        )
+        .await
+        {
+            Ok(session) => {
+                sessions.lock().await.insert(relocated.id.clone(), session);
+            }
+            Err(error) => warnings.push(format!("Reconnecting the workspace failed: {error}")),
+        }
+    }
+
+    let connected = sessions.lock().await.contains_key(&id);
+    let color = resolve_workspace_color(&relocated, parent_entry.as_ref());
+    Ok(WorkspaceRelocation {
+        workspace: WorkspaceInfo {
+            id: relocated.id,
+            name: relocated.name,
+            path: relocated.path,
+            codex_bin: relocated.codex_bin,
+            connected,
+            kind: relocated.kind,
+            parent_id: relocated.parent_id,
+            worktree: relocated.worktree,
+            settings: relocated.settings,
+            color,
+            path_exists: true,
+        },
+        warnings,
+    })
+}
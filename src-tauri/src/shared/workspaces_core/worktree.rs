@@ -8,6 +8,7 @@ use uuid::Uuid;
 
 use crate::backend::app_server::WorkspaceSession;
 use crate::codex::args::resolve_workspace_codex_args;
+use crate::codex::endpoint::with_inherited_session_settings;
 use crate::codex::home::resolve_workspace_codex_home;
 use crate::storage::write_workspaces;
 use crate::types::{
@@ -17,8 +18,8 @@ use crate::types::{
 
 use super::connect::kill_session_by_id;
 use super::helpers::{
-    copy_agents_md_from_parent_to_worktree, normalize_setup_script, worktree_setup_marker_path,
-    AGENTS_MD_FILE_NAME,
+    copy_agents_md_from_parent_to_worktree, normalize_setup_script, resolve_workspace_color,
+    worktree_setup_marker_path, AGENTS_MD_FILE_NAME,
 };
 
 pub(crate) async fn worktree_setup_status_core(
@@ -74,6 +75,47 @@ pub(crate) async fn worktree_setup_mark_ran_core(
     Ok(())
 }
 
+/// The configured remote a ref like `origin/main` points into, if any.
+fn remote_for_ref(repo_path: &PathBuf, base: &str) -> Option<String> {
+    let remote = base
+        .strip_prefix("refs/remotes/")
+        .unwrap_or(base)
+        .split('/')
+        .next()?;
+    let repo = git2::Repository::open(repo_path).ok()?;
+    repo.find_remote(remote).ok()?;
+    Some(remote.to_string())
+}
+
+async fn verify_base_ref<FRunGit, FutRunGit>(
+    repo_path: &PathBuf,
+    base: &str,
+    run_git_command: &FRunGit,
+) -> Result<(), String>
+where
+    FRunGit: Fn(&PathBuf, &[&str]) -> FutRunGit,
+    FutRunGit: Future<Output = Result<(), String>>,
+{
+    if base.starts_with('-') {
+        return Err(format!("Invalid base ref `{base}`."));
+    }
+    let commit = format!("{base}^{{commit}}");
+    if run_git_command(repo_path, &["rev-parse", "--verify", "--quiet", &commit])
+        .await
+        .is_ok()
+    {
+        return Ok(());
+    }
+    match remote_for_ref(repo_path, base) {
+        Some(remote) => Err(format!(
+            "Base ref `{base}` was not found. Fetch `{remote}` and try again."
+        )),
+        None => Err(format!("Base ref `{base}` was not found.")),
+    }
+}
+
+/// Without a `base`, new branches start from the parent's HEAD (or the
+/// matching remote-tracking branch when a lookup is provided).
 pub(crate) async fn add_worktree_core<
     FSpawn,
     FutSpawn,
@@ -90,6 +132,7 @@ pub(crate) async fn add_worktree_core<
     branch: String,
     name: Option<String>,
     copy_agents_md: bool,
+    base: Option<String>,
     data_dir: &PathBuf,
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
@@ -121,6 +164,9 @@ where
     let name = name
         .map(|value| value.trim().to_string())
         .filter(|value| !value.is_empty());
+    let base = base
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
 
     let parent_entry = {
         let workspaces = workspaces.lock().await;
@@ -144,7 +190,26 @@ where
 
     let repo_path = PathBuf::from(&parent_entry.path);
     let branch_exists = git_branch_exists(&repo_path, &branch).await?;
-    if branch_exists {
+    if let Some(base) = base.as_deref() {
+        if branch_exists {
+            return Err(format!(
+                "Branch `{branch}` already exists; a base ref only applies to new branches."
+            ));
+        }
+        verify_base_ref(&repo_path, base, &run_git_command).await?;
+        run_git_command(
+            &repo_path,
+            &[
+                "worktree",
+                "add",
+                "-b",
+                &branch,
+                &worktree_path_string,
+                base,
+            ],
+        )
+        .await?;
+    } else if branch_exists {
         run_git_command(
             &repo_path,
             &["worktree", "add", &worktree_path_string, &branch],
@@ -214,7 +279,13 @@ where
         )
     };
     let codex_home = resolve_workspace_codex_home(&entry, Some(&parent_entry));
-    let session = spawn_session(entry.clone(), default_bin, codex_args, codex_home).await?;
+    let session = spawn_session(
+        with_inherited_session_settings(&entry, Some(&parent_entry)),
+        default_bin,
+        codex_args,
+        codex_home,
+    )
+    .await?;
 
     {
         let mut workspaces = workspaces.lock().await;
@@ -225,6 +296,7 @@ where
 
     sessions.lock().await.insert(entry.id.clone(), session);
 
+    let color = resolve_workspace_color(&entry, Some(&parent_entry));
     Ok(WorkspaceInfo {
         id: entry.id,
         name: entry.name,
@@ -235,6 +307,8 @@ where
         parent_id: entry.parent_id,
         worktree: entry.worktree,
         settings: entry.settings,
+        color,
+        path_exists: true,
     })
 }
 
@@ -446,7 +520,14 @@ where
             )
         };
         let codex_home = resolve_workspace_codex_home(&entry_snapshot, Some(&parent));
-        match spawn_session(entry_snapshot.clone(), default_bin, codex_args, codex_home).await {
+        match spawn_session(
+            with_inherited_session_settings(&entry_snapshot, Some(&parent)),
+            default_bin,
+            codex_args,
+            codex_home,
+        )
+        .await
+        {
             Ok(session) => {
                 sessions
                     .lock()
@@ -463,6 +544,7 @@ where
     }
 
     let connected = sessions.lock().await.contains_key(&entry_snapshot.id);
+    let color = resolve_workspace_color(&entry_snapshot, Some(&parent));
     Ok(WorkspaceInfo {
         id: entry_snapshot.id,
         name: entry_snapshot.name,
@@ -473,6 +555,8 @@ where
         parent_id: entry_snapshot.parent_id,
         worktree: entry_snapshot.worktree,
         settings: entry_snapshot.settings,
+        color,
+        path_exists: true,
     })
 }
 
@@ -91,6 +91,39 @@ pub(crate) fn build_clone_destination_path(copies_folder: &PathBuf, copy_name: &
     unique_worktree_path_best_effort(copies_folder, &safe_name)
 }
 
+/// The share of the overall clone each `git clone --progress` stage covers,
+/// as `(stage, start, end)` percentages. A pull's fetch reports the same
+/// stages.
+pub(crate) const CLONE_PROGRESS_STAGES: &[(&str, u32, u32)] = &[
+    ("Receiving objects", 0, 80),
+    ("Resolving deltas", 80, 90),
+    ("Updating files", 90, 100),
+];
+
+/// Maps a progress line such as `Receiving objects:  42% (420/1000)` to its
+/// stage and the overall 0-100 percentage.
+pub(crate) fn parse_clone_progress(line: &str) -> Option<(&'static str, u8)> {
+    parse_git_progress(line, CLONE_PROGRESS_STAGES)
+}
+
+/// Same as [`parse_clone_progress`] for any command's `(stage, start, end)`
+/// weights.
+pub(crate) fn parse_git_progress(
+    line: &str,
+    stages: &[(&'static str, u32, u32)],
+) -> Option<(&'static str, u8)> {
+    let line = line.trim();
+    for (stage, start, end) in stages {
+        let Some(rest) = line.strip_prefix(stage) else {
+            continue;
+        };
+        let (percent, _) = rest.strip_prefix(':')?.split_once('%')?;
+        let percent = percent.trim().parse::<u32>().ok()?.min(100);
+        return Some((stage, (start + (end - start) * percent / 100) as u8));
+    }
+    None
+}
+
 pub(crate) fn null_device_path() -> &'static str {
     if cfg!(windows) {
         "NUL"
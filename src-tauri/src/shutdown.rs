@@ -0,0 +1,141 @@
+use std::io::Write;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde_json::json;
+use tauri::{AppHandle, State};
+use tokio::io::AsyncWriteExt;
+use tokio::time::{timeout_at, Instant};
+
+use crate::backend::app_server::WorkspaceSession;
+use crate::event_sink::TauriEventSink;
+use crate::remote_backend;
+use crate::shared::process_core::kill_child_process_tree;
+use crate::state::AppState;
+use crate::terminal;
+use crate::types::{RunningTurn, ShutdownReadiness};
+
+const DEFAULT_SHUTDOWN_TIMEOUT_MS: u64 = 10_000;
+const MAX_SHUTDOWN_TIMEOUT_MS: u64 = 60_000;
+const TURN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+async fn connected_sessions(state: &AppState) -> Vec<Arc<WorkspaceSession>> {
+    state.sessions.lock().await.values().cloned().collect()
+}
+
+async fn running_turns(state: &AppState) -> Vec<RunningTurn> {
+    let mut turns = Vec::new();
+    for session in connected_sessions(state).await {
+        let running = session.turn_traces.lock().await.running_turns();
+        turns.extend(
+            running
+                .into_iter()
+                .map(|(thread_id, turn_id, started_at_ms)| RunningTurn {
+                    workspace_id: session.entry.id.clone(),
+                    thread_id,
+                    turn_id,
+                    started_at_ms,
+                }),
+        );
+    }
+    turns.sort_by_key(|turn| turn.started_at_ms);
+    turns
+}
+
+/// Sessions left behind by quitting in remote mode keep running in the
+/// daemon, so their turns don't count.
+async fn local_running_turns(state: &AppState) -> Vec<RunningTurn> {
+    if remote_backend::is_remote_mode(state).await {
+        return Vec::new();
+    }
+    running_turns(state).await
+}
+
+/// Lists turns, operations and terminals that quitting now would cut short,
+/// so the UI can ask before closing.
+#[tauri::command]
+pub(crate) async fn prepare_shutdown(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ShutdownReadiness, String> {
+    let running_turns = local_running_turns(&state).await;
+    let operations = state.operations.list();
+    let terminals = terminal::terminal_inventory(&state).await;
+    let buffered_events = TauriEventSink::new(app).pending_events();
+    let safe_to_quit = running_turns.is_empty() && operations.is_empty() && terminals.is_empty();
+    Ok(ShutdownReadiness {
+        running_turns,
+        operations,
+        terminals,
+        buffered_events,
+        safe_to_quit,
+    })
+}
+
+/// Interrupts running turns and cancels operations, waiting up to
+/// `timeoutMs` (10 s by default) for them to wind down. Then it delivers
+/// held-back events, flushes the logs, closes each session's stdin so
+/// `codex app-server` can exit on its own, kills whatever is still running
+/// at the deadline along with the terminals, and exits. Settings and
+/// workspaces are written as they change, so there's nothing else to save.
+#[tauri::command]
+pub(crate) async fn graceful_shutdown(
+    timeout_ms: Option<u64>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    let timeout_ms = timeout_ms
+        .unwrap_or(DEFAULT_SHUTDOWN_TIMEOUT_MS)
+        .min(MAX_SHUTDOWN_TIMEOUT_MS);
+    let deadline = Instant::now() + Duration::from_millis(timeout_ms);
+    let remote = remote_backend::is_remote_mode(&*state).await;
+
+    if !remote {
+        for turn in running_turns(&state).await {
+            let Some(session) = state.sessions.lock().await.get(&turn.workspace_id).cloned() else {
+                continue;
+            };
+            // Sent without waiting; the loop below watches for completion.
+            tauri::async_runtime::spawn(async move {
+                let params = json!({ "threadId": turn.thread_id, "turnId": turn.turn_id });
+                let _ = timeout_at(deadline, session.send_request("turn/interrupt", params)).await;
+            });
+        }
+    }
+    for operation in state.operations.list() {
+        state.operations.cancel(&operation.id);
+    }
+    while Instant::now() < deadline {
+        if local_running_turns(&state).await.is_empty() && state.operations.list().is_empty() {
+            break;
+        }
+        tokio::time::sleep(TURN_POLL_INTERVAL).await;
+    }
+
+    TauriEventSink::new(app.clone()).flush();
+    let _ = std::io::stdout().flush();
+    let _ = std::io::stderr().flush();
+
+    if !remote {
+        let sessions: Vec<Arc<WorkspaceSession>> = state
+            .sessions
+            .lock()
+            .await
+            .drain()
+            .map(|(_, session)| session)
+            .collect();
+        for session in &sessions {
+            let _ = session.stdin.lock().await.shutdown().await;
+        }
+        for session in sessions {
+            let mut child = session.child.lock().await;
+            if !matches!(timeout_at(deadline, child.wait()).await, Ok(Ok(_))) {
+                kill_child_process_tree(&mut child).await;
+            }
+        }
+    }
+    terminal::close_all_terminals(&state).await;
+
+    app.exit(0);
+    Ok(())
+}
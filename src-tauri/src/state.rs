@@ -6,7 +6,10 @@ use tokio::process::Child;
 use tokio::sync::Mutex;
 
 use crate::dictation::DictationState;
+use crate::event_sink::TauriEventSink;
 use crate::shared::codex_core::CodexLoginCancelState;
+use crate::shared::operations_core::OperationRegistry;
+use crate::shared::{credentials_core, event_batch_core, http_client_core, redaction_core};
 use crate::storage::{read_settings, read_workspaces};
 use crate::types::{
     AppSettings, OrbitRunnerState, OrbitRunnerStatus, TcpDaemonState, TcpDaemonStatus,
@@ -58,7 +61,7 @@ impl Default for TcpDaemonRuntime {
 pub(crate) struct AppState {
     pub(crate) workspaces: Mutex<HashMap<String, WorkspaceEntry>>,
     pub(crate) sessions: Mutex<HashMap<String, Arc<crate::codex::WorkspaceSession>>>,
-    pub(crate) terminal_sessions: Mutex<HashMap<String, Arc<crate::terminal::TerminalSession>>>,
+    pub(crate) terminal_sessions: Mutex<crate::terminal::TerminalSessions>,
     pub(crate) remote_backend: Mutex<Option<crate::remote_backend::RemoteBackend>>,
     pub(crate) storage_path: PathBuf,
     pub(crate) settings_path: PathBuf,
@@ -67,6 +70,7 @@ pub(crate) struct AppState {
     pub(crate) codex_login_cancels: Mutex<HashMap<String, CodexLoginCancelState>>,
     pub(crate) orbit_runner: Mutex<OrbitRunnerRuntime>,
     pub(crate) tcp_daemon: Mutex<TcpDaemonRuntime>,
+    pub(crate) operations: OperationRegistry<TauriEventSink>,
 }
 
 impl AppState {
@@ -78,7 +82,11 @@ impl AppState {
         let storage_path = data_dir.join("workspaces.json");
         let settings_path = data_dir.join("settings.json");
         let workspaces = read_workspaces(&storage_path).unwrap_or_default();
-        let app_settings = read_settings(&settings_path).unwrap_or_default();
+        let mut app_settings = read_settings(&settings_path).unwrap_or_default();
+        credentials_core::load_settings_secrets(&settings_path, &mut app_settings);
+        http_client_core::apply_http_settings(&app_settings);
+        redaction_core::apply_redaction_settings(&app_settings);
+        event_batch_core::apply_event_batch_settings(&app_settings);
         Self {
             workspaces: Mutex::new(workspaces),
             sessions: Mutex::new(HashMap::new()),
@@ -91,6 +99,7 @@ impl AppState {
             codex_login_cancels: Mutex::new(HashMap::new()),
             orbit_runner: Mutex::new(OrbitRunnerRuntime::default()),
             tcp_daemon: Mutex::new(TcpDaemonRuntime::default()),
+            operations: OperationRegistry::new(TauriEventSink::new(app.clone())),
         }
     }
 }
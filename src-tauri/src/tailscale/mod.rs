@@ -5,7 +5,7 @@ mod rpc_client;
 use std::ffi::{OsStr, OsString};
 use std::io::ErrorKind;
 use std::process::Output;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::Duration;
 
 use serde_json::{json, Value};
 use tauri::State;
@@ -20,6 +20,7 @@ use crate::state::{AppState, TcpDaemonRuntime};
 use crate::types::{
     TailscaleDaemonCommandPreview, TailscaleStatus, TcpDaemonState, TcpDaemonStatus,
 };
+use crate::utils::now_unix_ms;
 
 use self::core as tailscale_core;
 
@@ -129,13 +130,6 @@ async fn resolve_tailscale_binary() -> Result<Option<(OsString, Output)>, String
     }
 }
 
-fn now_unix_ms() -> i64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|duration| duration.as_millis() as i64)
-        .unwrap_or(0)
-}
-
 fn parse_port_from_remote_host(remote_host: &str) -> Option<u16> {
     if remote_host.trim().is_empty() {
         return None;
@@ -1,21 +1,27 @@
+use std::collections::HashMap;
 use std::io::{Read, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use portable_pty::{native_pty_system, CommandBuilder, PtySize};
 use serde::Serialize;
 use tauri::{AppHandle, Manager, State};
 use tokio::sync::Mutex;
+use uuid::Uuid;
 
 use crate::backend::events::{EventSink, TerminalExit, TerminalOutput};
 use crate::event_sink::TauriEventSink;
+use crate::shared::inventory_core;
 use crate::state::AppState;
+use crate::types::{InventoryTerminal, TerminalSignal};
+use crate::utils::now_unix_ms;
 
 pub(crate) struct TerminalSession {
     pub(crate) id: String,
     pub(crate) master: Mutex<Box<dyn portable_pty::MasterPty + Send>>,
     pub(crate) writer: Mutex<Box<dyn Write + Send>>,
     pub(crate) child: Mutex<Box<dyn portable_pty::Child + Send>>,
+    pub(crate) started_at_ms: i64,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -23,8 +29,33 @@ pub(crate) struct TerminalSessionInfo {
     id: String,
 }
 
-fn terminal_key(workspace_id: &str, terminal_id: &str) -> String {
-    format!("{workspace_id}:{terminal_id}")
+/// Open terminals by workspace id, then terminal id.
+pub(crate) type TerminalSessions = HashMap<String, HashMap<String, Arc<TerminalSession>>>;
+
+/// Drops the workspace's entry once its last terminal is gone.
+fn remove_terminal_session(
+    sessions: &mut TerminalSessions,
+    workspace_id: &str,
+    terminal_id: &str,
+) -> Option<Arc<TerminalSession>> {
+    let workspace_sessions = sessions.get_mut(workspace_id)?;
+    let session = workspace_sessions.remove(terminal_id);
+    if workspace_sessions.is_empty() {
+        sessions.remove(workspace_id);
+    }
+    session
+}
+
+async fn kill_terminal_sessions(sessions: Vec<Arc<TerminalSession>>) {
+    if sessions.is_empty() {
+        return;
+    }
+    let _ = tokio::task::spawn_blocking(move || {
+        for session in sessions {
+            let _ = session.child.blocking_lock().kill();
+        }
+    })
+    .await;
 }
 
 fn is_terminal_closed_error(message: &str) -> bool {
@@ -40,11 +71,13 @@ fn is_terminal_closed_error(message: &str) -> bool {
 
 async fn get_terminal_session(
     state: &State<'_, AppState>,
-    key: &str,
+    workspace_id: &str,
+    terminal_id: &str,
 ) -> Result<Arc<TerminalSession>, String> {
     let sessions = state.terminal_sessions.lock().await;
     sessions
-        .get(key)
+        .get(workspace_id)
+        .and_then(|workspace_sessions| workspace_sessions.get(terminal_id))
         .cloned()
         .ok_or_else(|| "Terminal session not found".to_string())
 }
@@ -76,19 +109,56 @@ fn unix_shell_args() -> Vec<&'static str> {
 }
 
 #[cfg(target_os = "windows")]
-fn configure_shell_args(cmd: &mut CommandBuilder) {
-    for arg in windows_shell_args(&shell_path()) {
+fn configure_shell_args(cmd: &mut CommandBuilder, shell: &str) {
+    for arg in windows_shell_args(shell) {
         cmd.arg(arg);
     }
 }
 
 #[cfg(not(target_os = "windows"))]
-fn configure_shell_args(cmd: &mut CommandBuilder) {
+fn configure_shell_args(cmd: &mut CommandBuilder, _shell: &str) {
     for arg in unix_shell_args() {
         cmd.arg(arg);
     }
 }
 
+/// Resolves `cwd` against the workspace root. It has to be an existing
+/// directory inside the workspace once symlinks are followed; none means
+/// the root itself.
+fn resolve_terminal_cwd(workspace_root: &Path, cwd: Option<&str>) -> Result<PathBuf, String> {
+    let root = workspace_root
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve workspace path: {e}"))?;
+    let Some(cwd) = cwd.map(str::trim).filter(|cwd| !cwd.is_empty()) else {
+        return Ok(root);
+    };
+    let resolved = root
+        .join(cwd)
+        .canonicalize()
+        .map_err(|_| format!("Terminal directory not found: {cwd}"))?;
+    if !resolved.starts_with(&root) {
+        return Err(format!(
+            "Terminal directory is outside the workspace: {cwd}"
+        ));
+    }
+    if !resolved.is_dir() {
+        return Err(format!("Terminal directory is not a folder: {cwd}"));
+    }
+    Ok(resolved)
+}
+
+fn validate_terminal_env(env: &[(String, String)]) -> Result<(), String> {
+    for (key, value) in env {
+        if key.is_empty() || key.contains('=') || key.contains('\0') {
+            return Err(format!("Invalid environment variable name: {key:?}"));
+        }
+        if value.contains('\0') {
+            return Err(format!("Invalid value for environment variable {key}"));
+        }
+    }
+    Ok(())
+}
+
 fn resolve_locale() -> String {
     let candidate = std::env::var("LC_ALL")
         .or_else(|_| std::env::var("LANG"))
@@ -173,12 +243,12 @@ fn spawn_terminal_reader(
         tauri::async_runtime::spawn(async move {
             let state = app.state::<AppState>();
             let mut sessions = state.terminal_sessions.lock().await;
-            let key = terminal_key(&cleanup_workspace_id, &cleanup_terminal_id);
             let should_remove = sessions
-                .get(&key)
+                .get(&cleanup_workspace_id)
+                .and_then(|workspace_sessions| workspace_sessions.get(&cleanup_terminal_id))
                 .is_some_and(|current| Arc::ptr_eq(current, &cleanup_session));
             if should_remove {
-                sessions.remove(&key);
+                remove_terminal_session(&mut sessions, &cleanup_workspace_id, &cleanup_terminal_id);
             }
         });
     });
@@ -195,29 +265,47 @@ async fn get_workspace_path(
     Ok(PathBuf::from(&entry.path))
 }
 
+/// Opens a shell in the workspace. Without a `terminal_id` a new one is
+/// generated, so each call gets its own PTY; reopening an id that is still
+/// running returns the existing session. `shell` defaults to `$SHELL` (or
+/// `%COMSPEC%`), `cwd` is relative to the workspace root, and `env` is
+/// applied on top of the terminal defaults.
 #[tauri::command]
 pub(crate) async fn terminal_open(
     workspace_id: String,
-    terminal_id: String,
+    terminal_id: Option<String>,
     cols: u16,
     rows: u16,
+    shell: Option<String>,
+    cwd: Option<String>,
+    env: Option<Vec<(String, String)>>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<TerminalSessionInfo, String> {
-    if terminal_id.is_empty() {
-        return Err("Terminal id is required".to_string());
-    }
-    let key = terminal_key(&workspace_id, &terminal_id);
+    let terminal_id = terminal_id
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
     {
         let sessions = state.terminal_sessions.lock().await;
-        if let Some(existing) = sessions.get(&key) {
+        if let Some(existing) = sessions
+            .get(&workspace_id)
+            .and_then(|workspace_sessions| workspace_sessions.get(&terminal_id))
+        {
             return Ok(TerminalSessionInfo {
                 id: existing.id.clone(),
             });
         }
     }
 
-    let cwd = get_workspace_path(&workspace_id, &state).await?;
+    let workspace_path = get_workspace_path(&workspace_id, &state).await?;
+    let cwd = resolve_terminal_cwd(&workspace_path, cwd.as_deref())?;
+    let env = env.unwrap_or_default();
+    validate_terminal_env(&env)?;
+    let shell = shell
+        .map(|shell| shell.trim().to_string())
+        .filter(|shell| !shell.is_empty())
+        .unwrap_or_else(shell_path);
     let pty_system = native_pty_system();
     let size = PtySize {
         rows: rows.max(2),
@@ -229,14 +317,17 @@ pub(crate) async fn terminal_open(
         .openpty(size)
         .map_err(|e| format!("Failed to open pty: {e}"))?;
 
-    let mut cmd = CommandBuilder::new(shell_path());
+    let mut cmd = CommandBuilder::new(&shell);
     cmd.cwd(cwd);
-    configure_shell_args(&mut cmd);
+    configure_shell_args(&mut cmd, &shell);
     cmd.env("TERM", "xterm-256color");
     let locale = resolve_locale();
     cmd.env("LANG", &locale);
     cmd.env("LC_ALL", &locale);
     cmd.env("LC_CTYPE", &locale);
+    for (key, value) in &env {
+        cmd.env(key, value);
+    }
 
     let child = pair
         .slave
@@ -256,12 +347,14 @@ pub(crate) async fn terminal_open(
         master: Mutex::new(pair.master),
         writer: Mutex::new(writer),
         child: Mutex::new(child),
+        started_at_ms: now_unix_ms(),
     });
     let session_id = session.id.clone();
 
     {
         let mut sessions = state.terminal_sessions.lock().await;
-        if let Some(existing) = sessions.get(&key) {
+        let workspace_sessions = sessions.entry(workspace_id.clone()).or_default();
+        if let Some(existing) = workspace_sessions.get(&terminal_id) {
             let id = existing.id.clone();
             drop(sessions);
             let _ = tokio::task::spawn_blocking(move || {
@@ -271,7 +364,7 @@ pub(crate) async fn terminal_open(
             .await;
             return Ok(TerminalSessionInfo { id });
         }
-        sessions.insert(key, Arc::clone(&session));
+        workspace_sessions.insert(terminal_id.clone(), Arc::clone(&session));
     }
     let event_sink = TauriEventSink::new(app.clone());
     spawn_terminal_reader(
@@ -293,8 +386,7 @@ pub(crate) async fn terminal_write(
     data: String,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
-    let key = terminal_key(&workspace_id, &terminal_id);
-    let session = get_terminal_session(&state, &key).await?;
+    let session = get_terminal_session(&state, &workspace_id, &terminal_id).await?;
     let write_result = tokio::task::spawn_blocking(move || {
         let mut writer = session.writer.blocking_lock();
         writer
@@ -311,7 +403,7 @@ pub(crate) async fn terminal_write(
     if let Err(err) = write_result {
         if is_terminal_closed_error(&err) {
             let mut sessions = state.terminal_sessions.lock().await;
-            sessions.remove(&key);
+            remove_terminal_session(&mut sessions, &workspace_id, &terminal_id);
         }
         return Err(err);
     }
@@ -326,8 +418,7 @@ pub(crate) async fn terminal_resize(
     rows: u16,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
-    let key = terminal_key(&workspace_id, &terminal_id);
-    let session = get_terminal_session(&state, &key).await?;
+    let session = get_terminal_session(&state, &workspace_id, &terminal_id).await?;
     let size = PtySize {
         rows: rows.max(2),
         cols: cols.max(2),
@@ -345,36 +436,163 @@ pub(crate) async fn terminal_resize(
     if let Err(err) = resize_result {
         if is_terminal_closed_error(&err) {
             let mut sessions = state.terminal_sessions.lock().await;
-            sessions.remove(&key);
+            remove_terminal_session(&mut sessions, &workspace_id, &terminal_id);
         }
         return Err(err);
     }
     Ok(())
 }
 
+/// Signals the foreground process group of the PTY, falling back to the
+/// shell's own group when the foreground can't be read.
+#[cfg(unix)]
+fn signal_terminal(session: &TerminalSession, signal: TerminalSignal) -> Result<(), String> {
+    let signo = match signal {
+        TerminalSignal::Interrupt => libc::SIGINT,
+        TerminalSignal::Terminate => libc::SIGTERM,
+        TerminalSignal::Kill => libc::SIGKILL,
+    };
+    let process_group = session
+        .master
+        .blocking_lock()
+        .process_group_leader()
+        .filter(|pgid| *pgid > 0)
+        .or_else(|| {
+            session
+                .child
+                .blocking_lock()
+                .process_id()
+                .map(|pid| pid as libc::pid_t)
+        })
+        .ok_or_else(|| "Terminal process is not running".to_string())?;
+    let result = unsafe { libc::killpg(process_group, signo) };
+    if result != 0 {
+        let err = std::io::Error::last_os_error();
+        return Err(format!("Failed to signal terminal: {err}"));
+    }
+    Ok(())
+}
+
+/// ConPTY turns a written Ctrl+C into a `CTRL_C_EVENT` for every process
+/// attached to the console. There is no console event that ends a process
+/// outright, so terminate and kill end the shell, which takes the pseudo
+/// console and its processes down with it.
+#[cfg(windows)]
+fn signal_terminal(session: &TerminalSession, signal: TerminalSignal) -> Result<(), String> {
+    match signal {
+        TerminalSignal::Interrupt => {
+            let mut writer = session.writer.blocking_lock();
+            writer
+                .write_all(b"\x03")
+                .and_then(|_| writer.flush())
+                .map_err(|e| format!("Failed to signal terminal: {e}"))
+        }
+        TerminalSignal::Terminate | TerminalSignal::Kill => session
+            .child
+            .blocking_lock()
+            .kill()
+            .map_err(|e| format!("Failed to signal terminal: {e}")),
+    }
+}
+
+/// Sends `signal` to whatever is running in the terminal, for stopping a
+/// process that ignores a typed Ctrl+C.
+#[tauri::command]
+pub(crate) async fn terminal_signal(
+    workspace_id: String,
+    terminal_id: String,
+    signal: TerminalSignal,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let session = get_terminal_session(&state, &workspace_id, &terminal_id).await?;
+    tokio::task::spawn_blocking(move || signal_terminal(&session, signal))
+        .await
+        .map_err(|e| format!("Terminal signal task failed: {e}"))?
+}
+
 #[tauri::command]
 pub(crate) async fn terminal_close(
     workspace_id: String,
     terminal_id: String,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
-    let key = terminal_key(&workspace_id, &terminal_id);
-    let mut sessions = state.terminal_sessions.lock().await;
-    let session = sessions
-        .remove(&key)
-        .ok_or_else(|| "Terminal session not found".to_string())?;
-    drop(sessions);
-    let _ = tokio::task::spawn_blocking(move || {
-        let mut child = session.child.blocking_lock();
-        let _ = child.kill();
-    })
-    .await;
+    let session = {
+        let mut sessions = state.terminal_sessions.lock().await;
+        remove_terminal_session(&mut sessions, &workspace_id, &terminal_id)
+            .ok_or_else(|| "Terminal session not found".to_string())?
+    };
+    kill_terminal_sessions(vec![session]).await;
     Ok(())
 }
 
+/// Kills every open terminal, for `graceful_shutdown`.
+pub(crate) async fn close_all_terminals(state: &AppState) {
+    let sessions: Vec<Arc<TerminalSession>> = state
+        .terminal_sessions
+        .lock()
+        .await
+        .drain()
+        .flat_map(|(_, workspace_sessions)| workspace_sessions.into_values())
+        .collect();
+    kill_terminal_sessions(sessions).await;
+}
+
+/// Kills the terminals of workspaces that are no longer registered, after a
+/// workspace or worktree is removed.
+pub(crate) async fn close_removed_workspace_terminals(state: &AppState) {
+    let known: Vec<String> = state.workspaces.lock().await.keys().cloned().collect();
+    let sessions: Vec<Arc<TerminalSession>> = {
+        let mut sessions = state.terminal_sessions.lock().await;
+        let removed: Vec<String> = sessions
+            .keys()
+            .filter(|workspace_id| !known.contains(workspace_id))
+            .cloned()
+            .collect();
+        removed
+            .into_iter()
+            .filter_map(|workspace_id| sessions.remove(&workspace_id))
+            .flat_map(HashMap::into_values)
+            .collect()
+    };
+    kill_terminal_sessions(sessions).await;
+}
+
+/// Open terminals for `get_system_inventory`.
+pub(crate) async fn terminal_inventory(state: &AppState) -> Vec<InventoryTerminal> {
+    let sessions: Vec<(String, Arc<TerminalSession>)> = state
+        .terminal_sessions
+        .lock()
+        .await
+        .iter()
+        .flat_map(|(workspace_id, workspace_sessions)| {
+            workspace_sessions
+                .values()
+                .map(move |session| (workspace_id.clone(), Arc::clone(session)))
+        })
+        .collect();
+    let now = now_unix_ms();
+    let mut terminals = Vec::with_capacity(sessions.len());
+    for (workspace_id, session) in sessions {
+        let pid = session.child.lock().await.process_id();
+        terminals.push(InventoryTerminal {
+            workspace_id,
+            terminal_id: session.id.clone(),
+            pid,
+            started_at_ms: session.started_at_ms,
+            uptime_ms: inventory_core::uptime_ms(now, session.started_at_ms),
+        });
+    }
+    terminals.sort_by(|a, b| {
+        (a.workspace_id.as_str(), a.terminal_id.as_str())
+            .cmp(&(b.workspace_id.as_str(), b.terminal_id.as_str()))
+    });
+    terminals
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{unix_shell_args, windows_shell_args};
+    use super::{resolve_terminal_cwd, unix_shell_args, validate_terminal_env, windows_shell_args};
+    use uuid::Uuid;
 
     #[test]
     fn windows_shell_args_match_powershell_variants() {
@@ -410,4 +628,45 @@ mod tests {
     fn unix_shell_args_stay_interactive() {
         assert_eq!(unix_shell_args(), vec!["-i"]);
     }
+
+    #[test]
+    fn terminal_cwd_stays_inside_the_workspace() {
+        let base = std::env::temp_dir().join(format!("codex-monitor-terminal-{}", Uuid::new_v4()));
+        let root = base.join("repo");
+        std::fs::create_dir_all(root.join("src/app")).expect("create workspace");
+        std::fs::create_dir_all(base.join("elsewhere")).expect("create sibling");
+        std::fs::write(root.join("README.md"), "").expect("write file");
+        let canonical_root = root.canonicalize().expect("canonical root");
+
+        assert_eq!(
+            resolve_terminal_cwd(&root, None),
+            Ok(canonical_root.clone())
+        );
+        assert_eq!(
+            resolve_terminal_cwd(&root, Some("  ")),
+            Ok(canonical_root.clone())
+        );
+        assert_eq!(
+            resolve_terminal_cwd(&root, Some("src/app")),
+            Ok(canonical_root.join("src/app"))
+        );
+        let absolute = canonical_root.join("src");
+        assert_eq!(
+            resolve_terminal_cwd(&root, absolute.to_str()),
+            Ok(absolute.clone())
+        );
+        assert!(resolve_terminal_cwd(&root, Some("missing")).is_err());
+        assert!(resolve_terminal_cwd(&root, Some("README.md")).is_err());
+        assert!(resolve_terminal_cwd(&root, Some("../elsewhere")).is_err());
+
+        let _ = std::fs::remove_dir_all(base);
+    }
+
+    #[test]
+    fn terminal_env_rejects_malformed_names() {
+        let env = |key: &str| vec![(key.to_string(), "1".to_string())];
+        assert!(validate_terminal_env(&env("RUST_LOG")).is_ok());
+        assert!(validate_terminal_env(&env("")).is_err());
+        assert!(validate_terminal_env(&env("A=B")).is_err());
+    }
 }
@@ -1,7 +1,11 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
 use serde::Serialize;
 use tauri::{AppHandle, State};
 
 use crate::state::AppState;
+use crate::types::{InventoryTerminal, TerminalSignal};
 
 const UNSUPPORTED_MESSAGE: &str = "Terminal is not available on mobile builds.";
 
@@ -9,6 +13,8 @@ pub(crate) struct TerminalSession {
     pub(crate) id: String,
 }
 
+pub(crate) type TerminalSessions = HashMap<String, HashMap<String, Arc<TerminalSession>>>;
+
 #[derive(Debug, Serialize, Clone)]
 pub(crate) struct TerminalSessionInfo {
     id: String,
@@ -17,15 +23,15 @@ pub(crate) struct TerminalSessionInfo {
 #[tauri::command]
 pub(crate) async fn terminal_open(
     _workspace_id: String,
-    terminal_id: String,
+    _terminal_id: Option<String>,
     _cols: u16,
     _rows: u16,
+    _shell: Option<String>,
+    _cwd: Option<String>,
+    _env: Option<Vec<(String, String)>>,
     _state: State<'_, AppState>,
     _app: AppHandle,
 ) -> Result<TerminalSessionInfo, String> {
-    if terminal_id.trim().is_empty() {
-        return Err("Terminal id is required".to_string());
-    }
     Err(UNSUPPORTED_MESSAGE.to_string())
 }
 
@@ -50,6 +56,16 @@ pub(crate) async fn terminal_resize(
     Err(UNSUPPORTED_MESSAGE.to_string())
 }
 
+#[tauri::command]
+pub(crate) async fn terminal_signal(
+    _workspace_id: String,
+    _terminal_id: String,
+    _signal: TerminalSignal,
+    _state: State<'_, AppState>,
+) -> Result<(), String> {
+    Err(UNSUPPORTED_MESSAGE.to_string())
+}
+
 #[tauri::command]
 pub(crate) async fn terminal_close(
     _workspace_id: String,
@@ -58,3 +74,11 @@ pub(crate) async fn terminal_close(
 ) -> Result<(), String> {
     Err(UNSUPPORTED_MESSAGE.to_string())
 }
+
+pub(crate) async fn terminal_inventory(_state: &AppState) -> Vec<InventoryTerminal> {
+    Vec::new()
+}
+
+pub(crate) async fn close_all_terminals(_state: &AppState) {}
+
+pub(crate) async fn close_removed_workspace_terminals(_state: &AppState) {}
@@ -28,6 +28,38 @@ pub(crate) struct GitFileDiff {
     pub(crate) old_image_mime: Option<String>,
     #[serde(rename = "newImageMime")]
     pub(crate) new_image_mime: Option<String>,
+    /// Bytes on each side of a binary file; unset for text and for the
+    /// missing side of an added or deleted file.
+    #[serde(default, rename = "oldSize")]
+    pub(crate) old_size: Option<u64>,
+    #[serde(default, rename = "newSize")]
+    pub(crate) new_size: Option<u64>,
+    #[serde(default)]
+    pub(crate) redactions: Vec<SecretRedaction>,
+    #[serde(default)]
+    pub(crate) hunks: Vec<GitDiffHunk>,
+}
+
+/// `id` is derived from the path and the hunk's lines, so it stays the same
+/// across refreshes while the hunk itself is unchanged.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitDiffHunk {
+    pub(crate) id: String,
+    pub(crate) header: String,
+    pub(crate) old_start: u32,
+    pub(crate) old_lines: u32,
+    pub(crate) new_start: u32,
+    pub(crate) new_lines: u32,
+}
+
+/// A secret hidden from a diff. `side` is `old` or `new` and `line` is the
+/// 1-based line in that version of the file.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub(crate) struct SecretRedaction {
+    pub(crate) side: String,
+    pub(crate) line: u32,
+    pub(crate) pattern: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -61,10 +93,38 @@ pub(crate) struct GitLogEntry {
     pub(crate) timestamp: i64,
 }
 
+/// Narrows `get_git_log`; set filters must all match. `author` is plain
+/// text found anywhere in the author's name or email. `since` and `until`
+/// take anything git's date parser does, from `2024-05-01` to `2 weeks ago`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitLogFilter {
+    #[serde(default)]
+    pub(crate) author: Option<String>,
+    #[serde(default)]
+    pub(crate) since: Option<String>,
+    #[serde(default)]
+    pub(crate) until: Option<String>,
+}
+
+/// A remote from `git remote -v`. `push_url` is the fetch URL unless the
+/// remote sets its own.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitRemote {
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) fetch_url: Option<String>,
+    #[serde(default)]
+    pub(crate) push_url: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct GitLogResponse {
     pub(crate) total: usize,
     pub(crate) entries: Vec<GitLogEntry>,
+    #[serde(default, rename = "hasMore")]
+    pub(crate) has_more: bool,
     #[serde(default)]
     pub(crate) ahead: usize,
     #[serde(default)]
@@ -77,6 +137,575 @@ pub(crate) struct GitLogResponse {
     pub(crate) upstream: Option<String>,
 }
 
+/// One line of `git_blame`. Lines not committed yet have an all-zero `sha`,
+/// `committed: false` and "Not Committed Yet" as the author.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitBlameLine {
+    pub(crate) line_number: u32,
+    pub(crate) sha: String,
+    pub(crate) author: String,
+    pub(crate) author_email: String,
+    pub(crate) author_time: i64,
+    pub(crate) summary: String,
+    pub(crate) content: String,
+    pub(crate) committed: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitFileHistoryEntry {
+    pub(crate) sha: String,
+    pub(crate) summary: String,
+    pub(crate) author: String,
+    pub(crate) timestamp: i64,
+    /// Path of the file as of this commit.
+    pub(crate) path: String,
+    /// Set when this commit renamed the file.
+    #[serde(default)]
+    pub(crate) previous_path: Option<String>,
+    /// `None` for binary changes.
+    #[serde(default)]
+    pub(crate) additions: Option<u32>,
+    #[serde(default)]
+    pub(crate) deletions: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub(crate) struct GitRepoContributor {
+    pub(crate) name: String,
+    pub(crate) commits: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitRepoStats {
+    pub(crate) total_commits: u64,
+    pub(crate) contributor_count: usize,
+    pub(crate) top_contributors: Vec<GitRepoContributor>,
+    #[serde(default)]
+    pub(crate) last_commit_at: Option<i64>,
+    /// Tracked text files included in the line count.
+    pub(crate) file_count: usize,
+    pub(crate) lines_of_code: u64,
+    /// The repo had more tracked files than are counted.
+    #[serde(default)]
+    pub(crate) lines_truncated: bool,
+    pub(crate) computed_at_ms: i64,
+    #[serde(default)]
+    pub(crate) cached: bool,
+}
+
+/// `kind` is `secret`, `largeFile` or `sensitiveFile`; `severity` is `high`,
+/// `medium` or `low`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceAuditFinding {
+    pub(crate) path: String,
+    pub(crate) kind: String,
+    pub(crate) severity: String,
+    #[serde(default)]
+    pub(crate) line: Option<u32>,
+    pub(crate) message: String,
+    /// The file has changes staged for the next commit.
+    pub(crate) staged: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceAudit {
+    pub(crate) findings: Vec<WorkspaceAuditFinding>,
+    pub(crate) scanned_files: usize,
+    /// The index had more entries than are scanned.
+    #[serde(default)]
+    pub(crate) truncated: bool,
+    /// A high-severity finding is staged, so a commit should be held back.
+    pub(crate) blocking: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitBisectState {
+    pub(crate) active: bool,
+    #[serde(default)]
+    pub(crate) bad: Option<String>,
+    #[serde(default)]
+    pub(crate) good: Vec<String>,
+    /// Commit currently checked out for testing.
+    #[serde(default)]
+    pub(crate) candidate: Option<GitLogEntry>,
+    #[serde(default)]
+    pub(crate) revisions_left: Option<u32>,
+    #[serde(default)]
+    pub(crate) steps_left: Option<u32>,
+    /// Set once git has narrowed the range to a single commit.
+    #[serde(default)]
+    pub(crate) first_bad_commit: Option<GitLogEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub(crate) struct GitRefLabel {
+    pub(crate) name: String,
+    /// One of `head`, `branch`, `remote` or `tag`.
+    pub(crate) kind: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitGraphNode {
+    pub(crate) sha: String,
+    pub(crate) summary: String,
+    pub(crate) author: String,
+    pub(crate) timestamp: i64,
+    pub(crate) parents: Vec<String>,
+    #[serde(default)]
+    pub(crate) refs: Vec<GitRefLabel>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitGraphResponse {
+    pub(crate) nodes: Vec<GitGraphNode>,
+    /// More commits exist past `maxCount`.
+    #[serde(default)]
+    pub(crate) truncated: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitRemoteUpdates {
+    #[serde(default)]
+    pub(crate) upstream: Option<String>,
+    #[serde(default)]
+    pub(crate) remote_sha: Option<String>,
+    #[serde(default)]
+    pub(crate) ahead: usize,
+    #[serde(default)]
+    pub(crate) behind: usize,
+    #[serde(default)]
+    pub(crate) has_updates: bool,
+    pub(crate) checked_at: i64,
+    #[serde(default)]
+    pub(crate) cached: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum GitSyncOutcome {
+    UpToDate,
+    FastForwarded,
+    /// A new remote branch, a merge commit or a rebase.
+    Updated,
+    Rejected,
+    Conflicts,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitSyncResult {
+    pub(crate) outcome: GitSyncOutcome,
+    #[serde(default)]
+    pub(crate) upstream: Option<String>,
+    #[serde(default)]
+    pub(crate) conflicts: Vec<String>,
+    /// Git's output for `rejected` and `conflicts`.
+    #[serde(default)]
+    pub(crate) message: Option<String>,
+}
+
+/// A remote-tracking branch or tag a fetch changed, like `origin/main`.
+/// `old_sha` is unset for a new ref and `new_sha` for a pruned one.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitFetchedRef {
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) old_sha: Option<String>,
+    #[serde(default)]
+    pub(crate) new_sha: Option<String>,
+}
+
+/// `index` is the `N` of `stash@{N}`; `created_at` is in seconds.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitStashEntry {
+    pub(crate) index: usize,
+    pub(crate) message: String,
+    #[serde(default)]
+    pub(crate) branch: Option<String>,
+    pub(crate) created_at: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum GitStashOutcome {
+    Applied,
+    Conflicts,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitStashApplyResult {
+    pub(crate) outcome: GitStashOutcome,
+    #[serde(default)]
+    pub(crate) conflicts: Vec<String>,
+    /// Only a clean `git_stash_pop` drops the stash.
+    pub(crate) dropped: bool,
+    /// Git's output for `conflicts`.
+    #[serde(default)]
+    pub(crate) message: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitResetResult {
+    /// Short hash of HEAD after the reset.
+    pub(crate) head: String,
+    /// Files a hard reset put back to the target.
+    #[serde(default)]
+    pub(crate) restored: Vec<String>,
+    /// Files whose staged changes a mixed reset took out of the index.
+    #[serde(default)]
+    pub(crate) unstaged: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AppLockStatus {
+    pub(crate) locked: bool,
+    pub(crate) available: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CredentialStatus {
+    pub(crate) key: String,
+    pub(crate) exists: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct NetworkStatus {
+    pub(crate) online: bool,
+    #[serde(default)]
+    pub(crate) checked_at_ms: Option<i64>,
+    #[serde(default)]
+    pub(crate) changed_at_ms: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OperationTimeEstimate {
+    pub(crate) kind: String,
+    pub(crate) estimated_ms: u64,
+    pub(crate) sample_count: usize,
+    pub(crate) source: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OperationInfo {
+    pub(crate) id: String,
+    pub(crate) kind: String,
+    #[serde(default)]
+    pub(crate) workspace_id: Option<String>,
+    pub(crate) started_at_ms: i64,
+    #[serde(default)]
+    pub(crate) progress: Option<f64>,
+    #[serde(default)]
+    pub(crate) message: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct InventoryWorkspace {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) kind: WorkspaceKind,
+    #[serde(default)]
+    pub(crate) parent_id: Option<String>,
+    pub(crate) path: String,
+    pub(crate) connected: bool,
+    pub(crate) path_exists: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct InventorySession {
+    pub(crate) workspace_id: String,
+    #[serde(default)]
+    pub(crate) pid: Option<u32>,
+    pub(crate) started_at_ms: i64,
+    pub(crate) uptime_ms: u64,
+}
+
+/// What `terminal_signal` delivers to the terminal's foreground process.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum TerminalSignal {
+    Interrupt,
+    Terminate,
+    Kill,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct InventoryTerminal {
+    pub(crate) workspace_id: String,
+    pub(crate) terminal_id: String,
+    #[serde(default)]
+    pub(crate) pid: Option<u32>,
+    pub(crate) started_at_ms: i64,
+    pub(crate) uptime_ms: u64,
+}
+
+/// A background loop. `last_tick_ms` is the start of its latest pass, so a
+/// stuck loop shows a tick far older than its interval.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct InventoryScheduler {
+    pub(crate) name: String,
+    pub(crate) started_at_ms: i64,
+    #[serde(default)]
+    pub(crate) last_tick_ms: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SystemInventory {
+    pub(crate) generated_at_ms: i64,
+    pub(crate) workspaces: Vec<InventoryWorkspace>,
+    pub(crate) sessions: Vec<InventorySession>,
+    #[serde(default)]
+    pub(crate) terminals: Vec<InventoryTerminal>,
+    pub(crate) operations: Vec<OperationInfo>,
+    pub(crate) schedulers: Vec<InventoryScheduler>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RunningTurn {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: String,
+    pub(crate) started_at_ms: i64,
+}
+
+/// What would be cut short by quitting now. `buffered_events` counts
+/// streaming deltas not yet delivered to the UI.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ShutdownReadiness {
+    pub(crate) running_turns: Vec<RunningTurn>,
+    pub(crate) operations: Vec<OperationInfo>,
+    pub(crate) terminals: Vec<InventoryTerminal>,
+    pub(crate) buffered_events: usize,
+    pub(crate) safe_to_quit: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionStartupSample {
+    pub(crate) workspace_id: String,
+    pub(crate) workspace_name: String,
+    pub(crate) duration_ms: u64,
+    pub(crate) recorded_at_ms: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceStartupSummary {
+    pub(crate) workspace_id: String,
+    pub(crate) workspace_name: String,
+    pub(crate) sample_count: usize,
+    pub(crate) average_ms: u64,
+    pub(crate) max_ms: u64,
+    pub(crate) last_ms: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionStartupStats {
+    pub(crate) recent: Vec<SessionStartupSample>,
+    #[serde(default)]
+    pub(crate) average_ms: Option<u64>,
+    pub(crate) workspaces: Vec<WorkspaceStartupSummary>,
+}
+
+/// Who a workspace's session is signed in as. `auth_status` is one of
+/// `valid`, `expiring`, `needsRefresh`, `expired`, `apiKey` or `missing`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionAccount {
+    pub(crate) workspace_id: String,
+    #[serde(default)]
+    pub(crate) profile: Option<CodexProfile>,
+    #[serde(default)]
+    pub(crate) codex_home: Option<String>,
+    pub(crate) connected: bool,
+    #[serde(default)]
+    pub(crate) account_type: Option<String>,
+    #[serde(default)]
+    pub(crate) email: Option<String>,
+    #[serde(default)]
+    pub(crate) plan_type: Option<String>,
+    #[serde(default)]
+    pub(crate) requires_openai_auth: Option<bool>,
+    pub(crate) auth_status: String,
+    /// Unix seconds at which the stored access token expires.
+    #[serde(default)]
+    pub(crate) expires_at: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OrphanedCodexProcess {
+    pub(crate) pid: u32,
+    pub(crate) command: String,
+    pub(crate) cwd: String,
+    pub(crate) workspace_id: String,
+    pub(crate) workspace_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadReplayResult {
+    pub(crate) thread_id: String,
+    pub(crate) source_thread_id: String,
+    pub(crate) model: String,
+    pub(crate) turn_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadTurnSummary {
+    pub(crate) user_message: String,
+    pub(crate) response: String,
+    pub(crate) tool_call_count: usize,
+    #[serde(default)]
+    pub(crate) status: Option<String>,
+    #[serde(default)]
+    pub(crate) duration_ms: Option<i64>,
+    #[serde(default)]
+    pub(crate) token_usage: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadDiffTurn {
+    pub(crate) index: usize,
+    pub(crate) prompts_match: bool,
+    #[serde(default)]
+    pub(crate) a: Option<ThreadTurnSummary>,
+    #[serde(default)]
+    pub(crate) b: Option<ThreadTurnSummary>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadDiff {
+    pub(crate) thread_a: String,
+    pub(crate) thread_b: String,
+    pub(crate) turns: Vec<ThreadDiffTurn>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadExport {
+    pub(crate) thread_id: String,
+    pub(crate) format: String,
+    pub(crate) content: String,
+    /// Where the export was written, when a destination was given.
+    #[serde(default)]
+    pub(crate) path: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadSearchResult {
+    pub(crate) thread_id: String,
+    /// The thread's first user message, for showing untitled threads.
+    #[serde(default)]
+    pub(crate) preview: Option<String>,
+    /// The first match with some of the text around it.
+    pub(crate) snippet: String,
+    pub(crate) match_count: usize,
+    /// When the transcript was last written, in milliseconds.
+    pub(crate) updated_at: i64,
+    pub(crate) archived: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadWindow {
+    pub(crate) thread_id: String,
+    /// Item count across the whole thread.
+    pub(crate) total: usize,
+    /// Index of `items[0]` within the whole thread.
+    pub(crate) offset: usize,
+    pub(crate) anchor_index: usize,
+    pub(crate) items: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MessageTokenEstimate {
+    pub(crate) model: Option<String>,
+    pub(crate) tokenizer: String,
+    pub(crate) message_tokens: u64,
+    pub(crate) attachment_tokens: u64,
+    pub(crate) context_tokens: u64,
+    /// `usage` when the last turn reported it, `estimate` when the transcript
+    /// was tokenized instead.
+    pub(crate) context_source: String,
+    pub(crate) projected_tokens: u64,
+    pub(crate) context_window: Option<u64>,
+    pub(crate) projected_pct: Option<f64>,
+    pub(crate) will_compact: bool,
+    pub(crate) will_overflow: bool,
+    pub(crate) warning: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadUsage {
+    pub(crate) thread_id: String,
+    /// Session totals, including cached input.
+    pub(crate) input_tokens: u64,
+    pub(crate) cached_input_tokens: u64,
+    pub(crate) output_tokens: u64,
+    pub(crate) total_tokens: u64,
+    /// What the context holds now; unknown right after a compaction.
+    pub(crate) context_tokens: Option<u64>,
+    pub(crate) context_window: Option<u64>,
+    pub(crate) context_pct: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TurnInterruptOutcome {
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: String,
+    pub(crate) delivered: bool,
+    pub(crate) error: Option<String>,
+}
+
+/// One entry per connected workspace; `turns` is empty when nothing was
+/// running there.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceInterruptResult {
+    pub(crate) workspace_id: String,
+    pub(crate) turns: Vec<TurnInterruptOutcome>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PinnedMessage {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) message_id: String,
+    pub(crate) pinned_at_ms: i64,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct GitHubIssue {
     pub(crate) number: u64,
@@ -117,6 +746,28 @@ pub(crate) struct GitHubPullRequest {
     pub(crate) author: Option<GitHubPullRequestAuthor>,
 }
 
+/// What `create_github_pull_request` opens. `head` defaults to the current
+/// branch and `base` to the repository's default branch.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitHubPullRequestInput {
+    pub(crate) title: String,
+    #[serde(default)]
+    pub(crate) body: Option<String>,
+    #[serde(default)]
+    pub(crate) base: Option<String>,
+    #[serde(default)]
+    pub(crate) head: Option<String>,
+    #[serde(default)]
+    pub(crate) draft: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitHubCreatedPullRequest {
+    pub(crate) number: u64,
+    pub(crate) url: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct GitHubPullRequestsResponse {
     pub(crate) total: usize,
@@ -131,6 +782,54 @@ pub(crate) struct GitHubPullRequestDiff {
     pub(crate) diff: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum GitHubChecksState {
+    Success,
+    Pending,
+    Failure,
+}
+
+/// A check run or commit status. `status` is `queued`, `in_progress` or
+/// `completed`, and `conclusion` is set once it's completed.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitHubCheck {
+    pub(crate) name: String,
+    pub(crate) status: String,
+    #[serde(default)]
+    pub(crate) conclusion: Option<String>,
+    #[serde(default)]
+    pub(crate) details_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitHubChecksResponse {
+    pub(crate) state: GitHubChecksState,
+    pub(crate) head_sha: String,
+    pub(crate) checks: Vec<GitHubCheck>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitHubMergeResult {
+    pub(crate) merged: bool,
+    #[serde(default)]
+    pub(crate) sha: Option<String>,
+    #[serde(default)]
+    pub(crate) message: String,
+}
+
+/// An inline comment in a submitted review, on `line` of the PR's version
+/// of `path`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitHubReviewComment {
+    pub(crate) path: String,
+    pub(crate) line: u64,
+    pub(crate) body: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct GitHubPullRequestComment {
     pub(crate) id: u64,
@@ -352,6 +1051,134 @@ pub(crate) struct WorkspaceInfo {
     pub(crate) worktree: Option<WorktreeInfo>,
     #[serde(default)]
     pub(crate) settings: WorkspaceSettings,
+    /// Effective color: the workspace's own, or its parent's for worktrees.
+    #[serde(default)]
+    pub(crate) color: Option<String>,
+    /// False once the folder is gone from disk, e.g. moved or deleted outside
+    /// the app.
+    #[serde(default = "default_path_exists", rename = "pathExists")]
+    pub(crate) path_exists: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceRelocation {
+    pub(crate) workspace: WorkspaceInfo,
+    /// Child worktrees left at a missing path and git repairs that failed.
+    pub(crate) warnings: Vec<String>,
+}
+
+/// A workspace whose pinned `codexBin` is gone while the global one works.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StaleCodexBin {
+    pub(crate) workspace_id: String,
+    pub(crate) workspace_name: String,
+    pub(crate) codex_bin: String,
+    /// `None` means `codex` from PATH.
+    pub(crate) default_codex_bin: Option<String>,
+    pub(crate) default_version: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TurnTraceCapture {
+    pub(crate) path: String,
+    pub(crate) event_count: u32,
+    pub(crate) tool_call_count: u32,
+    /// False when the turn was still running at capture time.
+    pub(crate) complete: bool,
+    pub(crate) redacted_secrets: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TurnTraceReplay {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: String,
+    pub(crate) event_count: u32,
+    /// Approval and other server requests, which are not replayed.
+    pub(crate) skipped_requests: u32,
+}
+
+/// One approval rule: a `prefix_rule(...)` block of a workspace's rules
+/// file, or a glob or regex rule the app keeps beside it.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ApprovalRule {
+    /// Derived from the rule's text, so it stays put when the file is
+    /// reordered or edited elsewhere.
+    pub(crate) id: String,
+    /// `exact`, `glob` or `regex`.
+    #[serde(default = "default_approval_rule_kind")]
+    pub(crate) kind: String,
+    /// The `pattern` tokens; a list of alternatives reads `a|b`. Glob and
+    /// regex rules have a single entry.
+    pub(crate) pattern: Vec<String>,
+    pub(crate) decision: Option<String>,
+    /// The block as written in the file, or the glob or regex source.
+    pub(crate) rule: String,
+}
+
+/// What `test_approval_rule` matched a pattern against.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ApprovalRuleTest {
+    pub(crate) matched: bool,
+    /// The command as glob and regex rules see it: shell wrappers such as
+    /// `bash -lc '...'` unwrapped, tokens joined with single spaces.
+    pub(crate) command_line: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ApprovalRulesExport {
+    pub(crate) version: u32,
+    pub(crate) rules: Vec<ApprovalRule>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ApprovalRulesImport {
+    pub(crate) added: usize,
+    /// Rules already present, not a single `prefix_rule(...)` block, or with
+    /// a glob or regex that doesn't compile.
+    pub(crate) skipped: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceBundleExport {
+    pub(crate) path: String,
+    pub(crate) prompts: u32,
+    pub(crate) rules: u32,
+    pub(crate) threads: u32,
+    /// Lines in prompts and threads where a secret was replaced.
+    pub(crate) redacted_secrets: u32,
+    #[serde(default)]
+    pub(crate) skipped: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceBundleImport {
+    pub(crate) workspace: WorkspaceInfo,
+    pub(crate) prompts: u32,
+    pub(crate) rules: u32,
+    pub(crate) threads: u32,
+    /// What could not be restored, one sentence each.
+    #[serde(default)]
+    pub(crate) skipped: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceRemovalResult {
+    pub(crate) id: String,
+    pub(crate) removed: bool,
+    #[serde(default)]
+    pub(crate) error: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -388,6 +1215,16 @@ pub(crate) struct WorkspaceGroup {
     pub(crate) copies_folder: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceGroupInfo {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) sort_order: Option<u32>,
+    pub(crate) copies_folder: Option<String>,
+    pub(crate) member_count: u32,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub(crate) struct WorkspaceSettings {
     #[serde(default, rename = "sidebarCollapsed")]
@@ -408,6 +1245,43 @@ pub(crate) struct WorkspaceSettings {
     pub(crate) launch_scripts: Option<Vec<LaunchScriptEntry>>,
     #[serde(default, rename = "worktreeSetupScript")]
     pub(crate) worktree_setup_script: Option<String>,
+    /// Hex accent color (`#rrggbb`) used to tint this workspace in the UI.
+    #[serde(default)]
+    pub(crate) color: Option<String>,
+    /// Background fetch cadence while connected; unset or 0 disables it.
+    #[serde(default, rename = "autoFetchIntervalSecs")]
+    pub(crate) auto_fetch_interval_secs: Option<u64>,
+    /// OpenAI-compatible endpoint for this workspace's Codex session, for
+    /// self-hosted backends. Worktrees inherit it from their parent.
+    #[serde(default, rename = "codexBaseUrl")]
+    pub(crate) codex_base_url: Option<String>,
+    /// GitHub Enterprise host, like `ghe.company.com`, for the pull request
+    /// and issue features when the remote URL doesn't name it. Worktrees
+    /// inherit it from their parent.
+    #[serde(default, rename = "githubHost")]
+    pub(crate) github_host: Option<String>,
+    /// Codex profile whose `codexHome` this workspace was switched to.
+    #[serde(default, rename = "profileId")]
+    pub(crate) profile_id: Option<String>,
+    /// Compact a thread before a turn that would take its context past this
+    /// percentage of the model window. Worktrees inherit it from their parent.
+    #[serde(default, rename = "autoCompactAtPct")]
+    pub(crate) auto_compact_at_pct: Option<u8>,
+    /// Extra variables for the Codex process, applied in order. Worktrees use
+    /// their parent's when they set none.
+    #[serde(default)]
+    pub(crate) env: Vec<(String, String)>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceFileEntry {
+    pub(crate) path: String,
+    /// Bytes; for a symlink, the length of the link itself.
+    pub(crate) size: u64,
+    #[serde(default)]
+    pub(crate) modified_at_ms: Option<i64>,
+    pub(crate) is_symlink: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -419,6 +1293,29 @@ pub(crate) struct LaunchScriptEntry {
     pub(crate) label: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorktreeChangedFile {
+    pub(crate) path: String,
+    pub(crate) additions: u64,
+    pub(crate) deletions: u64,
+    #[serde(default)]
+    pub(crate) binary: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorktreeChangesPreview {
+    pub(crate) files: Vec<WorktreeChangedFile>,
+    pub(crate) additions: u64,
+    pub(crate) deletions: u64,
+    pub(crate) would_conflict: bool,
+    #[serde(default)]
+    pub(crate) conflict_detail: Option<String>,
+    /// Applying is refused while the parent has uncommitted changes.
+    pub(crate) parent_dirty: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct WorktreeSetupStatus {
     #[serde(rename = "shouldRun")]
@@ -658,8 +1555,17 @@ pub(crate) struct AppSettings {
     pub(crate) dictation_model_id: String,
     #[serde(default, rename = "dictationPreferredLanguage")]
     pub(crate) dictation_preferred_language: Option<String>,
+    /// Name of the microphone to record from; `None` uses the system default.
+    #[serde(default, rename = "dictationInputDevice")]
+    pub(crate) dictation_input_device: Option<String>,
+    /// Stops dictation after this much silence following speech; `None`
+    /// leaves stopping to the user.
+    #[serde(default, rename = "dictationAutoStopSilenceMs")]
+    pub(crate) dictation_auto_stop_silence_ms: Option<u32>,
     #[serde(default = "default_dictation_hold_key", rename = "dictationHoldKey")]
     pub(crate) dictation_hold_key: String,
+    #[serde(default, rename = "dictationSaveAudio")]
+    pub(crate) dictation_save_audio: bool,
     #[serde(
         default = "default_composer_editor_preset",
         rename = "composerEditorPreset"
@@ -711,6 +1617,43 @@ pub(crate) struct AppSettings {
     pub(crate) open_app_targets: Vec<OpenAppTarget>,
     #[serde(default = "default_selected_open_app_id", rename = "selectedOpenAppId")]
     pub(crate) selected_open_app_id: String,
+    #[serde(default, rename = "httpProxy")]
+    pub(crate) http_proxy: Option<String>,
+    #[serde(default, rename = "httpsProxy")]
+    pub(crate) https_proxy: Option<String>,
+    #[serde(default, rename = "noProxy")]
+    pub(crate) no_proxy: Option<String>,
+    #[serde(default, rename = "customCaCertPath")]
+    pub(crate) custom_ca_cert_path: Option<String>,
+    #[serde(default, rename = "customCaHosts")]
+    pub(crate) custom_ca_hosts: Vec<String>,
+    #[serde(default, rename = "requireUnlockOnLaunch")]
+    pub(crate) require_unlock_on_launch: bool,
+    #[serde(default, rename = "autoLockIdleSecs")]
+    pub(crate) auto_lock_idle_secs: Option<u64>,
+    #[serde(default = "default_redact_secrets", rename = "redactSecrets")]
+    pub(crate) redact_secrets: bool,
+    #[serde(default, rename = "secretPatterns")]
+    pub(crate) secret_patterns: Vec<String>,
+    #[serde(default, rename = "codexProfiles")]
+    pub(crate) codex_profiles: Vec<CodexProfile>,
+    /// Milliseconds to hold streaming deltas before sending them to the UI.
+    /// 0 sends every event as it arrives.
+    #[serde(default, rename = "eventBatchIntervalMs")]
+    pub(crate) event_batch_interval_ms: u64,
+    /// Restart a workspace's Codex process, with backoff, when it crashes.
+    #[serde(default, rename = "autoRespawnSessions")]
+    pub(crate) auto_respawn_sessions: bool,
+}
+
+/// A named Codex account. Credentials live in the profile's own `codexHome`,
+/// so switching profiles is switching homes.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct CodexProfile {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    #[serde(rename = "codexHome")]
+    pub(crate) codex_home: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -739,6 +1682,10 @@ impl Default for RemoteBackendProvider {
     }
 }
 
+fn default_approval_rule_kind() -> String {
+    "exact".to_string()
+}
+
 fn default_access_mode() -> String {
     "current".to_string()
 }
@@ -767,6 +1714,10 @@ fn default_theme() -> String {
     "system".to_string()
 }
 
+fn default_path_exists() -> bool {
+    true
+}
+
 fn default_usage_show_remaining() -> bool {
     false
 }
@@ -963,6 +1914,10 @@ fn default_git_diff_ignore_whitespace_changes() -> bool {
     false
 }
 
+fn default_redact_secrets() -> bool {
+    true
+}
+
 fn default_commit_message_prompt() -> String {
     "Generate a concise git commit message for the following changes. \
 Follow conventional commit format (e.g., feat:, fix:, refactor:, docs:, etc.). \
@@ -1237,7 +2192,10 @@ impl Default for AppSettings {
             dictation_enabled: false,
             dictation_model_id: default_dictation_model_id(),
             dictation_preferred_language: None,
+            dictation_input_device: None,
+            dictation_auto_stop_silence_ms: None,
             dictation_hold_key: default_dictation_hold_key(),
+            dictation_save_audio: false,
             composer_editor_preset: default_composer_editor_preset(),
             composer_fence_expand_on_space: default_composer_fence_expand_on_space(),
             composer_fence_expand_on_enter: default_composer_fence_expand_on_enter(),
@@ -1252,6 +2210,18 @@ impl Default for AppSettings {
             workspace_groups: default_workspace_groups(),
             open_app_targets: default_open_app_targets(),
             selected_open_app_id: default_selected_open_app_id(),
+            http_proxy: None,
+            https_proxy: None,
+            no_proxy: None,
+            custom_ca_cert_path: None,
+            custom_ca_hosts: Vec::new(),
+            require_unlock_on_launch: false,
+            auto_lock_idle_secs: None,
+            redact_secrets: default_redact_secrets(),
+            secret_patterns: Vec::new(),
+            codex_profiles: Vec::new(),
+            event_batch_interval_ms: 0,
+            auto_respawn_sessions: false,
         }
     }
 }
@@ -1402,7 +2372,10 @@ mod tests {
         assert!(!settings.dictation_enabled);
         assert_eq!(settings.dictation_model_id, "base");
         assert!(settings.dictation_preferred_language.is_none());
+        assert!(settings.dictation_input_device.is_none());
+        assert!(settings.dictation_auto_stop_silence_ms.is_none());
         assert_eq!(settings.dictation_hold_key, "alt");
+        assert!(!settings.dictation_save_audio);
         assert_eq!(settings.composer_editor_preset, "default");
         assert!(!settings.composer_fence_expand_on_space);
         assert!(!settings.composer_fence_expand_on_enter);
@@ -1,12 +1,14 @@
 use std::env;
 use std::ffi::OsString;
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 pub(crate) fn normalize_git_path(path: &str) -> String {
     path.replace('\\', "/")
 }
 
-fn find_in_path(binary: &str) -> Option<PathBuf> {
+pub(crate) fn find_in_path(binary: &str) -> Option<PathBuf> {
     let path_var = env::var_os("PATH")?;
     for dir in env::split_paths(&path_var) {
         let candidate = dir.join(binary);
@@ -84,12 +86,49 @@ pub(crate) fn git_env_path() -> String {
     joined.to_string_lossy().to_string()
 }
 
+/// Replaces `path` with `data` by writing a sibling `.tmp` file and renaming
+/// it over, so a crash mid-write leaves the old contents rather than half of
+/// the new ones.
+pub(crate) fn write_file_atomic(path: &Path, data: &[u8]) -> Result<(), String> {
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+    fs::write(&temp_path, data).map_err(|err| err.to_string())?;
+    fs::rename(&temp_path, path).map_err(|err| {
+        let _ = fs::remove_file(&temp_path);
+        err.to_string()
+    })
+}
+
+/// Milliseconds since the Unix epoch, or 0 if the clock reads before it.
+pub(crate) fn now_unix_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::normalize_git_path;
+    use super::{normalize_git_path, write_file_atomic};
 
     #[test]
     fn normalize_git_path_replaces_backslashes() {
         assert_eq!(normalize_git_path("foo\\bar\\baz"), "foo/bar/baz");
     }
+
+    #[test]
+    fn write_file_atomic_replaces_the_file_without_leaving_a_temp() {
+        let dir =
+            std::env::temp_dir().join(format!("codex-monitor-utils-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).expect("dir");
+        let path = dir.join("state.json");
+        std::fs::write(&path, "old").expect("seed");
+
+        write_file_atomic(&path, b"new").expect("write");
+
+        assert_eq!(std::fs::read_to_string(&path).expect("read"), "new");
+        assert!(!dir.join("state.json.tmp").exists());
+        let _ = std::fs::remove_dir_all(dir);
+    }
 }
@@ -5,7 +5,10 @@ use std::sync::Arc;
 use serde_json::json;
 use tauri::{AppHandle, Manager, State};
 
-use super::files::{list_workspace_files_inner, read_workspace_file_inner, WorkspaceFileResponse};
+use super::files::{
+    list_workspace_files_detailed_inner, list_workspace_files_inner, read_workspace_file_inner,
+    WorkspaceFileResponse,
+};
 use super::git::{
     git_branch_exists, git_find_remote_for_branch, git_remote_branch_exists, git_remote_exists,
     is_missing_worktree_error, run_git_command_owned, unique_branch_name,
@@ -19,11 +22,17 @@ use super::worktree::{
 
 use crate::backend::app_server::WorkspaceSession;
 use crate::codex::spawn_workspace_session;
+use crate::event_sink::TauriEventSink;
 use crate::git_utils::resolve_git_root;
 use crate::remote_backend;
-use crate::shared::workspaces_core;
+use crate::shared::{workspace_bundle_core, workspace_groups_core, workspaces_core};
 use crate::state::AppState;
-use crate::types::{WorkspaceEntry, WorkspaceInfo, WorkspaceSettings, WorktreeSetupStatus};
+use crate::terminal;
+use crate::types::{
+    StaleCodexBin, WorkspaceBundleExport, WorkspaceBundleImport, WorkspaceEntry,
+    WorkspaceFileEntry, WorkspaceGroupInfo, WorkspaceInfo, WorkspaceRelocation,
+    WorkspaceRemovalResult, WorkspaceSettings, WorktreeChangesPreview, WorktreeSetupStatus,
+};
 
 fn spawn_with_app(
     app: &AppHandle,
@@ -134,22 +143,34 @@ pub(crate) async fn add_clone(
     source_workspace_id: String,
     copy_name: String,
     copies_folder: String,
+    operation_id: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<WorkspaceInfo, String> {
-    workspaces_core::add_clone_core(
-        source_workspace_id,
-        copy_name,
-        copies_folder,
-        &state.workspaces,
-        &state.sessions,
-        &state.app_settings,
-        &state.storage_path,
-        |entry, default_bin, codex_args, codex_home| {
-            spawn_with_app(&app, entry, default_bin, codex_args, codex_home)
-        },
-    )
-    .await
+    let event_sink = TauriEventSink::new(app.clone());
+    state
+        .operations
+        .run(
+            "clone",
+            Some(source_workspace_id.clone()),
+            operation_id,
+            |_| {
+                workspaces_core::add_clone_core(
+                    source_workspace_id,
+                    copy_name,
+                    copies_folder,
+                    &state.workspaces,
+                    &state.sessions,
+                    &state.app_settings,
+                    &state.storage_path,
+                    &event_sink,
+                    |entry, default_bin, codex_args, codex_home| {
+                        spawn_with_app(&app, entry, default_bin, codex_args, codex_home)
+                    },
+                )
+            },
+        )
+        .await
 }
 
 #[tauri::command]
@@ -158,6 +179,7 @@ pub(crate) async fn add_worktree(
     branch: String,
     name: Option<String>,
     copy_agents_md: Option<bool>,
+    base: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<WorkspaceInfo, String> {
@@ -171,7 +193,8 @@ pub(crate) async fn add_worktree(
                 "parentId": parent_id,
                 "branch": branch,
                 "name": name,
-                "copyAgentsMd": copy_agents_md
+                "copyAgentsMd": copy_agents_md,
+                "base": base
             }),
         )
         .await?;
@@ -188,6 +211,7 @@ pub(crate) async fn add_worktree(
         branch,
         name,
         copy_agents_md,
+        base,
         &data_dir,
         &state.workspaces,
         &state.sessions,
@@ -272,7 +296,7 @@ pub(crate) async fn remove_workspace(
         return Ok(());
     }
 
-    workspaces_core::remove_workspace_core(
+    let result = workspaces_core::remove_workspace_core(
         id,
         &state.workspaces,
         &state.sessions,
@@ -290,7 +314,9 @@ pub(crate) async fn remove_workspace(
         true,
         true,
     )
-    .await
+    .await;
+    terminal::close_removed_workspace_terminals(&state).await;
+    result
 }
 
 #[tauri::command]
@@ -304,7 +330,7 @@ pub(crate) async fn remove_worktree(
         return Ok(());
     }
 
-    workspaces_core::remove_worktree_core(
+    let result = workspaces_core::remove_worktree_core(
         id,
         &state.workspaces,
         &state.sessions,
@@ -320,7 +346,43 @@ pub(crate) async fn remove_worktree(
                 .map_err(|err| format!("Failed to remove worktree folder: {err}"))
         },
     )
-    .await
+    .await;
+    terminal::close_removed_workspace_terminals(&state).await;
+    result
+}
+
+#[tauri::command]
+pub(crate) async fn remove_workspaces(
+    ids: Vec<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<WorkspaceRemovalResult>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "remove_workspaces", json!({ "ids": ids }))
+                .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let result = workspaces_core::remove_workspaces_core(
+        ids,
+        &state.workspaces,
+        &state.sessions,
+        &state.storage_path,
+        |root, args| {
+            workspaces_core::run_git_command_unit(root, args, |repo, args_owned| {
+                run_git_command_owned(repo, args_owned)
+            })
+        },
+        |error| is_missing_worktree_error(error),
+        |path| {
+            std::fs::remove_dir_all(path)
+                .map_err(|err| format!("Failed to remove worktree folder: {err}"))
+        },
+    )
+    .await;
+    terminal::close_removed_workspace_terminals(&state).await;
+    result
 }
 
 #[tauri::command]
@@ -441,6 +503,30 @@ pub(crate) async fn apply_worktree_changes(
     workspaces_core::apply_worktree_changes_core(&state.workspaces, workspace_id).await
 }
 
+#[tauri::command]
+pub(crate) async fn apply_worktree_commits(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    workspaces_core::apply_worktree_commits_core(&state.workspaces, workspace_id).await
+}
+
+#[tauri::command]
+pub(crate) async fn move_worktree_changes_back(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    workspaces_core::move_worktree_changes_back_core(&state.workspaces, workspace_id).await
+}
+
+#[tauri::command]
+pub(crate) async fn preview_worktree_changes(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<WorktreeChangesPreview, String> {
+    workspaces_core::preview_worktree_changes_core(&state.workspaces, workspace_id).await
+}
+
 #[tauri::command]
 pub(crate) async fn update_workspace_settings(
     id: String,
@@ -476,6 +562,41 @@ pub(crate) async fn update_workspace_settings(
     .await
 }
 
+#[tauri::command]
+pub(crate) async fn set_workspace_profile(
+    workspace_id: String,
+    profile_id: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceInfo, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "set_workspace_profile",
+            json!({ "workspaceId": workspace_id, "profileId": profile_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    workspaces_core::set_workspace_profile_core(
+        workspace_id,
+        profile_id,
+        &state.workspaces,
+        &state.sessions,
+        &state.app_settings,
+        &state.storage_path,
+        |workspaces, workspace_id, next_settings| {
+            apply_workspace_settings_update(workspaces, workspace_id, next_settings)
+        },
+        |entry, default_bin, codex_args, codex_home| {
+            spawn_with_app(&app, entry, default_bin, codex_args, codex_home)
+        },
+    )
+    .await
+}
+
 #[tauri::command]
 pub(crate) async fn update_workspace_codex_bin(
     id: String,
@@ -505,6 +626,288 @@ pub(crate) async fn update_workspace_codex_bin(
     .await
 }
 
+#[tauri::command]
+pub(crate) async fn check_workspace_codex_bins(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<StaleCodexBin>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "check_workspace_codex_bins", json!({}))
+                .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    Ok(workspaces_core::find_stale_codex_bins_core(&state.workspaces, &state.app_settings).await)
+}
+
+#[tauri::command]
+pub(crate) async fn repoint_codex_bin(
+    id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceInfo, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "repoint_codex_bin", json!({ "id": id }))
+                .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    workspaces_core::repoint_codex_bin_core(
+        id,
+        &state.workspaces,
+        &state.sessions,
+        &state.storage_path,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn set_workspace_color(
+    id: String,
+    color: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceInfo, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "set_workspace_color",
+            json!({ "id": id, "color": color }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    workspaces_core::set_workspace_color_core(
+        id,
+        color,
+        &state.workspaces,
+        &state.sessions,
+        &state.storage_path,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn rename_workspace(
+    id: String,
+    name: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceInfo, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "rename_workspace",
+            json!({ "id": id, "name": name }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    workspaces_core::rename_workspace_core(
+        id,
+        name,
+        &state.workspaces,
+        &state.sessions,
+        &state.storage_path,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn relocate_workspace(
+    id: String,
+    new_path: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceRelocation, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let new_path = remote_backend::normalize_path_for_remote(new_path);
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "relocate_workspace",
+            json!({ "id": id, "newPath": new_path }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    workspaces_core::relocate_workspace_core(
+        id,
+        new_path,
+        &state.workspaces,
+        &state.sessions,
+        &state.app_settings,
+        &state.storage_path,
+        |entry, default_bin, codex_args, codex_home| {
+            spawn_with_app(&app, entry, default_bin, codex_args, codex_home)
+        },
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn export_workspace_bundle(
+    workspace_id: String,
+    destination: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceBundleExport, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let destination = destination.map(remote_backend::normalize_path_for_remote);
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "export_workspace_bundle",
+            json!({ "workspaceId": workspace_id, "destination": destination }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    workspace_bundle_core::export_workspace_bundle_core(
+        &state.workspaces,
+        &state.app_settings,
+        &state.settings_path,
+        workspace_id,
+        destination,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn import_workspace_bundle(
+    path: String,
+    target_path: String,
+    trusted: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceBundleImport, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let path = remote_backend::normalize_path_for_remote(path);
+        let target_path = remote_backend::normalize_path_for_remote(target_path);
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "import_workspace_bundle",
+            json!({ "path": path, "targetPath": target_path, "trusted": trusted }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    workspace_bundle_core::import_workspace_bundle_core(
+        &state.workspaces,
+        &state.sessions,
+        &state.app_settings,
+        &state.storage_path,
+        &state.settings_path,
+        path,
+        target_path,
+        trusted.unwrap_or(false),
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn list_workspace_groups(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<WorkspaceGroupInfo>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "list_workspace_groups", json!({})).await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    Ok(
+        workspace_groups_core::list_workspace_groups_core(&state.workspaces, &state.app_settings)
+            .await,
+    )
+}
+
+#[tauri::command]
+pub(crate) async fn create_workspace_group(
+    name: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceGroupInfo, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "create_workspace_group",
+            json!({ "name": name }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    workspace_groups_core::create_workspace_group_core(
+        name,
+        &state.workspaces,
+        &state.app_settings,
+        &state.settings_path,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn rename_workspace_group(
+    id: String,
+    name: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceGroupInfo, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "rename_workspace_group",
+            json!({ "id": id, "name": name }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    workspace_groups_core::rename_workspace_group_core(
+        id,
+        name,
+        &state.workspaces,
+        &state.app_settings,
+        &state.settings_path,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn delete_workspace_group(
+    id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        remote_backend::call_remote(&*state, app, "delete_workspace_group", json!({ "id": id }))
+            .await?;
+        return Ok(());
+    }
+
+    workspace_groups_core::delete_workspace_group_core(
+        id,
+        &state.workspaces,
+        &state.app_settings,
+        &state.storage_path,
+        &state.settings_path,
+    )
+    .await
+}
+
 #[tauri::command]
 pub(crate) async fn connect_workspace(
     id: String,
@@ -516,6 +919,7 @@ pub(crate) async fn connect_workspace(
         return Ok(());
     }
 
+    workspaces_core::interrupt_prewarm();
     workspaces_core::connect_workspace_core(
         id,
         &state.workspaces,
@@ -528,25 +932,83 @@ pub(crate) async fn connect_workspace(
     .await
 }
 
+#[tauri::command]
+pub(crate) async fn prewarm_sessions(
+    workspace_ids: Vec<String>,
+    app: AppHandle,
+) -> Result<(), String> {
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        if remote_backend::is_remote_mode(&*state).await {
+            let _ = remote_backend::call_remote(
+                &*state,
+                app.clone(),
+                "prewarm_sessions",
+                json!({ "workspaceIds": workspace_ids }),
+            )
+            .await;
+            return;
+        }
+        workspaces_core::prewarm_sessions_core(
+            workspace_ids,
+            &state.workspaces,
+            &state.sessions,
+            &state.app_settings,
+            |entry, default_bin, codex_args, codex_home| {
+                spawn_with_app(&app, entry, default_bin, codex_args, codex_home)
+            },
+        )
+        .await;
+    });
+    Ok(())
+}
+
 #[tauri::command]
 pub(crate) async fn list_workspace_files(
     workspace_id: String,
+    include_ignored: Option<bool>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<Vec<String>, String> {
+    let include_ignored = include_ignored.unwrap_or(false);
     if remote_backend::is_remote_mode(&*state).await {
         let response = remote_backend::call_remote(
             &*state,
             app,
             "list_workspace_files",
-            json!({ "workspaceId": workspace_id }),
+            json!({ "workspaceId": workspace_id, "includeIgnored": include_ignored }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    workspaces_core::list_workspace_files_core(&state.workspaces, &workspace_id, |root| {
+        list_workspace_files_inner(root, usize::MAX, include_ignored)
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn list_workspace_files_detailed(
+    workspace_id: String,
+    include_ignored: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<WorkspaceFileEntry>, String> {
+    let include_ignored = include_ignored.unwrap_or(false);
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "list_workspace_files_detailed",
+            json!({ "workspaceId": workspace_id, "includeIgnored": include_ignored }),
         )
         .await?;
         return serde_json::from_value(response).map_err(|err| err.to_string());
     }
 
     workspaces_core::list_workspace_files_core(&state.workspaces, &workspace_id, |root| {
-        list_workspace_files_inner(root, usize::MAX)
+        list_workspace_files_detailed_inner(root, usize::MAX, include_ignored)
     })
     .await
 }
@@ -571,8 +1033,19 @@ pub(crate) async fn get_open_app_icon(app_name: String) -> Result<Option<String>
         .await;
     }
 
-    #[cfg(not(target_os = "macos"))]
+    #[cfg(target_os = "windows")]
+    {
+        return workspaces_core::get_open_app_icon_core(app_name, |name| {
+            super::windows::get_open_app_icon_inner(name)
+        })
+        .await;
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
     {
-        workspaces_core::get_open_app_icon_core(app_name, |_name| None).await
+        workspaces_core::get_open_app_icon_core(app_name, |name| {
+            super::xdg::get_open_app_icon_inner(name)
+        })
+        .await
     }
 }
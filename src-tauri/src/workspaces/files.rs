@@ -1,10 +1,12 @@
 use std::fs::File;
 use std::io::Read;
 use std::path::PathBuf;
+use std::time::UNIX_EPOCH;
 
-use ignore::WalkBuilder;
+use ignore::{Walk, WalkBuilder};
 use serde::{Deserialize, Serialize};
 
+use crate::types::WorkspaceFileEntry;
 use crate::utils::normalize_git_path;
 
 fn should_skip_dir(name: &str) -> bool {
@@ -14,28 +16,47 @@ fn should_skip_dir(name: &str) -> bool {
     )
 }
 
-pub(crate) fn list_workspace_files_inner(root: &PathBuf, max_files: usize) -> Vec<String> {
-    let mut results = Vec::new();
-    let walker = WalkBuilder::new(root)
+/// Honors `.gitignore`, `.git/info/exclude`, the global excludes file and
+/// `.ignore` unless `include_ignored` is set, in which case only `.git` itself
+/// is skipped.
+fn workspace_walker(root: &PathBuf, include_ignored: bool) -> Walk {
+    let respect_ignore_rules = !include_ignored;
+    WalkBuilder::new(root)
         // Allow hidden entries.
         .hidden(false)
         // Avoid crawling symlink targets.
         .follow_links(false)
-        // Don't require git to be present to apply to apply git-related ignore rules.
+        .git_ignore(respect_ignore_rules)
+        .git_exclude(respect_ignore_rules)
+        .git_global(respect_ignore_rules)
+        .ignore(respect_ignore_rules)
+        .parents(respect_ignore_rules)
+        // Don't require git to be present to apply to apply git-related ignore rules,
+        // so a non-git workspace still honors its own `.gitignore`.
         .require_git(false)
-        .filter_entry(|entry| {
+        .filter_entry(move |entry| {
             if entry.depth() == 0 {
                 return true;
             }
             if entry.file_type().is_some_and(|ft| ft.is_dir()) {
                 let name = entry.file_name().to_string_lossy();
+                if include_ignored {
+                    return name != ".git";
+                }
                 return !should_skip_dir(&name);
             }
             true
         })
-        .build();
+        .build()
+}
 
-    for entry in walker {
+pub(crate) fn list_workspace_files_inner(
+    root: &PathBuf,
+    max_files: usize,
+    include_ignored: bool,
+) -> Vec<String> {
+    let mut results = Vec::new();
+    for entry in workspace_walker(root, include_ignored) {
         let entry = match entry {
             Ok(entry) => entry,
             Err(_) => continue,
@@ -58,6 +79,55 @@ pub(crate) fn list_workspace_files_inner(root: &PathBuf, max_files: usize) -> Ve
     results
 }
 
+/// Like [`list_workspace_files_inner`] but with size and mtime. Symlinks are
+/// listed with the link's own metadata and never followed.
+pub(crate) fn list_workspace_files_detailed_inner(
+    root: &PathBuf,
+    max_files: usize,
+    include_ignored: bool,
+) -> Vec<WorkspaceFileEntry> {
+    let mut results = Vec::new();
+    for entry in workspace_walker(root, include_ignored) {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(_) => continue,
+        };
+        let Some(file_type) = entry.file_type() else {
+            continue;
+        };
+        if !file_type.is_file() && !file_type.is_symlink() {
+            continue;
+        }
+        let Ok(rel_path) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        let normalized = normalize_git_path(&rel_path.to_string_lossy());
+        if normalized.is_empty() {
+            continue;
+        }
+        let Ok(metadata) = std::fs::symlink_metadata(entry.path()) else {
+            continue;
+        };
+        let modified_at_ms = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_millis() as i64);
+        results.push(WorkspaceFileEntry {
+            path: normalized,
+            size: metadata.len(),
+            modified_at_ms,
+            is_symlink: file_type.is_symlink(),
+        });
+        if results.len() >= max_files {
+            break;
+        }
+    }
+
+    results.sort_by(|a, b| a.path.cmp(&b.path));
+    results
+}
+
 const MAX_WORKSPACE_FILE_BYTES: u64 = 400_000;
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -3,7 +3,11 @@ mod files;
 mod git;
 mod macos;
 mod settings;
+#[cfg(target_os = "windows")]
+mod windows;
 mod worktree;
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+mod xdg;
 
 pub(crate) use commands::*;
 
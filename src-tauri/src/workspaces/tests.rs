@@ -3,17 +3,21 @@ use std::future::Future;
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use super::files::{list_workspace_files_detailed_inner, list_workspace_files_inner};
 use super::settings::{apply_workspace_settings_update, sort_workspaces};
 use super::worktree::{
     build_clone_destination_path, sanitize_clone_dir_name, sanitize_worktree_name,
 };
 use crate::backend::app_server::WorkspaceSession;
 use crate::shared::workspaces_core::{
-    remove_workspace_core, remove_worktree_core, rename_worktree_core,
+    remove_workspace_core, remove_workspaces_core, remove_worktree_core, rename_workspace_core,
+    rename_worktree_core, set_workspace_profile_core,
 };
+use crate::shared::worktree_core::parse_clone_progress;
 use crate::storage::{read_workspaces, write_workspaces};
 use crate::types::{
-    AppSettings, WorkspaceEntry, WorkspaceInfo, WorkspaceKind, WorkspaceSettings, WorktreeInfo,
+    AppSettings, CodexProfile, WorkspaceEntry, WorkspaceInfo, WorkspaceKind, WorkspaceSettings,
+    WorktreeInfo,
 };
 use tokio::runtime::Runtime;
 use tokio::sync::Mutex;
@@ -58,7 +62,16 @@ fn workspace_with_id_and_kind(
             launch_script: None,
             launch_scripts: None,
             worktree_setup_script: None,
+            color: None,
+            auto_fetch_interval_secs: None,
+            codex_base_url: None,
+            github_host: None,
+            profile_id: None,
+            auto_compact_at_pct: None,
+            env: Vec::new(),
         },
+        color: None,
+        path_exists: true,
     }
 }
 
@@ -123,6 +136,27 @@ fn build_clone_destination_path_sanitizes_and_uniquifies() {
     );
 }
 
+#[test]
+fn parse_clone_progress_weights_stages_into_one_percentage() {
+    assert_eq!(
+        parse_clone_progress("Receiving objects:  50% (500/1000), 1.20 MiB | 2.40 MiB/s"),
+        Some(("Receiving objects", 40))
+    );
+    assert_eq!(
+        parse_clone_progress("Resolving deltas: 100% (20/20), done."),
+        Some(("Resolving deltas", 90))
+    );
+    assert_eq!(
+        parse_clone_progress("Updating files:  50% (5/10)"),
+        Some(("Updating files", 95))
+    );
+    assert_eq!(parse_clone_progress("Cloning into 'copy'..."), None);
+    assert_eq!(
+        parse_clone_progress("remote: Counting objects: 10% (1/10)"),
+        None
+    );
+}
+
 #[test]
 fn sort_workspaces_orders_by_sort_then_name() {
     let mut items = vec![
@@ -513,3 +547,310 @@ fn remove_worktree_succeeds_when_parent_repo_folder_is_missing() {
         assert!(!workspaces_guard.contains_key(&child.id));
     });
 }
+
+#[test]
+fn remove_workspaces_prunes_once_per_parent_and_reports_per_id() {
+    run_async(async {
+        let temp_dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let parent_repo_path = temp_dir.join("parent-repo");
+        std::fs::create_dir_all(&parent_repo_path).expect("create parent repo path");
+
+        let parent = WorkspaceEntry {
+            id: "parent".to_string(),
+            name: "Parent".to_string(),
+            path: parent_repo_path.to_string_lossy().to_string(),
+            codex_bin: None,
+            kind: WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings::default(),
+        };
+        let worktree = |id: &str| {
+            let path = temp_dir.join("worktrees").join(id);
+            std::fs::create_dir_all(&path).expect("create worktree path");
+            WorkspaceEntry {
+                id: id.to_string(),
+                name: id.to_string(),
+                path: path.to_string_lossy().to_string(),
+                codex_bin: None,
+                kind: WorkspaceKind::Worktree,
+                parent_id: Some(parent.id.clone()),
+                worktree: Some(WorktreeInfo {
+                    branch: id.to_string(),
+                }),
+                settings: WorkspaceSettings::default(),
+            }
+        };
+        let ok = worktree("wt-ok");
+        let stuck = worktree("wt-stuck");
+        let workspaces = Mutex::new(HashMap::from([
+            (parent.id.clone(), parent.clone()),
+            (ok.id.clone(), ok.clone()),
+            (stuck.id.clone(), stuck.clone()),
+        ]));
+        let sessions: Mutex<HashMap<String, Arc<WorkspaceSession>>> = Mutex::new(HashMap::new());
+        let storage_path = temp_dir.join("workspaces.json");
+        let git_calls = Arc::new(std::sync::Mutex::new(Vec::<String>::new()));
+        let stuck_path = stuck.path.clone();
+
+        let results = remove_workspaces_core(
+            vec![ok.id.clone(), stuck.id.clone(), "missing".to_string()],
+            &workspaces,
+            &sessions,
+            &storage_path,
+            |_root, args| {
+                let command = args.join(" ");
+                git_calls.lock().unwrap().push(command.clone());
+                let stuck = command.ends_with(&stuck_path);
+                async move {
+                    if stuck {
+                        Err("worktree is locked".to_string())
+                    } else {
+                        Ok(())
+                    }
+                }
+            },
+            |_error| false,
+            |path| std::fs::remove_dir_all(path).map_err(|err| err.to_string()),
+        )
+        .await
+        .expect("remove workspaces");
+
+        let summary: Vec<(&str, bool)> = results
+            .iter()
+            .map(|result| (result.id.as_str(), result.removed))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("wt-ok", true), ("wt-stuck", false), ("missing", false)]
+        );
+        assert_eq!(results[1].error.as_deref(), Some("worktree is locked"));
+        assert_eq!(results[2].error.as_deref(), Some("workspace not found"));
+        let prunes = git_calls
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|call| call.starts_with("worktree prune"))
+            .count();
+        assert_eq!(prunes, 1);
+
+        let stored = read_workspaces(&storage_path).expect("read workspaces");
+        assert!(stored.contains_key("parent"));
+        assert!(stored.contains_key("wt-stuck"));
+        assert!(!stored.contains_key("wt-ok"));
+        let _ = std::fs::remove_dir_all(temp_dir);
+    });
+}
+
+#[test]
+fn rename_workspace_updates_main_name_only() {
+    let temp_dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&temp_dir).expect("create temp dir");
+    let storage_path = temp_dir.join("workspaces.json");
+    let main = WorkspaceEntry {
+        id: "main".to_string(),
+        name: "repo".to_string(),
+        path: temp_dir.to_string_lossy().to_string(),
+        codex_bin: None,
+        kind: WorkspaceKind::Main,
+        parent_id: None,
+        worktree: None,
+        settings: WorkspaceSettings::default(),
+    };
+    let child = WorkspaceEntry {
+        id: "child".to_string(),
+        name: "feature".to_string(),
+        path: temp_dir.join("feature").to_string_lossy().to_string(),
+        codex_bin: None,
+        kind: WorkspaceKind::Worktree,
+        parent_id: Some("main".to_string()),
+        worktree: Some(WorktreeInfo {
+            branch: "feature".to_string(),
+        }),
+        settings: WorkspaceSettings::default(),
+    };
+    let workspaces = Mutex::new(HashMap::from([
+        (main.id.clone(), main),
+        (child.id.clone(), child),
+    ]));
+    let sessions = Mutex::new(HashMap::new());
+
+    run_async(async {
+        let err = rename_workspace_core(
+            "main".to_string(),
+            "   ".to_string(),
+            &workspaces,
+            &sessions,
+            &storage_path,
+        )
+        .await
+        .expect_err("empty name");
+        assert_eq!(err, "Workspace name is required.");
+        assert!(rename_workspace_core(
+            "child".to_string(),
+            "other".to_string(),
+            &workspaces,
+            &sessions,
+            &storage_path,
+        )
+        .await
+        .is_err());
+
+        let renamed = rename_workspace_core(
+            "main".to_string(),
+            " prod-hotfix ".to_string(),
+            &workspaces,
+            &sessions,
+            &storage_path,
+        )
+        .await
+        .expect("rename");
+        assert_eq!(renamed.name, "prod-hotfix");
+    });
+
+    let stored = read_workspaces(&storage_path).expect("read workspaces");
+    assert_eq!(
+        stored.get("main").map(|entry| entry.name.as_str()),
+        Some("prod-hotfix")
+    );
+    assert_eq!(
+        stored.get("child").map(|entry| entry.name.as_str()),
+        Some("feature")
+    );
+    assert!(stored
+        .get("main")
+        .is_some_and(|entry| entry.path == temp_dir.to_string_lossy()));
+
+    let _ = std::fs::remove_dir_all(temp_dir);
+}
+
+#[test]
+fn set_workspace_profile_switches_and_clears_codex_home() {
+    let temp_dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&temp_dir).expect("create temp dir");
+    let storage_path = temp_dir.join("workspaces.json");
+    let entry = WorkspaceEntry {
+        id: "main".to_string(),
+        name: "repo".to_string(),
+        path: temp_dir.to_string_lossy().to_string(),
+        codex_bin: None,
+        kind: WorkspaceKind::Main,
+        parent_id: None,
+        worktree: None,
+        settings: WorkspaceSettings::default(),
+    };
+    let workspaces = Mutex::new(HashMap::from([(entry.id.clone(), entry)]));
+    let sessions = Mutex::new(HashMap::new());
+    let app_settings = Mutex::new(AppSettings {
+        codex_profiles: vec![CodexProfile {
+            id: "work".to_string(),
+            name: "Work".to_string(),
+            codex_home: "~/.codex-work".to_string(),
+        }],
+        ..AppSettings::default()
+    });
+    let set_profile = |profile_id: Option<&str>| {
+        set_workspace_profile_core(
+            "main".to_string(),
+            profile_id.map(str::to_string),
+            &workspaces,
+            &sessions,
+            &app_settings,
+            &storage_path,
+            |workspaces, workspace_id, next_settings| {
+                apply_workspace_settings_update(workspaces, workspace_id, next_settings)
+            },
+            |_, _, _, _| async {
+                Err::<Arc<WorkspaceSession>, String>("not connected".to_string())
+            },
+        )
+    };
+
+    run_async(async {
+        assert!(set_profile(Some("missing")).await.is_err());
+
+        let switched = set_profile(Some("work")).await.expect("switch profile");
+        assert_eq!(switched.settings.profile_id.as_deref(), Some("work"));
+        assert_eq!(
+            switched.settings.codex_home.as_deref(),
+            Some("~/.codex-work")
+        );
+
+        let cleared = set_profile(None).await.expect("clear profile");
+        assert!(cleared.settings.profile_id.is_none());
+        assert!(cleared.settings.codex_home.is_none());
+    });
+
+    let _ = std::fs::remove_dir_all(temp_dir);
+}
+
+#[test]
+fn list_workspace_files_honors_gitignore_unless_including_ignored() {
+    let temp_dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+    for dir in ["src", "build", "node_modules/pkg"] {
+        std::fs::create_dir_all(temp_dir.join(dir)).expect("create dir");
+    }
+    for (path, content) in [
+        (".gitignore", "build/\n*.log\n"),
+        ("src/main.rs", "fn main() {}\n"),
+        ("build/out.js", "\n"),
+        ("debug.log", "\n"),
+        ("node_modules/pkg/index.js", "\n"),
+    ] {
+        std::fs::write(temp_dir.join(path), content).expect("write file");
+    }
+
+    let files = list_workspace_files_inner(&temp_dir, usize::MAX, false);
+    assert_eq!(files, vec![".gitignore", "src/main.rs"]);
+
+    let everything = list_workspace_files_inner(&temp_dir, usize::MAX, true);
+    assert_eq!(
+        everything,
+        vec![
+            ".gitignore",
+            "build/out.js",
+            "debug.log",
+            "node_modules/pkg/index.js",
+            "src/main.rs",
+        ]
+    );
+
+    let _ = std::fs::remove_dir_all(temp_dir);
+}
+
+#[cfg(unix)]
+#[test]
+fn list_workspace_files_detailed_reports_links_without_following_them() {
+    let temp_dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(temp_dir.join("src")).expect("create dir");
+    std::fs::write(temp_dir.join("src/main.rs"), "fn main() {}\n").expect("write file");
+    std::os::unix::fs::symlink(&temp_dir, temp_dir.join("src/loop")).expect("create symlink");
+
+    let files = list_workspace_files_detailed_inner(&temp_dir, usize::MAX, false);
+    let paths: Vec<(&str, bool)> = files
+        .iter()
+        .map(|file| (file.path.as_str(), file.is_symlink))
+        .collect();
+    assert_eq!(paths, vec![("src/loop", true), ("src/main.rs", false)]);
+    assert_eq!(files[1].size, 13);
+    assert!(files[1].modified_at_ms.is_some());
+
+    let _ = std::fs::remove_dir_all(temp_dir);
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+#[test]
+fn desktop_entries_match_by_name_exec_or_reverse_dns_id() {
+    use super::xdg::{entry_matches, parse_desktop_entry};
+
+    let code = parse_desktop_entry(
+        "[Desktop Entry]\nName=Visual Studio Code\nName[de]=Code\nExec=/usr/share/code/code --unity-launch %F\nIcon=vscode\n\n[Desktop Action new-empty-window]\nName=New Empty Window\nIcon=other\n",
+    );
+    assert_eq!(code.name.as_deref(), Some("Visual Studio Code"));
+    assert_eq!(code.icon.as_deref(), Some("vscode"));
+    assert!(entry_matches(&code, "code", "Visual Studio Code"));
+
+    let zed = parse_desktop_entry("[Desktop Entry]\nName=Zed Editor\nExec=env FOO=1 zeditor %U\n");
+    assert!(entry_matches(&zed, "dev.zed.Zed", "Zed"));
+    assert!(!entry_matches(&zed, "dev.zed.Zed", "Cursor"));
+}
@@ -0,0 +1,81 @@
+use std::path::{Path, PathBuf};
+
+use crate::shared::process_core::{resolve_windows_executable, std_command};
+use crate::shared::workspaces_core::app_binary_candidates;
+
+// `ExtractAssociatedIcon` is reached through Windows PowerShell so no Win32
+// bindings are needed. `App Paths` covers apps that are not on PATH.
+const ICON_SCRIPT: &str = "param($target) \
+Add-Type -AssemblyName System.Drawing; \
+if (-not (Test-Path -LiteralPath $target -PathType Leaf)) { \
+foreach ($root in 'HKCU:', 'HKLM:') { \
+$key = \"$root\\Software\\Microsoft\\Windows\\CurrentVersion\\App Paths\\$target.exe\"; \
+$value = (Get-ItemProperty -LiteralPath $key -ErrorAction SilentlyContinue).'(default)'; \
+if ($value) { $target = $value.Trim('\"'); break } } }; \
+if (-not (Test-Path -LiteralPath $target -PathType Leaf)) { exit 1 }; \
+$icon = [System.Drawing.Icon]::ExtractAssociatedIcon($target); \
+if (-not $icon) { exit 1 }; \
+$stream = New-Object System.IO.MemoryStream; \
+$icon.ToBitmap().Save($stream, [System.Drawing.Imaging.ImageFormat]::Png); \
+[Convert]::ToBase64String($stream.ToArray())";
+
+/// Launcher scripts such as VS Code's `bin\code.cmd` carry no icon; the
+/// matching executable sits one directory up.
+fn executable_for_script(script: &Path) -> Option<PathBuf> {
+    let stem = script.file_stem()?.to_string_lossy().to_ascii_lowercase();
+    let install_dir = script.parent()?.parent()?;
+    std::fs::read_dir(install_dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| {
+            let is_exe = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("exe"));
+            let same_stem = path
+                .file_stem()
+                .map(|value| value.to_string_lossy().to_ascii_lowercase() == stem)
+                .unwrap_or(false);
+            is_exe && same_stem
+        })
+}
+
+fn resolve_icon_target(app_name: &str) -> String {
+    for candidate in app_binary_candidates(app_name) {
+        let Some(resolved) = resolve_windows_executable(&candidate, None) else {
+            continue;
+        };
+        let is_script = resolved
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("cmd") || ext.eq_ignore_ascii_case("bat"));
+        if !is_script {
+            return resolved.to_string_lossy().to_string();
+        }
+        if let Some(executable) = executable_for_script(&resolved) {
+            return executable.to_string_lossy().to_string();
+        }
+    }
+    app_name.trim().to_string()
+}
+
+pub(crate) fn get_open_app_icon_inner(app_name: &str) -> Option<String> {
+    let target = resolve_icon_target(app_name);
+    let script = format!(
+        "& {{ {ICON_SCRIPT} }} -target '{}'",
+        target.replace('\'', "''")
+    );
+    let output = std_command("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let encoded = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if encoded.is_empty() {
+        return None;
+    }
+    Some(format!("data:image/png;base64,{encoded}"))
+}
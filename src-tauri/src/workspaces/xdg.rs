@@ -0,0 +1,190 @@
+use base64::Engine as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::shared::workspaces_core::app_binary_candidates;
+
+/// Preferred raster sizes, largest first; the UI scales icons down.
+const ICON_SIZES: &[&str] = &[
+    "256x256", "128x128", "512x512", "96x96", "64x64", "48x48", "32x32",
+];
+
+fn data_home() -> Option<PathBuf> {
+    std::env::var_os("XDG_DATA_HOME")
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+}
+
+/// `$XDG_DATA_HOME` followed by `$XDG_DATA_DIRS`, plus the Flatpak export
+/// directories that desktop environments also read.
+fn data_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(home) = data_home() {
+        dirs.push(home.join("flatpak/exports/share"));
+        dirs.push(home);
+    }
+    let system = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+    dirs.extend(
+        system
+            .split(':')
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from),
+    );
+    dirs.push(PathBuf::from("/var/lib/flatpak/exports/share"));
+    dirs
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub(super) struct DesktopEntry {
+    pub(super) name: Option<String>,
+    pub(super) exec: Option<String>,
+    pub(super) icon: Option<String>,
+}
+
+/// Reads the unlocalized `Name`, `Exec` and `Icon` keys of the
+/// `[Desktop Entry]` group.
+pub(super) fn parse_desktop_entry(contents: &str) -> DesktopEntry {
+    let mut entry = DesktopEntry::default();
+    let mut in_main_group = false;
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_main_group = line == "[Desktop Entry]";
+            continue;
+        }
+        if !in_main_group {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim().to_string();
+        match key.trim() {
+            "Name" => entry.name = Some(value),
+            "Exec" => entry.exec = Some(value),
+            "Icon" => entry.icon = Some(value),
+            _ => {}
+        }
+    }
+    entry
+}
+
+fn exec_binary(exec: &str) -> Option<String> {
+    let program = exec
+        .split_whitespace()
+        .find(|token| *token != "env" && !token.contains('='))?;
+    let program = program.trim_matches('"');
+    Some(program.rsplit('/').next().unwrap_or(program).to_string())
+}
+
+pub(super) fn entry_matches(entry: &DesktopEntry, file_stem: &str, app_name: &str) -> bool {
+    if entry
+        .name
+        .as_deref()
+        .is_some_and(|name| name.eq_ignore_ascii_case(app_name.trim()))
+    {
+        return true;
+    }
+    let candidates = app_binary_candidates(app_name);
+    let stem = file_stem.to_ascii_lowercase();
+    // Reverse-DNS ids such as `dev.zed.Zed` end in the app name.
+    let short_stem = stem.rsplit('.').next().unwrap_or(&stem);
+    let binary = entry.exec.as_deref().and_then(exec_binary);
+    candidates.iter().any(|candidate| {
+        let candidate = candidate.to_ascii_lowercase();
+        candidate == stem
+            || candidate == short_stem
+            || binary.as_deref() == Some(candidate.as_str())
+    })
+}
+
+fn find_desktop_icon(app_name: &str) -> Option<String> {
+    for dir in data_dirs() {
+        let Ok(entries) = fs::read_dir(dir.join("applications")) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("desktop") {
+                continue;
+            }
+            let Ok(contents) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let stem = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let desktop = parse_desktop_entry(&contents);
+            if entry_matches(&desktop, &stem, app_name) {
+                if let Some(icon) = desktop.icon.filter(|icon| !icon.is_empty()) {
+                    return Some(icon);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Looks in `hicolor`, the theme every icon theme falls back to, and then
+/// `pixmaps`. A PNG is preferred; SVG is used only when nothing else exists.
+fn resolve_icon_path(icon: &str) -> Option<PathBuf> {
+    let direct = Path::new(icon);
+    if direct.is_absolute() {
+        return direct.is_file().then(|| direct.to_path_buf());
+    }
+    let mut bases: Vec<PathBuf> = data_dirs()
+        .into_iter()
+        .map(|dir| dir.join("icons"))
+        .collect();
+    if let Some(home) = std::env::var_os("HOME") {
+        bases.insert(0, PathBuf::from(home).join(".icons"));
+    }
+    for size in ICON_SIZES {
+        for base in &bases {
+            let path = base
+                .join("hicolor")
+                .join(size)
+                .join("apps")
+                .join(format!("{icon}.png"));
+            if path.is_file() {
+                return Some(path);
+            }
+        }
+    }
+    for dir in data_dirs() {
+        let path = dir.join("pixmaps").join(format!("{icon}.png"));
+        if path.is_file() {
+            return Some(path);
+        }
+    }
+    bases
+        .iter()
+        .map(|base| {
+            base.join("hicolor/scalable/apps")
+                .join(format!("{icon}.svg"))
+        })
+        .chain(
+            data_dirs()
+                .into_iter()
+                .map(|dir| dir.join("pixmaps").join(format!("{icon}.svg"))),
+        )
+        .find(|path| path.is_file())
+}
+
+pub(crate) fn get_open_app_icon_inner(app_name: &str) -> Option<String> {
+    let icon = find_desktop_icon(app_name)?;
+    let icon_path = resolve_icon_path(&icon)?;
+    let mime = match icon_path.extension().and_then(|ext| ext.to_str()) {
+        Some("png") => "image/png",
+        Some("svg") => "image/svg+xml",
+        _ => return None,
+    };
+    let bytes = fs::read(&icon_path).ok()?;
+    let encoded = base64::engine::general_purpose::STANDARD.encode(bytes);
+    Some(format!("data:{mime};base64,{encoded}"))
+}
@@ -36,6 +36,7 @@ import "./styles/settings.css";
 import "./styles/compact-base.css";
 import "./styles/compact-phone.css";
 import "./styles/compact-tablet.css";
+import "./styles/app-lock.css";
 import successSoundUrl from "@/assets/success-notification.mp3";
 import errorSoundUrl from "@/assets/error-notification.mp3";
 import { AppLayout } from "@app/components/AppLayout";
@@ -58,6 +59,8 @@ import { useModels } from "@/features/models/hooks/useModels";
 import { useCollaborationModes } from "@/features/collaboration/hooks/useCollaborationModes";
 import { useCollaborationModeSelection } from "@/features/collaboration/hooks/useCollaborationModeSelection";
 import { useSkills } from "@/features/skills/hooks/useSkills";
+import { AppLockScreen } from "@/features/lock/components/AppLockScreen";
+import { useAppLock } from "@/features/lock/hooks/useAppLock";
 import { useApps } from "@/features/apps/hooks/useApps";
 import { useCustomPrompts } from "@/features/prompts/hooks/useCustomPrompts";
 import { useWorkspaceFileListing } from "@app/hooks/useWorkspaceFileListing";
@@ -171,6 +174,7 @@ function MainApp() {
     dictationModel,
     dictationState,
     dictationLevel,
+    dictationSpeaking,
     dictationTranscript,
     dictationError,
     dictationHint,
@@ -471,6 +475,7 @@ function MainApp() {
     updatePrompt,
     deletePrompt,
     movePrompt,
+    duplicatePrompt,
     getWorkspacePromptsDir,
     getGlobalPromptsDir,
   } = useCustomPrompts({ activeWorkspace, onDebug: addDebugEntry });
@@ -576,6 +581,7 @@ function MainApp() {
     handleTestSystemNotification,
   } = useUpdaterController({
     enabled: updaterEnabled,
+    proxy: appSettings.httpsProxy ?? appSettings.httpProxy,
     notificationSoundsEnabled: appSettings.notificationSoundsEnabled,
     systemNotificationsEnabled: appSettings.systemNotificationsEnabled,
     subagentSystemNotificationsEnabled:
@@ -1456,6 +1462,17 @@ function MainApp() {
     [alertError, movePrompt],
   );
 
+  const handleDuplicatePrompt = useCallback(
+    async (path: string) => {
+      try {
+        await duplicatePrompt(path);
+      } catch (error) {
+        alertError(error);
+      }
+    },
+    [alertError, duplicatePrompt],
+  );
+
   const handleRevealWorkspacePrompts = useCallback(async () => {
     try {
       const path = await getWorkspacePromptsDir();
@@ -2093,6 +2110,7 @@ function MainApp() {
     onUpdatePrompt: handleUpdatePrompt,
     onDeletePrompt: handleDeletePrompt,
     onMovePrompt: handleMovePrompt,
+    onDuplicatePrompt: handleDuplicatePrompt,
     onRevealWorkspacePrompts: handleRevealWorkspacePrompts,
     onRevealGeneralPrompts: handleRevealGeneralPrompts,
     canRevealGeneralPrompts: Boolean(activeWorkspace),
@@ -2172,6 +2190,7 @@ function MainApp() {
     dictationEnabled: appSettings.dictationEnabled && dictationReady,
     dictationState,
     dictationLevel,
+    dictationSpeaking,
     onToggleDictation: handleToggleDictation,
     dictationTranscript,
     onDictationTranscriptHandled: (id) => {
@@ -2276,6 +2295,7 @@ function MainApp() {
       dictationEnabled={appSettings.dictationEnabled && dictationReady}
       dictationState={dictationState}
       dictationLevel={dictationLevel}
+      dictationSpeaking={dictationSpeaking}
       onToggleDictation={handleToggleDictation}
       onOpenDictationSettings={() => openSettings("dictation")}
       dictationError={dictationError}
@@ -2453,6 +2473,42 @@ function MainApp() {
   );
 }
 
+// Every command is rejected while the app is locked, so a launch lock keeps
+// MainApp from mounting (and loading) until it is lifted. A later idle lock
+// covers the running app instead.
+function LockableMainApp() {
+  const { status, unlocking, error, unlock } = useAppLock();
+  const [unlockedOnce, setUnlockedOnce] = useState(false);
+
+  useEffect(() => {
+    if (status && !status.locked) {
+      setUnlockedOnce(true);
+    }
+  }, [status]);
+
+  if (!status) {
+    return null;
+  }
+  const lockScreen = status.locked ? (
+    <AppLockScreen
+      unlocking={unlocking}
+      error={error}
+      onUnlock={() => {
+        void unlock();
+      }}
+    />
+  ) : null;
+  if (status.locked && !unlockedOnce) {
+    return lockScreen;
+  }
+  return (
+    <>
+      <MainApp />
+      {lockScreen}
+    </>
+  );
+}
+
 function App() {
   const windowLabel = useWindowLabel();
   if (windowLabel === "about") {
@@ -2462,7 +2518,7 @@ function App() {
       </Suspense>
     );
   }
-  return <MainApp />;
+  return <LockableMainApp />;
 }
 
 export default App;
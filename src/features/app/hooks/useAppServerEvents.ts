@@ -2,7 +2,9 @@ import { useEffect, useRef } from "react";
 import type {
   AppServerEvent,
   ApprovalRequest,
+  AutoCompactionEvent,
   RequestUserInputRequest,
+  StaleCodexBin,
 } from "../../../types";
 import { subscribeAppServerEvents } from "../../../services/events";
 import {
@@ -79,6 +81,8 @@ type AppServerEventHandlers = {
     threadId: string,
     tokenUsage: Record<string, unknown> | null,
   ) => void;
+  onAutoCompaction?: (workspaceId: string, event: AutoCompactionEvent) => void;
+  onCodexBinMissing?: (workspaceId: string, stale: StaleCodexBin) => void;
   onAccountRateLimitsUpdated?: (
     workspaceId: string,
     rateLimits: Record<string, unknown>,
@@ -94,7 +98,9 @@ export const METHODS_ROUTED_IN_USE_APP_SERVER_EVENTS = [
   "account/login/completed",
   "account/rateLimits/updated",
   "account/updated",
+  "codex/autoCompaction",
   "codex/backgroundThread",
+  "codex/codexBinMissing",
   "codex/connected",
   "error",
   "item/agentMessage/delta",
@@ -316,6 +322,36 @@ export function useAppServerEvents(handlers: AppServerEventHandlers) {
         return;
       }
 
+      if (method === "codex/autoCompaction") {
+        const threadId = String(params.threadId ?? "");
+        if (threadId) {
+          currentHandlers.onAutoCompaction?.(workspace_id, {
+            threadId,
+            status: String(params.status ?? "") as AutoCompactionEvent["status"],
+            contextTokens: (params.contextTokens as number | undefined) ?? null,
+            contextWindow: (params.contextWindow as number | undefined) ?? null,
+            projectedPct: (params.projectedPct as number | undefined) ?? null,
+            thresholdPct: (params.thresholdPct as number | undefined) ?? null,
+            error: (params.error as string | undefined) ?? null,
+          });
+        }
+        return;
+      }
+
+      if (method === "codex/codexBinMissing") {
+        const codexBin = String(params.codexBin ?? "");
+        if (codexBin) {
+          currentHandlers.onCodexBinMissing?.(workspace_id, {
+            workspaceId: workspace_id,
+            workspaceName: String(params.workspaceName ?? ""),
+            codexBin,
+            defaultCodexBin: (params.defaultCodexBin as string | undefined) ?? null,
+            defaultVersion: (params.defaultVersion as string | undefined) ?? null,
+          });
+        }
+        return;
+      }
+
       if (method === "account/rateLimits/updated") {
         const rateLimits =
           (params.rateLimits as Record<string, unknown> | undefined) ??
@@ -9,6 +9,7 @@ type DictationController = {
   dictationModel: ReturnType<typeof useDictationModel>;
   dictationState: ReturnType<typeof useDictation>["state"];
   dictationLevel: ReturnType<typeof useDictation>["level"];
+  dictationSpeaking: ReturnType<typeof useDictation>["speaking"];
   dictationTranscript: ReturnType<typeof useDictation>["transcript"];
   dictationError: ReturnType<typeof useDictation>["error"];
   dictationHint: ReturnType<typeof useDictation>["hint"];
@@ -27,6 +28,7 @@ export function useDictationController(appSettings: AppSettings): DictationContr
   const {
     state: dictationState,
     level: dictationLevel,
+    speaking: dictationSpeaking,
     transcript: dictationTranscript,
     error: dictationError,
     hint: dictationHint,
@@ -129,6 +131,7 @@ export function useDictationController(appSettings: AppSettings): DictationContr
     dictationModel,
     dictationState,
     dictationLevel,
+    dictationSpeaking,
     dictationTranscript,
     dictationError,
     dictationHint,
@@ -11,6 +11,7 @@ import type { DebugEntry } from "../../../types";
 
 type Params = {
   enabled?: boolean;
+  proxy?: string | null;
   notificationSoundsEnabled: boolean;
   systemNotificationsEnabled: boolean;
   subagentSystemNotificationsEnabled: boolean;
@@ -24,6 +25,7 @@ type Params = {
 
 export function useUpdaterController({
   enabled = true,
+  proxy,
   notificationSoundsEnabled,
   systemNotificationsEnabled,
   subagentSystemNotificationsEnabled,
@@ -36,6 +38,7 @@ export function useUpdaterController({
 }: Params) {
   const { state: updaterState, startUpdate, checkForUpdates, dismiss } = useUpdater({
     enabled,
+    proxy,
     onDebug,
   });
   const isWindowFocused = useWindowFocusState();
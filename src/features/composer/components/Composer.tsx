@@ -93,6 +93,7 @@ type ComposerProps = {
   dictationEnabled?: boolean;
   dictationState?: "idle" | "listening" | "processing";
   dictationLevel?: number;
+  dictationSpeaking?: boolean;
   onToggleDictation?: () => void;
   onOpenDictationSettings?: () => void;
   dictationTranscript?: DictationTranscript | null;
@@ -197,6 +198,7 @@ export const Composer = memo(function Composer({
   dictationEnabled = false,
   dictationState = "idle",
   dictationLevel = 0,
+  dictationSpeaking = false,
   onToggleDictation,
   onOpenDictationSettings,
   dictationTranscript = null,
@@ -671,6 +673,7 @@ export const Composer = memo(function Composer({
         dictationEnabled={dictationEnabled}
         dictationState={dictationState}
         dictationLevel={dictationLevel}
+        dictationSpeaking={dictationSpeaking}
         onToggleDictation={onToggleDictation}
         onOpenDictationSettings={onOpenDictationSettings}
         dictationError={dictationError}
@@ -44,6 +44,7 @@ type ComposerInputProps = {
   onSend: () => void;
   dictationState?: "idle" | "listening" | "processing";
   dictationLevel?: number;
+  dictationSpeaking?: boolean;
   dictationEnabled?: boolean;
   onToggleDictation?: () => void;
   onOpenDictationSettings?: () => void;
@@ -146,6 +147,7 @@ export function ComposerInput({
   onSend,
   dictationState = "idle",
   dictationLevel = 0,
+  dictationSpeaking = false,
   dictationEnabled = false,
   onToggleDictation,
   onOpenDictationSettings,
@@ -518,6 +520,7 @@ export function ComposerInput({
             active={isDictating}
             processing={dictationState === "processing"}
             level={dictationLevel}
+            speaking={dictationSpeaking}
           />
         )}
         {dictationError && (
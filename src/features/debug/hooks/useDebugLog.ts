@@ -1,5 +1,6 @@
 import { useCallback, useRef, useState } from "react";
 import type { DebugEntry } from "../../../types";
+import { redactSecrets } from "../../../services/tauri";
 
 const MAX_DEBUG_ENTRIES = 200;
 
@@ -76,7 +77,8 @@ export function useDebugLog() {
       })
       .join("\n\n");
     if (text) {
-      await navigator.clipboard.writeText(text);
+      const redacted = await redactSecrets(text).catch(() => text);
+      await navigator.clipboard.writeText(redacted);
     }
   }, [debugEntries]);
 
@@ -4,6 +4,7 @@ type DictationWaveformProps = {
   active: boolean;
   processing: boolean;
   level: number;
+  speaking?: boolean;
 };
 
 const MAX_BARS = 36;
@@ -20,6 +21,7 @@ export function DictationWaveform({
   active,
   processing,
   level,
+  speaking = false,
 }: DictationWaveformProps) {
   const [bars, setBars] = useState<number[]>(
     () => new Array(MAX_BARS).fill(0),
@@ -47,7 +49,9 @@ export function DictationWaveform({
 
   return (
     <div
-      className={`composer-waveform${processing ? " is-processing" : ""}`}
+      className={`composer-waveform${processing ? " is-processing" : ""}${
+        active && speaking ? " is-speaking" : ""
+      }`}
       aria-hidden
     >
       {processing && <span className="composer-waveform-label">Processing...</span>}
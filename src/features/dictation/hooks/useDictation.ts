@@ -10,10 +10,15 @@ import { subscribeDictationEvents } from "../../../services/events";
 type UseDictationResult = {
   state: DictationSessionState;
   level: number;
+  speaking: boolean;
   transcript: DictationTranscript | null;
+  partial: string | null;
   error: string | null;
   hint: string | null;
-  start: (preferredLanguage: string | null) => Promise<void>;
+  start: (
+    preferredLanguage: string | null,
+    targetId?: string | null,
+  ) => Promise<void>;
   stop: () => Promise<void>;
   cancel: () => Promise<void>;
   clearTranscript: (id: string) => void;
@@ -24,7 +29,9 @@ type UseDictationResult = {
 export function useDictation(): UseDictationResult {
   const [state, setState] = useState<DictationSessionState>("idle");
   const [level, setLevel] = useState(0);
+  const [speaking, setSpeaking] = useState(false);
   const [transcript, setTranscript] = useState<DictationTranscript | null>(null);
+  const [partial, setPartial] = useState<string | null>(null);
   const [error, setError] = useState<string | null>(null);
   const [hint, setHint] = useState<string | null>(null);
   const hintTimeoutRef = useRef<number | null>(null);
@@ -39,6 +46,8 @@ export function useDictation(): UseDictationResult {
         setState(event.state);
         if (event.state === "idle") {
           setLevel(0);
+          setSpeaking(false);
+          setPartial(null);
         }
         return;
       }
@@ -46,10 +55,24 @@ export function useDictation(): UseDictationResult {
         setLevel(event.value);
         return;
       }
-      if (event.type === "transcript") {
+      if (event.type === "voice_activity") {
+        setSpeaking(event.speaking);
+        return;
+      }
+      if (event.type === "auto_stopped") {
+        setSpeaking(false);
+        return;
+      }
+      if (event.type === "partial") {
+        setPartial(event.text);
+        return;
+      }
+      if (event.type === "final") {
+        setPartial(null);
         setTranscript({
           id: `${Date.now()}-${Math.random().toString(36).slice(2, 8)}`,
           text: event.text,
+          targetId: event.targetId ?? null,
         });
         return;
       }
@@ -57,7 +80,10 @@ export function useDictation(): UseDictationResult {
         setError(event.message);
         return;
       }
-      if (event.type === "canceled") {
+      if (event.type === "canceled" || event.type === "warning") {
+        if (event.type === "canceled") {
+          setPartial(null);
+        }
         setHint(event.message);
         if (hintTimeoutRef.current) {
           window.clearTimeout(hintTimeoutRef.current);
@@ -80,11 +106,14 @@ export function useDictation(): UseDictationResult {
     };
   }, []);
 
-  const start = useCallback(async (preferredLanguage: string | null) => {
-    setError(null);
-    setHint(null);
-    await startDictation(preferredLanguage);
-  }, []);
+  const start = useCallback(
+    async (preferredLanguage: string | null, targetId?: string | null) => {
+      setError(null);
+      setHint(null);
+      await startDictation(preferredLanguage, targetId ?? null);
+    },
+    [],
+  );
 
   const stop = useCallback(async () => {
     await stopDictation();
@@ -116,7 +145,9 @@ export function useDictation(): UseDictationResult {
   return {
     state,
     level,
+    speaking,
     transcript,
+    partial,
     error,
     hint,
     start,
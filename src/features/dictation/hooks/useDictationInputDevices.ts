@@ -0,0 +1,30 @@
+import { useCallback, useEffect, useState } from "react";
+import type { DictationInputDevice } from "../../../types";
+import { listDictationInputDevices } from "../../../services/tauri";
+
+type UseDictationInputDevicesResult = {
+  devices: DictationInputDevice[];
+  refresh: () => Promise<void>;
+};
+
+export function useDictationInputDevices(
+  enabled: boolean,
+): UseDictationInputDevicesResult {
+  const [devices, setDevices] = useState<DictationInputDevice[]>([]);
+
+  const refresh = useCallback(async () => {
+    try {
+      setDevices(await listDictationInputDevices());
+    } catch {
+      setDevices([]);
+    }
+  }, []);
+
+  useEffect(() => {
+    if (enabled) {
+      void refresh();
+    }
+  }, [enabled, refresh]);
+
+  return { devices, refresh };
+}
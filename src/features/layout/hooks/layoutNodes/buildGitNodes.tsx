@@ -63,6 +63,7 @@ export function buildGitNodes(options: LayoutNodesOptions): GitLayoutNodes {
         onUpdatePrompt={options.onUpdatePrompt}
         onDeletePrompt={options.onDeletePrompt}
         onMovePrompt={options.onMovePrompt}
+        onDuplicatePrompt={options.onDuplicatePrompt}
         onRevealWorkspacePrompts={options.onRevealWorkspacePrompts}
         onRevealGeneralPrompts={options.onRevealGeneralPrompts}
         canRevealGeneralPrompts={options.canRevealGeneralPrompts}
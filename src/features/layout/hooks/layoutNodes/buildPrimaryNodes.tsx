@@ -171,6 +171,7 @@ export function buildPrimaryNodes(options: LayoutNodesOptions): PrimaryLayoutNod
       dictationEnabled={options.dictationEnabled}
       dictationState={options.dictationState}
       dictationLevel={options.dictationLevel}
+      dictationSpeaking={options.dictationSpeaking}
       onToggleDictation={options.onToggleDictation}
       onOpenDictationSettings={options.onOpenDictationSettings}
       dictationTranscript={options.dictationTranscript}
@@ -364,6 +364,7 @@ export type LayoutNodesOptions = {
   }) => void | Promise<void>;
   onDeletePrompt: (path: string) => void | Promise<void>;
   onMovePrompt: (data: { path: string; scope: "workspace" | "global" }) => void | Promise<void>;
+  onDuplicatePrompt: (path: string) => void | Promise<void>;
   onRevealWorkspacePrompts: () => void | Promise<void>;
   onRevealGeneralPrompts: () => void | Promise<void>;
   canRevealGeneralPrompts: boolean;
@@ -449,6 +450,7 @@ export type LayoutNodesOptions = {
   dictationEnabled: boolean;
   dictationState: DictationSessionState;
   dictationLevel: number;
+  dictationSpeaking: boolean;
   onToggleDictation: () => void;
   dictationTranscript: DictationTranscript | null;
   onDictationTranscriptHandled: (id: string) => void;
@@ -0,0 +1,33 @@
+// @vitest-environment jsdom
+import { fireEvent, render, screen } from "@testing-library/react";
+import { describe, expect, it, vi } from "vitest";
+import { AppLockScreen } from "./AppLockScreen";
+
+describe("AppLockScreen", () => {
+  it("asks to unlock and shows the last failure", () => {
+    const onUnlock = vi.fn();
+
+    render(
+      <AppLockScreen
+        unlocking={false}
+        error="Authentication was canceled."
+        onUnlock={onUnlock}
+      />,
+    );
+
+    expect(screen.getByRole("dialog")).toBeTruthy();
+    expect(screen.getByRole("alert").textContent).toBe(
+      "Authentication was canceled.",
+    );
+    fireEvent.click(screen.getByRole("button", { name: "Unlock" }));
+    expect(onUnlock).toHaveBeenCalledTimes(1);
+  });
+
+  it("disables the button while the OS prompt is open", () => {
+    render(<AppLockScreen unlocking error={null} onUnlock={vi.fn()} />);
+
+    const button = screen.getByRole("button", { name: "Unlocking…" });
+    expect((button as HTMLButtonElement).disabled).toBe(true);
+    expect(screen.queryByRole("alert")).toBeNull();
+  });
+});
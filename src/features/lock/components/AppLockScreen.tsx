@@ -0,0 +1,43 @@
+type AppLockScreenProps = {
+  unlocking: boolean;
+  error: string | null;
+  onUnlock: () => void;
+};
+
+export function AppLockScreen({
+  unlocking,
+  error,
+  onUnlock,
+}: AppLockScreenProps) {
+  return (
+    <div
+      className="app-lock"
+      role="dialog"
+      aria-modal="true"
+      aria-labelledby="app-lock-title"
+    >
+      <div className="drag-strip" data-tauri-drag-region />
+      <div className="app-lock-card">
+        <div className="app-lock-title" id="app-lock-title">
+          CodexMonitor is locked
+        </div>
+        <div className="app-lock-subtitle">
+          Unlock with Touch ID, Windows Hello or your account password.
+        </div>
+        {error ? (
+          <div className="app-lock-error" role="alert">
+            {error}
+          </div>
+        ) : null}
+        <button
+          type="button"
+          className="primary"
+          onClick={onUnlock}
+          disabled={unlocking}
+        >
+          {unlocking ? "Unlocking…" : "Unlock"}
+        </button>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,53 @@
+import { useCallback, useEffect, useState } from "react";
+import type { AppLockStatus } from "@/types";
+import { subscribeAppLock } from "@services/events";
+import { getAppLockStatus, unlockApp } from "@services/tauri";
+import { useTauriEvent } from "../../app/hooks/useTauriEvent";
+
+export function useAppLock() {
+  const [status, setStatus] = useState<AppLockStatus | null>(null);
+  const [unlocking, setUnlocking] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    let cancelled = false;
+    getAppLockStatus()
+      .then((next) => {
+        if (!cancelled) {
+          setStatus(next);
+        }
+      })
+      .catch(() => {
+        // No backend (web preview, tests): nothing to unlock.
+        if (!cancelled) {
+          setStatus({ locked: false, available: false });
+        }
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, []);
+
+  useTauriEvent(subscribeAppLock, (next: AppLockStatus) => {
+    setStatus(next);
+    if (!next.locked) {
+      setError(null);
+    }
+  });
+
+  const unlock = useCallback(async () => {
+    setUnlocking(true);
+    setError(null);
+    try {
+      setStatus(await unlockApp());
+    } catch (unlockError) {
+      setError(
+        unlockError instanceof Error ? unlockError.message : String(unlockError),
+      );
+    } finally {
+      setUnlocking(false);
+    }
+  }, []);
+
+  return { status, unlocking, error, unlock };
+}
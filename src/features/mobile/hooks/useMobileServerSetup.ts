@@ -1,5 +1,9 @@
 import { useCallback, useEffect, useMemo, useState } from "react";
-import { listWorkspaces } from "../../../services/tauri";
+import {
+  REMOTE_BACKEND_TOKEN_KEY,
+  getCredentialStatus,
+  listWorkspaces,
+} from "../../../services/tauri";
 import type { AppSettings } from "../../../types";
 import { isMobilePlatform } from "../../../utils/platformPaths";
 import type { MobileServerSetupWizardProps } from "../components/MobileServerSetupWizard";
@@ -18,9 +22,8 @@ type UseMobileServerSetupResult = {
   handleMobileConnectSuccess: () => Promise<void>;
 };
 
-function isRemoteServerConfigured(settings: AppSettings): boolean {
-  const tokenConfigured = Boolean(settings.remoteBackendToken?.trim());
-  if (!tokenConfigured) {
+function isRemoteServerConfigured(settings: AppSettings, tokenSaved: boolean): boolean {
+  if (!tokenSaved) {
     return false;
   }
   if (settings.remoteBackendProvider === "orbit") {
@@ -49,7 +52,9 @@ export function useMobileServerSetup({
   );
   const [remoteHostDraft, setRemoteHostDraft] = useState(appSettings.remoteBackendHost);
   const [orbitWsUrlDraft, setOrbitWsUrlDraft] = useState(appSettings.orbitWsUrl ?? "");
-  const [remoteTokenDraft, setRemoteTokenDraft] = useState(appSettings.remoteBackendToken ?? "");
+  const [remoteTokenDraft, setRemoteTokenDraft] = useState("");
+  // Null until `getCredentialStatus` answers.
+  const [remoteTokenSaved, setRemoteTokenSaved] = useState<boolean | null>(null);
   const [busy, setBusy] = useState(false);
   const [checking, setChecking] = useState(false);
   const [statusMessage, setStatusMessage] = useState<string | null>(null);
@@ -63,15 +68,31 @@ export function useMobileServerSetup({
     setProviderDraft(appSettings.remoteBackendProvider);
     setRemoteHostDraft(appSettings.remoteBackendHost);
     setOrbitWsUrlDraft(appSettings.orbitWsUrl ?? "");
-    setRemoteTokenDraft(appSettings.remoteBackendToken ?? "");
   }, [
     appSettings.orbitWsUrl,
     appSettings.remoteBackendHost,
     appSettings.remoteBackendProvider,
-    appSettings.remoteBackendToken,
     isMobileRuntime,
   ]);
 
+  useEffect(() => {
+    if (!isMobileRuntime) {
+      return;
+    }
+    let active = true;
+    void getCredentialStatus(REMOTE_BACKEND_TOKEN_KEY)
+      .then((status) => status.exists)
+      .catch(() => false)
+      .then((exists) => {
+        if (active) {
+          setRemoteTokenSaved(exists);
+        }
+      });
+    return () => {
+      active = false;
+    };
+  }, [isMobileRuntime]);
+
   const runConnectivityCheck = useCallback(
     async (options?: { announceSuccess?: boolean }) => {
       if (!isMobileRuntime) {
@@ -118,7 +139,7 @@ export function useMobileServerSetup({
       const nextOrbitWsUrl = orbitWsUrlDraft.trim() ? orbitWsUrlDraft.trim() : null;
       const missingEndpoint = nextProvider === "orbit" ? !nextOrbitWsUrl : !nextHost.trim();
 
-      if (missingEndpoint || !nextToken) {
+      if (missingEndpoint || (!nextToken && !remoteTokenSaved)) {
         setMobileServerReady(false);
         setStatusError(true);
         setStatusMessage(defaultMobileSetupMessage(nextProvider));
@@ -137,6 +158,9 @@ export function useMobileServerSetup({
           remoteBackendToken: nextToken,
           orbitWsUrl: nextOrbitWsUrl,
         });
+        if (nextToken) {
+          setRemoteTokenSaved(true);
+        }
         await runConnectivityCheck({ announceSuccess: true });
       } catch (error) {
         const message =
@@ -157,14 +181,15 @@ export function useMobileServerSetup({
     queueSaveSettings,
     remoteHostDraft,
     remoteTokenDraft,
+    remoteTokenSaved,
     runConnectivityCheck,
   ]);
 
   useEffect(() => {
-    if (!isMobileRuntime || appSettingsLoading || busy) {
+    if (!isMobileRuntime || appSettingsLoading || busy || remoteTokenSaved === null) {
       return;
     }
-    if (!isRemoteServerConfigured(appSettings)) {
+    if (!isRemoteServerConfigured(appSettings, remoteTokenSaved)) {
       setMobileServerReady(false);
       setChecking(false);
       setStatusError(true);
@@ -193,6 +218,7 @@ export function useMobileServerSetup({
     appSettingsLoading,
     busy,
     isMobileRuntime,
+    remoteTokenSaved,
     runConnectivityCheck,
   ]);
 
@@ -45,6 +45,7 @@ type PromptPanelProps = {
   }) => void | Promise<void>;
   onDeletePrompt: (path: string) => void | Promise<void>;
   onMovePrompt: (data: { path: string; scope: "workspace" | "global" }) => void | Promise<void>;
+  onDuplicatePrompt: (path: string) => void | Promise<void>;
   onRevealWorkspacePrompts: () => void | Promise<void>;
   onRevealGeneralPrompts: () => void | Promise<void>;
   canRevealGeneralPrompts: boolean;
@@ -82,6 +83,7 @@ export function PromptPanel({
   onUpdatePrompt,
   onDeletePrompt,
   onMovePrompt,
+  onDuplicatePrompt,
   onRevealWorkspacePrompts,
   onRevealGeneralPrompts,
   canRevealGeneralPrompts,
@@ -288,6 +290,17 @@ export function PromptPanel({
     }
   };
 
+  const handleDuplicate = async (prompt: CustomPromptOption) => {
+    if (!prompt.path) {
+      return;
+    }
+    try {
+      await onDuplicatePrompt(prompt.path);
+    } catch (error) {
+      showError(error);
+    }
+  };
+
   const showPromptMenu = async (
     event: ReactMouseEvent<HTMLButtonElement>,
     prompt: CustomPromptOption,
@@ -302,6 +315,10 @@ export function PromptPanel({
           text: "Edit",
           action: () => startEdit(prompt),
         }),
+        await MenuItem.new({
+          text: "Duplicate",
+          action: () => void handleDuplicate(prompt),
+        }),
         await MenuItem.new({
           text: `Move to ${nextScope === "workspace" ? "workspace" : "general"}`,
           action: () => void handleMove(prompt, nextScope),
@@ -327,7 +344,10 @@ export function PromptPanel({
     return (
       <div className={`prompt-row${isHighlighted ? " is-highlight" : ""}`} key={key}>
         <div className="prompt-row-header">
-          <div className="prompt-name">{prompt.name}</div>
+          <div className="prompt-name">
+            {prompt.folder && <span className="prompt-folder">{prompt.folder}/</span>}
+            {prompt.name}
+          </div>
           {prompt.description && (
             <div className="prompt-description">{prompt.description}</div>
           )}
@@ -8,6 +8,7 @@ import { useCustomPrompts } from "./useCustomPrompts";
 vi.mock("../../../services/tauri", () => ({
   createPrompt: vi.fn(),
   deletePrompt: vi.fn(),
+  duplicatePrompt: vi.fn(),
   getPromptsList: vi.fn(),
   getGlobalPromptsDir: vi.fn(),
   getWorkspacePromptsDir: vi.fn(),
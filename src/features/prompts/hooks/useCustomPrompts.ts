@@ -3,6 +3,7 @@ import type { CustomPromptOption, DebugEntry, WorkspaceInfo } from "../../../typ
 import {
   createPrompt as createPromptService,
   deletePrompt as deletePromptService,
+  duplicatePrompt as duplicatePromptService,
   getPromptsList,
   getGlobalPromptsDir as getGlobalPromptsDirService,
   getWorkspacePromptsDir as getWorkspacePromptsDirService,
@@ -92,6 +93,10 @@ export function useCustomPrompts({ activeWorkspace, onDebug }: UseCustomPromptsO
           argumentHint,
           content: String(item.content ?? ""),
           scope,
+          folder: item.folder ? String(item.folder) : undefined,
+          variables: Array.isArray(item.variables)
+            ? item.variables.map((name: unknown) => String(name))
+            : undefined,
         };
       });
       setPrompts(data);
@@ -193,6 +198,20 @@ export function useCustomPrompts({ activeWorkspace, onDebug }: UseCustomPromptsO
     [logPromptError, refreshPrompts, requireWorkspaceId],
   );
 
+  const duplicatePrompt = useCallback(
+    async (path: string) => {
+      const id = requireWorkspaceId();
+      try {
+        await duplicatePromptService(id, path);
+        await refreshPrompts();
+      } catch (error) {
+        logPromptError("client-prompts-duplicate-error", "prompts/duplicate error", error);
+        throw error;
+      }
+    },
+    [logPromptError, refreshPrompts, requireWorkspaceId],
+  );
+
   const getWorkspacePromptsDir = useCallback(async () => {
     const id = requireWorkspaceId();
     try {
@@ -222,6 +241,7 @@ export function useCustomPrompts({ activeWorkspace, onDebug }: UseCustomPromptsO
     updatePrompt,
     deletePrompt,
     movePrompt,
+    duplicatePrompt,
     getWorkspacePromptsDir,
     getGlobalPromptsDir,
   };
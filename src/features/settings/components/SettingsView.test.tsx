@@ -11,7 +11,11 @@ import {
 import type { ComponentProps } from "react";
 import { describe, expect, it, vi } from "vitest";
 import type { AppSettings, WorkspaceInfo } from "@/types";
-import { getExperimentalFeatureList, getModelList } from "@services/tauri";
+import {
+  getCredentialStatus,
+  getExperimentalFeatureList,
+  getModelList,
+} from "@services/tauri";
 import { DEFAULT_COMMIT_MESSAGE_PROMPT } from "@utils/commitMessagePrompt";
 import { SettingsView } from "./SettingsView";
 
@@ -28,11 +32,13 @@ vi.mock("@services/tauri", async () => {
     ...actual,
     getModelList: vi.fn(),
     getExperimentalFeatureList: vi.fn(),
+    getCredentialStatus: vi.fn(),
   };
 });
 
 const getModelListMock = vi.mocked(getModelList);
 const getExperimentalFeatureListMock = vi.mocked(getExperimentalFeatureList);
+const getCredentialStatusMock = vi.mocked(getCredentialStatus);
 
 const baseSettings: AppSettings = {
   codexBin: null,
@@ -99,7 +105,10 @@ const baseSettings: AppSettings = {
   dictationEnabled: false,
   dictationModelId: "base",
   dictationPreferredLanguage: null,
+  dictationInputDevice: null,
+  dictationAutoStopSilenceMs: null,
   dictationHoldKey: null,
+  dictationSaveAudio: false,
   composerEditorPreset: "default",
   composerFenceExpandOnSpace: false,
   composerFenceExpandOnEnter: false,
@@ -121,6 +130,18 @@ const baseSettings: AppSettings = {
     },
   ],
   selectedOpenAppId: "vscode",
+  httpProxy: null,
+  httpsProxy: null,
+  noProxy: null,
+  customCaCertPath: null,
+  customCaHosts: [],
+  requireUnlockOnLaunch: false,
+  autoLockIdleSecs: null,
+  redactSecrets: true,
+  secretPatterns: [],
+  codexProfiles: [],
+  eventBatchIntervalMs: 0,
+  autoRespawnSessions: false,
 };
 
 const createDoctorResult = () => ({
@@ -880,6 +901,57 @@ describe("SettingsView Codex overrides", () => {
     });
   });
 
+  it("shows a saved remote backend token without its value", async () => {
+    cleanup();
+    getCredentialStatusMock.mockResolvedValueOnce({
+      key: "remoteBackendToken",
+      exists: true,
+    });
+    render(
+      <SettingsView
+        workspaceGroups={[]}
+        groupedWorkspaces={[]}
+        ungroupedLabel="Ungrouped"
+        onClose={vi.fn()}
+        onMoveWorkspace={vi.fn()}
+        onDeleteWorkspace={vi.fn()}
+        onCreateWorkspaceGroup={vi.fn().mockResolvedValue(null)}
+        onRenameWorkspaceGroup={vi.fn().mockResolvedValue(null)}
+        onMoveWorkspaceGroup={vi.fn().mockResolvedValue(null)}
+        onDeleteWorkspaceGroup={vi.fn().mockResolvedValue(null)}
+        onAssignWorkspaceGroup={vi.fn().mockResolvedValue(null)}
+        reduceTransparency={false}
+        onToggleTransparency={vi.fn()}
+        appSettings={{
+          ...baseSettings,
+          backendMode: "local",
+          remoteBackendProvider: "tcp",
+        }}
+        openAppIconById={{}}
+        onUpdateAppSettings={vi.fn().mockResolvedValue(undefined)}
+        onRunDoctor={vi.fn().mockResolvedValue(createDoctorResult())}
+        onUpdateWorkspaceCodexBin={vi.fn().mockResolvedValue(undefined)}
+        onUpdateWorkspaceSettings={vi.fn().mockResolvedValue(undefined)}
+        scaleShortcutTitle="Scale shortcut"
+        scaleShortcutText="Use Command +/-"
+        onTestNotificationSound={vi.fn()}
+        onTestSystemNotification={vi.fn()}
+        dictationModelStatus={null}
+        onDownloadDictationModel={vi.fn()}
+        onCancelDictationDownload={vi.fn()}
+        onRemoveDictationModel={vi.fn()}
+        initialSection="server"
+      />,
+    );
+
+    await waitFor(() => {
+      const token = screen.getByLabelText("Remote backend token") as HTMLInputElement;
+      expect(token.placeholder).toBe("Token saved");
+      expect(token.value).toBe("");
+    });
+    expect(getCredentialStatusMock).toHaveBeenCalledWith("remoteBackendToken");
+  });
+
   it("shows mobile-only server controls on iOS runtime", async () => {
     cleanup();
     const originalPlatformDescriptor = Object.getOwnPropertyDescriptor(
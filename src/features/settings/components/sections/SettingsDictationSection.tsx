@@ -1,6 +1,18 @@
-import type { AppSettings, DictationModelStatus } from "@/types";
+import type {
+  AppSettings,
+  DictationInputDevice,
+  DictationModelStatus,
+} from "@/types";
 import { formatDownloadSize } from "@utils/formatting";
 
+const AUTO_STOP_OPTIONS = [
+  { value: "", label: "Off" },
+  { value: "1000", label: "After 1 second" },
+  { value: "2000", label: "After 2 seconds" },
+  { value: "3000", label: "After 3 seconds" },
+  { value: "5000", label: "After 5 seconds" },
+];
+
 type DictationModelOption = {
   id: string;
   label: string;
@@ -16,6 +28,8 @@ type SettingsDictationSectionProps = {
   selectedDictationModel: DictationModelOption;
   dictationModelStatus?: DictationModelStatus | null;
   dictationReady: boolean;
+  dictationInputDevices: DictationInputDevice[];
+  onRefreshDictationInputDevices: () => void;
   onUpdateAppSettings: (next: AppSettings) => Promise<void>;
   onDownloadDictationModel?: () => void;
   onCancelDictationDownload?: () => void;
@@ -30,12 +44,18 @@ export function SettingsDictationSection({
   selectedDictationModel,
   dictationModelStatus,
   dictationReady,
+  dictationInputDevices,
+  onRefreshDictationInputDevices,
   onUpdateAppSettings,
   onDownloadDictationModel,
   onCancelDictationDownload,
   onRemoveDictationModel,
 }: SettingsDictationSectionProps) {
   const dictationProgress = dictationModelStatus?.progress ?? null;
+  const selectedInputDevice = appSettings.dictationInputDevice;
+  const selectedInputDeviceMissing =
+    selectedInputDevice != null &&
+    !dictationInputDevices.some((device) => device.id === selectedInputDevice);
 
   return (
     <section className="settings-section">
@@ -68,7 +88,8 @@ export function SettingsDictationSection({
             }
             if (
               nextEnabled &&
-              dictationModelStatus?.state === "missing" &&
+              (dictationModelStatus?.state === "missing" ||
+                dictationModelStatus?.state === "corrupt") &&
               onDownloadDictationModel
             ) {
               onDownloadDictationModel();
@@ -143,6 +164,74 @@ export function SettingsDictationSection({
           Auto-detect stays on; this nudges the decoder toward your preference.
         </div>
       </div>
+      <div className="settings-field">
+        <label className="settings-field-label" htmlFor="dictation-input-device">
+          Microphone
+        </label>
+        <select
+          id="dictation-input-device"
+          className="settings-select"
+          value={selectedInputDevice ?? ""}
+          onFocus={() => onRefreshDictationInputDevices()}
+          onChange={(event) =>
+            void onUpdateAppSettings({
+              ...appSettings,
+              dictationInputDevice: event.target.value || null,
+            })
+          }
+        >
+          <option value="">System default</option>
+          {dictationInputDevices.map((device) => (
+            <option key={device.id} value={device.id}>
+              {device.name}
+              {device.isDefault ? " (default)" : ""}
+            </option>
+          ))}
+          {selectedInputDeviceMissing ? (
+            <option value={selectedInputDevice}>
+              {selectedInputDevice} (unavailable)
+            </option>
+          ) : null}
+        </select>
+        <div className="settings-help">
+          Falls back to the system default when this microphone is unplugged.
+        </div>
+      </div>
+      <div className="settings-field">
+        <label className="settings-field-label" htmlFor="dictation-auto-stop">
+          Stop on silence
+        </label>
+        <select
+          id="dictation-auto-stop"
+          className="settings-select"
+          value={appSettings.dictationAutoStopSilenceMs?.toString() ?? ""}
+          onChange={(event) =>
+            void onUpdateAppSettings({
+              ...appSettings,
+              dictationAutoStopSilenceMs: event.target.value
+                ? Number(event.target.value)
+                : null,
+            })
+          }
+        >
+          {AUTO_STOP_OPTIONS.map((option) => (
+            <option key={option.value} value={option.value}>
+              {option.label}
+            </option>
+          ))}
+          {appSettings.dictationAutoStopSilenceMs != null &&
+          !AUTO_STOP_OPTIONS.some(
+            (option) => option.value === appSettings.dictationAutoStopSilenceMs?.toString(),
+          ) ? (
+            <option value={appSettings.dictationAutoStopSilenceMs.toString()}>
+              After {appSettings.dictationAutoStopSilenceMs} ms
+            </option>
+          ) : null}
+        </select>
+        <div className="settings-help">
+          Finishes dictation once you stop talking. Pauses shorter than this are ignored.
+        </div>
+      </div>
       <div className="settings-field">
         <label className="settings-field-label" htmlFor="dictation-hold-key">
           Hold-to-dictate key
@@ -168,6 +257,28 @@ export function SettingsDictationSection({
           Hold the key to start dictation, release to stop and process.
         </div>
       </div>
+      <div className="settings-toggle-row">
+        <div>
+          <div className="settings-toggle-title">Save dictation audio</div>
+          <div className="settings-toggle-subtitle">
+            Keeps each recording as a WAV file for debugging transcription.
+            Recordings contain your voice and stay on this device.
+          </div>
+        </div>
+        <button
+          type="button"
+          className={`settings-toggle ${appSettings.dictationSaveAudio ? "on" : ""}`}
+          onClick={() =>
+            void onUpdateAppSettings({
+              ...appSettings,
+              dictationSaveAudio: !appSettings.dictationSaveAudio,
+            })
+          }
+          aria-pressed={appSettings.dictationSaveAudio}
+        >
+          <span className="settings-toggle-knob" />
+        </button>
+      </div>
       {dictationModelStatus && (
         <div className="settings-field">
           <div className="settings-field-label">Model status ({selectedDictationModel.label})</div>
@@ -175,6 +286,8 @@ export function SettingsDictationSection({
             {dictationModelStatus.state === "ready" && "Ready for dictation."}
             {dictationModelStatus.state === "missing" && "Model not downloaded yet."}
             {dictationModelStatus.state === "downloading" && "Downloading model..."}
+            {dictationModelStatus.state === "corrupt" &&
+              (dictationModelStatus.error ?? "The model file is damaged.")}
             {dictationModelStatus.state === "error" &&
               (dictationModelStatus.error ?? "Download error.")}
           </div>
@@ -209,6 +322,17 @@ export function SettingsDictationSection({
                 Download model
               </button>
             )}
+            {(dictationModelStatus.state === "corrupt" ||
+              dictationModelStatus.state === "error") && (
+              <button
+                type="button"
+                className="primary"
+                onClick={onDownloadDictationModel}
+                disabled={!onDownloadDictationModel}
+              >
+                {dictationModelStatus.state === "corrupt" ? "Re-download model" : "Retry download"}
+              </button>
+            )}
             {dictationModelStatus.state === "downloading" && (
               <button
                 type="button"
@@ -15,6 +15,7 @@ type SettingsServerSectionProps = {
   mobileConnectStatusError: boolean;
   remoteHostDraft: string;
   remoteTokenDraft: string;
+  remoteTokenSaved: boolean;
   orbitWsUrlDraft: string;
   orbitAuthUrlDraft: string;
   orbitRunnerNameDraft: string;
@@ -71,6 +72,7 @@ export function SettingsServerSection({
   mobileConnectStatusError,
   remoteHostDraft,
   remoteTokenDraft,
+  remoteTokenSaved,
   orbitWsUrlDraft,
   orbitAuthUrlDraft,
   orbitRunnerNameDraft,
@@ -243,7 +245,7 @@ export function SettingsServerSection({
                   type="password"
                   className="settings-input settings-input--compact"
                   value={remoteTokenDraft}
-                  placeholder="Token (required)"
+                  placeholder={remoteTokenSaved ? "Token saved" : "Token (required)"}
                   onChange={(event) => onSetRemoteTokenDraft(event.target.value)}
                   onBlur={() => {
                     void onCommitRemoteToken();
@@ -452,7 +454,7 @@ export function SettingsServerSection({
                     type="password"
                     className="settings-input settings-input--compact"
                     value={remoteTokenDraft}
-                    placeholder="Token (required)"
+                    placeholder={remoteTokenSaved ? "Token saved" : "Token (required)"}
                     onChange={(event) => onSetRemoteTokenDraft(event.target.value)}
                     onBlur={() => {
                       void onCommitRemoteToken();
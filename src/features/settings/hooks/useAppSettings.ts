@@ -90,7 +90,10 @@ function buildDefaultSettings(): AppSettings {
     dictationEnabled: false,
     dictationModelId: "base",
     dictationPreferredLanguage: null,
+    dictationInputDevice: null,
+    dictationAutoStopSilenceMs: null,
     dictationHoldKey: "alt",
+    dictationSaveAudio: false,
     composerEditorPreset: "default",
     composerFenceExpandOnSpace: false,
     composerFenceExpandOnEnter: false,
@@ -103,6 +106,18 @@ function buildDefaultSettings(): AppSettings {
     workspaceGroups: [],
     openAppTargets: DEFAULT_OPEN_APP_TARGETS,
     selectedOpenAppId: DEFAULT_OPEN_APP_ID,
+    httpProxy: null,
+    httpsProxy: null,
+    noProxy: null,
+    customCaCertPath: null,
+    customCaHosts: [],
+    requireUnlockOnLaunch: false,
+    autoLockIdleSecs: null,
+    redactSecrets: true,
+    secretPatterns: [],
+    codexProfiles: [],
+    eventBatchIntervalMs: 0,
+    autoRespawnSessions: false,
   };
 }
 
@@ -7,6 +7,8 @@ import type {
   TcpDaemonStatus,
 } from "@/types";
 import {
+  REMOTE_BACKEND_TOKEN_KEY,
+  getCredentialStatus,
   listWorkspaces,
   tailscaleDaemonCommandPreview as fetchTailscaleDaemonCommandPreview,
   tailscaleDaemonStart,
@@ -44,6 +46,7 @@ export type SettingsServerSectionProps = {
   mobileConnectStatusError: boolean;
   remoteHostDraft: string;
   remoteTokenDraft: string;
+  remoteTokenSaved: boolean;
   orbitWsUrlDraft: string;
   orbitAuthUrlDraft: string;
   orbitRunnerNameDraft: string;
@@ -114,7 +117,8 @@ export const useSettingsServerSection = ({
   orbitServiceClient,
 }: UseSettingsServerSectionArgs): SettingsServerSectionProps => {
   const [remoteHostDraft, setRemoteHostDraft] = useState(appSettings.remoteBackendHost);
-  const [remoteTokenDraft, setRemoteTokenDraft] = useState(appSettings.remoteBackendToken ?? "");
+  const [remoteTokenDraft, setRemoteTokenDraft] = useState("");
+  const [remoteTokenSaved, setRemoteTokenSaved] = useState(false);
   const [orbitWsUrlDraft, setOrbitWsUrlDraft] = useState(appSettings.orbitWsUrl ?? "");
   const [orbitAuthUrlDraft, setOrbitAuthUrlDraft] = useState(appSettings.orbitAuthUrl ?? "");
   const [orbitRunnerNameDraft, setOrbitRunnerNameDraft] = useState(
@@ -159,9 +163,18 @@ export const useSettingsServerSection = ({
     setRemoteHostDraft(appSettings.remoteBackendHost);
   }, [appSettings.remoteBackendHost]);
 
+  const refreshRemoteTokenSaved = useCallback(async () => {
+    try {
+      const status = await getCredentialStatus(REMOTE_BACKEND_TOKEN_KEY);
+      setRemoteTokenSaved(status.exists);
+    } catch {
+      setRemoteTokenSaved(false);
+    }
+  }, []);
+
   useEffect(() => {
-    setRemoteTokenDraft(appSettings.remoteBackendToken ?? "");
-  }, [appSettings.remoteBackendToken]);
+    void refreshRemoteTokenSaved();
+  }, [refreshRemoteTokenSaved]);
 
   useEffect(() => {
     setOrbitWsUrlDraft(appSettings.orbitWsUrl ?? "");
@@ -225,8 +238,11 @@ export const useSettingsServerSection = ({
       }
       await onUpdateAppSettings(nextSettings);
       latestSettingsRef.current = nextSettings;
+      if (nextToken) {
+        await refreshRemoteTokenSaved();
+      }
     },
-    [mobilePlatform, onUpdateAppSettings],
+    [mobilePlatform, onUpdateAppSettings, refreshRemoteTokenSaved],
   );
 
   const applyRemoteHost = async (rawValue: string) => {
@@ -242,6 +258,10 @@ export const useSettingsServerSection = ({
   const handleCommitRemoteToken = async () => {
     const nextToken = remoteTokenDraft.trim() ? remoteTokenDraft.trim() : null;
     setRemoteTokenDraft(nextToken ?? "");
+    if (!nextToken) {
+      // An empty field keeps the saved token.
+      return;
+    }
     await updateRemoteBackendSettings({ token: nextToken });
   };
 
@@ -491,6 +511,7 @@ export const useSettingsServerSection = ({
       ...nextSettings,
     });
     latestSettingsRef.current = nextSettings;
+    await refreshRemoteTokenSaved();
   };
 
   const handleOrbitConnectTest = () => {
@@ -625,11 +646,11 @@ export const useSettingsServerSection = ({
     }
   }, [
     appSettings.remoteBackendProvider,
-    appSettings.remoteBackendToken,
     handleRefreshTailscaleCommandPreview,
     handleRefreshTailscaleStatus,
     handleTcpDaemonStatus,
     mobilePlatform,
+    remoteTokenSaved,
     tailscaleStatus,
     tailscaleStatusBusy,
     tailscaleStatusError,
@@ -640,6 +661,7 @@ export const useSettingsServerSection = ({
     onUpdateAppSettings,
     remoteHostDraft,
     remoteTokenDraft,
+    remoteTokenSaved,
     orbitWsUrlDraft,
     orbitAuthUrlDraft,
     orbitRunnerNameDraft,
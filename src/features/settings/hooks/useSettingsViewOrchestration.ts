@@ -8,6 +8,7 @@ import type {
   WorkspaceSettings,
 } from "@/types";
 import { isMacPlatform, isWindowsPlatform } from "@utils/platformPaths";
+import { useDictationInputDevices } from "../../dictation/hooks/useDictationInputDevices";
 import { useSettingsOpenAppDrafts } from "./useSettingsOpenAppDrafts";
 import { useSettingsShortcutDrafts } from "./useSettingsShortcutDrafts";
 import { useSettingsCodexSection } from "./useSettingsCodexSection";
@@ -133,6 +134,10 @@ export function useSettingsViewOrchestration({
   }, [appSettings.dictationModelId]);
 
   const dictationReady = dictationModelStatus?.state === "ready";
+  const {
+    devices: dictationInputDevices,
+    refresh: refreshDictationInputDevices,
+  } = useDictationInputDevices(appSettings.dictationEnabled);
 
   const {
     openAppDrafts,
@@ -244,6 +249,8 @@ export function useSettingsViewOrchestration({
       selectedDictationModel,
       dictationModelStatus,
       dictationReady,
+      dictationInputDevices,
+      onRefreshDictationInputDevices: refreshDictationInputDevices,
       onUpdateAppSettings,
       onDownloadDictationModel,
       onCancelDictationDownload,
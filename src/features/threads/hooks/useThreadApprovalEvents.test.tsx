@@ -1,8 +1,11 @@
 // @vitest-environment jsdom
-import { act, renderHook } from "@testing-library/react";
+import { act, renderHook, waitFor } from "@testing-library/react";
 import { beforeEach, describe, expect, it, vi } from "vitest";
 import type { ApprovalRequest } from "@/types";
-import { respondToServerRequest } from "@services/tauri";
+import {
+  acceptServerRequestIfAllowed,
+  respondToServerRequest,
+} from "@services/tauri";
 import {
   getApprovalCommandInfo,
   matchesCommandPrefix,
@@ -10,6 +13,7 @@ import {
 import { useThreadApprovalEvents } from "./useThreadApprovalEvents";
 
 vi.mock("@services/tauri", () => ({
+  acceptServerRequestIfAllowed: vi.fn(),
   respondToServerRequest: vi.fn(),
 }));
 
@@ -53,7 +57,7 @@ describe("useThreadApprovalEvents", () => {
     expect(dispatch).not.toHaveBeenCalled();
   });
 
-  it("dispatches approvals that do not match the allowlist", () => {
+  it("dispatches approvals that do not match the allowlist", async () => {
     const dispatch = vi.fn();
     const approvalAllowlistRef = {
       current: { "ws-1": [["git", "status"]] },
@@ -70,6 +74,7 @@ describe("useThreadApprovalEvents", () => {
       preview: "git pull",
     });
     vi.mocked(matchesCommandPrefix).mockReturnValue(false);
+    vi.mocked(acceptServerRequestIfAllowed).mockResolvedValue(false);
 
     const { result } = renderHook(() =>
       useThreadApprovalEvents({ dispatch, approvalAllowlistRef }),
@@ -79,7 +84,49 @@ describe("useThreadApprovalEvents", () => {
       result.current(approval);
     });
 
+    await waitFor(() =>
+      expect(dispatch).toHaveBeenCalledWith({ type: "addApproval", approval }),
+    );
+    expect(acceptServerRequestIfAllowed).toHaveBeenCalledWith("ws-1", 7, [
+      "git",
+      "pull",
+    ]);
     expect(respondToServerRequest).not.toHaveBeenCalled();
-    expect(dispatch).toHaveBeenCalledWith({ type: "addApproval", approval });
+  });
+
+  it("auto-accepts approvals covered by a backend glob rule", async () => {
+    const dispatch = vi.fn();
+    const approvalAllowlistRef = { current: {} };
+    const approval: ApprovalRequest = {
+      workspace_id: "ws-1",
+      request_id: 9,
+      method: "approval/request",
+      params: { command: "cargo test foo" },
+    };
+
+    vi.mocked(getApprovalCommandInfo).mockReturnValue({
+      tokens: ["cargo", "test", "foo"],
+      preview: "cargo test foo",
+    });
+    vi.mocked(matchesCommandPrefix).mockReturnValue(false);
+    vi.mocked(acceptServerRequestIfAllowed).mockResolvedValue(true);
+
+    const { result } = renderHook(() =>
+      useThreadApprovalEvents({ dispatch, approvalAllowlistRef }),
+    );
+
+    act(() => {
+      result.current(approval);
+    });
+
+    await waitFor(() =>
+      expect(acceptServerRequestIfAllowed).toHaveBeenCalledWith("ws-1", 9, [
+        "cargo",
+        "test",
+        "foo",
+      ]),
+    );
+    expect(respondToServerRequest).not.toHaveBeenCalled();
+    expect(dispatch).not.toHaveBeenCalled();
   });
 });
@@ -5,7 +5,10 @@ import {
   getApprovalCommandInfo,
   matchesCommandPrefix,
 } from "@utils/approvalRules";
-import { respondToServerRequest } from "@services/tauri";
+import {
+  acceptServerRequestIfAllowed,
+  respondToServerRequest,
+} from "@services/tauri";
 import type { ThreadAction } from "./useThreadsReducer";
 
 type UseThreadApprovalEventsOptions = {
@@ -30,7 +33,23 @@ export function useThreadApprovalEvents({
         );
         return;
       }
-      dispatch({ type: "addApproval", approval });
+      if (!commandInfo) {
+        dispatch({ type: "addApproval", approval });
+        return;
+      }
+      // Glob and regex rules are only known to the backend, which answers
+      // the request itself when one of them covers the command.
+      void acceptServerRequestIfAllowed(
+        approval.workspace_id,
+        approval.request_id,
+        commandInfo.tokens,
+      )
+        .catch(() => false)
+        .then((accepted) => {
+          if (!accepted) {
+            dispatch({ type: "addApproval", approval });
+          }
+        });
     },
     [approvalAllowlistRef, dispatch],
   );
@@ -29,10 +29,11 @@ export type UpdateState = {
 
 type UseUpdaterOptions = {
   enabled?: boolean;
+  proxy?: string | null;
   onDebug?: (entry: DebugEntry) => void;
 };
 
-export function useUpdater({ enabled = true, onDebug }: UseUpdaterOptions) {
+export function useUpdater({ enabled = true, proxy, onDebug }: UseUpdaterOptions) {
   const [state, setState] = useState<UpdateState>({ stage: "idle" });
   const updateRef = useRef<Update | null>(null);
   const latestTimeoutRef = useRef<number | null>(null);
@@ -61,7 +62,7 @@ export function useUpdater({ enabled = true, onDebug }: UseUpdaterOptions) {
     try {
       clearLatestTimeout();
       setState({ stage: "checking" });
-      update = await check();
+      update = await check(proxy ? { proxy } : undefined);
       if (!update) {
         if (options?.announceNoUpdate) {
           setState({ stage: "latest" });
@@ -96,7 +97,7 @@ export function useUpdater({ enabled = true, onDebug }: UseUpdaterOptions) {
         await update?.close();
       }
     }
-  }, [clearLatestTimeout, enabled, onDebug]);
+  }, [clearLatestTimeout, enabled, onDebug, proxy]);
 
   const startUpdate = useCallback(async () => {
     if (!enabled) {
@@ -79,6 +79,7 @@ type WorkspaceHomeProps = {
   dictationEnabled: boolean;
   dictationState: DictationSessionState;
   dictationLevel: number;
+  dictationSpeaking: boolean;
   onToggleDictation: () => void;
   onOpenDictationSettings: () => void;
   dictationError: string | null;
@@ -141,6 +142,7 @@ export function WorkspaceHome({
   dictationEnabled,
   dictationState,
   dictationLevel,
+  dictationSpeaking,
   onToggleDictation,
   onOpenDictationSettings,
   dictationError,
@@ -388,6 +390,7 @@ export function WorkspaceHome({
             }}
             dictationState={dictationState}
             dictationLevel={dictationLevel}
+            dictationSpeaking={dictationSpeaking}
             dictationEnabled={dictationEnabled}
             onToggleDictation={onToggleDictation}
             onOpenDictationSettings={onOpenDictationSettings}
@@ -405,6 +405,7 @@ export function useWorkspaces(options: UseWorkspacesOptions = {}) {
       activate?: boolean;
       displayName?: string | null;
       copyAgentsMd?: boolean;
+      base?: string | null;
     },
   ) {
     const trimmed = branch.trim();
@@ -413,6 +414,7 @@ export function useWorkspaces(options: UseWorkspacesOptions = {}) {
     }
     const trimmedName = options?.displayName?.trim() || null;
     const copyAgentsMd = options?.copyAgentsMd ?? true;
+    const base = options?.base?.trim() || null;
     onDebug?.({
       id: `${Date.now()}-client-add-worktree`,
       timestamp: Date.now(),
@@ -423,6 +425,7 @@ export function useWorkspaces(options: UseWorkspacesOptions = {}) {
         branch: trimmed,
         name: trimmedName,
         copyAgentsMd,
+        base,
       },
     });
     try {
@@ -431,6 +434,7 @@ export function useWorkspaces(options: UseWorkspacesOptions = {}) {
         trimmed,
         trimmedName,
         copyAgentsMd,
+        base,
       );
       setWorkspaces((prev) => [...prev, workspace]);
       if (options?.activate !== false) {
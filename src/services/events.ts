@@ -1,5 +1,18 @@
 import { listen } from "@tauri-apps/api/event";
-import type { AppServerEvent, DictationEvent, DictationModelStatus } from "../types";
+import type {
+  AppLockStatus,
+  AppServerEvent,
+  CloneProgressEvent,
+  DictationEvent,
+  DictationModelStatus,
+  GitSyncProgressEvent,
+  MessageQueuedEvent,
+  NetworkStatus,
+  OperationEvent,
+  ProfileLoginEvent,
+  RemoteUpdatesEvent,
+  SessionCrashedEvent,
+} from "../types";
 
 export type Unsubscribe = () => void;
 
@@ -86,6 +99,16 @@ const dictationDownloadHub = createEventHub<DictationModelStatus>("dictation-dow
 const dictationEventHub = createEventHub<DictationEvent>("dictation-event");
 const terminalOutputHub = createEventHub<TerminalOutputEvent>("terminal-output");
 const terminalExitHub = createEventHub<TerminalExitEvent>("terminal-exit");
+const operationEventHub = createEventHub<OperationEvent>("operation-event");
+const remoteUpdatesHub = createEventHub<RemoteUpdatesEvent>("remote-updates-available");
+const networkStatusHub = createEventHub<NetworkStatus>("network-status-changed");
+const cloneProgressHub = createEventHub<CloneProgressEvent>("clone-progress");
+const gitSyncProgressHub =
+  createEventHub<GitSyncProgressEvent>("git-sync-progress");
+const profileLoginHub = createEventHub<ProfileLoginEvent>("profile-login");
+const messageQueuedHub = createEventHub<MessageQueuedEvent>("message-queued");
+const sessionCrashedHub = createEventHub<SessionCrashedEvent>("session-crashed");
+const appLockHub = createEventHub<AppLockStatus>("app-lock-changed");
 const updaterCheckHub = createEventHub<void>("updater-check");
 const menuNewAgentHub = createEventHub<void>("menu-new-agent");
 const menuNewWorktreeAgentHub = createEventHub<void>("menu-new-worktree-agent");
@@ -146,6 +169,69 @@ export function subscribeTerminalExit(
   return terminalExitHub.subscribe(onEvent, options);
 }
 
+export function subscribeOperationEvents(
+  onEvent: (event: OperationEvent) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return operationEventHub.subscribe(onEvent, options);
+}
+
+export function subscribeRemoteUpdates(
+  onEvent: (event: RemoteUpdatesEvent) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return remoteUpdatesHub.subscribe(onEvent, options);
+}
+
+export function subscribeNetworkStatus(
+  onEvent: (event: NetworkStatus) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return networkStatusHub.subscribe(onEvent, options);
+}
+
+export function subscribeCloneProgress(
+  onEvent: (event: CloneProgressEvent) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return cloneProgressHub.subscribe(onEvent, options);
+}
+
+export function subscribeGitSyncProgress(
+  onEvent: (event: GitSyncProgressEvent) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return gitSyncProgressHub.subscribe(onEvent, options);
+}
+
+export function subscribeProfileLogin(
+  onEvent: (event: ProfileLoginEvent) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return profileLoginHub.subscribe(onEvent, options);
+}
+
+export function subscribeMessageQueued(
+  onEvent: (event: MessageQueuedEvent) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return messageQueuedHub.subscribe(onEvent, options);
+}
+
+export function subscribeSessionCrashed(
+  onEvent: (event: SessionCrashedEvent) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return sessionCrashedHub.subscribe(onEvent, options);
+}
+
+export function subscribeAppLock(
+  onEvent: (event: AppLockStatus) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return appLockHub.subscribe(onEvent, options);
+}
+
 export function subscribeUpdaterCheck(
   onEvent: () => void,
   options?: SubscriptionOptions,
@@ -4,8 +4,11 @@ import { open } from "@tauri-apps/plugin-dialog";
 import * as notification from "@tauri-apps/plugin-notification";
 import {
   addWorkspace,
+  cancelOperation,
+  checkRemoteUpdates,
   compactThread,
   createGitHubRepo,
+  estimateOperationTime,
   fetchGit,
   forkThread,
   getAppsList,
@@ -164,6 +167,7 @@ describe("tauri invoke wrappers", () => {
     invokeMock.mockResolvedValueOnce({
       total: 0,
       entries: [],
+      hasMore: false,
       ahead: 0,
       behind: 0,
       aheadEntries: [],
@@ -175,7 +179,10 @@ describe("tauri invoke wrappers", () => {
 
     expect(invokeMock).toHaveBeenCalledWith("get_git_log", {
       workspaceId: "ws-3",
-      limit: 40,
+      skip: 0,
+      limit: 100,
+      path: null,
+      filter: null,
     });
   });
 
@@ -290,6 +297,64 @@ describe("tauri invoke wrappers", () => {
     });
   });
 
+  it("invokes check_remote_updates", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({ hasUpdates: false });
+
+    await checkRemoteUpdates("ws-7");
+
+    expect(invokeMock).toHaveBeenCalledWith("check_remote_updates", {
+      workspaceId: "ws-7",
+    });
+  });
+
+  it("maps kind and size hint for estimate_operation_time", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({});
+
+    await estimateOperationTime("clone");
+
+    expect(invokeMock).toHaveBeenCalledWith("estimate_operation_time", {
+      kind: "clone",
+      repoSizeHint: null,
+    });
+  });
+
+  it("passes operationId through fetch_git when provided", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({});
+
+    await fetchGit("ws-7", "op-1");
+
+    expect(invokeMock).toHaveBeenCalledWith("fetch_git", {
+      workspaceId: "ws-7",
+      operationId: "op-1",
+    });
+  });
+
+  it("passes priority through get_git_status when provided", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({});
+
+    await getGitStatus("ws-7", "background");
+
+    expect(invokeMock).toHaveBeenCalledWith("get_git_status", {
+      workspaceId: "ws-7",
+      priority: "background",
+    });
+  });
+
+  it("invokes cancel_operation", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce(true);
+
+    await expect(cancelOperation("op-1")).resolves.toBe(true);
+
+    expect(invokeMock).toHaveBeenCalledWith("cancel_operation", {
+      operationId: "op-1",
+    });
+  });
+
   it("maps openWorkspaceIn options", async () => {
     const invokeMock = vi.mocked(invoke);
     invokeMock.mockResolvedValueOnce({});
@@ -2,10 +2,21 @@ import { invoke } from "@tauri-apps/api/core";
 import { open } from "@tauri-apps/plugin-dialog";
 import type { Options as NotificationOptions } from "@tauri-apps/plugin-notification";
 import type {
+  AppLockStatus,
   AppSettings,
+  ApprovalRule,
+  ApprovalRuleKind,
+  ApprovalRuleTest,
+  ApprovalRulesExport,
+  ApprovalRulesImport,
+  CodexProfile,
   CodexUpdateResult,
   CodexDoctorResult,
+  CredentialStatus,
+  DictationInputDevice,
+  DictationLanguage,
   DictationModelStatus,
+  DictationModelVariant,
   DictationSessionState,
   LocalUsageSnapshot,
   OrbitConnectTestResult,
@@ -13,23 +24,75 @@ import type {
   OrbitRunnerStatus,
   OrbitSignInPollResult,
   OrbitSignOutResult,
+  SessionAccount,
+  StaleCodexBin,
   TcpDaemonStatus,
   TailscaleDaemonCommandPreview,
   TailscaleStatus,
+  WorkspaceBundleExport,
+  WorkspaceBundleImport,
+  WorkspaceGroupInfo,
   WorkspaceInfo,
+  WorkspaceRelocation,
+  WorkspaceRemovalResult,
   AppMention,
   WorkspaceSettings,
 } from "../types";
 import type {
+  GitBisectState,
+  GitBisectVerdict,
+  GitBlameLine,
+  GitFetchedRef,
   GitFileDiff,
+  GitFileHistoryEntry,
   GitFileStatus,
   GitCommitDiff,
+  GitHubChecksResponse,
+  GitHubCreatedPullRequest,
   GitHubIssuesResponse,
+  GitHubMergeMethod,
+  GitHubMergeResult,
   GitHubPullRequestComment,
   GitHubPullRequestDiff,
+  GitHubPullRequestInput,
   GitHubPullRequestsResponse,
+  GitHubReviewComment,
+  GitHubReviewEvent,
+  GitGraphResponse,
+  GitLogFilter,
   GitLogResponse,
+  GitRemote,
+  GitRemoteUpdates,
+  GitRepoStats,
+  GitResetMode,
+  GitResetResult,
+  GitStashApplyResult,
+  GitStashEntry,
+  GitSyncResult,
+  MessageTokenEstimate,
+  NetworkStatus,
+  OperationInfo,
+  OperationPriority,
+  OperationTimeEstimate,
+  OrphanedCodexProcess,
+  PinnedMessage,
   ReviewTarget,
+  SessionStartupStats,
+  ShutdownReadiness,
+  SystemInventory,
+  ThreadDiff,
+  ThreadExport,
+  ThreadExportFormat,
+  ThreadReplayResult,
+  ThreadSearchResult,
+  ThreadUsage,
+  ThreadWindow,
+  TurnTraceCapture,
+  TurnTraceReplay,
+  WorkspaceAudit,
+  WorkspaceFileEntry,
+  WorkspaceInterruptResult,
+  WorktreeChangesPreview,
 } from "../types";
 
 function isMissingTauriInvokeError(error: unknown) {
@@ -148,6 +211,19 @@ export async function getConfigModel(workspaceId: string): Promise<string | null
   return trimmed.length > 0 ? trimmed : null;
 }
 
+export async function getEffectiveCodexConfig(
+  workspaceId: string,
+): Promise<{ model: string | null; codexBaseUrl: string | null }> {
+  const response = await invoke<{
+    model?: string | null;
+    codexBaseUrl?: string | null;
+  }>("get_config_model", { workspaceId });
+  return {
+    model: response?.model ?? null,
+    codexBaseUrl: response?.codexBaseUrl ?? null,
+  };
+}
+
 export async function addWorkspace(
   path: string,
   codex_bin: string | null,
@@ -163,12 +239,17 @@ export async function addClone(
   sourceWorkspaceId: string,
   copiesFolder: string,
   copyName: string,
+  operationId?: string,
 ): Promise<WorkspaceInfo> {
-  return invoke<WorkspaceInfo>("add_clone", {
+  const payload: Record<string, unknown> = {
     sourceWorkspaceId,
     copiesFolder,
     copyName,
-  });
+  };
+  if (operationId) {
+    payload.operationId = operationId;
+  }
+  return invoke<WorkspaceInfo>("add_clone", payload);
 }
 
 export async function addWorktree(
@@ -176,8 +257,15 @@ export async function addWorktree(
   branch: string,
   name: string | null,
   copyAgentsMd = true,
+  base: string | null = null,
 ): Promise<WorkspaceInfo> {
-  return invoke<WorkspaceInfo>("add_worktree", { parentId, branch, name, copyAgentsMd });
+  return invoke<WorkspaceInfo>("add_worktree", {
+    parentId,
+    branch,
+    name,
+    copyAgentsMd,
+    base,
+  });
 }
 
 export type WorktreeSetupStatus = {
@@ -209,6 +297,113 @@ export async function updateWorkspaceCodexBin(
   return invoke<WorkspaceInfo>("update_workspace_codex_bin", { id, codex_bin });
 }
 
+export async function checkWorkspaceCodexBins(): Promise<StaleCodexBin[]> {
+  return invoke<StaleCodexBin[]>("check_workspace_codex_bins");
+}
+
+export async function repointCodexBin(id: string): Promise<WorkspaceInfo> {
+  return invoke<WorkspaceInfo>("repoint_codex_bin", { id });
+}
+
+export async function setWorkspaceColor(
+  id: string,
+  color: string | null,
+): Promise<WorkspaceInfo> {
+  return invoke<WorkspaceInfo>("set_workspace_color", { id, color });
+}
+
+export async function renameWorkspace(
+  id: string,
+  name: string,
+): Promise<WorkspaceInfo> {
+  return invoke<WorkspaceInfo>("rename_workspace", { id, name });
+}
+
+export async function relocateWorkspace(
+  id: string,
+  newPath: string,
+): Promise<WorkspaceRelocation> {
+  return invoke<WorkspaceRelocation>("relocate_workspace", { id, newPath });
+}
+
+export async function exportWorkspaceBundle(
+  workspaceId: string,
+  destination?: string | null,
+): Promise<WorkspaceBundleExport> {
+  return invoke<WorkspaceBundleExport>("export_workspace_bundle", {
+    workspaceId,
+    destination: destination ?? null,
+  });
+}
+
+// Approval rules and command settings (codexBin, launch scripts, env...)
+// are only applied with `trusted`; otherwise they come back in `skipped`.
+export async function importWorkspaceBundle(
+  path: string,
+  targetPath: string,
+  trusted = false,
+): Promise<WorkspaceBundleImport> {
+  return invoke<WorkspaceBundleImport>("import_workspace_bundle", {
+    path,
+    targetPath,
+    trusted,
+  });
+}
+
+export async function listWorkspaceGroups(): Promise<WorkspaceGroupInfo[]> {
+  return invoke<WorkspaceGroupInfo[]>("list_workspace_groups");
+}
+
+export async function createWorkspaceGroup(
+  name: string,
+): Promise<WorkspaceGroupInfo> {
+  return invoke<WorkspaceGroupInfo>("create_workspace_group", { name });
+}
+
+export async function renameWorkspaceGroup(
+  id: string,
+  name: string,
+): Promise<WorkspaceGroupInfo> {
+  return invoke<WorkspaceGroupInfo>("rename_workspace_group", { id, name });
+}
+
+export async function deleteWorkspaceGroup(id: string): Promise<void> {
+  return invoke("delete_workspace_group", { id });
+}
+
+export async function setWorkspaceProfile(
+  workspaceId: string,
+  profileId: string | null,
+): Promise<WorkspaceInfo> {
+  return invoke<WorkspaceInfo>("set_workspace_profile", {
+    workspaceId,
+    profileId,
+  });
+}
+
+export async function listProfiles(): Promise<CodexProfile[]> {
+  return invoke<CodexProfile[]>("list_profiles");
+}
+
+export async function createProfile(
+  name: string,
+  codexHome: string,
+): Promise<CodexProfile> {
+  return invoke<CodexProfile>("create_profile", { name, codexHome });
+}
+
+export async function deleteProfile(profileId: string): Promise<void> {
+  return invoke("delete_profile", { profileId });
+}
+
+export async function codexProfileLogin(profileId: string): Promise<void> {
+  return invoke("codex_profile_login", { profileId });
+}
+
+export async function codexProfileLogout(profileId: string): Promise<void> {
+  return invoke("codex_profile_logout", { profileId });
+}
+
 export async function removeWorkspace(id: string): Promise<void> {
   return invoke("remove_workspace", { id });
 }
@@ -217,6 +412,12 @@ export async function removeWorktree(id: string): Promise<void> {
   return invoke("remove_worktree", { id });
 }
 
+export async function removeWorkspaces(
+  ids: string[],
+): Promise<WorkspaceRemovalResult[]> {
+  return invoke<WorkspaceRemovalResult[]>("remove_workspaces", { ids });
+}
+
 export async function renameWorktree(
   id: string,
   branch: string,
@@ -236,6 +437,24 @@ export async function applyWorktreeChanges(workspaceId: string): Promise<void> {
   return invoke("apply_worktree_changes", { workspaceId });
 }
 
+export async function applyWorktreeCommits(workspaceId: string): Promise<void> {
+  return invoke("apply_worktree_commits", { workspaceId });
+}
+
+export async function moveWorktreeChangesBack(
+  workspaceId: string,
+): Promise<void> {
+  return invoke("move_worktree_changes_back", { workspaceId });
+}
+
+export async function previewWorktreeChanges(
+  workspaceId: string,
+): Promise<WorktreeChangesPreview> {
+  return invoke<WorktreeChangesPreview>("preview_worktree_changes", {
+    workspaceId,
+  });
+}
+
 export async function openWorkspaceIn(
   path: string,
   options: {
@@ -260,6 +479,10 @@ export async function connectWorkspace(id: string): Promise<void> {
   return invoke("connect_workspace", { id });
 }
 
+export async function prewarmSessions(workspaceIds: string[]): Promise<void> {
+  return invoke("prewarm_sessions", { workspaceIds });
+}
+
 export async function startThread(workspaceId: string) {
   return invoke<any>("start_thread", { workspaceId });
 }
@@ -268,6 +491,147 @@ export async function forkThread(workspaceId: string, threadId: string) {
   return invoke<any>("fork_thread", { workspaceId, threadId });
 }
 
+export async function replayThread(
+  workspaceId: string,
+  sourceThreadId: string,
+  model: string,
+  operationId?: string,
+): Promise<ThreadReplayResult> {
+  const payload: Record<string, unknown> = {
+    workspaceId,
+    sourceThreadId,
+    model,
+  };
+  if (operationId) {
+    payload.operationId = operationId;
+  }
+  return invoke<ThreadReplayResult>("replay_thread", payload);
+}
+
+export async function diffThreads(
+  workspaceId: string,
+  threadA: string,
+  threadB: string,
+): Promise<ThreadDiff> {
+  return invoke<ThreadDiff>("diff_threads", { workspaceId, threadA, threadB });
+}
+
+export async function getThreadWindow(
+  workspaceId: string,
+  threadId: string,
+  aroundMessageId: string,
+  radius?: number,
+): Promise<ThreadWindow> {
+  const payload: Record<string, unknown> = {
+    workspaceId,
+    threadId,
+    aroundMessageId,
+  };
+  if (radius !== undefined) {
+    payload.radius = radius;
+  }
+  return invoke<ThreadWindow>("get_thread_window", payload);
+}
+
+export async function exportThread(
+  workspaceId: string,
+  threadId: string,
+  options: { format?: ThreadExportFormat; destination?: string | null } = {},
+): Promise<ThreadExport> {
+  return invoke<ThreadExport>("export_thread", {
+    workspaceId,
+    threadId,
+    format: options.format ?? "markdown",
+    destination: options.destination ?? null,
+  });
+}
+
+export async function searchThreads(
+  workspaceId: string,
+  query: string,
+  options: {
+    includeArchived?: boolean;
+    maxMessageBytes?: number;
+    operationId?: string;
+  } = {},
+): Promise<ThreadSearchResult[]> {
+  return invoke<ThreadSearchResult[]>("search_threads", {
+    workspaceId,
+    query,
+    includeArchived: options.includeArchived ?? false,
+    maxMessageBytes: options.maxMessageBytes ?? null,
+    operationId: options.operationId ?? null,
+  });
+}
+
+export async function estimateMessageTokens(
+  workspaceId: string,
+  threadId: string,
+  message: string,
+  attachments: string[] = [],
+  model?: string | null,
+): Promise<MessageTokenEstimate> {
+  return invoke<MessageTokenEstimate>("estimate_message_tokens", {
+    workspaceId,
+    threadId,
+    message,
+    attachments,
+    model: model ?? null,
+  });
+}
+
+export async function threadUsage(
+  workspaceId: string,
+  threadId: string,
+): Promise<ThreadUsage> {
+  return invoke<ThreadUsage>("thread_usage", { workspaceId, threadId });
+}
+
+export async function captureTurnTrace(
+  workspaceId: string,
+  turnId: string,
+): Promise<TurnTraceCapture> {
+  return invoke<TurnTraceCapture>("capture_turn_trace", {
+    workspaceId,
+    turnId,
+  });
+}
+
+export async function loadTurnTrace(
+  path: string,
+  workspaceId?: string | null,
+): Promise<TurnTraceReplay> {
+  return invoke<TurnTraceReplay>("load_turn_trace", {
+    path,
+    workspaceId: workspaceId ?? null,
+  });
+}
+
+export async function pinThreadMessage(
+  workspaceId: string,
+  threadId: string,
+  messageId: string,
+): Promise<PinnedMessage[]> {
+  return invoke<PinnedMessage[]>("pin_thread_message", {
+    workspaceId,
+    threadId,
+    messageId,
+  });
+}
+
+export async function unpinThreadMessage(
+  threadId: string,
+  messageId: string,
+): Promise<PinnedMessage[]> {
+  return invoke<PinnedMessage[]>("unpin_thread_message", { threadId, messageId });
+}
+
+export async function listPinnedMessages(
+  threadId: string,
+): Promise<PinnedMessage[]> {
+  return invoke<PinnedMessage[]>("list_pinned_messages", { threadId });
+}
+
 export async function compactThread(workspaceId: string, threadId: string) {
   return invoke<any>("compact_thread", { workspaceId, threadId });
 }
@@ -283,6 +647,7 @@ export async function sendUserMessage(
     images?: string[];
     collaborationMode?: Record<string, unknown> | null;
     appMentions?: AppMention[];
+    queue?: boolean;
   },
 ) {
   const payload: Record<string, unknown> = {
@@ -300,9 +665,16 @@ export async function sendUserMessage(
   if (options?.appMentions && options.appMentions.length > 0) {
     payload.appMentions = options.appMentions;
   }
+  if (options?.queue) {
+    payload.queue = true;
+  }
   return invoke("send_user_message", payload);
 }
 
+export async function clearMessageQueue(workspaceId: string): Promise<number> {
+  return invoke<number>("clear_message_queue", { workspaceId });
+}
+
 export async function interruptTurn(
   workspaceId: string,
   threadId: string,
@@ -311,6 +683,10 @@ export async function interruptTurn(
   return invoke("turn_interrupt", { workspaceId, threadId, turnId });
 }
 
+export async function interruptAllTurns(): Promise<WorkspaceInterruptResult[]> {
+  return invoke<WorkspaceInterruptResult[]>("interrupt_all_turns");
+}
+
 export async function steerTurn(
   workspaceId: string,
   threadId: string,
@@ -357,6 +733,21 @@ export async function respondToServerRequest(
   });
 }
 
+// Accepts only when one of the workspace's approval rules covers `command`;
+// the backend makes that call. Resolves to whether the request was answered.
+export async function acceptServerRequestIfAllowed(
+  workspaceId: string,
+  requestId: number | string,
+  command: string[],
+): Promise<boolean> {
+  return invoke<boolean>("respond_to_server_request", {
+    workspaceId,
+    requestId,
+    result: { decision: "accept" },
+    ruleCommand: command,
+  });
+}
+
 export async function respondToUserInputRequest(
   workspaceId: string,
   requestId: number | string,
@@ -372,19 +763,74 @@ export async function respondToUserInputRequest(
 export async function rememberApprovalRule(
   workspaceId: string,
   command: string[],
+  options?: { kind?: ApprovalRuleKind; pattern?: string },
 ) {
-  return invoke("remember_approval_rule", { workspaceId, command });
+  return invoke("remember_approval_rule", {
+    workspaceId,
+    command,
+    kind: options?.kind ?? null,
+    pattern: options?.pattern ?? null,
+  });
+}
+
+export async function testApprovalRule(
+  pattern: string,
+  command: string[],
+  kind?: ApprovalRuleKind,
+): Promise<ApprovalRuleTest> {
+  return invoke<ApprovalRuleTest>("test_approval_rule", {
+    pattern,
+    command,
+    kind: kind ?? null,
+  });
+}
+
+export async function listApprovalRules(
+  workspaceId: string,
+): Promise<ApprovalRule[]> {
+  return invoke<ApprovalRule[]>("list_approval_rules", { workspaceId });
+}
+
+export async function deleteApprovalRule(workspaceId: string, id: string) {
+  return invoke("delete_approval_rule", { workspaceId, id });
+}
+
+export async function exportApprovalRules(
+  workspaceId: string,
+): Promise<ApprovalRulesExport> {
+  return invoke<ApprovalRulesExport>("export_approval_rules", { workspaceId });
 }
 
-export async function getGitStatus(workspace_id: string): Promise<{
+export async function importApprovalRules(
+  workspaceId: string,
+  data: string,
+): Promise<ApprovalRulesImport> {
+  return invoke<ApprovalRulesImport>("import_approval_rules", {
+    workspaceId,
+    data,
+  });
+}
+
+export async function getGitStatus(
+  workspace_id: string,
+  priority?: OperationPriority,
+): Promise<{
   branchName: string;
+  detached?: boolean;
+  upstream?: string | null;
+  ahead?: number | null;
+  behind?: number | null;
   files: GitFileStatus[];
   stagedFiles: GitFileStatus[];
   unstagedFiles: GitFileStatus[];
   totalAdditions: number;
   totalDeletions: number;
 }> {
-  return invoke("get_git_status", { workspaceId: workspace_id });
+  const payload: Record<string, unknown> = { workspaceId: workspace_id };
+  if (priority) {
+    payload.priority = priority;
+  }
+  return invoke("get_git_status", payload);
 }
 
 export type InitGitRepoResponse =
@@ -433,15 +879,134 @@ export async function listGitRoots(
 
 export async function getGitDiffs(
   workspace_id: string,
+  priority?: OperationPriority,
+  contextLines?: number,
+): Promise<GitFileDiff[]> {
+  const payload: Record<string, unknown> = { workspaceId: workspace_id };
+  if (priority) {
+    payload.priority = priority;
+  }
+  if (contextLines !== undefined) {
+    payload.contextLines = contextLines;
+  }
+  return invoke("get_git_diffs", payload);
+}
+
+export async function getGitDiffRange(
+  workspaceId: string,
+  base: string,
+  head: string,
+  options: { paths?: string[]; twoDot?: boolean } = {},
 ): Promise<GitFileDiff[]> {
-  return invoke("get_git_diffs", { workspaceId: workspace_id });
+  return invoke<GitFileDiff[]>("get_git_diff_range", {
+    workspaceId,
+    base,
+    head,
+    paths: options.paths ?? [],
+    twoDot: options.twoDot ?? false,
+  });
 }
 
 export async function getGitLog(
   workspace_id: string,
-  limit = 40,
+  limit = 100,
+  options: { skip?: number; path?: string | null; filter?: GitLogFilter } = {},
 ): Promise<GitLogResponse> {
-  return invoke("get_git_log", { workspaceId: workspace_id, limit });
+  return invoke("get_git_log", {
+    workspaceId: workspace_id,
+    skip: options.skip ?? 0,
+    limit,
+    path: options.path ?? null,
+    filter: options.filter ?? null,
+  });
+}
+
+export async function getGitGraph(
+  workspace_id: string,
+  maxCount?: number,
+  refs?: string[],
+): Promise<GitGraphResponse> {
+  const payload: Record<string, unknown> = { workspaceId: workspace_id };
+  if (maxCount !== undefined) {
+    payload.maxCount = maxCount;
+  }
+  if (refs && refs.length > 0) {
+    payload.refs = refs;
+  }
+  return invoke<GitGraphResponse>("get_git_graph", payload);
+}
+
+export async function getFileHistory(
+  workspace_id: string,
+  path: string,
+  options?: { followRenames?: boolean; maxCount?: number },
+): Promise<GitFileHistoryEntry[]> {
+  const payload: Record<string, unknown> = { workspaceId: workspace_id, path };
+  if (options?.followRenames !== undefined) {
+    payload.followRenames = options.followRenames;
+  }
+  if (options?.maxCount !== undefined) {
+    payload.maxCount = options.maxCount;
+  }
+  return invoke<GitFileHistoryEntry[]>("get_file_history", payload);
+}
+
+export async function gitBlame(
+  workspaceId: string,
+  path: string,
+  rev: string | null = null,
+): Promise<GitBlameLine[]> {
+  return invoke<GitBlameLine[]>("git_blame", { workspaceId, path, rev });
+}
+
+export async function getRepoStats(
+  workspace_id: string,
+): Promise<GitRepoStats> {
+  return invoke<GitRepoStats>("get_repo_stats", { workspaceId: workspace_id });
+}
+
+export async function auditWorkspace(
+  workspace_id: string,
+): Promise<WorkspaceAudit> {
+  return invoke<WorkspaceAudit>("audit_workspace", { workspaceId: workspace_id });
+}
+
+export async function gitBisectStart(
+  workspace_id: string,
+  bad: string,
+  good: string,
+): Promise<GitBisectState> {
+  return invoke<GitBisectState>("git_bisect_start", {
+    workspaceId: workspace_id,
+    bad,
+    good,
+  });
+}
+
+export async function gitBisectMark(
+  workspace_id: string,
+  verdict: GitBisectVerdict,
+): Promise<GitBisectState> {
+  return invoke<GitBisectState>("git_bisect_mark", {
+    workspaceId: workspace_id,
+    verdict,
+  });
+}
+
+export async function gitBisectReset(
+  workspace_id: string,
+): Promise<GitBisectState> {
+  return invoke<GitBisectState>("git_bisect_reset", {
+    workspaceId: workspace_id,
+  });
+}
+
+export async function getGitBisectState(
+  workspace_id: string,
+): Promise<GitBisectState> {
+  return invoke<GitBisectState>("get_git_bisect_state", {
+    workspaceId: workspace_id,
+  });
 }
 
 export async function getGitCommitDiff(
@@ -455,10 +1020,25 @@ export async function getGitRemote(workspace_id: string): Promise<string | null>
   return invoke("get_git_remote", { workspaceId: workspace_id });
 }
 
+export async function listGitRemotes(
+  workspaceId: string,
+): Promise<GitRemote[]> {
+  return invoke<GitRemote[]>("list_git_remotes", { workspaceId });
+}
+
 export async function stageGitFile(workspaceId: string, path: string) {
   return invoke("stage_git_file", { workspaceId, path });
 }
 
+export async function stageGitHunk(
+  workspaceId: string,
+  path: string,
+  hunkHeader: string,
+  patch: string,
+): Promise<void> {
+  return invoke("stage_git_hunk", { workspaceId, path, hunkHeader, patch });
+}
+
 export async function stageGitAll(workspaceId: string): Promise<void> {
   return invoke("stage_git_all", { workspaceId });
 }
@@ -467,6 +1047,19 @@ export async function unstageGitFile(workspaceId: string, path: string) {
   return invoke("unstage_git_file", { workspaceId, path });
 }
 
+export async function unstageGitHunk(
+  workspaceId: string,
+  path: string,
+  hunkHeader: string,
+  patch: string,
+): Promise<void> {
+  return invoke("unstage_git_hunk", { workspaceId, path, hunkHeader, patch });
+}
+
+export async function unstageGitAll(workspaceId: string): Promise<number> {
+  return invoke<number>("unstage_git_all", { workspaceId });
+}
+
 export async function revertGitFile(workspaceId: string, path: string) {
   return invoke("revert_git_file", { workspaceId, path });
 }
@@ -475,6 +1068,19 @@ export async function revertGitAll(workspaceId: string) {
   return invoke("revert_git_all", { workspaceId });
 }
 
+export async function gitReset(
+  workspaceId: string,
+  mode: GitResetMode,
+  options: { target?: string; confirm?: boolean } = {},
+): Promise<GitResetResult> {
+  return invoke<GitResetResult>("git_reset", {
+    workspaceId,
+    target: options.target ?? null,
+    mode,
+    confirm: options.confirm ?? false,
+  });
+}
+
 export async function commitGit(
   workspaceId: string,
   message: string,
@@ -482,22 +1088,204 @@ export async function commitGit(
   return invoke("commit_git", { workspaceId, message });
 }
 
+export async function gitCommit(
+  workspaceId: string,
+  message: string,
+  options: { amend?: boolean; signoff?: boolean } = {},
+): Promise<string> {
+  return invoke<string>("git_commit", {
+    workspaceId,
+    message,
+    amend: options.amend ?? false,
+    signoff: options.signoff ?? false,
+  });
+}
+
 export async function pushGit(workspaceId: string): Promise<void> {
   return invoke("push_git", { workspaceId });
 }
 
-export async function pullGit(workspaceId: string): Promise<void> {
-  return invoke("pull_git", { workspaceId });
+export async function gitPush(
+  workspaceId: string,
+  setUpstream = false,
+  priority?: OperationPriority,
+): Promise<GitSyncResult> {
+  const payload: Record<string, unknown> = { workspaceId, setUpstream };
+  if (priority) {
+    payload.priority = priority;
+  }
+  return invoke<GitSyncResult>("git_push", payload);
+}
+
+export async function gitPull(
+  workspaceId: string,
+  rebase = false,
+  priority?: OperationPriority,
+): Promise<GitSyncResult> {
+  const payload: Record<string, unknown> = { workspaceId, rebase };
+  if (priority) {
+    payload.priority = priority;
+  }
+  return invoke<GitSyncResult>("git_pull", payload);
+}
+
+export async function gitFetch(
+  workspaceId: string,
+  remote: string | null = null,
+  prune = false,
+  priority?: OperationPriority,
+): Promise<GitFetchedRef[]> {
+  const payload: Record<string, unknown> = { workspaceId, remote, prune };
+  if (priority) {
+    payload.priority = priority;
+  }
+  return invoke<GitFetchedRef[]>("git_fetch", payload);
+}
+
+export async function gitStashList(
+  workspaceId: string,
+): Promise<GitStashEntry[]> {
+  return invoke<GitStashEntry[]>("git_stash_list", { workspaceId });
+}
+
+export async function gitStashPush(
+  workspaceId: string,
+  message: string | null = null,
+  includeUntracked = false,
+): Promise<void> {
+  return invoke("git_stash_push", { workspaceId, message, includeUntracked });
 }
 
-export async function fetchGit(workspaceId: string): Promise<void> {
-  return invoke("fetch_git", { workspaceId });
+export async function gitStashApply(
+  workspaceId: string,
+  index: number,
+): Promise<GitStashApplyResult> {
+  return invoke<GitStashApplyResult>("git_stash_apply", { workspaceId, index });
+}
+
+export async function gitStashPop(
+  workspaceId: string,
+  index: number,
+): Promise<GitStashApplyResult> {
+  return invoke<GitStashApplyResult>("git_stash_pop", { workspaceId, index });
+}
+
+export async function gitStashDrop(
+  workspaceId: string,
+  index: number,
+): Promise<void> {
+  return invoke("git_stash_drop", { workspaceId, index });
+}
+
+export async function pullGit(
+  workspaceId: string,
+  operationId?: string,
+  priority?: OperationPriority,
+): Promise<void> {
+  const payload: Record<string, unknown> = { workspaceId };
+  if (operationId) {
+    payload.operationId = operationId;
+  }
+  if (priority) {
+    payload.priority = priority;
+  }
+  return invoke("pull_git", payload);
+}
+
+export async function fetchGit(
+  workspaceId: string,
+  operationId?: string,
+  priority?: OperationPriority,
+): Promise<void> {
+  const payload: Record<string, unknown> = { workspaceId };
+  if (operationId) {
+    payload.operationId = operationId;
+  }
+  if (priority) {
+    payload.priority = priority;
+  }
+  return invoke("fetch_git", payload);
 }
 
 export async function syncGit(workspaceId: string): Promise<void> {
   return invoke("sync_git", { workspaceId });
 }
 
+export async function checkRemoteUpdates(
+  workspaceId: string,
+): Promise<GitRemoteUpdates> {
+  return invoke<GitRemoteUpdates>("check_remote_updates", { workspaceId });
+}
+
+export async function estimateOperationTime(
+  kind: string,
+  repoSizeHint?: number | null,
+): Promise<OperationTimeEstimate> {
+  return invoke<OperationTimeEstimate>("estimate_operation_time", {
+    kind,
+    repoSizeHint: repoSizeHint ?? null,
+  });
+}
+
+export async function cancelOperation(operationId: string): Promise<boolean> {
+  return invoke<boolean>("cancel_operation", { operationId });
+}
+
+export async function listOperations(): Promise<OperationInfo[]> {
+  return invoke<OperationInfo[]>("list_operations");
+}
+
+export async function getSystemInventory(): Promise<SystemInventory> {
+  return invoke<SystemInventory>("get_system_inventory");
+}
+
+export async function prepareShutdown(): Promise<ShutdownReadiness> {
+  return invoke<ShutdownReadiness>("prepare_shutdown");
+}
+
+export async function gracefulShutdown(timeoutMs?: number): Promise<void> {
+  return invoke("graceful_shutdown", { timeoutMs: timeoutMs ?? null });
+}
+
+export async function getNetworkStatus(): Promise<NetworkStatus> {
+  return invoke<NetworkStatus>("get_network_status");
+}
+
+// The remote backend token is never sent back with the app settings; ask
+// `getCredentialStatus` with this key whether one is stored.
+export const REMOTE_BACKEND_TOKEN_KEY = "remoteBackendToken";
+
+export async function setCredential(
+  key: string,
+  secret: string,
+): Promise<CredentialStatus> {
+  return invoke<CredentialStatus>("set_credential", { key, secret });
+}
+
+export async function getCredentialStatus(key: string): Promise<CredentialStatus> {
+  return invoke<CredentialStatus>("get_credential_status", { key });
+}
+
+export async function deleteCredential(key: string): Promise<CredentialStatus> {
+  return invoke<CredentialStatus>("delete_credential", { key });
+}
+
+export async function redactSecrets(text: string): Promise<string> {
+  return invoke<string>("redact_secrets", { text });
+}
+
+export async function getAppLockStatus(): Promise<AppLockStatus> {
+  return invoke<AppLockStatus>("get_app_lock_status");
+}
+
+export async function lockApp(): Promise<AppLockStatus> {
+  return invoke<AppLockStatus>("lock_app");
+}
+
+export async function unlockApp(): Promise<AppLockStatus> {
+  return invoke<AppLockStatus>("unlock_app");
+}
+
 export async function getGitHubIssues(
   workspace_id: string,
 ): Promise<GitHubIssuesResponse> {
@@ -530,6 +1318,16 @@ export async function getGitHubPullRequestComments(
   });
 }
 
+export async function getGitHubPullRequestChecks(
+  workspaceId: string,
+  prNumber: number,
+): Promise<GitHubChecksResponse> {
+  return invoke<GitHubChecksResponse>("get_github_pull_request_checks", {
+    workspaceId,
+    prNumber,
+  });
+}
+
 export async function checkoutGitHubPullRequest(
   workspace_id: string,
   prNumber: number,
@@ -540,6 +1338,47 @@ export async function checkoutGitHubPullRequest(
   });
 }
 
+export async function createGitHubPullRequest(
+  workspaceId: string,
+  pullRequest: GitHubPullRequestInput,
+): Promise<GitHubCreatedPullRequest> {
+  return invoke<GitHubCreatedPullRequest>("create_github_pull_request", {
+    workspaceId,
+    pullRequest,
+  });
+}
+
+export async function submitGitHubPullRequestReview(
+  workspaceId: string,
+  prNumber: number,
+  event: GitHubReviewEvent,
+  body: string | null = null,
+  comments: GitHubReviewComment[] = [],
+): Promise<number> {
+  return invoke<number>("submit_github_pull_request_review", {
+    workspaceId,
+    prNumber,
+    event,
+    body,
+    comments,
+  });
+}
+
+export async function mergeGitHubPullRequest(
+  workspaceId: string,
+  prNumber: number,
+  method: GitHubMergeMethod,
+  options: { commitTitle?: string; commitMessage?: string } = {},
+): Promise<GitHubMergeResult> {
+  return invoke<GitHubMergeResult>("merge_github_pull_request", {
+    workspaceId,
+    prNumber,
+    method,
+    commitTitle: options.commitTitle ?? null,
+    commitMessage: options.commitMessage ?? null,
+  });
+}
+
 export async function localUsageSnapshot(
   days?: number,
   workspacePath?: string | null,
@@ -551,7 +1390,13 @@ export async function localUsageSnapshot(
   return invoke("local_usage_snapshot", payload);
 }
 
-export async function getModelList(workspaceId: string) {
+export async function getModelList(
+  workspaceId: string,
+  options?: { refresh?: boolean },
+) {
+  if (options?.refresh) {
+    return invoke<any>("model_list", { workspaceId, refresh: true });
+  }
   return invoke<any>("model_list", { workspaceId });
 }
 
@@ -589,6 +1434,12 @@ export async function getAccountInfo(workspaceId: string) {
   return invoke<any>("account_read", { workspaceId });
 }
 
+export async function getSessionAccount(
+  workspaceId: string,
+): Promise<SessionAccount> {
+  return invoke<SessionAccount>("get_session_account", { workspaceId });
+}
+
 export async function runCodexLogin(workspaceId: string) {
   return invoke<{ loginId: string; authUrl: string; raw?: unknown }>("codex_login", {
     workspaceId,
@@ -602,7 +1453,13 @@ export async function cancelCodexLogin(workspaceId: string) {
   );
 }
 
-export async function getSkillsList(workspaceId: string) {
+export async function getSkillsList(
+  workspaceId: string,
+  options?: { refresh?: boolean },
+) {
+  if (options?.refresh) {
+    return invoke<any>("skills_list", { workspaceId, refresh: true });
+  }
   return invoke<any>("skills_list", { workspaceId });
 }
 
@@ -631,6 +1488,7 @@ export async function createPrompt(
   workspaceId: string,
   data: {
     scope: "workspace" | "global";
+    folder?: string | null;
     name: string;
     description?: string | null;
     argumentHint?: string | null;
@@ -640,6 +1498,7 @@ export async function createPrompt(
   return invoke<any>("prompts_create", {
     workspaceId,
     scope: data.scope,
+    folder: data.folder ?? null,
     name: data.name,
     description: data.description ?? null,
     argumentHint: data.argumentHint ?? null,
@@ -673,12 +1532,31 @@ export async function deletePrompt(workspaceId: string, path: string) {
 
 export async function movePrompt(
   workspaceId: string,
-  data: { path: string; scope: "workspace" | "global" },
+  data: { path: string; scope: "workspace" | "global"; folder?: string | null },
 ) {
   return invoke<any>("prompts_move", {
     workspaceId,
     path: data.path,
     scope: data.scope,
+    folder: data.folder ?? null,
+  });
+}
+
+export async function duplicatePrompt(workspaceId: string, path: string) {
+  return invoke<any>("prompts_duplicate", { workspaceId, path });
+}
+
+export async function renderPrompt(
+  workspaceId: string,
+  path: string,
+  vars: Record<string, string>,
+  leaveUnfilled = false,
+) {
+  return invoke<string>("prompts_render", {
+    workspaceId,
+    path,
+    vars,
+    leaveUnfilled,
   });
 }
 
@@ -760,6 +1638,22 @@ export async function runCodexDoctor(
   return invoke<CodexDoctorResult>("codex_doctor", { codexBin, codexArgs });
 }
 
+export async function getSessionStartupStats(): Promise<SessionStartupStats> {
+  return invoke<SessionStartupStats>("get_session_startup_stats");
+}
+
+export async function findOrphanedCodexProcesses(): Promise<
+  OrphanedCodexProcess[]
+> {
+  return invoke<OrphanedCodexProcess[]>("find_orphaned_codex_processes");
+}
+
+export async function killOrphanedCodexProcesses(
+  pids: number[],
+): Promise<number[]> {
+  return invoke<number[]>("kill_orphaned_codex_processes", { pids });
+}
+
 export async function runCodexUpdate(
   codexBin: string | null,
   codexArgs: string | null,
@@ -767,8 +1661,24 @@ export async function runCodexUpdate(
   return invoke<CodexUpdateResult>("codex_update", { codexBin, codexArgs });
 }
 
-export async function getWorkspaceFiles(workspaceId: string) {
-  return invoke<string[]>("list_workspace_files", { workspaceId });
+export async function getWorkspaceFiles(
+  workspaceId: string,
+  includeIgnored = false,
+) {
+  return invoke<string[]>("list_workspace_files", {
+    workspaceId,
+    includeIgnored,
+  });
+}
+
+export async function getWorkspaceFilesDetailed(
+  workspaceId: string,
+  includeIgnored = false,
+): Promise<WorkspaceFileEntry[]> {
+  return invoke<WorkspaceFileEntry[]>("list_workspace_files_detailed", {
+    workspaceId,
+    includeIgnored,
+  });
 }
 
 export async function readWorkspaceFile(
@@ -814,6 +1724,16 @@ export async function getDictationModelStatus(
   );
 }
 
+export async function listDictationModels(): Promise<DictationModelVariant[]> {
+  return invoke<DictationModelVariant[]>("dictation_list_available_models");
+}
+
+export async function selectDictationModel(
+  modelId: string,
+): Promise<DictationModelStatus> {
+  return invoke<DictationModelStatus>("dictation_select_model", { modelId });
+}
+
 export async function downloadDictationModel(
   modelId?: string | null,
 ): Promise<DictationModelStatus> {
@@ -843,8 +1763,35 @@ export async function removeDictationModel(
 
 export async function startDictation(
   preferredLanguage: string | null,
+  targetId: string | null = null,
+  language: string | null = null,
+  deviceId: string | null = null,
+  autoStopSilenceMs: number | null = null,
+  translate = false,
 ): Promise<DictationSessionState> {
-  return invoke("dictation_start", { preferredLanguage });
+  return invoke("dictation_start", {
+    preferredLanguage,
+    targetId,
+    language,
+    deviceId,
+    autoStopSilenceMs,
+    translate,
+  });
+}
+
+export async function listDictationInputDevices(): Promise<
+  DictationInputDevice[]
+> {
+  return invoke<DictationInputDevice[]>("dictation_list_input_devices");
+}
+
+export async function getDictationLanguages(
+  modelId?: string | null,
+): Promise<DictationLanguage[]> {
+  return invoke<DictationLanguage[]>(
+    "dictation_supported_languages",
+    withModelId(modelId),
+  );
 }
 
 export async function requestDictationPermission(): Promise<boolean> {
@@ -859,13 +1806,42 @@ export async function cancelDictation(): Promise<DictationSessionState> {
   return invoke("dictation_cancel");
 }
 
+export async function transcribeDictationFile(
+  path: string,
+  preferredLanguage?: string | null,
+  modelId?: string | null,
+): Promise<string> {
+  return invoke<string>("dictation_transcribe_file", {
+    path,
+    preferredLanguage: preferredLanguage ?? null,
+    ...withModelId(modelId),
+  });
+}
+
+export type TerminalOpenOptions = {
+  shell?: string | null;
+  // Relative to the workspace root; must stay inside it.
+  cwd?: string | null;
+  env?: Record<string, string>;
+};
+
+// Pass a null terminalId to have the backend pick a fresh session id.
 export async function openTerminalSession(
   workspaceId: string,
-  terminalId: string,
+  terminalId: string | null,
   cols: number,
   rows: number,
+  options: TerminalOpenOptions = {},
 ): Promise<{ id: string }> {
-  return invoke("terminal_open", { workspaceId, terminalId, cols, rows });
+  return invoke("terminal_open", {
+    workspaceId,
+    terminalId: terminalId ?? null,
+    cols,
+    rows,
+    shell: options.shell ?? null,
+    cwd: options.cwd ?? null,
+    env: options.env ? Object.entries(options.env) : null,
+  });
 }
 
 export async function writeTerminalSession(
@@ -885,6 +1861,16 @@ export async function resizeTerminalSession(
   return invoke("terminal_resize", { workspaceId, terminalId, cols, rows });
 }
 
+export type TerminalSignal = "interrupt" | "terminate" | "kill";
+
+export async function signalTerminalSession(
+  workspaceId: string,
+  terminalId: string,
+  signal: TerminalSignal,
+): Promise<void> {
+  return invoke("terminal_signal", { workspaceId, terminalId, signal });
+}
+
 export async function closeTerminalSession(
   workspaceId: string,
   terminalId: string,
@@ -925,6 +1911,18 @@ export async function setThreadName(
   return invoke<any>("set_thread_name", { workspaceId, threadId, name });
 }
 
+export async function renameThread(
+  workspaceId: string,
+  threadId: string,
+  title: string,
+): Promise<string | null> {
+  return invoke<string | null>("rename_thread", {
+    workspaceId,
+    threadId,
+    title,
+  });
+}
+
 export async function generateCommitMessage(
   workspaceId: string,
 ): Promise<string> {
@@ -8,6 +8,20 @@ export type WorkspaceSettings = {
   launchScript?: string | null;
   launchScripts?: LaunchScriptEntry[] | null;
   worktreeSetupScript?: string | null;
+  color?: string | null;
+  autoFetchIntervalSecs?: number | null;
+  codexBaseUrl?: string | null;
+  githubHost?: string | null;
+  profileId?: string | null;
+  autoCompactAtPct?: number | null;
+  env?: [string, string][];
+};
+
+export type WorkspaceFileEntry = {
+  path: string;
+  size: number;
+  modifiedAtMs: number | null;
+  isSymlink: boolean;
 };
 
 export type LaunchScriptIconId =
@@ -41,6 +55,14 @@ export type WorkspaceGroup = {
   copiesFolder?: string | null;
 };
 
+export type WorkspaceGroupInfo = {
+  id: string;
+  name: string;
+  sortOrder: number | null;
+  copiesFolder: string | null;
+  memberCount: number;
+};
+
 export type WorkspaceKind = "main" | "worktree";
 
 export type WorktreeInfo = {
@@ -56,9 +78,63 @@ export type WorkspaceInfo = {
   kind?: WorkspaceKind;
   parentId?: string | null;
   worktree?: WorktreeInfo | null;
+  color?: string | null;
+  pathExists?: boolean;
   settings: WorkspaceSettings;
 };
 
+export type WorkspaceRelocation = {
+  workspace: WorkspaceInfo;
+  warnings: string[];
+};
+
+export type StaleCodexBin = {
+  workspaceId: string;
+  workspaceName: string;
+  codexBin: string;
+  defaultCodexBin: string | null;
+  defaultVersion: string | null;
+};
+
+export type WorkspaceBundleExport = {
+  path: string;
+  prompts: number;
+  rules: number;
+  threads: number;
+  redactedSecrets: number;
+  skipped: string[];
+};
+
+export type WorkspaceBundleImport = {
+  workspace: WorkspaceInfo;
+  prompts: number;
+  rules: number;
+  threads: number;
+  skipped: string[];
+};
+
+export type WorktreeChangedFile = {
+  path: string;
+  additions: number;
+  deletions: number;
+  binary: boolean;
+};
+
+export type WorktreeChangesPreview = {
+  files: WorktreeChangedFile[];
+  additions: number;
+  deletions: number;
+  wouldConflict: boolean;
+  conflictDetail: string | null;
+  parentDirty: boolean;
+};
+
+export type WorkspaceRemovalResult = {
+  id: string;
+  removed: boolean;
+  error: string | null;
+};
+
 export type AppServerEvent = {
   workspace_id: string;
   message: Record<string, unknown>;
@@ -233,7 +309,10 @@ export type AppSettings = {
   dictationEnabled: boolean;
   dictationModelId: string;
   dictationPreferredLanguage: string | null;
+  dictationInputDevice: string | null;
+  dictationAutoStopSilenceMs: number | null;
   dictationHoldKey: string | null;
+  dictationSaveAudio: boolean;
   composerEditorPreset: ComposerEditorPreset;
   composerFenceExpandOnSpace: boolean;
   composerFenceExpandOnEnter: boolean;
@@ -246,6 +325,24 @@ export type AppSettings = {
   workspaceGroups: WorkspaceGroup[];
   openAppTargets: OpenAppTarget[];
   selectedOpenAppId: string;
+  httpProxy: string | null;
+  httpsProxy: string | null;
+  noProxy: string | null;
+  customCaCertPath: string | null;
+  customCaHosts: string[];
+  requireUnlockOnLaunch: boolean;
+  autoLockIdleSecs: number | null;
+  redactSecrets: boolean;
+  secretPatterns: string[];
+  codexProfiles: CodexProfile[];
+  eventBatchIntervalMs: number;
+  autoRespawnSessions: boolean;
+};
+
+export type CodexProfile = {
+  id: string;
+  name: string;
+  codexHome: string;
 };
 
 export type OrbitConnectTestResult = {
@@ -372,6 +469,31 @@ export type ApprovalRequest = {
   params: Record<string, unknown>;
 };
 
+export type ApprovalRuleKind = "exact" | "glob" | "regex";
+
+export type ApprovalRule = {
+  id: string;
+  kind: ApprovalRuleKind;
+  pattern: string[];
+  decision: string | null;
+  rule: string;
+};
+
+export type ApprovalRuleTest = {
+  matched: boolean;
+  commandLine: string;
+};
+
+export type ApprovalRulesExport = {
+  version: number;
+  rules: ApprovalRule[];
+};
+
+export type ApprovalRulesImport = {
+  added: number;
+  skipped: number;
+};
+
 export type RequestUserInputOption = {
   label: string;
   description: string;
@@ -424,6 +546,25 @@ export type GitFileDiff = {
   newImageData?: string | null;
   oldImageMime?: string | null;
   newImageMime?: string | null;
+  oldSize?: number | null;
+  newSize?: number | null;
+  redactions?: SecretRedaction[];
+  hunks?: GitDiffHunk[];
+};
+
+export type GitDiffHunk = {
+  id: string;
+  header: string;
+  oldStart: number;
+  oldLines: number;
+  newStart: number;
+  newLines: number;
+};
+
+export type SecretRedaction = {
+  side: "old" | "new";
+  line: number;
+  pattern: string;
 };
 
 export type GitCommitDiff = {
@@ -447,9 +588,16 @@ export type GitLogEntry = {
   timestamp: number;
 };
 
+export type GitLogFilter = {
+  author?: string | null;
+  since?: string | null;
+  until?: string | null;
+};
+
 export type GitLogResponse = {
   total: number;
   entries: GitLogEntry[];
+  hasMore: boolean;
   ahead: number;
   behind: number;
   aheadEntries: GitLogEntry[];
@@ -457,6 +605,476 @@ export type GitLogResponse = {
   upstream: string | null;
 };
 
+export type GitBlameLine = {
+  lineNumber: number;
+  sha: string;
+  author: string;
+  authorEmail: string;
+  authorTime: number;
+  summary: string;
+  content: string;
+  committed: boolean;
+};
+
+export type GitFileHistoryEntry = GitLogEntry & {
+  path: string;
+  previousPath: string | null;
+  additions: number | null;
+  deletions: number | null;
+};
+
+export type GitRepoContributor = {
+  name: string;
+  commits: number;
+};
+
+export type WorkspaceAuditFinding = {
+  path: string;
+  kind: "secret" | "largeFile" | "sensitiveFile";
+  severity: "high" | "medium" | "low";
+  line: number | null;
+  message: string;
+  staged: boolean;
+};
+
+export type WorkspaceAudit = {
+  findings: WorkspaceAuditFinding[];
+  scannedFiles: number;
+  truncated: boolean;
+  blocking: boolean;
+};
+
+export type GitRepoStats = {
+  totalCommits: number;
+  contributorCount: number;
+  topContributors: GitRepoContributor[];
+  lastCommitAt: number | null;
+  fileCount: number;
+  linesOfCode: number;
+  linesTruncated: boolean;
+  computedAtMs: number;
+  cached: boolean;
+};
+
+export type GitBisectState = {
+  active: boolean;
+  bad: string | null;
+  good: string[];
+  candidate: GitLogEntry | null;
+  revisionsLeft: number | null;
+  stepsLeft: number | null;
+  firstBadCommit: GitLogEntry | null;
+};
+
+export type GitBisectVerdict = "good" | "bad";
+
+export type RemoteUpdatesEvent = {
+  workspaceId: string;
+  updates: GitRemoteUpdates;
+};
+
+export type ProfileLoginEvent = {
+  profileId: string;
+  status: "prompt" | "done" | "failed";
+  url: string | null;
+  code: string | null;
+  error: string | null;
+};
+
+export type MessageQueuedEvent = {
+  workspaceId: string;
+  threadId: string;
+  status: "queued" | "sent" | "failed" | "cleared";
+  queueLength: number;
+  error: string | null;
+};
+
+export type SessionCrashedEvent = {
+  workspaceId: string;
+  status: "crashed" | "respawning" | "respawned" | "gaveUp";
+  kind: "outOfMemory" | "notFound" | "notExecutable" | "signal" | "exit";
+  reason: string;
+  exitCode: number | null;
+  signal: number | null;
+  stderrTail: string[];
+  attempt: number;
+  retryInMs: number | null;
+  error: string | null;
+};
+
+export type CloneProgressEvent = {
+  sourceWorkspaceId: string;
+  status: "progress" | "done" | "failed";
+  percent: number;
+  stage: string | null;
+  error: string | null;
+};
+
+export type GitRefLabel = {
+  name: string;
+  kind: "head" | "branch" | "remote" | "tag";
+};
+
+export type GitGraphNode = GitLogEntry & {
+  parents: string[];
+  refs: GitRefLabel[];
+};
+
+export type GitGraphResponse = {
+  nodes: GitGraphNode[];
+  truncated: boolean;
+};
+
+export type GitRemoteUpdates = {
+  upstream: string | null;
+  remoteSha: string | null;
+  ahead: number;
+  behind: number;
+  hasUpdates: boolean;
+  checkedAt: number;
+  cached: boolean;
+};
+
+export type GitRemote = {
+  name: string;
+  fetchUrl: string | null;
+  pushUrl: string | null;
+};
+
+export type GitSyncOutcome =
+  | "upToDate"
+  | "fastForwarded"
+  | "updated"
+  | "rejected"
+  | "conflicts";
+
+export type GitSyncResult = {
+  outcome: GitSyncOutcome;
+  upstream: string | null;
+  conflicts: string[];
+  message: string | null;
+};
+
+export type GitFetchedRef = {
+  name: string;
+  oldSha: string | null;
+  newSha: string | null;
+};
+
+export type GitStashEntry = {
+  index: number;
+  message: string;
+  branch: string | null;
+  createdAt: number;
+};
+
+export type GitStashApplyResult = {
+  outcome: "applied" | "conflicts";
+  conflicts: string[];
+  dropped: boolean;
+  message: string | null;
+};
+
+export type GitResetMode = "soft" | "mixed" | "hard";
+
+export type GitResetResult = {
+  head: string;
+  restored: string[];
+  unstaged: string[];
+};
+
+export type GitSyncProgressEvent = {
+  workspaceId: string;
+  operation: "push" | "pull";
+  status: "progress" | "done" | "failed";
+  percent: number;
+  stage: string | null;
+  error: string | null;
+};
+
+export type AppLockStatus = {
+  locked: boolean;
+  available: boolean;
+};
+
+export type CredentialStatus = {
+  key: string;
+  exists: boolean;
+};
+
+export type NetworkStatus = {
+  online: boolean;
+  checkedAtMs: number | null;
+  changedAtMs: number | null;
+};
+
+export type OperationTimeEstimate = {
+  kind: string;
+  estimatedMs: number;
+  sampleCount: number;
+  source: "history" | "heuristic";
+};
+
+export type OperationPriority = "interactive" | "background";
+
+export type OperationStatus =
+  | "started"
+  | "progress"
+  | "done"
+  | "failed"
+  | "cancelled";
+
+export type OperationInfo = {
+  id: string;
+  kind: string;
+  workspaceId: string | null;
+  startedAtMs: number;
+  progress: number | null;
+  message: string | null;
+};
+
+export type InventoryWorkspace = {
+  id: string;
+  name: string;
+  kind: WorkspaceKind;
+  parentId: string | null;
+  path: string;
+  connected: boolean;
+  pathExists: boolean;
+};
+
+export type InventorySession = {
+  workspaceId: string;
+  pid: number | null;
+  startedAtMs: number;
+  uptimeMs: number;
+};
+
+export type InventoryTerminal = {
+  workspaceId: string;
+  terminalId: string;
+  pid: number | null;
+  startedAtMs: number;
+  uptimeMs: number;
+};
+
+export type InventoryScheduler = {
+  name: string;
+  startedAtMs: number;
+  lastTickMs: number | null;
+};
+
+export type SystemInventory = {
+  generatedAtMs: number;
+  workspaces: InventoryWorkspace[];
+  sessions: InventorySession[];
+  terminals: InventoryTerminal[];
+  operations: OperationInfo[];
+  schedulers: InventoryScheduler[];
+};
+
+export type RunningTurn = {
+  workspaceId: string;
+  threadId: string;
+  turnId: string;
+  startedAtMs: number;
+};
+
+export type ShutdownReadiness = {
+  runningTurns: RunningTurn[];
+  operations: OperationInfo[];
+  terminals: InventoryTerminal[];
+  bufferedEvents: number;
+  safeToQuit: boolean;
+};
+
+export type OperationEvent = {
+  operationId: string;
+  kind: string;
+  workspaceId: string | null;
+  status: OperationStatus;
+  progress: number | null;
+  message: string | null;
+  error: string | null;
+};
+
+export type SessionStartupSample = {
+  workspaceId: string;
+  workspaceName: string;
+  durationMs: number;
+  recordedAtMs: number;
+};
+
+export type WorkspaceStartupSummary = {
+  workspaceId: string;
+  workspaceName: string;
+  sampleCount: number;
+  averageMs: number;
+  maxMs: number;
+  lastMs: number;
+};
+
+export type SessionStartupStats = {
+  recent: SessionStartupSample[];
+  averageMs: number | null;
+  workspaces: WorkspaceStartupSummary[];
+};
+
+export type SessionAuthStatus =
+  | "valid"
+  | "expiring"
+  | "needsRefresh"
+  | "expired"
+  | "apiKey"
+  | "missing";
+
+export type SessionAccount = {
+  workspaceId: string;
+  profile: CodexProfile | null;
+  codexHome: string | null;
+  connected: boolean;
+  accountType: string | null;
+  email: string | null;
+  planType: string | null;
+  requiresOpenaiAuth: boolean | null;
+  authStatus: SessionAuthStatus;
+  expiresAt: number | null;
+};
+
+export type OrphanedCodexProcess = {
+  pid: number;
+  command: string;
+  cwd: string;
+  workspaceId: string;
+  workspaceName: string;
+};
+
+export type ThreadReplayResult = {
+  threadId: string;
+  sourceThreadId: string;
+  model: string;
+  turnCount: number;
+};
+
+export type ThreadTurnSummary = {
+  userMessage: string;
+  response: string;
+  toolCallCount: number;
+  status: string | null;
+  durationMs: number | null;
+  tokenUsage: Record<string, unknown> | null;
+};
+
+export type ThreadDiffTurn = {
+  index: number;
+  promptsMatch: boolean;
+  a: ThreadTurnSummary | null;
+  b: ThreadTurnSummary | null;
+};
+
+export type ThreadDiff = {
+  threadA: string;
+  threadB: string;
+  turns: ThreadDiffTurn[];
+};
+
+export type ThreadExportFormat = "markdown";
+
+export type ThreadExport = {
+  threadId: string;
+  format: ThreadExportFormat;
+  content: string;
+  path: string | null;
+};
+
+export type ThreadSearchResult = {
+  threadId: string;
+  preview: string | null;
+  snippet: string;
+  matchCount: number;
+  updatedAt: number;
+  archived: boolean;
+};
+
+export type ThreadWindow = {
+  threadId: string;
+  total: number;
+  offset: number;
+  anchorIndex: number;
+  items: Record<string, unknown>[];
+};
+
+export type AutoCompactionEvent = {
+  threadId: string;
+  status: "started" | "completed" | "failed";
+  contextTokens: number | null;
+  contextWindow: number | null;
+  projectedPct: number | null;
+  thresholdPct: number | null;
+  error: string | null;
+};
+
+export type MessageTokenEstimate = {
+  model: string | null;
+  tokenizer: string;
+  messageTokens: number;
+  attachmentTokens: number;
+  contextTokens: number;
+  contextSource: "usage" | "estimate";
+  projectedTokens: number;
+  contextWindow: number | null;
+  projectedPct: number | null;
+  willCompact: boolean;
+  willOverflow: boolean;
+  warning: string | null;
+};
+
+export type ThreadUsage = {
+  threadId: string;
+  inputTokens: number;
+  cachedInputTokens: number;
+  outputTokens: number;
+  totalTokens: number;
+  contextTokens: number | null;
+  contextWindow: number | null;
+  contextPct: number | null;
+};
+
+export type TurnInterruptOutcome = {
+  threadId: string;
+  turnId: string;
+  delivered: boolean;
+  error: string | null;
+};
+
+export type WorkspaceInterruptResult = {
+  workspaceId: string;
+  turns: TurnInterruptOutcome[];
+};
+
+export type TurnTraceCapture = {
+  path: string;
+  eventCount: number;
+  toolCallCount: number;
+  complete: boolean;
+  redactedSecrets: number;
+};
+
+export type TurnTraceReplay = {
+  workspaceId: string;
+  threadId: string;
+  turnId: string;
+  eventCount: number;
+  skippedRequests: number;
+};
+
+export type PinnedMessage = {
+  workspaceId: string;
+  threadId: string;
+  messageId: string;
+  pinnedAtMs: number;
+};
+
 export type GitHubIssue = {
   number: number;
   title: string;
@@ -491,12 +1109,56 @@ export type GitHubPullRequestsResponse = {
   pullRequests: GitHubPullRequest[];
 };
 
+export type GitHubPullRequestInput = {
+  title: string;
+  body?: string | null;
+  base?: string | null;
+  head?: string | null;
+  draft?: boolean;
+};
+
+export type GitHubCreatedPullRequest = {
+  number: number;
+  url: string;
+};
+
 export type GitHubPullRequestDiff = {
   path: string;
   status: string;
   diff: string;
 };
 
+export type GitHubChecksState = "success" | "pending" | "failure";
+
+export type GitHubCheck = {
+  name: string;
+  status: string;
+  conclusion: string | null;
+  detailsUrl: string | null;
+};
+
+export type GitHubChecksResponse = {
+  state: GitHubChecksState;
+  headSha: string;
+  checks: GitHubCheck[];
+};
+
+export type GitHubMergeMethod = "merge" | "squash" | "rebase";
+
+export type GitHubMergeResult = {
+  merged: boolean;
+  sha: string | null;
+  message: string;
+};
+
+export type GitHubReviewEvent = "APPROVE" | "REQUEST_CHANGES" | "COMMENT";
+
+export type GitHubReviewComment = {
+  path: string;
+  line: number;
+  body: string;
+};
+
 export type GitHubPullRequestComment = {
   id: number;
   body: string;
@@ -645,6 +1307,8 @@ export type CustomPromptOption = {
   argumentHint?: string;
   content: string;
   scope?: "workspace" | "global";
+  folder?: string;
+  variables?: string[];
 };
 
 export type BranchInfo = {
@@ -662,7 +1326,12 @@ export type DebugEntry = {
 
 export type TerminalStatus = "idle" | "connecting" | "ready" | "error";
 
-export type DictationModelState = "missing" | "downloading" | "ready" | "error";
+export type DictationModelState =
+  | "missing"
+  | "downloading"
+  | "ready"
+  | "corrupt"
+  | "error";
 
 export type DictationDownloadProgress = {
   totalBytes?: number | null;
@@ -677,16 +1346,40 @@ export type DictationModelStatus = {
   path?: string | null;
 };
 
+export type DictationModelVariant = {
+  id: string;
+  sizeBytes: number;
+  downloaded: boolean;
+  active: boolean;
+};
+
+export type DictationInputDevice = {
+  id: string;
+  name: string;
+  isDefault: boolean;
+};
+
+export type DictationLanguage = {
+  code: string;
+  name: string;
+};
+
 export type DictationSessionState = "idle" | "listening" | "processing";
 
 export type DictationEvent =
   | { type: "state"; state: DictationSessionState }
   | { type: "level"; value: number }
-  | { type: "transcript"; text: string }
+  | { type: "partial"; text: string; targetId: string | null }
+  | { type: "final"; text: string; targetId: string | null }
+  | { type: "audio_saved"; path: string }
   | { type: "error"; message: string }
+  | { type: "warning"; message: string }
+  | { type: "voice_activity"; speaking: boolean }
+  | { type: "auto_stopped"; silenceMs: number }
   | { type: "canceled"; message: string };
 
 export type DictationTranscript = {
   id: string;
   text: string;
+  targetId?: string | null;
 };
@@ -5,7 +5,9 @@ export const SUPPORTED_APP_SERVER_METHODS = [
   "account/login/completed",
   "account/rateLimits/updated",
   "account/updated",
+  "codex/autoCompaction",
   "codex/backgroundThread",
+  "codex/codexBinMissing",
   "codex/connected",
   "codex/event/skills_update_available",
   "error",